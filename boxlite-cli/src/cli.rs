@@ -2,8 +2,8 @@
 //! This module contains all CLI-related code including the main CLI structure,
 //! subcommands, and flag definitions.
 
-use boxlite::{BoxOptions, BoxliteOptions, BoxliteRuntime};
-use clap::{Args, Parser, Subcommand};
+use boxlite::{BoxOptions, BoxliteOptions, BoxliteRuntime, SecurityOptions};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 /// Helper to parse CLI environment variables and apply them to BoxOptions
 pub fn apply_env_vars(env: &[String], opts: &mut BoxOptions) {
@@ -16,16 +16,22 @@ where
     F: Fn(&str) -> Option<String>,
 {
     for env_str in env {
-        if let Some((k, v)) = env_str.split_once('=') {
-            opts.env.push((k.to_string(), v.to_string()));
-        } else if let Some(val) = lookup(env_str) {
-            opts.env.push((env_str.to_string(), val));
-        } else {
-            tracing::warn!(
-                "Environment variable '{}' not found on host, skipping",
-                env_str
-            );
-        }
+        let (key, value) = match env_str.split_once('=') {
+            Some((k, v)) => (k.to_string(), v.to_string()),
+            None => match lookup(env_str) {
+                Some(val) => (env_str.to_string(), val),
+                None => {
+                    tracing::warn!(
+                        "Environment variable '{}' not found on host, skipping",
+                        env_str
+                    );
+                    continue;
+                }
+            },
+        };
+        // Later values (e.g. flags over a config file) replace earlier ones
+        opts.env.retain(|(k, _)| *k != key);
+        opts.env.push((key, value));
     }
 }
 
@@ -48,6 +54,9 @@ pub struct Cli {
 pub enum Commands {
     Run(crate::commands::run::RunArgs),
 
+    /// Run a command in a running box
+    Exec(crate::commands::exec::ExecArgs),
+
     /// Create a new box
     Create(crate::commands::create::CreateArgs),
 
@@ -55,6 +64,9 @@ pub enum Commands {
     #[command(visible_alias = "ls", visible_alias = "ps")]
     List(crate::commands::list::ListArgs),
 
+    /// Display detailed information on one or more boxes
+    Inspect(crate::commands::inspect::InspectArgs),
+
     /// Remove one or more boxes
     Rm(crate::commands::rm::RmArgs),
 
@@ -64,11 +76,26 @@ pub enum Commands {
     /// Stop one or more running boxes
     Stop(crate::commands::stop::StopArgs),
 
+    /// Display a live stream of box resource usage
+    Stats(crate::commands::stats::StatsArgs),
+
     /// Restart one or more boxes
     Restart(crate::commands::restart::RestartArgs),
 
+    /// Show a box's console log
+    Logs(crate::commands::logs::LogsArgs),
+
+    /// Copy files between the host and a box
+    Cp(crate::commands::cp::CpArgs),
+
     /// Pull an image from a registry
     Pull(crate::commands::pull::PullArgs),
+
+    /// Manage cached images
+    Image(crate::commands::image::ImageArgs),
+
+    /// Block until a box stops, then exit with its status code
+    Wait(crate::commands::wait::WaitArgs),
 }
 
 // ============================================================================
@@ -84,6 +111,19 @@ pub struct GlobalFlags {
     /// BoxLite home directory
     #[arg(long, global = true, env = "BOXLITE_HOME")]
     pub home: Option<std::path::PathBuf>,
+
+    /// Output format for list, inspect and stats
+    #[arg(short, long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
+}
+
+/// How query commands render their results.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable table
+    Table,
+    /// Machine-readable JSON
+    Json,
 }
 
 impl GlobalFlags {
@@ -92,6 +132,7 @@ impl GlobalFlags {
             BoxliteOptions {
                 home_dir: home.clone(),
                 image_registries: vec![],
+                ..Default::default()
             }
         } else {
             BoxliteOptions::default()
@@ -135,7 +176,9 @@ impl ProcessFlags {
     where
         F: Fn(&str) -> Option<String>,
     {
-        opts.working_dir = self.workdir.clone();
+        if let Some(workdir) = &self.workdir {
+            opts.working_dir = Some(workdir.clone());
+        }
         apply_env_vars_with_lookup(&self.env, opts, lookup);
         Ok(())
     }
@@ -170,6 +213,25 @@ impl ResourceFlags {
     }
 }
 
+// ============================================================================
+// SECURITY FLAGS
+// ============================================================================
+
+#[derive(Args, Debug, Clone)]
+pub struct SecurityFlags {
+    /// Disable process isolation (bwrap/sandbox-exec, seccomp) for debugging
+    #[arg(long)]
+    pub no_sandbox: bool,
+}
+
+impl SecurityFlags {
+    pub fn apply_to(&self, opts: &mut BoxOptions) {
+        if self.no_sandbox {
+            opts.security = SecurityOptions::disabled();
+        }
+    }
+}
+
 // ============================================================================
 // MANAGEMENT FLAGS
 // ============================================================================
@@ -187,12 +249,24 @@ pub struct ManagementFlags {
     /// Assign a name to the box
     #[arg(long)]
     pub name: Option<String>,
+
+    /// Set metadata on the box (key=value)
+    #[arg(short = 'l', long = "label")]
+    pub labels: Vec<String>,
 }
 
 impl ManagementFlags {
     pub fn apply_to(&self, opts: &mut BoxOptions) {
-        opts.auto_remove = self.rm;
+        // Detached boxes outlive this process and are removed with `boxlite rm`
+        if self.rm && self.detach {
+            eprintln!("Warning: --rm is ignored with --detach; remove the box with `boxlite rm`");
+        }
+        opts.auto_remove = self.rm && !self.detach;
         opts.detach = self.detach;
+        for label in &self.labels {
+            let (key, value) = label.split_once('=').unwrap_or((label.as_str(), ""));
+            opts.labels.insert(key.to_string(), value.to_string());
+        }
     }
 }
 
@@ -260,4 +334,39 @@ mod tests {
 
         assert_eq!(opts.cpus, Some(255));
     }
+
+    #[test]
+    fn test_security_flags_no_sandbox() {
+        let mut opts = BoxOptions::default();
+        SecurityFlags { no_sandbox: false }.apply_to(&mut opts);
+        assert!(opts.security.isolation_enabled);
+
+        SecurityFlags { no_sandbox: true }.apply_to(&mut opts);
+        assert!(!opts.security.isolation_enabled);
+    }
+
+    #[test]
+    fn test_run_rm_sets_auto_remove() {
+        let options = |args: &[&str]| {
+            let cli = Cli::try_parse_from(args).unwrap();
+            let Commands::Run(run) = cli.command else {
+                panic!("expected run command");
+            };
+            let mut opts = BoxOptions::default();
+            run.management.apply_to(&mut opts);
+            opts
+        };
+
+        let opts = options(&["boxlite", "run", "--rm", "alpine"]);
+        assert!(opts.auto_remove);
+        assert!(!opts.detach);
+
+        assert!(!options(&["boxlite", "run", "alpine"]).auto_remove);
+
+        // --rm with --detach keeps the box so it passes BoxOptions::sanitize
+        let opts = options(&["boxlite", "run", "--rm", "-d", "alpine"]);
+        assert!(!opts.auto_remove);
+        assert!(opts.detach);
+        opts.sanitize().unwrap();
+    }
 }
@@ -0,0 +1,266 @@
+//! Attach the host terminal to a running execution.
+//!
+//! Shared by `run` and `exec`: streams stdout/stderr to the host, forwards
+//! host stdin when interactive, puts the host terminal in raw mode for
+//! interactive TTY sessions, and relays signals and window resizes.
+
+use futures::StreamExt;
+use nix::sys::signal::Signal;
+use nix::sys::termios::{
+    InputFlags, LocalFlags, OutputFlags, SetArg, Termios, tcgetattr, tcsetattr,
+};
+use std::io::{self, IsTerminal, Write};
+use tokio::select;
+use tokio::signal::unix::{SignalKind, signal};
+
+/// How the host terminal is attached to an execution.
+#[derive(Debug, Clone, Copy)]
+pub struct AttachMode {
+    /// Forward host stdin to the execution
+    pub interactive: bool,
+    /// The execution runs on a guest PTY
+    pub tty: bool,
+}
+
+impl AttachMode {
+    /// Fail early when a TTY is requested but stdin is not a terminal.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.tty && !io::stdin().is_terminal() {
+            anyhow::bail!("the input device is not a TTY.");
+        }
+        Ok(())
+    }
+}
+
+/// Stream IO and signals for `execution` until it exits and output drains.
+pub async fn attach(
+    mut execution: boxlite::Execution,
+    mode: AttachMode,
+) -> anyhow::Result<boxlite::ExecResult> {
+    let _raw_guard = setup_raw_mode(mode);
+    let (completion_tasks, cancellation_tasks) = setup_io_streaming(&mut execution, mode);
+    wait_for_completion(execution, mode, completion_tasks, cancellation_tasks).await
+}
+
+/// Exit the process with the execution's exit code if it is non-zero.
+pub fn exit_with_status(status: &boxlite::ExecResult) {
+    if status.exit_code != 0 {
+        let code = match status.exit_code {
+            // Signal termination: BoxLite encodes signals as negative values.
+            // Convert to shell convention: 128 + signal_number
+            // e.g. -9 (encoded SIGKILL) -> 128 + 9 = 137
+            code if code < 0 => 128 + code.abs(),
+            code => code,
+        };
+        std::process::exit(code);
+    }
+}
+
+fn setup_io_streaming(
+    execution: &mut boxlite::Execution,
+    mode: AttachMode,
+) -> (
+    Vec<tokio::task::JoinHandle<()>>,
+    Vec<tokio::task::JoinHandle<()>>,
+) {
+    let mut completion_tasks = Vec::new(); // stdout, stderr
+    let mut cancellation_tasks = Vec::new(); // stdin only (signals now handled in main loop)
+
+    // IO Streaming
+    if let Some(mut stdout) = execution.stdout() {
+        completion_tasks.push(tokio::spawn(async move {
+            while let Some(line) = stdout.next().await {
+                print!("{}", line);
+                let _ = io::stdout().flush();
+            }
+        }));
+    }
+
+    if let Some(mut stderr) = execution.stderr() {
+        let is_tty = mode.tty;
+        completion_tasks.push(tokio::spawn(async move {
+            while let Some(line) = stderr.next().await {
+                if is_tty {
+                    // TTY mode: stderr also goes to stdout (merged output)
+                    print!("{}", line);
+                    let _ = io::stdout().flush();
+                } else {
+                    // Non-TTY mode: stderr goes to stderr (separated output)
+                    eprint!("{}", line);
+                    let _ = io::stderr().flush();
+                }
+            }
+        }));
+    }
+
+    if mode.interactive
+        && let Some(stdin_tx) = execution.stdin()
+    {
+        cancellation_tasks.push(tokio::spawn(async move {
+            stream_stdin(stdin_tx).await;
+        }));
+    }
+
+    (completion_tasks, cancellation_tasks)
+}
+
+fn setup_raw_mode(mode: AttachMode) -> Option<RawModeGuard> {
+    if !(mode.tty && mode.interactive) {
+        return None;
+    }
+    match enable_raw_mode() {
+        Ok(guard) => Some(guard),
+        Err(e) => {
+            eprintln!("Warning: Failed to enable raw mode: {}", e);
+            eprintln!("Continuing in cooked mode. Some features may not work correctly.");
+            None
+        }
+    }
+}
+
+async fn wait_for_completion(
+    mut execution: boxlite::Execution,
+    mode: AttachMode,
+    completion_tasks: Vec<tokio::task::JoinHandle<()>>,
+    cancellation_tasks: Vec<tokio::task::JoinHandle<()>>,
+) -> anyhow::Result<boxlite::ExecResult> {
+    // created in main task context for reliable delivery
+    let mut sig_int = signal(SignalKind::interrupt()).unwrap();
+    let mut sig_term = signal(SignalKind::terminate()).unwrap();
+    let mut sig_hup = signal(SignalKind::hangup()).unwrap();
+    let mut sig_winch = if mode.tty {
+        Some(signal(SignalKind::window_change()).unwrap())
+    } else {
+        None
+    };
+
+    if let Some((w, h)) = mode.tty.then(term_size::dimensions).flatten() {
+        let _ = execution.resize_tty(h as u32, w as u32).await;
+    }
+
+    let signal_exec = execution.clone();
+    let exit_fut = execution.wait();
+
+    let io_fut = async {
+        for handle in completion_tasks {
+            let _ = handle.await;
+        }
+    };
+
+    tokio::pin!(exit_fut);
+    tokio::pin!(io_fut);
+
+    let mut io_done = false;
+    let mut exit_status: Option<boxlite::ExecResult> = None;
+
+    // Handles IO, signals, and exit
+    loop {
+        select! {
+            status = &mut exit_fut, if exit_status.is_none() => {
+                exit_status = Some(status?);
+                // Stop stdin forwarding to avoid EPIPE
+                for task in &cancellation_tasks {
+                    task.abort();
+                }
+                if io_done {
+                    return Ok(exit_status.unwrap());
+                }
+            }
+
+            _ = &mut io_fut, if !io_done => {
+                io_done = true;
+                //  exit already happened
+                if let Some(status) = exit_status {
+                    return Ok(status);
+                }
+            }
+
+            _ = sig_int.recv() => {
+                let _ = signal_exec.signal(Signal::SIGINT as i32).await;
+            }
+
+            _ = sig_term.recv() => {
+                let _ = signal_exec.signal(Signal::SIGTERM as i32).await;
+            }
+
+            _ = sig_hup.recv() => {
+                let _ = signal_exec.signal(Signal::SIGHUP as i32).await;
+            }
+
+            // TTY resize
+            Some(_) = async {
+                match sig_winch.as_mut() {
+                    Some(s) => s.recv().await,
+                    None => std::future::pending().await,
+                }
+            } => {
+                if let Some((w, h)) = term_size::dimensions() {
+                    let _ = signal_exec.resize_tty(h as u32, w as u32).await;
+                }
+            }
+        }
+    }
+}
+
+async fn stream_stdin(mut tx: boxlite::ExecStdin) {
+    let mut stdin = tokio::io::stdin();
+    let mut buf = [0u8; 1024];
+    loop {
+        match tokio::io::AsyncReadExt::read(&mut stdin, &mut buf).await {
+            Ok(0) => break, // EOF
+            Ok(n) => {
+                if tx.write(&buf[..n]).await.is_err() {
+                    break;
+                }
+            }
+            Err(e) => {
+                tracing::debug!("stdin read error: {}", e);
+                break;
+            }
+        }
+    }
+}
+
+// Raw Mode
+struct RawModeGuard {
+    original_termios: Termios,
+}
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        let stdin = io::stdin();
+        let _ = tcsetattr(&stdin, SetArg::TCSANOW, &self.original_termios);
+    }
+}
+
+fn enable_raw_mode() -> anyhow::Result<RawModeGuard> {
+    if !io::stdin().is_terminal() {
+        return Err(anyhow::anyhow!("stdin is not a terminal"));
+    }
+
+    let stdin = io::stdin();
+    let original = tcgetattr(&stdin)?;
+    let mut raw = original.clone();
+
+    // Standard Raw Mode flags
+    raw.input_flags &= !(InputFlags::IGNBRK
+        | InputFlags::BRKINT
+        | InputFlags::PARMRK
+        | InputFlags::ISTRIP
+        | InputFlags::INLCR
+        | InputFlags::IGNCR
+        | InputFlags::ICRNL
+        | InputFlags::IXON);
+    raw.output_flags &= !OutputFlags::OPOST;
+    raw.local_flags &= !(LocalFlags::ECHO
+        | LocalFlags::ECHONL
+        | LocalFlags::ICANON
+        | LocalFlags::ISIG
+        | LocalFlags::IEXTEN);
+
+    tcsetattr(&stdin, SetArg::TCSANOW, &raw)?;
+
+    Ok(RawModeGuard {
+        original_termios: original,
+    })
+}
@@ -0,0 +1,139 @@
+use clap::Args;
+use std::path::PathBuf;
+
+/// Copy files between the host and a box
+#[derive(Args, Debug)]
+pub struct CpArgs {
+    /// Source: a host path or `BOX:/path`
+    pub src: String,
+
+    /// Destination: a host path or `BOX:/path`
+    pub dst: String,
+}
+
+/// Resolved direction of a `cp` transfer.
+#[derive(Debug, PartialEq, Eq)]
+enum Transfer {
+    /// Host file into a box
+    ToBox {
+        host_path: PathBuf,
+        target: String,
+        guest_path: String,
+    },
+    /// File out of a box onto the host
+    FromBox {
+        target: String,
+        guest_path: String,
+        host_path: PathBuf,
+    },
+}
+
+pub async fn execute(args: CpArgs, global: &crate::cli::GlobalFlags) -> anyhow::Result<()> {
+    let transfer = parse_transfer(&args.src, &args.dst)?;
+    let runtime = global.create_runtime()?;
+
+    match transfer {
+        Transfer::ToBox {
+            host_path,
+            target,
+            guest_path,
+        } => {
+            let litebox = runtime
+                .get(&target)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("No such box: {}", target))?;
+            litebox.copy_to_guest(&host_path, &guest_path).await?;
+        }
+        Transfer::FromBox {
+            target,
+            guest_path,
+            host_path,
+        } => {
+            let litebox = runtime
+                .get(&target)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("No such box: {}", target))?;
+            litebox.copy_from_guest(&guest_path, &host_path).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Split `BOX:/path` into the box reference and guest path.
+///
+/// Anything with a `/` before the first `:` (e.g. `./a:b`, `/tmp/x:y`) is a
+/// host path, as is a spec with an empty box or path part.
+fn parse_box_path(spec: &str) -> Option<(&str, &str)> {
+    let (target, path) = spec.split_once(':')?;
+    if target.is_empty() || path.is_empty() || target.contains('/') {
+        return None;
+    }
+    Some((target, path))
+}
+
+fn parse_transfer(src: &str, dst: &str) -> anyhow::Result<Transfer> {
+    match (parse_box_path(src), parse_box_path(dst)) {
+        (None, Some((target, guest_path))) => Ok(Transfer::ToBox {
+            host_path: PathBuf::from(src),
+            target: target.to_string(),
+            guest_path: guest_path.to_string(),
+        }),
+        (Some((target, guest_path)), None) => Ok(Transfer::FromBox {
+            target: target.to_string(),
+            guest_path: guest_path.to_string(),
+            host_path: PathBuf::from(dst),
+        }),
+        (Some(_), Some(_)) => anyhow::bail!("copying between two boxes is not supported"),
+        (None, None) => {
+            anyhow::bail!("one of source or destination must be a box path (BOX:/path)")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_box_path() {
+        assert_eq!(
+            parse_box_path("mybox:/tmp/foo"),
+            Some(("mybox", "/tmp/foo"))
+        );
+        assert_eq!(
+            parse_box_path("01HJK4TNRPQSXYZ8WM6NCVT9R5:/etc/hosts"),
+            Some(("01HJK4TNRPQSXYZ8WM6NCVT9R5", "/etc/hosts"))
+        );
+        assert_eq!(parse_box_path("./foo"), None);
+        assert_eq!(parse_box_path("./dir:with:colons"), None);
+        assert_eq!(parse_box_path("/abs/a:b"), None);
+        assert_eq!(parse_box_path(":/tmp/foo"), None);
+        assert_eq!(parse_box_path("mybox:"), None);
+    }
+
+    #[test]
+    fn test_parse_transfer_direction() {
+        assert_eq!(
+            parse_transfer("./foo", "mybox:/tmp/foo").unwrap(),
+            Transfer::ToBox {
+                host_path: PathBuf::from("./foo"),
+                target: "mybox".to_string(),
+                guest_path: "/tmp/foo".to_string(),
+            }
+        );
+        assert_eq!(
+            parse_transfer("mybox:/var/log/app.log", "app.log").unwrap(),
+            Transfer::FromBox {
+                target: "mybox".to_string(),
+                guest_path: "/var/log/app.log".to_string(),
+                host_path: PathBuf::from("app.log"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_transfer_rejects_invalid() {
+        assert!(parse_transfer("./a", "./b").is_err());
+        assert!(parse_transfer("box1:/a", "box2:/b").is_err());
+    }
+}
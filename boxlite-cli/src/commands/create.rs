@@ -1,13 +1,19 @@
-use crate::cli::{GlobalFlags, ResourceFlags};
+use crate::cli::{GlobalFlags, ResourceFlags, SecurityFlags};
+use crate::config::BoxFileConfig;
 use boxlite::{BoxOptions, RootfsSpec};
 use clap::Args;
+use std::path::PathBuf;
 
 /// Create a new box
 #[derive(Args, Debug)]
 pub struct CreateArgs {
     /// Image to create from
-    #[arg(index = 1)]
-    pub image: String,
+    #[arg(index = 1, required_unless_present = "config")]
+    pub image: Option<String>,
+
+    /// Read box options from a TOML file (flags override its values)
+    #[arg(long)]
+    pub config: Option<PathBuf>,
 
     /// Assign a name to the box
     #[arg(long)]
@@ -27,26 +33,34 @@ pub struct CreateArgs {
 
     #[command(flatten)]
     pub resource: ResourceFlags,
+
+    #[command(flatten)]
+    pub security: SecurityFlags,
 }
 
 pub async fn execute(args: CreateArgs, global: &GlobalFlags) -> anyhow::Result<()> {
-    let rt = global.create_runtime()?;
-    let box_options = args.to_box_options();
+    let file = BoxFileConfig::load_optional(args.config.as_deref())?;
+    let box_options = args.to_box_options(&file)?;
+    let name = args.name.or(file.name);
 
-    let litebox = rt.create(box_options, args.name).await?;
+    let rt = global.create_runtime()?;
+    let litebox = rt.create(box_options, name).await?;
     println!("{}", litebox.id());
 
     Ok(())
 }
 
 impl CreateArgs {
-    fn to_box_options(&self) -> BoxOptions {
-        let mut options = BoxOptions::default();
+    fn to_box_options(&self, file: &BoxFileConfig) -> anyhow::Result<BoxOptions> {
+        let mut options = file.to_box_options();
         self.resource.apply_to(&mut options);
+        self.security.apply_to(&mut options);
         options.auto_remove = self.rm;
-        options.working_dir = self.workdir.clone();
+        if let Some(workdir) = &self.workdir {
+            options.working_dir = Some(workdir.clone());
+        }
         crate::cli::apply_env_vars(&self.env, &mut options);
-        options.rootfs = RootfsSpec::Image(self.image.clone());
-        options
+        options.rootfs = RootfsSpec::Image(file.resolve_image(self.image.as_deref())?);
+        Ok(options)
     }
 }
@@ -0,0 +1,63 @@
+use crate::cli::{GlobalFlags, ProcessFlags};
+use crate::commands::attach::{AttachMode, attach, exit_with_status};
+use boxlite::{BoxCommand, BoxOptions};
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct ExecArgs {
+    #[command(flatten)]
+    pub process: ProcessFlags,
+
+    /// Read environment variables from a dotenv-style file (-e wins, later files override earlier ones)
+    #[arg(long = "env-file")]
+    pub env_file: Vec<std::path::PathBuf>,
+
+    /// Name or ID of the box
+    #[arg(index = 1)]
+    pub target: String,
+
+    /// Command to run inside the box
+    #[arg(index = 2, required = true, trailing_var_arg = true)]
+    pub command: Vec<String>,
+}
+
+/// Entry point
+pub async fn execute(args: ExecArgs, global: &GlobalFlags) -> anyhow::Result<()> {
+    let mode = AttachMode {
+        interactive: args.process.interactive,
+        tty: args.process.tty,
+    };
+    mode.validate()?;
+
+    let runtime = global.create_runtime()?;
+    let litebox = runtime
+        .get(&args.target)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("No such box: {}", args.target))?;
+
+    let execution = litebox.exec(build_command(&args)?).await?;
+    let status = attach(execution, mode).await?;
+
+    exit_with_status(&status);
+    Ok(())
+}
+
+fn build_command(args: &ExecArgs) -> anyhow::Result<BoxCommand> {
+    // Resolve -e/-w the same way `run` does, then move them onto the command
+    let mut resolved = BoxOptions::default();
+    args.process.apply_to(&mut resolved)?;
+
+    let mut cmd = BoxCommand::new(&args.command[0])
+        .args(&args.command[1..])
+        .tty(args.process.tty);
+    for (key, value) in resolved.env {
+        cmd = cmd.env(key, value);
+    }
+    for path in &args.env_file {
+        cmd = cmd.env_file(path)?;
+    }
+    if let Some(dir) = resolved.working_dir {
+        cmd = cmd.working_dir(dir);
+    }
+    Ok(cmd)
+}
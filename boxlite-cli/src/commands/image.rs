@@ -0,0 +1,174 @@
+use crate::cli::{GlobalFlags, OutputFormat};
+use crate::commands::stats::format_bytes;
+use boxlite::ImageInfo;
+use clap::{Args, Subcommand};
+use comfy_table::{Attribute, Cell, Table, presets};
+
+/// Manage cached images
+#[derive(Args, Debug)]
+pub struct ImageArgs {
+    #[command(subcommand)]
+    pub command: ImageCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ImageCommand {
+    /// List cached images
+    #[command(visible_alias = "list")]
+    Ls(ImageLsArgs),
+
+    /// Display detailed information on one or more cached images
+    Inspect(ImageInspectArgs),
+
+    /// Remove one or more cached images
+    #[command(visible_alias = "remove")]
+    Rm(ImageRmArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ImageLsArgs {
+    /// Only show image IDs
+    #[arg(short, long)]
+    pub quiet: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ImageInspectArgs {
+    /// Reference of the image(s) to inspect
+    #[arg(required = true, num_args = 1..)]
+    pub images: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ImageRmArgs {
+    /// Remove the image even if boxes were created from it
+    #[arg(short, long)]
+    pub force: bool,
+
+    /// Reference of the image(s) to remove
+    #[arg(required = true, num_args = 1..)]
+    pub images: Vec<String>,
+}
+
+pub async fn execute(args: ImageArgs, global: &GlobalFlags) -> anyhow::Result<()> {
+    match args.command {
+        ImageCommand::Ls(args) => list(args, global).await,
+        ImageCommand::Inspect(args) => inspect(args, global).await,
+        ImageCommand::Rm(args) => remove(args, global).await,
+    }
+}
+
+async fn list(args: ImageLsArgs, global: &GlobalFlags) -> anyhow::Result<()> {
+    let rt = global.create_runtime()?;
+    let images = rt.list_images().await?;
+
+    if args.quiet {
+        for image in images {
+            println!("{}", image.id);
+        }
+        return Ok(());
+    }
+
+    match global.output {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&images)?),
+        OutputFormat::Table => print_images(images),
+    }
+
+    Ok(())
+}
+
+async fn inspect(args: ImageInspectArgs, global: &GlobalFlags) -> anyhow::Result<()> {
+    let rt = global.create_runtime()?;
+
+    let mut images = Vec::new();
+    let mut errors = Vec::new();
+    for reference in &args.images {
+        match rt.inspect_image(reference).await {
+            Ok(details) => images.push(details),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                errors.push(format!("{}: {}", reference, e));
+            }
+        }
+    }
+
+    println!("{}", serde_json::to_string_pretty(&images)?);
+
+    if !errors.is_empty() {
+        anyhow::bail!("Errors:\n  {}", errors.join("\n  "));
+    }
+    Ok(())
+}
+
+async fn remove(args: ImageRmArgs, global: &GlobalFlags) -> anyhow::Result<()> {
+    let rt = global.create_runtime()?;
+
+    let mut failed = false;
+    for reference in &args.images {
+        match rt.remove_image(reference, args.force).await {
+            Ok(()) => println!("{}", reference),
+            Err(e) => {
+                eprintln!("Error removing image '{}': {}", reference, e);
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        anyhow::bail!("Some images could not be removed");
+    }
+    Ok(())
+}
+
+fn print_images(images: Vec<ImageInfo>) {
+    let mut table = Table::new();
+    table
+        .load_preset(presets::UTF8_NO_BORDERS)
+        .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+    table.set_header(vec![
+        Cell::new("REPOSITORY").add_attribute(Attribute::Bold),
+        Cell::new("TAG").add_attribute(Attribute::Bold),
+        Cell::new("PLATFORM").add_attribute(Attribute::Bold),
+        Cell::new("IMAGE ID").add_attribute(Attribute::Bold),
+        Cell::new("PULLED").add_attribute(Attribute::Bold),
+        Cell::new("LAYERS").add_attribute(Attribute::Bold),
+        Cell::new("SIZE").add_attribute(Attribute::Bold),
+    ]);
+
+    for image in images {
+        table.add_row(vec![
+            image.repository.clone(),
+            image.tag.clone(),
+            image.platform.clone().unwrap_or_else(|| "--".to_string()),
+            short_id(&image.id).to_string(),
+            image.cached_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            image.layer_count.to_string(),
+            image
+                .size
+                .map(|size| format_bytes(size.as_bytes()))
+                .unwrap_or_else(|| "--".to_string()),
+        ]);
+    }
+
+    println!("{table}");
+}
+
+/// First 12 hex digits of a `sha256:` digest.
+fn short_id(digest: &str) -> &str {
+    let hex = digest.strip_prefix("sha256:").unwrap_or(digest);
+    &hex[..hex.len().min(12)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_id() {
+        assert_eq!(
+            short_id("sha256:4bcff63911fcb4448bd4fdacec207030997caf25e9bea4045fa6c8c44de311d1"),
+            "4bcff63911fc"
+        );
+        assert_eq!(short_id("sha256:abc"), "abc");
+    }
+}
@@ -0,0 +1,164 @@
+use crate::cli::OutputFormat;
+use boxlite::BoxInfo;
+use boxlite::BoxOptions;
+use boxlite::vmm::VmmKind;
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// Display detailed information on one or more boxes
+#[derive(Args, Debug)]
+pub struct InspectArgs {
+    /// Name or ID of the box(es) to inspect
+    #[arg(required = true, num_args = 1..)]
+    pub targets: Vec<String>,
+
+    /// Format output using a field selector (e.g. `status`, `options.cpus`)
+    /// or a template (e.g. `'{{.name}} {{.status}}'`); ignored with `-o json`
+    #[arg(short, long)]
+    pub format: Option<String>,
+}
+
+/// Everything known about a box: its public info plus creation-time config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoxInspect {
+    #[serde(flatten)]
+    pub info: BoxInfo,
+    pub engine: VmmKind,
+    pub box_home: PathBuf,
+    pub options: BoxOptions,
+}
+
+pub async fn execute(args: InspectArgs, global: &crate::cli::GlobalFlags) -> anyhow::Result<()> {
+    let runtime = global.create_runtime()?;
+
+    let mut boxes = Vec::new();
+    let mut errors = Vec::new();
+    for target in &args.targets {
+        let info = runtime.get_info(target).await?;
+        let litebox = runtime.get(target).await?;
+        let (Some(info), Some(litebox)) = (info, litebox) else {
+            eprintln!("Error: No such box: {}", target);
+            errors.push(format!("{}: not found", target));
+            continue;
+        };
+
+        let config = litebox.config();
+        boxes.push(BoxInspect {
+            info,
+            engine: config.engine_kind,
+            box_home: config.box_home.clone(),
+            options: config.options.clone(),
+        });
+    }
+
+    match args
+        .format
+        .as_ref()
+        .filter(|_| global.output != OutputFormat::Json)
+    {
+        Some(format) => {
+            for inspect in &boxes {
+                println!("{}", render_format(format, &serde_json::to_value(inspect)?));
+            }
+        }
+        None => println!("{}", serde_json::to_string_pretty(&boxes)?),
+    }
+
+    if !errors.is_empty() {
+        anyhow::bail!("Errors:\n  {}", errors.join("\n  "));
+    }
+    Ok(())
+}
+
+/// Render `format` against a box's JSON.
+///
+/// Each `{{.path}}` placeholder is replaced by the selected value; a format
+/// without placeholders is treated as a single field path.
+fn render_format(format: &str, value: &Value) -> String {
+    if !format.contains("{{") {
+        return select_field(value, format);
+    }
+
+    let mut out = String::new();
+    let mut rest = format;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        out.push_str(&select_field(value, &rest[start + 2..start + len]));
+        rest = &rest[start + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Look up a dotted path (keys matched case-insensitively); missing fields render as `<no value>`.
+fn select_field(value: &Value, path: &str) -> String {
+    let path = path.trim().trim_start_matches('.');
+    let mut current = value;
+    if !path.is_empty() {
+        for key in path.split('.') {
+            let next = match current {
+                Value::Object(map) => map
+                    .iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                    .map(|(_, v)| v),
+                Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+                _ => None,
+            };
+            match next {
+                Some(v) => current = v,
+                None => return "<no value>".to_string(),
+            }
+        }
+    }
+
+    match current {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Value {
+        serde_json::json!({
+            "id": "01HJK4TNRPQSXYZ8WM6NCVT9R5",
+            "name": "web",
+            "status": "running",
+            "cpus": 2,
+            "labels": {"env": "prod"},
+            "options": {"volumes": [{"guest_path": "/data"}]},
+        })
+    }
+
+    #[test]
+    fn test_render_field_selector() {
+        let value = sample();
+        assert_eq!(render_format("status", &value), "running");
+        assert_eq!(render_format(".Status", &value), "running");
+        assert_eq!(render_format("cpus", &value), "2");
+        assert_eq!(render_format("labels.env", &value), "prod");
+        assert_eq!(
+            render_format("options.volumes.0.guest_path", &value),
+            "/data"
+        );
+        assert_eq!(render_format("missing", &value), "<no value>");
+    }
+
+    #[test]
+    fn test_render_template() {
+        let value = sample();
+        assert_eq!(
+            render_format("{{.Name}}: {{ .status }} ({{.cpus}} cpus)", &value),
+            "web: running (2 cpus)"
+        );
+        assert_eq!(render_format("{{.labels}}", &value), r#"{"env":"prod"}"#);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::cli::GlobalFlags;
+use crate::cli::{GlobalFlags, OutputFormat};
 use boxlite::BoxInfo;
 use clap::Args;
 use comfy_table::{Attribute, Cell, Table, presets};
@@ -13,28 +13,52 @@ pub struct ListArgs {
     /// Only show IDs
     #[arg(short, long)]
     pub quiet: bool,
+
+    /// Filter output (label=key=value)
+    #[arg(short, long)]
+    pub filter: Vec<String>,
 }
 
 pub async fn execute(args: ListArgs, global: &GlobalFlags) -> anyhow::Result<()> {
+    let selector = parse_label_filters(&args.filter)?;
     let rt = global.create_runtime()?;
-    let boxes = rt.list_info().await?;
+    let boxes: Vec<BoxInfo> = rt
+        .list_info_filtered(&selector)
+        .await?
+        .into_iter()
+        .filter(|info| args.all || info.status.is_active())
+        .collect();
 
     if args.quiet {
         for info in boxes {
-            if !args.all && !info.status.is_active() {
-                continue;
-            }
             println!("{}", info.id);
         }
         return Ok(());
     }
 
-    print_info(boxes, args.all);
+    match global.output {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&boxes)?),
+        OutputFormat::Table => print_info(boxes),
+    }
 
     Ok(())
 }
 
-fn print_info(boxes: Vec<BoxInfo>, all: bool) {
+/// Parse `label=key=value` filters into a label selector.
+fn parse_label_filters(filters: &[String]) -> anyhow::Result<Vec<(String, String)>> {
+    filters
+        .iter()
+        .map(|filter| {
+            let Some(label) = filter.strip_prefix("label=") else {
+                anyhow::bail!("unsupported filter '{}' (expected label=key=value)", filter);
+            };
+            let (key, value) = label.split_once('=').unwrap_or((label, ""));
+            Ok((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+fn print_info(boxes: Vec<BoxInfo>) {
     let mut table = Table::new();
     table
         .load_preset(presets::UTF8_NO_BORDERS)
@@ -44,24 +68,49 @@ fn print_info(boxes: Vec<BoxInfo>, all: bool) {
         Cell::new("IMAGE").add_attribute(Attribute::Bold),
         Cell::new("STATUS").add_attribute(Attribute::Bold),
         Cell::new("CREATED").add_attribute(Attribute::Bold),
+        Cell::new("PORTS").add_attribute(Attribute::Bold),
         Cell::new("NAMES").add_attribute(Attribute::Bold),
     ]);
 
     for info in boxes {
-        if !all && !info.status.is_active() {
-            continue;
-        }
-
         let created = info.created_at.format("%Y-%m-%d %H:%M:%S").to_string();
+        let ports = info
+            .port_forwards
+            .iter()
+            .map(|f| f.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
 
         table.add_row(vec![
             info.id.to_string(),
             info.image.clone(),
             format!("{:?}", info.status),
             created,
+            ports,
             info.name.clone().unwrap_or_else(|| "".to_string()),
         ]);
     }
 
     println!("{table}");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_label_filters() {
+        let selector =
+            parse_label_filters(&["label=team=infra".to_string(), "label=tier=a=b".to_string()])
+                .unwrap();
+        assert_eq!(
+            selector,
+            vec![
+                ("team".to_string(), "infra".to_string()),
+                ("tier".to_string(), "a=b".to_string()),
+            ]
+        );
+
+        assert!(parse_label_filters(&["status=running".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,43 @@
+use clap::Args;
+use futures::StreamExt;
+
+#[derive(Args, Debug)]
+pub struct LogsArgs {
+    /// Name or ID of the box
+    pub target: String,
+
+    /// Follow log output
+    #[arg(short, long)]
+    pub follow: bool,
+
+    /// Number of lines to show from the end of the log
+    #[arg(short = 'n', long)]
+    pub tail: Option<usize>,
+}
+
+pub async fn execute(args: LogsArgs, global: &crate::cli::GlobalFlags) -> anyhow::Result<()> {
+    let runtime = global.create_runtime()?;
+
+    let litebox = runtime
+        .get(&args.target)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("No such box: {}", args.target))?;
+
+    // Subscribe before reading: a line written in between may print twice but is never lost
+    let mut follow = args.follow.then(|| litebox.follow_logs().boxed());
+
+    print!("{}", litebox.read_logs(args.tail).await?);
+
+    if let Some(stream) = follow.as_mut() {
+        loop {
+            tokio::select! {
+                line = stream.next() => match line {
+                    Some(line) => println!("{}", line?),
+                    None => break,
+                },
+                _ = tokio::signal::ctrl_c() => break,
+            }
+        }
+    }
+    Ok(())
+}
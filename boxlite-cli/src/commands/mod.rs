@@ -1,8 +1,16 @@
+pub mod attach;
+pub mod cp;
 pub mod create;
+pub mod exec;
+pub mod image;
+pub mod inspect;
 pub mod list;
+pub mod logs;
 pub mod pull;
 pub mod restart;
 pub mod rm;
 pub mod run;
 pub mod start;
+pub mod stats;
 pub mod stop;
+pub mod wait;
@@ -1,8 +1,15 @@
+use std::collections::HashMap;
+use std::io::{self, IsTerminal, Write};
+use std::sync::Mutex;
+
 use anyhow::Result;
+use boxlite::{PullOptions, PullProgress};
 use clap::Args;
 
 use crate::cli::GlobalFlags;
 
+const BAR_WIDTH: usize = 30;
+
 #[derive(Args, Debug)]
 pub struct PullArgs {
     /// Image to pull
@@ -11,12 +18,29 @@ pub struct PullArgs {
     /// Quiet mode - only show digest
     #[arg(short, long)]
     pub quiet: bool,
+
+    /// Platform to pull from a multi-arch image (e.g. linux/arm64)
+    #[arg(long)]
+    pub platform: Option<String>,
 }
 
 pub async fn execute(args: PullArgs, global: &GlobalFlags) -> Result<()> {
     let runtime = global.create_runtime()?;
 
-    let image = runtime.pull_image(&args.image).await?;
+    let bar = ProgressBar::default();
+    let update = |p: PullProgress| bar.update(p);
+    let options = PullOptions {
+        platform: args.platform.clone(),
+        progress: if args.quiet || !io::stderr().is_terminal() {
+            None
+        } else {
+            Some(&update)
+        },
+    };
+    let result = runtime.pull_image_with(&args.image, &options).await;
+    bar.finish();
+    let image = result?;
+
     if args.quiet {
         println!("{}", image.config_digest());
     } else {
@@ -27,3 +51,48 @@ pub async fn execute(args: PullArgs, global: &GlobalFlags) -> Result<()> {
 
     Ok(())
 }
+
+/// Single-line download bar on stderr, summed over all layers.
+#[derive(Default)]
+struct ProgressBar {
+    /// (bytes_done, bytes_total) per layer index
+    layers: Mutex<HashMap<usize, (u64, u64)>>,
+}
+
+impl ProgressBar {
+    fn update(&self, progress: PullProgress) {
+        let mut layers = self.layers.lock().unwrap();
+        layers.insert(
+            progress.layer_index,
+            (progress.bytes_done, progress.bytes_total),
+        );
+
+        let done: u64 = layers.values().map(|(done, _)| *done).sum();
+        let total: u64 = layers.values().map(|(_, total)| *total).sum();
+        let complete = layers.values().filter(|(d, t)| d >= t).count();
+        let filled = if total == 0 {
+            0
+        } else {
+            ((done.min(total) as f64 / total as f64) * BAR_WIDTH as f64) as usize
+        };
+
+        let mut stderr = io::stderr().lock();
+        let _ = write!(
+            stderr,
+            "\r[{}{}] {:.1}/{:.1} MiB  layers {}/{}",
+            "#".repeat(filled),
+            " ".repeat(BAR_WIDTH - filled),
+            done as f64 / (1024.0 * 1024.0),
+            total as f64 / (1024.0 * 1024.0),
+            complete,
+            progress.layer_count
+        );
+        let _ = stderr.flush();
+    }
+
+    fn finish(&self) {
+        if !self.layers.lock().unwrap().is_empty() {
+            eprintln!();
+        }
+    }
+}
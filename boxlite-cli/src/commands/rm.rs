@@ -30,20 +30,25 @@ pub async fn execute(args: RmArgs, global: &crate::cli::GlobalFlags) -> anyhow::
         }
     }
 
-    let targets = if args.all {
+    let results = if args.all {
         runtime
-            .list_info()
+            .remove_all(args.force)
             .await?
             .into_iter()
-            .map(|info| info.id.to_string())
+            .map(|(box_id, result)| (box_id.to_string(), result))
             .collect()
     } else {
-        args.targets
+        let mut results = Vec::new();
+        for target in args.targets {
+            let result = runtime.remove(&target, args.force).await;
+            results.push((target, result));
+        }
+        results
     };
 
     let mut active_error = false;
-    for target in targets {
-        if let Err(e) = runtime.remove(&target, args.force).await {
+    for (target, result) in results {
+        if let Err(e) = result {
             eprintln!("Error removing box '{}': {}", target, e);
             active_error = true;
         } else {
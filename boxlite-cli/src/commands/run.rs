@@ -1,15 +1,9 @@
-use crate::cli::{GlobalFlags, ManagementFlags, ProcessFlags, ResourceFlags};
+use crate::cli::{GlobalFlags, ManagementFlags, ProcessFlags, ResourceFlags, SecurityFlags};
+use crate::commands::attach::{AttachMode, attach, exit_with_status};
+use crate::config::BoxFileConfig;
 use boxlite::BoxCommand;
-use boxlite::{BoxOptions, BoxliteRuntime, LiteBox, RootfsSpec};
+use boxlite::{BoxOptions, BoxliteRuntime, RootfsSpec};
 use clap::Args;
-use futures::StreamExt;
-use nix::sys::signal::Signal;
-use nix::sys::termios::{
-    InputFlags, LocalFlags, OutputFlags, SetArg, Termios, tcgetattr, tcsetattr,
-};
-use std::io::{self, IsTerminal, Write};
-use tokio::select;
-use tokio::signal::unix::{SignalKind, signal};
 
 #[derive(Args, Debug)]
 pub struct RunArgs {
@@ -22,8 +16,17 @@ pub struct RunArgs {
     #[command(flatten)]
     pub management: ManagementFlags,
 
-    #[arg(index = 1)]
-    pub image: String,
+    #[command(flatten)]
+    pub security: SecurityFlags,
+
+    /// Read box options from a TOML file (flags override its values). When
+    /// the file sets `image`, every positional argument is the command.
+    #[arg(long)]
+    pub config: Option<std::path::PathBuf>,
+
+    /// Image to run, unless the config file sets one
+    #[arg(index = 1, required_unless_present = "config")]
+    pub image: Option<String>,
 
     /// Command to run inside the image
     #[arg(index = 2, trailing_var_arg = true)]
@@ -50,287 +53,79 @@ impl BoxRunner {
 
     async fn run(&mut self) -> anyhow::Result<()> {
         // Validate flags and environment
-        self.validate_flags()?;
-
-        let litebox = self.create_box().await?;
+        self.attach_mode().validate()?;
 
-        // Start execution
-        let cmd = self.prepare_command();
-        let mut execution = litebox.exec(cmd).await?;
+        let (options, name, command) = box_options(&self.args)?;
+        let litebox = self.rt.create(options, name).await?;
 
-        // Detach mode: Print ID and exit
+        // Detach mode: the command runs as the box's main process; print ID
+        // and exit without waiting for it
         if self.args.management.detach {
+            litebox.start().await?;
             println!("{}", litebox.id());
             return Ok(());
         }
 
-        let _raw_guard = self.setup_raw_mode()?;
-
-        // IO streaming and signal handling
-        let (completion_tasks, cancellation_tasks) = self.setup_io_streaming(&mut execution);
+        // Start execution
+        let cmd = self.prepare_command(&command);
+        let execution = litebox.exec(cmd).await?;
 
-        // Wait for box exit and handle IO completion
-        let status = self
-            .wait_for_completion(execution, completion_tasks, cancellation_tasks)
-            .await?;
+        // IO streaming, raw mode and signal handling until the box exits
+        let status = attach(execution, self.attach_mode()).await?;
 
         // Exit with box's exit code
-        if status.exit_code != 0 {
-            let code = match status.exit_code {
-                // Signal termination: BoxLite encodes signals as negative values.
-                // Convert to shell convention: 128 + signal_number
-                // e.g. -9 (encoded SIGKILL) -> 128 + 9 = 137
-                code if code < 0 => 128 + code.abs(),
-                code => code,
-            };
-            std::process::exit(code);
-        }
+        exit_with_status(&status);
 
         Ok(())
     }
 
-    async fn create_box(&self) -> anyhow::Result<LiteBox> {
-        let mut options = BoxOptions::default();
-        self.args.resource.apply_to(&mut options);
-        self.args.management.apply_to(&mut options);
-        self.args.process.apply_to(&mut options)?;
-
-        options.rootfs = RootfsSpec::Image(self.args.image.clone());
-
-        let litebox = self
-            .rt
-            .create(options, self.args.management.name.clone())
-            .await?;
-
-        Ok(litebox)
-    }
-
-    fn prepare_command(&self) -> BoxCommand {
-        let (program, args) = parse_command_args(&self.args.command);
+    fn prepare_command(&self, command: &[String]) -> BoxCommand {
+        let (program, args) = parse_command_args(command);
 
         BoxCommand::new(program)
             .args(args)
             .tty(self.args.process.tty)
     }
 
-    fn setup_io_streaming(
-        &self,
-        execution: &mut boxlite::Execution,
-    ) -> (
-        Vec<tokio::task::JoinHandle<()>>,
-        Vec<tokio::task::JoinHandle<()>>,
-    ) {
-        let mut completion_tasks = Vec::new(); // stdout, stderr
-        let mut cancellation_tasks = Vec::new(); // stdin only (signals now handled in main loop)
-
-        // IO Streaming
-        if let Some(mut stdout) = execution.stdout() {
-            completion_tasks.push(tokio::spawn(async move {
-                while let Some(line) = stdout.next().await {
-                    print!("{}", line);
-                    let _ = io::stdout().flush();
-                }
-            }));
-        }
-
-        if let Some(mut stderr) = execution.stderr() {
-            let is_tty = self.args.process.tty;
-            completion_tasks.push(tokio::spawn(async move {
-                while let Some(line) = stderr.next().await {
-                    if is_tty {
-                        // TTY mode: stderr also goes to stdout (merged output)
-                        print!("{}", line);
-                        let _ = io::stdout().flush();
-                    } else {
-                        // Non-TTY mode: stderr goes to stderr (separated output)
-                        eprint!("{}", line);
-                        let _ = io::stderr().flush();
-                    }
-                }
-            }));
-        }
-
-        if self.args.process.interactive
-            && let Some(stdin_tx) = execution.stdin()
-        {
-            cancellation_tasks.push(tokio::spawn(async move {
-                stream_stdin(stdin_tx).await;
-            }));
-        }
-
-        (completion_tasks, cancellation_tasks)
-    }
-
-    fn validate_flags(&self) -> anyhow::Result<()> {
-        // Check TTY availability if requested
-        if self.args.process.tty && !io::stdin().is_terminal() {
-            anyhow::bail!("the input device is not a TTY.");
-        }
-
-        Ok(())
-    }
-
-    fn setup_raw_mode(&self) -> anyhow::Result<Option<RawModeGuard>> {
-        if self.args.process.tty && self.args.process.interactive {
-            match enable_raw_mode() {
-                Ok(guard) => Ok(Some(guard)),
-                Err(e) => {
-                    eprintln!("Warning: Failed to enable raw mode: {}", e);
-                    eprintln!("Continuing in cooked mode. Some features may not work correctly.");
-                    Ok(None)
-                }
-            }
-        } else {
-            Ok(None)
+    fn attach_mode(&self) -> AttachMode {
+        AttachMode {
+            interactive: self.args.process.interactive,
+            tty: self.args.process.tty,
         }
     }
-
-    async fn wait_for_completion(
-        &self,
-        mut execution: boxlite::Execution,
-        completion_tasks: Vec<tokio::task::JoinHandle<()>>,
-        cancellation_tasks: Vec<tokio::task::JoinHandle<()>>,
-    ) -> anyhow::Result<boxlite::ExecResult> {
-        // created in main task context for reliable delivery
-        let mut sig_int = signal(SignalKind::interrupt()).unwrap();
-        let mut sig_term = signal(SignalKind::terminate()).unwrap();
-        let mut sig_hup = signal(SignalKind::hangup()).unwrap();
-        let mut sig_winch = if self.args.process.tty {
-            Some(signal(SignalKind::window_change()).unwrap())
-        } else {
-            None
-        };
-
-        if let Some((w, h)) = self.args.process.tty.then(term_size::dimensions).flatten() {
-            let _ = execution.resize_tty(h as u32, w as u32).await;
-        }
-
-        let signal_exec = execution.clone();
-        let exit_fut = execution.wait();
-
-        let io_fut = async {
-            for handle in completion_tasks {
-                let _ = handle.await;
-            }
-        };
-
-        tokio::pin!(exit_fut);
-        tokio::pin!(io_fut);
-
-        let mut io_done = false;
-        let mut exit_status: Option<boxlite::ExecResult> = None;
-
-        // Handles IO, signals, and exit
-        loop {
-            select! {
-                status = &mut exit_fut, if exit_status.is_none() => {
-                    exit_status = Some(status?);
-                    // Stop stdin forwarding to avoid EPIPE
-                    for task in &cancellation_tasks {
-                        task.abort();
-                    }
-                    if io_done {
-                        return Ok(exit_status.unwrap());
-                    }
-                }
-
-                _ = &mut io_fut, if !io_done => {
-                    io_done = true;
-                    //  exit already happened
-                    if let Some(status) = exit_status {
-                        return Ok(status);
-                    }
-                }
-
-                _ = sig_int.recv() => {
-                    let _ = signal_exec.signal(Signal::SIGINT as i32).await;
-                }
-
-                _ = sig_term.recv() => {
-                    let _ = signal_exec.signal(Signal::SIGTERM as i32).await;
-                }
-
-                _ = sig_hup.recv() => {
-                    let _ = signal_exec.signal(Signal::SIGHUP as i32).await;
-                }
-
-                // TTY resize
-                Some(_) = async {
-                    match sig_winch.as_mut() {
-                        Some(s) => s.recv().await,
-                        None => std::future::pending().await,
-                    }
-                } => {
-                    if let Some((w, h)) = term_size::dimensions() {
-                        let _ = signal_exec.resize_tty(h as u32, w as u32).await;
-                    }
-                }
-            }
-        }
-    }
-}
-
-async fn stream_stdin(mut tx: boxlite::ExecStdin) {
-    let mut stdin = tokio::io::stdin();
-    let mut buf = [0u8; 1024];
-    loop {
-        match tokio::io::AsyncReadExt::read(&mut stdin, &mut buf).await {
-            Ok(0) => break, // EOF
-            Ok(n) => {
-                if tx.write(&buf[..n]).await.is_err() {
-                    break;
-                }
-            }
-            Err(e) => {
-                tracing::debug!("stdin read error: {}", e);
-                break;
-            }
-        }
-    }
-}
-
-// Raw Mode
-struct RawModeGuard {
-    original_termios: Termios,
-}
-
-impl Drop for RawModeGuard {
-    fn drop(&mut self) {
-        let stdin = io::stdin();
-        let _ = tcsetattr(&stdin, SetArg::TCSANOW, &self.original_termios);
-    }
 }
 
-fn enable_raw_mode() -> anyhow::Result<RawModeGuard> {
-    if !io::stdin().is_terminal() {
-        return Err(anyhow::anyhow!("stdin is not a terminal"));
+/// Box options, name and command for `args`, with flags applied over the
+/// config file.
+fn box_options(args: &RunArgs) -> anyhow::Result<(BoxOptions, Option<String>, Vec<String>)> {
+    let file = BoxFileConfig::load_optional(args.config.as_deref())?;
+    let mut options = file.to_box_options();
+    args.resource.apply_to(&mut options);
+    args.management.apply_to(&mut options);
+    args.security.apply_to(&mut options);
+    args.process.apply_to(&mut options)?;
+
+    // With the image in the file, `run --config box.toml python app.py`
+    // runs `python app.py` rather than an image named `python`
+    let (image, command) = match &file.image {
+        Some(image) => (
+            image.clone(),
+            args.image.iter().chain(&args.command).cloned().collect(),
+        ),
+        None => (
+            file.resolve_image(args.image.as_deref())?,
+            args.command.clone(),
+        ),
+    };
+    options.rootfs = RootfsSpec::Image(image);
+    // A detached box outlives this process, so its command can't be exec'd
+    // and streamed; it replaces the image's entrypoint instead
+    if options.detach && !command.is_empty() {
+        options.command = Some(command.clone());
     }
+    let name = args.management.name.clone().or(file.name);
 
-    let stdin = io::stdin();
-    let original = tcgetattr(&stdin)?;
-    let mut raw = original.clone();
-
-    // Standard Raw Mode flags
-    raw.input_flags &= !(InputFlags::IGNBRK
-        | InputFlags::BRKINT
-        | InputFlags::PARMRK
-        | InputFlags::ISTRIP
-        | InputFlags::INLCR
-        | InputFlags::IGNCR
-        | InputFlags::ICRNL
-        | InputFlags::IXON);
-    raw.output_flags &= !OutputFlags::OPOST;
-    raw.local_flags &= !(LocalFlags::ECHO
-        | LocalFlags::ECHONL
-        | LocalFlags::ICANON
-        | LocalFlags::ISIG
-        | LocalFlags::IEXTEN);
-
-    tcsetattr(&stdin, SetArg::TCSANOW, &raw)?;
-
-    Ok(RawModeGuard {
-        original_termios: original,
-    })
+    Ok((options, name, command))
 }
 
 fn parse_command_args(input: &[String]) -> (&str, &[String]) {
@@ -344,6 +139,7 @@ fn parse_command_args(input: &[String]) -> (&str, &[String]) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use clap::Parser;
 
     #[test]
     fn test_parse_command_args_defaults() {
@@ -351,6 +147,50 @@ mod tests {
         assert_eq!(parse_command_args(&empty), ("sh", &[] as &[String]));
     }
 
+    #[test]
+    fn test_detach_runs_command_as_main_process() {
+        let cli = crate::cli::Cli::try_parse_from([
+            "boxlite", "run", "-d", "--name", "bg", "alpine", "sleep", "300",
+        ])
+        .unwrap();
+        let crate::cli::Commands::Run(args) = cli.command else {
+            panic!("expected run command");
+        };
+
+        let (options, name, _) = box_options(&args).unwrap();
+        assert!(options.detach);
+        assert!(!options.auto_remove);
+        assert_eq!(
+            options.command,
+            Some(vec!["sleep".to_string(), "300".to_string()])
+        );
+        assert_eq!(name.as_deref(), Some("bg"));
+    }
+
+    #[test]
+    fn test_config_image_leaves_positionals_to_the_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("box.toml");
+        std::fs::write(&config, "image = \"python:alpine\"\n").unwrap();
+
+        let cli = crate::cli::Cli::try_parse_from([
+            "boxlite",
+            "run",
+            "--config",
+            config.to_str().unwrap(),
+            "python",
+            "app.py",
+        ])
+        .unwrap();
+        let crate::cli::Commands::Run(args) = cli.command else {
+            panic!("expected run command");
+        };
+
+        let (options, _, command) = box_options(&args).unwrap();
+        assert!(matches!(&options.rootfs, RootfsSpec::Image(image) if image == "python:alpine"));
+        assert_eq!(command, vec!["python".to_string(), "app.py".to_string()]);
+    }
+
     #[test]
     fn test_parse_command_args_explicit() {
         let input = vec!["echo".to_string(), "hello".to_string()];
@@ -0,0 +1,211 @@
+use crate::cli::{GlobalFlags, OutputFormat};
+use boxlite::{BoxMetrics, BoxStatus, BoxliteRuntime};
+use clap::Args;
+use comfy_table::{Attribute, Cell, Table, presets};
+use serde::Serialize;
+use std::time::Duration;
+
+/// How often the live view refreshes.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Display a live stream of box resource usage
+#[derive(Args, Debug)]
+pub struct StatsArgs {
+    /// Name or ID of the box(es) to show (default: all running boxes)
+    pub targets: Vec<String>,
+
+    /// Print a single snapshot instead of refreshing
+    #[arg(long)]
+    pub no_stream: bool,
+}
+
+/// One table row: a box and its metrics snapshot.
+#[derive(Serialize)]
+struct StatsRow {
+    id: String,
+    name: String,
+    metrics: BoxMetrics,
+}
+
+pub async fn execute(args: StatsArgs, global: &GlobalFlags) -> anyhow::Result<()> {
+    let runtime = global.create_runtime()?;
+
+    let json = global.output == OutputFormat::Json;
+
+    if args.no_stream {
+        let rows = collect_rows(&runtime, &args.targets).await?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&rows)?);
+        } else {
+            println!("{}", render_table(&rows));
+        }
+        return Ok(());
+    }
+
+    loop {
+        let rows = collect_rows(&runtime, &args.targets).await?;
+        if json {
+            // One JSON array per refresh, newline-delimited
+            println!("{}", serde_json::to_string(&rows)?);
+        } else {
+            // Clear the screen and redraw from the top-left corner
+            print!("\x1b[2J\x1b[H");
+            println!("{}", render_table(&rows));
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(REFRESH_INTERVAL) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+/// Sample metrics for every running box among `targets` (or all boxes).
+///
+/// Boxes that are not running, or stop while being sampled, are left out.
+async fn collect_rows(
+    runtime: &BoxliteRuntime,
+    targets: &[String],
+) -> anyhow::Result<Vec<StatsRow>> {
+    let targets = if targets.is_empty() {
+        runtime
+            .list_by_status(&[BoxStatus::Running])
+            .await?
+            .into_iter()
+            .map(|info| info.id.to_string())
+            .collect()
+    } else {
+        targets.to_vec()
+    };
+
+    let mut rows = Vec::new();
+    for target in targets {
+        let Some(litebox) = runtime.get(&target).await? else {
+            continue;
+        };
+        let info = litebox.info();
+        // metrics() would start a stopped box; only sample running ones
+        if info.status != BoxStatus::Running {
+            continue;
+        }
+        match litebox.metrics().await {
+            Ok(metrics) => rows.push(StatsRow {
+                id: info.id.to_string(),
+                name: info.name.unwrap_or_default(),
+                metrics,
+            }),
+            Err(e) => tracing::debug!(box_id = %info.id, "Skipping box in stats: {}", e),
+        }
+    }
+    Ok(rows)
+}
+
+fn render_table(rows: &[StatsRow]) -> Table {
+    let mut table = Table::new();
+    table
+        .load_preset(presets::UTF8_NO_BORDERS)
+        .set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+    table.set_header(vec![
+        Cell::new("ID").add_attribute(Attribute::Bold),
+        Cell::new("NAME").add_attribute(Attribute::Bold),
+        Cell::new("CPU %").add_attribute(Attribute::Bold),
+        Cell::new("MEM USAGE").add_attribute(Attribute::Bold),
+        Cell::new("DISK READ / WRITE").add_attribute(Attribute::Bold),
+        Cell::new("COMMANDS").add_attribute(Attribute::Bold),
+    ]);
+
+    for row in rows {
+        table.add_row(stats_columns(row));
+    }
+    table
+}
+
+fn stats_columns(row: &StatsRow) -> Vec<String> {
+    let metrics = &row.metrics;
+    vec![
+        row.id.clone(),
+        row.name.clone(),
+        metrics
+            .cpu_percent()
+            .map(|cpu| format!("{:.2}%", cpu))
+            .unwrap_or_else(|| "--".to_string()),
+        format_optional_bytes(metrics.memory_bytes()),
+        format!(
+            "{} / {}",
+            format_optional_bytes(metrics.disk_read_bytes()),
+            format_optional_bytes(metrics.disk_write_bytes())
+        ),
+        format!(
+            "{} ({} failed)",
+            metrics.commands_executed_total(),
+            metrics.exec_errors_total()
+        ),
+    ]
+}
+
+fn format_optional_bytes(bytes: Option<u64>) -> String {
+    bytes.map(format_bytes).unwrap_or_else(|| "--".to_string())
+}
+
+/// Human-readable binary size, e.g. `512B`, `1.50MiB`.
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}B", bytes)
+    } else {
+        format!("{:.2}{}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(1536), "1.50KiB");
+        assert_eq!(format_bytes(256 * 1024 * 1024), "256.00MiB");
+    }
+
+    #[test]
+    fn test_stats_columns_from_snapshot() {
+        let metrics: BoxMetrics = serde_json::from_value(serde_json::json!({
+            "commands_executed_total": 7,
+            "exec_errors_total": 1,
+            "bytes_sent_total": 0,
+            "bytes_received_total": 0,
+            "cpu_percent": 12.5,
+            "memory_bytes": 64 * 1024 * 1024,
+            "disk_write_bytes": 2048,
+        }))
+        .unwrap();
+        let row = StatsRow {
+            id: "01HJK4TNRPQSXYZ8WM6NCVT9R5".to_string(),
+            name: "web".to_string(),
+            metrics,
+        };
+
+        assert_eq!(
+            stats_columns(&row),
+            vec![
+                "01HJK4TNRPQSXYZ8WM6NCVT9R5",
+                "web",
+                "12.50%",
+                "64.00MiB",
+                "-- / 2.00KiB",
+                "7 (1 failed)",
+            ]
+        );
+
+        let table = render_table(&[row]).to_string();
+        assert!(table.contains("CPU %"));
+        assert!(table.contains("12.50%"));
+    }
+}
@@ -2,8 +2,12 @@ use clap::Args;
 
 #[derive(Args, Debug)]
 pub struct StopArgs {
+    /// Stop all boxes
+    #[arg(short, long)]
+    pub all: bool,
+
     /// Name or ID of the box(es) to stop
-    #[arg(required = true, num_args = 1..)]
+    #[arg(required_unless_present = "all", num_args = 1..)]
     pub targets: Vec<String>,
 }
 
@@ -13,6 +17,18 @@ pub async fn execute(args: StopArgs, global: &crate::cli::GlobalFlags) -> anyhow
     let mut errors = Vec::new();
     let mut success_count = 0;
 
+    if args.all {
+        for (box_id, result) in runtime.stop_all().await? {
+            if let Err(e) = result {
+                eprintln!("Error stopping box '{}': {}", box_id, e);
+                errors.push(format!("{}: {}", box_id, e));
+            } else {
+                println!("{}", box_id);
+                success_count += 1;
+            }
+        }
+    }
+
     for target in args.targets {
         // Get the box first
         let litebox = match runtime.get(&target).await? {
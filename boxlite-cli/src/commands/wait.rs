@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use boxlite::BoxStatus;
+use clap::Args;
+
+/// Exit code when `--timeout` elapses before the box stops, as with timeout(1).
+const TIMEOUT_EXIT_CODE: i32 = 124;
+
+#[derive(Args, Debug)]
+pub struct WaitArgs {
+    /// Name or ID of the box to wait for
+    pub target: String,
+
+    /// Give up after this many seconds (exits with code 124)
+    #[arg(long, value_name = "SECS")]
+    pub timeout: Option<u64>,
+}
+
+pub async fn execute(args: WaitArgs, global: &crate::cli::GlobalFlags) -> anyhow::Result<()> {
+    let runtime = global.create_runtime()?;
+    let litebox = runtime
+        .get(&args.target)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("No such box: {}", args.target))?;
+
+    let status = match args.timeout {
+        Some(secs) => litebox.wait_timeout(Duration::from_secs(secs)).await?,
+        None => Some(litebox.wait().await?),
+    };
+
+    match status {
+        Some(status) => println!("{}", status),
+        None => eprintln!("Timed out waiting for box '{}' to stop", args.target),
+    }
+
+    let code = exit_code(status);
+    if code != 0 {
+        std::process::exit(code);
+    }
+    Ok(())
+}
+
+/// Exit code for the outcome of a wait; `None` means the timeout elapsed.
+///
+/// The runtime does not track the exit code of the box's main process, so a
+/// box that reached `Stopped` exits 0 and any other final status exits 1.
+fn exit_code(status: Option<BoxStatus>) -> i32 {
+    match status {
+        Some(BoxStatus::Stopped) => 0,
+        Some(_) => 1,
+        None => TIMEOUT_EXIT_CODE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Cli, Commands};
+    use clap::Parser;
+
+    #[test]
+    fn test_wait_args_and_exit_codes() {
+        let cli = Cli::try_parse_from(["boxlite", "wait", "web", "--timeout", "5"]).unwrap();
+        let Commands::Wait(args) = cli.command else {
+            panic!("expected wait command");
+        };
+        assert_eq!(args.target, "web");
+        assert_eq!(args.timeout, Some(5));
+
+        assert!(Cli::try_parse_from(["boxlite", "wait"]).is_err());
+        assert!(Cli::try_parse_from(["boxlite", "wait", "web", "--timeout", "soon"]).is_err());
+
+        assert_eq!(exit_code(Some(BoxStatus::Stopped)), 0);
+        assert_eq!(exit_code(Some(BoxStatus::Unknown)), 1);
+        assert_eq!(exit_code(None), TIMEOUT_EXIT_CODE);
+    }
+}
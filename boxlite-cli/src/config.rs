@@ -0,0 +1,230 @@
+//! Box configuration files for `run --config` / `create --config`.
+//!
+//! A TOML file describing the box, so long command lines can be checked in:
+//!
+//! ```toml
+//! image = "python:alpine"
+//! cpus = 2
+//! memory_mib = 1024
+//! working_dir = "/app"
+//!
+//! [env]
+//! PYTHONUNBUFFERED = "1"
+//!
+//! [labels]
+//! team = "infra"
+//!
+//! [[volumes]]
+//! host_path = "/srv/app"
+//! guest_path = "/app"
+//! read_only = true
+//! ```
+//!
+//! Values from the file form the base options; explicit command-line flags
+//! are applied on top and win.
+
+use boxlite::runtime::options::VolumeSpec;
+use boxlite::{BoxOptions, RootfsSpec};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Contents of a box config file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BoxFileConfig {
+    /// Image to run
+    pub image: Option<String>,
+    /// Box name
+    pub name: Option<String>,
+    /// Number of CPUs
+    pub cpus: Option<u8>,
+    /// Memory limit (in MiB)
+    pub memory_mib: Option<u32>,
+    /// Rootfs disk size (in GB)
+    pub disk_size_gb: Option<u64>,
+    /// Working directory inside the box
+    pub working_dir: Option<String>,
+    /// Environment variables
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Labels (key = value)
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// Host directories to mount into the box
+    #[serde(default)]
+    pub volumes: Vec<FileVolume>,
+}
+
+/// A `[[volumes]]` entry.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FileVolume {
+    pub host_path: String,
+    pub guest_path: String,
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+impl BoxFileConfig {
+    /// Read and validate a config file.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("failed to read config {}: {}", path.display(), e))?;
+        Self::parse(&content)
+            .map_err(|e| anyhow::anyhow!("invalid config {}: {}", path.display(), e))
+    }
+
+    /// Load `path` if given, otherwise an empty config.
+    pub fn load_optional(path: Option<&Path>) -> anyhow::Result<Self> {
+        path.map(Self::load)
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    fn parse(content: &str) -> anyhow::Result<Self> {
+        let config: Self = toml::from_str(content)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        if self.cpus == Some(0) {
+            anyhow::bail!("`cpus` must be at least 1");
+        }
+        if self.memory_mib == Some(0) {
+            anyhow::bail!("`memory_mib` must be greater than 0");
+        }
+        for (i, volume) in self.volumes.iter().enumerate() {
+            if volume.host_path.is_empty() {
+                anyhow::bail!("`volumes[{}].host_path` must not be empty", i);
+            }
+            if !volume.guest_path.starts_with('/') {
+                anyhow::bail!(
+                    "`volumes[{}].guest_path` must be an absolute path (got '{}')",
+                    i,
+                    volume.guest_path
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Base options described by the file, before command-line flags are applied.
+    pub fn to_box_options(&self) -> BoxOptions {
+        let mut options = BoxOptions {
+            cpus: self.cpus,
+            memory_mib: self.memory_mib,
+            disk_size_gb: self.disk_size_gb,
+            working_dir: self.working_dir.clone(),
+            env: self
+                .env
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            volumes: self
+                .volumes
+                .iter()
+                .map(|v| VolumeSpec {
+                    host_path: v.host_path.clone(),
+                    guest_path: v.guest_path.clone(),
+                    read_only: v.read_only,
+                    ..Default::default()
+                })
+                .collect(),
+            labels: self
+                .labels
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            ..Default::default()
+        };
+        if let Some(image) = &self.image {
+            options.rootfs = RootfsSpec::Image(image.clone());
+        }
+        options
+    }
+
+    /// Pick the image: the command-line argument wins over the file.
+    pub fn resolve_image(&self, arg: Option<&str>) -> anyhow::Result<String> {
+        arg.map(str::to_string)
+            .or_else(|| self.image.clone())
+            .ok_or_else(|| {
+                anyhow::anyhow!("no image specified: pass IMAGE or set `image` in the config file")
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+        image = "python:alpine"
+        name = "worker"
+        cpus = 2
+        memory_mib = 1024
+        working_dir = "/app"
+
+        [env]
+        PYTHONUNBUFFERED = "1"
+
+        [labels]
+        team = "infra"
+
+        [[volumes]]
+        host_path = "/srv/data"
+        guest_path = "/data"
+        read_only = true
+    "#;
+
+    #[test]
+    fn test_config_into_box_options() {
+        let config = BoxFileConfig::parse(SAMPLE).unwrap();
+        assert_eq!(config.name.as_deref(), Some("worker"));
+
+        let options = config.to_box_options();
+        assert!(matches!(&options.rootfs, RootfsSpec::Image(image) if image == "python:alpine"));
+        assert_eq!(options.cpus, Some(2));
+        assert_eq!(options.memory_mib, Some(1024));
+        assert_eq!(options.working_dir.as_deref(), Some("/app"));
+        assert_eq!(
+            options.env,
+            vec![("PYTHONUNBUFFERED".to_string(), "1".to_string())]
+        );
+        assert_eq!(
+            options.labels.get("team").map(String::as_str),
+            Some("infra")
+        );
+        assert_eq!(options.volumes.len(), 1);
+        assert_eq!(options.volumes[0].host_path, "/srv/data");
+        assert_eq!(options.volumes[0].guest_path, "/data");
+        assert!(options.volumes[0].read_only);
+    }
+
+    #[test]
+    fn test_config_errors_name_offending_key() {
+        let err = BoxFileConfig::parse("image = \"alpine\"\ncpu = 2\n").unwrap_err();
+        assert!(err.to_string().contains("cpu"), "got: {}", err);
+
+        let err = BoxFileConfig::parse("memory_mib = \"lots\"\n").unwrap_err();
+        assert!(err.to_string().contains("memory_mib"), "got: {}", err);
+
+        let err =
+            BoxFileConfig::parse("[[volumes]]\nhost_path = \"/srv\"\nguest_path = \"data\"\n")
+                .unwrap_err();
+        assert!(
+            err.to_string().contains("volumes[0].guest_path"),
+            "got: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_resolve_image_prefers_argument() {
+        let config = BoxFileConfig::parse("image = \"alpine\"\n").unwrap();
+        assert_eq!(config.resolve_image(Some("ubuntu")).unwrap(), "ubuntu");
+        assert_eq!(config.resolve_image(None).unwrap(), "alpine");
+        assert!(BoxFileConfig::default().resolve_image(None).is_err());
+    }
+}
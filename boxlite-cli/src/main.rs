@@ -1,5 +1,6 @@
 mod cli;
 mod commands;
+mod config;
 
 use std::process;
 
@@ -34,13 +35,20 @@ async fn main() {
 
     let result = match cli.command {
         cli::Commands::Run(args) => commands::run::execute(args, &cli.global).await,
+        cli::Commands::Exec(args) => commands::exec::execute(args, &cli.global).await,
         cli::Commands::Create(args) => commands::create::execute(args, &cli.global).await,
         cli::Commands::List(args) => commands::list::execute(args, &cli.global).await,
+        cli::Commands::Inspect(args) => commands::inspect::execute(args, &cli.global).await,
         cli::Commands::Rm(args) => commands::rm::execute(args, &cli.global).await,
         cli::Commands::Start(args) => commands::start::execute(args, &cli.global).await,
         cli::Commands::Stop(args) => commands::stop::execute(args, &cli.global).await,
+        cli::Commands::Stats(args) => commands::stats::execute(args, &cli.global).await,
         cli::Commands::Restart(args) => commands::restart::execute(args, &cli.global).await,
+        cli::Commands::Logs(args) => commands::logs::execute(args, &cli.global).await,
+        cli::Commands::Cp(args) => commands::cp::execute(args, &cli.global).await,
         cli::Commands::Pull(args) => commands::pull::execute(args, &cli.global).await,
+        cli::Commands::Image(args) => commands::image::execute(args, &cli.global).await,
+        cli::Commands::Wait(args) => commands::wait::execute(args, &cli.global).await,
     };
 
     if let Err(error) = result {
@@ -0,0 +1,88 @@
+use boxlite::{ImageDetails, ImageInfo};
+use predicates::prelude::*;
+
+mod common;
+
+#[test]
+fn test_image_ls_lists_pulled_image() {
+    let mut ctx = common::boxlite();
+    ctx.cmd.args(["pull", "alpine:latest"]);
+    ctx.cmd.assert().success();
+
+    let output = ctx
+        .new_cmd()
+        .args(["image", "ls", "-o", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let images: Vec<ImageInfo> = serde_json::from_slice(&output.stdout).unwrap();
+    let alpine = images
+        .iter()
+        .find(|image| image.reference == "docker.io/library/alpine:latest")
+        .expect("alpine should be listed");
+    assert!(alpine.layer_count > 0);
+    assert!(alpine.size.is_some_and(|size| size.as_bytes() > 0));
+
+    ctx.new_cmd()
+        .args(["image", "ls"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("REPOSITORY").and(predicate::str::contains("alpine")));
+}
+
+#[test]
+fn test_image_inspect() {
+    let mut ctx = common::boxlite();
+    ctx.cmd.args(["pull", "alpine:latest"]);
+    ctx.cmd.assert().success();
+
+    let output = ctx
+        .new_cmd()
+        .args(["image", "inspect", "alpine:latest"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let images: Vec<ImageDetails> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].layers.len(), images[0].info.layer_count);
+    assert!(images[0].config.config().is_some());
+}
+
+#[test]
+fn test_image_inspect_missing() {
+    let ctx = common::boxlite();
+    ctx.new_cmd()
+        .args(["image", "inspect", "alpine:does-not-exist"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not found"));
+}
+
+#[test]
+fn test_image_rm_in_use_requires_force() {
+    let mut ctx = common::boxlite();
+    let name = "image-rm-user";
+    ctx.cmd.args(["pull", "docker.io/busybox:latest"]);
+    ctx.cmd.assert().success();
+    ctx.new_cmd()
+        .args(["create", "--name", name, "docker.io/busybox:latest"])
+        .assert()
+        .success();
+
+    ctx.new_cmd()
+        .args(["image", "rm", "docker.io/busybox:latest"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(name));
+
+    ctx.new_cmd()
+        .args(["image", "rm", "--force", "docker.io/busybox:latest"])
+        .assert()
+        .success();
+    ctx.new_cmd()
+        .args(["image", "inspect", "docker.io/busybox:latest"])
+        .assert()
+        .failure();
+
+    ctx.cleanup_box(name);
+}
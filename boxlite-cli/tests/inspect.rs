@@ -0,0 +1,43 @@
+use boxlite::{BoxInfo, BoxStatus};
+use predicates::prelude::*;
+
+mod common;
+
+#[test]
+fn test_inspect_outputs_box_json() {
+    let mut ctx = common::boxlite();
+    let name = "inspect-json";
+
+    let output = ctx
+        .cmd
+        .args(["create", "--name", name, "alpine:latest"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let id = String::from_utf8(output.stdout).unwrap().trim().to_string();
+
+    let output = ctx.new_cmd().args(["inspect", name]).output().unwrap();
+    assert!(output.status.success());
+    let boxes: Vec<BoxInfo> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(boxes.len(), 1);
+    assert_eq!(boxes[0].id.as_str(), id);
+    assert_eq!(boxes[0].status, BoxStatus::Configured);
+
+    ctx.new_cmd()
+        .args(["inspect", "--format", "{{.name}} {{.status}}", name])
+        .assert()
+        .success()
+        .stdout(format!("{} configured\n", name));
+
+    ctx.cleanup_box(name);
+}
+
+#[test]
+fn test_inspect_missing_box() {
+    let mut ctx = common::boxlite();
+    ctx.cmd
+        .args(["inspect", "no-such-box"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No such box"));
+}
@@ -40,6 +40,38 @@ fn test_list_lifecycle() {
     ctx.cleanup_box(name);
 }
 
+#[test]
+fn test_list_json_output() {
+    let mut ctx = common::boxlite();
+    let name = "list-json";
+
+    let _ = ctx
+        .cmd
+        .args(["create", "--name", name, "alpine:latest"])
+        .output();
+
+    let output = ctx
+        .new_cmd()
+        .args(["list", "-a", "-o", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let boxes: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(
+        boxes
+            .iter()
+            .all(|b| b["id"].is_string() && b["status"].is_string())
+    );
+    let created = boxes
+        .iter()
+        .find(|b| b["name"] == name)
+        .expect("created box listed");
+    assert_eq!(created["status"], "configured");
+
+    ctx.cleanup_box(name);
+}
+
 #[test]
 fn test_list_alias_ls() {
     let mut ctx = common::boxlite();
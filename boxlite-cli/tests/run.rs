@@ -325,6 +325,13 @@ fn test_run_detach() {
     assert!(!box_id.is_empty());
     assert!(box_id.chars().all(|c| c.is_alphanumeric()));
 
+    // The command returned after start; the box keeps running without it
+    ctx.new_cmd()
+        .args(["list", "-q"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(box_id));
+
     // Cleanup: Detached boxes must be manually removed since they don't support --rm
     let mut rm_cmd = ctx.new_cmd();
     rm_cmd.args(["rm", "--force", box_id]);
@@ -70,6 +70,153 @@ pub enum BoxliteError {
     /// Resource (box or runtime) has been stopped/shutdown.
     #[error("stopped: {0}")]
     Stopped(String),
+
+    /// A lock is held by someone else; unlike other errors it clears up on
+    /// its own, so it is retryable.
+    #[error("lock contention: {0}")]
+    LockContention(String),
+
+    /// `source` was raised while `stage` of box `box_id` was running.
+    ///
+    /// `code()` and `is_retryable()` report those of `source`.
+    #[error("box {box_id} stage {stage}: {source}")]
+    Context {
+        box_id: String,
+        stage: String,
+        source: Box<BoxliteError>,
+    },
+}
+
+/// Error category of a [`BoxliteError`]; `Context` errors report their source's.
+///
+/// Lets callers (and SDK bindings) branch on the kind of failure without
+/// matching on message text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    UnsupportedEngine,
+    Engine,
+    Config,
+    Storage,
+    Image,
+    Portal,
+    Network,
+    Rpc,
+    RpcTransport,
+    Internal,
+    Execution,
+    Unsupported,
+    NotFound,
+    AlreadyExists,
+    InvalidState,
+    Database,
+    Metadata,
+    InvalidArgument,
+    Stopped,
+    LockContention,
+}
+
+impl ErrorKind {
+    /// Stable machine-readable code, e.g. `"NOT_FOUND"`.
+    ///
+    /// Codes never change once released, so they are safe to persist or
+    /// expose across FFI boundaries.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::UnsupportedEngine => "UNSUPPORTED_ENGINE",
+            ErrorKind::Engine => "ENGINE",
+            ErrorKind::Config => "CONFIG",
+            ErrorKind::Storage => "STORAGE",
+            ErrorKind::Image => "IMAGE",
+            ErrorKind::Portal => "PORTAL",
+            ErrorKind::Network => "NETWORK",
+            ErrorKind::Rpc => "RPC",
+            ErrorKind::RpcTransport => "RPC_TRANSPORT",
+            ErrorKind::Internal => "INTERNAL",
+            ErrorKind::Execution => "EXECUTION",
+            ErrorKind::Unsupported => "UNSUPPORTED",
+            ErrorKind::NotFound => "NOT_FOUND",
+            ErrorKind::AlreadyExists => "ALREADY_EXISTS",
+            ErrorKind::InvalidState => "INVALID_STATE",
+            ErrorKind::Database => "DATABASE",
+            ErrorKind::Metadata => "METADATA",
+            ErrorKind::InvalidArgument => "INVALID_ARGUMENT",
+            ErrorKind::Stopped => "STOPPED",
+            ErrorKind::LockContention => "LOCK_CONTENTION",
+        }
+    }
+}
+
+impl BoxliteError {
+    /// Category of this error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            BoxliteError::UnsupportedEngine => ErrorKind::UnsupportedEngine,
+            BoxliteError::Engine(_) => ErrorKind::Engine,
+            BoxliteError::Config(_) => ErrorKind::Config,
+            BoxliteError::Storage(_) => ErrorKind::Storage,
+            BoxliteError::Image(_) => ErrorKind::Image,
+            BoxliteError::Portal(_) => ErrorKind::Portal,
+            BoxliteError::Network(_) => ErrorKind::Network,
+            BoxliteError::Rpc(_) => ErrorKind::Rpc,
+            BoxliteError::RpcTransport(_) => ErrorKind::RpcTransport,
+            BoxliteError::Internal(_) => ErrorKind::Internal,
+            BoxliteError::Execution(_) => ErrorKind::Execution,
+            BoxliteError::Unsupported(_) => ErrorKind::Unsupported,
+            BoxliteError::NotFound(_) => ErrorKind::NotFound,
+            BoxliteError::AlreadyExists(_) => ErrorKind::AlreadyExists,
+            BoxliteError::InvalidState(_) => ErrorKind::InvalidState,
+            BoxliteError::Database(_) => ErrorKind::Database,
+            BoxliteError::MetadataError(_) => ErrorKind::Metadata,
+            BoxliteError::InvalidArgument(_) => ErrorKind::InvalidArgument,
+            BoxliteError::Stopped(_) => ErrorKind::Stopped,
+            BoxliteError::LockContention(_) => ErrorKind::LockContention,
+            BoxliteError::Context { source, .. } => source.kind(),
+        }
+    }
+
+    /// Stable machine-readable code of this error, e.g. `"NOT_FOUND"`.
+    ///
+    /// Prefer this over matching on the message text.
+    pub fn code(&self) -> &'static str {
+        self.kind().code()
+    }
+
+    /// Error for a lock currently held by someone else.
+    pub fn lock_contention(detail: impl std::fmt::Display) -> Self {
+        BoxliteError::LockContention(detail.to_string())
+    }
+
+    /// Whether the same operation may succeed if tried again later.
+    ///
+    /// True for network and transport failures and for lock contention;
+    /// false for everything else, including invalid arguments and missing
+    /// resources.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            BoxliteError::Network(_)
+            | BoxliteError::RpcTransport(_)
+            | BoxliteError::LockContention(_) => true,
+            BoxliteError::Context { source, .. } => source.is_retryable(),
+            _ => false,
+        }
+    }
+}
+
+/// Adds box context to errors on their way up.
+pub trait BoxliteResultExt<T> {
+    /// Wrap an error in [`BoxliteError::Context`] naming the box and the
+    /// stage (e.g. an init task) it came from.
+    fn with_box_context(self, box_id: impl std::fmt::Display, stage: &str) -> BoxliteResult<T>;
+}
+
+impl<T> BoxliteResultExt<T> for BoxliteResult<T> {
+    fn with_box_context(self, box_id: impl std::fmt::Display, stage: &str) -> BoxliteResult<T> {
+        self.map_err(|source| BoxliteError::Context {
+            box_id: box_id.to_string(),
+            stage: stage.to_string(),
+            source: Box::new(source),
+        })
+    }
 }
 
 // Implement From for common error types to enable `?` operator
@@ -108,3 +255,83 @@ impl From<tonic::transport::Error> for BoxliteError {
         BoxliteError::RpcTransport(err.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes() {
+        let cases = [
+            (BoxliteError::UnsupportedEngine, "UNSUPPORTED_ENGINE"),
+            (BoxliteError::Engine(String::new()), "ENGINE"),
+            (BoxliteError::Config(String::new()), "CONFIG"),
+            (BoxliteError::Storage(String::new()), "STORAGE"),
+            (BoxliteError::Image(String::new()), "IMAGE"),
+            (BoxliteError::Portal(String::new()), "PORTAL"),
+            (BoxliteError::Network(String::new()), "NETWORK"),
+            (BoxliteError::Rpc(String::new()), "RPC"),
+            (BoxliteError::RpcTransport(String::new()), "RPC_TRANSPORT"),
+            (BoxliteError::Internal(String::new()), "INTERNAL"),
+            (BoxliteError::Execution(String::new()), "EXECUTION"),
+            (BoxliteError::Unsupported(String::new()), "UNSUPPORTED"),
+            (BoxliteError::NotFound(String::new()), "NOT_FOUND"),
+            (BoxliteError::AlreadyExists(String::new()), "ALREADY_EXISTS"),
+            (BoxliteError::InvalidState(String::new()), "INVALID_STATE"),
+            (BoxliteError::Database(String::new()), "DATABASE"),
+            (BoxliteError::MetadataError(String::new()), "METADATA"),
+            (
+                BoxliteError::InvalidArgument(String::new()),
+                "INVALID_ARGUMENT",
+            ),
+            (BoxliteError::Stopped(String::new()), "STOPPED"),
+            (
+                BoxliteError::LockContention(String::new()),
+                "LOCK_CONTENTION",
+            ),
+        ];
+        for (err, code) in &cases {
+            assert_eq!(err.code(), *code, "{err:?}");
+        }
+
+        let err = BoxliteError::NotFound("box-1".into());
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert_eq!(err.code(), "NOT_FOUND");
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(BoxliteError::Network("connection reset".into()).is_retryable());
+        assert!(BoxliteError::RpcTransport("broken pipe".into()).is_retryable());
+        assert!(BoxliteError::lock_contention("home dir in use").is_retryable());
+
+        assert!(!BoxliteError::InvalidState("box is stopped".into()).is_retryable());
+        assert!(!BoxliteError::InvalidState("lock contention: spoofed".into()).is_retryable());
+        assert!(!BoxliteError::Storage("hash mismatch".into()).is_retryable());
+        assert!(!BoxliteError::InvalidArgument("bad cpus".into()).is_retryable());
+        assert!(!BoxliteError::NotFound("box-1".into()).is_retryable());
+        assert!(!BoxliteError::Internal("bug".into()).is_retryable());
+    }
+
+    #[test]
+    fn test_box_context() {
+        let err = Err::<(), _>(BoxliteError::Network("connection reset".into()))
+            .with_box_context("01JABC", "guest_connect")
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "box 01JABC stage guest_connect: network error: connection reset"
+        );
+        assert_eq!(err.code(), "NETWORK");
+        assert!(err.is_retryable());
+        let source = std::error::Error::source(&err).unwrap();
+        assert_eq!(source.to_string(), "network error: connection reset");
+
+        let err = Err::<(), _>(BoxliteError::NotFound("alpine".into()))
+            .with_box_context("01JABC", "container_rootfs_prep")
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert!(!err.is_retryable());
+    }
+}
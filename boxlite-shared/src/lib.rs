@@ -14,7 +14,7 @@ pub mod generated {
     tonic::include_proto!("boxlite.v1");
 }
 
-pub use errors::{BoxliteError, BoxliteResult};
+pub use errors::{BoxliteError, BoxliteResult, BoxliteResultExt, ErrorKind};
 pub use transport::Transport;
 
 // Container service
@@ -25,6 +25,10 @@ pub use generated::container_server::{Container, ContainerServer};
 pub use generated::guest_client::GuestClient;
 pub use generated::guest_server::{Guest, GuestServer};
 
+// Files service
+pub use generated::files_client::FilesClient;
+pub use generated::files_server::{Files, FilesServer};
+
 // Execution service
 pub use generated::execution_client::ExecutionClient;
 pub use generated::execution_server::{Execution, ExecutionServer};
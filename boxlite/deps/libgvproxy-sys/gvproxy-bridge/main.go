@@ -163,6 +163,7 @@ func gvproxy_set_log_callback(callback unsafe.Pointer) {
 type PortMapping struct {
 	HostPort  uint16 `json:"host_port"`
 	GuestPort uint16 `json:"guest_port"`
+	Protocol  string `json:"protocol"` // "tcp" (default) or "udp"
 }
 
 // DNSZone represents a local DNS zone configuration
@@ -280,9 +281,12 @@ func gvproxy_create(configJSON *C.char) C.longlong {
 	// Containers bind to 0.0.0.0 inside the guest, accessible via guest IP
 	for _, pm := range config.PortMappings {
 		forwardKey := fmt.Sprintf("0.0.0.0:%d", pm.HostPort)
+		if pm.Protocol == "udp" {
+			forwardKey = "udp:" + forwardKey
+		}
 		forwardVal := fmt.Sprintf("%s:%d", config.GuestIP, pm.GuestPort)
 		tapConfig.Forwards[forwardKey] = forwardVal
-		logrus.WithFields(logrus.Fields{"host": forwardKey, "guest": forwardVal}).Info("Added TCP port forward")
+		logrus.WithFields(logrus.Fields{"host": forwardKey, "guest": forwardVal, "protocol": pm.Protocol}).Info("Added port forward")
 	}
 
 	// Platform-specific socket creation
@@ -17,6 +17,7 @@ use std::thread;
 use std::time::Duration;
 
 use boxlite::{
+    LogFormat,
     runtime::layout,
     util::{self, is_process_alive},
     vmm::{self, InstanceSpec, VmmConfig, VmmKind},
@@ -27,7 +28,7 @@ use clap::Parser;
 use tracing_subscriber::{EnvFilter, fmt, prelude::*};
 
 #[cfg(feature = "gvproxy-backend")]
-use boxlite::net::{ConnectionType, NetworkBackendEndpoint, gvproxy::GvproxyInstance};
+use boxlite::net::gvproxy::{GvproxyConfig, GvproxyInstance};
 
 /// Universal Box runner binary - subprocess that executes isolated Boxes
 #[derive(Parser, Debug)]
@@ -53,13 +54,11 @@ struct ShimArgs {
 
 /// Initialize tracing with file logging.
 ///
-/// Logs are written to {home_dir}/logs/boxlite-shim.log with daily rotation.
+/// Logs are written to {logs_dir}/boxlite-shim.log with daily rotation.
 /// Returns WorkerGuard that must be kept alive to maintain the background writer thread.
-fn init_logging(home_dir: &Path) -> tracing_appender::non_blocking::WorkerGuard {
-    let logs_dir = home_dir.join(layout::dirs::LOGS_DIR);
-
+fn init_logging(logs_dir: &Path) -> tracing_appender::non_blocking::WorkerGuard {
     // Create logs directory if it doesn't exist
-    std::fs::create_dir_all(&logs_dir).expect("Failed to create logs directory");
+    std::fs::create_dir_all(logs_dir).expect("Failed to create logs directory");
 
     // Set up file appender with daily rotation
     let file_appender = tracing_appender::rolling::daily(logs_dir, "boxlite-shim.log");
@@ -73,7 +72,7 @@ fn init_logging(home_dir: &Path) -> tracing_appender::non_blocking::WorkerGuard
         .unwrap();
 
     // Initialize subscriber with file output
-    util::register_to_tracing(non_blocking, env_filter);
+    util::register_to_tracing(non_blocking, env_filter, LogFormat::default());
 
     guard
 }
@@ -88,9 +87,14 @@ fn main() -> BoxliteResult<()> {
         boxlite_shared::errors::BoxliteError::Engine(format!("Failed to parse config JSON: {}", e))
     })?;
 
-    // Initialize logging using home_dir from config
+    // Initialize logging in the runtime's logs dir (home_dir/logs unless overridden)
     // Keep guard alive until end of main to ensure logs are written
-    let _log_guard = init_logging(&config.home_dir);
+    let logs_dir = config
+        .layout_dirs
+        .logs
+        .clone()
+        .unwrap_or_else(|| config.home_dir.join(layout::dirs::LOGS_DIR));
+    let _log_guard = init_logging(&logs_dir);
 
     tracing::info!(
         engine = ?args.engine,
@@ -126,14 +130,17 @@ fn main() -> BoxliteResult<()> {
         use boxlite::jailer::platform::linux;
         use boxlite::runtime::layout::{FilesystemLayout, FsLayoutConfig};
 
-        if config.security.jailer_enabled {
+        if config.security.isolation_enabled && config.security.jailer_enabled {
             tracing::info!(
                 box_id = %config.box_id,
                 seccomp_enabled = config.security.seccomp_enabled,
                 "Applying Linux jailer isolation"
             );
 
-            let layout = FilesystemLayout::new(config.home_dir.clone(), FsLayoutConfig::default());
+            let layout = FilesystemLayout::new(
+                config.home_dir.clone(),
+                FsLayoutConfig::default().with_dirs(config.layout_dirs.clone()),
+            );
 
             if let Err(e) = linux::apply_isolation(&config.security, &config.box_id, &layout) {
                 // Log error but don't fail - allows debugging with isolation disabled
@@ -165,12 +172,12 @@ fn main() -> BoxliteResult<()> {
     #[cfg(feature = "gvproxy-backend")]
     if let Some(ref net_config) = config.network_config {
         tracing::info!(
-            port_mappings = ?net_config.port_mappings,
+            port_forwards = ?net_config.port_forwards,
             "Creating network backend (gvproxy) from config"
         );
 
-        // Create gvproxy instance
-        let gvproxy = GvproxyInstance::new(&net_config.port_mappings)?;
+        // Create gvproxy instance (guest MAC/IP feed its DHCP static lease)
+        let gvproxy = GvproxyInstance::with_config(GvproxyConfig::from(net_config))?;
         let socket_path = gvproxy.get_socket_path()?;
 
         tracing::info!(
@@ -178,30 +185,24 @@ fn main() -> BoxliteResult<()> {
             "Network backend created"
         );
 
-        // Create NetworkBackendEndpoint from socket path
-        // Platform-specific connection type:
-        // - macOS: UnixDgram with VFKit protocol
-        // - Linux: UnixStream with Qemu protocol
-        let connection_type = if cfg!(target_os = "macos") {
-            ConnectionType::UnixDgram
-        } else {
-            ConnectionType::UnixStream
-        };
-
-        // Use GUEST_MAC constant - must match DHCP static lease in gvproxy config
-        use boxlite::net::constants::GUEST_MAC;
-
-        config.network_backend_endpoint = Some(NetworkBackendEndpoint::UnixSocket {
-            path: socket_path,
-            connection_type,
-            mac_address: GUEST_MAC,
-        });
+        // Create NetworkBackendEndpoint from socket path; the MAC must match
+        // the DHCP static lease in the gvproxy config
+        config.network_backend_endpoint = Some(net_config.unix_endpoint(socket_path));
 
         // Leak the gvproxy instance to keep it alive for VM lifetime.
         // This is intentional - the VM needs networking for its entire life,
         // and OS cleanup handles resources when process exits.
-        let _gvproxy_leaked = Box::leak(Box::new(gvproxy));
+        let gvproxy: &'static GvproxyInstance = Box::leak(Box::new(gvproxy));
         tracing::debug!("Leaked gvproxy instance for VM lifetime");
+
+        // Publish network counters for the host-side metrics() path
+        start_net_stats_publisher(
+            gvproxy,
+            boxlite::runtime::constants::filenames::net_stats_path(
+                &config.home_dir,
+                &config.box_id,
+            ),
+        );
     }
 
     // Save detach/parent_pid before config is moved into engine.create()
@@ -254,6 +255,34 @@ fn main() -> BoxliteResult<()> {
     }
 }
 
+/// Periodically write gvproxy network counters to `path`.
+///
+/// The host reads this file when collecting box metrics, since the network
+/// backend only exists inside this process.
+#[cfg(feature = "gvproxy-backend")]
+fn start_net_stats_publisher(gvproxy: &'static GvproxyInstance, path: std::path::PathBuf) {
+    use boxlite::net::NetworkMetrics;
+
+    thread::spawn(move || {
+        loop {
+            thread::sleep(Duration::from_secs(1));
+
+            let Ok(stats) = gvproxy.get_stats() else {
+                continue;
+            };
+            let metrics = NetworkMetrics {
+                bytes_sent: stats.bytes_sent,
+                bytes_received: stats.bytes_received,
+                tcp_connections: Some(stats.tcp.current_established),
+                tcp_connection_errors: Some(stats.tcp.failed_connection_attempts),
+            };
+            if let Err(e) = metrics.write_to(&path) {
+                tracing::debug!(path = %path.display(), error = %e, "Failed to publish network stats");
+            }
+        }
+    });
+}
+
 /// Timeout for graceful shutdown before force kill (in seconds).
 const GRACEFUL_SHUTDOWN_TIMEOUT_SECS: u64 = 5;
 
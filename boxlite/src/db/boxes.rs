@@ -6,11 +6,11 @@
 //!
 //! Each table has queryable columns for filtering + JSON blob for full struct.
 
-use chrono::Utc;
-use rusqlite::{OptionalExtension, params};
+use chrono::{DateTime, Utc};
+use rusqlite::{OptionalExtension, Params, params, params_from_iter};
 
 use crate::litebox::config::BoxConfig;
-use crate::runtime::types::{BoxID, BoxState};
+use crate::runtime::types::{BoxID, BoxState, BoxStatus};
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
 use super::{Database, db_err};
@@ -50,7 +50,7 @@ impl BoxStore {
 
         match json {
             Some(j) => {
-                let config: BoxConfig = serde_json::from_str(&j).map_err(|e| {
+                let config = BoxConfig::from_json(&j).map_err(|e| {
                     BoxliteError::Database(format!("Failed to deserialize config: {}", e))
                 })?;
                 Ok(Some(config))
@@ -59,6 +59,31 @@ impl BoxStore {
         }
     }
 
+    /// Update box configuration.
+    ///
+    /// Rewrites the name column and JSON blob. The UNIQUE constraint on
+    /// `name` rejects a name already used by another box.
+    pub fn update_config(&self, config: &BoxConfig) -> BoxliteResult<()> {
+        let conn = self.db.conn();
+
+        let json = serde_json::to_string(config)
+            .map_err(|e| BoxliteError::Database(format!("Failed to serialize config: {}", e)))?;
+
+        let rows_affected = db_err!(conn.execute(
+            "UPDATE box_config SET name = ?1, json = ?2 WHERE id = ?3",
+            params![config.name.as_deref(), json, config.id],
+        ))?;
+
+        if rows_affected == 0 {
+            return Err(BoxliteError::NotFound(format!(
+                "Box not found: {}",
+                config.id
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Delete box configuration (and state via CASCADE).
     pub fn delete(&self, box_id: &str) -> BoxliteResult<bool> {
         let conn = self.db.conn();
@@ -140,12 +165,14 @@ impl BoxStore {
 
         // Insert config (name has UNIQUE constraint, will fail on duplicate)
         db_err!(tx.execute(
-            "INSERT INTO box_config (id, name, created_at, json) VALUES (?1, ?2, ?3, ?4)",
+            "INSERT INTO box_config (id, name, created_at, json, container_id)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
             params![
                 config.id,
                 config.name.as_deref(),
                 config.created_at.timestamp(),
-                config_json
+                config_json,
+                config.container.id.as_str()
             ],
         ))?;
 
@@ -173,57 +200,130 @@ impl BoxStore {
         }
     }
 
+    /// Find the box whose container has ID `container_id`.
+    pub fn find_by_container_id(
+        &self,
+        container_id: &str,
+    ) -> BoxliteResult<Option<(BoxConfig, BoxState)>> {
+        Ok(self
+            .query_boxes(
+                r#"
+                SELECT c.json as config_json, s.json as state_json
+                FROM box_config c
+                JOIN box_state s ON c.id = s.id
+                WHERE c.container_id = ?1
+                "#,
+                params![container_id],
+            )?
+            .into_iter()
+            .next())
+    }
+
+    /// Find the box named exactly `name`.
+    pub fn find_by_name(&self, name: &str) -> BoxliteResult<Option<(BoxConfig, BoxState)>> {
+        Ok(self
+            .query_boxes(
+                r#"
+                SELECT c.json as config_json, s.json as state_json
+                FROM box_config c
+                JOIN box_state s ON c.id = s.id
+                WHERE c.name = ?1
+                "#,
+                params![name],
+            )?
+            .into_iter()
+            .next())
+    }
+
     /// List all boxes as (config, state) pairs.
     ///
     /// Returns boxes sorted by creation time (newest first).
     pub fn list_all(&self) -> BoxliteResult<Vec<(BoxConfig, BoxState)>> {
-        let conn = self.db.conn();
-
-        let mut stmt = db_err!(conn.prepare(
+        self.query_boxes(
             r#"
             SELECT c.json as config_json, s.json as state_json
             FROM box_config c
             JOIN box_state s ON c.id = s.id
             ORDER BY c.created_at DESC
-            "#
-        ))?;
+            "#,
+            [],
+        )
+    }
 
-        let rows = db_err!(stmt.query_map([], |row| {
-            let config_json: String = row.get(0)?;
-            let state_json: String = row.get(1)?;
-            Ok((config_json, state_json))
-        }))?;
+    /// List active boxes (Starting, Running, Detached).
+    pub fn list_active(&self) -> BoxliteResult<Vec<(BoxConfig, BoxState)>> {
+        self.query_boxes(
+            r#"
+            SELECT c.json as config_json, s.json as state_json
+            FROM box_config c
+            JOIN box_state s ON c.id = s.id
+            WHERE s.status IN ('starting', 'running', 'detached')
+            ORDER BY c.created_at DESC
+            "#,
+            [],
+        )
+    }
 
-        let mut result = Vec::new();
-        for row in rows {
-            let (config_json, state_json) = db_err!(row)?;
-            let config: BoxConfig = serde_json::from_str(&config_json).map_err(|e| {
-                BoxliteError::Database(format!("Failed to deserialize config: {}", e))
-            })?;
-            let state: BoxState = serde_json::from_str(&state_json).map_err(|e| {
-                BoxliteError::Database(format!("Failed to deserialize state: {}", e))
-            })?;
-            result.push((config, state));
+    /// List boxes whose status is one of `statuses`.
+    ///
+    /// Filters on the indexed `status` column so only matching rows are
+    /// deserialized. Returns boxes sorted by creation time (newest first).
+    pub fn list_by_status(
+        &self,
+        statuses: &[BoxStatus],
+    ) -> BoxliteResult<Vec<(BoxConfig, BoxState)>> {
+        if statuses.is_empty() {
+            return Ok(Vec::new());
         }
 
-        Ok(result)
+        let placeholders = vec!["?"; statuses.len()].join(", ");
+        let sql = format!(
+            r#"
+            SELECT c.json as config_json, s.json as state_json
+            FROM box_config c
+            JOIN box_state s ON c.id = s.id
+            WHERE s.status IN ({})
+            ORDER BY c.created_at DESC
+            "#,
+            placeholders
+        );
+        self.query_boxes(
+            &sql,
+            params_from_iter(statuses.iter().map(|status| status.as_str())),
+        )
     }
 
-    /// List active boxes (Starting, Running, Detached).
-    pub fn list_active(&self) -> BoxliteResult<Vec<(BoxConfig, BoxState)>> {
-        let conn = self.db.conn();
-
-        let mut stmt = db_err!(conn.prepare(
+    /// List boxes created in `[start, end)`, newest first.
+    ///
+    /// Uses the indexed `created_at` column, which has second precision.
+    pub fn list_created_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> BoxliteResult<Vec<(BoxConfig, BoxState)>> {
+        self.query_boxes(
             r#"
             SELECT c.json as config_json, s.json as state_json
             FROM box_config c
             JOIN box_state s ON c.id = s.id
-            WHERE s.status IN ('starting', 'running', 'detached')
+            WHERE c.created_at >= ?1 AND c.created_at < ?2
             ORDER BY c.created_at DESC
-            "#
-        ))?;
+            "#,
+            params![start.timestamp(), end.timestamp()],
+        )
+    }
 
-        let rows = db_err!(stmt.query_map([], |row| {
+    /// Run a `(config_json, state_json)` query and deserialize each row.
+    fn query_boxes(
+        &self,
+        sql: &str,
+        params: impl Params,
+    ) -> BoxliteResult<Vec<(BoxConfig, BoxState)>> {
+        let conn = self.db.conn();
+
+        let mut stmt = db_err!(conn.prepare(sql))?;
+
+        let rows = db_err!(stmt.query_map(params, |row| {
             let config_json: String = row.get(0)?;
             let state_json: String = row.get(1)?;
             Ok((config_json, state_json))
@@ -232,7 +332,7 @@ impl BoxStore {
         let mut result = Vec::new();
         for row in rows {
             let (config_json, state_json) = db_err!(row)?;
-            let config: BoxConfig = serde_json::from_str(&config_json).map_err(|e| {
+            let config = BoxConfig::from_json(&config_json).map_err(|e| {
                 BoxliteError::Database(format!("Failed to deserialize config: {}", e))
             })?;
             let state: BoxState = serde_json::from_str(&state_json).map_err(|e| {
@@ -337,7 +437,7 @@ fn get_boot_id() -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::litebox::config::ContainerRuntimeConfig;
+    use crate::litebox::config::{BOX_CONFIG_VERSION, ContainerRuntimeConfig};
     use crate::runtime::types::{BoxID, BoxStatus, ContainerID};
     use crate::vmm::VmmKind;
     use boxlite_shared::Transport;
@@ -355,8 +455,10 @@ mod tests {
         use crate::runtime::options::{BoxOptions, RootfsSpec};
         let now = Utc::now();
         BoxConfig {
+            config_version: BOX_CONFIG_VERSION,
             id: BoxID::parse(id).unwrap(),
             name: None,
+            warm_pool: false,
             created_at: now,
             container: ContainerRuntimeConfig {
                 id: ContainerID::new(),
@@ -392,6 +494,40 @@ mod tests {
         assert_eq!(loaded.unwrap().id, config.id);
     }
 
+    #[test]
+    fn test_load_config_upgrades_older_version() {
+        let (store, _dir) = create_test_db();
+        let config = create_test_config(TEST_ID_1);
+        store.save(&config, &BoxState::new()).unwrap();
+
+        // Shape written before versioning, without fields added since
+        let mut old: serde_json::Value = serde_json::to_value(&config).unwrap();
+        old.as_object_mut().unwrap().remove("config_version");
+        let options = old["options"].as_object_mut().unwrap();
+        options.remove("ports");
+        options.remove("kernel_cmdline_extra");
+        store
+            .db
+            .conn()
+            .execute(
+                "UPDATE box_config SET json = ?1 WHERE id = ?2",
+                params![old.to_string(), TEST_ID_1],
+            )
+            .unwrap();
+
+        let loaded = store.load_config(TEST_ID_1).unwrap().unwrap();
+        assert_eq!(loaded.config_version, BOX_CONFIG_VERSION);
+        assert!(loaded.options.ports.is_empty());
+        assert!(loaded.options.kernel_cmdline_extra.is_empty());
+        assert_eq!(loaded.options.cpus, Some(2));
+
+        // A blob from a newer version is reported rather than misread
+        let mut newer = serde_json::to_value(&config).unwrap();
+        newer["config_version"] = (BOX_CONFIG_VERSION + 1).into();
+        let err = BoxConfig::from_json(&newer.to_string()).unwrap_err();
+        assert!(err.to_string().contains("newer"), "{err}");
+    }
+
     #[test]
     fn test_save_and_load_state() {
         let (store, _dir) = create_test_db();
@@ -424,6 +560,27 @@ mod tests {
         assert_eq!(loaded.pid, Some(12345));
     }
 
+    #[test]
+    fn test_update_config() {
+        let (store, _dir) = create_test_db();
+        let mut config = create_test_config(TEST_ID_1);
+        config.name = Some("before".to_string());
+        store.save(&config, &BoxState::new()).unwrap();
+
+        config.name = Some("after".to_string());
+        store.update_config(&config).unwrap();
+
+        let loaded = store.load_config(config.id.as_str()).unwrap().unwrap();
+        assert_eq!(loaded.name.as_deref(), Some("after"));
+
+        // Unknown box is reported as NotFound
+        let missing = create_test_config(TEST_ID_2);
+        assert!(matches!(
+            store.update_config(&missing),
+            Err(BoxliteError::NotFound(_))
+        ));
+    }
+
     #[test]
     fn test_delete() {
         let (store, _dir) = create_test_db();
@@ -453,6 +610,60 @@ mod tests {
         assert_eq!(all.len(), 3);
     }
 
+    #[test]
+    fn test_list_created_between() {
+        let (store, _dir) = create_test_db();
+
+        let base = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        for (id, offset_secs) in [(TEST_ID_1, 0), (TEST_ID_2, 60), (TEST_ID_3, 120)] {
+            let mut config = create_test_config(id);
+            config.created_at = base + chrono::Duration::seconds(offset_secs);
+            store.save(&config, &BoxState::new()).unwrap();
+        }
+
+        let ids = |start: i64, end: i64| -> Vec<String> {
+            store
+                .list_created_between(
+                    base + chrono::Duration::seconds(start),
+                    base + chrono::Duration::seconds(end),
+                )
+                .unwrap()
+                .into_iter()
+                .map(|(config, _)| config.id.to_string())
+                .collect()
+        };
+
+        // Start is inclusive, end is exclusive; newest first
+        assert_eq!(ids(0, 120), vec![TEST_ID_2, TEST_ID_1]);
+        assert_eq!(ids(30, 121), vec![TEST_ID_3, TEST_ID_2]);
+        assert_eq!(ids(61, 120), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_find_by_container_id() {
+        let (store, _dir) = create_test_db();
+
+        let config = create_test_config(TEST_ID_1);
+        store.save(&config, &BoxState::new()).unwrap();
+        store
+            .save(&create_test_config(TEST_ID_2), &BoxState::new())
+            .unwrap();
+
+        let (found, _) = store
+            .find_by_container_id(config.container.id.as_str())
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.id, config.id);
+
+        let unknown = ContainerID::new();
+        assert!(
+            store
+                .find_by_container_id(unknown.as_str())
+                .unwrap()
+                .is_none()
+        );
+    }
+
     #[test]
     fn test_list_active() {
         let (store, _dir) = create_test_db();
@@ -474,6 +685,36 @@ mod tests {
         assert_eq!(active[0].0.id.as_str(), TEST_ID_1);
     }
 
+    #[test]
+    fn test_list_by_status() {
+        let (store, _dir) = create_test_db();
+
+        for (id, status) in [
+            (TEST_ID_1, BoxStatus::Running),
+            (TEST_ID_2, BoxStatus::Stopped),
+            (TEST_ID_3, BoxStatus::Configured),
+        ] {
+            let mut state = BoxState::new();
+            state.set_status(status);
+            store.save(&create_test_config(id), &state).unwrap();
+        }
+
+        let running = store.list_by_status(&[BoxStatus::Running]).unwrap();
+        assert_eq!(running.len(), 1);
+        assert_eq!(running[0].0.id.as_str(), TEST_ID_1);
+
+        let mut ids: Vec<_> = store
+            .list_by_status(&[BoxStatus::Stopped, BoxStatus::Configured])
+            .unwrap()
+            .into_iter()
+            .map(|(config, _)| config.id.as_str().to_string())
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec![TEST_ID_2, TEST_ID_3]);
+
+        assert!(store.list_by_status(&[]).unwrap().is_empty());
+    }
+
     #[test]
     fn test_reboot_detection() {
         let (store, _dir) = create_test_db();
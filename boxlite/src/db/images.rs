@@ -3,6 +3,8 @@
 //! Provides database-backed storage for the image index, replacing the
 //! JSON file-based approach for better reliability and concurrent access.
 
+use std::collections::{HashMap, HashSet};
+
 use rusqlite::{OptionalExtension, params};
 
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
@@ -107,7 +109,6 @@ impl ImageIndexStore {
     }
 
     /// Remove cached image from index.
-    #[allow(dead_code)]
     pub fn remove(&self, reference: &str) -> BoxliteResult<bool> {
         let conn = self.db.conn();
         let rows_affected = db_err!(conn.execute(
@@ -131,6 +132,21 @@ impl ImageIndexStore {
         Ok(self.len()? == 0)
     }
 
+    /// Number of cached images referencing each layer digest.
+    ///
+    /// Layers are stored once per digest and shared between images; a layer
+    /// missing from the map is referenced by no image and may be deleted.
+    pub fn layer_refcounts(&self) -> BoxliteResult<HashMap<String, usize>> {
+        let mut refcounts = HashMap::new();
+        for (_, image) in self.list_all()? {
+            let unique: HashSet<String> = image.layers.into_iter().collect();
+            for digest in unique {
+                *refcounts.entry(digest).or_insert(0) += 1;
+            }
+        }
+        Ok(refcounts)
+    }
+
     /// List all cached images.
     pub fn list_all(&self) -> BoxliteResult<Vec<(String, CachedImage)>> {
         let conn = self.db.conn();
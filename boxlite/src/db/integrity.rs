@@ -0,0 +1,238 @@
+//! Database integrity check and repair.
+//!
+//! A partially written row or a schema change can leave a box row whose JSON
+//! no longer deserializes, which would otherwise fail every box listing and
+//! wedge startup recovery. The check reports such rows; repair moves them to
+//! `box_config_quarantine` so the rest of the database stays usable. Rows
+//! written by a newer boxlite are only reported: they are not corrupt, and
+//! the newer build can still load them.
+
+use chrono::Utc;
+use rusqlite::params;
+
+use crate::litebox::config::{BoxConfig, newer_config_version};
+use crate::runtime::types::BoxState;
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+
+use super::{Database, db_err, schema};
+
+/// Outcome of [`Database::check_integrity`] / [`Database::repair`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// Problems reported by `PRAGMA integrity_check` (empty when healthy)
+    pub sqlite_errors: Vec<String>,
+    /// Box rows that could not be loaded
+    pub corrupt_boxes: Vec<CorruptBox>,
+    /// Box rows written by a newer boxlite; never quarantined
+    pub newer_boxes: Vec<CorruptBox>,
+    /// Number of box rows moved to quarantine (repair only)
+    pub quarantined: usize,
+}
+
+impl IntegrityReport {
+    /// Whether no problems were found.
+    pub fn is_ok(&self) -> bool {
+        self.sqlite_errors.is_empty()
+            && self.corrupt_boxes.is_empty()
+            && self.newer_boxes.is_empty()
+    }
+}
+
+/// A box row that failed to load.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorruptBox {
+    pub id: String,
+    pub reason: String,
+}
+
+impl Database {
+    /// Check the SQLite file and every box row.
+    ///
+    /// Runs `PRAGMA integrity_check` and verifies each `box_config` row (and
+    /// its `box_state` row) deserializes. Nothing is modified.
+    pub fn check_integrity(&self) -> BoxliteResult<IntegrityReport> {
+        let conn = self.conn();
+
+        let mut stmt = db_err!(conn.prepare("PRAGMA integrity_check"))?;
+        let sqlite_errors = db_err!(
+            db_err!(stmt.query_map([], |row| row.get::<_, String>(0)))?
+                .collect::<Result<Vec<_>, _>>()
+        )?
+        .into_iter()
+        .filter(|line| line != "ok")
+        .collect();
+
+        let mut stmt = db_err!(conn.prepare(
+            "SELECT c.id, c.json, s.json FROM box_config c
+             LEFT JOIN box_state s ON s.id = c.id"
+        ))?;
+        let rows = db_err!(
+            db_err!(stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                ))
+            }))?
+            .collect::<Result<Vec<_>, _>>()
+        )?;
+
+        let mut corrupt_boxes = Vec::new();
+        let mut newer_boxes = Vec::new();
+        for (id, config_json, state_json) in rows {
+            if let Some(version) = newer_config_version(&config_json) {
+                newer_boxes.push(CorruptBox {
+                    id,
+                    reason: format!("written with newer config version {}", version),
+                });
+                continue;
+            }
+            let reason = if let Err(e) = BoxConfig::from_json(&config_json) {
+                format!("invalid config: {}", e)
+            } else {
+                match state_json.map(|j| serde_json::from_str::<BoxState>(&j)) {
+                    None => "missing state row".to_string(),
+                    Some(Err(e)) => format!("invalid state: {}", e),
+                    Some(Ok(_)) => continue,
+                }
+            };
+            corrupt_boxes.push(CorruptBox { id, reason });
+        }
+
+        Ok(IntegrityReport {
+            sqlite_errors,
+            corrupt_boxes,
+            newer_boxes,
+            quarantined: 0,
+        })
+    }
+
+    /// Check integrity and quarantine box rows that fail to load.
+    ///
+    /// Corrupt rows are copied to `box_config_quarantine` (with the reason)
+    /// and removed from `box_config`/`box_state` in one transaction. Rows of
+    /// a newer version and file-level damage reported by SQLite are returned
+    /// but left in place.
+    pub fn repair(&self) -> BoxliteResult<IntegrityReport> {
+        let mut report = self.check_integrity()?;
+        if report.corrupt_boxes.is_empty() {
+            return Ok(report);
+        }
+
+        let mut conn = self.conn();
+        // Databases created before the quarantine table existed
+        db_err!(conn.execute_batch(schema::BOX_CONFIG_QUARANTINE_TABLE))?;
+
+        let tx = db_err!(conn.transaction())?;
+        let now = Utc::now().timestamp();
+        for corrupt in &report.corrupt_boxes {
+            db_err!(tx.execute(
+                "INSERT OR REPLACE INTO box_config_quarantine
+                     (id, name, config_json, state_json, reason, quarantined_at)
+                 SELECT c.id, c.name, c.json, s.json, ?2, ?3 FROM box_config c
+                 LEFT JOIN box_state s ON s.id = c.id WHERE c.id = ?1",
+                params![corrupt.id, corrupt.reason, now],
+            ))?;
+            db_err!(tx.execute("DELETE FROM box_state WHERE id = ?1", params![corrupt.id]))?;
+            db_err!(tx.execute("DELETE FROM box_config WHERE id = ?1", params![corrupt.id]))?;
+            tracing::warn!(
+                box_id = %corrupt.id,
+                reason = %corrupt.reason,
+                "Quarantined corrupt box row"
+            );
+        }
+        db_err!(tx.commit())?;
+
+        report.quarantined = report.corrupt_boxes.len();
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn insert_box(db: &Database, id: &str, config_json: &str, state_json: &str) {
+        let conn = db.conn();
+        conn.execute(
+            "INSERT INTO box_config (id, name, created_at, json) VALUES (?1, NULL, 0, ?2)",
+            params![id, config_json],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO box_state (id, status, pid, json) VALUES (?1, 'stopped', NULL, ?2)",
+            params![id, state_json],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_healthy_database_passes() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(&temp_dir.path().join("test.db")).unwrap();
+
+        let report = db.check_integrity().unwrap();
+        assert!(report.is_ok(), "{:?}", report);
+    }
+
+    #[test]
+    fn test_malformed_row_is_flagged_and_quarantined() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(&temp_dir.path().join("test.db")).unwrap();
+        insert_box(&db, "broken", "{\"id\": \"broken\", \"truncat", "{}");
+
+        let report = db.check_integrity().unwrap();
+        assert!(!report.is_ok());
+        assert_eq!(report.corrupt_boxes.len(), 1);
+        assert_eq!(report.corrupt_boxes[0].id, "broken");
+        assert!(report.corrupt_boxes[0].reason.starts_with("invalid config"));
+
+        let report = db.repair().unwrap();
+        assert_eq!(report.quarantined, 1);
+
+        let conn = db.conn();
+        let remaining: i64 = conn
+            .query_row("SELECT COUNT(*) FROM box_config", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(remaining, 0);
+        let (reason, config_json): (String, String) = conn
+            .query_row(
+                "SELECT reason, config_json FROM box_config_quarantine WHERE id = 'broken'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert!(reason.starts_with("invalid config"));
+        assert!(config_json.contains("truncat"));
+        drop(conn);
+
+        assert!(db.check_integrity().unwrap().is_ok());
+    }
+
+    #[test]
+    fn test_newer_version_row_is_reported_not_quarantined() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(&temp_dir.path().join("test.db")).unwrap();
+        insert_box(
+            &db,
+            "future",
+            "{\"config_version\": 99, \"id\": \"future\"}",
+            "{}",
+        );
+
+        let report = db.repair().unwrap();
+        assert!(!report.is_ok());
+        assert!(report.corrupt_boxes.is_empty());
+        assert_eq!(report.quarantined, 0);
+        assert_eq!(report.newer_boxes.len(), 1);
+        assert_eq!(report.newer_boxes[0].id, "future");
+        assert!(report.newer_boxes[0].reason.contains("99"));
+
+        let remaining: i64 = db
+            .conn()
+            .query_row("SELECT COUNT(*) FROM box_config", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(remaining, 1);
+    }
+}
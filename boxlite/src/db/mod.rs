@@ -8,6 +8,7 @@
 
 mod boxes;
 mod images;
+mod integrity;
 mod schema;
 
 use std::path::Path;
@@ -19,6 +20,8 @@ use rusqlite::{Connection, OptionalExtension};
 
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
+use crate::runtime::options::DatabaseOptions;
+
 pub use boxes::BoxStore;
 pub use images::{CachedImage, ImageIndexStore};
 
@@ -41,33 +44,59 @@ pub struct Database {
 }
 
 impl Database {
-    /// Open or create the database.
+    /// Open or create the database with default options.
+    #[cfg(test)]
     pub fn open(db_path: &Path) -> BoxliteResult<Self> {
+        Self::open_with(db_path, &DatabaseOptions::default())
+    }
+
+    /// Open or create the database.
+    pub fn open_with(db_path: &Path, options: &DatabaseOptions) -> BoxliteResult<Self> {
         if let Some(parent) = db_path.parent() {
             std::fs::create_dir_all(parent)?;
         }
 
         let conn = db_err!(Connection::open(db_path))?;
+        Self::configure_connection(&conn, options)?;
+        Self::init_schema(&conn)?;
+
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Apply per-connection settings. Must run on every new connection.
+    ///
+    /// SQLite configuration (matches Podman patterns):
+    /// - WAL mode (optional): Better concurrent read performance
+    /// - FULL sync: Maximum durability (fsync after each transaction)
+    /// - Foreign keys: Referential integrity
+    /// - Busy timeout: wait for competing writers instead of failing
+    ///   immediately with "database is locked"
+    fn configure_connection(conn: &Connection, options: &DatabaseOptions) -> BoxliteResult<()> {
+        let journal_mode = if options.wal { "WAL" } else { "DELETE" };
+        // journal_mode returns the resulting mode as a row
+        let applied: String = db_err!(conn.query_row(
+            &format!("PRAGMA journal_mode={}", journal_mode),
+            [],
+            |row| row.get(0),
+        ))?;
+        if !applied.eq_ignore_ascii_case(journal_mode) {
+            tracing::warn!(
+                requested = journal_mode,
+                applied = %applied,
+                "SQLite did not apply the requested journal mode"
+            );
+        }
 
-        // SQLite configuration (matches Podman patterns)
-        // - WAL mode: Better concurrent read performance
-        // - FULL sync: Maximum durability (fsync after each transaction)
-        // - Foreign keys: Referential integrity
-        // - Busy timeout: 100s to handle long operations (Podman uses 100s)
+        db_err!(conn.busy_timeout(options.busy_timeout))?;
         db_err!(conn.execute_batch(
             "
-            PRAGMA journal_mode=WAL;
             PRAGMA synchronous=FULL;
             PRAGMA foreign_keys=ON;
-            PRAGMA busy_timeout=100000;
             "
         ))?;
-
-        Self::init_schema(&conn)?;
-
-        Ok(Self {
-            conn: Arc::new(Mutex::new(conn)),
-        })
+        Ok(())
     }
 
     /// Acquire the database connection.
@@ -175,6 +204,21 @@ impl Database {
             current = 4;
         }
 
+        // Migration 4 -> 5: Add container_id column
+        if current == 4 {
+            tracing::info!("Running migration 4 -> 5: Adding container_id column to box_config");
+
+            db_err!(conn.execute_batch("ALTER TABLE box_config ADD COLUMN container_id TEXT;"))?;
+            db_err!(conn.execute_batch(
+                "UPDATE box_config SET container_id = json_extract(json, '$.container.id');"
+            ))?;
+            db_err!(conn.execute_batch(
+                "CREATE INDEX IF NOT EXISTS idx_box_config_container_id ON box_config(container_id);"
+            ))?;
+
+            current = 5;
+        }
+
         // Update schema version
         let now = Utc::now().to_rfc3339();
         db_err!(conn.execute(
@@ -198,4 +242,41 @@ mod tests {
         let db_path = temp_dir.path().join("test.db");
         let _db = Database::open(&db_path).unwrap();
     }
+
+    fn pragma<T: rusqlite::types::FromSql>(db: &Database, name: &str) -> T {
+        db.conn()
+            .query_row(&format!("PRAGMA {}", name), [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_db_open_applies_wal_and_busy_timeout() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open_with(
+            &temp_dir.path().join("test.db"),
+            &DatabaseOptions {
+                wal: true,
+                busy_timeout: std::time::Duration::from_secs(5),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(pragma::<String>(&db, "journal_mode"), "wal");
+        assert_eq!(pragma::<i64>(&db, "busy_timeout"), 5000);
+    }
+
+    #[test]
+    fn test_db_open_without_wal() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open_with(
+            &temp_dir.path().join("test.db"),
+            &DatabaseOptions {
+                wal: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(pragma::<String>(&db, "journal_mode"), "delete");
+    }
 }
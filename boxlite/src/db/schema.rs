@@ -7,7 +7,7 @@
 //! Each table has queryable columns for efficient filtering + JSON blob for full data.
 
 /// Current schema version.
-pub const SCHEMA_VERSION: i32 = 4;
+pub const SCHEMA_VERSION: i32 = 5;
 
 /// Schema version tracking table.
 pub const SCHEMA_VERSION_TABLE: &str = r#"
@@ -21,18 +21,21 @@ CREATE TABLE IF NOT EXISTS schema_version (
 /// BoxConfig table schema.
 ///
 /// Stores immutable box configuration. JSON blob contains full BoxConfig struct.
-/// Queryable columns: id, name, created_at (for sorting/filtering).
+/// Queryable columns: id, name, created_at (for sorting/filtering),
+/// container_id (for resolving guest-side container IDs back to a box).
 /// Name is UNIQUE but allows NULL (multiple unnamed boxes are allowed).
 pub const BOX_CONFIG_TABLE: &str = r#"
 CREATE TABLE IF NOT EXISTS box_config (
     id TEXT PRIMARY KEY NOT NULL,
     name TEXT UNIQUE,
     created_at INTEGER NOT NULL,
-    json TEXT NOT NULL
+    json TEXT NOT NULL,
+    container_id TEXT
 );
 
 CREATE INDEX IF NOT EXISTS idx_box_config_created_at ON box_config(created_at);
 CREATE INDEX IF NOT EXISTS idx_box_config_name ON box_config(name);
+CREATE INDEX IF NOT EXISTS idx_box_config_container_id ON box_config(container_id);
 "#;
 
 /// BoxState table schema.
@@ -52,6 +55,21 @@ CREATE INDEX IF NOT EXISTS idx_box_state_status ON box_state(status);
 CREATE INDEX IF NOT EXISTS idx_box_state_pid ON box_state(pid);
 "#;
 
+/// Quarantine for box rows that failed to deserialize.
+///
+/// Filled by `Database::repair`; keeps the raw JSON for manual inspection.
+/// Not referenced by any other table.
+pub const BOX_CONFIG_QUARANTINE_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS box_config_quarantine (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT,
+    config_json TEXT NOT NULL,
+    state_json TEXT,
+    reason TEXT NOT NULL,
+    quarantined_at INTEGER NOT NULL
+);
+"#;
+
 /// Alive file table schema.
 ///
 /// Tracks runtime instance. If stale on startup, indicates reboot.
@@ -86,6 +104,7 @@ pub fn all_schemas() -> Vec<&'static str> {
         SCHEMA_VERSION_TABLE,
         BOX_CONFIG_TABLE,
         BOX_STATE_TABLE,
+        BOX_CONFIG_QUARANTINE_TABLE,
         ALIVE_TABLE,
         IMAGE_INDEX_TABLE,
     ]
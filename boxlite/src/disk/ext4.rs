@@ -1,5 +1,6 @@
 use crate::util;
 use boxlite_shared::{BoxliteError, BoxliteResult};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use walkdir::WalkDir;
@@ -254,3 +255,186 @@ fn fix_ownership_with_debugfs(image_path: &Path, source_dir: &Path) -> BoxliteRe
 
     Ok(())
 }
+
+/// Copy the contents of an ext4 image into the existing directory `dest`.
+///
+/// Uses `debugfs rdump`, which keeps modes and timestamps but only keeps
+/// ownership when running as root; see `ext4_owners`. `lost+found` is
+/// skipped.
+pub(crate) fn dump_ext4(image_path: &Path, dest: &Path) -> BoxliteResult<()> {
+    let listing = run_debugfs(image_path, "ls -p /")?;
+    let entries: Vec<&str> = listing
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix('/')?.split('/').nth(4))
+        .filter(|name| !matches!(*name, "" | "." | ".." | "lost+found"))
+        .collect();
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let dest_str = dest
+        .to_str()
+        .ok_or_else(|| BoxliteError::Storage(format!("Invalid dump path: {}", dest.display())))?;
+    let mut request = String::from("rdump");
+    for name in &entries {
+        request.push_str(&format!(" \"/{}\"", name));
+    }
+    request.push_str(&format!(" \"{}\"", dest_str));
+    run_debugfs(image_path, &request)?;
+
+    tracing::debug!(
+        "Dumped {} top-level entries of {} to {}",
+        entries.len(),
+        image_path.display(),
+        dest.display()
+    );
+    Ok(())
+}
+
+/// Owner (uid, gid) of every entry of an ext4 image, keyed by its path
+/// relative to the image root.
+///
+/// `dump_ext4` loses ownership when not running as root; this reads it back
+/// from the inodes. Each directory level is listed in one debugfs run.
+pub(crate) fn ext4_owners(image_path: &Path) -> BoxliteResult<HashMap<PathBuf, (u32, u32)>> {
+    let mut owners = HashMap::new();
+    let mut level = vec![PathBuf::new()];
+    while !level.is_empty() {
+        let mut script = String::new();
+        for dir in &level {
+            script.push_str(&format!("ls -p \"/{}\"\n", dir.display()));
+        }
+        let output = run_debugfs_script(image_path, &script)?;
+
+        // Every listing follows the echo of its request
+        let mut listings = Vec::new();
+        for line in output.lines() {
+            if line.starts_with("debugfs: ") {
+                listings.push(Vec::new());
+            } else if let Some(listing) = listings.last_mut() {
+                listing.push(line);
+            }
+        }
+
+        let mut next = Vec::new();
+        for (dir, listing) in level.iter().zip(listings) {
+            for entry in listing.into_iter().filter_map(parse_ls_entry) {
+                if dir.as_os_str().is_empty() && entry.name == "lost+found" {
+                    continue;
+                }
+                let path = dir.join(entry.name);
+                if entry.is_dir {
+                    next.push(path.clone());
+                }
+                owners.insert(path, (entry.uid, entry.gid));
+            }
+        }
+        level = next;
+    }
+    Ok(owners)
+}
+
+/// One line of `debugfs ls -p`: `/inode/mode/uid/gid/name/size/`.
+struct LsEntry<'a> {
+    name: &'a str,
+    is_dir: bool,
+    uid: u32,
+    gid: u32,
+}
+
+fn parse_ls_entry(line: &str) -> Option<LsEntry<'_>> {
+    let mut fields = line.trim().strip_prefix('/')?.split('/');
+    let _inode = fields.next()?;
+    let mode = u32::from_str_radix(fields.next()?, 8).ok()?;
+    let uid = fields.next()?.parse().ok()?;
+    let gid = fields.next()?.parse().ok()?;
+    let name = fields.next()?;
+    if matches!(name, "" | "." | "..") {
+        return None;
+    }
+    Some(LsEntry {
+        name,
+        is_dir: mode & 0o170000 == 0o040000,
+        uid,
+        gid,
+    })
+}
+
+/// Run read-only debugfs requests, one per line, and return their stdout.
+///
+/// debugfs echoes each request as a `debugfs: ` line before its output.
+fn run_debugfs_script(image_path: &Path, script: &str) -> BoxliteResult<String> {
+    use std::io::Write;
+
+    let debugfs = get_debugfs_path();
+    let mut child = Command::new(&debugfs)
+        .args(["-f", "-"])
+        .arg(image_path)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| BoxliteError::Storage(format!("Failed to spawn debugfs: {}", e)))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(script.as_bytes()).map_err(|e| {
+            BoxliteError::Storage(format!("Failed to write to debugfs stdin: {}", e))
+        })?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| BoxliteError::Storage(format!("Failed to wait for debugfs: {}", e)))?;
+    if !output.status.success() {
+        return Err(BoxliteError::Storage(format!(
+            "debugfs on {} failed with exit code {:?}: {}",
+            image_path.display(),
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Run a single read-only debugfs request and return its stdout.
+fn run_debugfs(image_path: &Path, request: &str) -> BoxliteResult<String> {
+    let debugfs = get_debugfs_path();
+    let output = Command::new(&debugfs)
+        .arg("-R")
+        .arg(request)
+        .arg(image_path)
+        .output()
+        .map_err(|e| BoxliteError::Storage(format!("Failed to run debugfs: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(BoxliteError::Storage(format!(
+            "debugfs '{}' on {} failed with exit code {:?}: {}",
+            request,
+            image_path.display(),
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ls_entry() {
+        let entry = parse_ls_entry("/14/100644/1000/100/app.log/3/").unwrap();
+        assert_eq!(entry.name, "app.log");
+        assert!(!entry.is_dir);
+        assert_eq!((entry.uid, entry.gid), (1000, 100));
+
+        let entry = parse_ls_entry(" /13/040755/0/0/sub dir//").unwrap();
+        assert_eq!(entry.name, "sub dir");
+        assert!(entry.is_dir);
+
+        assert!(parse_ls_entry("/2/040755/0/0/.//").is_none());
+        assert!(parse_ls_entry("/2/040755/0/0/..//").is_none());
+        assert!(parse_ls_entry("debugfs 1.47.0 (5-Feb-2023)").is_none());
+    }
+}
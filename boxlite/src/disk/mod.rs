@@ -5,12 +5,16 @@
 //! - `DiskFormat` - Disk format types (Ext4, Qcow2)
 //! - `create_ext4_from_dir` - Create ext4 filesystem from directory
 //! - `Qcow2Helper` - QCOW2 copy-on-write disk creation
+//! - `DiskSnapshots` - Named copies of a box's writable disk
 
 pub mod constants;
 pub(crate) mod ext4;
 mod image;
 mod qcow2;
+mod snapshot;
 
 pub use ext4::create_ext4_from_dir;
 pub use image::{Disk, DiskFormat};
 pub use qcow2::{BackingFormat, Qcow2Helper};
+pub use snapshot::DiskSnapshot;
+pub(crate) use snapshot::DiskSnapshots;
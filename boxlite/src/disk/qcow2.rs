@@ -13,6 +13,111 @@ use qcow2_rs::meta::Qcow2Header;
 use super::constants::qcow2::{BLOCK_SIZE, CLUSTER_BITS, DEFAULT_DISK_SIZE_GB, REFCOUNT_ORDER};
 use super::{Disk, DiskFormat};
 
+/// qcow2 header magic (`QFI\xfb`).
+const QCOW2_MAGIC: u32 = 0x514649fb;
+/// Header extension type carrying the backing file format.
+const QCOW2_EXT_BACKING_FORMAT: u32 = 0xE2792ACA;
+/// Host offset bits of an L1/L2 table entry.
+const QCOW2_OFFSET_MASK: u64 = 0x00ff_ffff_ffff_fe00;
+/// L2 entry flag: cluster is compressed.
+const QCOW2_COMPRESSED: u64 = 1 << 62;
+/// L2 entry flag: cluster reads as zeros.
+const QCOW2_ZERO: u64 = 1;
+/// L1/L2 entry flag: the referenced cluster has a refcount of exactly one.
+const QCOW2_COPIED: u64 = 1 << 63;
+/// Incompatible feature bit: refcounts may be inconsistent.
+const QCOW2_INCOMPAT_DIRTY: u64 = 1;
+/// Valid `cluster_bits` values (512 B to 2 MiB clusters).
+const QCOW2_CLUSTER_BITS_RANGE: std::ops::RangeInclusive<u32> = 9..=21;
+
+/// Cluster size for a header's `cluster_bits`, rejecting values outside the
+/// range the format allows.
+fn qcow2_cluster_size(cluster_bits: u32, path: &Path) -> BoxliteResult<u64> {
+    if !QCOW2_CLUSTER_BITS_RANGE.contains(&cluster_bits) {
+        return Err(BoxliteError::Storage(format!(
+            "Invalid qcow2 cluster_bits {} in {}",
+            cluster_bits,
+            path.display()
+        )));
+    }
+    Ok(1u64 << cluster_bits)
+}
+
+/// Where a guest cluster's data lives in a compacted image.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ClusterMapping {
+    /// Not allocated: reads from the backing file, or zeros.
+    Unallocated,
+    /// Reads as zeros, hiding the backing file.
+    Zero,
+    /// Data to copy from this host offset of the original image.
+    Data(u64),
+}
+
+/// Cluster layout of a compacted image.
+///
+/// Clusters are laid out as: header, L1 table, L2 tables, data, refcount
+/// table, refcount blocks.
+struct CompactLayout {
+    version: u32,
+    cluster_size: u64,
+    l1_clusters: u64,
+    l2_clusters: u64,
+    data_clusters: u64,
+    rt_clusters: u64,
+    rb_clusters: u64,
+}
+
+impl CompactLayout {
+    fn new(
+        version: u32,
+        cluster_size: u64,
+        l1_clusters: u64,
+        l2_clusters: u64,
+        data_clusters: u64,
+    ) -> Self {
+        let mut layout = Self {
+            version,
+            cluster_size,
+            l1_clusters,
+            l2_clusters,
+            data_clusters,
+            rt_clusters: 0,
+            rb_clusters: 0,
+        };
+        // Refcount blocks also count themselves, so grow until stable
+        let refcounts_per_block = cluster_size / 2; // 16-bit refcounts
+        loop {
+            let blocks = layout.total_clusters().div_ceil(refcounts_per_block);
+            let table = (blocks * 8).div_ceil(cluster_size);
+            if (table, blocks) == (layout.rt_clusters, layout.rb_clusters) {
+                return layout;
+            }
+            (layout.rt_clusters, layout.rb_clusters) = (table, blocks);
+        }
+    }
+
+    fn l1_start(&self) -> u64 {
+        1
+    }
+
+    fn l2_start(&self) -> u64 {
+        self.l1_start() + self.l1_clusters
+    }
+
+    fn data_start(&self) -> u64 {
+        self.l2_start() + self.l2_clusters
+    }
+
+    fn refcount_start(&self) -> u64 {
+        self.data_start() + self.data_clusters
+    }
+
+    fn total_clusters(&self) -> u64 {
+        self.refcount_start() + self.rt_clusters + self.rb_clusters
+    }
+}
+
 /// Parsed qcow2 header information.
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -247,12 +352,40 @@ impl Qcow2Helper {
     }
 
     /// Get the virtual size of a qcow2 disk image.
-    #[allow(dead_code)]
     pub fn qcow2_virtual_size(path: &Path) -> BoxliteResult<u64> {
         let header = Self::read_qcow2_header(path)?;
         Ok(header.size)
     }
 
+    /// Backing file referenced by a qcow2 image, if any.
+    pub fn backing_file(path: &Path) -> BoxliteResult<Option<std::path::PathBuf>> {
+        use std::os::unix::fs::FileExt;
+
+        let storage_err = |e: std::io::Error| {
+            BoxliteError::Storage(format!("Failed to read {}: {}", path.display(), e))
+        };
+        let file = std::fs::File::open(path).map_err(storage_err)?;
+        let mut header = [0u8; 20];
+        file.read_exact_at(&mut header, 0).map_err(storage_err)?;
+        if u32::from_be_bytes(header[0..4].try_into().unwrap()) != QCOW2_MAGIC {
+            return Err(BoxliteError::Storage(format!(
+                "{} is not a qcow2 image",
+                path.display()
+            )));
+        }
+
+        let offset = u64::from_be_bytes(header[8..16].try_into().unwrap());
+        let len = u32::from_be_bytes(header[16..20].try_into().unwrap());
+        if offset == 0 || len == 0 {
+            return Ok(None);
+        }
+        let mut name = vec![0u8; len as usize];
+        file.read_exact_at(&mut name, offset).map_err(storage_err)?;
+        Ok(Some(std::path::PathBuf::from(
+            String::from_utf8_lossy(&name).into_owned(),
+        )))
+    }
+
     /// Read qcow2 header from disk file.
     #[allow(dead_code)]
     fn read_qcow2_header(path: &Path) -> BoxliteResult<Qcow2HeaderInfo> {
@@ -497,6 +630,396 @@ impl Qcow2Helper {
         Ok(())
     }
 
+    /// Flatten a disk image into a standalone raw image at `dst`.
+    ///
+    /// For a qcow2 image, clusters allocated in the image are merged with the
+    /// contents of its raw backing file, so the result no longer depends on
+    /// the shared base disk. A file that is not qcow2 is copied as-is.
+    /// Unallocated regions are left as holes. Returns the output size in bytes.
+    pub fn flatten_to_raw(src: &Path, dst: &Path) -> BoxliteResult<u64> {
+        use std::fs::File;
+        use std::os::unix::fs::FileExt;
+
+        let storage_err = |path: &Path, e: std::io::Error| {
+            BoxliteError::Storage(format!("Failed to flatten {}: {}", path.display(), e))
+        };
+
+        let image = File::open(src).map_err(|e| storage_err(src, e))?;
+        let mut header = [0u8; 104];
+        let header_len = image
+            .read_at(&mut header, 0)
+            .map_err(|e| storage_err(src, e))?;
+        if header_len < 72 || header[0..4] != QCOW2_MAGIC.to_be_bytes() {
+            return std::fs::copy(src, dst).map_err(|e| storage_err(dst, e));
+        }
+
+        let be_u32 = |at: usize| u32::from_be_bytes(header[at..at + 4].try_into().unwrap());
+        let be_u64 = |at: usize| u64::from_be_bytes(header[at..at + 8].try_into().unwrap());
+        let version = be_u32(4);
+        let backing_offset = be_u64(8);
+        let backing_len = be_u32(16) as usize;
+        let cluster_size = qcow2_cluster_size(be_u32(20), src)?;
+        let virtual_size = be_u64(24);
+        let l1_size = be_u32(36) as usize;
+        let l1_offset = be_u64(40);
+
+        if be_u32(32) != 0 {
+            return Err(BoxliteError::Unsupported(format!(
+                "Cannot flatten encrypted qcow2 image {}",
+                src.display()
+            )));
+        }
+
+        let backing = if backing_offset != 0 && backing_len > 0 {
+            let mut name = vec![0u8; backing_len];
+            image
+                .read_exact_at(&mut name, backing_offset)
+                .map_err(|e| storage_err(src, e))?;
+            let format = Self::read_backing_format(&image, version, &header)
+                .map_err(|e| storage_err(src, e))?;
+            if format
+                .as_deref()
+                .is_some_and(|f| f != BackingFormat::Raw.as_str())
+            {
+                return Err(BoxliteError::Unsupported(format!(
+                    "Cannot flatten {}: only raw backing files are supported",
+                    src.display()
+                )));
+            }
+            let path = std::path::PathBuf::from(String::from_utf8_lossy(&name).into_owned());
+            let file = File::open(&path).map_err(|e| storage_err(&path, e))?;
+            let len = file.metadata().map_err(|e| storage_err(&path, e))?.len();
+            Some((file, len))
+        } else {
+            None
+        };
+
+        let out = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(dst)
+            .map_err(|e| storage_err(dst, e))?;
+        out.set_len(virtual_size).map_err(|e| storage_err(dst, e))?;
+
+        let mut l1 = vec![0u8; l1_size * 8];
+        image
+            .read_exact_at(&mut l1, l1_offset)
+            .map_err(|e| storage_err(src, e))?;
+        let l2_entries = (cluster_size / 8) as usize;
+        let mut l2 = vec![0u8; cluster_size as usize];
+        let mut cluster = vec![0u8; cluster_size as usize];
+
+        for (l1_index, l1_entry) in l1.chunks_exact(8).enumerate() {
+            let l2_offset = u64::from_be_bytes(l1_entry.try_into().unwrap()) & QCOW2_OFFSET_MASK;
+            if l2_offset == 0 {
+                l2.fill(0);
+            } else {
+                image
+                    .read_exact_at(&mut l2, l2_offset)
+                    .map_err(|e| storage_err(src, e))?;
+            }
+
+            for (l2_index, l2_entry) in l2.chunks_exact(8).enumerate() {
+                let guest_offset = (l1_index * l2_entries + l2_index) as u64 * cluster_size;
+                if guest_offset >= virtual_size {
+                    break;
+                }
+                let len = cluster_size.min(virtual_size - guest_offset) as usize;
+                let entry = u64::from_be_bytes(l2_entry.try_into().unwrap());
+                let host_offset = entry & QCOW2_OFFSET_MASK;
+
+                let data = &mut cluster[..len];
+                if entry & QCOW2_COMPRESSED != 0 {
+                    return Err(BoxliteError::Unsupported(format!(
+                        "Cannot flatten {}: compressed clusters are not supported",
+                        src.display()
+                    )));
+                } else if entry & QCOW2_ZERO != 0 {
+                    continue;
+                } else if host_offset != 0 {
+                    image
+                        .read_exact_at(data, host_offset)
+                        .map_err(|e| storage_err(src, e))?;
+                } else if let Some((file, backing_size)) = &backing
+                    && guest_offset < *backing_size
+                {
+                    let available = (*backing_size - guest_offset).min(len as u64) as usize;
+                    data.fill(0);
+                    file.read_exact_at(&mut data[..available], guest_offset)
+                        .map_err(|e| storage_err(src, e))?;
+                } else {
+                    continue;
+                }
+
+                // Keep the output sparse
+                if data.iter().any(|&b| b != 0) {
+                    out.write_all_at(data, guest_offset)
+                        .map_err(|e| storage_err(dst, e))?;
+                }
+            }
+        }
+
+        out.sync_all().map_err(|e| storage_err(dst, e))?;
+        Ok(virtual_size)
+    }
+
+    /// Rewrite a qcow2 image in place without its unused clusters.
+    ///
+    /// Writes the header (backing file reference included), the clusters
+    /// still mapped by the L2 tables and fresh L1/L2/refcount tables to a
+    /// new file next to `path`, then renames it over the original. Mapped
+    /// clusters that only hold zeros are dropped, or turned into zero
+    /// clusters where they hide backing file data. Returns the number of
+    /// bytes reclaimed.
+    ///
+    /// The image must not be in use. Images with snapshots, encryption,
+    /// compressed clusters or unknown incompatible features are rejected
+    /// with `Unsupported`.
+    pub fn compact(path: &Path) -> BoxliteResult<u64> {
+        use std::fs::File;
+        use std::os::unix::fs::FileExt;
+
+        let storage_err = |path: &Path, e: std::io::Error| {
+            BoxliteError::Storage(format!("Failed to compact {}: {}", path.display(), e))
+        };
+        let unsupported = |reason: &str| {
+            BoxliteError::Unsupported(format!("Cannot compact {}: {}", path.display(), reason))
+        };
+
+        let image = File::open(path).map_err(|e| storage_err(path, e))?;
+        let old_size = image.metadata().map_err(|e| storage_err(path, e))?.len();
+        let mut header = [0u8; 104];
+        let header_len = image
+            .read_at(&mut header, 0)
+            .map_err(|e| storage_err(path, e))?;
+        if header_len < 72 || header[0..4] != QCOW2_MAGIC.to_be_bytes() {
+            return Err(BoxliteError::Storage(format!(
+                "Cannot compact {}: not a qcow2 image",
+                path.display()
+            )));
+        }
+
+        let be_u32 = |at: usize| u32::from_be_bytes(header[at..at + 4].try_into().unwrap());
+        let be_u64 = |at: usize| u64::from_be_bytes(header[at..at + 8].try_into().unwrap());
+        let version = be_u32(4);
+        let backing_offset = be_u64(8);
+        let backing_len = be_u32(16) as u64;
+        let cluster_size = qcow2_cluster_size(be_u32(20), path)?;
+        let l1_size = be_u32(36) as usize;
+        let l1_offset = be_u64(40);
+
+        if be_u32(32) != 0 {
+            return Err(unsupported("encrypted images are not supported"));
+        }
+        if be_u32(60) != 0 {
+            return Err(unsupported("images with snapshots are not supported"));
+        }
+        // Refcounts are rebuilt from scratch, so only the dirty bit is safe to drop
+        if version >= 3 && be_u64(72) & !QCOW2_INCOMPAT_DIRTY != 0 {
+            return Err(unsupported("unknown incompatible features"));
+        }
+        // The header cluster is copied verbatim and must hold the backing name
+        if backing_offset + backing_len > cluster_size {
+            return Err(unsupported("backing file name outside the header cluster"));
+        }
+        let has_backing = backing_offset != 0 && backing_len > 0;
+
+        // Pass 1: decide what every guest cluster maps to
+        let mut l1 = vec![0u8; l1_size * 8];
+        image
+            .read_exact_at(&mut l1, l1_offset)
+            .map_err(|e| storage_err(path, e))?;
+        let l2_entries = (cluster_size / 8) as usize;
+        let mut l2 = vec![0u8; cluster_size as usize];
+        let mut cluster = vec![0u8; cluster_size as usize];
+        let mut tables: Vec<(usize, Vec<ClusterMapping>)> = Vec::new();
+        let mut data_clusters = 0u64;
+
+        for (l1_index, l1_entry) in l1.chunks_exact(8).enumerate() {
+            let l2_offset = u64::from_be_bytes(l1_entry.try_into().unwrap()) & QCOW2_OFFSET_MASK;
+            if l2_offset == 0 {
+                continue;
+            }
+            image
+                .read_exact_at(&mut l2, l2_offset)
+                .map_err(|e| storage_err(path, e))?;
+
+            let mut mappings = vec![ClusterMapping::Unallocated; l2_entries];
+            for (l2_index, l2_entry) in l2.chunks_exact(8).enumerate() {
+                let entry = u64::from_be_bytes(l2_entry.try_into().unwrap());
+                let host_offset = entry & QCOW2_OFFSET_MASK;
+                let mapping = if entry & QCOW2_COMPRESSED != 0 {
+                    return Err(unsupported("compressed clusters are not supported"));
+                } else if version >= 3 && entry & QCOW2_ZERO != 0 {
+                    ClusterMapping::Zero
+                } else if host_offset == 0 {
+                    continue;
+                } else {
+                    image
+                        .read_exact_at(&mut cluster, host_offset)
+                        .map_err(|e| storage_err(path, e))?;
+                    if cluster.iter().all(|&b| b == 0) && version >= 3 {
+                        ClusterMapping::Zero
+                    } else {
+                        ClusterMapping::Data(host_offset)
+                    }
+                };
+                // Without a backing file, unallocated clusters read as zeros too
+                if mapping == ClusterMapping::Zero && !has_backing {
+                    continue;
+                }
+                if matches!(mapping, ClusterMapping::Data(_)) {
+                    data_clusters += 1;
+                }
+                mappings[l2_index] = mapping;
+            }
+            if mappings.iter().any(|m| *m != ClusterMapping::Unallocated) {
+                tables.push((l1_index, mappings));
+            }
+        }
+
+        let layout = CompactLayout::new(
+            version,
+            cluster_size,
+            (l1_size as u64 * 8).div_ceil(cluster_size),
+            tables.len() as u64,
+            data_clusters,
+        );
+
+        let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".compact");
+        let tmp_path = path.with_file_name(tmp_name);
+        let out = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&tmp_path)
+            .map_err(|e| storage_err(&tmp_path, e))?;
+
+        let written = Self::write_compacted(&image, &out, &layout, &tables)
+            .and_then(|()| std::fs::rename(&tmp_path, path));
+        if let Err(e) = written {
+            let _ = std::fs::remove_file(&tmp_path);
+            return Err(storage_err(path, e));
+        }
+
+        let new_size = layout.total_clusters() * cluster_size;
+        tracing::info!(
+            path = %path.display(),
+            old_size,
+            new_size,
+            "Compacted qcow2 disk"
+        );
+        Ok(old_size.saturating_sub(new_size))
+    }
+
+    /// Write the compacted image described by `layout` and `tables` to `out`.
+    fn write_compacted(
+        image: &std::fs::File,
+        out: &std::fs::File,
+        layout: &CompactLayout,
+        tables: &[(usize, Vec<ClusterMapping>)],
+    ) -> std::io::Result<()> {
+        use std::os::unix::fs::FileExt;
+
+        let cluster_size = layout.cluster_size;
+        let total_clusters = layout.total_clusters();
+
+        // Header cluster, pointing at the new tables
+        let mut head = vec![0u8; cluster_size as usize];
+        let head_len = image.read_at(&mut head, 0)?;
+        head[head_len..].fill(0);
+        head[40..48].copy_from_slice(&(layout.l1_start() * cluster_size).to_be_bytes());
+        head[48..56].copy_from_slice(&(layout.refcount_start() * cluster_size).to_be_bytes());
+        head[56..60].copy_from_slice(&(layout.rt_clusters as u32).to_be_bytes());
+        if layout.version >= 3 {
+            // Clear the dirty bit and autoclear features (e.g. bitmaps)
+            head[72..80].copy_from_slice(&0u64.to_be_bytes());
+            head[88..96].copy_from_slice(&0u64.to_be_bytes());
+            head[96..100].copy_from_slice(&(REFCOUNT_ORDER as u32).to_be_bytes());
+        }
+        out.write_all_at(&head, 0)?;
+
+        // L1 table, L2 tables and data clusters
+        let mut l1 = vec![0u8; (layout.l1_clusters * cluster_size) as usize];
+        let mut l2 = vec![0u8; cluster_size as usize];
+        let mut cluster = vec![0u8; cluster_size as usize];
+        let mut next_data = layout.data_start();
+        for (table_index, (l1_index, mappings)) in tables.iter().enumerate() {
+            let l2_offset = (layout.l2_start() + table_index as u64) * cluster_size;
+            l1[l1_index * 8..l1_index * 8 + 8]
+                .copy_from_slice(&(l2_offset | QCOW2_COPIED).to_be_bytes());
+
+            l2.fill(0);
+            for (l2_index, mapping) in mappings.iter().enumerate() {
+                let entry = match *mapping {
+                    ClusterMapping::Unallocated => continue,
+                    ClusterMapping::Zero => QCOW2_ZERO,
+                    ClusterMapping::Data(host_offset) => {
+                        let new_offset = next_data * cluster_size;
+                        next_data += 1;
+                        image.read_exact_at(&mut cluster, host_offset)?;
+                        out.write_all_at(&cluster, new_offset)?;
+                        new_offset | QCOW2_COPIED
+                    }
+                };
+                l2[l2_index * 8..l2_index * 8 + 8].copy_from_slice(&entry.to_be_bytes());
+            }
+            out.write_all_at(&l2, l2_offset)?;
+        }
+        out.write_all_at(&l1, layout.l1_start() * cluster_size)?;
+
+        // Refcount table and blocks: every cluster is used exactly once
+        let rb_start = layout.refcount_start() + layout.rt_clusters;
+        let mut table = vec![0u8; (layout.rt_clusters * cluster_size) as usize];
+        for block in 0..layout.rb_clusters {
+            let at = block as usize * 8;
+            table[at..at + 8].copy_from_slice(&((rb_start + block) * cluster_size).to_be_bytes());
+        }
+        out.write_all_at(&table, layout.refcount_start() * cluster_size)?;
+
+        let mut blocks = vec![0u8; (layout.rb_clusters * cluster_size) as usize];
+        for index in 0..total_clusters as usize {
+            blocks[index * 2..index * 2 + 2].copy_from_slice(&1u16.to_be_bytes());
+        }
+        out.write_all_at(&blocks, rb_start * cluster_size)?;
+
+        out.set_len(total_clusters * cluster_size)?;
+        out.sync_all()
+    }
+
+    /// Read the backing format header extension, if present.
+    fn read_backing_format(
+        image: &std::fs::File,
+        version: u32,
+        header: &[u8],
+    ) -> std::io::Result<Option<String>> {
+        use std::os::unix::fs::FileExt;
+
+        // Extensions follow the header (fixed 72 bytes in v2, header_length in v3)
+        let mut offset = if version >= 3 {
+            u32::from_be_bytes(header[100..104].try_into().unwrap()) as u64
+        } else {
+            72
+        };
+        let mut ext = [0u8; 8];
+        loop {
+            image.read_exact_at(&mut ext, offset)?;
+            let kind = u32::from_be_bytes(ext[0..4].try_into().unwrap());
+            let len = u32::from_be_bytes(ext[4..8].try_into().unwrap()) as usize;
+            match kind {
+                0 => return Ok(None),
+                QCOW2_EXT_BACKING_FORMAT => {
+                    let mut name = vec![0u8; len];
+                    image.read_exact_at(&mut name, offset + 8)?;
+                    return Ok(Some(String::from_utf8_lossy(&name).into_owned()));
+                }
+                _ => offset += 8 + ((len as u64 + 7) & !7),
+            }
+        }
+    }
+
     /// Create COW child disk using external qemu-img binary.
     #[allow(dead_code)]
     fn create_cow_child_disk_external(
@@ -568,11 +1091,29 @@ pub enum BackingFormat {
     /// Raw disk image (ext4, etc.)
     Raw,
     /// Qcow2 disk image.
-    #[allow(dead_code)]
     Qcow2,
 }
 
 impl BackingFormat {
+    /// Detect the format of an existing base image from its magic.
+    ///
+    /// Anything that isn't qcow2 is treated as raw.
+    pub fn detect(path: &Path) -> BoxliteResult<Self> {
+        use std::io::Read;
+
+        let mut magic = [0u8; 4];
+        let read = std::fs::File::open(path).and_then(|mut f| f.read(&mut magic));
+        match read {
+            Ok(4) if u32::from_be_bytes(magic) == QCOW2_MAGIC => Ok(BackingFormat::Qcow2),
+            Ok(_) => Ok(BackingFormat::Raw),
+            Err(e) => Err(BoxliteError::Storage(format!(
+                "Failed to read base disk {}: {}",
+                path.display(),
+                e
+            ))),
+        }
+    }
+
     /// Get format string for qcow2 backing format extension.
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -581,3 +1122,173 @@ impl BackingFormat {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flatten_cow_child_reads_through_backing() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("base.ext4");
+        let mut content = vec![0u8; 3 * 65536 + 100];
+        content[..5].copy_from_slice(b"hello");
+        content[2 * 65536..2 * 65536 + 5].copy_from_slice(b"world");
+        std::fs::write(&base, &content).unwrap();
+
+        let child = dir.path().join("disk.qcow2");
+        let virtual_size = 1024 * 1024;
+        Qcow2Helper::new()
+            .create_cow_child_disk(&base, BackingFormat::Raw, &child, virtual_size)
+            .unwrap()
+            .leak();
+
+        let out = dir.path().join("rootfs.raw");
+        assert_eq!(
+            Qcow2Helper::flatten_to_raw(&child, &out).unwrap(),
+            virtual_size
+        );
+
+        let flat = std::fs::read(&out).unwrap();
+        assert_eq!(flat.len() as u64, virtual_size);
+        assert_eq!(&flat[..content.len()], &content[..]);
+        assert!(flat[content.len()..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_overlays_share_one_base() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("base.qcow2");
+        let helper = Qcow2Helper::new();
+        helper.create_disk(&base, true).unwrap().leak();
+        assert_eq!(BackingFormat::detect(&base).unwrap(), BackingFormat::Qcow2);
+        let virtual_size = Qcow2Helper::qcow2_virtual_size(&base).unwrap();
+
+        let overlays = [dir.path().join("a.qcow2"), dir.path().join("b.qcow2")];
+        for overlay in &overlays {
+            helper
+                .create_cow_child_disk(&base, BackingFormat::Qcow2, overlay, virtual_size)
+                .unwrap()
+                .leak();
+        }
+
+        let canonical = base.canonicalize().unwrap();
+        for overlay in &overlays {
+            assert_eq!(
+                Qcow2Helper::backing_file(overlay).unwrap(),
+                Some(canonical.clone())
+            );
+        }
+        assert_eq!(Qcow2Helper::backing_file(&base).unwrap(), None);
+    }
+
+    #[test]
+    fn test_compact_drops_unused_clusters() {
+        use std::os::unix::fs::FileExt;
+
+        let cluster_size = 65536u64;
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("base.ext4");
+        let mut content = vec![0u8; 3 * cluster_size as usize];
+        content[cluster_size as usize..cluster_size as usize + 5].copy_from_slice(b"world");
+        std::fs::write(&base, &content).unwrap();
+
+        let child = dir.path().join("disk.qcow2");
+        Qcow2Helper::new()
+            .create_cow_child_disk(&base, BackingFormat::Raw, &child, 1024 * 1024)
+            .unwrap()
+            .leak();
+
+        // Append an L2 table, a data cluster, a zeroed cluster hiding the
+        // backing data, and a cluster nothing references any more
+        let file = OpenOptions::new().write(true).open(&child).unwrap();
+        let end = file.metadata().unwrap().len();
+        let (l2, data, zeroed, leaked) = (
+            end,
+            end + cluster_size,
+            end + 2 * cluster_size,
+            end + 3 * cluster_size,
+        );
+        file.write_all_at(&(l2 | QCOW2_COPIED).to_be_bytes(), cluster_size)
+            .unwrap();
+        let mut table = vec![0u8; cluster_size as usize];
+        table[0..8].copy_from_slice(&(data | QCOW2_COPIED).to_be_bytes());
+        table[8..16].copy_from_slice(&(zeroed | QCOW2_COPIED).to_be_bytes());
+        file.write_all_at(&table, l2).unwrap();
+        let mut cluster = vec![0u8; cluster_size as usize];
+        cluster[..5].copy_from_slice(b"hello");
+        file.write_all_at(&cluster, data).unwrap();
+        file.write_all_at(&vec![0u8; cluster_size as usize], zeroed)
+            .unwrap();
+        file.write_all_at(&vec![0xaa; cluster_size as usize], leaked)
+            .unwrap();
+        drop(file);
+
+        let before = dir.path().join("before.raw");
+        Qcow2Helper::flatten_to_raw(&child, &before).unwrap();
+        let old_len = std::fs::metadata(&child).unwrap().len();
+
+        let reclaimed = Qcow2Helper::compact(&child).unwrap();
+
+        let new_len = std::fs::metadata(&child).unwrap().len();
+        assert!(new_len <= old_len);
+        assert_eq!(reclaimed, old_len - new_len);
+        assert_eq!(reclaimed, 2 * cluster_size);
+        assert!(!dir.path().join("disk.qcow2.compact").exists());
+
+        let after = dir.path().join("after.raw");
+        Qcow2Helper::flatten_to_raw(&child, &after).unwrap();
+        let flat = std::fs::read(&after).unwrap();
+        assert_eq!(flat, std::fs::read(&before).unwrap());
+        assert_eq!(&flat[..5], b"hello");
+        assert!(
+            flat[cluster_size as usize..2 * cluster_size as usize]
+                .iter()
+                .all(|&b| b == 0)
+        );
+
+        // Compacting a compact image changes nothing
+        assert_eq!(Qcow2Helper::compact(&child).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_invalid_cluster_bits_rejected() {
+        use std::os::unix::fs::FileExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("base.ext4");
+        std::fs::write(&base, vec![0u8; 65536]).unwrap();
+        let child = dir.path().join("disk.qcow2");
+        Qcow2Helper::new()
+            .create_cow_child_disk(&base, BackingFormat::Raw, &child, 1024 * 1024)
+            .unwrap()
+            .leak();
+
+        for bits in [0u32, 8, 22, 64] {
+            let file = OpenOptions::new().write(true).open(&child).unwrap();
+            file.write_all_at(&bits.to_be_bytes(), 20).unwrap();
+            drop(file);
+
+            let out = dir.path().join("out.raw");
+            assert!(matches!(
+                Qcow2Helper::flatten_to_raw(&child, &out),
+                Err(BoxliteError::Storage(_))
+            ));
+            assert!(matches!(
+                Qcow2Helper::compact(&child),
+                Err(BoxliteError::Storage(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_flatten_copies_raw_image() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("raw.img");
+        std::fs::write(&src, b"raw disk").unwrap();
+
+        let out = dir.path().join("out.img");
+        assert_eq!(Qcow2Helper::flatten_to_raw(&src, &out).unwrap(), 8);
+        assert_eq!(std::fs::read(&out).unwrap(), b"raw disk");
+    }
+}
@@ -0,0 +1,194 @@
+//! Named snapshots of a box's writable disk.
+//!
+//! A snapshot is a full copy of `disk.qcow2` stored as
+//! `snapshots/<name>.qcow2`, with its metadata in a `<name>.json` sidecar.
+//! Copies keep the overlay's backing file reference, so a snapshot stays
+//! small and depends on the same base as the disk it was taken from.
+
+use std::path::{Path, PathBuf};
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Metadata of a disk snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiskSnapshot {
+    /// Snapshot name, unique per box.
+    pub name: String,
+    /// When the snapshot was taken.
+    pub created_at: DateTime<Utc>,
+    /// Size of the snapshot file in bytes.
+    pub size_bytes: u64,
+}
+
+/// Snapshot files of one box.
+pub(crate) struct DiskSnapshots {
+    dir: PathBuf,
+}
+
+impl DiskSnapshots {
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Copy `disk_path` into a new snapshot called `name`.
+    pub(crate) fn create(&self, disk_path: &Path, name: &str) -> BoxliteResult<DiskSnapshot> {
+        validate_name(name)?;
+        let image = self.image_path(name);
+        if image.exists() {
+            return Err(BoxliteError::AlreadyExists(format!(
+                "disk snapshot {} already exists",
+                name
+            )));
+        }
+
+        std::fs::create_dir_all(&self.dir).map_err(|e| {
+            BoxliteError::Storage(format!(
+                "Failed to create snapshots directory {}: {}",
+                self.dir.display(),
+                e
+            ))
+        })?;
+        let size_bytes = copy_atomic(disk_path, &image)?;
+
+        let snapshot = DiskSnapshot {
+            name: name.to_string(),
+            created_at: Utc::now(),
+            size_bytes,
+        };
+        let json = serde_json::to_vec_pretty(&snapshot).map_err(|e| {
+            BoxliteError::Internal(format!("Failed to serialize snapshot metadata: {}", e))
+        })?;
+        let sidecar = self.metadata_path(name);
+        if let Err(e) = std::fs::write(&sidecar, json) {
+            let _ = std::fs::remove_file(&image);
+            return Err(BoxliteError::Storage(format!(
+                "Failed to write {}: {}",
+                sidecar.display(),
+                e
+            )));
+        }
+        Ok(snapshot)
+    }
+
+    /// Replace `disk_path` with the contents of snapshot `name`.
+    pub(crate) fn restore(&self, disk_path: &Path, name: &str) -> BoxliteResult<DiskSnapshot> {
+        validate_name(name)?;
+        let snapshot = self.get(name)?;
+        copy_atomic(&self.image_path(name), disk_path)?;
+        Ok(snapshot)
+    }
+
+    /// Metadata of snapshot `name`.
+    pub(crate) fn get(&self, name: &str) -> BoxliteResult<DiskSnapshot> {
+        let sidecar = self.metadata_path(name);
+        let json = match std::fs::read(&sidecar) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(BoxliteError::NotFound(format!(
+                    "disk snapshot {} not found",
+                    name
+                )));
+            }
+            Err(e) => {
+                return Err(BoxliteError::Storage(format!(
+                    "Failed to read {}: {}",
+                    sidecar.display(),
+                    e
+                )));
+            }
+        };
+        serde_json::from_slice(&json).map_err(|e| {
+            BoxliteError::Storage(format!(
+                "Corrupt snapshot metadata {}: {}",
+                sidecar.display(),
+                e
+            ))
+        })
+    }
+
+    fn image_path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{}.qcow2", name))
+    }
+
+    fn metadata_path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", name))
+    }
+}
+
+/// Snapshot names become file names, so keep them to a safe character set.
+fn validate_name(name: &str) -> BoxliteResult<()> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(BoxliteError::InvalidArgument(format!(
+            "invalid snapshot name {:?}: use letters, digits, '-', '_' and '.'",
+            name
+        )))
+    }
+}
+
+/// Copy through a temporary file so `dest` is never left half-written.
+fn copy_atomic(src: &Path, dest: &Path) -> BoxliteResult<u64> {
+    let mut tmp = dest.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+
+    let size = std::fs::copy(src, &tmp).map_err(|e| {
+        BoxliteError::Storage(format!(
+            "Failed to copy {} to {}: {}",
+            src.display(),
+            tmp.display(),
+            e
+        ))
+    })?;
+    std::fs::rename(&tmp, dest).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp);
+        BoxliteError::Storage(format!("Failed to replace {}: {}", dest.display(), e))
+    })?;
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_brings_back_snapshot_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let disk = dir.path().join("disk.qcow2");
+        std::fs::write(&disk, b"original disk contents").unwrap();
+
+        let snapshots = DiskSnapshots::new(dir.path().join("snapshots"));
+        let snapshot = snapshots.create(&disk, "before").unwrap();
+        assert_eq!(snapshot.size_bytes, 22);
+        assert_eq!(snapshots.get("before").unwrap(), snapshot);
+        assert!(matches!(
+            snapshots.create(&disk, "before"),
+            Err(BoxliteError::AlreadyExists(_))
+        ));
+
+        std::fs::write(&disk, b"mutated").unwrap();
+        snapshots.restore(&disk, "before").unwrap();
+        assert_eq!(
+            std::fs::read(&disk).unwrap(),
+            std::fs::read(dir.path().join("snapshots/before.qcow2")).unwrap()
+        );
+        assert_eq!(std::fs::read(&disk).unwrap(), b"original disk contents");
+
+        assert!(matches!(
+            snapshots.restore(&disk, "missing"),
+            Err(BoxliteError::NotFound(_))
+        ));
+        assert!(matches!(
+            snapshots.create(&disk, "../escape"),
+            Err(BoxliteError::InvalidArgument(_))
+        ));
+    }
+}
@@ -1,5 +1,6 @@
 //! Container image configuration extracted from OCI images config
 
+use crate::runtime::options::BoxOptions;
 use serde::{Deserialize, Serialize};
 
 /// Container image configuration extracted from OCI images.
@@ -14,6 +15,10 @@ pub struct ContainerImageConfig {
     /// This combines the images's ENTRYPOINT and CMD directives.
     pub cmd: Vec<String>,
 
+    /// The ENTRYPOINT part of `cmd` (kept when only the arguments are overridden)
+    #[serde(default)]
+    pub entrypoint: Vec<String>,
+
     /// Exposed ports from the images (e.g., ["8080/tcp", "443/tcp"])
     ///
     /// These are the ports declared in the images's EXPOSE directive.
@@ -25,6 +30,12 @@ pub struct ContainerImageConfig {
 
     /// Working directory (e.g., "/app", "/workspace")
     pub working_dir: String,
+
+    /// User the container runs as (image USER, e.g. "nginx" or "1000:1000")
+    ///
+    /// Resolved against the container's /etc/passwd by the guest. `None` = root.
+    #[serde(default)]
+    pub user: Option<String>,
 }
 
 impl ContainerImageConfig {
@@ -34,6 +45,22 @@ impl ContainerImageConfig {
         Self::default()
     }
 
+    /// Replace the image's command with a user-supplied one.
+    ///
+    /// Follows `docker run` semantics: `command` replaces ENTRYPOINT and
+    /// drops the image's CMD, `args` replaces CMD. With neither set the image
+    /// defaults are kept.
+    pub fn override_command(&mut self, command: Option<&[String]>, args: Option<&[String]>) {
+        if let Some(command) = command {
+            self.entrypoint = command.to_vec();
+        } else if args.is_none() {
+            return;
+        }
+
+        self.cmd = self.entrypoint.clone();
+        self.cmd.extend(args.unwrap_or_default().iter().cloned());
+    }
+
     /// Parse port number and protocol from exposed port string
     ///
     /// # Examples
@@ -150,11 +177,16 @@ impl ContainerImageConfig {
         // Extract exposed ports
         let exposed_ports = config.exposed_ports().clone().unwrap_or_default();
 
+        // Extract user (empty means root)
+        let user = config.user().clone().filter(|u| !u.is_empty());
+
         Ok(ContainerImageConfig {
+            entrypoint: config.entrypoint().clone().unwrap_or_default(),
             cmd: entrypoint,
             env,
             working_dir: workdir,
             exposed_ports,
+            user,
         })
     }
 }
@@ -163,13 +195,73 @@ impl Default for ContainerImageConfig {
     fn default() -> Self {
         Self {
             cmd: vec!["/bin/sh".to_string()],
+            entrypoint: Vec::new(),
             env: vec![
                 "PATH=/usr/local/sbin:/usr/local/bin:/usr/sbin:/usr/bin:/sbin:/bin".to_string(),
             ],
             working_dir: "/".to_string(),
             exposed_ports: Vec::new(),
+            user: None,
+        }
+    }
+}
+
+/// Changes applied to a base image's config when committing a new image.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ImageConfigChanges {
+    /// Variables set on top of the base env, replacing same-named ones.
+    pub env: Vec<(String, String)>,
+    pub working_dir: Option<String>,
+    /// New ENTRYPOINT; also drops the base image's CMD unless `cmd` is set.
+    pub entrypoint: Option<Vec<String>>,
+    pub cmd: Option<Vec<String>>,
+    pub user: Option<String>,
+}
+
+impl ImageConfigChanges {
+    /// Changes that bake a box's command, environment and user into its image.
+    ///
+    /// `command` becomes the ENTRYPOINT and `args` the CMD, mirroring how
+    /// they override the image config when the box starts.
+    pub(crate) fn from_box_options(options: &BoxOptions) -> Self {
+        Self {
+            env: options.env.clone(),
+            working_dir: options.working_dir.clone(),
+            entrypoint: options.command.clone(),
+            cmd: options.args.clone(),
+            user: options.user.clone(),
         }
     }
+
+    /// Apply the changes to `image_config`, creating its `config` if missing.
+    pub(crate) fn apply_to(&self, image_config: &mut oci_spec::image::ImageConfiguration) {
+        let mut config = image_config.config().clone().unwrap_or_default();
+
+        if !self.env.is_empty() {
+            let mut env = config.env().clone().unwrap_or_default();
+            for (key, value) in &self.env {
+                let prefix = format!("{}=", key);
+                env.retain(|entry| !entry.starts_with(&prefix) && entry != key);
+                env.push(format!("{}={}", key, value));
+            }
+            config.set_env(Some(env));
+        }
+        if let Some(dir) = &self.working_dir {
+            config.set_working_dir(Some(dir.clone()));
+        }
+        if let Some(entrypoint) = &self.entrypoint {
+            config.set_entrypoint(Some(entrypoint.clone()));
+            config.set_cmd(None);
+        }
+        if let Some(cmd) = &self.cmd {
+            config.set_cmd(Some(cmd.clone()));
+        }
+        if let Some(user) = &self.user {
+            config.set_user(Some(user.clone()));
+        }
+
+        image_config.set_config(Some(config));
+    }
 }
 
 #[cfg(test)]
@@ -197,8 +289,10 @@ mod tests {
     fn test_tcp_ports() {
         let config = ContainerImageConfig {
             cmd: vec![],
+            entrypoint: vec![],
             env: vec![],
             working_dir: "/".to_string(),
+            user: None,
             exposed_ports: vec![
                 "8080/tcp".to_string(),
                 "443/tcp".to_string(),
@@ -213,8 +307,10 @@ mod tests {
     fn test_udp_ports() {
         let config = ContainerImageConfig {
             cmd: vec![],
+            entrypoint: vec![],
             env: vec![],
             working_dir: "/".to_string(),
+            user: None,
             exposed_ports: vec![
                 "8080/tcp".to_string(),
                 "53/udp".to_string(),
@@ -224,4 +320,134 @@ mod tests {
 
         assert_eq!(config.udp_ports(), vec![53, 123]);
     }
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn python_image() -> ContainerImageConfig {
+        ContainerImageConfig {
+            cmd: strings(&["python3", "-u", "app.py"]),
+            entrypoint: strings(&["python3", "-u"]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_from_oci_config_applies_image_settings() {
+        let image_config: oci_spec::image::ImageConfiguration = serde_json::from_str(
+            r#"{
+                "architecture": "amd64",
+                "os": "linux",
+                "config": {
+                    "User": "nginx",
+                    "Env": ["PATH=/usr/sbin:/usr/bin", "NGINX_VERSION=1.27.0"],
+                    "Entrypoint": ["/docker-entrypoint.sh"],
+                    "Cmd": ["nginx", "-g", "daemon off;"],
+                    "WorkingDir": "/usr/share/nginx",
+                    "ExposedPorts": {"80/tcp": {}}
+                },
+                "rootfs": {"type": "layers", "diff_ids": []}
+            }"#,
+        )
+        .unwrap();
+
+        let config = ContainerImageConfig::from_oci_config(&image_config).unwrap();
+
+        assert_eq!(
+            config.cmd,
+            strings(&["/docker-entrypoint.sh", "nginx", "-g", "daemon off;"])
+        );
+        assert_eq!(config.entrypoint, strings(&["/docker-entrypoint.sh"]));
+        assert_eq!(
+            config.env,
+            strings(&["PATH=/usr/sbin:/usr/bin", "NGINX_VERSION=1.27.0"])
+        );
+        assert_eq!(config.working_dir, "/usr/share/nginx");
+        assert_eq!(config.user.as_deref(), Some("nginx"));
+        assert_eq!(config.tcp_ports(), vec![80]);
+    }
+
+    #[test]
+    fn test_from_oci_config_defaults() {
+        let image_config: oci_spec::image::ImageConfiguration = serde_json::from_str(
+            r#"{"architecture": "amd64", "os": "linux", "config": {"User": ""},
+                "rootfs": {"type": "layers", "diff_ids": []}}"#,
+        )
+        .unwrap();
+
+        let config = ContainerImageConfig::from_oci_config(&image_config).unwrap();
+
+        assert!(config.cmd.is_empty());
+        assert_eq!(config.working_dir, "/");
+        assert_eq!(config.user, None);
+    }
+
+    #[test]
+    fn test_override_command_replaces_entrypoint_and_cmd() {
+        let mut config = python_image();
+        config.override_command(
+            Some(&strings(&["/bin/sh", "-c"])),
+            Some(&strings(&["echo hi"])),
+        );
+        assert_eq!(config.cmd, strings(&["/bin/sh", "-c", "echo hi"]));
+
+        // A new command alone drops the image's CMD too
+        let mut config = python_image();
+        config.override_command(Some(&strings(&["/bin/sleep"])), None);
+        assert_eq!(config.cmd, strings(&["/bin/sleep"]));
+    }
+
+    #[test]
+    fn test_override_args_keeps_image_entrypoint() {
+        let mut config = python_image();
+        config.override_command(None, Some(&strings(&["other.py", "--flag"])));
+        assert_eq!(
+            config.cmd,
+            strings(&["python3", "-u", "other.py", "--flag"])
+        );
+    }
+
+    #[test]
+    fn test_box_options_override_image_config() {
+        let mut image_config: oci_spec::image::ImageConfiguration = serde_json::from_str(
+            r#"{
+                "architecture": "amd64",
+                "os": "linux",
+                "config": {
+                    "Env": ["PATH=/usr/bin", "MODE=dev"],
+                    "Entrypoint": ["python3"],
+                    "Cmd": ["app.py"]
+                },
+                "rootfs": {"type": "layers", "diff_ids": []}
+            }"#,
+        )
+        .unwrap();
+        let options = BoxOptions {
+            env: vec![("MODE".into(), "prod".into())],
+            command: Some(strings(&["/usr/bin/serve"])),
+            user: Some("1000".into()),
+            ..Default::default()
+        };
+
+        ImageConfigChanges::from_box_options(&options).apply_to(&mut image_config);
+        let config = image_config.config().clone().unwrap();
+        assert_eq!(
+            config.env().clone().unwrap(),
+            strings(&["PATH=/usr/bin", "MODE=prod"])
+        );
+        assert_eq!(
+            config.entrypoint().clone().unwrap(),
+            strings(&["/usr/bin/serve"])
+        );
+        assert_eq!(config.cmd(), &None);
+        assert_eq!(config.user().as_deref(), Some("1000"));
+    }
+
+    #[test]
+    fn test_no_override_keeps_image_defaults() {
+        let mut config = python_image();
+        config.override_command(None, None);
+        assert_eq!(config.cmd, strings(&["python3", "-u", "app.py"]));
+    }
 }
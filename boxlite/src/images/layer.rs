@@ -0,0 +1,428 @@
+//! Filesystem diffs packed as OCI layers.
+//!
+//! Committing a box compares its rootfs (`upper`) against the merged rootfs
+//! of its image (`lower`) and writes every difference to a gzipped tarball.
+//! Deleted paths become `.wh.<name>` whiteouts, as in any OCI layer.
+
+use std::collections::HashMap;
+use std::fs::{self, File, Metadata};
+use std::io::{self, Write};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+/// Files the guest rewrites on every boot; they are never committed.
+const VOLATILE_PATHS: &[&str] = &["etc/hostname", "etc/hosts", "etc/resolv.conf"];
+
+/// A layer tarball written by `write_diff_layer`.
+#[derive(Debug)]
+pub(crate) struct LayerBlob {
+    pub path: PathBuf,
+    /// Digest of the compressed tarball, as listed in the manifest.
+    pub digest: String,
+    /// Digest of the uncompressed tarball, as listed in the config's `rootfs`.
+    pub diff_id: String,
+    /// Compressed size in bytes.
+    pub size: u64,
+    /// Entries written, whiteouts included.
+    pub entries: usize,
+}
+
+/// Write the changes that turn `lower` into `upper` as a layer at `out`.
+///
+/// Regular files count as changed when their type, mode, owner or size
+/// differ, or when their mtime differs and the contents do too. Owners of
+/// `upper` entries come from `upper_owners` (keyed by relative path) when
+/// given, since unprivileged copies of a disk lose them. Otherwise files
+/// owned by the current user are recorded as root when not running as root,
+/// matching how unprivileged disk images are built.
+pub(crate) fn write_diff_layer(
+    lower: &Path,
+    upper: &Path,
+    upper_owners: Option<&HashMap<PathBuf, (u32, u32)>>,
+    out: &Path,
+) -> BoxliteResult<LayerBlob> {
+    let storage_err = |e: io::Error| BoxliteError::Storage(format!("Failed to write layer: {}", e));
+
+    let file = File::create(out).map_err(storage_err)?;
+    let encoder =
+        flate2::write::GzEncoder::new(HashWriter::new(file), flate2::Compression::default());
+    let mut writer = LayerWriter {
+        builder: tar::Builder::new(HashWriter::new(encoder)),
+        owner: OwnerMap::current(upper_owners),
+        entries: 0,
+    };
+    writer.builder.follow_symlinks(false);
+
+    write_deletions(&mut writer, lower, upper).map_err(storage_err)?;
+    write_additions(&mut writer, lower, upper).map_err(storage_err)?;
+
+    let entries = writer.entries;
+    let uncompressed = writer.builder.into_inner().map_err(storage_err)?;
+    let diff_id = format!("sha256:{:x}", uncompressed.hasher.finalize());
+    let compressed = uncompressed.inner.finish().map_err(storage_err)?;
+    compressed.inner.sync_all().map_err(storage_err)?;
+
+    Ok(LayerBlob {
+        path: out.to_path_buf(),
+        digest: format!("sha256:{:x}", compressed.hasher.finalize()),
+        diff_id,
+        size: compressed.written,
+        entries,
+    })
+}
+
+/// Whiteouts for every path of `lower` missing from `upper`.
+fn write_deletions<W: Write>(
+    writer: &mut LayerWriter<'_, W>,
+    lower: &Path,
+    upper: &Path,
+) -> io::Result<()> {
+    let mut walk = WalkDir::new(lower)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter();
+    while let Some(entry) = walk.next() {
+        let entry = entry.map_err(io::Error::other)?;
+        let rel = relative(lower, entry.path());
+        if is_volatile(rel) {
+            continue;
+        }
+
+        match fs::symlink_metadata(upper.join(rel)) {
+            Ok(meta) => {
+                // A directory replaced by a file is whited out with the file
+                if entry.file_type().is_dir() && !meta.is_dir() {
+                    walk.skip_current_dir();
+                }
+            }
+            Err(_) => {
+                writer.append_whiteout(rel)?;
+                if entry.file_type().is_dir() {
+                    walk.skip_current_dir();
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Entries for every path of `upper` that is new or differs from `lower`.
+fn write_additions<W: Write>(
+    writer: &mut LayerWriter<'_, W>,
+    lower: &Path,
+    upper: &Path,
+) -> io::Result<()> {
+    for entry in WalkDir::new(upper).min_depth(1).sort_by_file_name() {
+        let entry = entry.map_err(io::Error::other)?;
+        let rel = relative(upper, entry.path());
+        if is_volatile(rel) {
+            continue;
+        }
+
+        let meta = entry.metadata().map_err(io::Error::other)?;
+        let lower_path = lower.join(rel);
+        match fs::symlink_metadata(&lower_path) {
+            Ok(lower_meta) if lower_meta.file_type() != meta.file_type() => {
+                writer.append_whiteout(rel)?;
+                writer.append(entry.path(), rel, &meta)?;
+            }
+            Ok(lower_meta) => {
+                if writer.owner.lower(&lower_meta) != writer.owner.upper(rel, &meta)
+                    || changed(&lower_path, &lower_meta, entry.path(), &meta)?
+                {
+                    writer.append(entry.path(), rel, &meta)?;
+                }
+            }
+            Err(_) => writer.append(entry.path(), rel, &meta)?,
+        }
+    }
+    Ok(())
+}
+
+/// Whether `upper` differs from `lower`, both of the same file type.
+///
+/// Owners are compared by the caller, through `OwnerMap`.
+fn changed(lower: &Path, lower_meta: &Metadata, upper: &Path, meta: &Metadata) -> io::Result<bool> {
+    if lower_meta.mode() != meta.mode() {
+        return Ok(true);
+    }
+
+    let file_type = meta.file_type();
+    if file_type.is_symlink() {
+        return Ok(fs::read_link(lower)? != fs::read_link(upper)?);
+    }
+    if !file_type.is_file() {
+        // Directory timestamps change whenever an entry does
+        return Ok(false);
+    }
+    if lower_meta.size() != meta.size() {
+        return Ok(true);
+    }
+    if lower_meta.mtime() == meta.mtime() {
+        return Ok(false);
+    }
+    Ok(!same_contents(lower, upper)?)
+}
+
+fn same_contents(a: &Path, b: &Path) -> io::Result<bool> {
+    use std::io::Read;
+
+    let mut a = io::BufReader::new(File::open(a)?);
+    let mut b = io::BufReader::new(File::open(b)?);
+    let mut buf_a = [0u8; 8192];
+    let mut buf_b = [0u8; 8192];
+    loop {
+        let n = a.read(&mut buf_a)?;
+        if n == 0 {
+            return Ok(b.read(&mut buf_b)? == 0);
+        }
+        b.read_exact(&mut buf_b[..n])?;
+        if buf_a[..n] != buf_b[..n] {
+            return Ok(false);
+        }
+    }
+}
+
+fn relative<'a>(root: &Path, path: &'a Path) -> &'a Path {
+    path.strip_prefix(root).unwrap_or(path)
+}
+
+fn is_volatile(rel: &Path) -> bool {
+    VOLATILE_PATHS.iter().any(|p| rel == Path::new(p))
+}
+
+/// Owners recorded in the layer.
+///
+/// Maps the current user to root when not running as root, unless the real
+/// owners of `upper` are known.
+struct OwnerMap<'a> {
+    uid: u32,
+    gid: u32,
+    upper: Option<&'a HashMap<PathBuf, (u32, u32)>>,
+}
+
+impl<'a> OwnerMap<'a> {
+    fn current(upper: Option<&'a HashMap<PathBuf, (u32, u32)>>) -> Self {
+        Self {
+            uid: unsafe { libc::getuid() },
+            gid: unsafe { libc::getgid() },
+            upper,
+        }
+    }
+
+    fn lower(&self, meta: &Metadata) -> (u32, u32) {
+        let uid = if self.uid != 0 && meta.uid() == self.uid {
+            0
+        } else {
+            meta.uid()
+        };
+        let gid = if self.gid != 0 && meta.gid() == self.gid {
+            0
+        } else {
+            meta.gid()
+        };
+        (uid, gid)
+    }
+
+    fn upper(&self, rel: &Path, meta: &Metadata) -> (u32, u32) {
+        self.upper
+            .and_then(|owners| owners.get(rel).copied())
+            .unwrap_or_else(|| self.lower(meta))
+    }
+}
+
+struct LayerWriter<'a, W: Write> {
+    builder: tar::Builder<W>,
+    owner: OwnerMap<'a>,
+    entries: usize,
+}
+
+impl<W: Write> LayerWriter<'_, W> {
+    fn append(&mut self, path: &Path, rel: &Path, meta: &Metadata) -> io::Result<()> {
+        let mut header = tar::Header::new_gnu();
+        header.set_metadata_in_mode(meta, tar::HeaderMode::Complete);
+        let (uid, gid) = self.owner.upper(rel, meta);
+        header.set_uid(uid.into());
+        header.set_gid(gid.into());
+
+        let file_type = meta.file_type();
+        if file_type.is_symlink() {
+            let target = fs::read_link(path)?;
+            self.builder.append_link(&mut header, rel, target)?;
+        } else if file_type.is_file() {
+            self.builder
+                .append_data(&mut header, rel, File::open(path)?)?;
+        } else {
+            header.set_size(0);
+            self.builder.append_data(&mut header, rel, io::empty())?;
+        }
+        self.entries += 1;
+        Ok(())
+    }
+
+    fn append_whiteout(&mut self, rel: &Path) -> io::Result<()> {
+        let Some(name) = rel.file_name() else {
+            return Ok(());
+        };
+        let mut whiteout = std::ffi::OsString::from(".wh.");
+        whiteout.push(name);
+        let path = rel.with_file_name(whiteout);
+
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Regular);
+        header.set_size(0);
+        header.set_mode(0o644);
+        self.builder.append_data(&mut header, path, io::empty())?;
+        self.entries += 1;
+        Ok(())
+    }
+}
+
+/// Writer that hashes and counts everything written through it.
+struct HashWriter<W> {
+    inner: W,
+    hasher: Sha256,
+    written: u64,
+}
+
+impl<W> HashWriter<W> {
+    fn new(inner: W) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+            written: 0,
+        }
+    }
+}
+
+impl<W: Write> Write for HashWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use std::io::Read;
+
+    #[test]
+    fn test_diff_layer_records_changes_and_whiteouts() {
+        let dir = tempfile::tempdir().unwrap();
+        let lower = dir.path().join("lower");
+        let upper = dir.path().join("upper");
+        for root in [&lower, &upper] {
+            fs::create_dir_all(root.join("etc")).unwrap();
+            fs::write(root.join("etc/os-release"), b"ID=alpine\n").unwrap();
+            fs::write(root.join("etc/motd"), b"welcome\n").unwrap();
+        }
+        fs::create_dir_all(lower.join("var/cache/apk")).unwrap();
+        fs::write(lower.join("var/cache/apk/index"), b"stale").unwrap();
+        fs::write(lower.join("etc/hosts"), b"127.0.0.1 localhost\n").unwrap();
+
+        fs::write(upper.join("etc/motd"), b"built by boxlite\n").unwrap();
+        fs::write(upper.join("etc/hosts"), b"10.0.2.15 box\n").unwrap();
+        fs::create_dir_all(upper.join("app")).unwrap();
+        fs::write(upper.join("app/main.sh"), b"echo hi\n").unwrap();
+        std::os::unix::fs::symlink("main.sh", upper.join("app/run")).unwrap();
+        fs::create_dir_all(upper.join("var")).unwrap();
+
+        let out = dir.path().join("layer.tar.gz");
+        let blob = write_diff_layer(&lower, &upper, None, &out).unwrap();
+
+        let mut archive =
+            tar::Archive::new(flate2::read::GzDecoder::new(File::open(&out).unwrap()));
+        let names: BTreeSet<String> = archive
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().display().to_string())
+            .collect();
+        let expected: BTreeSet<String> =
+            ["app", "app/main.sh", "app/run", "etc/motd", "var/.wh.cache"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+        assert_eq!(names, expected);
+        assert_eq!(blob.entries, expected.len());
+
+        let mut compressed = Vec::new();
+        File::open(&out)
+            .unwrap()
+            .read_to_end(&mut compressed)
+            .unwrap();
+        assert_eq!(blob.size, compressed.len() as u64);
+        assert_eq!(
+            blob.digest,
+            format!("sha256:{:x}", Sha256::digest(&compressed))
+        );
+        let mut uncompressed = Vec::new();
+        flate2::read::GzDecoder::new(compressed.as_slice())
+            .read_to_end(&mut uncompressed)
+            .unwrap();
+        assert_eq!(
+            blob.diff_id,
+            format!("sha256:{:x}", Sha256::digest(&uncompressed))
+        );
+    }
+
+    #[test]
+    fn test_diff_layer_uses_known_upper_owners() {
+        let dir = tempfile::tempdir().unwrap();
+        let lower = dir.path().join("lower");
+        let upper = dir.path().join("upper");
+        for root in [&lower, &upper] {
+            fs::create_dir_all(root.join("home/app")).unwrap();
+            fs::write(root.join("home/app/.profile"), b"export PS1=$\n").unwrap();
+        }
+        fs::write(upper.join("home/app/notes"), b"todo\n").unwrap();
+
+        let owners = OwnerMap::current(None);
+        let lower_owner = owners.lower(&fs::metadata(lower.join("home/app")).unwrap());
+        let upper_owners: HashMap<PathBuf, (u32, u32)> = [
+            (PathBuf::from("home"), lower_owner),
+            (PathBuf::from("home/app"), (1000, 1000)),
+            (PathBuf::from("home/app/.profile"), lower_owner),
+            (PathBuf::from("home/app/notes"), (1000, 1000)),
+        ]
+        .into_iter()
+        .collect();
+
+        let out = dir.path().join("layer.tar.gz");
+        write_diff_layer(&lower, &upper, Some(&upper_owners), &out).unwrap();
+
+        let mut archive =
+            tar::Archive::new(flate2::read::GzDecoder::new(File::open(&out).unwrap()));
+        let entries: Vec<(String, u64, u64)> = archive
+            .entries()
+            .unwrap()
+            .map(|e| {
+                let e = e.unwrap();
+                let header = e.header();
+                (
+                    e.path().unwrap().display().to_string(),
+                    header.uid().unwrap(),
+                    header.gid().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("home/app".to_string(), 1000, 1000),
+                ("home/app/notes".to_string(), 1000, 1000),
+            ]
+        );
+    }
+}
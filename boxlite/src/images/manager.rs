@@ -15,11 +15,15 @@ use std::sync::Arc;
 use chrono::{DateTime, Utc};
 
 use super::object::ImageObject;
-use crate::db::Database;
+use crate::db::{CachedImage, Database};
+use crate::images::layer::LayerBlob;
+use crate::images::progress::PullProgressFn;
+use crate::images::retry::RetryPolicy;
 use crate::images::store::{ImageStore, SharedImageStore};
-use crate::runtime::types::ImageInfo;
-use boxlite_shared::errors::BoxliteResult;
+use crate::runtime::types::{Bytes, ImageDetails, ImageInfo};
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use oci_client::Reference;
+use oci_spec::image::ImageConfiguration;
 use std::str::FromStr;
 
 // ============================================================================
@@ -34,10 +38,22 @@ pub(super) struct ImageManifest {
     pub(super) config_digest: String,
 }
 
+/// Options of a single image pull.
+#[derive(Default)]
+pub struct PullOptions<'a> {
+    /// Platform to pick from multi-arch images, e.g. `linux/arm64`.
+    /// `None` selects the host platform.
+    pub platform: Option<String>,
+    /// Called as layer bytes are downloaded. `None` pulls silently.
+    pub progress: Option<&'a PullProgressFn<'a>>,
+}
+
 #[derive(Debug, Clone)]
 pub(super) struct LayerInfo {
     pub(super) digest: String,
     pub(super) media_type: String,
+    /// Compressed size from the manifest.
+    pub(super) size: u64,
 }
 
 // ============================================================================
@@ -90,8 +106,14 @@ impl ImageManager {
     /// * `images_dir` - Directory for image cache
     /// * `db` - Database for image index
     /// * `registries` - Registries to search for unqualified images (tried in order)
-    pub fn new(images_dir: PathBuf, db: Database, registries: Vec<String>) -> BoxliteResult<Self> {
-        let store = Arc::new(ImageStore::new(images_dir, db, registries)?);
+    /// * `retry` - Retry policy for registry requests
+    pub fn new(
+        images_dir: PathBuf,
+        db: Database,
+        registries: Vec<String>,
+        retry: RetryPolicy,
+    ) -> BoxliteResult<Self> {
+        let store = Arc::new(ImageStore::new(images_dir, db, registries, retry)?);
         Ok(Self { store })
     }
 
@@ -103,7 +125,19 @@ impl ImageManager {
     /// Thread Safety: `ImageStore` handles locking internally. Multiple
     /// concurrent pulls of the same image will only download once.
     pub async fn pull(&self, image_ref: &str) -> BoxliteResult<ImageObject> {
-        let manifest = self.store.pull(image_ref).await?;
+        self.pull_with(image_ref, &PullOptions::default()).await
+    }
+
+    /// Pull an OCI image for a specific platform and/or with progress.
+    ///
+    /// Progress: layers already on disk are reported once as complete; a
+    /// fully cached image reports nothing.
+    pub async fn pull_with(
+        &self,
+        image_ref: &str,
+        options: &PullOptions<'_>,
+    ) -> BoxliteResult<ImageObject> {
+        let manifest = self.store.pull(image_ref, options).await?;
 
         Ok(ImageObject::new(
             image_ref.to_string(),
@@ -118,32 +152,224 @@ impl ImageManager {
 
         let mut images = Vec::with_capacity(raw_images.len());
         for (reference, cached) in raw_images {
-            // If parsing fails, default to UNIX_EPOCH to signal error
-            let cached_at = DateTime::parse_from_rfc3339(&cached.cached_at)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(|_| DateTime::<Utc>::from(std::time::SystemTime::UNIX_EPOCH));
-
-            let (repository, tag) = match Reference::from_str(&reference) {
-                Ok(r) => (
-                    r.repository().to_string(),
-                    r.tag().unwrap_or("latest").to_string(),
-                ),
-                Err(_) => {
-                    // Fallback if reference stored in DB is invalid
-                    (reference.clone(), "<none>".to_string())
-                }
-            };
-
-            images.push(ImageInfo {
-                reference,
-                repository,
-                tag,
-                id: cached.manifest_digest,
-                cached_at,
-                size: None, // Size calculation is expensive now? omitted for list temporarily
-            });
+            images.push(self.image_info(reference, &cached).await);
         }
 
         Ok(images)
     }
+
+    /// Describe the cached image `image_ref` resolves to.
+    ///
+    /// Only the local store is consulted; an image that has not been pulled
+    /// returns `BoxliteError::NotFound`.
+    pub async fn inspect(&self, image_ref: &str) -> BoxliteResult<ImageDetails> {
+        let Some((reference, cached)) = self.store.find(image_ref, None).await? else {
+            return Err(BoxliteError::NotFound(format!("image {}", image_ref)));
+        };
+
+        let config_json = self.store.config(&cached.config_digest).await?;
+        let config: ImageConfiguration = serde_json::from_str(&config_json).map_err(|e| {
+            BoxliteError::Image(format!(
+                "Failed to parse config of image {}: {}",
+                reference, e
+            ))
+        })?;
+
+        Ok(ImageDetails {
+            info: self.image_info(reference, &cached).await,
+            layers: cached.layers,
+            config_digest: cached.config_digest,
+            config,
+        })
+    }
+
+    /// Index key of the cached image `image_ref` resolves to for `platform`,
+    /// or `None` if it is not cached.
+    pub(crate) async fn cached_key(
+        &self,
+        image_ref: &str,
+        platform: Option<&str>,
+    ) -> BoxliteResult<Option<String>> {
+        Ok(self
+            .store
+            .find(image_ref, platform)
+            .await?
+            .map(|(key, _)| key))
+    }
+
+    /// Remove the cached image indexed under `key`.
+    ///
+    /// Layers shared with other cached images are kept, and so is the image's
+    /// disk image if `keep_disk` is set. Returns the number of layers removed.
+    pub(crate) async fn remove(&self, key: &str, keep_disk: bool) -> BoxliteResult<usize> {
+        self.store.remove(key, keep_disk).await
+    }
+
+    async fn image_info(&self, reference: String, cached: &CachedImage) -> ImageInfo {
+        // If parsing fails, default to UNIX_EPOCH to signal error
+        let cached_at = DateTime::parse_from_rfc3339(&cached.cached_at)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| DateTime::<Utc>::from(std::time::SystemTime::UNIX_EPOCH));
+
+        // Non-host platform pulls are indexed as `<reference>|<platform>`
+        let (image_ref, platform) = match reference.split_once('|') {
+            Some((image_ref, platform)) => (image_ref, Some(platform.to_string())),
+            None => (reference.as_str(), None),
+        };
+        let (repository, tag) = match Reference::from_str(image_ref) {
+            Ok(r) => (
+                r.repository().to_string(),
+                r.tag().unwrap_or("latest").to_string(),
+            ),
+            Err(_) => {
+                // Fallback if reference stored in DB is invalid
+                (image_ref.to_string(), "<none>".to_string())
+            }
+        };
+
+        ImageInfo {
+            reference,
+            repository,
+            tag,
+            platform,
+            id: cached.manifest_digest.clone(),
+            cached_at,
+            size: Some(Bytes::from_bytes(self.store.disk_size(cached).await)),
+            layer_count: cached.layers.len(),
+        }
+    }
+
+    /// Create a local image from `parent` with `layer` on top.
+    ///
+    /// The image is indexed under `image_ref` and resolves like a pulled
+    /// image afterwards, so boxes can use it as `RootfsSpec::Image`.
+    /// `created_by` is recorded in the image history for the new layer.
+    pub(crate) async fn commit(
+        &self,
+        image_ref: &str,
+        parent: &ImageObject,
+        layer: LayerBlob,
+        config: ImageConfiguration,
+        created_by: &str,
+    ) -> BoxliteResult<ImageObject> {
+        let manifest = self
+            .store
+            .install_image(image_ref, parent.manifest(), layer, config, created_by)
+            .await?;
+
+        Ok(ImageObject::new(
+            image_ref.to_string(),
+            manifest,
+            Arc::clone(&self.store),
+        ))
+    }
+
+    /// Delete cached layers that no image references any more.
+    ///
+    /// Returns the number of layers removed.
+    pub async fn prune(&self) -> BoxliteResult<usize> {
+        self.store.prune_layers().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::ImageIndexStore;
+    use crate::images::storage::ImageStorage;
+
+    #[tokio::test]
+    async fn test_cached_image_is_listed_and_inspected() {
+        let dir = tempfile::tempdir().unwrap();
+        let images_dir = dir.path().join("images");
+        let db = Database::open(&dir.path().join("test.db")).unwrap();
+        let manager = ImageManager::new(
+            images_dir.clone(),
+            db.clone(),
+            Vec::new(),
+            RetryPolicy::default(),
+        )
+        .unwrap();
+
+        // Fake a pulled image: its blobs on disk plus an index entry
+        let storage = ImageStorage::new(images_dir).unwrap();
+        std::fs::write(storage.layer_tarball_path("sha256:layer"), vec![0u8; 4096]).unwrap();
+        std::fs::write(
+            storage.config_path("sha256:config"),
+            r#"{
+                "architecture": "amd64",
+                "os": "linux",
+                "config": {"Cmd": ["/bin/sh"]},
+                "rootfs": {"type": "layers", "diff_ids": ["sha256:diff"]}
+            }"#,
+        )
+        .unwrap();
+        let index = ImageIndexStore::new(db);
+        index
+            .upsert(
+                "docker.io/library/alpine:3.20",
+                &CachedImage {
+                    manifest_digest: "sha256:manifest".to_string(),
+                    config_digest: "sha256:config".to_string(),
+                    layers: vec!["sha256:layer".to_string()],
+                    cached_at: Utc::now().to_rfc3339(),
+                    complete: true,
+                },
+            )
+            .unwrap();
+
+        let images = manager.list().await.unwrap();
+        assert_eq!(images.len(), 1);
+        let info = &images[0];
+        assert_eq!(info.reference, "docker.io/library/alpine:3.20");
+        assert_eq!(info.tag, "3.20");
+        assert_eq!(info.platform, None);
+        assert_eq!(info.id, "sha256:manifest");
+        assert_eq!(info.layer_count, 1);
+        assert!(info.size.unwrap().as_bytes() > 4096);
+
+        // Short references resolve as they would for a pull
+        let details = manager.inspect("alpine:3.20").await.unwrap();
+        assert_eq!(details.info.reference, info.reference);
+        assert_eq!(details.layers, vec!["sha256:layer".to_string()]);
+        assert_eq!(
+            details.config.config().as_ref().unwrap().cmd(),
+            &Some(vec!["/bin/sh".to_string()])
+        );
+
+        assert!(matches!(
+            manager.inspect("alpine:edge").await,
+            Err(BoxliteError::NotFound(_))
+        ));
+
+        // Pulls for another platform keep the reference parseable
+        index
+            .upsert(
+                "docker.io/library/alpine:3.20|linux/riscv64",
+                &CachedImage {
+                    manifest_digest: "sha256:riscv-manifest".to_string(),
+                    config_digest: "sha256:config".to_string(),
+                    layers: vec!["sha256:layer".to_string()],
+                    cached_at: Utc::now().to_rfc3339(),
+                    complete: true,
+                },
+            )
+            .unwrap();
+        let images = manager.list().await.unwrap();
+        let riscv = images
+            .iter()
+            .find(|image| image.id == "sha256:riscv-manifest")
+            .unwrap();
+        assert_eq!(
+            riscv.reference,
+            "docker.io/library/alpine:3.20|linux/riscv64"
+        );
+        let host = images
+            .iter()
+            .find(|image| image.platform.is_none())
+            .unwrap();
+        assert_eq!(riscv.repository, host.repository);
+        assert_eq!(riscv.tag, "3.20");
+        assert_eq!(riscv.platform.as_deref(), Some("linux/riscv64"));
+    }
 }
@@ -1,14 +1,25 @@
 mod archive;
 mod config;
+mod layer;
 mod manager;
 mod object;
+mod platform;
+mod progress;
+mod retry;
 mod storage;
 mod store;
 
 pub use archive::extract_layer_tarball_streaming;
 pub use config::ContainerImageConfig;
-pub use manager::ImageManager;
+pub(crate) use config::ImageConfigChanges;
+pub(crate) use layer::write_diff_layer;
+pub use manager::{ImageManager, PullOptions};
 pub use object::ImageObject;
+pub use platform::Platform;
+pub use progress::{PullProgress, PullProgressFn};
+pub use retry::RetryPolicy;
+#[cfg(test)]
+pub(crate) use storage::disk_image_digest;
 
 use oci_client::Reference;
 
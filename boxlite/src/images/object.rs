@@ -7,6 +7,7 @@
 use std::path::PathBuf;
 
 use super::manager::ImageManifest;
+use crate::images::storage::disk_image_digest;
 use crate::images::store::SharedImageStore;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
@@ -51,6 +52,10 @@ impl ImageObject {
     // METADATA OPERATIONS
     // ========================================================================
 
+    pub(super) fn manifest(&self) -> &ImageManifest {
+        &self.manifest
+    }
+
     /// Get the image reference (e.g., "python:alpine")
     #[allow(dead_code)]
     pub fn reference(&self) -> &str {
@@ -67,6 +72,11 @@ impl ImageObject {
             .collect()
     }
 
+    /// Get manifest digest (the image ID)
+    pub fn manifest_digest(&self) -> &str {
+        &self.manifest.manifest_digest
+    }
+
     /// Get config digest
     #[allow(dead_code)]
     pub fn config_digest(&self) -> &str {
@@ -163,13 +173,7 @@ impl ImageObject {
     /// This is used as a cache key for base disks - same layers = same base disk.
     /// Uses SHA256 hash of concatenated layer digests.
     pub(crate) fn compute_image_digest(&self) -> String {
-        use sha2::{Digest, Sha256};
-
-        let mut hasher = Sha256::new();
-        for layer in &self.manifest.layers {
-            hasher.update(layer.digest.as_bytes());
-        }
-        format!("sha256:{:x}", hasher.finalize())
+        disk_image_digest(self.manifest.layers.iter().map(|l| l.digest.as_str()))
     }
 
     /// Get existing disk image if available.
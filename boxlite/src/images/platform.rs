@@ -0,0 +1,149 @@
+//! Platform selection for multi-arch images.
+
+use std::fmt;
+use std::str::FromStr;
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use oci_client::manifest::{ImageIndexEntry, OciImageIndex};
+
+/// Target platform of an image, e.g. `linux/arm64` or `linux/arm/v7`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Platform {
+    pub os: String,
+    /// OCI (GOARCH) architecture name, e.g. `amd64`, `arm64`.
+    pub architecture: String,
+    /// CPU variant, e.g. `v7`. `None` matches any variant.
+    pub variant: Option<String>,
+}
+
+impl Platform {
+    /// Platform of the host, which guests run on natively.
+    pub fn host() -> Self {
+        let architecture = match std::env::consts::ARCH {
+            "aarch64" => "arm64",
+            "x86_64" => "amd64",
+            "x86" => "386",
+            other => other,
+        };
+        Self {
+            os: "linux".to_string(),
+            architecture: architecture.to_string(),
+            variant: None,
+        }
+    }
+
+    fn matches(&self, entry: &ImageIndexEntry) -> bool {
+        let Some(p) = &entry.platform else {
+            return false;
+        };
+        p.os == self.os
+            && p.architecture == self.architecture
+            && (self.variant.is_none() || p.variant == self.variant)
+    }
+}
+
+impl FromStr for Platform {
+    type Err = BoxliteError;
+
+    /// Parse `os/arch` or `os/arch/variant`.
+    fn from_str(s: &str) -> BoxliteResult<Self> {
+        let parts: Vec<&str> = s.split('/').collect();
+        match parts.as_slice() {
+            [os, arch] | [os, arch, _] if !os.is_empty() && !arch.is_empty() => Ok(Self {
+                os: os.to_string(),
+                architecture: arch.to_string(),
+                variant: parts
+                    .get(2)
+                    .filter(|v| !v.is_empty())
+                    .map(|v| v.to_string()),
+            }),
+            _ => Err(BoxliteError::InvalidArgument(format!(
+                "invalid platform {:?}: expected os/arch or os/arch/variant, e.g. linux/arm64",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.os, self.architecture)?;
+        if let Some(variant) = &self.variant {
+            write!(f, "/{}", variant)?;
+        }
+        Ok(())
+    }
+}
+
+/// Pick the manifest for `platform` from a multi-arch image index.
+pub(crate) fn select_manifest<'a>(
+    index: &'a OciImageIndex,
+    platform: &Platform,
+) -> BoxliteResult<&'a ImageIndexEntry> {
+    index
+        .manifests
+        .iter()
+        .find(|m| platform.matches(m))
+        .ok_or_else(|| {
+            let available = index
+                .manifests
+                .iter()
+                .filter_map(|m| {
+                    m.platform.as_ref().map(|p| match &p.variant {
+                        Some(v) => format!("{}/{}/{}", p.os, p.architecture, v),
+                        None => format!("{}/{}", p.os, p.architecture),
+                    })
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            BoxliteError::Image(format!(
+                "no image found for platform {}. Available platforms: {}",
+                platform, available
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(digest: &str, arch: &str, variant: Option<&str>) -> serde_json::Value {
+        serde_json::json!({
+            "mediaType": "application/vnd.oci.image.manifest.v1+json",
+            "digest": digest,
+            "size": 1024,
+            "platform": { "os": "linux", "architecture": arch, "variant": variant }
+        })
+    }
+
+    #[test]
+    fn test_select_arm64_from_index() {
+        let index: OciImageIndex = serde_json::from_value(serde_json::json!({
+            "schemaVersion": 2,
+            "manifests": [
+                entry("sha256:amd64", "amd64", None),
+                entry("sha256:armv7", "arm", Some("v7")),
+                entry("sha256:arm64", "arm64", Some("v8")),
+            ]
+        }))
+        .unwrap();
+
+        let arm64: Platform = "linux/arm64".parse().unwrap();
+        assert_eq!(
+            select_manifest(&index, &arm64).unwrap().digest,
+            "sha256:arm64"
+        );
+
+        let armv7: Platform = "linux/arm/v7".parse().unwrap();
+        assert_eq!(
+            select_manifest(&index, &armv7).unwrap().digest,
+            "sha256:armv7"
+        );
+
+        let err = select_manifest(&index, &"linux/s390x".parse().unwrap()).unwrap_err();
+        assert!(matches!(err, BoxliteError::Image(_)), "{err}");
+        assert!(err.to_string().contains("linux/arm/v7"), "{err}");
+
+        assert!("linux".parse::<Platform>().is_err());
+    }
+}
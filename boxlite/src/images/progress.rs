@@ -0,0 +1,124 @@
+//! Progress reporting for image pulls.
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::AsyncWrite;
+
+/// Progress of one layer during an image pull.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PullProgress {
+    /// Digest of the layer this event is about.
+    pub layer_digest: String,
+    /// Position of the layer in the image, bottom layer first.
+    pub layer_index: usize,
+    /// Number of layers in the image.
+    pub layer_count: usize,
+    /// Bytes of the layer downloaded so far. Restarts from zero when a
+    /// failed download is retried.
+    pub bytes_done: u64,
+    /// Compressed layer size from the manifest.
+    pub bytes_total: u64,
+}
+
+impl PullProgress {
+    /// Whether the layer is fully downloaded (or was already cached).
+    pub fn is_complete(&self) -> bool {
+        self.bytes_done >= self.bytes_total
+    }
+}
+
+/// Callback invoked with pull progress. Layers download concurrently, so
+/// events for different layers interleave.
+pub type PullProgressFn<'a> = dyn Fn(PullProgress) + Send + Sync + 'a;
+
+/// `AsyncWrite` adapter reporting the bytes written through it.
+pub(crate) struct ProgressWriter<'a, W> {
+    inner: W,
+    progress: &'a PullProgressFn<'a>,
+    event: PullProgress,
+}
+
+impl<'a, W> ProgressWriter<'a, W> {
+    /// `event` describes the layer being written; its `bytes_done` is the
+    /// starting count.
+    pub(crate) fn new(inner: W, progress: &'a PullProgressFn<'a>, event: PullProgress) -> Self {
+        Self {
+            inner,
+            progress,
+            event,
+        }
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for ProgressWriter<'_, W> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = &mut *self;
+        let poll = Pin::new(&mut this.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(n)) = poll
+            && n > 0
+        {
+            this.event.bytes_done += n as u64;
+            (this.progress)(this.event.clone());
+        }
+        poll
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tokio::io::AsyncWriteExt;
+
+    #[tokio::test]
+    async fn test_progress_is_reported_per_layer_with_increasing_bytes() {
+        let events = Mutex::new(Vec::new());
+        let record = |p: PullProgress| events.lock().unwrap().push(p);
+        let layers = [("sha256:base", 3usize), ("sha256:app", 2usize)];
+
+        // Fake pull: each layer arrives in 4 KiB chunks
+        for (index, (digest, chunks)) in layers.iter().enumerate() {
+            let total = (*chunks as u64) * 4096;
+            let mut sink = Vec::new();
+            let mut writer = ProgressWriter::new(
+                &mut sink,
+                &record,
+                PullProgress {
+                    layer_digest: digest.to_string(),
+                    layer_index: index,
+                    layer_count: layers.len(),
+                    bytes_done: 0,
+                    bytes_total: total,
+                },
+            );
+            for _ in 0..*chunks {
+                writer.write_all(&[0u8; 4096]).await.unwrap();
+            }
+            writer.flush().await.unwrap();
+            assert_eq!(sink.len() as u64, total);
+        }
+
+        let events = events.into_inner().unwrap();
+        for (index, (digest, _)) in layers.iter().enumerate() {
+            let layer: Vec<_> = events.iter().filter(|e| e.layer_index == index).collect();
+            assert!(!layer.is_empty());
+            assert!(layer.iter().all(|e| e.layer_digest == *digest));
+            assert!(layer.windows(2).all(|w| w[0].bytes_done < w[1].bytes_done));
+            assert!(layer.last().unwrap().is_complete());
+        }
+    }
+}
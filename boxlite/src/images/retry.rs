@@ -0,0 +1,170 @@
+//! Retry with exponential backoff for registry requests.
+//!
+//! Connection failures and 5xx responses are usually transient (registry
+//! restarts, rate-limited CDNs, flaky networks) and are retried. Client
+//! errors such as 404 or 401 will not change on retry and fail immediately.
+
+use std::fmt::Display;
+use std::future::Future;
+use std::time::Duration;
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use oci_client::errors::OciDistributionError;
+
+/// How often and how patiently registry requests are retried.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts per request, the first one included. Values below 1
+    /// are treated as 1.
+    ///
+    /// Default: 4
+    pub max_attempts: u32,
+
+    /// Delay before the first retry; doubled for each further retry.
+    ///
+    /// Default: 500ms
+    pub initial_backoff: Duration,
+
+    /// Upper bound of the delay between two attempts.
+    ///
+    /// Default: 10s
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay after failed attempt number `attempt` (1-based).
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Run `op` until it succeeds, fails with an error `is_transient` rejects,
+/// or `policy.max_attempts` is used up.
+///
+/// The final error is a `BoxliteError::Image` naming `what` and the number
+/// of attempts made.
+pub(crate) async fn retry<T, E, F, Fut>(
+    policy: &RetryPolicy,
+    what: &str,
+    is_transient: impl Fn(&E) -> bool,
+    mut op: F,
+) -> BoxliteResult<T>
+where
+    E: Display,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < max_attempts && is_transient(&e) => {
+                let delay = policy.backoff(attempt);
+                tracing::warn!(
+                    attempt,
+                    max_attempts,
+                    delay_ms = delay.as_millis() as u64,
+                    error = %e,
+                    "{} failed, retrying",
+                    what
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                return Err(BoxliteError::Image(format!(
+                    "{} failed after {} attempt(s): {}",
+                    what, attempt, e
+                )));
+            }
+        }
+    }
+}
+
+/// Whether a registry error is worth retrying: connection problems and
+/// server-side (5xx) failures are, client errors (4xx) are not.
+pub(crate) fn is_transient(err: &OciDistributionError) -> bool {
+    match err {
+        OciDistributionError::RequestError(e) => match e.status() {
+            Some(status) => status.is_server_error(),
+            None => e.is_connect() || e.is_timeout() || e.is_request() || e.is_body(),
+        },
+        OciDistributionError::ServerError { code, .. } => *code >= 500,
+        OciDistributionError::IoError(_) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn server_error(code: u16) -> OciDistributionError {
+        OciDistributionError::ServerError {
+            code,
+            url: "https://registry.example/v2/library/alpine/blobs/sha256:abc".into(),
+            message: String::new(),
+        }
+    }
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(2),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transient_failures_are_retried_until_success() {
+        let calls = AtomicU32::new(0);
+        let result = retry(&fast_policy(), "pull layer", is_transient, || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(server_error(503)),
+                _ => Ok("layer"),
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), "layer");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_not_found_is_not_retried() {
+        let calls = AtomicU32::new(0);
+        let err = retry(&fast_policy(), "pull manifest", is_transient, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(server_error(404))
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(matches!(err, BoxliteError::Image(_)), "{err}");
+        assert!(err.to_string().contains("after 1 attempt(s)"), "{err}");
+
+        // Exhausted retries report how many attempts were made
+        let err = retry(&fast_policy(), "pull manifest", is_transient, || async {
+            Err::<(), _>(server_error(502))
+        })
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("after 4 attempt(s)"), "{err}");
+    }
+}
@@ -9,6 +9,7 @@
 //! - Registry communication (ImageManager's responsibility)
 //! - Cache lookup logic (ImageManager's responsibility)
 
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 use oci_client::manifest::OciManifest;
@@ -268,6 +269,67 @@ impl ImageStorage {
         Ok(())
     }
 
+    /// Digests of all layers on disk, whether as tarball, extracted
+    /// directory or both. In-progress downloads and extractions are skipped.
+    ///
+    /// **Mutability**: Immutable - reads directories only, no state changes.
+    pub fn layer_digests(&self) -> BoxliteResult<HashSet<String>> {
+        let mut digests = HashSet::new();
+        for dir in [self.layout.layers_dir(), self.layout.extracted_dir()] {
+            let entries = match std::fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => {
+                    return Err(BoxliteError::Storage(format!(
+                        "Failed to read {}: {}",
+                        dir.display(),
+                        e
+                    )));
+                }
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name();
+                let Some(name) = name.to_str() else {
+                    continue;
+                };
+                let stem = name.strip_suffix(".tar.gz").unwrap_or(name);
+                if stem.contains('-') && !stem.contains('.') {
+                    digests.insert(stem.replacen('-', ":", 1));
+                }
+            }
+        }
+        Ok(digests)
+    }
+
+    /// Delete a layer's tarball and extracted directory.
+    ///
+    /// **Mutability**: Mutating - callers must ensure no image references
+    /// the layer.
+    pub fn remove_layer(&self, digest: &str) -> BoxliteResult<()> {
+        let tarball = self.layer_tarball_path(digest);
+        if let Err(e) = std::fs::remove_file(&tarball)
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            return Err(BoxliteError::Storage(format!(
+                "Failed to remove layer tarball {}: {}",
+                tarball.display(),
+                e
+            )));
+        }
+
+        let extracted = self.layer_extracted_path(digest);
+        if let Err(e) = std::fs::remove_dir_all(&extracted)
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            return Err(BoxliteError::Storage(format!(
+                "Failed to remove extracted layer {}: {}",
+                extracted.display(),
+                e
+            )));
+        }
+        Ok(())
+    }
+
     /// Start a staged download for a layer blob.
     ///
     /// **Mutability**: Atomic - creates unique temp file with random suffix.
@@ -427,7 +489,6 @@ impl ImageStorage {
     /// Get the layers directory path.
     ///
     /// **Mutability**: Immutable - returns path to layers directory.
-    #[allow(unused)]
     pub fn layer_dir(&self) -> PathBuf {
         self.layout.layers_dir()
     }
@@ -443,6 +504,27 @@ impl ImageStorage {
             .join(format!("{}.{}", filename, format.as_str()))
     }
 
+    /// Delete the disk images cached for an image digest, in every format.
+    pub fn remove_disk_images(&self, image_digest: &str) -> BoxliteResult<()> {
+        use crate::disk::DiskFormat;
+
+        for format in [DiskFormat::Ext4, DiskFormat::Qcow2] {
+            let path = self.disk_image_path(image_digest, format);
+            match std::fs::remove_file(&path) {
+                Ok(()) => tracing::debug!("Removed disk image: {}", path.display()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    return Err(BoxliteError::Storage(format!(
+                        "Failed to remove disk image {}: {}",
+                        path.display(),
+                        e
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Find existing disk image for an image digest, checking all known formats.
     ///
     /// Returns the path and format if a cached disk image exists.
@@ -468,6 +550,18 @@ impl ImageStorage {
     }
 }
 
+/// Digest a cached disk image is stored under: SHA-256 over the layer
+/// digests, in order. Same layers means same disk image.
+pub(crate) fn disk_image_digest<'a>(layers: impl IntoIterator<Item = &'a str>) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    for layer in layers {
+        hasher.update(layer.as_bytes());
+    }
+    format!("sha256:{:x}", hasher.finalize())
+}
+
 // ============================================================================
 // STAGED DOWNLOAD
 // ============================================================================
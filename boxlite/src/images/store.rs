@@ -14,12 +14,23 @@
 //! - `layer_extracted()` - Get extracted layer path (extracts if needed)
 
 use crate::db::{CachedImage, Database, ImageIndexStore};
-use crate::images::manager::{ImageManifest, LayerInfo};
-use crate::images::storage::ImageStorage;
+use crate::images::layer::LayerBlob;
+use crate::images::manager::{ImageManifest, LayerInfo, PullOptions};
+use crate::images::platform::{Platform, select_manifest};
+use crate::images::progress::{ProgressWriter, PullProgress, PullProgressFn};
+use crate::images::retry::{RetryPolicy, is_transient, retry};
+use crate::images::storage::{ImageStorage, disk_image_digest};
 use boxlite_shared::{BoxliteError, BoxliteResult};
 use oci_client::Reference;
-use oci_client::manifest::OciDescriptor;
+use oci_client::errors::OciDistributionError;
+use oci_client::manifest::{
+    IMAGE_CONFIG_MEDIA_TYPE, IMAGE_LAYER_GZIP_MEDIA_TYPE, OCI_IMAGE_MEDIA_TYPE, OciDescriptor,
+    OciManifest,
+};
 use oci_client::secrets::RegistryAuth;
+use oci_spec::image::{HistoryBuilder, ImageConfiguration};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -66,7 +77,7 @@ impl ImageStoreInner {
 /// let store = Arc::new(ImageStore::new(images_dir)?);
 ///
 /// // Pull image (thread-safe, releases lock during download)
-/// let manifest = store.pull("python:alpine").await?;
+/// let manifest = store.pull("python:alpine", &PullOptions::default()).await?;
 ///
 /// // Access layer data
 /// let tarball = store.layer_tarball(&manifest.layers[0].digest);
@@ -80,6 +91,11 @@ pub struct ImageStore {
     /// Registries to search for unqualified image references.
     /// Tried in order; first successful pull wins.
     registries: Vec<String>,
+    /// Retry policy for manifest and blob requests.
+    retry: RetryPolicy,
+    /// Held shared by registry pulls and exclusively by `prune_layers()`, so
+    /// layers downloaded before their image is indexed are never pruned.
+    prune_gate: RwLock<()>,
 }
 
 impl std::fmt::Debug for ImageStore {
@@ -95,12 +111,20 @@ impl ImageStore {
     /// * `images_dir` - Directory for image cache
     /// * `db` - Database for image index
     /// * `registries` - Registries to search for unqualified images (tried in order)
-    pub fn new(images_dir: PathBuf, db: Database, registries: Vec<String>) -> BoxliteResult<Self> {
+    /// * `retry` - Retry policy for registry requests
+    pub fn new(
+        images_dir: PathBuf,
+        db: Database,
+        registries: Vec<String>,
+        retry: RetryPolicy,
+    ) -> BoxliteResult<Self> {
         let inner = ImageStoreInner::new(images_dir, db)?;
         Ok(Self {
             client: oci_client::Client::new(Default::default()),
             inner: RwLock::new(inner),
             registries,
+            retry,
+            prune_gate: RwLock::new(()),
         })
     }
 
@@ -118,7 +142,14 @@ impl ImageStore {
     ///
     /// Thread-safe: Multiple concurrent pulls of the same image will only
     /// download once; others will get the cached result.
-    pub async fn pull(&self, image_ref: &str) -> BoxliteResult<ImageManifest> {
+    ///
+    /// Multi-arch images resolve to `options.platform`, or the host platform
+    /// if unset. `options.progress`, if given, is called as layer bytes arrive.
+    pub async fn pull(
+        &self,
+        image_ref: &str,
+        options: &PullOptions<'_>,
+    ) -> BoxliteResult<ImageManifest> {
         use super::ReferenceIter;
 
         tracing::debug!(
@@ -131,15 +162,21 @@ impl ImageStore {
         let candidates = ReferenceIter::new(image_ref, &self.registries)
             .map_err(|e| BoxliteError::Storage(format!("invalid image reference: {e}")))?;
 
+        let platform = match &options.platform {
+            Some(platform) => platform.parse::<Platform>()?,
+            None => Platform::host(),
+        };
+
         let mut errors: Vec<(String, BoxliteError)> = Vec::new();
 
         for reference in candidates {
             let ref_str = reference.whole();
+            let cache_key = Self::cache_key(&reference, &platform);
 
             // Fast path: check cache with read lock
             {
                 let inner = self.inner.read().await;
-                if let Some(manifest) = self.try_load_cached(&inner, &ref_str)? {
+                if let Some(manifest) = self.try_load_cached(&inner, &cache_key)? {
                     tracing::info!("Using cached image: {}", ref_str);
                     return Ok(manifest);
                 }
@@ -147,7 +184,10 @@ impl ImageStore {
 
             // Slow path: pull from registry
             tracing::info!("Pulling image from registry: {}", ref_str);
-            match self.pull_from_registry(&reference).await {
+            match self
+                .pull_from_registry(&reference, &cache_key, &platform, options.progress)
+                .await
+            {
                 Ok(manifest) => {
                     if !errors.is_empty() {
                         tracing::info!(
@@ -182,7 +222,7 @@ impl ImageStore {
                 .map(|(registry, err)| format!("  - {}: {}", registry, err))
                 .collect();
 
-            Err(BoxliteError::Storage(format!(
+            let message = format!(
                 "Failed to pull image '{}' after trying {} {}:\n{}",
                 image_ref,
                 errors.len(),
@@ -192,7 +232,16 @@ impl ImageStore {
                     "registries"
                 },
                 details.join("\n")
-            )))
+            );
+            // Registry-side failures (e.g. a missing platform) stay image errors
+            if errors
+                .iter()
+                .all(|(_, e)| matches!(e, BoxliteError::Image(_)))
+            {
+                Err(BoxliteError::Image(message))
+            } else {
+                Err(BoxliteError::Storage(message))
+            }
         }
     }
 
@@ -204,6 +253,53 @@ impl ImageStore {
         inner.index.list_all()
     }
 
+    /// Find the cached image `image_ref` resolves to, without pulling.
+    ///
+    /// `image_ref` is matched against the index as stored, then as each
+    /// registry candidate would be cached when pulled for `platform` (the
+    /// host platform if `None`). Returns the matching index key.
+    pub async fn find(
+        &self,
+        image_ref: &str,
+        platform: Option<&str>,
+    ) -> BoxliteResult<Option<(String, CachedImage)>> {
+        use super::ReferenceIter;
+
+        let inner = self.inner.read().await;
+        if let Some(cached) = inner.index.get(image_ref)? {
+            return Ok(Some((image_ref.to_string(), cached)));
+        }
+        let Ok(candidates) = ReferenceIter::new(image_ref, &self.registries) else {
+            return Ok(None);
+        };
+        let platform = match platform {
+            Some(platform) => platform.parse::<Platform>()?,
+            None => Platform::host(),
+        };
+        for reference in candidates {
+            let key = Self::cache_key(&reference, &platform);
+            if let Some(cached) = inner.index.get(&key)? {
+                return Ok(Some((key, cached)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Bytes the image's layer tarballs and config take on disk.
+    ///
+    /// Blobs that are missing count as zero.
+    pub async fn disk_size(&self, image: &CachedImage) -> u64 {
+        let inner = self.inner.read().await;
+        let blob_size = |path: PathBuf| std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+
+        let layers: u64 = image
+            .layers
+            .iter()
+            .map(|digest| blob_size(inner.storage.layer_tarball_path(digest)))
+            .sum();
+        layers + blob_size(inner.storage.config_path(&image.config_digest))
+    }
+
     /// Load config JSON for an image.
     ///
     /// Returns the raw JSON string. Use `serde_json::from_str()` to parse.
@@ -271,6 +367,199 @@ impl ImageStore {
             .collect()
     }
 
+    /// Delete layers no cached image references.
+    ///
+    /// Layers are stored once per digest and shared by every image that
+    /// lists them, so a layer is only removed once its reference count in
+    /// the image index drops to zero. Waits for in-flight pulls to finish.
+    ///
+    /// Returns the number of layers removed.
+    pub async fn prune_layers(&self) -> BoxliteResult<usize> {
+        let _gate = self.prune_gate.write().await;
+        let inner = self.inner.write().await;
+
+        let refcounts = inner.index.layer_refcounts()?;
+        let mut removed = 0;
+        for digest in inner.storage.layer_digests()? {
+            if refcounts.contains_key(&digest) {
+                continue;
+            }
+            tracing::debug!("Pruning unreferenced layer: {}", digest);
+            inner.storage.remove_layer(&digest)?;
+            removed += 1;
+        }
+
+        if removed > 0 {
+            tracing::info!("Pruned {} unreferenced layers", removed);
+        }
+        Ok(removed)
+    }
+
+    /// Remove the image indexed under `key` along with blobs only it uses.
+    ///
+    /// Layers still listed by another cached image are kept, as are the
+    /// manifest and config when the same image is cached under another
+    /// reference. The disk image built from the layers goes too, unless
+    /// `keep_disk` is set (boxes still use it) or another cached image has
+    /// the same layers. Waits for in-flight pulls to finish.
+    ///
+    /// Returns the number of layers removed.
+    pub async fn remove(&self, key: &str, keep_disk: bool) -> BoxliteResult<usize> {
+        let _gate = self.prune_gate.write().await;
+        let inner = self.inner.write().await;
+
+        let Some(image) = inner.index.get(key)? else {
+            return Err(BoxliteError::NotFound(format!("image {}", key)));
+        };
+        inner.index.remove(key)?;
+
+        let refcounts = inner.index.layer_refcounts()?;
+        let mut removed = 0;
+        let layers: HashSet<&String> = image.layers.iter().collect();
+        for digest in layers {
+            if refcounts.contains_key(digest) {
+                continue;
+            }
+            inner.storage.remove_layer(digest)?;
+            removed += 1;
+        }
+
+        let others = inner.index.list_all()?;
+        if !others
+            .iter()
+            .any(|(_, other)| other.manifest_digest == image.manifest_digest)
+        {
+            let _ = std::fs::remove_file(inner.storage.manifest_path(&image.manifest_digest));
+        }
+        if !others
+            .iter()
+            .any(|(_, other)| other.config_digest == image.config_digest)
+        {
+            let _ = std::fs::remove_file(inner.storage.config_path(&image.config_digest));
+        }
+        if !keep_disk && !others.iter().any(|(_, other)| other.layers == image.layers) {
+            inner
+                .storage
+                .remove_disk_images(&disk_image_digest(image.layers.iter().map(String::as_str)))?;
+        }
+
+        tracing::info!(image = %key, layers = removed, "Removed image");
+        Ok(removed)
+    }
+
+    /// Register a local image made of `parent`'s layers plus `layer`.
+    ///
+    /// Moves the layer tarball into the store, writes `config` (with the
+    /// layer's diff ID appended and a history entry recording `created_by`)
+    /// and a new manifest, then indexes the image under `image_ref`,
+    /// replacing whatever was cached under it before.
+    pub async fn install_image(
+        &self,
+        image_ref: &str,
+        parent: &ImageManifest,
+        layer: LayerBlob,
+        mut config: ImageConfiguration,
+        created_by: &str,
+    ) -> BoxliteResult<ImageManifest> {
+        use super::ReferenceIter;
+
+        let cache_key = ReferenceIter::new(image_ref, &self.registries)
+            .map_err(|e| BoxliteError::InvalidArgument(format!("invalid image reference: {e}")))?
+            .next()
+            .map(|reference| reference.whole())
+            .ok_or_else(|| {
+                BoxliteError::InvalidArgument(format!("invalid image reference: {}", image_ref))
+            })?;
+
+        // Keep prune_layers() from deleting the new layer until it is indexed
+        let _gate = self.prune_gate.read().await;
+        let manifest = {
+            let inner = self.inner.read().await;
+            let OciManifest::Image(mut image) =
+                inner.storage.load_manifest(&parent.manifest_digest)?
+            else {
+                return Err(BoxliteError::Storage(
+                    "cached manifest is not a simple image".into(),
+                ));
+            };
+
+            let layer_path = inner.storage.layer_tarball_path(&layer.digest);
+            crate::util::move_file(&layer.path, &layer_path).map_err(|e| {
+                BoxliteError::Storage(format!(
+                    "Failed to install layer {} to {}: {}",
+                    layer.path.display(),
+                    layer_path.display(),
+                    e
+                ))
+            })?;
+
+            config
+                .rootfs_mut()
+                .diff_ids_mut()
+                .push(layer.diff_id.clone());
+            let created = chrono::Utc::now().to_rfc3339();
+            config.set_created(Some(created.clone()));
+            if let Some(history) = config.history_mut()
+                && let Ok(entry) = HistoryBuilder::default()
+                    .created(created)
+                    .created_by(created_by)
+                    .build()
+            {
+                history.push(entry);
+            }
+            let config_json = serde_json::to_vec(&config).map_err(|e| {
+                BoxliteError::Internal(format!("Failed to serialize image config: {}", e))
+            })?;
+            let config_digest = format!("sha256:{:x}", Sha256::digest(&config_json));
+            let config_path = inner.storage.config_path(&config_digest);
+            std::fs::write(&config_path, &config_json).map_err(|e| {
+                BoxliteError::Storage(format!(
+                    "Failed to write config {}: {}",
+                    config_path.display(),
+                    e
+                ))
+            })?;
+
+            image.media_type = Some(OCI_IMAGE_MEDIA_TYPE.to_string());
+            image.config = OciDescriptor {
+                media_type: IMAGE_CONFIG_MEDIA_TYPE.to_string(),
+                digest: config_digest.clone(),
+                size: config_json.len() as i64,
+                urls: None,
+                annotations: None,
+            };
+            image.layers.push(OciDescriptor {
+                media_type: IMAGE_LAYER_GZIP_MEDIA_TYPE.to_string(),
+                digest: layer.digest.clone(),
+                size: layer.size as i64,
+                urls: None,
+                annotations: None,
+            });
+            let manifest_json = serde_json::to_vec(&image).map_err(|e| {
+                BoxliteError::Internal(format!("Failed to serialize manifest: {}", e))
+            })?;
+            let manifest_digest = format!("sha256:{:x}", Sha256::digest(&manifest_json));
+            let layers = Self::layers_from_image(&image);
+            inner
+                .storage
+                .save_manifest(&OciManifest::Image(image), &manifest_digest)?;
+
+            ImageManifest {
+                manifest_digest,
+                layers,
+                config_digest,
+            }
+        };
+
+        self.update_index(&cache_key, &manifest).await?;
+        tracing::info!(
+            image = %cache_key,
+            layer = %layer.digest,
+            "Installed local image"
+        );
+        Ok(manifest)
+    }
+
     /// Get existing disk image for an image digest if available.
     ///
     /// Returns a persistent Disk if the cached disk image exists, None otherwise.
@@ -326,8 +615,8 @@ impl ImageStore {
 
         let source_path = disk.path().to_path_buf();
 
-        // Atomic rename (move) - works if on same filesystem
-        std::fs::rename(&source_path, &target_path).map_err(|e| {
+        // Atomic rename (move), or a copy if temp is on another filesystem
+        crate::util::move_file(&source_path, &target_path).map_err(|e| {
             BoxliteError::Storage(format!(
                 "Failed to install disk image from {} to {}: {}",
                 source_path.display(),
@@ -439,13 +728,25 @@ impl ImageStore {
     ///
     /// This method handles the actual network I/O - manifest pull, layer download, etc.
     /// Lock is released during network I/O to allow other operations.
-    async fn pull_from_registry(&self, reference: &Reference) -> BoxliteResult<ImageManifest> {
+    async fn pull_from_registry(
+        &self,
+        reference: &Reference,
+        cache_key: &str,
+        platform: &Platform,
+        progress: Option<&PullProgressFn<'_>>,
+    ) -> BoxliteResult<ImageManifest> {
+        // Keep prune_layers() from deleting layers until the image is indexed
+        let _gate = self.prune_gate.read().await;
+
         // Step 1: Pull manifest (no lock needed - uses self.client)
-        let (manifest, manifest_digest_str) = self
-            .client
-            .pull_manifest(reference, &RegistryAuth::Anonymous)
-            .await
-            .map_err(|e| BoxliteError::Storage(format!("failed to pull manifest: {e}")))?;
+        let auth = RegistryAuth::Anonymous;
+        let (manifest, manifest_digest_str) = retry(
+            &self.retry,
+            &format!("pull manifest {}", reference.whole()),
+            is_transient,
+            || self.client.pull_manifest(reference, &auth),
+        )
+        .await?;
 
         // Step 2: Save manifest (quick write lock)
         {
@@ -457,24 +758,35 @@ impl ImageStore {
 
         // Step 3: Extract image manifest (may pull platform-specific manifest for multi-platform images)
         let image_manifest = self
-            .extract_image_manifest(reference, &manifest, manifest_digest_str)
+            .extract_image_manifest(reference, &manifest, manifest_digest_str, platform)
             .await?;
 
         // Step 4: Download layers (no lock during download, atomic file writes)
-        self.download_layers(reference, &image_manifest.layers)
+        self.download_layers(reference, &image_manifest.layers, progress)
             .await?;
 
         // Step 5: Download config (no lock during download)
         self.download_config(reference, &image_manifest.config_digest)
             .await?;
 
-        // Step 6: Update index using reference.whole() as the cache key
-        self.update_index(&reference.whole(), &image_manifest)
-            .await?;
+        // Step 6: Update index under the platform-qualified cache key
+        self.update_index(cache_key, &image_manifest).await?;
 
         Ok(image_manifest)
     }
 
+    /// Index key of `reference` pulled for `platform`.
+    ///
+    /// Host-platform pulls use the plain reference, so existing caches stay
+    /// valid; other platforms get their own entry (`<ref>|linux/arm64`).
+    fn cache_key(reference: &Reference, platform: &Platform) -> String {
+        if *platform == Platform::host() {
+            reference.whole()
+        } else {
+            format!("{}|{}", reference.whole(), platform)
+        }
+    }
+
     /// Update index with newly pulled image.
     async fn update_index(&self, image_ref: &str, manifest: &ImageManifest) -> BoxliteResult<()> {
         let inner = self.inner.read().await;
@@ -502,6 +814,7 @@ impl ImageStore {
         reference: &Reference,
         manifest: &oci_client::manifest::OciManifest,
         manifest_digest: String,
+        platform: &Platform,
     ) -> BoxliteResult<ImageManifest> {
         match manifest {
             oci_client::manifest::OciManifest::Image(img) => {
@@ -514,7 +827,8 @@ impl ImageStore {
                 })
             }
             oci_client::manifest::OciManifest::ImageIndex(index) => {
-                self.extract_platform_manifest(reference, index).await
+                self.extract_platform_manifest(reference, index, platform)
+                    .await
             }
         }
     }
@@ -526,6 +840,7 @@ impl ImageStore {
             .map(|layer| LayerInfo {
                 digest: layer.digest.clone(),
                 media_type: layer.media_type.clone(),
+                size: layer.size.max(0) as u64,
             })
             .collect()
     }
@@ -534,17 +849,15 @@ impl ImageStore {
         &self,
         reference: &Reference,
         index: &oci_client::manifest::OciImageIndex,
+        platform: &Platform,
     ) -> BoxliteResult<ImageManifest> {
-        let (platform_os, platform_arch) = Self::detect_platform();
-
         tracing::debug!(
-            "Image index detected, selecting platform: {}/{} (Rust arch: {})",
-            platform_os,
-            platform_arch,
+            "Image index detected, selecting platform: {} (Rust arch: {})",
+            platform,
             std::env::consts::ARCH
         );
 
-        let platform_manifest = self.select_platform_manifest(index, platform_os, platform_arch)?;
+        let platform_manifest = select_manifest(index, platform)?;
 
         let platform_ref = format!("{}@{}", reference.whole(), platform_manifest.digest);
         let platform_reference: Reference = platform_ref
@@ -555,11 +868,14 @@ impl ImageStore {
             "Pulling platform-specific manifest: {}",
             platform_manifest.digest
         );
-        let (platform_image, platform_digest) = self
-            .client
-            .pull_manifest(&platform_reference, &RegistryAuth::Anonymous)
-            .await
-            .map_err(|e| BoxliteError::Storage(format!("failed to pull platform manifest: {e}")))?;
+        let auth = RegistryAuth::Anonymous;
+        let (platform_image, platform_digest) = retry(
+            &self.retry,
+            &format!("pull platform manifest {}", platform_reference.whole()),
+            is_transient,
+            || self.client.pull_manifest(&platform_reference, &auth),
+        )
+        .await?;
 
         // Save platform manifest (quick lock)
         {
@@ -585,52 +901,6 @@ impl ImageStore {
         }
     }
 
-    fn detect_platform() -> (&'static str, &'static str) {
-        let os = "linux";
-        let arch = match std::env::consts::ARCH {
-            "aarch64" => "arm64",
-            "x86_64" => "amd64",
-            "x86" => "386",
-            "arm" => "arm",
-            other => other,
-        };
-        (os, arch)
-    }
-
-    fn select_platform_manifest<'b>(
-        &self,
-        index: &'b oci_client::manifest::OciImageIndex,
-        platform_os: &str,
-        platform_arch: &str,
-    ) -> BoxliteResult<&'b oci_client::manifest::ImageIndexEntry> {
-        index
-            .manifests
-            .iter()
-            .find(|m| {
-                if let Some(p) = &m.platform {
-                    p.os == platform_os && p.architecture == platform_arch
-                } else {
-                    false
-                }
-            })
-            .ok_or_else(|| {
-                let available = index
-                    .manifests
-                    .iter()
-                    .filter_map(|m| {
-                        m.platform
-                            .as_ref()
-                            .map(|p| format!("{}/{}", p.os, p.architecture))
-                    })
-                    .collect::<Vec<_>>()
-                    .join(", ");
-                BoxliteError::Storage(format!(
-                    "no image found for platform {}/{}. Available platforms: {}",
-                    platform_os, platform_arch, available
-                ))
-            })
-    }
-
     // ========================================================================
     // INTERNAL: Layer Download (no lock during I/O)
     // ========================================================================
@@ -639,21 +909,33 @@ impl ImageStore {
         &self,
         reference: &Reference,
         layers: &[LayerInfo],
+        progress: Option<&PullProgressFn<'_>>,
     ) -> BoxliteResult<()> {
         use futures::future::join_all;
 
+        let event = |index: usize, layer: &LayerInfo, bytes_done: u64| PullProgress {
+            layer_digest: layer.digest.clone(),
+            layer_index: index,
+            layer_count: layers.len(),
+            bytes_done,
+            bytes_total: layer.size,
+        };
+
         // Check which layers need downloading (quick read lock)
         let layers_to_download: Vec<_> = {
             let inner = self.inner.read().await;
             let mut to_download = Vec::new();
-            for layer in layers {
+            for (index, layer) in layers.iter().enumerate() {
                 if !inner.storage.has_layer(&layer.digest) {
-                    to_download.push(layer.clone());
+                    to_download.push((index, layer.clone()));
                 } else {
                     // Verify cached layer
                     match inner.storage.verify_layer(&layer.digest).await {
                         Ok(true) => {
                             tracing::debug!("Layer tarball cached and verified: {}", layer.digest);
+                            if let Some(progress) = progress {
+                                progress(event(index, layer, layer.size));
+                            }
                         }
                         _ => {
                             tracing::warn!(
@@ -663,7 +945,7 @@ impl ImageStore {
                             let _ = std::fs::remove_file(
                                 inner.storage.layer_tarball_path(&layer.digest),
                             );
-                            to_download.push(layer.clone());
+                            to_download.push((index, layer.clone()));
                         }
                     }
                 }
@@ -675,15 +957,25 @@ impl ImageStore {
             return Ok(());
         }
 
+        // Fail before writing anything rather than filling the disk midway
+        let required: u64 = layers_to_download.iter().map(|(_, l)| l.size).sum();
+        let layers_dir = self.inner.read().await.storage.layer_dir();
+        crate::util::ensure_available_space(
+            &layers_dir,
+            required,
+            &format!("pulling {}", reference.whole()),
+        )?;
+
         tracing::info!(
             "Downloading {} layers in parallel",
             layers_to_download.len()
         );
 
         // Download in parallel (no lock held)
-        let download_futures = layers_to_download
-            .iter()
-            .map(|layer| self.download_layer(reference, layer));
+        let download_futures = layers_to_download.iter().map(|(index, layer)| {
+            let progress = progress.map(|p| (p, event(*index, layer, 0)));
+            self.download_layer(reference, layer, progress)
+        });
 
         let results = join_all(download_futures).await;
 
@@ -694,84 +986,34 @@ impl ImageStore {
         Ok(())
     }
 
-    async fn download_layer(&self, reference: &Reference, layer: &LayerInfo) -> BoxliteResult<()> {
-        const MAX_RETRIES: u32 = 3;
-
+    async fn download_layer(
+        &self,
+        reference: &Reference,
+        layer: &LayerInfo,
+        progress: Option<(&PullProgressFn<'_>, PullProgress)>,
+    ) -> BoxliteResult<()> {
         tracing::info!("Downloading layer: {}", layer.digest);
 
-        let mut last_error = None;
-
-        for attempt in 1..=MAX_RETRIES {
-            if attempt > 1 {
-                tracing::info!(
-                    "Retrying layer download (attempt {}/{}): {}",
-                    attempt,
-                    MAX_RETRIES,
-                    layer.digest
-                );
-            }
-
-            // Stage download (quick read lock for path computation)
-            let mut staged = {
-                let inner = self.inner.read().await;
-                match inner.storage.stage_layer_download(&layer.digest).await {
-                    Ok(result) => result,
-                    Err(e) => {
-                        last_error = Some(format!(
-                            "Failed to stage layer {} download: {e}",
-                            layer.digest
-                        ));
-                        continue;
-                    }
-                }
-            };
-
-            // Download (no lock)
-            match self
-                .client
-                .pull_blob(
-                    reference,
-                    &OciDescriptor {
-                        digest: layer.digest.clone(),
-                        media_type: layer.media_type.clone(),
-                        size: 0,
-                        urls: None,
-                        annotations: None,
-                    },
-                    staged.file(),
-                )
-                .await
-            {
-                Ok(_) => match staged.commit().await {
-                    Ok(true) => {
-                        tracing::info!("Downloaded and verified layer: {}", layer.digest);
-                        return Ok(());
-                    }
-                    Ok(false) => {
-                        tracing::warn!(
-                            "Layer integrity check failed (attempt {}): hash mismatch for {}",
-                            attempt,
-                            layer.digest
-                        );
-                        last_error =
-                            Some("layer integrity verification failed: hash mismatch".to_string());
-                    }
-                    Err(e) => {
-                        tracing::warn!("Layer commit error (attempt {}): {}", attempt, e);
-                        last_error = Some(format!("layer commit error: {e}"));
-                    }
-                },
-                Err(e) => {
-                    tracing::warn!("Layer download failed (attempt {}): {}", attempt, e);
-                    last_error = Some(format!("failed to pull layer {}: {e}", layer.digest));
-                    staged.abort().await;
-                }
-            }
-        }
-
-        Err(BoxliteError::Storage(last_error.unwrap_or_else(|| {
-            "download failed after retries".to_string()
-        })))
+        let descriptor = OciDescriptor {
+            digest: layer.digest.clone(),
+            media_type: layer.media_type.clone(),
+            size: 0,
+            urls: None,
+            annotations: None,
+        };
+        retry(
+            &self.retry,
+            &format!("download layer {}", layer.digest),
+            BlobAttemptError::is_transient,
+            || {
+                let progress = progress.as_ref().map(|(p, event)| (*p, event.clone()));
+                self.download_blob_once(reference, &descriptor, BlobKind::Layer, progress)
+            },
+        )
+        .await?;
+
+        tracing::info!("Downloaded and verified layer: {}", layer.digest);
+        Ok(())
     }
 
     async fn download_config(
@@ -790,41 +1032,107 @@ impl ImageStore {
 
         tracing::debug!("Downloading config blob: {}", config_digest);
 
-        // Start staged download (quick read lock)
+        let descriptor = OciDescriptor {
+            digest: config_digest.to_string(),
+            media_type: "application/vnd.oci.image.config.v1+json".to_string(),
+            size: 0,
+            urls: None,
+            annotations: None,
+        };
+        retry(
+            &self.retry,
+            &format!("download config {}", config_digest),
+            BlobAttemptError::is_transient,
+            || self.download_blob_once(reference, &descriptor, BlobKind::Config, None),
+        )
+        .await
+    }
+
+    /// One download attempt: stage a temp file, pull into it (no lock),
+    /// then verify and commit it to its final location.
+    async fn download_blob_once(
+        &self,
+        reference: &Reference,
+        descriptor: &OciDescriptor,
+        kind: BlobKind,
+        progress: Option<(&PullProgressFn<'_>, PullProgress)>,
+    ) -> Result<(), BlobAttemptError> {
+        // Stage download (quick read lock for path computation)
         let mut staged = {
             let inner = self.inner.read().await;
-            inner.storage.stage_config_download(config_digest).await?
+            match kind {
+                BlobKind::Layer => inner.storage.stage_layer_download(&descriptor.digest).await,
+                BlobKind::Config => {
+                    inner
+                        .storage
+                        .stage_config_download(&descriptor.digest)
+                        .await
+                }
+            }
+            .map_err(BlobAttemptError::Local)?
         };
 
-        // Download to temp file (no lock)
-        if let Err(e) = self
-            .client
-            .pull_blob(
-                reference,
-                &OciDescriptor {
-                    digest: config_digest.to_string(),
-                    media_type: "application/vnd.oci.image.config.v1+json".to_string(),
-                    size: 0,
-                    urls: None,
-                    annotations: None,
-                },
-                staged.file(),
-            )
-            .await
-        {
+        let pulled = match progress {
+            Some((progress, event)) => {
+                let writer = ProgressWriter::new(staged.file(), progress, event);
+                self.client.pull_blob(reference, descriptor, writer).await
+            }
+            None => {
+                self.client
+                    .pull_blob(reference, descriptor, staged.file())
+                    .await
+            }
+        };
+        if let Err(e) = pulled {
             staged.abort().await;
-            return Err(BoxliteError::Storage(format!("failed to pull config: {e}")));
+            return Err(BlobAttemptError::Pull(e));
         }
 
-        // Verify and commit (atomic move to final location)
-        if !staged.commit().await? {
-            return Err(BoxliteError::Storage(format!(
-                "Config blob verification failed for {}",
-                config_digest
-            )));
+        match staged.commit().await {
+            Ok(true) => Ok(()),
+            // The registry served other bytes than the digest promised
+            Ok(false) => Err(BlobAttemptError::Local(BoxliteError::Storage(format!(
+                "integrity verification failed: hash mismatch for {}",
+                descriptor.digest
+            )))),
+            Err(e) => Err(BlobAttemptError::Local(e)),
         }
+    }
+}
 
-        Ok(())
+/// Which staging area a blob download goes to.
+#[derive(Clone, Copy)]
+enum BlobKind {
+    Layer,
+    Config,
+}
+
+/// Why one blob download attempt failed.
+enum BlobAttemptError {
+    /// The registry request failed.
+    Pull(OciDistributionError),
+    /// Staging, verification or commit failed locally.
+    Local(BoxliteError),
+}
+
+impl BlobAttemptError {
+    /// Registry errors follow the usual classification, local failures
+    /// `BoxliteError::is_retryable` (a hash mismatch or a full disk is not
+    /// retried).
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::Pull(e) => is_transient(e),
+            Self::Local(e) => e.is_retryable(),
+        }
+    }
+}
+
+impl std::fmt::Display for BlobAttemptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pull(e) => write!(f, "{}", e),
+            Self::Local(e) => write!(f, "{}", e),
+        }
     }
 }
 
@@ -836,3 +1144,243 @@ impl ImageStore {
 ///
 /// Used by `ImageManager` and `ImageObject` to share the same store.
 pub type SharedImageStore = Arc<ImageStore>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_layer(storage: &ImageStorage, digest: &str, file: &str) {
+        let tarball = std::fs::File::create(storage.layer_tarball_path(digest)).unwrap();
+        let encoder = flate2::write::GzEncoder::new(tarball, flate2::Compression::fast());
+        let mut builder = tar::Builder::new(encoder);
+        let data = digest.as_bytes();
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, file, data).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    fn manifest(digest: &str, layers: &[&str]) -> ImageManifest {
+        ImageManifest {
+            manifest_digest: digest.to_string(),
+            layers: layers
+                .iter()
+                .map(|d| LayerInfo {
+                    digest: d.to_string(),
+                    media_type: "application/vnd.oci.image.layer.v1.tar+gzip".to_string(),
+                    size: 0,
+                })
+                .collect(),
+            config_digest: "sha256:config".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shared_layer_extracted_once_and_kept_while_referenced() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).unwrap();
+        let store = ImageStore::new(
+            dir.path().join("images"),
+            db,
+            Vec::new(),
+            RetryPolicy::default(),
+        )
+        .unwrap();
+
+        {
+            let inner = store.inner.read().await;
+            write_layer(&inner.storage, "sha256:base", "base.txt");
+            write_layer(&inner.storage, "sha256:app", "app.txt");
+            write_layer(&inner.storage, "sha256:tool", "tool.txt");
+        }
+        let image_a = manifest("sha256:image-a", &["sha256:base", "sha256:app"]);
+        let image_b = manifest("sha256:image-b", &["sha256:base", "sha256:tool"]);
+        store
+            .update_index("example.com/a:latest", &image_a)
+            .await
+            .unwrap();
+        store
+            .update_index("example.com/b:latest", &image_b)
+            .await
+            .unwrap();
+
+        let layers_a = store
+            .layer_extracted(vec!["sha256:base".into(), "sha256:app".into()])
+            .await
+            .unwrap();
+        // Re-extracting would drop this marker
+        std::fs::write(layers_a[0].join("marker"), b"").unwrap();
+
+        let layers_b = store
+            .layer_extracted(vec!["sha256:base".into(), "sha256:tool".into()])
+            .await
+            .unwrap();
+        assert_eq!(layers_a[0], layers_b[0]);
+        assert!(layers_b[0].join("marker").exists());
+        assert!(layers_b[0].join("base.txt").exists());
+
+        // Removing image a releases only the layer nothing else references
+        store
+            .inner
+            .read()
+            .await
+            .index
+            .remove("example.com/a:latest")
+            .unwrap();
+        assert_eq!(store.prune_layers().await.unwrap(), 1);
+        assert!(!layers_a[1].exists());
+        assert!(layers_b[0].join("marker").exists());
+        assert!(layers_b[1].exists());
+        assert!(store.layer_tarball("sha256:base").await.exists());
+        assert!(!store.layer_tarball("sha256:app").await.exists());
+    }
+
+    #[tokio::test]
+    async fn test_remove_drops_disk_image_unless_kept() {
+        use crate::disk::DiskFormat;
+
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).unwrap();
+        let store = ImageStore::new(
+            dir.path().join("images"),
+            db,
+            Vec::new(),
+            RetryPolicy::default(),
+        )
+        .unwrap();
+
+        let image = manifest("sha256:image", &["sha256:base"]);
+        for key in [
+            "example.com/a:latest",
+            "example.com/b:latest",
+            "example.com/c:latest",
+        ] {
+            store.update_index(key, &image).await.unwrap();
+        }
+        let disk = {
+            let inner = store.inner.read().await;
+            inner
+                .storage
+                .disk_image_path(&disk_image_digest(["sha256:base"]), DiskFormat::Ext4)
+        };
+        std::fs::create_dir_all(disk.parent().unwrap()).unwrap();
+        std::fs::write(&disk, b"rootfs").unwrap();
+
+        // Another reference still has the same layers
+        store.remove("example.com/a:latest", false).await.unwrap();
+        assert!(disk.exists());
+        // Boxes still use it
+        store.remove("example.com/b:latest", true).await.unwrap();
+        assert!(disk.exists());
+
+        store.remove("example.com/c:latest", false).await.unwrap();
+        assert!(!disk.exists());
+    }
+
+    #[tokio::test]
+    async fn test_installed_image_resolves_by_reference() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).unwrap();
+        let store = ImageStore::new(
+            dir.path().join("images"),
+            db,
+            Vec::new(),
+            RetryPolicy::default(),
+        )
+        .unwrap();
+
+        let config_json = r#"{
+            "architecture": "amd64",
+            "os": "linux",
+            "config": {"Env": ["PATH=/usr/bin"], "Cmd": ["/bin/sh"]},
+            "rootfs": {"type": "layers", "diff_ids": ["sha256:base-diff"]},
+            "history": [{"created_by": "ADD base.txt /"}]
+        }"#;
+        {
+            let inner = store.inner.read().await;
+            write_layer(&inner.storage, "sha256:base", "base.txt");
+            std::fs::write(inner.storage.config_path("sha256:config"), config_json).unwrap();
+            let parent = oci_client::manifest::OciImageManifest {
+                config: OciDescriptor {
+                    media_type: IMAGE_CONFIG_MEDIA_TYPE.to_string(),
+                    digest: "sha256:config".to_string(),
+                    size: config_json.len() as i64,
+                    ..Default::default()
+                },
+                layers: vec![OciDescriptor {
+                    media_type: IMAGE_LAYER_GZIP_MEDIA_TYPE.to_string(),
+                    digest: "sha256:base".to_string(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            };
+            inner
+                .storage
+                .save_manifest(&OciManifest::Image(parent), "sha256:parent")
+                .unwrap();
+        }
+        let parent = manifest("sha256:parent", &["sha256:base"]);
+        store
+            .update_index("example.com/base:latest", &parent)
+            .await
+            .unwrap();
+
+        let lower = dir.path().join("lower");
+        let upper = dir.path().join("upper");
+        std::fs::create_dir_all(&lower).unwrap();
+        std::fs::create_dir_all(upper.join("app")).unwrap();
+        std::fs::write(upper.join("app/run.sh"), b"echo hi\n").unwrap();
+        let layer =
+            crate::images::write_diff_layer(&lower, &upper, None, &dir.path().join("layer.tar.gz"))
+                .unwrap();
+        let (layer_digest, diff_id) = (layer.digest.clone(), layer.diff_id.clone());
+
+        let mut config: ImageConfiguration = serde_json::from_str(config_json).unwrap();
+        crate::images::ImageConfigChanges {
+            entrypoint: Some(vec!["/app/run.sh".to_string()]),
+            ..Default::default()
+        }
+        .apply_to(&mut config);
+        let installed = store
+            .install_image(
+                "example.com/app:v1",
+                &parent,
+                layer,
+                config,
+                "RUN /bin/sh -c make",
+            )
+            .await
+            .unwrap();
+
+        let resolved = store
+            .pull("example.com/app:v1", &PullOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(resolved.manifest_digest, installed.manifest_digest);
+        let layers: Vec<&str> = resolved.layers.iter().map(|l| l.digest.as_str()).collect();
+        assert_eq!(layers, vec!["sha256:base", layer_digest.as_str()]);
+        assert!(store.layer_tarball(&layer_digest).await.exists());
+
+        let config: ImageConfiguration =
+            serde_json::from_str(&store.config(&resolved.config_digest).await.unwrap()).unwrap();
+        assert_eq!(
+            config.rootfs().diff_ids(),
+            &vec!["sha256:base-diff".to_string(), diff_id]
+        );
+        let history: Vec<_> = config
+            .history()
+            .iter()
+            .flatten()
+            .map(|h| h.created_by().clone().unwrap_or_default())
+            .collect();
+        assert_eq!(history, ["ADD base.txt /", "RUN /bin/sh -c make"]);
+        let config = config.config().clone().unwrap();
+        assert_eq!(
+            config.entrypoint().clone().unwrap(),
+            vec!["/app/run.sh".to_string()]
+        );
+        assert_eq!(config.cmd(), &None);
+    }
+}
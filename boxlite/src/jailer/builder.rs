@@ -4,6 +4,7 @@
 //! along with a fluent `JailerBuilder` for configuration.
 
 use crate::jailer::config::{ResourceLimits, SecurityOptions};
+use crate::runtime::layout::LayoutDirs;
 use crate::runtime::options::VolumeSpec;
 use std::path::{Path, PathBuf};
 
@@ -47,6 +48,10 @@ pub struct Jailer {
     pub(crate) box_id: String,
     /// Box directory path
     pub(crate) box_dir: PathBuf,
+    /// Shared base of the box's disk overlay (`DiskConfig::backing_file`)
+    pub(crate) disk_backing_file: Option<PathBuf>,
+    /// Runtime home subdirectories placed outside the home
+    pub(crate) layout_dirs: LayoutDirs,
 }
 
 impl Jailer {
@@ -61,6 +66,8 @@ impl Jailer {
             volumes: Vec::new(),
             box_id: box_id.into(),
             box_dir: box_dir.into(),
+            disk_backing_file: None,
+            layout_dirs: LayoutDirs::default(),
         }
     }
 
@@ -96,6 +103,22 @@ impl Jailer {
         self
     }
 
+    /// Set the shared base of the box's disk overlay (consuming builder pattern).
+    ///
+    /// The base is made readable inside the sandbox (Linux).
+    pub fn with_disk_backing_file(mut self, backing_file: Option<PathBuf>) -> Self {
+        self.disk_backing_file = backing_file;
+        self
+    }
+
+    /// Set runtime home subdirectories placed outside the home (consuming builder pattern).
+    ///
+    /// The sandbox exposes the logs, temp and images directories at these paths.
+    pub fn with_layout_dirs(mut self, layout_dirs: LayoutDirs) -> Self {
+        self.layout_dirs = layout_dirs;
+        self
+    }
+
     // ─────────────────────────────────────────────────────────────────────
     // Getters
     // ─────────────────────────────────────────────────────────────────────
@@ -185,6 +208,8 @@ pub struct JailerBuilder {
     volumes: Vec<VolumeSpec>,
     box_id: Option<String>,
     box_dir: Option<PathBuf>,
+    disk_backing_file: Option<PathBuf>,
+    layout_dirs: LayoutDirs,
 }
 
 impl Default for JailerBuilder {
@@ -201,6 +226,8 @@ impl JailerBuilder {
             volumes: Vec::new(),
             box_id: None,
             box_dir: None,
+            disk_backing_file: None,
+            layout_dirs: LayoutDirs::default(),
         }
     }
 
@@ -252,6 +279,24 @@ impl JailerBuilder {
         self
     }
 
+    /// Set the shared base of the box's disk overlay.
+    ///
+    /// # Arguments
+    /// * `backing_file` - Base image path (`DiskConfig::backing_file`)
+    pub fn disk_backing_file(&mut self, backing_file: impl Into<PathBuf>) -> &mut Self {
+        self.disk_backing_file = Some(backing_file.into());
+        self
+    }
+
+    /// Set runtime home subdirectories placed outside the home.
+    ///
+    /// # Arguments
+    /// * `layout_dirs` - Overrides from `BoxliteOptions::layout_dirs`
+    pub fn layout_dirs(&mut self, layout_dirs: LayoutDirs) -> &mut Self {
+        self.layout_dirs = layout_dirs;
+        self
+    }
+
     /// Enable or disable jailer isolation.
     ///
     /// Shorthand for modifying `security.jailer_enabled`.
@@ -298,6 +343,8 @@ impl JailerBuilder {
             volumes: self.volumes.clone(),
             box_id,
             box_dir,
+            disk_backing_file: self.disk_backing_file.clone(),
+            layout_dirs: self.layout_dirs.clone(),
         })
     }
 }
@@ -391,11 +438,13 @@ mod tests {
                 host_path: "/data".to_string(),
                 guest_path: "/mnt/data".to_string(),
                 read_only: true,
+                ..Default::default()
             })
             .add_volume(VolumeSpec {
                 host_path: "/output".to_string(),
                 guest_path: "/mnt/output".to_string(),
                 read_only: false,
+                ..Default::default()
             })
             .build()
             .expect("Should build successfully");
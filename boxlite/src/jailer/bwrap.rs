@@ -21,7 +21,7 @@
 //! ## What We Add Outside Bubblewrap
 //!
 //! - Cgroups v2 setup (before spawn)
-//! - Seccomp BPF filter generation (before spawn)
+//! - Seccomp BPF filter generation (before spawn, passed to bwrap on fd 3)
 //! - FD cleanup (inside shim, bwrap leaks some FDs)
 //! - rlimits (inside shim)
 
@@ -35,6 +35,9 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::OnceLock;
 
+/// FD number on which the seccomp BPF program is passed to bwrap.
+pub const SECCOMP_FD: i32 = 3;
+
 /// Cached path to the bwrap binary (system or bundled).
 ///
 /// This is initialized once on first access and cached for the process lifetime.
@@ -259,7 +262,8 @@ impl BwrapCommand {
 
     /// Add seccomp filter from file descriptor.
     ///
-    /// The filter should be passed via fd 3 using process_stdio.
+    /// The filter should be passed via [`SECCOMP_FD`] using the pre_exec hook
+    /// (see `pre_exec::add_pre_exec_hook_with_fd`).
     pub fn with_seccomp_fd(&mut self, fd: i32) -> &mut Self {
         self.args.push("--seccomp".to_string());
         self.args.push(fd.to_string());
@@ -291,19 +295,91 @@ impl BwrapCommand {
         );
 
         let mut cmd = Command::new(bwrap_path);
-        cmd.args(&self.args);
-        cmd.arg("--");
-        cmd.arg(executable.as_ref());
-        cmd.args(args);
+        cmd.args(self.to_argv(executable, args));
         cmd
     }
 
+    /// Get the fully assembled bwrap argument vector.
+    ///
+    /// Contains every namespace, mount, and environment option followed by
+    /// `--`, the executable, and its arguments: exactly what [`build`](Self::build)
+    /// passes to bwrap. Useful for logging and tests.
+    pub fn to_argv(&self, executable: impl AsRef<Path>, args: &[String]) -> Vec<String> {
+        let mut argv = Vec::with_capacity(self.args.len() + args.len() + 2);
+        argv.extend(self.args.iter().cloned());
+        argv.push("--".to_string());
+        argv.push(executable.as_ref().to_string_lossy().to_string());
+        argv.extend(args.iter().cloned());
+        argv
+    }
+
     /// Get the arguments as a vector (for testing/debugging).
     pub fn get_args(&self) -> &[String] {
         &self.args
     }
 }
 
+/// Detect directories needed for the dynamic linker of `binary`.
+///
+/// Reads the ELF `PT_INTERP` entry of `binary` and returns the interpreter's
+/// directory (and, if it is a symlink, the resolved target's directory) when
+/// not already covered by `system_mounts`. On hosts like NixOS the loader
+/// lives under `/nix/store/...` and would otherwise be missing in the sandbox.
+pub fn dynamic_linker_dirs(binary: &Path, system_mounts: &[PathBuf]) -> Vec<PathBuf> {
+    let Some(interp) = read_elf_interpreter(binary) else {
+        return Vec::new();
+    };
+
+    let mut candidates = vec![interp.clone()];
+    if let Ok(resolved) = std::fs::canonicalize(&interp)
+        && resolved != interp
+    {
+        candidates.push(resolved);
+    }
+
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for path in candidates {
+        if let Some(dir) = path.parent()
+            && !system_mounts.iter().any(|mount| dir.starts_with(mount))
+            && !dirs.iter().any(|d| d == dir)
+        {
+            dirs.push(dir.to_path_buf());
+        }
+    }
+    dirs
+}
+
+/// Read the `PT_INTERP` path from a little-endian ELF64 binary.
+fn read_elf_interpreter(binary: &Path) -> Option<PathBuf> {
+    const PT_INTERP: u32 = 3;
+
+    let data = std::fs::read(binary).ok()?;
+    // ELF magic, 64-bit class, little-endian
+    if data.len() < 64 || &data[..4] != b"\x7fELF" || data[4] != 2 || data[5] != 1 {
+        return None;
+    }
+
+    let u16_at = |off: usize| Some(u16::from_le_bytes(data.get(off..off + 2)?.try_into().ok()?));
+    let u32_at = |off: usize| Some(u32::from_le_bytes(data.get(off..off + 4)?.try_into().ok()?));
+    let u64_at = |off: usize| Some(u64::from_le_bytes(data.get(off..off + 8)?.try_into().ok()?));
+
+    let phoff = u64_at(0x20)? as usize;
+    let phentsize = u16_at(0x36)? as usize;
+    let phnum = u16_at(0x38)? as usize;
+
+    for i in 0..phnum {
+        let ph = phoff + i * phentsize;
+        if u32_at(ph)? == PT_INTERP {
+            let offset = u64_at(ph + 8)? as usize;
+            let size = u64_at(ph + 32)? as usize;
+            let raw = data.get(offset..offset + size)?;
+            let raw = raw.split(|b| *b == 0).next()?;
+            return Some(PathBuf::from(std::str::from_utf8(raw).ok()?));
+        }
+    }
+    None
+}
+
 impl Default for BwrapCommand {
     fn default() -> Self {
         Self::new()
@@ -317,7 +393,7 @@ impl Default for BwrapCommand {
 /// ## Mount Strategy
 ///
 /// The sandbox mounts:
-/// - System directories (`/usr`, `/lib`, `/lib64`, `/bin`, `/sbin`) - read-only
+/// - System directories (`SecurityOptions::system_mounts`) - read-only
 /// - Device nodes (`/dev/kvm`, `/dev/net/tun`) - for KVM and networking
 /// - BoxLite home directory (`~/.boxlite`) - read-write for runtime data
 /// - Shim binary directory - read-only for the binary and bundled libraries
@@ -340,7 +416,7 @@ pub fn build_shim_command(
     shim_path: &Path,
     shim_args: &[String],
     layout: &FilesystemLayout,
-    _security: &SecurityOptions,
+    security: &SecurityOptions,
 ) -> Command {
     let mut bwrap = BwrapCommand::new();
 
@@ -355,12 +431,12 @@ pub fn build_shim_command(
     // =========================================================================
     // Mount system directories (read-only)
     // =========================================================================
-    bwrap
-        .ro_bind_if_exists("/usr", "/usr")
-        .ro_bind_if_exists("/lib", "/lib")
-        .ro_bind_if_exists("/lib64", "/lib64")
-        .ro_bind_if_exists("/bin", "/bin")
-        .ro_bind_if_exists("/sbin", "/sbin");
+    for mount in &security.system_mounts {
+        bwrap.ro_bind_if_exists(mount, mount);
+    }
+    for dir in dynamic_linker_dirs(shim_path, &security.system_mounts) {
+        bwrap.ro_bind_if_exists(&dir, &dir);
+    }
 
     // =========================================================================
     // Mount devices
@@ -522,6 +598,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_to_argv_appends_executable() {
+        let mut bwrap = BwrapCommand::new();
+        bwrap.with_die_with_parent();
+
+        let argv = bwrap.to_argv("/bin/shim", &["--engine".to_string()]);
+        assert_eq!(argv, ["--die-with-parent", "--", "/bin/shim", "--engine"]);
+    }
+
     #[test]
     fn test_bwrap_non_consuming_pattern() {
         // Verify builder can be reused (non-consuming pattern)
@@ -538,6 +623,30 @@ mod tests {
         assert!(args.contains(&"--clearenv".to_string()));
     }
 
+    #[test]
+    fn test_read_elf_interpreter_of_current_exe() {
+        let exe = std::env::current_exe().unwrap();
+        // Test binaries are dynamically linked on glibc hosts
+        if let Some(interp) = read_elf_interpreter(&exe) {
+            assert!(
+                interp.is_absolute(),
+                "interpreter should be absolute: {:?}",
+                interp
+            );
+        }
+        assert!(read_elf_interpreter(Path::new("/nonexistent")).is_none());
+    }
+
+    #[test]
+    fn test_dynamic_linker_dirs_covered_by_system_mounts() {
+        let exe = std::env::current_exe().unwrap();
+        let Some(interp) = read_elf_interpreter(&exe) else {
+            return;
+        };
+        let root = interp.ancestors().last().unwrap().to_path_buf();
+        assert!(dynamic_linker_dirs(&exe, &[root]).is_empty());
+    }
+
     #[test]
     fn test_bwrap_conditional_mount() {
         let mut bwrap = BwrapCommand::new();
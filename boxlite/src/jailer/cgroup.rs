@@ -26,7 +26,10 @@
 //!         ├── cpu.weight        # CPU shares
 //!         ├── memory.max        # Memory limit
 //!         ├── memory.high       # Memory throttle threshold
+//!         ├── memory.swap.max   # Swap limit
 //!         ├── pids.max          # Max processes
+//!         ├── memory.current    # Memory usage (read for metrics)
+//!         ├── cpu.stat          # CPU usage (read for metrics)
 //!         └── cgroup.procs      # Add process here
 //! ```
 
@@ -35,6 +38,7 @@ use super::config::ResourceLimits;
 use super::error::JailerError;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Base path for cgroup v2 filesystem.
 const CGROUP_ROOT: &str = "/sys/fs/cgroup";
@@ -104,6 +108,10 @@ pub struct CgroupConfig {
     /// Processes exceeding this are throttled.
     pub memory_high: Option<u64>,
 
+    /// Swap limit in bytes (memory.swap.max).
+    /// `Some(0)` disables swap entirely.
+    pub memory_swap_max: Option<u64>,
+
     /// CPU weight (1-10000, default 100).
     /// Higher = more CPU time relative to other cgroups.
     pub cpu_weight: Option<u32>,
@@ -225,6 +233,19 @@ fn apply_limits(cgroup_path: &Path, config: &CgroupConfig) -> Result<(), JailerE
         write_file(&cgroup_path.join("memory.high"), &memory_high.to_string())?;
     }
 
+    // Swap limit (swap accounting may be compiled out or disabled)
+    if let Some(memory_swap_max) = config.memory_swap_max {
+        let swap_max = cgroup_path.join("memory.swap.max");
+        if swap_max.exists() {
+            write_file(&swap_max, &memory_swap_max.to_string())?;
+        } else {
+            tracing::warn!(
+                path = %swap_max.display(),
+                "Swap controller not available, skipping memory.swap.max"
+            );
+        }
+    }
+
     // CPU weight
     if let Some(cpu_weight) = config.cpu_weight {
         write_file(&cgroup_path.join("cpu.weight"), &cpu_weight.to_string())?;
@@ -290,6 +311,57 @@ pub fn remove_cgroup(box_id: &str) -> Result<(), JailerError> {
     Ok(())
 }
 
+/// Usage statistics reported by a box's cgroup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CgroupStats {
+    /// Current memory usage in bytes (memory.current).
+    pub memory_current: u64,
+    /// Cumulative CPU time in microseconds (`usage_usec` from cpu.stat).
+    pub cpu_usage_usec: u64,
+}
+
+impl CgroupStats {
+    /// CPU usage percentage between an earlier sample and this one.
+    ///
+    /// 100% = one full CPU; multi-vCPU boxes can exceed 100%.
+    pub fn cpu_percent_since(&self, earlier: &CgroupStats, elapsed: Duration) -> f32 {
+        let elapsed_usec = elapsed.as_micros();
+        if elapsed_usec == 0 {
+            return 0.0;
+        }
+        let used_usec = self.cpu_usage_usec.saturating_sub(earlier.cpu_usage_usec);
+        (used_usec as f64 / elapsed_usec as f64 * 100.0) as f32
+    }
+}
+
+/// Read usage statistics from a box's cgroup.
+///
+/// Returns `None` if the cgroup does not exist (e.g., jailer disabled) or
+/// the stat files cannot be parsed.
+pub fn read_stats(box_id: &str) -> Option<CgroupStats> {
+    read_stats_from(&cgroup_path(box_id))
+}
+
+/// Read usage statistics from a cgroup directory.
+fn read_stats_from(cgroup_path: &Path) -> Option<CgroupStats> {
+    let memory_current = fs::read_to_string(cgroup_path.join("memory.current"))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+
+    let cpu_stat = fs::read_to_string(cgroup_path.join("cpu.stat")).ok()?;
+    let cpu_usage_usec = cpu_stat.lines().find_map(|line| {
+        let (key, value) = line.split_once(' ')?;
+        (key == "usage_usec").then(|| value.trim().parse().ok())?
+    })?;
+
+    Some(CgroupStats {
+        memory_current,
+        cpu_usage_usec,
+    })
+}
+
 /// Helper to write to a cgroup file.
 fn write_file(path: &Path, content: &str) -> Result<(), JailerError> {
     fs::write(path, content)
@@ -302,7 +374,8 @@ impl From<&ResourceLimits> for CgroupConfig {
         Self {
             memory_max: limits.max_memory,
             memory_high: limits.max_memory.map(|m| m * 9 / 10), // 90% of max
-            cpu_weight: None,                                   // Could add to ResourceLimits
+            memory_swap_max: limits.max_swap,
+            cpu_weight: limits.cpu_weight,
             cpu_max: limits.max_cpu_time.map(|t| {
                 // Convert seconds to quota/period
                 // 1 CPU = 100000/100000
@@ -439,5 +512,77 @@ mod tests {
         assert_eq!(config.memory_max, Some(1024 * 1024 * 1024));
         assert_eq!(config.pids_max, Some(100));
         assert!(config.cpu_max.is_some());
+        assert_eq!(config.cpu_weight, None);
+        assert_eq!(config.memory_swap_max, None);
+    }
+
+    #[test]
+    fn test_read_stats_from() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("memory.current"), "52428800\n").unwrap();
+        fs::write(
+            dir.path().join("cpu.stat"),
+            "usage_usec 1500000\nuser_usec 1000000\nsystem_usec 500000\n",
+        )
+        .unwrap();
+
+        let stats = read_stats_from(dir.path()).expect("should parse stats");
+        assert_eq!(stats.memory_current, 52428800);
+        assert_eq!(stats.cpu_usage_usec, 1500000);
+
+        // 0.5s of CPU over 1s wall time = 50%
+        let later = CgroupStats {
+            cpu_usage_usec: 2000000,
+            ..stats
+        };
+        let percent = later.cpu_percent_since(&stats, Duration::from_secs(1));
+        assert!((percent - 50.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_read_stats_missing_cgroup() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read_stats_from(&dir.path().join("missing")).is_none());
+    }
+
+    #[test]
+    fn test_cgroup_config_swap_max() {
+        let limits = ResourceLimits {
+            max_swap: Some(256 * 1024 * 1024),
+            ..Default::default()
+        };
+        assert_eq!(
+            CgroupConfig::from(&limits).memory_swap_max,
+            Some(256 * 1024 * 1024)
+        );
+
+        // Zero disables swap and must be passed through, not dropped
+        let no_swap = ResourceLimits {
+            max_swap: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(CgroupConfig::from(&no_swap).memory_swap_max, Some(0));
+    }
+
+    #[test]
+    fn test_cgroup_config_cpu_weight() {
+        let limits = ResourceLimits {
+            cpu_weight: Some(500),
+            ..Default::default()
+        };
+        assert!(limits.validate().is_ok());
+        assert_eq!(CgroupConfig::from(&limits).cpu_weight, Some(500));
+
+        let out_of_range = ResourceLimits {
+            cpu_weight: Some(0),
+            ..Default::default()
+        };
+        assert!(out_of_range.validate().is_err());
+
+        let out_of_range = ResourceLimits {
+            cpu_weight: Some(10001),
+            ..Default::default()
+        };
+        assert!(out_of_range.validate().is_err());
     }
 }
@@ -8,6 +8,8 @@
 //! - **Linux**: Wraps with bubblewrap (bwrap) for namespace isolation
 //! - **macOS**: Wraps with sandbox-exec for Seatbelt sandbox
 //! - **Other**: Falls back to direct execution with rlimits only
+//! - **Isolation disabled** (`SecurityOptions::disabled()`): Direct execution
+//!   with FD cleanup only, for debugging
 //!
 //! # Pre-exec Hook
 //!
@@ -16,8 +18,14 @@
 //! - FD cleanup (closes inherited file descriptors)
 //! - Resource limits (rlimits)
 //! - Cgroup membership (Linux only)
+//!
+//! # Process Group
+//!
+//! The shim leads its own process group, so the runtime can kill it and its
+//! helpers together even without bwrap's `--die-with-parent`.
 
 use crate::jailer::builder::Jailer;
+use crate::jailer::common::pid::PidFilePaths;
 use crate::jailer::pre_exec;
 use std::path::Path;
 use std::process::Command;
@@ -34,18 +42,36 @@ impl Jailer {
     ///
     /// On Linux, this creates the cgroup directory and configures resource limits.
     /// The child process will add itself to the cgroup in the pre_exec hook.
+    /// If `seccomp_self_check` is set, the seccomp self-check runs first.
     ///
     /// # Errors
     ///
-    /// Returns an error if cgroup creation fails on Linux. On macOS, this
-    /// function always succeeds (no-op).
+    /// Returns an error if the seccomp self-check fails on Linux. On macOS,
+    /// this function always succeeds (no-op).
     ///
     /// Note: Cgroup failures are treated as warnings, not errors, to allow
     /// boxes to run without cgroup limits if the system doesn't support them.
     pub fn setup_pre_spawn(&self) -> boxlite_shared::errors::BoxliteResult<()> {
+        if !self.security.isolation_enabled {
+            return Ok(());
+        }
+
         #[cfg(target_os = "linux")]
         {
             use crate::jailer::cgroup::{CgroupConfig, setup_cgroup};
+            use crate::jailer::{SeccompProfile, seccomp};
+
+            if self.security.seccomp_self_check
+                && self.security.seccomp_enabled
+                && self.security.seccomp_profile != SeccompProfile::Disabled
+            {
+                seccomp::self_check(
+                    self.security.seccomp_profile,
+                    &self.security.extra_allowed_syscalls,
+                    self.security.seccomp_action,
+                )?;
+                tracing::info!(box_id = %self.box_id, "Seccomp self-check passed");
+            }
 
             let cgroup_config = CgroupConfig::from(&self.security.resource_limits);
 
@@ -93,8 +119,21 @@ impl Jailer {
     ///
     /// # Returns
     ///
-    /// A `Command` configured with appropriate isolation for the platform.
+    /// A `Command` configured with appropriate isolation for the platform,
+    /// spawning into a new process group.
     pub fn build_command(&self, binary: &Path, args: &[String]) -> Command {
+        use std::os::unix::process::CommandExt;
+
+        let mut cmd = self.build_platform_command(binary, args);
+        cmd.process_group(0);
+        cmd
+    }
+
+    fn build_platform_command(&self, binary: &Path, args: &[String]) -> Command {
+        if !self.security.isolation_enabled {
+            return self.build_command_unsandboxed(binary, args);
+        }
+
         #[cfg(target_os = "linux")]
         {
             self.build_command_linux(binary, args)
@@ -117,27 +156,41 @@ impl Jailer {
     fn build_command_linux(&self, binary: &Path, args: &[String]) -> Command {
         use crate::jailer::{bwrap, cgroup};
 
-        let mut cmd = if bwrap::is_available() {
+        let (mut cmd, seccomp_fd) = if bwrap::is_available() {
             tracing::info!("Building bwrap-isolated command");
             self.build_bwrap_command(binary, args)
         } else {
             tracing::warn!("bwrap not available, using direct command");
             let mut cmd = Command::new(binary);
             cmd.args(args);
-            cmd
+            (cmd, None)
         };
 
         let resource_limits = self.security.resource_limits.clone();
         let cgroup_procs_path = cgroup::build_cgroup_procs_path(&self.box_id);
         let pid_file_path = self.build_pid_file_path();
 
-        pre_exec::add_pre_exec_hook(&mut cmd, resource_limits, cgroup_procs_path, pid_file_path);
+        pre_exec::add_pre_exec_hook_with_fd(
+            &mut cmd,
+            resource_limits,
+            cgroup_procs_path,
+            pid_file_path,
+            seccomp_fd.map(|fd| (bwrap::SECCOMP_FD, fd)),
+        );
         cmd
     }
 
+    /// Build the bwrap command.
+    ///
+    /// Returns the command plus the seccomp program FD (if any) that must be
+    /// passed to the child as [`bwrap::SECCOMP_FD`](crate::jailer::bwrap::SECCOMP_FD).
     #[cfg(target_os = "linux")]
-    fn build_bwrap_command(&self, binary: &Path, args: &[String]) -> Command {
-        use crate::jailer::{bwrap, shim_copy};
+    fn build_bwrap_command(
+        &self,
+        binary: &Path,
+        args: &[String],
+    ) -> (Command, Option<std::os::fd::OwnedFd>) {
+        use crate::jailer::shim_copy;
 
         // =====================================================================
         // Firecracker pattern: Copy shim binary and libraries to box directory
@@ -147,7 +200,12 @@ impl Jailer {
         // 2. Complete memory isolation between boxes (no shared .text section)
         // 3. Each box has its own copy of the shim and libraries
 
-        let (shim_binary, bin_dir) = match shim_copy::copy_shim_to_box(binary, &self.box_dir) {
+        let (shim_binary, bin_dir) = match shim_copy::copy_shim_to_box(
+            binary,
+            &self.box_dir,
+            self.security.verify_shim_checksums,
+            self.security.shim_link_mode,
+        ) {
             Ok(copied_shim) => {
                 let bin_dir = copied_shim.parent().unwrap_or(&self.box_dir).to_path_buf();
                 tracing::info!(
@@ -168,6 +226,98 @@ impl Jailer {
             }
         };
 
+        let mut bwrap = self.build_bwrap_args(&shim_binary, &bin_dir);
+        let seccomp_fd = self.attach_seccomp_filter(&mut bwrap);
+        tracing::debug!(
+            box_id = %self.box_id,
+            argv = ?bwrap.to_argv(&shim_binary, args),
+            "bwrap arguments"
+        );
+        (bwrap.build(&shim_binary, args), seccomp_fd)
+    }
+
+    /// Generate the seccomp launch filter and hand it to bwrap via `--seccomp`.
+    ///
+    /// Writes the BPF program to a memfd and adds `--seccomp 3` to `bwrap`.
+    /// Returns `None` (without touching `bwrap`) if seccomp is disabled or
+    /// the filter can't be generated; the shim still applies its own filter
+    /// after exec in that case.
+    #[cfg(target_os = "linux")]
+    fn attach_seccomp_filter(
+        &self,
+        bwrap: &mut crate::jailer::bwrap::BwrapCommand,
+    ) -> Option<std::os::fd::OwnedFd> {
+        use crate::jailer::{SeccompProfile, bwrap::SECCOMP_FD, seccomp};
+        use std::io::{Seek, Write};
+        use std::os::fd::{FromRawFd, OwnedFd};
+
+        if !self.security.seccomp_enabled
+            || self.security.seccomp_profile == SeccompProfile::Disabled
+        {
+            return None;
+        }
+
+        let program = match seccomp::generate_launch_filter(
+            self.security.seccomp_profile,
+            &self.security.extra_allowed_syscalls,
+            self.security.seccomp_action,
+        ) {
+            Ok(program) => program,
+            Err(e) => {
+                tracing::warn!(
+                    box_id = %self.box_id,
+                    error = %e,
+                    "Failed to generate seccomp filter for bwrap, skipping --seccomp"
+                );
+                return None;
+            }
+        };
+
+        let write_memfd = || -> std::io::Result<OwnedFd> {
+            let raw = unsafe { libc::memfd_create(c"boxlite-seccomp".as_ptr(), libc::MFD_CLOEXEC) };
+            if raw < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            // SAFETY: memfd_create returned a fresh FD that we exclusively own
+            let mut file = std::fs::File::from(unsafe { OwnedFd::from_raw_fd(raw) });
+            file.write_all(&seccomp::bpf_to_bytes(&program))?;
+            file.rewind()?;
+            Ok(file.into())
+        };
+
+        match write_memfd() {
+            Ok(fd) => {
+                bwrap.with_seccomp_fd(SECCOMP_FD);
+                tracing::debug!(
+                    box_id = %self.box_id,
+                    bpf_instructions = program.len(),
+                    "Passing seccomp filter to bwrap"
+                );
+                Some(fd)
+            }
+            Err(e) => {
+                tracing::warn!(
+                    box_id = %self.box_id,
+                    error = %e,
+                    "Failed to write seccomp filter for bwrap, skipping --seccomp"
+                );
+                None
+            }
+        }
+    }
+
+    /// Assemble the bwrap arguments for an already-copied shim binary.
+    ///
+    /// Pure with respect to the box: no files are copied, so the result can
+    /// be inspected in tests.
+    #[cfg(target_os = "linux")]
+    fn build_bwrap_args(
+        &self,
+        shim_binary: &Path,
+        bin_dir: &Path,
+    ) -> crate::jailer::bwrap::BwrapCommand {
+        use crate::jailer::bwrap;
+
         let mut bwrap = bwrap::BwrapCommand::new();
 
         // =====================================================================
@@ -186,13 +336,16 @@ impl Jailer {
         // 1. Build libkrun with musl (CC=musl-gcc)
         // 2. Build libgvproxy with musl (CGO_ENABLED=1 CC=musl-gcc)
         // 3. Build boxlite-shim with --target x86_64-unknown-linux-musl
-        // 4. Remove these ro_bind_if_exists calls below
-        bwrap
-            .ro_bind_if_exists("/usr", "/usr")
-            .ro_bind_if_exists("/lib", "/lib")
-            .ro_bind_if_exists("/lib64", "/lib64")
-            .ro_bind_if_exists("/bin", "/bin")
-            .ro_bind_if_exists("/sbin", "/sbin");
+        // 4. Remove the default system_mounts
+        for mount in &self.security.system_mounts {
+            bwrap.ro_bind_if_exists(mount, mount);
+        }
+
+        // Always include the dynamic linker's directory (e.g. /nix/store on NixOS)
+        for dir in bwrap::dynamic_linker_dirs(shim_binary, &self.security.system_mounts) {
+            tracing::debug!(dir = %dir.display(), "bwrap: mounted dynamic linker directory (ro)");
+            bwrap.ro_bind_if_exists(&dir, &dir);
+        }
 
         // =====================================================================
         // Devices and special mounts
@@ -220,7 +373,8 @@ impl Jailer {
             && let Some(home_dir) = boxes_dir.parent()
         {
             // 2. Mount logs directory (read-write for shim logging + console output)
-            let logs_dir = home_dir.join("logs");
+            let dirs = &self.layout_dirs;
+            let logs_dir = dirs.logs.clone().unwrap_or_else(|| home_dir.join("logs"));
             if logs_dir.exists() {
                 bwrap.bind(&logs_dir, &logs_dir);
                 tracing::debug!(logs_dir = %logs_dir.display(), "bwrap: mounted logs directory");
@@ -228,7 +382,7 @@ impl Jailer {
 
             // 3. Mount tmp directory (read-write for rootfs preparation)
             //    Contains: temporary rootfs mounts during box creation
-            let tmp_dir = home_dir.join("tmp");
+            let tmp_dir = dirs.temp.clone().unwrap_or_else(|| home_dir.join("tmp"));
             if tmp_dir.exists() {
                 bwrap.bind(&tmp_dir, &tmp_dir);
                 tracing::debug!(tmp_dir = %tmp_dir.display(), "bwrap: mounted tmp directory");
@@ -236,13 +390,24 @@ impl Jailer {
 
             // 4. Mount images directory (read-only for extracted OCI layers)
             //    Contains: extracted layer data used for rootfs
-            let images_dir = home_dir.join("images");
+            let images_dir = dirs
+                .images
+                .clone()
+                .unwrap_or_else(|| home_dir.join("images"));
             if images_dir.exists() {
                 bwrap.ro_bind(&images_dir, &images_dir);
                 tracing::debug!(images_dir = %images_dir.display(), "bwrap: mounted images directory (ro)");
             }
         }
 
+        // 5. Mount the shared base of the disk overlay (read-only)
+        //    libkrun opens it through the overlay's canonical backing path
+        if let Some(base) = &self.disk_backing_file {
+            let base = base.canonicalize().unwrap_or_else(|_| base.clone());
+            bwrap.ro_bind_if_exists(&base, &base);
+            tracing::debug!(base = %base.display(), "bwrap: mounted disk backing file (ro)");
+        }
+
         // NOTE: No external shim directory bind mount needed!
         // The shim and libraries are now copied into box_dir/bin/
 
@@ -266,7 +431,7 @@ impl Jailer {
 
         bwrap.chdir("/");
 
-        bwrap.build(&shim_binary, args)
+        bwrap
     }
 
     // ─────────────────────────────────────────────────────────────────────
@@ -279,8 +444,9 @@ impl Jailer {
 
         let mut cmd = if macos::is_sandbox_available() {
             tracing::info!("Building sandbox-exec isolated command");
+            let volumes = self.sandbox_volumes();
             let (sandbox_cmd, sandbox_args) =
-                macos::get_sandbox_exec_args(&self.security, &self.box_dir, binary, &self.volumes);
+                macos::get_sandbox_exec_args(&self.security, &self.box_dir, binary, &volumes);
             let mut cmd = Command::new(sandbox_cmd);
             cmd.args(sandbox_args);
             cmd.arg(binary);
@@ -299,6 +465,33 @@ impl Jailer {
         cmd
     }
 
+    /// User volumes plus runtime directories placed outside the home.
+    ///
+    /// The Seatbelt policy only covers the boxlite home, so overridden
+    /// images (read-only), logs and temp directories are added like volumes.
+    #[cfg(target_os = "macos")]
+    fn sandbox_volumes(&self) -> Vec<crate::runtime::options::VolumeSpec> {
+        use crate::runtime::options::VolumeSpec;
+
+        let dirs = &self.layout_dirs;
+        let mut volumes = self.volumes.clone();
+        for (path, label, read_only) in [
+            (&dirs.images, "images directory", true),
+            (&dirs.logs, "logs directory", false),
+            (&dirs.temp, "temp directory", false),
+        ] {
+            if let Some(path) = path {
+                volumes.push(VolumeSpec {
+                    host_path: path.to_string_lossy().into_owned(),
+                    guest_path: label.to_string(),
+                    read_only,
+                    ..Default::default()
+                });
+            }
+        }
+        volumes
+    }
+
     // ─────────────────────────────────────────────────────────────────────
     // Fallback for unsupported platforms
     // ─────────────────────────────────────────────────────────────────────
@@ -315,16 +508,186 @@ impl Jailer {
         cmd
     }
 
+    // ─────────────────────────────────────────────────────────────────────
+    // Isolation disabled (debugging)
+    // ─────────────────────────────────────────────────────────────────────
+
+    /// Build a direct command with only FD cleanup (and PID file) in pre_exec.
+    ///
+    /// No sandbox wrapper, rlimits or cgroup membership are applied.
+    fn build_command_unsandboxed(&self, binary: &Path, args: &[String]) -> Command {
+        tracing::warn!(
+            box_id = %self.box_id,
+            "SANDBOX DISABLED: spawning shim without bwrap/sandbox-exec, cgroups or seccomp. \
+             Do not run untrusted workloads with isolation disabled."
+        );
+
+        let mut cmd = Command::new(binary);
+        cmd.args(args);
+
+        let pid_file_path = self.build_pid_file_path();
+        pre_exec::add_pre_exec_hook(&mut cmd, Default::default(), None, pid_file_path);
+        cmd
+    }
+
     // ─────────────────────────────────────────────────────────────────────
     // Helper methods
     // ─────────────────────────────────────────────────────────────────────
 
-    /// Build the PID file path as a CString for use in pre_exec hook.
+    /// Build the PID file paths as CStrings for use in pre_exec hook.
     ///
-    /// Returns the path to `{box_dir}/shim.pid` as a CString, ready for
+    /// Returns `{box_dir}/shim.pid` and its temp sibling, ready for
     /// async-signal-safe operations in the pre_exec context.
-    fn build_pid_file_path(&self) -> Option<std::ffi::CString> {
-        let pid_file = self.box_dir.join("shim.pid");
-        std::ffi::CString::new(pid_file.to_string_lossy().as_bytes()).ok()
+    fn build_pid_file_path(&self) -> Option<PidFilePaths> {
+        PidFilePaths::new(&self.box_dir.join("shim.pid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jailer::SecurityOptions;
+
+    #[test]
+    fn test_build_command_isolation_disabled_runs_shim_directly() {
+        let box_dir = tempfile::tempdir().unwrap();
+        let jailer =
+            Jailer::new("test-box", box_dir.path()).with_security(SecurityOptions::disabled());
+
+        let binary = Path::new("/opt/boxlite/boxlite-shim");
+        let args = vec!["--engine".to_string(), "Libkrun".to_string()];
+        let cmd = jailer.build_command(binary, &args);
+
+        assert_eq!(cmd.get_program(), binary.as_os_str());
+        let cmd_args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(cmd_args, ["--engine", "Libkrun"]);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_bwrap_args_custom_system_mounts() {
+        let box_dir = tempfile::tempdir().unwrap();
+        let custom_mount = tempfile::tempdir().unwrap();
+
+        let security = SecurityOptions {
+            system_mounts: vec![custom_mount.path().to_path_buf()],
+            ..Default::default()
+        };
+        let jailer = Jailer::new("test-box", box_dir.path()).with_security(security);
+
+        let shim = box_dir.path().join("bin").join("boxlite-shim");
+        let bwrap = jailer.build_bwrap_args(&shim, &box_dir.path().join("bin"));
+        let args = bwrap.get_args();
+
+        let custom = custom_mount.path().to_string_lossy().to_string();
+        assert!(
+            args.windows(3)
+                .any(|w| w[0] == "--ro-bind" && w[1] == custom && w[2] == custom),
+            "custom system mount missing from args: {:?}",
+            args
+        );
+        // Default mounts are replaced, not extended
+        assert!(
+            !args
+                .windows(2)
+                .any(|w| w[0] == "--ro-bind" && w[1] == "/sbin")
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_bwrap_argv_for_sample_box() {
+        let home = tempfile::tempdir().unwrap();
+        let box_dir = home.path().join("boxes").join("sample-box");
+        let bin_dir = box_dir.join("bin");
+        let jailer = Jailer::new("sample-box", &box_dir);
+
+        let shim = bin_dir.join("boxlite-shim");
+        let argv = jailer
+            .build_bwrap_args(&shim, &bin_dir)
+            .to_argv(&shim, &["--engine".to_string()]);
+
+        let box_dir_str = box_dir.to_string_lossy().to_string();
+        let bin_dir_str = bin_dir.to_string_lossy().to_string();
+        assert!(
+            argv.windows(3)
+                .any(|w| w[0] == "--bind" && w[1] == box_dir_str && w[2] == box_dir_str),
+            "box directory bind missing: {:?}",
+            argv
+        );
+        assert!(argv.contains(&"--die-with-parent".to_string()));
+        assert!(
+            argv.windows(3)
+                .any(|w| w[0] == "--setenv" && w[1] == "LD_LIBRARY_PATH" && w[2] == bin_dir_str),
+            "LD_LIBRARY_PATH setenv missing: {:?}",
+            argv
+        );
+        assert_eq!(argv.last().map(String::as_str), Some("--engine"));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_launch_filter_lets_shim_stack_its_filter() {
+        use crate::jailer::{SeccompAction, seccomp};
+
+        for security in [SecurityOptions::standard(), SecurityOptions::maximum()] {
+            let box_dir = tempfile::tempdir().unwrap();
+            let jailer = Jailer::new("test-box", box_dir.path()).with_security(security.clone());
+            let bin_dir = box_dir.path().join("bin");
+            let mut bwrap = jailer.build_bwrap_args(&bin_dir.join("boxlite-shim"), &bin_dir);
+            assert!(jailer.attach_seccomp_filter(&mut bwrap).is_some());
+
+            // What bwrap installs, then what the shim's apply_isolation stacks
+            let launch = seccomp::generate_launch_filter(
+                security.seccomp_profile,
+                &security.extra_allowed_syscalls,
+                SeccompAction::Trap,
+            )
+            .unwrap();
+            let regular = seccomp::generate_bpf_filter(
+                security.seccomp_profile,
+                &security.extra_allowed_syscalls,
+                SeccompAction::Trap,
+            )
+            .unwrap();
+
+            // SAFETY: the child only applies prebuilt filters and `_exit`s
+            let pid = unsafe { libc::fork() };
+            assert!(pid >= 0, "fork failed");
+            if pid == 0 {
+                unsafe {
+                    libc::signal(libc::SIGSYS, libc::SIG_DFL);
+                    let code = if seccomp::apply_filter(&launch).is_err() {
+                        1
+                    } else if seccomp::apply_filter(&regular).is_err() {
+                        2
+                    } else {
+                        0
+                    };
+                    libc::_exit(code);
+                }
+            }
+
+            let mut status = 0;
+            assert_eq!(unsafe { libc::waitpid(pid, &mut status, 0) }, pid);
+            assert!(
+                libc::WIFEXITED(status) && libc::WEXITSTATUS(status) == 0,
+                "stacking the shim filter failed: status {:#x}",
+                status
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_bwrap_args_skip_seccomp_when_disabled() {
+        let box_dir = tempfile::tempdir().unwrap();
+        let jailer = Jailer::new("test-box", box_dir.path());
+
+        let bin_dir = box_dir.path().join("bin");
+        let mut bwrap = jailer.build_bwrap_args(&bin_dir.join("boxlite-shim"), &bin_dir);
+
+        assert!(jailer.attach_seccomp_filter(&mut bwrap).is_none());
+        assert!(!bwrap.get_args().contains(&"--seccomp".to_string()));
     }
 }
@@ -29,13 +29,21 @@
 pub fn close_inherited_fds_raw() -> Result<(), i32> {
     const FIRST_FD: i32 = 3; // Keep stdin(0), stdout(1), stderr(2)
 
+    close_fds_from_raw(FIRST_FD)
+}
+
+/// Close every FD `>= first_fd` - async-signal-safe version for pre_exec.
+///
+/// Same constraints as [`close_inherited_fds_raw`]; used when FDs below
+/// `first_fd` (e.g. a seccomp program on fd 3) must survive into the child.
+pub fn close_fds_from_raw(first_fd: i32) -> Result<(), i32> {
     #[cfg(target_os = "linux")]
     {
         // Try close_range syscall (Linux 5.9+, most efficient)
         let result = unsafe {
             libc::syscall(
                 libc::SYS_close_range,
-                FIRST_FD as libc::c_uint,
+                first_fd as libc::c_uint,
                 libc::c_uint::MAX,
                 0 as libc::c_uint,
             )
@@ -48,7 +56,7 @@ pub fn close_inherited_fds_raw() -> Result<(), i32> {
         // Note: We can't use /proc/self/fd here because:
         // 1. read_dir allocates memory (not async-signal-safe)
         // 2. We might be in a mount namespace where /proc isn't mounted
-        for fd in FIRST_FD..1024 {
+        for fd in first_fd..1024 {
             // Ignore errors - FD might not be open
             unsafe { libc::close(fd) };
         }
@@ -59,7 +67,7 @@ pub fn close_inherited_fds_raw() -> Result<(), i32> {
     {
         // macOS: brute force close (no close_range syscall)
         // 4096 is a reasonable upper bound for most processes
-        for fd in FIRST_FD..4096 {
+        for fd in first_fd..4096 {
             // Ignore errors - FD might not be open
             unsafe { libc::close(fd) };
         }
@@ -46,6 +46,48 @@ pub fn copy_if_newer(src: &Path, dest: &Path) -> io::Result<bool> {
     }
 }
 
+/// Clone a file if the source is newer or sizes differ.
+///
+/// Same decision as [`copy_if_newer`], but writes the destination with
+/// [`reflink_or_copy`].
+#[cfg(target_os = "linux")]
+pub fn reflink_if_newer(src: &Path, dest: &Path) -> io::Result<bool> {
+    if should_copy_file(src, dest) {
+        reflink_or_copy(src, dest)?;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+/// Clone `src` to `dest` with `FICLONE`, falling back to a regular copy.
+///
+/// On filesystems with reflink support (btrfs, XFS) the clone shares extents
+/// with the source until either file is written. Elsewhere, including across
+/// filesystems, this behaves like [`fs::copy`].
+#[cfg(target_os = "linux")]
+pub fn reflink_or_copy(src: &Path, dest: &Path) -> io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let src_file = fs::File::open(src)?;
+    let permissions = src_file.metadata()?.permissions();
+    let dest_file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(dest)?;
+
+    // SAFETY: both descriptors stay open for the duration of the call
+    let ret = unsafe { libc::ioctl(dest_file.as_raw_fd(), libc::FICLONE, src_file.as_raw_fd()) };
+    if ret != 0 {
+        drop(dest_file);
+        fs::copy(src, dest)?;
+        return Ok(());
+    }
+
+    dest_file.set_permissions(permissions)
+}
+
 /// Check if a file should be copied based on modification time and size.
 ///
 /// Returns `true` if:
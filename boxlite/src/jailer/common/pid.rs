@@ -6,6 +6,28 @@
 //! The PID file serves as the single source of truth for the shim process PID,
 //! enabling crash recovery and process tracking.
 
+use std::ffi::CString;
+use std::path::Path;
+
+/// PID file path and its temp sibling, pre-allocated for `pre_exec`.
+pub struct PidFilePaths {
+    path: CString,
+    tmp_path: CString,
+}
+
+impl PidFilePaths {
+    /// Paths for the PID file at `path`. `None` if it contains a NUL byte.
+    pub fn new(path: &Path) -> Option<Self> {
+        use std::os::unix::ffi::OsStrExt;
+
+        let tmp_path = crate::util::pid_tmp_path(path);
+        Some(Self {
+            path: CString::new(path.as_os_str().as_bytes()).ok()?,
+            tmp_path: CString::new(tmp_path.as_os_str().as_bytes()).ok()?,
+        })
+    }
+}
+
 /// Write current process PID to file - async-signal-safe version for pre_exec.
 ///
 /// This function is designed to be called from a `pre_exec` hook, which runs
@@ -14,7 +36,11 @@
 ///
 /// # Safety
 ///
-/// This function only uses async-signal-safe syscalls (getpid, open, write, close).
+/// The PID is written to the temp path and renamed into place, so a reader
+/// never sees a torn write.
+///
+/// This function only uses async-signal-safe syscalls (getpid, open, write,
+/// close, rename).
 /// Do NOT add:
 /// - Logging (tracing, println)
 /// - Memory allocation (Box, Vec, String)
@@ -22,12 +48,12 @@
 /// - Most Rust stdlib functions
 ///
 /// # Arguments
-/// * `path` - CString path to the PID file (pre-allocated by caller)
+/// * `paths` - PID file paths (pre-allocated by caller)
 ///
 /// # Returns
 /// * `Ok(())` - PID file written successfully
 /// * `Err(errno)` - Failed (returns raw errno for io::Error conversion)
-pub fn write_pid_file_raw(path: &std::ffi::CStr) -> Result<(), i32> {
+pub fn write_pid_file_raw(paths: &PidFilePaths) -> Result<(), i32> {
     unsafe {
         let pid = libc::getpid();
 
@@ -38,7 +64,7 @@ pub fn write_pid_file_raw(path: &std::ffi::CStr) -> Result<(), i32> {
         // Open file: O_WRONLY | O_CREAT | O_TRUNC
         // Mode 0o644: rw-r--r--
         let fd = libc::open(
-            path.as_ptr(),
+            paths.tmp_path.as_ptr(),
             libc::O_WRONLY | libc::O_CREAT | libc::O_TRUNC,
             0o644 as libc::c_uint,
         );
@@ -62,6 +88,11 @@ pub fn write_pid_file_raw(path: &std::ffi::CStr) -> Result<(), i32> {
             return Err(errno);
         }
 
+        // Atomically replace the PID file
+        if libc::rename(paths.tmp_path.as_ptr(), paths.path.as_ptr()) < 0 {
+            return Err(super::get_errno());
+        }
+
         Ok(())
     }
 }
@@ -120,7 +151,6 @@ fn format_pid_to_buffer(mut pid: i32, buf: &mut [u8; 16]) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::ffi::CString;
 
     #[test]
     fn test_format_pid_to_buffer() {
@@ -145,28 +175,16 @@ mod tests {
 
     #[test]
     fn test_write_pid_file_raw() {
-        use std::io::Read;
-
-        // Create temp file path
-        let temp_dir = std::env::temp_dir();
-        let pid_file = temp_dir.join("test_pid_file.pid");
-        let path = CString::new(pid_file.to_string_lossy().as_bytes()).unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let pid_file = temp_dir.path().join("shim.pid");
+        let paths = PidFilePaths::new(&pid_file).unwrap();
 
         // Write PID file
-        write_pid_file_raw(&path).expect("Should write PID file");
-
-        // Read and verify
-        let mut content = String::new();
-        std::fs::File::open(&pid_file)
-            .expect("Should open file")
-            .read_to_string(&mut content)
-            .expect("Should read file");
-
-        let expected_pid = std::process::id();
-        let file_pid: u32 = content.trim().parse().expect("Should parse PID");
-        assert_eq!(file_pid, expected_pid);
+        write_pid_file_raw(&paths).expect("Should write PID file");
 
-        // Cleanup
-        let _ = std::fs::remove_file(&pid_file);
+        // Read and verify; the temp file was renamed into place
+        let file_pid = crate::util::read_pid_file(&pid_file).expect("Should parse PID");
+        assert_eq!(file_pid, std::process::id());
+        assert!(!crate::util::pid_tmp_path(&pid_file).exists());
     }
 }
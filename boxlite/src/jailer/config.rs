@@ -5,4 +5,6 @@
 //! configuration types together and avoid circular dependencies.
 
 // Re-export security types from runtime::options
-pub use crate::runtime::options::{ResourceLimits, SecurityOptions};
+pub use crate::runtime::options::{
+    ResourceLimits, SeccompAction, SeccompProfile, SecurityOptions, ShimLinkMode,
+};
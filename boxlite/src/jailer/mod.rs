@@ -14,7 +14,7 @@
 //! ├── command.rs      (Command building for isolated processes)
 //! ├── pre_exec.rs     (Pre-exec hook for process isolation)
 //! ├── shim_copy.rs    (Firecracker copy-to-jail pattern)
-//! ├── config.rs       (Re-exports SecurityOptions, ResourceLimits)
+//! ├── config.rs       (Re-exports SecurityOptions, ResourceLimits, SeccompProfile, SeccompAction)
 //! ├── error.rs        (Hierarchical error types)
 //! ├── seccomp.rs      (Seccomp BPF filter generation)
 //! ├── bwrap.rs        (Bubblewrap command builder)
@@ -90,7 +90,7 @@ pub(crate) mod shim_copy;
 
 // Core types
 pub use builder::{Jailer, JailerBuilder};
-pub use config::{ResourceLimits, SecurityOptions};
+pub use config::{ResourceLimits, SeccompAction, SeccompProfile, SecurityOptions, ShimLinkMode};
 pub use error::{ConfigError, IsolationError, JailerError, SystemError};
 pub use platform::{PlatformIsolation, SpawnIsolation};
 
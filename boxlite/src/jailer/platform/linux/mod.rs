@@ -18,7 +18,7 @@
 //! Seccomp must be applied after exec because the seccompiler library
 //! is not async-signal-safe (cannot be used in pre_exec hook).
 
-use crate::jailer::config::SecurityOptions;
+use crate::jailer::config::{SeccompProfile, SecurityOptions};
 use crate::jailer::seccomp;
 use crate::runtime::layout::FilesystemLayout;
 use boxlite_shared::errors::BoxliteResult;
@@ -67,12 +67,14 @@ pub fn apply_isolation(
     tracing::info!(
         box_id = %box_id,
         seccomp_enabled = security.seccomp_enabled,
+        seccomp_profile = ?security.seccomp_profile,
+        seccomp_action = ?security.seccomp_action,
         "Applying Linux jailer isolation"
     );
 
     // Apply seccomp filter if enabled
-    if security.seccomp_enabled {
-        apply_seccomp_filter(box_id)?;
+    if security.seccomp_enabled && security.seccomp_profile != SeccompProfile::Disabled {
+        apply_seccomp_filter(box_id, security)?;
     } else {
         tracing::warn!(
             box_id = %box_id,
@@ -92,11 +94,13 @@ pub fn apply_isolation(
 /// Apply seccomp BPF filter to the current process.
 ///
 /// Generates and applies a BPF filter that:
-/// - Allows syscalls needed for VMM operation (107 syscalls)
-/// - Traps (SIGSYS) for all other syscalls
+/// - Allows syscalls in the profile's allowlist plus `extra_allowed_syscalls`
+/// - Traps (SIGSYS) or logs all other syscalls, per `seccomp_action`
 ///
 /// Once applied, the filter cannot be removed.
-fn apply_seccomp_filter(box_id: &str) -> BoxliteResult<()> {
+fn apply_seccomp_filter(box_id: &str, security: &SecurityOptions) -> BoxliteResult<()> {
+    let profile = security.seccomp_profile;
+
     tracing::debug!(
         box_id = %box_id,
         filter_description = %seccomp::describe_filter(),
@@ -104,7 +108,12 @@ fn apply_seccomp_filter(box_id: &str) -> BoxliteResult<()> {
     );
 
     // Generate BPF bytecode from syscall allowlist
-    let bpf = seccomp::generate_bpf_filter().map_err(|e| {
+    let bpf = seccomp::generate_bpf_filter(
+        profile,
+        &security.extra_allowed_syscalls,
+        security.seccomp_action,
+    )
+    .map_err(|e| {
         tracing::error!(
             box_id = %box_id,
             error = %e,
@@ -131,7 +140,9 @@ fn apply_seccomp_filter(box_id: &str) -> BoxliteResult<()> {
 
     tracing::info!(
         box_id = %box_id,
-        allowed_syscalls = seccomp::ALLOWED_SYSCALLS.len(),
+        ?profile,
+        allowed_syscalls = seccomp::allowed_syscalls(profile).len(),
+        extra_allowed_syscalls = ?security.extra_allowed_syscalls,
         blocked_syscalls = seccomp::BLOCKED_SYSCALLS.len(),
         "Seccomp filter applied successfully"
     );
@@ -167,6 +178,23 @@ mod tests {
         assert!(result.is_ok(), "Should succeed with seccomp disabled");
     }
 
+    #[test]
+    fn test_apply_isolation_with_disabled_profile() {
+        use crate::runtime::layout::FsLayoutConfig;
+
+        let security = SecurityOptions {
+            seccomp_enabled: true,
+            seccomp_profile: SeccompProfile::Disabled,
+            ..Default::default()
+        };
+
+        let layout = FilesystemLayout::new(PathBuf::from("/tmp/test"), FsLayoutConfig::default());
+
+        // Disabled profile generates no filter, so nothing is applied
+        let result = apply_isolation(&security, "test-box", &layout);
+        assert!(result.is_ok(), "Should succeed with disabled profile");
+    }
+
     // Note: Testing apply_isolation with seccomp enabled is tricky because:
     // 1. Seccomp cannot be un-applied once set
     // 2. It would restrict syscalls for the test process itself
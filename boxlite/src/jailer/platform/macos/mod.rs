@@ -387,7 +387,7 @@ fn build_dynamic_read_volumes(
 /// Static tmp paths are in seatbelt_file_write_policy.sbpl.
 /// This function adds:
 /// - {box_dir}/ (entire box directory for sockets, shared, etc.)
-/// - {home_dir}/logs/ (for shim and console logs)
+/// - {home_dir}/logs/ (for shim logs)
 /// - User volumes with read_only=false
 fn build_dynamic_write_paths(box_dir: &Path, volumes: &[VolumeSpec]) -> String {
     let box_dir_canon = canonicalize_or_original(box_dir);
@@ -593,11 +593,13 @@ mod tests {
                 host_path: "/data/input".to_string(),
                 guest_path: "/mnt/input".to_string(),
                 read_only: true,
+                ..Default::default()
             },
             VolumeSpec {
                 host_path: "/data/output".to_string(),
                 guest_path: "/mnt/output".to_string(),
                 read_only: false,
+                ..Default::default()
             },
         ];
 
@@ -620,11 +622,13 @@ mod tests {
                 host_path: "/data/input".to_string(),
                 guest_path: "/mnt/input".to_string(),
                 read_only: true, // Should NOT be in write policy
+                ..Default::default()
             },
             VolumeSpec {
                 host_path: "/data/output".to_string(),
                 guest_path: "/mnt/output".to_string(),
                 read_only: false, // Should be in write policy
+                ..Default::default()
             },
         ];
         let box_dir = PathBuf::from("/Users/test/.boxlite/boxes/test-box");
@@ -21,6 +21,7 @@
 //! See the [`common`](crate::jailer::common) module for async-signal-safe utilities.
 
 use crate::jailer::common;
+use crate::jailer::common::pid::PidFilePaths;
 use crate::jailer::config::ResourceLimits;
 use std::process::Command;
 
@@ -34,7 +35,7 @@ use std::process::Command;
 /// * `cmd` - The Command to add the hook to
 /// * `resource_limits` - Resource limits to apply
 /// * `cgroup_procs_path` - Path to cgroup.procs file (Linux only, pre-computed)
-/// * `pid_file_path` - Path to PID file (pre-computed CString for async-signal-safety)
+/// * `pid_file` - PID file paths (pre-computed CStrings for async-signal-safety)
 ///
 /// # Safety
 ///
@@ -43,6 +44,7 @@ use std::process::Command;
 /// - `close()` / `close_range()` syscalls
 /// - `setrlimit()` syscall
 /// - `open()` / `write()` / `close()` syscalls (for cgroup and PID file)
+/// - `rename()` syscall (PID file)
 /// - `getpid()` syscall
 ///
 /// **Do NOT add any of the following to the hook:**
@@ -65,11 +67,31 @@ use std::process::Command;
 /// cmd.spawn()?;
 /// ```
 pub fn add_pre_exec_hook(
+    cmd: &mut Command,
+    resource_limits: ResourceLimits,
+    cgroup_procs_path: Option<std::ffi::CString>,
+    pid_file: Option<PidFilePaths>,
+) {
+    add_pre_exec_hook_with_fd(cmd, resource_limits, cgroup_procs_path, pid_file, None);
+}
+
+/// Like [`add_pre_exec_hook`], but passes one FD through to the child.
+///
+/// `inherited_fd` is installed as `(target_fd, fd)`: the child sees `fd`
+/// at `target_fd` (e.g. a seccomp BPF program on fd 3 for bwrap), and
+/// every other FD `>= 3` is closed as usual. The `OwnedFd` is kept alive
+/// by the hook, so it stays open until the `Command` is dropped.
+///
+/// `target_fd` must be 3, the first non-stdio FD, so the FD cleanup can
+/// close everything above it.
+pub fn add_pre_exec_hook_with_fd(
     cmd: &mut Command,
     resource_limits: ResourceLimits,
     #[allow(unused_variables)] cgroup_procs_path: Option<std::ffi::CString>,
-    pid_file_path: Option<std::ffi::CString>,
+    pid_file: Option<PidFilePaths>,
+    inherited_fd: Option<(i32, std::os::fd::OwnedFd)>,
 ) {
+    use std::os::fd::AsRawFd;
     use std::os::unix::process::CommandExt;
 
     // SAFETY: The hook only uses async-signal-safe syscalls.
@@ -78,7 +100,21 @@ pub fn add_pre_exec_hook(
         cmd.pre_exec(move || {
             // 1. Close inherited file descriptors
             // This prevents information leakage through inherited FDs
-            common::fd::close_inherited_fds_raw().map_err(std::io::Error::from_raw_os_error)?;
+            if let Some((target_fd, ref fd)) = inherited_fd {
+                let raw = fd.as_raw_fd();
+                if raw == target_fd {
+                    // Already in place: just clear FD_CLOEXEC
+                    if libc::fcntl(raw, libc::F_SETFD, 0) < 0 {
+                        return Err(std::io::Error::last_os_error());
+                    }
+                } else if libc::dup2(raw, target_fd) < 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                common::fd::close_fds_from_raw(target_fd + 1)
+                    .map_err(std::io::Error::from_raw_os_error)?;
+            } else {
+                common::fd::close_inherited_fds_raw().map_err(std::io::Error::from_raw_os_error)?;
+            }
 
             // 2. Apply resource limits (rlimits)
             // This is enforced by the kernel
@@ -95,8 +131,9 @@ pub fn add_pre_exec_hook(
 
             // 4. Write PID file (single source of truth for process tracking)
             // This must happen after fork() - child has its own PID now
-            if let Some(ref path) = pid_file_path {
-                common::pid::write_pid_file_raw(path).map_err(std::io::Error::from_raw_os_error)?;
+            if let Some(ref paths) = pid_file {
+                common::pid::write_pid_file_raw(paths)
+                    .map_err(std::io::Error::from_raw_os_error)?;
             }
 
             Ok(())
@@ -134,12 +171,32 @@ mod tests {
 
     #[test]
     fn test_add_hook_with_pid_file() {
-        use std::ffi::CString;
-
         let mut cmd = Command::new("/bin/echo");
         let limits = ResourceLimits::default();
-        let pid_file = CString::new("/tmp/test.pid").ok();
+        let pid_file = PidFilePaths::new(std::path::Path::new("/tmp/test.pid"));
 
         add_pre_exec_hook(&mut cmd, limits, None, pid_file);
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_inherited_fd_visible_in_child() {
+        use std::os::fd::OwnedFd;
+
+        // Child checks that fd 3 is open and fd 4 is not
+        let mut cmd = Command::new("/bin/sh");
+        cmd.args(["-c", "[ -e /proc/self/fd/3 ] && [ ! -e /proc/self/fd/4 ]"]);
+        let file = tempfile::tempfile().unwrap();
+
+        add_pre_exec_hook_with_fd(
+            &mut cmd,
+            ResourceLimits::default(),
+            None,
+            None,
+            Some((3, OwnedFd::from(file))),
+        );
+
+        let status = cmd.status().unwrap();
+        assert!(status.success(), "fd 3 should be inherited by the child");
+    }
 }
@@ -25,6 +25,11 @@
 //! - Signals: rt_sigaction, rt_sigprocmask, rt_sigreturn, sigaltstack, tkill
 //! - Process: exit, exit_group, futex, sched_yield
 //!
+//! **PERMISSIVE** (debugging extras, see [`PERMISSIVE_EXTRA_SYSCALLS`]):
+//! - Process: fork, vfork, wait4, waitid, getppid
+//! - File I/O: readv, writev, dup2, dup3, pipe2, readlinkat, getcwd
+//! - Polling: poll, ppoll
+//!
 //! **BLOCKED** (dangerous, attack vectors):
 //! - mount, umount - filesystem manipulation
 //! - ptrace - process debugging/control
@@ -35,7 +40,8 @@
 
 #[cfg(target_os = "linux")]
 use super::error::IsolationError;
-use super::error::JailerError;
+use super::error::{ConfigError, JailerError};
+use crate::runtime::options::{SeccompAction, SeccompProfile};
 use std::collections::HashSet;
 
 // Unused imports on non-Linux (kept for potential future use)
@@ -146,6 +152,45 @@ pub const ALLOWED_SYSCALLS: &[&str] = &[
     "uname",      // Get system info
 ];
 
+/// Extra syscalls allowed by [`SeccompProfile::Permissive`].
+///
+/// Useful when attaching diagnostics to the shim (helper processes, shell
+/// pipelines, stack dumps). None of these appear in [`BLOCKED_SYSCALLS`]:
+/// `ptrace` and `process_vm_readv`/`process_vm_writev` stay blocked.
+pub const PERMISSIVE_EXTRA_SYSCALLS: &[&str] = &[
+    // === Process creation/reaping (helper processes) ===
+    "fork",    // Spawn helper process (x86_64 only)
+    "vfork",   // Spawn helper process (x86_64 only)
+    "wait4",   // Reap child processes
+    "waitid",  // Reap child processes (extended)
+    "getppid", // Get parent process ID
+    // === File I/O (diagnostic tooling) ===
+    "readv",      // Scatter-gather read
+    "writev",     // Scatter-gather write (stderr dumps)
+    "dup2",       // Redirect file descriptors (x86_64 only)
+    "dup3",       // Redirect file descriptors with flags
+    "pipe2",      // Create pipes to helper processes
+    "readlinkat", // Resolve /proc/self/* links
+    "getcwd",     // Get working directory
+    // === Polling ===
+    "poll",  // Wait for fd events (x86_64 only)
+    "ppoll", // Wait for fd events with signal mask
+];
+
+/// Syscalls needed between bwrap applying its `--seccomp` filter and the
+/// shim applying its own filter: the final `execve`, dynamic loader and
+/// Rust runtime startup, and the `seccomp` call that stacks the shim's filter.
+pub const LAUNCH_SYSCALLS: &[&str] = &[
+    "execve",            // bwrap exec's the shim under the filter
+    "seccomp",           // Shim stacks its regular filter
+    "access",            // ld.so checks /etc/ld.so.preload
+    "faccessat",         // ld.so (newer glibc)
+    "readlink",          // Resolve /proc/self/exe
+    "readlinkat",        // Resolve /proc/self/exe
+    "poll",              // Rust std checks fds 0-2 at startup
+    "sched_getaffinity", // Thread pool sizing
+];
+
 /// Syscalls that are explicitly blocked (dangerous).
 pub const BLOCKED_SYSCALLS: &[&str] = &[
     // Filesystem manipulation
@@ -194,6 +239,45 @@ pub const BLOCKED_SYSCALLS: &[&str] = &[
     "quotactl_fd",
 ];
 
+/// Resolve the syscall allowlist for a seccomp profile.
+///
+/// Returns an empty list for [`SeccompProfile::Disabled`].
+pub fn allowed_syscalls(profile: SeccompProfile) -> Vec<&'static str> {
+    let mut syscalls: Vec<&'static str> = match profile {
+        SeccompProfile::Strict => ALLOWED_SYSCALLS.to_vec(),
+        SeccompProfile::Permissive => ALLOWED_SYSCALLS
+            .iter()
+            .chain(PERMISSIVE_EXTRA_SYSCALLS)
+            .copied()
+            .collect(),
+        SeccompProfile::Disabled => Vec::new(),
+    };
+    let mut seen = HashSet::new();
+    syscalls.retain(|name| seen.insert(*name));
+    syscalls
+}
+
+/// Reject user-supplied extra syscalls that are in [`BLOCKED_SYSCALLS`].
+///
+/// Blocked syscalls are security-critical and can't be re-enabled via
+/// `SecurityOptions::extra_allowed_syscalls`.
+pub fn validate_extra_syscalls(extra_allowed: &[String]) -> Result<(), JailerError> {
+    let blocked: Vec<&str> = extra_allowed
+        .iter()
+        .map(String::as_str)
+        .filter(|name| is_blocked(name))
+        .collect();
+
+    if blocked.is_empty() {
+        Ok(())
+    } else {
+        Err(JailerError::Config(ConfigError::InvalidConfig(format!(
+            "extra_allowed_syscalls contains blocked syscalls: {}",
+            blocked.join(", ")
+        ))))
+    }
+}
+
 /// Generate a seccomp filter description for logging/debugging.
 pub fn describe_filter() -> String {
     let allowed: HashSet<&str> = ALLOWED_SYSCALLS.iter().copied().collect();
@@ -227,23 +311,137 @@ pub fn generate_filter_json() -> String {
     json
 }
 
-/// Generate a seccomp BPF filter program.
+/// Generate a seccomp BPF filter program for the given profile.
 ///
 /// Creates a filter that:
-/// - **Allows** all syscalls in `ALLOWED_SYSCALLS`
-/// - **Traps** (sends SIGSYS) for all other syscalls
+/// - **Allows** all syscalls in the profile's allowlist (see [`allowed_syscalls`])
+///   plus `extra_allowed`
+/// - **Traps** (sends SIGSYS) or **logs** all other syscalls, per `action`
+///
+/// [`SeccompProfile::Disabled`] yields an empty program.
 ///
 /// The filter uses seccompiler to generate BPF bytecode that can be
 /// applied to the current process.
 ///
 /// # Errors
 ///
-/// Returns an error if filter creation or BPF compilation fails.
+/// Returns an error if `extra_allowed` contains a blocked syscall, or if
+/// filter creation or BPF compilation fails.
+#[cfg(target_os = "linux")]
+pub fn generate_bpf_filter(
+    profile: SeccompProfile,
+    extra_allowed: &[String],
+    action: SeccompAction,
+) -> Result<seccompiler::BpfProgram, JailerError> {
+    validate_extra_syscalls(extra_allowed)?;
+
+    if profile == SeccompProfile::Disabled {
+        return Ok(Vec::new());
+    }
+
+    let rules = build_rules(profile, extra_allowed, &[])?;
+    compile_filter(rules, action)
+}
+
+/// Generate the seccomp filter handed to bwrap via `--seccomp`.
+///
+/// Bwrap installs this filter right before exec'ing the shim, so on top of
+/// the profile it allows [`LAUNCH_SYSCALLS`] (`execve` and what the dynamic
+/// loader needs, plus `seccomp` itself). Once running, the shim stacks the
+/// regular filter from [`generate_bpf_filter`] via `apply_isolation`, which
+/// blocks these again.
+///
+/// # Errors
+///
+/// Same as [`generate_bpf_filter`].
 #[cfg(target_os = "linux")]
-pub fn generate_bpf_filter() -> Result<seccompiler::BpfProgram, JailerError> {
-    use seccompiler::{SeccompAction, SeccompFilter, SeccompRule};
+pub fn generate_launch_filter(
+    profile: SeccompProfile,
+    extra_allowed: &[String],
+    action: SeccompAction,
+) -> Result<seccompiler::BpfProgram, JailerError> {
+    validate_extra_syscalls(extra_allowed)?;
+
+    if profile == SeccompProfile::Disabled {
+        return Ok(Vec::new());
+    }
+
+    let rules = build_rules(profile, extra_allowed, LAUNCH_SYSCALLS)?;
+    compile_filter(rules, action)
+}
+
+/// Compile a rules map into BPF with the given default action.
+#[cfg(target_os = "linux")]
+fn compile_filter(
+    rules: std::collections::BTreeMap<i64, Vec<seccompiler::SeccompRule>>,
+    action: SeccompAction,
+) -> Result<seccompiler::BpfProgram, JailerError> {
+    use seccompiler::SeccompFilter;
+
+    // Create filter with:
+    // - Default action: Trap (send SIGSYS) or Log (audit only) for unlisted syscalls
+    // - Filter action: Allow (for matched syscalls)
+    let default_action = match action {
+        SeccompAction::Trap => seccompiler::SeccompAction::Trap,
+        SeccompAction::Log => seccompiler::SeccompAction::Log,
+    };
+    let filter = SeccompFilter::new(
+        rules,
+        default_action,                    // Default: trap or log unlisted syscalls
+        seccompiler::SeccompAction::Allow, // Match: allow the syscall
+        target_arch(),
+    )
+    .map_err(|e| {
+        JailerError::Isolation(IsolationError::Seccomp(format!(
+            "Failed to create seccomp filter: {}",
+            e
+        )))
+    })?;
+
+    // Convert to BPF bytecode
+    filter.try_into().map_err(|e: seccompiler::BackendError| {
+        JailerError::Isolation(IsolationError::Seccomp(format!(
+            "Failed to compile seccomp filter to BPF: {}",
+            e
+        )))
+    })
+}
+
+/// Serialize a BPF program into the raw `sock_filter` layout the kernel
+/// (and bwrap's `--seccomp`) expects.
+#[cfg(target_os = "linux")]
+pub fn bpf_to_bytes(program: &seccompiler::BpfProgram) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(program.len() * 8);
+    for insn in program {
+        bytes.extend_from_slice(&insn.code.to_ne_bytes());
+        bytes.push(insn.jt);
+        bytes.push(insn.jf);
+        bytes.extend_from_slice(&insn.k.to_ne_bytes());
+    }
+    bytes
+}
+
+/// Build the seccompiler rules map for a profile plus extra syscalls.
+///
+/// `internal_extra` is for syscalls added by boxlite itself (e.g. for the
+/// bwrap launch filter) and bypasses the blocked-list check.
+///
+/// Maps syscall names to numbers; names unknown on this architecture
+/// are logged and skipped.
+#[cfg(target_os = "linux")]
+fn build_rules(
+    profile: SeccompProfile,
+    extra_allowed: &[String],
+    internal_extra: &[&str],
+) -> Result<std::collections::BTreeMap<i64, Vec<seccompiler::SeccompRule>>, JailerError> {
+    use seccompiler::SeccompRule;
     use std::collections::BTreeMap;
 
+    validate_extra_syscalls(extra_allowed)?;
+
+    let mut syscalls = allowed_syscalls(profile);
+    syscalls.extend_from_slice(internal_extra);
+
     // Build rules map: syscall_number -> Vec<SeccompRule>
     // Empty rules vector = unconditional allow for that syscall
     let mut rules: BTreeMap<i64, Vec<SeccompRule>> = BTreeMap::new();
@@ -251,7 +449,7 @@ pub fn generate_bpf_filter() -> Result<seccompiler::BpfProgram, JailerError> {
     let mut mapped_count = 0;
     let mut unmapped = Vec::new();
 
-    for syscall_name in ALLOWED_SYSCALLS {
+    for syscall_name in &syscalls {
         if let Some(nr) = syscall_name_to_nr(syscall_name) {
             rules.insert(nr, vec![]); // Empty rules = allow unconditionally
             mapped_count += 1;
@@ -267,43 +465,44 @@ pub fn generate_bpf_filter() -> Result<seccompiler::BpfProgram, JailerError> {
         );
     }
 
+    let mut unknown_extra = Vec::new();
+    for syscall_name in extra_allowed {
+        if let Some(nr) = syscall_name_to_nr(syscall_name) {
+            rules.insert(nr, vec![]);
+        } else {
+            unknown_extra.push(syscall_name.as_str());
+        }
+    }
+
+    if !unknown_extra.is_empty() {
+        tracing::warn!(
+            unknown_syscalls = ?unknown_extra,
+            "Ignoring unknown extra_allowed_syscalls"
+        );
+    }
+
     tracing::debug!(
-        total_syscalls = ALLOWED_SYSCALLS.len(),
+        ?profile,
+        total_syscalls = syscalls.len(),
         mapped = mapped_count,
         unmapped = unmapped.len(),
+        extra = extra_allowed.len(),
         "Building seccomp filter"
     );
 
-    // Create filter with:
-    // - Default action: Trap (send SIGSYS for unlisted syscalls)
-    // - Filter action: Allow (for matched syscalls)
-    let filter = SeccompFilter::new(
-        rules,
-        SeccompAction::Trap,  // Default: kill process on blocked syscall
-        SeccompAction::Allow, // Match: allow the syscall
-        target_arch(),
-    )
-    .map_err(|e| {
-        JailerError::Isolation(IsolationError::Seccomp(format!(
-            "Failed to create seccomp filter: {}",
-            e
-        )))
-    })?;
-
-    // Convert to BPF bytecode
-    filter.try_into().map_err(|e: seccompiler::BackendError| {
-        JailerError::Isolation(IsolationError::Seccomp(format!(
-            "Failed to compile seccomp filter to BPF: {}",
-            e
-        )))
-    })
+    Ok(rules)
 }
 
 /// Placeholder for non-Linux platforms.
 ///
 /// Seccomp is Linux-specific, so this returns an empty filter on other platforms.
 #[cfg(not(target_os = "linux"))]
-pub fn generate_bpf_filter() -> Result<Vec<u8>, JailerError> {
+pub fn generate_bpf_filter(
+    _profile: SeccompProfile,
+    extra_allowed: &[String],
+    _action: SeccompAction,
+) -> Result<Vec<u8>, JailerError> {
+    validate_extra_syscalls(extra_allowed)?;
     tracing::warn!("Seccomp is only available on Linux");
     Ok(Vec::new())
 }
@@ -340,6 +539,144 @@ pub fn apply_filter(_filter: &[u8]) -> Result<(), JailerError> {
     Ok(())
 }
 
+/// Verify a seccomp filter covers the operations the VMM relies on.
+///
+/// Forks a child that applies the filter built from `profile`,
+/// `extra_allowed` and `action` (the box's effective settings) and then
+/// exercises a representative set of operations: anonymous mmap, opening
+/// and reading a temp file and creating an eventfd. With
+/// [`SeccompAction::Log`] violations are only logged, so the check can
+/// only catch failing operations.
+///
+/// Converts a would-be SIGSYS crash inside a running box into an
+/// actionable startup error.
+///
+/// # Errors
+///
+/// Returns [`IsolationError::Seccomp`] if the child was killed by SIGSYS,
+/// an operation failed, or the fork itself failed.
+#[cfg(target_os = "linux")]
+pub fn self_check(
+    profile: SeccompProfile,
+    extra_allowed: &[String],
+    action: SeccompAction,
+) -> Result<(), JailerError> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let seccomp_err = |msg: String| JailerError::Isolation(IsolationError::Seccomp(msg));
+
+    if profile == SeccompProfile::Disabled {
+        return Ok(());
+    }
+
+    // Prepare everything that allocates before forking
+    let filter = generate_bpf_filter(profile, extra_allowed, action)?;
+    let mut probe = tempfile::NamedTempFile::new()?;
+    probe.write_all(b"boxlite seccomp self-check")?;
+    let probe_path = CString::new(probe.path().as_os_str().as_bytes())
+        .map_err(|e| seccomp_err(format!("self-check temp path: {}", e)))?;
+
+    // SAFETY: the child only performs raw syscalls and `_exit`s; it never
+    // returns into Rust code that may hold locks from other threads.
+    let pid = unsafe { libc::fork() };
+    if pid < 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+
+    if pid == 0 {
+        let code = unsafe { self_check_child(&filter, &probe_path) };
+        unsafe { libc::_exit(code) };
+    }
+
+    let mut status = 0;
+    if unsafe { libc::waitpid(pid, &mut status, 0) } < 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+
+    if libc::WIFSIGNALED(status) {
+        let signal = libc::WTERMSIG(status);
+        return Err(seccomp_err(if signal == libc::SIGSYS {
+            "self-check child killed by SIGSYS: allowlist is missing a required syscall".to_string()
+        } else {
+            format!("self-check child killed by signal {}", signal)
+        }));
+    }
+
+    match libc::WEXITSTATUS(status) {
+        0 => Ok(()),
+        step => Err(seccomp_err(format!(
+            "self-check operation failed at step {}",
+            step
+        ))),
+    }
+}
+
+/// Body of the self-check child. Returns the exit code (0 = success,
+/// otherwise the index of the failing step).
+///
+/// # Safety
+///
+/// Must only be called in a freshly forked child process.
+#[cfg(target_os = "linux")]
+unsafe fn self_check_child(filter: &seccompiler::BpfProgram, probe_path: &std::ffi::CStr) -> i32 {
+    unsafe {
+        // Make sure SIGSYS terminates the child even if the parent installed a handler
+        libc::signal(libc::SIGSYS, libc::SIG_DFL);
+
+        if seccompiler::apply_filter(filter).is_err() {
+            return 1;
+        }
+
+        // mmap/munmap
+        let len = 4096;
+        let addr = libc::mmap(
+            std::ptr::null_mut(),
+            len,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        );
+        if addr == libc::MAP_FAILED {
+            return 2;
+        }
+        libc::munmap(addr, len);
+
+        // open/read a file
+        let fd = libc::openat(libc::AT_FDCWD, probe_path.as_ptr(), libc::O_RDONLY);
+        if fd < 0 {
+            return 3;
+        }
+        let mut buf = [0u8; 16];
+        let n = libc::read(fd, buf.as_mut_ptr().cast(), buf.len());
+        libc::close(fd);
+        if n <= 0 {
+            return 4;
+        }
+
+        // eventfd
+        let efd = libc::eventfd(0, libc::EFD_CLOEXEC);
+        if efd < 0 {
+            return 5;
+        }
+        libc::close(efd);
+
+        0
+    }
+}
+
+/// Placeholder for non-Linux platforms.
+#[cfg(not(target_os = "linux"))]
+pub fn self_check(
+    _profile: SeccompProfile,
+    _extra_allowed: &[String],
+    _action: SeccompAction,
+) -> Result<(), JailerError> {
+    tracing::warn!("Seccomp is only available on Linux, self-check skipped");
+    Ok(())
+}
+
 /// Get the target architecture for seccomp filter compilation.
 #[cfg(target_os = "linux")]
 fn target_arch() -> seccompiler::TargetArch {
@@ -418,6 +755,8 @@ fn syscall_name_to_nr(name: &str) -> Option<i64> {
         "fallocate" => libc::SYS_fallocate,
         "fsync" => libc::SYS_fsync,
         "fdatasync" => libc::SYS_fdatasync,
+        "sync" => libc::SYS_sync,
+        "syncfs" => libc::SYS_syncfs,
 
         // KVM operations
         "ioctl" => libc::SYS_ioctl,
@@ -505,6 +844,7 @@ fn syscall_name_to_nr(name: &str) -> Option<i64> {
         "uname" => libc::SYS_uname,
         "arch_prctl" => libc::SYS_arch_prctl,
         "prctl" => libc::SYS_prctl,
+        "seccomp" => libc::SYS_seccomp,
         "getrandom" => libc::SYS_getrandom,
         "sched_yield" => libc::SYS_sched_yield,
         "sched_getaffinity" => libc::SYS_sched_getaffinity,
@@ -524,6 +864,11 @@ fn syscall_name_to_nr(name: &str) -> Option<i64> {
         "signalfd4" => libc::SYS_signalfd4,
 
         // Process management (for VM process lifecycle)
+        "execve" => libc::SYS_execve,
+        #[cfg(target_arch = "x86_64")]
+        "fork" => libc::SYS_fork,
+        #[cfg(target_arch = "x86_64")]
+        "vfork" => libc::SYS_vfork,
         "wait4" => libc::SYS_wait4,
         "waitid" => libc::SYS_waitid,
 
@@ -604,7 +949,7 @@ mod tests {
     #[cfg(target_os = "linux")]
     fn test_generate_bpf_filter() {
         // Test that BPF filter generation succeeds
-        let result = generate_bpf_filter();
+        let result = generate_bpf_filter(SeccompProfile::Strict, &[], SeccompAction::Trap);
         assert!(result.is_ok(), "BPF filter generation should succeed");
 
         let bpf = result.unwrap();
@@ -612,6 +957,87 @@ mod tests {
         assert!(!bpf.is_empty(), "BPF program should not be empty");
     }
 
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_generate_bpf_filter_log_mode() {
+        let bpf = generate_bpf_filter(SeccompProfile::Strict, &[], SeccompAction::Log)
+            .expect("Log-mode filter should compile");
+        assert!(!bpf.is_empty(), "Log-mode BPF program should not be empty");
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_self_check_passes() {
+        for profile in [SeccompProfile::Strict, SeccompProfile::Permissive] {
+            self_check(profile, &[], SeccompAction::Trap)
+                .unwrap_or_else(|e| panic!("{:?} should pass the self-check: {}", profile, e));
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_launch_filter_allows_execve() {
+        let rules = build_rules(SeccompProfile::Strict, &[], LAUNCH_SYSCALLS).unwrap();
+        assert!(rules.contains_key(&libc::SYS_execve));
+        assert!(rules.contains_key(&libc::SYS_seccomp));
+
+        let bpf = generate_launch_filter(SeccompProfile::Strict, &[], SeccompAction::Trap).unwrap();
+        assert_eq!(bpf_to_bytes(&bpf).len(), bpf.len() * 8);
+    }
+
+    #[test]
+    fn test_strict_is_strict_subset_of_permissive() {
+        let strict: HashSet<&str> = allowed_syscalls(SeccompProfile::Strict)
+            .into_iter()
+            .collect();
+        let permissive: HashSet<&str> = allowed_syscalls(SeccompProfile::Permissive)
+            .into_iter()
+            .collect();
+
+        assert!(strict.is_subset(&permissive));
+        assert!(strict.len() < permissive.len());
+        assert!(permissive.contains("fork"));
+    }
+
+    #[test]
+    fn test_permissive_keeps_blocked_syscalls_blocked() {
+        for syscall in allowed_syscalls(SeccompProfile::Permissive) {
+            assert!(!is_blocked(syscall), "{} must stay blocked", syscall);
+        }
+    }
+
+    #[test]
+    fn test_disabled_profile_generates_empty_program() {
+        assert!(allowed_syscalls(SeccompProfile::Disabled).is_empty());
+
+        let bpf = generate_bpf_filter(SeccompProfile::Disabled, &[], SeccompAction::Trap).unwrap();
+        assert!(bpf.is_empty(), "Disabled profile should produce no filter");
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_extra_allowed_syscall_in_rules() {
+        let extra = vec!["sync".to_string()];
+        let rules = build_rules(SeccompProfile::Strict, &extra, &[]).unwrap();
+        assert!(rules.contains_key(&libc::SYS_sync));
+
+        // Strict profile alone doesn't allow sync
+        let rules = build_rules(SeccompProfile::Strict, &[], &[]).unwrap();
+        assert!(!rules.contains_key(&libc::SYS_sync));
+
+        assert!(generate_bpf_filter(SeccompProfile::Strict, &extra, SeccompAction::Trap).is_ok());
+    }
+
+    #[test]
+    fn test_extra_allowed_blocked_syscall_rejected() {
+        let extra = vec!["ptrace".to_string()];
+        let result = generate_bpf_filter(SeccompProfile::Strict, &extra, SeccompAction::Trap);
+        assert!(matches!(
+            result,
+            Err(JailerError::Config(ConfigError::InvalidConfig(_)))
+        ));
+    }
+
     #[test]
     #[cfg(target_os = "linux")]
     fn test_syscall_name_to_nr() {
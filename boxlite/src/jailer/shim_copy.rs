@@ -16,22 +16,36 @@
 //! 3. **No External Dependencies**: The jail only needs access to files
 //!    inside the box directory, not external paths.
 //!
+//! Development and single-tenant setups can opt out of the per-box copy with
+//! [`ShimLinkMode::Hardlink`] or [`ShimLinkMode::Reflink`].
+//!
 //! # Usage
 //!
 //! ```ignore
 //! use boxlite::jailer::shim_copy::copy_shim_to_box;
 //!
-//! let copied_shim = copy_shim_to_box(&shim_path, &box_dir)?;
+//! let copied_shim = copy_shim_to_box(&shim_path, &box_dir, false, ShimLinkMode::Copy)?;
 //! // copied_shim is now at box_dir/bin/boxlite-shim
 //! ```
 
-use crate::jailer::common::fs::copy_if_newer;
+use crate::jailer::common::fs::{copy_if_newer, reflink_if_newer, reflink_or_copy};
+use crate::runtime::options::ShimLinkMode;
+use crate::util::sha256_file;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use std::path::{Path, PathBuf};
 
 /// Library file patterns to copy alongside the shim binary.
+///
+/// Used when the shim directory has no [`BUNDLED_LIB_MANIFEST`].
 const BUNDLED_LIB_PATTERNS: &[&str] = &["libkrun.so", "libkrunfw.so", "libgvproxy.so"];
 
+/// Optional manifest next to the shim listing the libraries it needs.
+///
+/// One file name prefix per line (e.g. `libkrun.so`, `libepoxy.so.0`);
+/// blank lines and `#` comments are ignored. Every entry must match at
+/// least one file in the shim directory.
+const BUNDLED_LIB_MANIFEST: &str = "boxlite-libs.txt";
+
 /// Copy shim binary and bundled libraries to box directory for jail isolation.
 ///
 /// This follows Firecracker's approach: copy (not hard-link) binaries into the
@@ -41,6 +55,9 @@ const BUNDLED_LIB_PATTERNS: &[&str] = &["libkrun.so", "libkrunfw.so", "libgvprox
 ///
 /// * `shim_path` - Path to the original shim binary
 /// * `box_dir` - Path to the box directory (e.g., `~/.boxlite/boxes/{box_id}`)
+/// * `verify` - Compare SHA-256 checksums instead of trusting mtime and size
+///   (see [`copy_verified`])
+/// * `mode` - Copy, hard-link, or reflink the files (see [`copy_file`])
 ///
 /// # Returns
 ///
@@ -56,11 +73,16 @@ const BUNDLED_LIB_PATTERNS: &[&str] = &["libkrun.so", "libkrunfw.so", "libgvprox
 /// # Example
 ///
 /// ```ignore
-/// let copied_shim = copy_shim_to_box(&shim_path, &box_dir)?;
+/// let copied_shim = copy_shim_to_box(&shim_path, &box_dir, false, ShimLinkMode::Copy)?;
 /// // Use copied_shim instead of original shim_path
 /// ```
 #[cfg(target_os = "linux")]
-pub fn copy_shim_to_box(shim_path: &Path, box_dir: &Path) -> BoxliteResult<PathBuf> {
+pub fn copy_shim_to_box(
+    shim_path: &Path,
+    box_dir: &Path,
+    verify: bool,
+    mode: ShimLinkMode,
+) -> BoxliteResult<PathBuf> {
     let bin_dir = box_dir.join("bin");
     std::fs::create_dir_all(&bin_dir).map_err(|e| {
         BoxliteError::Storage(format!(
@@ -74,7 +96,7 @@ pub fn copy_shim_to_box(shim_path: &Path, box_dir: &Path) -> BoxliteResult<PathB
     let shim_name = shim_path.file_name().unwrap_or_default();
     let dest_shim = bin_dir.join(shim_name);
 
-    let copied = copy_if_newer(shim_path, &dest_shim).map_err(|e| {
+    let copied = copy_file(shim_path, &dest_shim, verify, mode).map_err(|e| {
         BoxliteError::Storage(format!(
             "Failed to copy shim {} to {}: {}",
             shim_path.display(),
@@ -93,15 +115,16 @@ pub fn copy_shim_to_box(shim_path: &Path, box_dir: &Path) -> BoxliteResult<PathB
 
     // Copy bundled libraries from shim's directory
     if let Some(shim_dir) = shim_path.parent() {
-        copy_bundled_libraries(shim_dir, &bin_dir)?;
+        copy_bundled_libraries(shim_dir, &bin_dir, verify, mode)?;
     }
 
     Ok(dest_shim)
 }
 
-/// Copy bundled libraries (libkrun, libkrunfw, libgvproxy) to destination.
+/// Copy bundled libraries (libkrun, libkrunfw, libgvproxy, ...) to destination.
 ///
-/// Only copies libraries that match the patterns in `BUNDLED_LIB_PATTERNS`.
+/// Copies libraries matching the entries of `BUNDLED_LIB_MANIFEST` when the
+/// source directory has one, otherwise those matching `BUNDLED_LIB_PATTERNS`.
 /// Uses copy-if-newer to avoid unnecessary copies.
 ///
 /// # Arguments
@@ -111,9 +134,22 @@ pub fn copy_shim_to_box(shim_path: &Path, box_dir: &Path) -> BoxliteResult<PathB
 ///
 /// # Errors
 ///
-/// Returns [`BoxliteError::Storage`] if a library copy fails.
+/// Returns [`BoxliteError::Storage`] if a library copy fails, the manifest
+/// is unreadable, or a manifest entry matches no file.
 #[cfg(target_os = "linux")]
-fn copy_bundled_libraries(src_dir: &Path, dest_dir: &Path) -> BoxliteResult<()> {
+fn copy_bundled_libraries(
+    src_dir: &Path,
+    dest_dir: &Path,
+    verify: bool,
+    mode: ShimLinkMode,
+) -> BoxliteResult<()> {
+    let manifest = read_lib_manifest(src_dir)?;
+    let patterns: Vec<&str> = match &manifest {
+        Some(entries) => entries.iter().map(String::as_str).collect(),
+        None => BUNDLED_LIB_PATTERNS.to_vec(),
+    };
+    let mut matched = vec![false; patterns.len()];
+
     let entries = match std::fs::read_dir(src_dir) {
         Ok(entries) => entries,
         Err(e) => {
@@ -131,11 +167,18 @@ fn copy_bundled_libraries(src_dir: &Path, dest_dir: &Path) -> BoxliteResult<()>
         let name_str = name.to_string_lossy();
 
         // Check if this file matches any of our library patterns
-        if BUNDLED_LIB_PATTERNS.iter().any(|p| name_str.starts_with(p)) {
+        let mut is_lib = false;
+        for (pattern, hit) in patterns.iter().zip(matched.iter_mut()) {
+            if name_str.starts_with(pattern) {
+                *hit = true;
+                is_lib = true;
+            }
+        }
+        if is_lib {
             let src_path = entry.path();
             let dest_path = dest_dir.join(&name);
 
-            let copied = copy_if_newer(&src_path, &dest_path).map_err(|e| {
+            let copied = copy_file(&src_path, &dest_path, verify, mode).map_err(|e| {
                 BoxliteError::Storage(format!(
                     "Failed to copy library {} to {}: {}",
                     src_path.display(),
@@ -154,9 +197,197 @@ fn copy_bundled_libraries(src_dir: &Path, dest_dir: &Path) -> BoxliteResult<()>
         }
     }
 
+    // A listed library that is missing would only surface as a shim load failure
+    if manifest.is_some()
+        && let Some(missing) = patterns
+            .iter()
+            .zip(&matched)
+            .find_map(|(pattern, hit)| (!hit).then_some(pattern))
+    {
+        return Err(BoxliteError::Storage(format!(
+            "Library '{}' listed in {} not found in {}",
+            missing,
+            BUNDLED_LIB_MANIFEST,
+            src_dir.display()
+        )));
+    }
+
     Ok(())
 }
 
+/// Place `src` at `dest` according to `mode`.
+///
+/// `Hardlink` links when both paths share a filesystem; otherwise, and for
+/// the other modes, the file is written with [`copy_verified`] or the
+/// copy-if-newer helpers. Returns `Ok(true)` if `dest` was (re)written.
+#[cfg(target_os = "linux")]
+fn copy_file(src: &Path, dest: &Path, verify: bool, mode: ShimLinkMode) -> BoxliteResult<bool> {
+    if mode == ShimLinkMode::Hardlink {
+        if let Some(linked) = try_hard_link(src, dest)? {
+            return Ok(linked);
+        }
+    } else if is_same_file(src, dest) {
+        // Left over from Hardlink mode: writing through it would truncate the source
+        remove_file(dest)?;
+    }
+
+    if verify {
+        return copy_verified(src, dest, mode);
+    }
+    let copied = match mode {
+        ShimLinkMode::Reflink => reflink_if_newer(src, dest),
+        ShimLinkMode::Copy | ShimLinkMode::Hardlink => copy_if_newer(src, dest),
+    };
+    copied.map_err(|e| BoxliteError::Storage(e.to_string()))
+}
+
+/// Hard-link `src` at `dest` if both are on the same filesystem.
+///
+/// Returns `Ok(Some(false))` if `dest` is already a link to `src`, and
+/// `Ok(None)` if no link could be made, in which case the caller copies.
+#[cfg(target_os = "linux")]
+fn try_hard_link(src: &Path, dest: &Path) -> BoxliteResult<Option<bool>> {
+    use std::os::unix::fs::MetadataExt;
+
+    if is_same_file(src, dest) {
+        return Ok(Some(false));
+    }
+
+    let src_dev = std::fs::metadata(src)
+        .map_err(|e| BoxliteError::Storage(format!("Failed to stat {}: {}", src.display(), e)))?
+        .dev();
+    let dest_dir = dest.parent().unwrap_or(Path::new("."));
+    if !std::fs::metadata(dest_dir).is_ok_and(|m| m.dev() == src_dev) {
+        return Ok(None);
+    }
+
+    if dest.exists() {
+        remove_file(dest)?;
+    }
+    match std::fs::hard_link(src, dest) {
+        Ok(()) => Ok(Some(true)),
+        Err(e) => {
+            // e.g. fs.protected_hardlinks when the source belongs to another user
+            tracing::debug!(
+                src = %src.display(),
+                dest = %dest.display(),
+                error = %e,
+                "Hard link failed, copying instead"
+            );
+            Ok(None)
+        }
+    }
+}
+
+/// Whether `a` and `b` are the same inode.
+#[cfg(target_os = "linux")]
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    match (std::fs::metadata(a), std::fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn remove_file(path: &Path) -> BoxliteResult<()> {
+    std::fs::remove_file(path)
+        .map_err(|e| BoxliteError::Storage(format!("Failed to remove {}: {}", path.display(), e)))
+}
+
+/// Copy `src` to `dest` unless `dest` already has the same SHA-256.
+///
+/// The source checksum is recorded in a `{dest}.sha256` sidecar after each
+/// copy. A destination whose content or sidecar no longer matches the source
+/// is re-copied with a warning, catching corruption that mtime and size
+/// checks miss.
+///
+/// Returns `Ok(true)` if the file was copied.
+#[cfg(target_os = "linux")]
+fn copy_verified(src: &Path, dest: &Path, mode: ShimLinkMode) -> BoxliteResult<bool> {
+    let sidecar = checksum_sidecar(dest);
+    let expected = sha256_file(src)?;
+
+    if dest.exists() {
+        let actual = sha256_file(dest)?;
+        let recorded = std::fs::read_to_string(&sidecar).unwrap_or_default();
+        if actual == expected && recorded.trim() == expected {
+            return Ok(false);
+        }
+        tracing::warn!(
+            dest = %dest.display(),
+            expected = %expected,
+            actual = %actual,
+            "Checksum mismatch for copied file, re-copying"
+        );
+    }
+
+    let storage_err = |what: &str, path: &Path, e: std::io::Error| {
+        BoxliteError::Storage(format!("Failed to {} {}: {}", what, path.display(), e))
+    };
+    let written = match mode {
+        ShimLinkMode::Reflink => reflink_or_copy(src, dest),
+        ShimLinkMode::Copy | ShimLinkMode::Hardlink => std::fs::copy(src, dest).map(drop),
+    };
+    written.map_err(|e| storage_err("copy to", dest, e))?;
+
+    let copied = sha256_file(dest)?;
+    if copied != expected {
+        return Err(BoxliteError::Storage(format!(
+            "Checksum of {} is {} after copy, expected {}",
+            dest.display(),
+            copied,
+            expected
+        )));
+    }
+    std::fs::write(&sidecar, format!("{}\n", expected))
+        .map_err(|e| storage_err("write checksum", &sidecar, e))?;
+    Ok(true)
+}
+
+/// Path of the checksum sidecar for a copied file (`{file}.sha256`).
+#[cfg(target_os = "linux")]
+fn checksum_sidecar(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".sha256");
+    path.with_file_name(name)
+}
+
+/// Read the library entries from `src_dir/BUNDLED_LIB_MANIFEST`, if present.
+#[cfg(target_os = "linux")]
+fn read_lib_manifest(src_dir: &Path) -> BoxliteResult<Option<Vec<String>>> {
+    let path = src_dir.join(BUNDLED_LIB_MANIFEST);
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(BoxliteError::Storage(format!(
+                "Failed to read library manifest {}: {}",
+                path.display(),
+                e
+            )));
+        }
+    };
+
+    let mut entries = Vec::new();
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        // Entries name files in the shim directory, never other paths
+        if line.contains('/') || line == "." || line == ".." {
+            return Err(BoxliteError::Storage(format!(
+                "Invalid library entry '{}' in {}",
+                line,
+                path.display()
+            )));
+        }
+        entries.push(line.to_string());
+    }
+    Ok(Some(entries))
+}
+
 /// Result of a shim copy operation.
 #[allow(dead_code)] // Prepared for future structured return type
 #[derive(Debug)]
@@ -203,4 +434,100 @@ mod tests {
         assert!(!patterns.iter().any(|p| "libc.so.6".starts_with(p)));
         assert!(!patterns.iter().any(|p| "boxlite-shim".starts_with(p)));
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_manifest_libraries_are_copied() {
+        let src = tempfile::tempdir().unwrap();
+        let dest = tempfile::tempdir().unwrap();
+        for name in ["libkrun.so.1", "libepoxy.so.0", "libunrelated.so"] {
+            std::fs::write(src.path().join(name), name).unwrap();
+        }
+        std::fs::write(
+            src.path().join(BUNDLED_LIB_MANIFEST),
+            "# shim dependencies\nlibkrun.so\n\nlibepoxy.so.0\n",
+        )
+        .unwrap();
+
+        copy_bundled_libraries(src.path(), dest.path(), false, ShimLinkMode::Copy).unwrap();
+
+        assert!(dest.path().join("libkrun.so.1").exists());
+        assert!(dest.path().join("libepoxy.so.0").exists());
+        assert!(!dest.path().join("libunrelated.so").exists());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_manifest_missing_library_fails() {
+        let src = tempfile::tempdir().unwrap();
+        let dest = tempfile::tempdir().unwrap();
+        std::fs::write(src.path().join(BUNDLED_LIB_MANIFEST), "libmissing.so\n").unwrap();
+
+        let err =
+            copy_bundled_libraries(src.path(), dest.path(), false, ShimLinkMode::Copy).unwrap_err();
+        assert!(err.to_string().contains("libmissing.so"), "{}", err);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_no_manifest_uses_default_patterns() {
+        let src = tempfile::tempdir().unwrap();
+        let dest = tempfile::tempdir().unwrap();
+        std::fs::write(src.path().join("libgvproxy.so"), "").unwrap();
+        std::fs::write(src.path().join("libepoxy.so.0"), "").unwrap();
+
+        copy_bundled_libraries(src.path(), dest.path(), false, ShimLinkMode::Copy).unwrap();
+
+        assert!(dest.path().join("libgvproxy.so").exists());
+        assert!(!dest.path().join("libepoxy.so.0").exists());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_verified_copy_recopies_corrupted_destination() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("boxlite-shim");
+        let dest = dir.path().join("bin-boxlite-shim");
+        std::fs::write(&src, b"good shim").unwrap();
+
+        assert!(copy_verified(&src, &dest, ShimLinkMode::Copy).unwrap());
+        assert!(!copy_verified(&src, &dest, ShimLinkMode::Copy).unwrap());
+        assert_eq!(
+            std::fs::read_to_string(checksum_sidecar(&dest))
+                .unwrap()
+                .trim(),
+            sha256_file(&src).unwrap()
+        );
+
+        // Same size and a newer mtime: invisible to copy-if-newer
+        std::fs::write(&dest, b"evil shim").unwrap();
+        assert!(!copy_if_newer(&src, &dest).unwrap());
+
+        assert!(copy_verified(&src, &dest, ShimLinkMode::Copy).unwrap());
+        assert_eq!(std::fs::read(&dest).unwrap(), b"good shim");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_hardlink_mode_links_same_inode() {
+        use std::os::unix::fs::MetadataExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("boxlite-shim");
+        let dest = dir.path().join("bin-boxlite-shim");
+        std::fs::write(&src, b"shim").unwrap();
+
+        assert!(copy_file(&src, &dest, false, ShimLinkMode::Hardlink).unwrap());
+        assert!(!copy_file(&src, &dest, false, ShimLinkMode::Hardlink).unwrap());
+        let src_meta = std::fs::metadata(&src).unwrap();
+        let dest_meta = std::fs::metadata(&dest).unwrap();
+        assert_eq!(src_meta.ino(), dest_meta.ino());
+        assert_eq!(src_meta.nlink(), 2);
+
+        // Switching back to Copy replaces the link without touching the source
+        assert!(copy_file(&src, &dest, false, ShimLinkMode::Copy).unwrap());
+        assert!(!is_same_file(&src, &dest));
+        assert_eq!(std::fs::read(&src).unwrap(), b"shim");
+        assert_eq!(std::fs::read(&dest).unwrap(), b"shim");
+    }
 }
@@ -26,6 +26,8 @@ mod portal;
 mod rootfs;
 mod volumes;
 
+pub use disk::DiskSnapshot;
+pub use images::{Platform, PullOptions, PullProgress, PullProgressFn, RetryPolicy};
 pub use litebox::LiteBox;
 pub use runtime::BoxliteRuntime;
 
@@ -34,19 +36,32 @@ pub use litebox::{
     BoxCommand, ExecResult, ExecStderr, ExecStdin, ExecStdout, Execution, ExecutionId,
 };
 pub use metrics::{BoxMetrics, RuntimeMetrics};
+pub use pipeline::PipelineObserver;
 use runtime::layout::FilesystemLayout;
+pub use runtime::layout::LayoutDirs;
 pub use runtime::options::{
-    BoxOptions, BoxliteOptions, ResourceLimits, RootfsSpec, SecurityOptions,
+    BoxOptions, BoxliteOptions, CleanTempOnStart, DatabaseOptions, DbIntegrityMode, DiskConfig,
+    LogFormat, LogRotation, PortForward, PortProtocol, ResourceLimits, RestartPolicy, RootfsSpec,
+    SeccompAction, SeccompProfile, SecurityOptions, ShimLinkMode, WarmPoolOptions,
 };
 pub use runtime::types::ContainerID;
-pub use runtime::types::{BoxID, BoxInfo, BoxState, BoxStateInfo, BoxStatus};
+pub use runtime::types::{
+    BoxID, BoxInfo, BoxState, BoxStateInfo, BoxStatus, HealthStatus, ImageDetails, ImageInfo,
+    StartupTimings,
+};
 
 /// Initialize tracing for Boxlite using the provided filesystem layout.
 ///
-/// Logs are written to `<layout.home_dir()>/logs/boxlite.log` with daily rotation.
+/// Logs are written to `<layout.home_dir()>/logs/boxlite.log`, formatted
+/// according to `format` and rotated according to `rotation`.
 /// Uses the `RUST_LOG` environment variable for filtering (defaults to `info`).
-/// Idempotent: subsequent calls return immediately once initialized.
-pub fn init_logging_for(layout: &FilesystemLayout) -> BoxliteResult<()> {
+/// Idempotent: subsequent calls return immediately once initialized, so the
+/// format, rotation (and log directory) of the first call win.
+pub fn init_logging_for(
+    layout: &FilesystemLayout,
+    format: LogFormat,
+    rotation: LogRotation,
+) -> BoxliteResult<()> {
     let logs_dir = layout.logs_dir();
     std::fs::create_dir_all(&logs_dir).map_err(|e| {
         BoxliteError::Storage(format!(
@@ -56,9 +71,40 @@ pub fn init_logging_for(layout: &FilesystemLayout) -> BoxliteResult<()> {
         ))
     })?;
 
+    if LOG_GUARD.get().is_some() {
+        return Ok(());
+    }
+
+    // Open the size-limited writer up front so failures surface as errors
+    let size_limited = match rotation {
+        LogRotation::SizeLimited { max_bytes } => Some(
+            util::rolling::SizeRollingWriter::new(&logs_dir, "boxlite.log", max_bytes).map_err(
+                |e| {
+                    BoxliteError::Storage(format!(
+                        "Failed to open log file in {}: {}",
+                        logs_dir.display(),
+                        e
+                    ))
+                },
+            )?,
+        ),
+        _ => None,
+    };
+
     let _ = LOG_GUARD.get_or_init(|| {
-        let file_appender = tracing_appender::rolling::daily(logs_dir, "boxlite.log");
-        let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+        let (non_blocking, guard) = match (rotation, size_limited) {
+            (_, Some(writer)) => tracing_appender::non_blocking(writer),
+            (LogRotation::Hourly, None) => tracing_appender::non_blocking(
+                tracing_appender::rolling::hourly(logs_dir, "boxlite.log"),
+            ),
+            (LogRotation::Never, None) => tracing_appender::non_blocking(
+                tracing_appender::rolling::never(logs_dir, "boxlite.log"),
+            ),
+            _ => tracing_appender::non_blocking(tracing_appender::rolling::daily(
+                logs_dir,
+                "boxlite.log",
+            )),
+        };
 
         let env_filter = EnvFilter::try_from_default_env()
             .or_else(|_| EnvFilter::try_new("info"))
@@ -66,7 +112,7 @@ pub fn init_logging_for(layout: &FilesystemLayout) -> BoxliteResult<()> {
 
         // If global default subscriber is already set, this will return an error.
         // We ignore it to avoid interfering with host-configured tracing.
-        util::register_to_tracing(non_blocking, env_filter);
+        util::register_to_tracing(non_blocking, env_filter, format);
 
         guard
     });
@@ -4,24 +4,30 @@
 // IMPORTS
 // ============================================================================
 
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
-use parking_lot::RwLock;
+use chrono::Utc;
+use parking_lot::{Mutex, RwLock};
 use tokio::sync::OnceCell;
 use tokio_util::sync::CancellationToken;
 
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
+use super::bundle::{self, BUNDLE_VERSION, BundleManifest};
 use super::config::BoxConfig;
 use super::exec::{BoxCommand, ExecStderr, ExecStdin, ExecStdout, Execution};
-use super::state::BoxState;
-use crate::disk::Disk;
+use super::state::{BoxState, HealthStatus, StartupTimings};
+use crate::disk::{Disk, DiskSnapshot, DiskSnapshots, Qcow2Helper};
 #[cfg(target_os = "linux")]
 use crate::fs::BindMountHandle;
 use crate::lock::LockGuard;
-use crate::metrics::{BoxMetrics, BoxMetricsStorage};
+use crate::metrics::{BoxMetrics, BoxMetricsStorage, MetricsWindow};
 use crate::portal::GuestSession;
+use crate::runtime::constants::filenames;
+use crate::runtime::options::PortForward;
 use crate::runtime::rt_impl::SharedRuntimeImpl;
 use crate::runtime::types::BoxStatus;
 use crate::vmm::controller::VmmHandler;
@@ -34,6 +40,9 @@ use crate::{BoxID, BoxInfo};
 /// Shared reference to BoxImpl.
 pub type SharedBoxImpl = Arc<BoxImpl>;
 
+/// How long `health()` waits for the guest to answer a ping.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
 // ============================================================================
 // LIVE STATE
 // ============================================================================
@@ -49,12 +58,20 @@ pub(crate) struct LiveState {
 
     // Metrics
     metrics: BoxMetricsStorage,
+    /// Stage breakdown of this start, persisted on `BoxState`
+    startup_timings: StartupTimings,
+    /// Samples taken by the runtime's background sampler (empty when disabled)
+    samples: MetricsWindow,
 
     // Disk resources (kept for lifecycle management)
     _container_rootfs_disk: Disk,
     #[allow(dead_code)]
     guest_rootfs_disk: Option<Disk>,
 
+    // Networking
+    /// Port forwards applied at spawn (None when reattached to a running box)
+    port_forwards: Option<Vec<PortForward>>,
+
     // Platform-specific
     #[cfg(target_os = "linux")]
     #[allow(dead_code)]
@@ -67,16 +84,21 @@ impl LiveState {
         handler: Box<dyn VmmHandler>,
         guest_session: GuestSession,
         metrics: BoxMetricsStorage,
+        startup_timings: StartupTimings,
         container_rootfs_disk: Disk,
         guest_rootfs_disk: Option<Disk>,
+        port_forwards: Option<Vec<PortForward>>,
         #[cfg(target_os = "linux")] bind_mount: Option<BindMountHandle>,
     ) -> Self {
         Self {
             handler: std::sync::Mutex::new(handler),
             guest_session,
             metrics,
+            startup_timings,
+            samples: MetricsWindow::new(),
             _container_rootfs_disk: container_rootfs_disk,
             guest_rootfs_disk,
+            port_forwards,
             #[cfg(target_os = "linux")]
             bind_mount,
         }
@@ -98,6 +120,17 @@ pub(crate) struct BoxImpl {
     /// Cancellation token for this box (child of runtime's token).
     /// When cancelled (via stop() or runtime shutdown), all operations abort gracefully.
     pub(crate) shutdown_token: CancellationToken,
+    /// Cancelled once the box has reached `Stopped`, by `stop()` or the exit
+    /// watcher. Wakes `wait()`.
+    pub(crate) stopped: CancellationToken,
+    /// Result of the last health probe, reported on `BoxInfo`.
+    last_health: Mutex<Option<HealthStatus>>,
+    /// Whether an exit watcher runs for this box. It lives until
+    /// `shutdown_token` is cancelled, which also retires the handle, so one
+    /// per handle is enough.
+    exit_watcher: AtomicBool,
+    /// Current name: `config.name` until the box is renamed.
+    name: RwLock<Option<String>>,
 
     // --- Lazily initialized ---
     live: OnceCell<LiveState>,
@@ -124,10 +157,14 @@ impl BoxImpl {
         shutdown_token: CancellationToken,
     ) -> Self {
         Self {
+            name: RwLock::new(config.name.clone()),
             config,
             state: RwLock::new(state),
             runtime,
             shutdown_token,
+            stopped: CancellationToken::new(),
+            last_health: Mutex::new(None),
+            exit_watcher: AtomicBool::new(false),
             live: OnceCell::new(),
         }
     }
@@ -140,13 +177,237 @@ impl BoxImpl {
         &self.config.id
     }
 
+    /// Current name of the box, following renames.
+    pub(crate) fn name(&self) -> Option<String> {
+        self.name.read().clone()
+    }
+
+    pub(crate) fn set_name(&self, name: Option<String>) {
+        *self.name.write() = name;
+    }
+
     pub(crate) fn container_id(&self) -> &str {
         self.config.container.id.as_str()
     }
 
     pub(crate) fn info(&self) -> BoxInfo {
         let state = self.state.read();
-        BoxInfo::new(&self.config, &state)
+        let mut info = BoxInfo::new(&self.config, &state);
+        info.name = self.name();
+        info.health = *self.last_health.lock();
+        info
+    }
+
+    pub(crate) fn log_path(&self) -> PathBuf {
+        self.config.box_home.join(filenames::CONSOLE_LOG)
+    }
+
+    /// Read the console log, optionally keeping only the last `tail` lines.
+    ///
+    /// Returns an empty string if the box has not written any output yet.
+    pub(crate) async fn read_logs(&self, tail: Option<usize>) -> BoxliteResult<String> {
+        let path = self.log_path();
+        let content = match tokio::fs::read(&path).await {
+            Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(String::new()),
+            Err(e) => {
+                return Err(BoxliteError::Storage(format!(
+                    "Failed to read console log {}: {}",
+                    path.display(),
+                    e
+                )));
+            }
+        };
+        Ok(match tail {
+            Some(n) => tail_lines(&content, n).to_string(),
+            None => content,
+        })
+    }
+
+    /// Write the box's container rootfs disk to `out_path` as a raw image.
+    ///
+    /// The box must not be running. The COW disk is flattened together with
+    /// its shared base, so the output is self-contained.
+    pub(crate) async fn export_rootfs(&self, out_path: &Path) -> BoxliteResult<()> {
+        let status = self.state.read().status;
+        if status.is_active() || status == BoxStatus::Stopping {
+            return Err(BoxliteError::InvalidState(format!(
+                "Cannot export rootfs of box in {} state; stop it first",
+                status
+            )));
+        }
+
+        let disk_path = self
+            .runtime
+            .layout
+            .box_layout(self.id().as_str(), self.config.options.isolate_mounts)?
+            .disk_path();
+        if !disk_path.exists() {
+            return Err(BoxliteError::InvalidState(
+                "Box has no rootfs disk yet; start it at least once before exporting".into(),
+            ));
+        }
+
+        let out_path = out_path.to_path_buf();
+        let size = tokio::task::spawn_blocking({
+            let out_path = out_path.clone();
+            move || Qcow2Helper::flatten_to_raw(&disk_path, &out_path)
+        })
+        .await
+        .map_err(|e| BoxliteError::Internal(format!("Rootfs export task failed: {}", e)))??;
+
+        tracing::info!(
+            box_id = %self.id(),
+            out_path = %out_path.display(),
+            size_mb = size / (1024 * 1024),
+            "Exported container rootfs"
+        );
+        Ok(())
+    }
+
+    /// Write the box's options, metadata and flattened disk to a bundle at `out`.
+    pub(crate) async fn export_bundle(&self, out: &Path) -> BoxliteResult<()> {
+        let status = self.state.read().status;
+        if status.is_active() || status == BoxStatus::Stopping {
+            return Err(BoxliteError::InvalidState(format!(
+                "Cannot export box in {} state; stop it first",
+                status
+            )));
+        }
+
+        let layout = self
+            .runtime
+            .layout
+            .box_layout(self.id().as_str(), self.config.options.isolate_mounts)?;
+        // An imported box that never started still only has its bundled base
+        let disk = [layout.disk_path(), layout.imported_base_path()]
+            .into_iter()
+            .find(|path| path.exists());
+
+        let manifest = BundleManifest {
+            version: BUNDLE_VERSION,
+            source_id: self.id().to_string(),
+            name: self.name(),
+            created_at: self.config.created_at,
+            exported_at: Utc::now(),
+            options: bundle::portable_options(&self.config.options, disk.is_some())?,
+        };
+
+        let out = out.to_path_buf();
+        tokio::task::spawn_blocking({
+            let out = out.clone();
+            move || bundle::write_bundle(&out, &manifest, disk.as_deref())
+        })
+        .await
+        .map_err(|e| BoxliteError::Internal(format!("Bundle export task failed: {}", e)))??;
+
+        tracing::info!(
+            box_id = %self.id(),
+            out_path = %out.display(),
+            "Exported box bundle"
+        );
+        Ok(())
+    }
+
+    /// Rewrite the box's qcow2 disk without unused clusters.
+    ///
+    /// Returns the number of bytes reclaimed; a box that has never been
+    /// started has no disk and reclaims nothing.
+    pub(crate) async fn compact_disk(&self) -> BoxliteResult<u64> {
+        // Hold the box lock so the box can't start while its disk is rewritten
+        let lock_id = self.state.read().lock_id;
+        let locker = lock_id
+            .map(|id| self.runtime.lock_manager.retrieve(id))
+            .transpose()?;
+        let _guard = locker.as_deref().map(LockGuard::new);
+
+        let status = self.state.read().status;
+        if status.is_active() || status == BoxStatus::Stopping {
+            return Err(BoxliteError::InvalidState(format!(
+                "Cannot compact disk of box in {} state; stop it first",
+                status
+            )));
+        }
+
+        let disk_path = self
+            .runtime
+            .layout
+            .box_layout(self.id().as_str(), self.config.options.isolate_mounts)?
+            .disk_path();
+        if !disk_path.exists() {
+            return Ok(0);
+        }
+
+        let reclaimed = tokio::task::spawn_blocking(move || Qcow2Helper::compact(&disk_path))
+            .await
+            .map_err(|e| BoxliteError::Internal(format!("Disk compaction task failed: {}", e)))??;
+
+        tracing::info!(
+            box_id = %self.id(),
+            reclaimed_mb = reclaimed / (1024 * 1024),
+            "Compacted box disk"
+        );
+        Ok(reclaimed)
+    }
+
+    /// Copy the box's disk into snapshot `name`.
+    pub(crate) async fn snapshot_disk(&self, name: &str) -> BoxliteResult<DiskSnapshot> {
+        let (disk_path, snapshots) = self.disk_snapshots("snapshot")?;
+        if !disk_path.exists() {
+            return Err(BoxliteError::InvalidState(
+                "Box has no disk yet; start it at least once before taking a snapshot".into(),
+            ));
+        }
+
+        let name = name.to_string();
+        let snapshot = tokio::task::spawn_blocking(move || snapshots.create(&disk_path, &name))
+            .await
+            .map_err(|e| BoxliteError::Internal(format!("Disk snapshot task failed: {}", e)))??;
+
+        tracing::info!(
+            box_id = %self.id(),
+            snapshot = %snapshot.name,
+            size_mb = snapshot.size_bytes / (1024 * 1024),
+            "Took disk snapshot"
+        );
+        Ok(snapshot)
+    }
+
+    /// Replace the box's disk with snapshot `name`.
+    pub(crate) async fn restore_disk(&self, name: &str) -> BoxliteResult<DiskSnapshot> {
+        let (disk_path, snapshots) = self.disk_snapshots("restore")?;
+
+        let name = name.to_string();
+        let snapshot = tokio::task::spawn_blocking(move || snapshots.restore(&disk_path, &name))
+            .await
+            .map_err(|e| BoxliteError::Internal(format!("Disk restore task failed: {}", e)))??;
+
+        tracing::info!(
+            box_id = %self.id(),
+            snapshot = %snapshot.name,
+            "Restored disk from snapshot"
+        );
+        Ok(snapshot)
+    }
+
+    /// Disk path and snapshot store of a stopped box.
+    fn disk_snapshots(&self, action: &str) -> BoxliteResult<(PathBuf, DiskSnapshots)> {
+        let status = self.state.read().status;
+        if status.is_active() || status == BoxStatus::Stopping {
+            return Err(BoxliteError::InvalidState(format!(
+                "Cannot {} disk of box in {} state; stop it first",
+                action, status
+            )));
+        }
+
+        let layout = self
+            .runtime
+            .layout
+            .box_layout(self.id().as_str(), self.config.options.isolate_mounts)?;
+        Ok((
+            layout.disk_path(),
+            DiskSnapshots::new(layout.snapshots_dir()),
+        ))
     }
 
     // ========================================================================
@@ -183,6 +444,10 @@ impl BoxImpl {
             )));
         }
 
+        // A start resets the restart policy budget; restarts made by the
+        // policy record their own count afterwards
+        self.state.write().restart_count = 0;
+
         // Trigger lazy initialization (this does the actual work)
         let _ = self.live_state().await?;
 
@@ -253,6 +518,46 @@ impl BoxImpl {
         ))
     }
 
+    pub(crate) async fn copy_to_guest(
+        &self,
+        host_path: &std::path::Path,
+        guest_path: &str,
+    ) -> BoxliteResult<()> {
+        if self.shutdown_token.is_cancelled() {
+            return Err(BoxliteError::Stopped(
+                "Handle invalidated after stop(). Use runtime.get() to get a new handle.".into(),
+            ));
+        }
+
+        let live = self.live_state().await?;
+        let mut files = live.guest_session.files().await?;
+        let bytes = files
+            .upload(self.container_id(), host_path, guest_path)
+            .await?;
+        tracing::debug!(box_id = %self.config.id, guest_path, bytes, "Copied file into box");
+        Ok(())
+    }
+
+    pub(crate) async fn copy_from_guest(
+        &self,
+        guest_path: &str,
+        host_path: &std::path::Path,
+    ) -> BoxliteResult<()> {
+        if self.shutdown_token.is_cancelled() {
+            return Err(BoxliteError::Stopped(
+                "Handle invalidated after stop(). Use runtime.get() to get a new handle.".into(),
+            ));
+        }
+
+        let live = self.live_state().await?;
+        let mut files = live.guest_session.files().await?;
+        let bytes = files
+            .download(self.container_id(), guest_path, host_path)
+            .await?;
+        tracing::debug!(box_id = %self.config.id, guest_path, bytes, "Copied file out of box");
+        Ok(())
+    }
+
     pub(crate) async fn metrics(&self) -> BoxliteResult<BoxMetrics> {
         // Check if box is stopped before proceeding (via stop() or runtime shutdown)
         if self.shutdown_token.is_cancelled() {
@@ -262,24 +567,220 @@ impl BoxImpl {
         }
 
         let live = self.live_state().await?;
-        let handler = live
-            .handler
-            .lock()
-            .map_err(|e| BoxliteError::Internal(format!("handler lock poisoned: {}", e)))?;
-        let raw = handler.metrics()?;
 
-        Ok(BoxMetrics::from_storage(
+        // Prefer the background sampler's latest observation: its CPU figure is
+        // a delta over one sampling interval rather than since our last call.
+        let fresh_sample = self.runtime.options.metrics_interval.and_then(|interval| {
+            live.samples
+                .latest()
+                .filter(|sample| sample.at.elapsed() <= interval * 2)
+        });
+        let raw = match fresh_sample {
+            Some(sample) => sample.metrics,
+            None => {
+                let handler = live
+                    .handler
+                    .lock()
+                    .map_err(|e| BoxliteError::Internal(format!("handler lock poisoned: {}", e)))?;
+                handler.metrics()?
+            }
+        };
+
+        live.metrics
+            .record_disk_io(raw.disk_read_bytes, raw.disk_write_bytes);
+        let network = raw.network.as_ref();
+
+        let mut metrics = BoxMetrics::from_storage(
             &live.metrics,
             raw.cpu_percent,
             raw.memory_bytes,
-            None,
-            None,
-            None,
-            None,
-        ))
+            raw.disk_read_bytes,
+            raw.disk_write_bytes,
+            network.map(|n| n.bytes_sent),
+            network.map(|n| n.bytes_received),
+            network.and_then(|n| n.tcp_connections),
+            network.and_then(|n| n.tcp_connection_errors),
+        );
+        (metrics.cpu_percent_avg, metrics.memory_bytes_avg) = live.samples.averages();
+        Ok(metrics)
+    }
+
+    /// Ping the guest agent without initializing LiveState.
+    ///
+    /// Uses the live session when this handle started or attached to the
+    /// box; otherwise opens a one-off session over the box's transport.
+    pub(crate) async fn health(&self) -> BoxliteResult<HealthStatus> {
+        let running = !self.shutdown_token.is_cancelled() && self.state.read().status.is_running();
+        let status = if !running {
+            HealthStatus::NotRunning
+        } else {
+            let adhoc_session;
+            let session = match self.live.get() {
+                Some(live) => &live.guest_session,
+                None => {
+                    adhoc_session = GuestSession::new(self.config.transport.clone());
+                    &adhoc_session
+                }
+            };
+            let ping = async { session.guest().await?.ping().await };
+            match tokio::time::timeout(HEALTH_CHECK_TIMEOUT, ping).await {
+                Ok(Ok(())) => HealthStatus::Healthy,
+                Ok(Err(e)) => {
+                    tracing::debug!(box_id = %self.config.id, error = %e, "Health ping failed");
+                    HealthStatus::Unresponsive
+                }
+                Err(_) => {
+                    tracing::debug!(box_id = %self.config.id, "Health ping timed out");
+                    HealthStatus::Unresponsive
+                }
+            }
+        };
+
+        *self.last_health.lock() = Some(status);
+        Ok(status)
+    }
+
+    /// Record one metrics sample for the background sampler.
+    ///
+    /// Never initializes LiveState: boxes that are not started are skipped.
+    pub(crate) fn sample_metrics(&self) {
+        if self.shutdown_token.is_cancelled() {
+            return;
+        }
+        let Some(live) = self.live.get() else {
+            return;
+        };
+
+        let raw = match live.handler.lock() {
+            Ok(handler) => handler.metrics(),
+            Err(e) => Err(BoxliteError::Internal(format!(
+                "handler lock poisoned: {}",
+                e
+            ))),
+        };
+        match raw {
+            Ok(raw) => {
+                live.metrics
+                    .record_disk_io(raw.disk_read_bytes, raw.disk_write_bytes);
+                live.samples.push(raw);
+            }
+            Err(e) => {
+                tracing::debug!(box_id = %self.config.id, error = %e, "Failed to sample box metrics");
+            }
+        }
+    }
+
+    pub(crate) async fn signal(&self, signal: i32) -> BoxliteResult<()> {
+        if self.shutdown_token.is_cancelled() || !self.state.read().status.is_running() {
+            return Err(BoxliteError::InvalidState(format!(
+                "Cannot signal box {}: not running",
+                self.config.id
+            )));
+        }
+
+        let live = self.live_state().await?;
+        let mut container = live.guest_session.container().await?;
+        container
+            .signal(self.config.container.id.as_str(), signal)
+            .await
+    }
+
+    /// Push the host wall-clock time to the guest.
+    pub(crate) async fn sync_time(&self) -> BoxliteResult<()> {
+        if self.shutdown_token.is_cancelled() || !self.state.read().status.is_running() {
+            return Err(BoxliteError::InvalidState(format!(
+                "Cannot sync time of box {}: not running",
+                self.config.id
+            )));
+        }
+
+        let live = self.live_state().await?;
+        let mut guest = live.guest_session.guest().await?;
+        guest.sync_time(std::time::SystemTime::now()).await
+    }
+
+    /// Wait until the box is stopped, by `stop()` or because its VM exited.
+    pub(crate) async fn wait(&self) -> BoxliteResult<BoxStatus> {
+        let (status, pid) = {
+            let state = self.state.read();
+            (state.status, state.pid)
+        };
+        match status {
+            BoxStatus::Stopped => return Ok(status),
+            BoxStatus::Running | BoxStatus::Stopping | BoxStatus::Detached => {}
+            BoxStatus::Configured | BoxStatus::Unknown => {
+                return Err(BoxliteError::InvalidState(format!(
+                    "Cannot wait for box {}: not started",
+                    self.config.id
+                )));
+            }
+        }
+
+        // Handles attached to an already-running box have no exit watcher
+        if let Some(pid) = pid {
+            self.watch_exit(pid);
+        }
+
+        self.stopped.cancelled().await;
+        Ok(self.state.read().status)
+    }
+
+    /// Start the exit watcher for shim `pid` unless one already runs.
+    fn watch_exit(&self, pid: u32) {
+        if self.exit_watcher.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        self.runtime
+            .spawn_exit_watcher(self.config.id.clone(), pid, self.shutdown_token.clone());
+    }
+
+    /// Release this handle from the running VM without stopping it.
+    ///
+    /// Aborts in-flight operations on this handle, stops the exit watcher and
+    /// drops the box from the runtime's cache, so the next `runtime.get()`
+    /// builds a fresh handle that reattaches.
+    pub(crate) fn detach(&self) -> BoxliteResult<()> {
+        if self.shutdown_token.is_cancelled() {
+            return Err(BoxliteError::Stopped(
+                "Handle invalidated after stop(). Use runtime.get() to get a new handle.".into(),
+            ));
+        }
+
+        {
+            let mut state = self.state.write();
+            if state.status != BoxStatus::Running {
+                return Err(BoxliteError::InvalidState(format!(
+                    "Cannot detach box {} in {} state",
+                    self.config.id, state.status
+                )));
+            }
+            state.set_status(BoxStatus::Detached);
+            self.runtime.box_manager.save_box(&self.config.id, &state)?;
+        }
+
+        self.shutdown_token.cancel();
+        self.runtime.untrack_session_box(&self.config.id);
+        self.runtime
+            .invalidate_box_impl(self.id(), self.name().as_deref());
+
+        tracing::info!("Detached box {}", self.id());
+        Ok(())
     }
 
     pub(crate) async fn stop(&self) -> BoxliteResult<()> {
+        // Already torn down by an earlier stop() or the exit watcher
+        if self.shutdown_token.is_cancelled() && self.state.read().status.is_stopped() {
+            return Ok(());
+        }
+
+        // Reattach to a detached VM so the guest is shut down gracefully
+        if self.live.get().is_none()
+            && self.state.read().status == BoxStatus::Detached
+            && let Err(e) = self.live_state().await
+        {
+            tracing::warn!(box_id = %self.config.id, error = %e, "Failed to reattach before stop");
+        }
+
         // Cancel the token - signals all in-flight operations to abort
         self.shutdown_token.cancel();
 
@@ -296,6 +797,18 @@ impl BoxImpl {
             }
         }
 
+        // Reap helpers the shim left behind
+        let (pid, pgid, start_time) = {
+            let state = self.state.read();
+            (state.pid, state.pgid, state.pid_start_time)
+        };
+        if let (Some(pid), Some(pgid)) = (pid, pgid)
+            && !crate::util::kill_shim_process_group(pgid, pid, self.id().as_str(), start_time)
+        {
+            tracing::warn!(box_id = %self.config.id, pgid, "Failed to kill box process group");
+        }
+        self.runtime.untrack_session_box(&self.config.id);
+
         // Clean up PID file (single source of truth)
         let pid_file = self
             .runtime
@@ -322,6 +835,9 @@ impl BoxImpl {
             let mut state = self.state.write();
             state.set_status(BoxStatus::Stopped);
             state.set_pid(None);
+            state.port_forwards.clear();
+            // A user stop resets the restart policy budget
+            state.restart_count = 0;
 
             if was_persisted {
                 // Box was persisted - sync to DB
@@ -331,15 +847,16 @@ impl BoxImpl {
                 self.runtime.box_manager.add_box(&self.config, &state)?;
             }
         }
+        self.stopped.cancel();
 
         // Invalidate cache so new handles get fresh BoxImpl
         self.runtime
-            .invalidate_box_impl(self.id(), self.config.name.as_deref());
+            .invalidate_box_impl(self.id(), self.name().as_deref());
 
         tracing::info!("Stopped box {}", self.id());
 
         if self.config.options.auto_remove {
-            self.runtime.remove_box(self.id(), false)?;
+            self.runtime.remove_box(self.id(), false).await?;
         }
 
         Ok(())
@@ -370,6 +887,14 @@ impl BoxImpl {
 
         let state = self.state.read().clone();
         let is_first_start = state.status == BoxStatus::Configured;
+        // Reattaching to a VM that ran unattended; its clock may have drifted
+        let is_resume = state.status == BoxStatus::Detached;
+
+        // Booting writes to the disk, which may be the base of other boxes' overlays
+        if matches!(state.status, BoxStatus::Configured | BoxStatus::Stopped) {
+            self.runtime
+                .ensure_no_overlay_depends_on(self.id(), &self.config.box_home, "start")?;
+        }
 
         // Retrieve the lock (allocated in create())
         let lock_id = state.lock_id.ok_or_else(|| {
@@ -417,7 +942,16 @@ impl BoxImpl {
 
             let mut state = self.state.write();
             state.set_pid(Some(pid));
+            if state.pid_start_time.is_none() {
+                state.pid_start_time = crate::util::process_start_time(pid);
+            }
+            // Shims spawned before process groups were used share ours
+            state.pgid = crate::util::process_group_id(pid).filter(|&pgid| pgid == pid);
             state.set_status(BoxStatus::Running);
+            if let Some(forwards) = &live_state.port_forwards {
+                state.port_forwards = forwards.clone();
+            }
+            state.startup_timings = Some(live_state.startup_timings.clone());
 
             // Save to DB (cache for queries and recovery)
             self.runtime.box_manager.save_box(&self.config.id, &state)?;
@@ -427,11 +961,27 @@ impl BoxImpl {
                 pid = pid,
                 "Read PID from file and saved to DB"
             );
+
+            // Detached boxes must outlive the runtime that started them
+            if !self.config.options.detach {
+                self.runtime.track_session_box(&self.config.id, pid);
+            }
+            self.watch_exit(pid);
         }
 
         // All operations succeeded - disarm the cleanup guard
         cleanup_guard.disarm();
 
+        if is_resume && self.config.options.sync_time_on_resume {
+            let synced = match live_state.guest_session.guest().await {
+                Ok(mut guest) => guest.sync_time(std::time::SystemTime::now()).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = synced {
+                tracing::warn!(box_id = %self.config.id, error = %e, "Failed to sync guest time");
+            }
+        }
+
         tracing::info!(
             box_id = %self.config.id,
             "Box started successfully (first_start={})",
@@ -442,3 +992,31 @@ impl BoxImpl {
         Ok(live_state)
     }
 }
+
+/// Slice of `content` holding its last `n` lines.
+fn tail_lines(content: &str, n: usize) -> &str {
+    if n == 0 {
+        return "";
+    }
+    let body = content.strip_suffix('\n').unwrap_or(content);
+    match body.rmatch_indices('\n').nth(n - 1) {
+        Some((idx, _)) => &content[idx + 1..],
+        None => content,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tail_lines() {
+        let content = "one\ntwo\nthree\n";
+        assert_eq!(tail_lines(content, 0), "");
+        assert_eq!(tail_lines(content, 1), "three\n");
+        assert_eq!(tail_lines(content, 2), "two\nthree\n");
+        assert_eq!(tail_lines(content, 10), content);
+        assert_eq!(tail_lines("one\ntwo", 1), "two");
+        assert_eq!(tail_lines("", 3), "");
+    }
+}
@@ -0,0 +1,193 @@
+//! Portable box bundles.
+//!
+//! A bundle is a gzipped tarball holding `bundle.json` (the box's options and
+//! metadata) and, for boxes that have been started, `disk.raw` (the writable
+//! disk flattened together with its base). Host paths are left out of the
+//! options so a bundle can be imported on another machine.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::disk::Qcow2Helper;
+use crate::runtime::options::{BoxOptions, RootfsSpec};
+
+/// Bundle format version written by this build.
+pub(crate) const BUNDLE_VERSION: u32 = 1;
+
+const MANIFEST_ENTRY: &str = "bundle.json";
+const DISK_ENTRY: &str = "disk.raw";
+
+/// Contents of `bundle.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct BundleManifest {
+    pub version: u32,
+    /// ID of the exported box; imports get a fresh one.
+    pub source_id: String,
+    pub name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub exported_at: DateTime<Utc>,
+    pub options: BoxOptions,
+}
+
+/// A bundle unpacked into a staging directory.
+pub(crate) struct UnpackedBundle {
+    pub manifest: BundleManifest,
+    /// Raw disk image, if the box had been started before export.
+    pub disk: Option<PathBuf>,
+}
+
+/// `options` with host-specific paths removed.
+///
+/// Volumes, the shared backing file and the sandbox profile are dropped. A
+/// `Disk` or `Directory` rootfs is replaced by a placeholder that import
+/// rewrites to the bundled disk, so it fails if `has_disk` is false.
+pub(crate) fn portable_options(options: &BoxOptions, has_disk: bool) -> BoxliteResult<BoxOptions> {
+    let mut options = options.clone();
+    options.volumes.clear();
+    options.disk.backing_file = None;
+    options.security.sandbox_profile = None;
+    options.pipeline_observer = None;
+
+    match options.rootfs {
+        RootfsSpec::Image(_) => {}
+        RootfsSpec::Disk(_) | RootfsSpec::Directory(_) if has_disk => {
+            options.rootfs = RootfsSpec::Disk(PathBuf::from(DISK_ENTRY));
+        }
+        RootfsSpec::Disk(_) | RootfsSpec::Directory(_) => {
+            return Err(BoxliteError::InvalidState(
+                "Box has no disk yet; start it at least once before exporting".into(),
+            ));
+        }
+        RootfsSpec::RootfsPath(_) => {
+            return Err(BoxliteError::Unsupported(
+                "Boxes with a direct rootfs path cannot be exported".into(),
+            ));
+        }
+    }
+    Ok(options)
+}
+
+/// Make `base`, the disk unpacked from a bundle, the base of a box with `options`.
+///
+/// A placeholder `Disk` rootfs written by `portable_options` is pointed at
+/// `base`. An image rootfs takes it as `disk.backing_file`, so on first start
+/// the image only supplies its config.
+pub(crate) fn attach_disk(options: &mut BoxOptions, base: &Path) {
+    match &mut options.rootfs {
+        RootfsSpec::Disk(path) if path.as_os_str() == DISK_ENTRY => *path = base.to_path_buf(),
+        RootfsSpec::Image(_) => options.disk.backing_file = Some(base.to_path_buf()),
+        _ => {}
+    }
+}
+
+/// Write a bundle to `out`, flattening `disk` into it if given.
+///
+/// The bundle is assembled next to `out` and renamed into place, so a failed
+/// export never leaves a truncated file behind.
+pub(crate) fn write_bundle(
+    out: &Path,
+    manifest: &BundleManifest,
+    disk: Option<&Path>,
+) -> BoxliteResult<()> {
+    let storage_err =
+        |e: std::io::Error| BoxliteError::Storage(format!("Failed to write bundle: {}", e));
+    let dir = match out.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+
+    let json = serde_json::to_vec_pretty(manifest).map_err(|e| {
+        BoxliteError::Internal(format!("Failed to serialize bundle manifest: {}", e))
+    })?;
+
+    let tarball = tempfile::NamedTempFile::new_in(dir).map_err(storage_err)?;
+    let encoder = flate2::write::GzEncoder::new(tarball, flate2::Compression::fast());
+    let mut builder = tar::Builder::new(encoder);
+
+    let mut header = tar::Header::new_gnu();
+    header.set_size(json.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, MANIFEST_ENTRY, json.as_slice())
+        .map_err(storage_err)?;
+
+    if let Some(disk) = disk {
+        let raw = tempfile::NamedTempFile::new_in(dir).map_err(storage_err)?;
+        Qcow2Helper::flatten_to_raw(disk, raw.path())?;
+        builder
+            .append_path_with_name(raw.path(), DISK_ENTRY)
+            .map_err(storage_err)?;
+    }
+
+    let tarball = builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .map_err(storage_err)?;
+    tarball.persist(out).map_err(|e| storage_err(e.error))?;
+    Ok(())
+}
+
+/// Unpack the bundle at `bundle` into `dest`.
+///
+/// Only the entries this module writes are accepted, so a crafted bundle
+/// cannot place files outside `dest`.
+pub(crate) fn read_bundle(bundle: &Path, dest: &Path) -> BoxliteResult<UnpackedBundle> {
+    let invalid = |msg: String| {
+        BoxliteError::InvalidArgument(format!("invalid bundle {}: {}", bundle.display(), msg))
+    };
+
+    let file = File::open(bundle).map_err(|e| {
+        BoxliteError::Storage(format!("Failed to open bundle {}: {}", bundle.display(), e))
+    })?;
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(file));
+
+    let mut manifest = None;
+    let mut disk = None;
+    for entry in archive.entries().map_err(|e| invalid(e.to_string()))? {
+        let mut entry = entry.map_err(|e| invalid(e.to_string()))?;
+        let path = entry
+            .path()
+            .map_err(|e| invalid(e.to_string()))?
+            .into_owned();
+        if !entry.header().entry_type().is_file() {
+            return Err(invalid(format!("{} is not a regular file", path.display())));
+        }
+        match path.to_str() {
+            Some(MANIFEST_ENTRY) => {
+                let mut json = Vec::new();
+                entry
+                    .read_to_end(&mut json)
+                    .map_err(|e| invalid(e.to_string()))?;
+                let parsed: BundleManifest =
+                    serde_json::from_slice(&json).map_err(|e| invalid(e.to_string()))?;
+                manifest = Some(parsed);
+            }
+            Some(DISK_ENTRY) => {
+                let target = dest.join(DISK_ENTRY);
+                entry.unpack(&target).map_err(|e| {
+                    BoxliteError::Storage(format!("Failed to unpack {}: {}", target.display(), e))
+                })?;
+                disk = Some(target);
+            }
+            _ => return Err(invalid(format!("unexpected entry {}", path.display()))),
+        }
+    }
+
+    let manifest = manifest.ok_or_else(|| invalid(format!("missing {}", MANIFEST_ENTRY)))?;
+    if manifest.version > BUNDLE_VERSION {
+        return Err(BoxliteError::Unsupported(format!(
+            "bundle version {} is newer than the supported version {}",
+            manifest.version, BUNDLE_VERSION
+        )));
+    }
+    if disk.is_none() && !matches!(manifest.options.rootfs, RootfsSpec::Image(_)) {
+        return Err(invalid(format!("missing {}", DISK_ENTRY)));
+    }
+    Ok(UnpackedBundle { manifest, disk })
+}
@@ -0,0 +1,165 @@
+//! Committing a stopped box as an image.
+//!
+//! The box's disk is flattened and dumped to a directory, diffed against the
+//! merged rootfs of its base image, and the difference is installed as one new
+//! layer on top of the base.
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+
+use super::box_impl::BoxImpl;
+use crate::disk::Qcow2Helper;
+use crate::disk::ext4::{dump_ext4, ext4_owners};
+use crate::images::{ImageConfigChanges, ImageObject, PullOptions, write_diff_layer};
+use crate::runtime::options::RootfsSpec;
+use crate::runtime::types::BoxStatus;
+
+impl BoxImpl {
+    /// Snapshot the box's rootfs as a new image `image_ref`.
+    ///
+    /// The image is the box's base image plus one layer holding everything
+    /// the box changed, with `changes` applied to the base config and
+    /// `created_by` recorded in its history.
+    pub(crate) async fn commit(
+        &self,
+        image_ref: &str,
+        changes: &ImageConfigChanges,
+        created_by: &str,
+    ) -> BoxliteResult<ImageObject> {
+        let status = self.state.read().status;
+        if status.is_active() || status == BoxStatus::Stopping {
+            return Err(BoxliteError::InvalidState(format!(
+                "Cannot commit box in {} state; stop it first",
+                status
+            )));
+        }
+
+        let RootfsSpec::Image(base_ref) = &self.config.options.rootfs else {
+            return Err(BoxliteError::Unsupported(
+                "Only boxes created from an image can be committed".into(),
+            ));
+        };
+        let disk_path = self
+            .runtime
+            .layout
+            .box_layout(self.id().as_str(), self.config.options.isolate_mounts)?
+            .disk_path();
+        if !disk_path.exists() {
+            return Err(BoxliteError::InvalidState(
+                "Box has no rootfs disk yet; start it at least once before committing".into(),
+            ));
+        }
+
+        let image_manager = &self.runtime.image_manager;
+        let base = image_manager
+            .pull_with(
+                base_ref,
+                &PullOptions {
+                    platform: self.config.options.platform.clone(),
+                    progress: None,
+                },
+            )
+            .await?;
+        let mut config = base.load_config().await?;
+        changes.apply_to(&mut config);
+
+        // Diff the box's disk against the merged layers of its base image
+        let work_dir = tempfile::tempdir_in(self.runtime.layout.temp_dir()).map_err(|e| {
+            BoxliteError::Storage(format!("Failed to create temp directory: {}", e))
+        })?;
+        let lower = work_dir.path().join("lower");
+        crate::rootfs::RootfsBuilder::new()
+            .prepare(lower.clone(), &base)
+            .await?;
+
+        let work_path = work_dir.path().to_path_buf();
+        let layer = tokio::task::spawn_blocking(move || {
+            let raw = work_path.join("disk.raw");
+            let upper = work_path.join("upper");
+            std::fs::create_dir(&upper).map_err(|e| {
+                BoxliteError::Storage(format!("Failed to create {}: {}", upper.display(), e))
+            })?;
+            Qcow2Helper::flatten_to_raw(&disk_path, &raw)?;
+            dump_ext4(&raw, &upper)?;
+            let owners = ext4_owners(&raw)?;
+            let _ = std::fs::remove_file(&raw);
+            write_diff_layer(
+                &lower,
+                &upper,
+                Some(&owners),
+                &work_path.join("layer.tar.gz"),
+            )
+        })
+        .await
+        .map_err(|e| BoxliteError::Internal(format!("Commit task failed: {}", e)))??;
+
+        let entries = layer.entries;
+        let image = image_manager
+            .commit(image_ref, &base, layer, config, created_by)
+            .await?;
+        tracing::info!(
+            box_id = %self.id(),
+            image = %image_ref,
+            base = %base_ref,
+            entries,
+            "Committed box as image"
+        );
+        Ok(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BoxliteOptions;
+    use crate::runtime::options::BoxOptions;
+    use crate::runtime::rt_impl::RuntimeImpl;
+
+    #[tokio::test]
+    async fn test_commit_rejects_boxes_it_cannot_snapshot() {
+        let home = tempfile::tempdir().unwrap();
+        let runtime = RuntimeImpl::new(BoxliteOptions {
+            home_dir: home.path().to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap();
+        let changes = ImageConfigChanges::default();
+
+        // BoxOptions::default() uses alpine:latest, but the box never started
+        let litebox = runtime.create(BoxOptions::default(), None).await.unwrap();
+        let err = litebox
+            .inner
+            .commit("example.com/app:v1", &changes, "boxlite commit")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BoxliteError::InvalidState(_)), "{err}");
+        assert!(err.to_string().contains("no rootfs disk"), "{err}");
+
+        litebox.inner.state.write().status = BoxStatus::Running;
+        let err = litebox
+            .inner
+            .commit("example.com/app:v1", &changes, "boxlite commit")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BoxliteError::InvalidState(_)), "{err}");
+        assert!(err.to_string().contains("stop it first"), "{err}");
+
+        let rootfs = home.path().join("rootfs");
+        std::fs::create_dir_all(rootfs.join("bin")).unwrap();
+        let litebox = runtime
+            .create(
+                BoxOptions {
+                    rootfs: RootfsSpec::Directory(rootfs),
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        let err = litebox
+            .inner
+            .commit("example.com/app:v1", &changes, "boxlite commit")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BoxliteError::Unsupported(_)), "{err}");
+    }
+}
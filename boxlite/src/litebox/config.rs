@@ -1,10 +1,20 @@
 use crate::BoxID;
+use crate::runtime::options::BoxOptions;
 use crate::runtime::types::ContainerID;
 use boxlite_shared::Transport;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::path::PathBuf;
 
+/// Layout version of the `BoxConfig` JSON written by this build.
+///
+/// Bump it when a change needs more than newly added option fields, and add
+/// the matching step to `upgrade`. Only top-level `BoxOptions` fields are
+/// filled in from defaults; a field added inside a nested options struct
+/// still needs its own `#[serde(default)]`.
+pub const BOX_CONFIG_VERSION: u32 = 1;
+
 /// Container runtime configuration.
 ///
 /// Holds the container's identity.
@@ -21,11 +31,21 @@ pub struct ContainerRuntimeConfig {
 /// Separates static configuration from dynamic state.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BoxConfig {
+    /// Layout version of the serialized config. Rows written before
+    /// versioning carry none and load as version 0.
+    #[serde(default)]
+    pub config_version: u32,
+
     // === Identity & Timestamps ===
     /// Unique box identifier (ULID).
     pub id: BoxID,
     /// User-defined name (optional, must be unique if provided).
     pub name: Option<String>,
+    /// Whether the box is an unclaimed warm pool box.
+    ///
+    /// Cleared when the box is named; recovery removes boxes still marked.
+    #[serde(default)]
+    pub warm_pool: bool,
     /// Creation timestamp (UTC).
     pub created_at: DateTime<Utc>,
 
@@ -36,7 +56,7 @@ pub struct BoxConfig {
     // === User Options (preserved for restart) ===
     /// User-provided options at creation time.
     /// These are preserved to allow proper restart with the same configuration.
-    pub options: crate::runtime::options::BoxOptions,
+    pub options: BoxOptions,
 
     // === Runtime-Generated Configuration ===
     /// VMM engine type.
@@ -48,3 +68,69 @@ pub struct BoxConfig {
     /// Ready signal socket path.
     pub ready_socket_path: PathBuf,
 }
+
+impl BoxConfig {
+    /// Deserialize a config blob, upgrading older layouts first.
+    ///
+    /// Fails for blobs written by a newer version than this build knows.
+    pub(crate) fn from_json(json: &str) -> serde_json::Result<Self> {
+        let mut value: Value = serde_json::from_str(json)?;
+        let version = config_version(&value);
+        if version > u64::from(BOX_CONFIG_VERSION) {
+            return Err(serde::de::Error::custom(format!(
+                "config version {} is newer than supported version {}",
+                version, BOX_CONFIG_VERSION
+            )));
+        }
+        upgrade(&mut value)?;
+        serde_json::from_value(value)
+    }
+
+    /// When the box's TTL runs out, if it has one.
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        let ttl = chrono::Duration::from_std(self.options.ttl?).ok()?;
+        self.created_at.checked_add_signed(ttl)
+    }
+
+    /// Whether the box's TTL has run out at `now`.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at()
+            .is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// Layout version of a config blob written by a newer build, if it is one.
+pub(crate) fn newer_config_version(json: &str) -> Option<u64> {
+    let value: Value = serde_json::from_str(json).ok()?;
+    let version = config_version(&value);
+    (version > u64::from(BOX_CONFIG_VERSION)).then_some(version)
+}
+
+fn config_version(value: &Value) -> u64 {
+    value
+        .get("config_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0)
+}
+
+/// Bring a config blob of an older version up to `BOX_CONFIG_VERSION`.
+///
+/// Option fields missing from the blob are filled from `BoxOptions::default()`,
+/// so fields added later load without each needing a serde default. Nested
+/// objects are left alone: merging into them would corrupt externally tagged
+/// enums such as `rootfs`.
+fn upgrade(value: &mut Value) -> serde_json::Result<()> {
+    let Value::Object(defaults) = serde_json::to_value(BoxOptions::default())? else {
+        return Ok(());
+    };
+    let Some(config) = value.as_object_mut() else {
+        return Ok(());
+    };
+    if let Some(Value::Object(options)) = config.get_mut("options") {
+        for (key, default) in defaults {
+            options.entry(key).or_insert(default);
+        }
+    }
+    config.insert("config_version".into(), BOX_CONFIG_VERSION.into());
+    Ok(())
+}
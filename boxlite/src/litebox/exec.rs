@@ -4,8 +4,9 @@
 //! The actual execution logic is in BoxImpl::exec().
 
 use crate::portal::interfaces::ExecutionInterface;
-use boxlite_shared::errors::BoxliteResult;
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use futures::Stream;
+use std::path::Path;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::time::Duration;
@@ -31,9 +32,12 @@ pub struct BoxCommand {
     pub(crate) command: String,
     pub(crate) args: Vec<String>,
     pub(crate) env: Option<Vec<(String, String)>>,
+    /// Number of leading `env` entries that came from env files.
+    file_env_len: usize,
     pub(crate) timeout: Option<Duration>,
     pub(crate) working_dir: Option<String>,
     pub(crate) tty: bool,
+    pub(crate) max_output_bytes: Option<usize>,
 }
 
 impl BoxCommand {
@@ -43,9 +47,11 @@ impl BoxCommand {
             command: command.into(),
             args: vec![],
             env: None,
+            file_env_len: 0,
             timeout: None,
             working_dir: None,
             tty: false,
+            max_output_bytes: None,
         }
     }
 
@@ -73,6 +79,42 @@ impl BoxCommand {
         self
     }
 
+    /// Load environment variables from a dotenv-style file.
+    ///
+    /// Each non-blank line that is not a `#` comment must be `KEY=VALUE`,
+    /// optionally prefixed with `export`. Values may be wrapped in single or
+    /// double quotes. Variables set with [`env`](Self::env) win over the file,
+    /// whichever is called first; among files, the one loaded last wins.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BoxliteError::InvalidArgument`] if the file cannot be read or
+    /// a line is malformed.
+    pub fn env_file(mut self, path: impl AsRef<Path>) -> BoxliteResult<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            BoxliteError::InvalidArgument(format!(
+                "Failed to read env file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let vars = parse_env_file(&content)
+            .map_err(|e| BoxliteError::InvalidArgument(format!("{}: {}", path.display(), e)))?;
+
+        // File entries go first so later explicit env() calls override them
+        let env = self.env.get_or_insert_with(Vec::new);
+        let explicit = env.split_off(self.file_env_len);
+        env.retain(|(key, _)| !vars.iter().any(|(k, _)| k == key));
+        env.extend(
+            vars.into_iter()
+                .filter(|(key, _)| !explicit.iter().any(|(k, _)| k == key)),
+        );
+        self.file_env_len = env.len();
+        env.extend(explicit);
+        Ok(self)
+    }
+
     /// Set execution timeout.
     pub fn timeout(mut self, timeout: Duration) -> Self {
         self.timeout = Some(timeout);
@@ -92,6 +134,76 @@ impl BoxCommand {
         self.tty = enable;
         self
     }
+
+    /// Cap the combined stdout and stderr delivered to the caller.
+    ///
+    /// Output past `limit` bytes is dropped and the result is marked
+    /// [`ExecResult::truncated`]. The process itself keeps running.
+    pub fn max_output_bytes(mut self, limit: usize) -> Self {
+        self.max_output_bytes = Some(limit);
+        self
+    }
+}
+
+/// Parse dotenv-style `content` into `(key, value)` pairs in file order.
+fn parse_env_file(content: &str) -> Result<Vec<(String, String)>, String> {
+    let mut vars = Vec::new();
+    for (index, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let malformed = || format!("line {}: expected KEY=VALUE, got '{}'", index + 1, raw);
+
+        let (key, value) = line.split_once('=').ok_or_else(malformed)?;
+        let key = key.trim();
+        let valid_key = key
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_key {
+            return Err(malformed());
+        }
+
+        let value = value.trim();
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let inner = value[1..].strip_suffix(quote).ok_or_else(|| {
+                    format!("line {}: unterminated quote in '{}'", index + 1, raw)
+                })?;
+                if quote == '"' {
+                    unescape_double_quoted(inner)
+                } else {
+                    inner.to_string()
+                }
+            }
+            // Unquoted values end at an inline comment
+            _ => match value.find(" #") {
+                Some(pos) => value[..pos].trim_end().to_string(),
+                None => value.to_string(),
+            },
+        };
+        vars.push((key.to_string(), value));
+    }
+    Ok(vars)
+}
+
+/// Expand `\n`, `\"` and `\\` in a double-quoted env file value.
+fn unescape_double_quoted(inner: &str) -> String {
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some(next @ ('n' | '"' | '\\'))) => {
+                out.push(if next == 'n' { '\n' } else { next });
+                chars.next();
+            }
+            _ => out.push(c),
+        }
+    }
+    out
 }
 
 /// Handle to a running command execution.
@@ -249,6 +361,8 @@ impl Execution {
 pub struct ExecResult {
     /// Exit code (0 = success). If terminated by signal, code is negative signal number.
     pub exit_code: i32,
+    /// Output was cut off by [`BoxCommand::max_output_bytes`].
+    pub truncated: bool,
 }
 
 impl ExecResult {
@@ -339,3 +453,87 @@ impl Stream for ExecStderr {
         self.receiver.poll_recv(cx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+# Database settings
+export DB_HOST=localhost
+DB_PORT = 5432
+GREETING="hello world"
+QUOTED='single # not a comment'
+ESCAPED="line1\nsaid \"hi\""
+EMPTY=
+TRAILING=value # comment
+"#;
+
+    #[test]
+    fn test_parse_env_file() {
+        let vars = parse_env_file(SAMPLE).unwrap();
+        let expected = [
+            ("DB_HOST", "localhost"),
+            ("DB_PORT", "5432"),
+            ("GREETING", "hello world"),
+            ("QUOTED", "single # not a comment"),
+            ("ESCAPED", "line1\nsaid \"hi\""),
+            ("EMPTY", ""),
+            ("TRAILING", "value"),
+        ];
+        let vars: Vec<(&str, &str)> = vars.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+        assert_eq!(vars, expected);
+    }
+
+    #[test]
+    fn test_parse_env_file_rejects_malformed_lines() {
+        let err = parse_env_file("GOOD=1\nJUST_A_WORD\n").unwrap_err();
+        assert!(err.contains("line 2"), "{}", err);
+        assert!(parse_env_file("1BAD=x").is_err());
+        assert!(parse_env_file("OPEN=\"unterminated").is_err());
+    }
+
+    #[test]
+    fn test_env_file_explicit_env_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.env");
+        std::fs::write(&path, "A=from-file\nB=from-file\n").unwrap();
+
+        let cmd = BoxCommand::new("env")
+            .env("A", "explicit")
+            .env_file(&path)
+            .unwrap()
+            .env("B", "explicit");
+        let env: std::collections::HashMap<_, _> = cmd.env.unwrap().into_iter().collect();
+        assert_eq!(env["A"], "explicit");
+        assert_eq!(env["B"], "explicit");
+
+        std::fs::write(&path, "not valid\n").unwrap();
+        assert!(matches!(
+            BoxCommand::new("env").env_file(&path),
+            Err(BoxliteError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_env_file_later_file_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("base.env");
+        let local = dir.path().join("local.env");
+        std::fs::write(&base, "A=base\nB=base\nC=base\n").unwrap();
+        std::fs::write(&local, "B=local\nC=local\n").unwrap();
+
+        let cmd = BoxCommand::new("env")
+            .env("C", "explicit")
+            .env_file(&base)
+            .unwrap()
+            .env_file(&local)
+            .unwrap();
+        let env = cmd.env.unwrap();
+        assert_eq!(env.len(), 3);
+        let env: std::collections::HashMap<_, _> = env.into_iter().collect();
+        assert_eq!(env["A"], "base");
+        assert_eq!(env["B"], "local");
+        assert_eq!(env["C"], "explicit");
+    }
+}
@@ -33,8 +33,8 @@ mod types;
 
 pub(crate) use crate::litebox::box_impl::LiveState;
 
-use crate::litebox::BoxStatus;
 use crate::litebox::config::BoxConfig;
+use crate::litebox::{BoxStatus, StartupTimings};
 use crate::metrics::BoxMetricsStorage;
 use crate::pipeline::{
     BoxedTask, ExecutionPlan, PipelineBuilder, PipelineExecutor, PipelineMetrics, Stage,
@@ -43,6 +43,7 @@ use crate::runtime::rt_impl::SharedRuntimeImpl;
 use crate::runtime::types::BoxState;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
 
 use tasks::{
@@ -86,7 +87,7 @@ fn get_execution_plan(status: BoxStatus) -> ExecutionPlan<InitCtx> {
             // GuestInit must run - new VM process has fresh guest daemon
             Stage::sequential(vec![Box::new(GuestInitTask)]),
         ],
-        BoxStatus::Running => vec![
+        BoxStatus::Running | BoxStatus::Detached => vec![
             // Reattach: Attach to existing VM process and connect to guest
             Stage::sequential(vec![Box::new(VmmAttachTask)]),
             Stage::sequential(vec![Box::new(GuestConnectTask)]),
@@ -126,6 +127,29 @@ fn box_metrics_from_pipeline(pipeline_metrics: &PipelineMetrics) -> BoxMetricsSt
     metrics
 }
 
+/// Per-stage breakdown of a startup, keyed by the init task names.
+fn startup_timings_from_pipeline(
+    pipeline_metrics: &PipelineMetrics,
+    total: Duration,
+) -> StartupTimings {
+    let stage = |name: &str| {
+        pipeline_metrics
+            .task_duration_ms(name)
+            .map(|ms| Duration::from_millis(ms as u64))
+    };
+
+    StartupTimings {
+        filesystem: stage("filesystem_setup"),
+        container_rootfs: stage("container_rootfs_prep"),
+        guest_rootfs: stage("guest_rootfs_init"),
+        vmm_spawn: stage("vmm_spawn"),
+        vmm_attach: stage("vmm_attach"),
+        guest_connect: stage("guest_connect"),
+        guest_init: stage("guest_init"),
+        total,
+    }
+}
+
 /// Builds and initializes box components.
 ///
 /// # Example
@@ -187,7 +211,9 @@ impl BoxBuilder {
 
         let status = state.status;
         let reuse_rootfs = status == BoxStatus::Stopped;
-        let skip_guest_wait = status == BoxStatus::Running;
+        let reattach = matches!(status, BoxStatus::Running | BoxStatus::Detached);
+        let skip_guest_wait = reattach;
+        let observer = config.options.pipeline_observer.clone();
 
         let ctx = InitPipelineContext::new(config, runtime.clone(), reuse_rootfs, skip_guest_wait);
         let ctx = Arc::new(Mutex::new(ctx));
@@ -197,7 +223,9 @@ impl BoxBuilder {
 
         let plan = get_execution_plan(status);
         let pipeline = PipelineBuilder::from_plan(plan);
-        let pipeline_metrics = PipelineExecutor::execute(pipeline, Arc::clone(&ctx)).await?;
+        let pipeline_metrics =
+            PipelineExecutor::execute_observed(pipeline, Arc::clone(&ctx), observer.as_deref())
+                .await?;
 
         let mut ctx = ctx.lock().await;
         let total_create_duration_ms = total_start.elapsed().as_millis();
@@ -208,6 +236,8 @@ impl BoxBuilder {
 
         let mut metrics = box_metrics_from_pipeline(&pipeline_metrics);
         metrics.set_total_create_duration(total_create_duration_ms);
+        let startup_timings =
+            startup_timings_from_pipeline(&pipeline_metrics, total_start.elapsed());
 
         metrics.log_init_stages();
 
@@ -221,14 +251,13 @@ impl BoxBuilder {
             .ok_or_else(|| BoxliteError::Internal("guest_connect task must run first".into()))?;
 
         // Get disks from context (for Running, create disk reference directly)
-        let (container_disk, guest_disk) = if status == BoxStatus::Running {
+        let (container_disk, guest_disk) = if reattach {
             // Reattach: create disk reference to existing qcow2
             use crate::disk::DiskFormat;
-            let disk = crate::disk::Disk::new(
-                ctx.config.box_home.join("root.qcow2"),
-                DiskFormat::Qcow2,
-                true,
-            );
+            let layout = runtime
+                .layout
+                .box_layout(ctx.config.id.as_str(), ctx.config.options.isolate_mounts)?;
+            let disk = crate::disk::Disk::new(layout.disk_path(), DiskFormat::Qcow2, true);
             (disk, None)
         } else {
             // Starting/Stopped: get disks from rootfs tasks
@@ -241,6 +270,7 @@ impl BoxBuilder {
 
         #[cfg(target_os = "linux")]
         let bind_mount = ctx.bind_mount.take();
+        let port_forwards = ctx.port_forwards.take();
 
         // Take the guard out of context, replacing with a disarmed placeholder.
         // The caller is responsible for disarming the returned guard after all
@@ -254,8 +284,10 @@ impl BoxBuilder {
             handler,
             guest_session,
             metrics,
+            startup_timings,
             container_disk,
             guest_disk,
+            port_forwards,
             #[cfg(target_os = "linux")]
             bind_mount,
         );
@@ -263,3 +295,79 @@ impl BoxBuilder {
         Ok((live_state, guard))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::PipelineTask;
+    use async_trait::async_trait;
+
+    /// Stands in for a real init task: same name, no work.
+    struct MockTask(String);
+
+    #[async_trait]
+    impl PipelineTask<()> for MockTask {
+        async fn run(self: Box<Self>, _ctx: ()) -> BoxliteResult<()> {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            &self.0
+        }
+    }
+
+    /// Run a mock pipeline with the same task names as `get_execution_plan(status)`.
+    async fn mock_startup(status: BoxStatus) -> StartupTimings {
+        let stages = get_execution_plan(status)
+            .stages()
+            .into_iter()
+            .map(|stage| {
+                let tasks: Vec<BoxedTask<()>> = stage
+                    .tasks
+                    .iter()
+                    .map(|task| Box::new(MockTask(task.name().to_string())) as BoxedTask<()>)
+                    .collect();
+                Stage {
+                    tasks,
+                    execution: stage.execution,
+                }
+            })
+            .collect();
+
+        let start = std::time::Instant::now();
+        let pipeline = PipelineBuilder::from_plan(ExecutionPlan::new(stages));
+        let metrics = PipelineExecutor::execute(pipeline, ()).await.unwrap();
+        startup_timings_from_pipeline(&metrics, start.elapsed())
+    }
+
+    #[tokio::test]
+    async fn test_first_start_times_every_stage() {
+        let timings = mock_startup(BoxStatus::Configured).await;
+
+        for (stage, duration) in [
+            ("filesystem", timings.filesystem),
+            ("container_rootfs", timings.container_rootfs),
+            ("guest_rootfs", timings.guest_rootfs),
+            ("vmm_spawn", timings.vmm_spawn),
+            ("guest_connect", timings.guest_connect),
+            ("guest_init", timings.guest_init),
+        ] {
+            let duration = duration.unwrap_or_else(|| panic!("{} was not timed", stage));
+            assert!(duration <= timings.total, "{} exceeds total", stage);
+        }
+        assert_eq!(timings.vmm_attach, None);
+        assert!(timings.total > Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_reattach_times_only_executed_stages() {
+        let timings = mock_startup(BoxStatus::Running).await;
+
+        assert!(timings.vmm_attach.is_some());
+        assert!(timings.guest_connect.is_some());
+        assert_eq!(timings.filesystem, None);
+        assert_eq!(timings.vmm_spawn, None);
+        assert_eq!(timings.guest_init, None);
+    }
+}
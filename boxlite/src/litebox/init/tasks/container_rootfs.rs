@@ -6,13 +6,14 @@
 //!
 //! For restart (reuse_rootfs=true), opens existing COW disk instead of creating new.
 
-use super::{InitCtx, log_task_error, task_start};
+use super::{InitCtx, TaskResultExt, task_start};
 use crate::disk::{BackingFormat, Disk, DiskFormat, Qcow2Helper, create_ext4_from_dir};
 use crate::images::ContainerImageConfig;
 use crate::litebox::init::types::{ContainerRootfsPrepResult, USE_DISK_ROOTFS, USE_OVERLAYFS};
 use crate::pipeline::PipelineTask;
+use crate::rootfs::operations::resolve_rootfs_dir;
 use crate::runtime::layout::BoxFilesystemLayout;
-use crate::runtime::options::RootfsSpec;
+use crate::runtime::options::{BoxOptions, RootfsSpec, UserSpec};
 use crate::runtime::rt_impl::SharedRuntimeImpl;
 use async_trait::async_trait;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
@@ -25,32 +26,25 @@ impl PipelineTask<InitCtx> for ContainerRootfsTask {
         let task_name = self.name();
         let box_id = task_start(&ctx, task_name).await;
 
-        let (rootfs_spec, env, runtime, layout, reuse_rootfs, disk_size_gb) = {
+        let (options, runtime, layout, reuse_rootfs) = {
             let ctx = ctx.lock().await;
             let layout = ctx
                 .layout
                 .clone()
                 .ok_or_else(|| BoxliteError::Internal("filesystem task must run first".into()))?;
             (
-                ctx.config.options.rootfs.clone(),
-                ctx.config.options.env.clone(),
+                ctx.config.options.clone(),
                 ctx.runtime.clone(),
                 layout,
                 ctx.reuse_rootfs,
-                ctx.config.options.disk_size_gb,
             )
         };
 
-        let (container_image_config, disk) = run_container_rootfs(
-            &rootfs_spec,
-            &env,
-            &runtime,
-            &layout,
-            reuse_rootfs,
-            disk_size_gb,
-        )
-        .await
-        .inspect_err(|e| log_task_error(&box_id, task_name, e))?;
+        let (mut container_image_config, disk) =
+            run_container_rootfs(&options, &runtime, &layout, reuse_rootfs)
+                .await
+                .task_context(&box_id, task_name)?;
+        apply_box_overrides(&mut container_image_config, &options)?;
 
         let mut ctx = ctx.lock().await;
         ctx.container_image_config = Some(container_image_config);
@@ -66,13 +60,14 @@ impl PipelineTask<InitCtx> for ContainerRootfsTask {
 
 /// Pull image and prepare rootfs, then create or reuse COW disk.
 async fn run_container_rootfs(
-    rootfs_spec: &RootfsSpec,
-    env: &[(String, String)],
+    options: &BoxOptions,
     runtime: &SharedRuntimeImpl,
     layout: &BoxFilesystemLayout,
     reuse_rootfs: bool,
-    disk_size_gb: Option<u64>,
 ) -> BoxliteResult<(ContainerImageConfig, Disk)> {
+    let rootfs_spec = &options.rootfs;
+    let env = &options.env;
+    let platform = options.platform.as_deref();
     let disk_path = layout.disk_path();
 
     // For restart, reuse existing COW disk
@@ -91,27 +86,40 @@ async fn run_container_rootfs(
 
         let disk = Disk::new(disk_path.clone(), DiskFormat::Qcow2, true);
 
-        let image_ref = match rootfs_spec {
-            RootfsSpec::Image(r) => r,
+        let image = match rootfs_spec {
+            RootfsSpec::Image(r) => Some(pull_image(runtime, r, platform).await?),
+            RootfsSpec::Disk(_) | RootfsSpec::Directory(_) => None,
             RootfsSpec::RootfsPath(_) => {
                 return Err(BoxliteError::Storage(
                     "Direct rootfs paths not yet supported".into(),
                 ));
             }
         };
-        let image = pull_image(runtime, image_ref).await?;
-        let image_config = image.load_config().await?;
-        let mut container_image_config = ContainerImageConfig::from_oci_config(&image_config)?;
-        if !env.is_empty() {
-            container_image_config.merge_env(env.to_vec());
-        }
+        let container_image_config = load_image_config(image.as_ref(), env).await?;
 
         return Ok((container_image_config, disk));
     }
 
     // Fresh start: pull image and prepare rootfs
-    let image_ref = match rootfs_spec {
-        RootfsSpec::Image(r) => r,
+    let (rootfs_result, image) = match rootfs_spec {
+        RootfsSpec::Image(image_ref) => {
+            let image = pull_image(runtime, image_ref, platform).await?;
+            let rootfs_result = if let Some(base) = options.disk.backing_file.as_deref() {
+                // Shared base: the image only supplies its config
+                prepare_backing_file(base)?
+            } else if USE_DISK_ROOTFS {
+                prepare_disk_rootfs(runtime, &image).await?
+            } else if USE_OVERLAYFS {
+                prepare_overlayfs_layers(&image).await?
+            } else {
+                return Err(BoxliteError::Storage(
+                    "Merged rootfs not supported. Use overlayfs or disk rootfs.".into(),
+                ));
+            };
+            (rootfs_result, Some(image))
+        }
+        RootfsSpec::Disk(path) => (prepare_exported_disk(path)?, None),
+        RootfsSpec::Directory(dir) => (prepare_directory_rootfs(dir, layout).await?, None),
         RootfsSpec::RootfsPath(_) => {
             return Err(BoxliteError::Storage(
                 "Direct rootfs paths not yet supported".into(),
@@ -119,28 +127,130 @@ async fn run_container_rootfs(
         }
     };
 
-    let image = pull_image(runtime, image_ref).await?;
+    let disk = create_cow_disk(&rootfs_result, layout, options.disk_size_gb)?;
+    let container_image_config = load_image_config(image.as_ref(), env).await?;
 
-    let rootfs_result = if USE_DISK_ROOTFS {
-        prepare_disk_rootfs(runtime, &image).await?
-    } else if USE_OVERLAYFS {
-        prepare_overlayfs_layers(&image).await?
-    } else {
-        return Err(BoxliteError::Storage(
-            "Merged rootfs not supported. Use overlayfs or disk rootfs.".into(),
-        ));
-    };
-
-    let disk = create_cow_disk(&rootfs_result, layout, disk_size_gb)?;
+    Ok((container_image_config, disk))
+}
 
-    let image_config = image.load_config().await?;
-    let mut container_image_config = ContainerImageConfig::from_oci_config(&image_config)?;
+/// Apply `BoxOptions` settings that take precedence over the image config.
+fn apply_box_overrides(
+    config: &mut ContainerImageConfig,
+    options: &BoxOptions,
+) -> BoxliteResult<()> {
+    config.override_command(options.command.as_deref(), options.args.as_deref());
+    if let Some(workdir) = &options.working_dir {
+        config.working_dir = workdir.clone();
+    }
+    if let Some(user) = &options.user {
+        config.user = Some(UserSpec::parse(user)?.to_string());
+    }
+    Ok(())
+}
 
+/// Container config for the box: from the image if there is one, with user env merged in.
+///
+/// Disk-based rootfs carries no image metadata, so defaults are used.
+async fn load_image_config(
+    image: Option<&crate::images::ImageObject>,
+    env: &[(String, String)],
+) -> BoxliteResult<ContainerImageConfig> {
+    let mut container_image_config = match image {
+        Some(image) => ContainerImageConfig::from_oci_config(&image.load_config().await?)?,
+        None => ContainerImageConfig::default(),
+    };
     if !env.is_empty() {
         container_image_config.merge_env(env.to_vec());
     }
+    Ok(container_image_config)
+}
 
-    Ok((container_image_config, disk))
+/// Pack a host rootfs directory into an ext4 base disk inside the box directory.
+async fn prepare_directory_rootfs(
+    dir: &std::path::Path,
+    layout: &BoxFilesystemLayout,
+) -> BoxliteResult<ContainerRootfsPrepResult> {
+    let source = resolve_rootfs_dir(dir)?;
+    let base_disk_path = layout.rootfs_base_path();
+
+    tracing::info!(
+        source = %source.display(),
+        base_disk = %base_disk_path.display(),
+        "Packing rootfs directory into base disk"
+    );
+
+    let output = base_disk_path.clone();
+    let disk = tokio::task::spawn_blocking(move || create_ext4_from_dir(&source, &output))
+        .await
+        .map_err(|e| BoxliteError::Internal(format!("Disk creation task failed: {}", e)))??;
+    // Backing file of the COW disk; removed along with the box directory
+    let _ = disk.leak();
+
+    let disk_size = std::fs::metadata(&base_disk_path)
+        .map_err(|e| {
+            BoxliteError::Storage(format!(
+                "Failed to stat {}: {}",
+                base_disk_path.display(),
+                e
+            ))
+        })?
+        .len();
+
+    Ok(ContainerRootfsPrepResult::DiskImage {
+        base_disk_path,
+        disk_size,
+    })
+}
+
+/// Use an exported raw rootfs image as the read-only base of the COW disk.
+fn prepare_exported_disk(path: &std::path::Path) -> BoxliteResult<ContainerRootfsPrepResult> {
+    let disk_size = std::fs::metadata(path)
+        .map_err(|e| {
+            BoxliteError::Storage(format!(
+                "Rootfs disk {} is not readable: {}",
+                path.display(),
+                e
+            ))
+        })?
+        .len();
+
+    tracing::info!(
+        disk = %path.display(),
+        disk_size_mb = disk_size / (1024 * 1024),
+        "Using exported rootfs disk as base"
+    );
+
+    Ok(ContainerRootfsPrepResult::DiskImage {
+        base_disk_path: path.to_path_buf(),
+        disk_size,
+    })
+}
+
+/// Use a shared base image (raw or qcow2) from `DiskConfig::backing_file`.
+fn prepare_backing_file(path: &std::path::Path) -> BoxliteResult<ContainerRootfsPrepResult> {
+    let disk_size = match BackingFormat::detect(path)? {
+        BackingFormat::Qcow2 => Qcow2Helper::qcow2_virtual_size(path)?,
+        BackingFormat::Raw => std::fs::metadata(path)
+            .map_err(|e| {
+                BoxliteError::Storage(format!(
+                    "Backing file {} is not readable: {}",
+                    path.display(),
+                    e
+                ))
+            })?
+            .len(),
+    };
+
+    tracing::info!(
+        backing_file = %path.display(),
+        disk_size_mb = disk_size / (1024 * 1024),
+        "Using shared backing file as base"
+    );
+
+    Ok(ContainerRootfsPrepResult::DiskImage {
+        base_disk_path: path.to_path_buf(),
+        disk_size,
+    })
 }
 
 /// Create COW disk from base rootfs.
@@ -160,19 +270,37 @@ fn create_cow_disk(
             base_disk_path,
             disk_size: base_disk_size,
         } => {
+            let user_size_bytes = disk_size_gb
+                .map(|size_gb| {
+                    size_gb.checked_mul(1024 * 1024 * 1024).ok_or_else(|| {
+                        BoxliteError::InvalidArgument(format!(
+                            "disk_size_gb {} is too large",
+                            size_gb
+                        ))
+                    })
+                })
+                .transpose()?;
+
             // Calculate target disk size: use max of user-specified size and base disk size
-            let target_disk_size = if let Some(size_gb) = disk_size_gb {
-                let user_size_bytes = size_gb * 1024 * 1024 * 1024;
-                std::cmp::max(user_size_bytes, *base_disk_size)
-            } else {
-                *base_disk_size
+            let target_disk_size = match user_size_bytes {
+                Some(user_size_bytes) => std::cmp::max(user_size_bytes, *base_disk_size),
+                None => *base_disk_size,
             };
 
             let qcow2_helper = Qcow2Helper::new();
             let cow_disk_path = layout.disk_path();
+
+            // The sparse disk can grow to the requested size; refuse one the host can't back
+            if let (Some(size_gb), Some(user_size_bytes)) = (disk_size_gb, user_size_bytes) {
+                crate::util::ensure_available_space(
+                    &cow_disk_path,
+                    user_size_bytes,
+                    &format!("a {} GiB box disk", size_gb),
+                )?;
+            }
             let temp_disk = qcow2_helper.create_cow_child_disk(
                 base_disk_path,
-                BackingFormat::Raw,
+                BackingFormat::detect(base_disk_path)?,
                 &cow_disk_path,
                 target_disk_size,
             )?;
@@ -204,9 +332,14 @@ fn create_cow_disk(
 async fn pull_image(
     runtime: &crate::runtime::SharedRuntimeImpl,
     image_ref: &str,
+    platform: Option<&str>,
 ) -> BoxliteResult<crate::images::ImageObject> {
+    let options = crate::images::PullOptions {
+        platform: platform.map(str::to_string),
+        ..Default::default()
+    };
     // ImageManager has internal locking - direct access
-    runtime.image_manager.pull(image_ref).await
+    runtime.image_manager.pull_with(image_ref, &options).await
 }
 
 async fn prepare_overlayfs_layers(
@@ -342,3 +475,78 @@ async fn prepare_disk_rootfs(
         disk_size,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::litebox::BoxStatus;
+    use crate::runtime::options::BoxliteOptions;
+    use crate::runtime::rt_impl::RuntimeImpl;
+
+    #[tokio::test]
+    async fn test_imported_box_builds_disk_on_bundled_base() {
+        let home = tempfile::tempdir().unwrap();
+        let runtime = RuntimeImpl::new(BoxliteOptions {
+            home_dir: home.path().to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap();
+        let options = BoxOptions {
+            rootfs: RootfsSpec::Image("alpine:3.19".into()),
+            ..Default::default()
+        };
+        let source = runtime.create(options, None).await.unwrap();
+
+        // Stand-in for the disk the box wrote while it ran
+        let source_disk = runtime
+            .layout
+            .box_layout(source.id().as_str(), false)
+            .unwrap()
+            .disk_path();
+        std::fs::create_dir_all(source_disk.parent().unwrap()).unwrap();
+        let disk: Vec<u8> = (0..1024 * 1024).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&source_disk, &disk).unwrap();
+
+        let bundle = home.path().join("source.tar.gz");
+        source.export_bundle(&bundle).await.unwrap();
+        let imported = runtime.import_bundle(&bundle, None).await.unwrap();
+
+        // First start, not a restart: no per-box disks exist yet
+        assert_eq!(imported.info().status, BoxStatus::Configured);
+        let options = &imported.config().options;
+        let layout = runtime
+            .layout
+            .box_layout(imported.id().as_str(), false)
+            .unwrap();
+        let base = options.disk.backing_file.as_deref().unwrap();
+        let prepared = prepare_backing_file(base).unwrap();
+        let cow = create_cow_disk(&prepared, &layout, options.disk_size_gb).unwrap();
+        assert_eq!(cow.path(), layout.disk_path());
+
+        let flat = home.path().join("flat.raw");
+        Qcow2Helper::flatten_to_raw(cow.path(), &flat).unwrap();
+        assert_eq!(std::fs::read(&flat).unwrap(), disk);
+    }
+
+    #[test]
+    fn test_cow_disk_rejects_overflowing_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let base_disk_path = dir.path().join("base.ext4");
+        std::fs::write(&base_disk_path, vec![0u8; 65536]).unwrap();
+        let layout = crate::runtime::layout::BoxFilesystemLayout::new(
+            dir.path().join("box"),
+            Default::default(),
+            false,
+        );
+        let prepared = ContainerRootfsPrepResult::DiskImage {
+            base_disk_path,
+            disk_size: 65536,
+        };
+
+        assert!(matches!(
+            create_cow_disk(&prepared, &layout, Some(u64::MAX)),
+            Err(BoxliteError::InvalidArgument(_))
+        ));
+        assert!(!layout.disk_path().exists());
+    }
+}
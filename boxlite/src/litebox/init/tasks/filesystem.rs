@@ -2,7 +2,7 @@
 //!
 //! Creates box directory structure and optionally sets up the mounts/ → shared/ binding.
 
-use super::{InitCtx, log_task_error, task_start};
+use super::{InitCtx, TaskResultExt, task_start};
 use crate::pipeline::PipelineTask;
 use async_trait::async_trait;
 use boxlite_shared::errors::BoxliteResult;
@@ -23,11 +23,9 @@ impl PipelineTask<InitCtx> for FilesystemTask {
         let layout = runtime
             .layout
             .box_layout(box_id.as_str(), isolate_mounts)
-            .inspect_err(|e| log_task_error(&box_id, task_name, e))?;
+            .task_context(&box_id, task_name)?;
 
-        layout
-            .prepare()
-            .inspect_err(|e| log_task_error(&box_id, task_name, e))?;
+        layout.prepare().task_context(&box_id, task_name)?;
 
         #[cfg(target_os = "linux")]
         let bind_mount = if isolate_mounts {
@@ -35,7 +33,7 @@ impl PipelineTask<InitCtx> for FilesystemTask {
             let mount = create_bind_mount(
                 &BindMountConfig::new(&layout.mounts_dir(), &layout.shared_dir()).read_only(),
             )
-            .inspect_err(|e| log_task_error(&box_id, task_name, e))?;
+            .task_context(&box_id, task_name)?;
             Some(mount)
         } else {
             None
@@ -5,7 +5,7 @@
 //!
 //! IMPORTANT: Must wait for guest to be ready before creating session.
 
-use super::{InitCtx, log_task_error, task_start};
+use super::{InitCtx, TaskResultExt, task_start};
 use crate::pipeline::PipelineTask;
 use crate::portal::GuestSession;
 use async_trait::async_trait;
@@ -21,12 +21,16 @@ impl PipelineTask<InitCtx> for GuestConnectTask {
         let task_name = self.name();
         let box_id = task_start(&ctx, task_name).await;
 
-        let (transport, ready_transport, skip_guest_wait) = {
+        let (transport, ready_transport, skip_guest_wait, schedule) = {
             let ctx = ctx.lock().await;
             (
                 ctx.config.transport.clone(),
                 Transport::unix(ctx.config.ready_socket_path.clone()),
                 ctx.skip_guest_wait,
+                ready_backoff_schedule(
+                    ctx.config.options.ready_backoff,
+                    ctx.config.options.ready_retries,
+                ),
             )
         };
 
@@ -36,9 +40,9 @@ impl PipelineTask<InitCtx> for GuestConnectTask {
             tracing::debug!(box_id = %box_id, "Skipping guest ready wait (reattach)");
         } else {
             tracing::debug!(box_id = %box_id, "Waiting for guest to be ready");
-            wait_for_guest_ready(&ready_transport)
+            wait_for_guest_ready(&ctx, &ready_transport, &schedule)
                 .await
-                .inspect_err(|e| log_task_error(&box_id, task_name, e))?;
+                .task_context(&box_id, task_name)?;
         }
 
         tracing::debug!(box_id = %box_id, "Guest is ready, creating session");
@@ -55,11 +59,33 @@ impl PipelineTask<InitCtx> for GuestConnectTask {
     }
 }
 
+/// Longest single wait between readiness checks.
+const MAX_READY_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Waits between readiness checks: `initial` doubling each time (capped at
+/// `MAX_READY_BACKOFF`), one entry per retry.
+fn ready_backoff_schedule(initial: Duration, retries: u32) -> Vec<Duration> {
+    (0..retries)
+        .map(|attempt| {
+            initial
+                .checked_mul(1u32.checked_shl(attempt).unwrap_or(u32::MAX))
+                .unwrap_or(MAX_READY_BACKOFF)
+                .min(MAX_READY_BACKOFF)
+        })
+        .collect()
+}
+
 /// Wait for guest to signal readiness via ready socket.
 ///
 /// Creates a listener on the ready socket and waits for the guest to connect.
 /// The guest connects when its gRPC server is ready to serve requests.
-async fn wait_for_guest_ready(ready_transport: &boxlite_shared::Transport) -> BoxliteResult<()> {
+/// Waits follow `schedule`; between them the VM process is checked so a
+/// guest that died during boot fails fast instead of using up every retry.
+async fn wait_for_guest_ready(
+    ctx: &InitCtx,
+    ready_transport: &boxlite_shared::Transport,
+    schedule: &[Duration],
+) -> BoxliteResult<()> {
     let ready_socket_path = match ready_transport {
         boxlite_shared::Transport::Unix { socket_path } => socket_path,
         _ => {
@@ -88,22 +114,70 @@ async fn wait_for_guest_ready(ready_transport: &boxlite_shared::Transport) -> Bo
         "Listening for guest ready notification"
     );
 
-    // Wait for guest connection with timeout
-    let timeout = Duration::from_secs(30);
-    let accept_result = tokio::time::timeout(timeout, listener.accept()).await;
-
-    match accept_result {
-        Ok(Ok((_stream, _addr))) => {
-            tracing::debug!("Guest signaled ready via socket connection");
-            Ok(())
+    for (attempt, wait) in schedule.iter().enumerate() {
+        match tokio::time::timeout(*wait, listener.accept()).await {
+            Ok(Ok((_stream, _addr))) => {
+                tracing::debug!("Guest signaled ready via socket connection");
+                return Ok(());
+            }
+            Ok(Err(e)) => {
+                return Err(BoxliteError::Engine(format!(
+                    "Ready socket accept failed: {}",
+                    e
+                )));
+            }
+            Err(_) => {
+                if ctx.lock().await.guard.handler_running() == Some(false) {
+                    return Err(BoxliteError::Engine(
+                        "VM exited before guest signaled ready".into(),
+                    ));
+                }
+                tracing::debug!(
+                    attempt = attempt + 1,
+                    retries = schedule.len(),
+                    waited_ms = wait.as_millis() as u64,
+                    "Guest not ready yet"
+                );
+            }
         }
-        Ok(Err(e)) => Err(BoxliteError::Engine(format!(
-            "Ready socket accept failed: {}",
-            e
-        ))),
-        Err(_) => Err(BoxliteError::Engine(format!(
-            "Timeout waiting for guest ready ({}s)",
-            timeout.as_secs()
-        ))),
+    }
+
+    let total: Duration = schedule.iter().sum();
+    Err(BoxliteError::Engine(format!(
+        "Timeout waiting for guest ready ({} checks over {:.1}s)",
+        schedule.len(),
+        total.as_secs_f64()
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ready_backoff_schedule_doubles() {
+        let schedule = ready_backoff_schedule(Duration::from_millis(500), 4);
+        assert_eq!(
+            schedule,
+            vec![
+                Duration::from_millis(500),
+                Duration::from_secs(1),
+                Duration::from_secs(2),
+                Duration::from_secs(4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ready_backoff_schedule_caps() {
+        // One entry per retry
+        assert!(ready_backoff_schedule(Duration::from_secs(1), 0).is_empty());
+        assert_eq!(ready_backoff_schedule(Duration::from_secs(1), 40).len(), 40);
+
+        // Long waits (and shift overflow) stop at MAX_READY_BACKOFF
+        let schedule = ready_backoff_schedule(Duration::from_secs(1), 40);
+        assert_eq!(schedule[4], Duration::from_secs(16));
+        assert_eq!(schedule[5], MAX_READY_BACKOFF);
+        assert_eq!(schedule[39], MAX_READY_BACKOFF);
     }
 }
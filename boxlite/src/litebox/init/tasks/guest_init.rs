@@ -3,7 +3,7 @@
 //! Sends init configuration to guest and starts container.
 //! Builds guest volumes from volume manager, uses rootfs config from vmm_config stage.
 
-use super::{InitCtx, log_task_error, task_start};
+use super::{InitCtx, TaskResultExt, task_start};
 use crate::images::ContainerImageConfig;
 use crate::pipeline::PipelineTask;
 use crate::portal::GuestSession;
@@ -12,6 +12,7 @@ use crate::runtime::types::ContainerID;
 use crate::volumes::{ContainerMount, GuestVolumeManager};
 use async_trait::async_trait;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use std::net::Ipv4Addr;
 
 pub struct GuestInitTask;
 
@@ -28,6 +29,7 @@ impl PipelineTask<InitCtx> for GuestInitTask {
             volume_mgr,
             rootfs_init,
             container_mounts,
+            static_ip,
         ) =
             {
                 let mut ctx = ctx.lock().await;
@@ -55,6 +57,7 @@ impl PipelineTask<InitCtx> for GuestInitTask {
                     volume_mgr,
                     rootfs_init,
                     container_mounts,
+                    ctx.config.options.static_ip,
                 )
             };
 
@@ -65,9 +68,10 @@ impl PipelineTask<InitCtx> for GuestInitTask {
             &volume_mgr,
             &rootfs_init,
             &container_mounts,
+            static_ip,
         )
         .await
-        .inspect_err(|e| log_task_error(&box_id, task_name, e))?;
+        .task_context(&box_id, task_name)?;
 
         let mut ctx = ctx.lock().await;
         ctx.guest_session = Some(guest_session);
@@ -91,6 +95,7 @@ async fn run_guest_init(
     volume_mgr: &GuestVolumeManager,
     rootfs_init: &ContainerRootfsInitConfig,
     container_mounts: &[ContainerMount],
+    static_ip: Option<(Ipv4Addr, u8)>,
 ) -> BoxliteResult<()> {
     let container_id_str = container_id.as_str();
 
@@ -101,7 +106,7 @@ async fn run_guest_init(
         volumes: guest_volumes,
         network: Some(NetworkInitConfig {
             interface: "eth0".to_string(),
-            ip: Some("192.168.127.2/24".to_string()),
+            ip: Some(guest_ip_cidr(static_ip)),
             gateway: Some("192.168.127.1".to_string()),
         }),
     };
@@ -127,3 +132,11 @@ async fn run_guest_init(
 
     Ok(())
 }
+
+/// Guest interface address in CIDR notation, honoring a static IP override.
+fn guest_ip_cidr(static_ip: Option<(Ipv4Addr, u8)>) -> String {
+    match static_ip {
+        Some((ip, prefix)) => format!("{}/{}", ip, prefix),
+        None => format!("{}/24", crate::net::constants::GUEST_IP),
+    }
+}
@@ -3,12 +3,13 @@
 //! Lazily initializes the bootstrap guest rootfs as a disk image (shared across all boxes).
 //! Then creates or reuses per-box COW overlay disk.
 
-use super::{InitCtx, log_task_error, task_start};
+use super::{InitCtx, TaskResultExt, task_start};
 use crate::disk::{BackingFormat, Disk, DiskFormat, Qcow2Helper, create_ext4_from_dir};
 use crate::pipeline::PipelineTask;
 use crate::rootfs::RootfsBuilder;
 use crate::runtime::constants::images;
 use crate::runtime::guest_rootfs::{GuestRootfs, Strategy};
+use crate::runtime::guest_rootfs_cache::{GuestRootfsCache, GuestRootfsInputs};
 use crate::runtime::layout::BoxFilesystemLayout;
 use crate::runtime::rt_impl::SharedRuntimeImpl;
 use crate::util;
@@ -34,7 +35,7 @@ impl PipelineTask<InitCtx> for GuestRootfsTask {
 
         let disk = run_guest_rootfs(&runtime, &layout, reuse_rootfs)
             .await
-            .inspect_err(|e| log_task_error(&box_id, task_name, e))?;
+            .task_context(&box_id, task_name)?;
 
         let mut ctx = ctx.lock().await;
         ctx.guest_disk = disk;
@@ -160,45 +161,45 @@ fn create_or_reuse_cow_disk(
 }
 
 /// Prepare guest rootfs as a disk image.
+///
+/// The built disk is cached by its build inputs (init image and guest
+/// binary), so later runtimes reuse it until one of them changes.
 async fn prepare_guest_rootfs(
     runtime: &crate::runtime::SharedRuntimeImpl,
     base_image: &crate::images::ImageObject,
     env: Vec<(String, String)>,
 ) -> BoxliteResult<GuestRootfs> {
-    // Check if we already have a cached disk image
-    if let Some(disk) = base_image.disk_image().await {
-        // Verify guest binary is not newer than cached disk
-        if is_cache_valid(disk.path())? {
-            let disk_path = disk.path().to_path_buf();
-            tracing::info!(
-                "Using cached guest rootfs disk image: {}",
-                disk_path.display()
-            );
+    let guest_bin = util::find_binary("boxlite-guest")?;
+    let inputs = GuestRootfsInputs {
+        image_digest: base_image.compute_image_digest(),
+        guest_binary_sha256: util::sha256_file(&guest_bin)?,
+        kernel_sha256: None,
+    };
 
-            // Leak the disk to prevent cleanup (it's a cached persistent disk)
-            let _ = disk.leak();
-
-            return GuestRootfs::new(
-                disk_path.clone(),
-                Strategy::Disk {
-                    disk_path,
-                    device_path: None, // Set later in build_disk_attachments
-                },
-                None,
-                None,
-                env,
-            );
-        }
+    let cache = GuestRootfsCache::new(runtime.layout.image_layout().guest_rootfs_dir());
+    let disk_path = cache
+        .get_or_build(&inputs, || build_guest_rootfs_disk(runtime, base_image))
+        .await?;
 
-        // Cache invalid - delete and recreate
-        tracing::info!(
-            "Guest binary updated, invalidating cached guest rootfs disk: {}",
-            disk.path().display()
-        );
-        std::fs::remove_file(disk.path()).ok();
-    }
+    GuestRootfs::new(
+        disk_path.clone(),
+        Strategy::Disk {
+            disk_path,
+            device_path: None, // Set later in build_disk_attachments
+        },
+        None,
+        None,
+        env,
+    )
+}
 
-    // No cached disk - create from layers
+/// Build the guest rootfs disk from the init image layers plus the guest binary.
+///
+/// The disk is created in the runtime temp dir (same filesystem as the cache).
+async fn build_guest_rootfs_disk(
+    runtime: &crate::runtime::SharedRuntimeImpl,
+    base_image: &crate::images::ImageObject,
+) -> BoxliteResult<Disk> {
     tracing::info!("Creating guest rootfs disk image from layers (first run)");
 
     // Extract layers to temp directory within boxlite home (same filesystem as destination)
@@ -230,49 +231,31 @@ async fn prepare_guest_rootfs(
         )));
     }
 
-    // Create ext4 disk from merged directory
-    let temp_disk_path = temp_dir.path().join("guest-rootfs.ext4");
+    // Create ext4 disk next to (not inside) the temp dir, so it outlives it
+    let disk_path = temp_base.join(format!(
+        "guest-rootfs-{}.ext4",
+        temp_dir
+            .path()
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("build")
+    ));
     let merged_clone = prepared.path.clone();
-    let disk_clone = temp_disk_path.clone();
-    let temp_disk =
+    let disk_clone = disk_path.clone();
+    let disk =
         tokio::task::spawn_blocking(move || create_ext4_from_dir(&merged_clone, &disk_clone))
             .await
             .map_err(|e| BoxliteError::Internal(format!("Disk creation task failed: {}", e)))??;
 
-    let disk_size = std::fs::metadata(temp_disk.path())
-        .map(|m| m.len())
-        .unwrap_or(0);
-
+    let disk_size = std::fs::metadata(disk.path()).map(|m| m.len()).unwrap_or(0);
     tracing::info!(
         "Created guest rootfs disk: {} ({}MB)",
-        temp_disk.path().display(),
+        disk.path().display(),
         disk_size / (1024 * 1024)
     );
 
-    // Install disk image to cache
-    let installed_disk = base_image.install_disk_image(temp_disk).await?;
-    let final_path = installed_disk.path().to_path_buf();
-
-    // Leak the disk to prevent cleanup
-    let _ = installed_disk.leak();
-
-    tracing::info!(
-        "Installed guest rootfs disk to cache: {}",
-        final_path.display()
-    );
-
     // temp_dir is dropped here, cleaning up the merged directory
-
-    GuestRootfs::new(
-        final_path.clone(),
-        Strategy::Disk {
-            disk_path: final_path,
-            device_path: None, // Set later in build_disk_attachments
-        },
-        None,
-        None,
-        env,
-    )
+    Ok(disk)
 }
 
 async fn pull_guest_rootfs_image(
@@ -308,34 +291,3 @@ async fn extract_env_from_image(
 
     Ok(env)
 }
-
-/// Check if cached guest rootfs disk is still valid.
-///
-/// Returns false if the current guest binary is newer than the cached disk,
-/// indicating the cache should be invalidated and recreated.
-fn is_cache_valid(cache_path: &std::path::Path) -> BoxliteResult<bool> {
-    let guest_bin = util::find_binary("boxlite-guest")?;
-
-    let guest_mtime = std::fs::metadata(&guest_bin)
-        .and_then(|m| m.modified())
-        .map_err(|e| {
-            BoxliteError::Storage(format!(
-                "Failed to get guest binary mtime {}: {}",
-                guest_bin.display(),
-                e
-            ))
-        })?;
-
-    let cache_mtime = std::fs::metadata(cache_path)
-        .and_then(|m| m.modified())
-        .map_err(|e| {
-            BoxliteError::Storage(format!(
-                "Failed to get cache mtime {}: {}",
-                cache_path.display(),
-                e
-            ))
-        })?;
-
-    // Cache is valid if it's newer than the guest binary
-    Ok(cache_mtime >= guest_mtime)
-}
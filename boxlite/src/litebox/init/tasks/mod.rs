@@ -33,7 +33,7 @@ mod vmm_spawn;
 
 use super::types::InitPipelineContext;
 use crate::runtime::types::BoxID;
-use boxlite_shared::errors::BoxliteError;
+use boxlite_shared::errors::{BoxliteError, BoxliteResult, BoxliteResultExt};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -49,6 +49,19 @@ fn log_task_error(box_id: &BoxID, task_name: &str, err: &BoxliteError) {
     tracing::error!(box_id = %box_id, task = %task_name, "Task failed: {}", err);
 }
 
+/// Task boundary handling for fallible steps: log the failure and tag the
+/// error with the box and task it came from.
+trait TaskResultExt<T> {
+    fn task_context(self, box_id: &BoxID, task_name: &str) -> BoxliteResult<T>;
+}
+
+impl<T> TaskResultExt<T> for BoxliteResult<T> {
+    fn task_context(self, box_id: &BoxID, task_name: &str) -> BoxliteResult<T> {
+        self.inspect_err(|e| log_task_error(box_id, task_name, e))
+            .with_box_context(box_id, task_name)
+    }
+}
+
 pub use container_rootfs::ContainerRootfsTask;
 pub use filesystem::FilesystemTask;
 pub use guest_connect::GuestConnectTask;
@@ -5,6 +5,7 @@
 
 use super::{InitCtx, task_start};
 use crate::pipeline::PipelineTask;
+use crate::runtime::constants::filenames::net_stats_path;
 use crate::vmm::controller::ShimHandler;
 use async_trait::async_trait;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
@@ -17,9 +18,13 @@ impl PipelineTask<InitCtx> for VmmAttachTask {
         let task_name = self.name();
         let box_id = task_start(&ctx, task_name).await;
 
-        let (runtime, config_id) = {
+        let (runtime, config_id, isolate_mounts) = {
             let ctx = ctx.lock().await;
-            (ctx.runtime.clone(), ctx.config.id.clone())
+            (
+                ctx.runtime.clone(),
+                ctx.config.id.clone(),
+                ctx.config.options.isolate_mounts,
+            )
         };
 
         // Load state from box_manager to get PID
@@ -40,7 +45,13 @@ impl PipelineTask<InitCtx> for VmmAttachTask {
         }
 
         // Attach to existing process (no log_handler for reconnect)
-        let handler = ShimHandler::from_pid(pid, config_id);
+        let net_stats = net_stats_path(runtime.layout.home_dir(), config_id.as_str());
+        let layout = runtime
+            .layout
+            .box_layout(config_id.as_str(), isolate_mounts)?;
+        let handler = ShimHandler::from_pid(pid, config_id)
+            .with_disk_paths(vec![layout.disk_path()])
+            .with_net_stats_path(net_stats);
 
         let mut ctx = ctx.lock().await;
         ctx.guard.set_handler(Box::new(handler));
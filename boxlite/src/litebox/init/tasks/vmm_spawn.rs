@@ -3,7 +3,7 @@
 //! Builds VMM InstanceSpec from prepared components, then spawns a new VM
 //! subprocess and returns a handler for runtime operations.
 
-use super::{InitCtx, log_task_error, task_start};
+use super::{InitCtx, TaskResultExt, task_start};
 use crate::disk::DiskFormat;
 use crate::images::ContainerImageConfig;
 use crate::litebox::init::types::resolve_user_volumes;
@@ -12,13 +12,13 @@ use crate::pipeline::PipelineTask;
 use crate::runtime::constants::{guest_paths, mount_tags};
 use crate::runtime::guest_rootfs::{GuestRootfs, Strategy};
 use crate::runtime::layout::BoxFilesystemLayout;
-use crate::runtime::options::BoxOptions;
+use crate::runtime::options::{BoxOptions, PortForward, PortProtocol};
 use crate::runtime::rt_impl::SharedRuntimeImpl;
 use crate::runtime::types::{BoxID, ContainerID};
 use crate::util::find_binary;
 use crate::vmm::controller::{ShimController, VmmController, VmmHandler};
-use crate::vmm::{Entrypoint, InstanceSpec, VmmKind};
-use crate::volumes::{ContainerMount, ContainerVolumeManager, GuestVolumeManager};
+use crate::vmm::{Entrypoint, InstanceSpec, KernelSpec, VmmKind};
+use crate::volumes::{ContainerMount, ContainerVolumeManager, GuestVolumeManager, MountOptions};
 use async_trait::async_trait;
 use boxlite_shared::Transport;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
@@ -85,18 +85,29 @@ impl PipelineTask<InitCtx> for VmmSpawnTask {
             reuse_rootfs,
         )
         .await
-        .inspect_err(|e| log_task_error(&box_id, task_name, e))?;
+        .task_context(&box_id, task_name)?;
+
+        // Fail fast if another box (or process) already holds a host port the
+        // user asked for; forwards of image-exposed ports stay best effort
+        let port_forwards = instance_spec
+            .network_config
+            .as_ref()
+            .map(|c| c.port_forwards.clone())
+            .unwrap_or_default();
+        crate::net::check_host_ports(&user_port_forwards(&port_forwards, &options))
+            .task_context(&box_id, task_name)?;
 
         // Spawn VM
         let handler = spawn_vm(&box_id, &instance_spec, &options)
             .await
-            .inspect_err(|e| log_task_error(&box_id, task_name, e))?;
+            .task_context(&box_id, task_name)?;
 
         let mut ctx = ctx.lock().await;
         ctx.guard.set_handler(handler);
         ctx.volume_mgr = Some(volume_mgr);
         ctx.rootfs_init = Some(rootfs_init);
         ctx.container_mounts = Some(container_mounts);
+        ctx.port_forwards = Some(port_forwards);
         Ok(())
     }
 
@@ -137,7 +148,14 @@ async fn build_config(
     let mut volume_mgr = GuestVolumeManager::new();
 
     // SHARED virtiofs - needed by all strategies
-    volume_mgr.add_fs_share(mount_tags::SHARED, layout.shared_dir(), None, false, None);
+    volume_mgr.add_fs_share(
+        mount_tags::SHARED,
+        layout.shared_dir(),
+        None,
+        false,
+        None,
+        MountOptions::default(),
+    );
 
     // Add container rootfs disk (COW overlay workflow):
     // 1. Base disk: Pre-built ext4 image with container layers merged
@@ -174,6 +192,7 @@ async fn build_config(
             vol.host_path.clone(),
             &vol.guest_path,
             vol.read_only,
+            vol.mount.clone(),
         );
     }
     let container_mounts = container_mgr.build_container_mounts();
@@ -197,9 +216,8 @@ async fn build_config(
     // Network configuration
     let network_config = build_network_config(container_image_config, options);
 
-    // Use runtime home for logs (not box_home)
+    // Shim logs go under the runtime home; console output stays with the box
     let runtime_home = runtime.layout.home_dir();
-    let logs_dir = runtime.layout.logs_dir();
 
     // Assemble VMM instance spec
     let instance_spec = InstanceSpec {
@@ -219,7 +237,13 @@ async fn build_config(
         network_config,
         network_backend_endpoint: None,
         home_dir: runtime_home.to_path_buf(),
-        console_output: Some(logs_dir.join(format!("{}-console.log", box_id))),
+        layout_dirs: runtime.layout.dirs().clone(),
+        console_output: Some(layout.console_output_path()),
+        kernel: KernelSpec {
+            loglevel: options.kernel_loglevel,
+            extra: options.kernel_cmdline_extra.clone(),
+            rng: options.enable_rng,
+        },
         detach: options.detach,
         parent_pid: std::process::id(),
     };
@@ -294,30 +318,55 @@ fn build_guest_entrypoint(
     })
 }
 
+/// The forwards in `forwards` that come from `options.ports`.
+fn user_port_forwards(forwards: &[PortForward], options: &BoxOptions) -> Vec<PortForward> {
+    forwards
+        .iter()
+        .filter(|f| {
+            options.ports.iter().any(|p| {
+                p.protocol == f.protocol && p.host_port.unwrap_or(p.guest_port) == f.host_port
+            })
+        })
+        .copied()
+        .collect()
+}
+
 /// Build network configuration from container image config and options.
 fn build_network_config(
     container_image_config: &crate::images::ContainerImageConfig,
     options: &crate::runtime::options::BoxOptions,
 ) -> Option<NetworkBackendConfig> {
-    let mut port_map: HashMap<u16, u16> = HashMap::new();
+    // Keyed by (host_port, protocol): one forward per host socket
+    let mut port_map: HashMap<(u16, PortProtocol), u16> = HashMap::new();
 
     // Step 1: Collect guest ports that user wants to customize
-    let user_guest_ports: HashSet<u16> = options.ports.iter().map(|p| p.guest_port).collect();
+    let user_guest_ports: HashSet<u16> = options
+        .ports
+        .iter()
+        .filter(|p| p.protocol == PortProtocol::Tcp)
+        .map(|p| p.guest_port)
+        .collect();
 
     // Step 2: Image exposed ports (only add default 1:1 mapping if user didn't override)
     for port in container_image_config.tcp_ports() {
         if !user_guest_ports.contains(&port) {
-            port_map.insert(port, port);
+            port_map.insert((port, PortProtocol::Tcp), port);
         }
     }
 
     // Step 3: User-provided mappings (always applied)
     for port in &options.ports {
         let host_port = port.host_port.unwrap_or(port.guest_port);
-        port_map.insert(host_port, port.guest_port);
+        port_map.insert((host_port, port.protocol), port.guest_port);
     }
 
-    let final_mappings: Vec<(u16, u16)> = port_map.into_iter().collect();
+    let mut final_mappings: Vec<PortForward> = port_map
+        .into_iter()
+        .map(|((host_port, protocol), guest_port)| {
+            PortForward::new(host_port, guest_port, protocol)
+        })
+        .collect();
+    final_mappings.sort_by_key(|f| (f.host_port, f.protocol.as_str()));
 
     tracing::info!(
         "Port mappings: {} (image: {}, user: {}, overridden: {})",
@@ -329,8 +378,16 @@ fn build_network_config(
             .count()
     );
 
+    let mut config = NetworkBackendConfig::new(final_mappings);
+    if let Some(mac) = options.mac_address {
+        config = config.with_guest_mac(mac);
+    }
+    if let Some((ip, prefix)) = options.static_ip {
+        config = config.with_guest_ip(ip, prefix);
+    }
+
     // Always return Some - gvproxy provides virtio-net (eth0) even without port mappings
-    Some(NetworkBackendConfig::new(final_mappings))
+    Some(config)
 }
 
 /// Spawn VM subprocess and return handler.
@@ -348,3 +405,76 @@ async fn spawn_vm(
 
     controller.start(config).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::options::{BoxliteOptions, PortSpec};
+    use crate::runtime::rt_impl::RuntimeImpl;
+
+    #[tokio::test]
+    async fn test_console_output_is_inside_box_dir() {
+        let home = tempfile::tempdir().unwrap();
+        let runtime = RuntimeImpl::new(BoxliteOptions {
+            home_dir: home.path().to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap();
+        runtime
+            .guest_rootfs
+            .set(GuestRootfs {
+                path: home.path().join("guest-rootfs"),
+                strategy: Strategy::Direct,
+                kernel: None,
+                initrd: None,
+                env: Vec::new(),
+            })
+            .unwrap();
+
+        let box_id = BoxID::new();
+        let layout = runtime.layout.box_layout(box_id.as_str(), false).unwrap();
+        let (spec, ..) = build_config(
+            &box_id,
+            &BoxOptions::default(),
+            &layout,
+            &ContainerImageConfig::default(),
+            &layout.disk_path(),
+            None,
+            &ContainerID::new(),
+            &runtime,
+            false,
+        )
+        .await
+        .unwrap();
+
+        let console = spec.console_output.unwrap();
+        assert_eq!(console, layout.console_output_path());
+        assert!(console.starts_with(layout.root()));
+    }
+
+    #[test]
+    fn test_only_user_ports_are_checked() {
+        let options = BoxOptions {
+            ports: vec![PortSpec {
+                host_port: Some(8080),
+                guest_port: 80,
+                protocol: PortProtocol::Tcp,
+                host_ip: None,
+            }],
+            ..Default::default()
+        };
+        let image_config = ContainerImageConfig {
+            exposed_ports: vec!["443/tcp".to_string()],
+            ..Default::default()
+        };
+
+        let forwards = build_network_config(&image_config, &options)
+            .unwrap()
+            .port_forwards;
+        assert_eq!(forwards.len(), 2);
+        assert_eq!(
+            user_port_forwards(&forwards, &options),
+            vec![PortForward::tcp(8080, 80)]
+        );
+    }
+}
@@ -9,11 +9,12 @@ use crate::litebox::config::BoxConfig;
 use crate::portal::GuestSession;
 use crate::portal::interfaces::ContainerRootfsInitConfig;
 use crate::runtime::layout::BoxFilesystemLayout;
-use crate::runtime::options::VolumeSpec;
+use crate::runtime::options::{PortForward, VolumeSpec};
 use crate::runtime::rt_impl::SharedRuntimeImpl;
 use crate::vmm::controller::VmmHandler;
-use crate::volumes::{ContainerMount, GuestVolumeManager};
+use crate::volumes::{ContainerMount, GuestVolumeManager, MountOptions};
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::atomic::Ordering;
 
@@ -37,13 +38,41 @@ pub struct ResolvedVolume {
     pub host_path: PathBuf,
     pub guest_path: String,
     pub read_only: bool,
+    pub mount: MountOptions,
+}
+
+/// Derive the virtiofs tag of a user volume from its guest path.
+///
+/// The tag only depends on the mount target, so it is stable across restarts
+/// and reordering, and unique because targets are.
+fn volume_tag(guest_path: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let digest = Sha256::digest(guest_path.as_bytes());
+    format!("uservol-{}", &hex::encode(digest)[..12])
 }
 
 pub fn resolve_user_volumes(volumes: &[VolumeSpec]) -> BoxliteResult<Vec<ResolvedVolume>> {
     let mut resolved = Vec::with_capacity(volumes.len());
+    let mut targets = HashSet::new();
+
+    for vol in volumes {
+        let target = normalize_guest_path(&vol.guest_path);
+        if !targets.insert(target.clone()) {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "Volume target {} is mounted more than once",
+                target
+            )));
+        }
+        for option in &vol.mount_options {
+            if option.is_empty() || option.contains(',') {
+                return Err(BoxliteError::InvalidArgument(format!(
+                    "Invalid mount option '{}' for volume {}",
+                    option, vol.guest_path
+                )));
+            }
+        }
 
-    for (i, vol) in volumes.iter().enumerate() {
-        let host_path = PathBuf::from(&vol.host_path);
+        let host_path = vol.absolute_host_path()?;
 
         if !host_path.exists() {
             return Err(BoxliteError::Config(format!(
@@ -66,13 +95,15 @@ pub fn resolve_user_volumes(volumes: &[VolumeSpec]) -> BoxliteResult<Vec<Resolve
             )));
         }
 
-        let tag = format!("uservol{}", i);
+        let tag = volume_tag(&target);
 
         tracing::debug!(
             tag = %tag,
             host_path = %resolved_path.display(),
             guest_path = %vol.guest_path,
             read_only = vol.read_only,
+            mount_options = ?vol.mount_options,
+            cache_mode = ?vol.cache_mode,
             "Resolved user volume"
         );
 
@@ -81,12 +112,26 @@ pub fn resolve_user_volumes(volumes: &[VolumeSpec]) -> BoxliteResult<Vec<Resolve
             host_path: resolved_path,
             guest_path: vol.guest_path.clone(),
             read_only: vol.read_only,
+            mount: MountOptions {
+                options: vol.mount_options.clone(),
+                cache_mode: vol.cache_mode,
+            },
         });
     }
 
     Ok(resolved)
 }
 
+/// Strip trailing slashes so `/data` and `/data/` are the same target.
+fn normalize_guest_path(path: &str) -> String {
+    let trimmed = path.trim_end_matches('/');
+    if trimmed.is_empty() {
+        "/".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
 /// Result of rootfs preparation - either merged, separate layers, or disk image.
 #[derive(Debug)]
 pub enum ContainerRootfsPrepResult {
@@ -144,6 +189,11 @@ impl CleanupGuard {
         self.handler = Some(handler);
     }
 
+    /// Whether the registered VM process is still alive (None if none yet).
+    pub fn handler_running(&self) -> Option<bool> {
+        self.handler.as_ref().map(|handler| handler.is_running())
+    }
+
     /// Take ownership of handler (for success path).
     pub fn take_handler(&mut self) -> Option<Box<dyn VmmHandler>> {
         self.handler.take()
@@ -218,6 +268,8 @@ pub struct InitPipelineContext {
     pub rootfs_init: Option<ContainerRootfsInitConfig>,
     pub container_mounts: Option<Vec<ContainerMount>>,
     pub guest_session: Option<GuestSession>,
+    /// Port forwards resolved by vmm_spawn (None on reattach)
+    pub port_forwards: Option<Vec<PortForward>>,
 
     #[cfg(target_os = "linux")]
     pub bind_mount: Option<BindMountHandle>,
@@ -245,8 +297,69 @@ impl InitPipelineContext {
             rootfs_init: None,
             container_mounts: None,
             guest_session: None,
+            port_forwards: None,
             #[cfg(target_os = "linux")]
             bind_mount: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::options::VolumeCacheMode;
+
+    fn volume(host: &std::path::Path, guest: &str) -> VolumeSpec {
+        VolumeSpec {
+            host_path: host.to_string_lossy().into_owned(),
+            guest_path: guest.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_duplicate_volume_targets_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let volumes = vec![volume(dir.path(), "/data"), volume(dir.path(), "/data/")];
+
+        let err = resolve_user_volumes(&volumes).unwrap_err();
+        assert!(
+            matches!(err, BoxliteError::InvalidArgument(_)),
+            "got {:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_volume_tags_are_stable_and_unique() {
+        let dir = tempfile::tempdir().unwrap();
+        let forward =
+            resolve_user_volumes(&[volume(dir.path(), "/a"), volume(dir.path(), "/b")]).unwrap();
+        let reversed =
+            resolve_user_volumes(&[volume(dir.path(), "/b"), volume(dir.path(), "/a")]).unwrap();
+
+        assert_ne!(forward[0].tag, forward[1].tag);
+        assert_eq!(forward[0].tag, reversed[1].tag);
+        assert_eq!(forward[1].tag, reversed[0].tag);
+        // virtio-fs tags are limited to 36 bytes
+        assert!(forward[0].tag.len() <= 36);
+    }
+
+    #[test]
+    fn test_volume_mount_options_resolved() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut spec = volume(dir.path(), "/data");
+        spec.mount_options = vec!["nosuid".to_string(), "nodev".to_string()];
+        spec.cache_mode = VolumeCacheMode::Never;
+
+        let resolved = resolve_user_volumes(std::slice::from_ref(&spec)).unwrap();
+        assert_eq!(resolved[0].mount.options, spec.mount_options);
+        assert_eq!(resolved[0].mount.cache_mode, VolumeCacheMode::Never);
+
+        spec.mount_options = vec!["nosuid,nodev".to_string()];
+        assert!(matches!(
+            resolve_user_volumes(&[spec]),
+            Err(BoxliteError::InvalidArgument(_))
+        ));
+    }
+}
@@ -0,0 +1,233 @@
+//! Console log following.
+//!
+//! Tails a box's console log file like `tail -f`: lines appended after
+//! subscribing are yielded as they are written. When the file is replaced
+//! (rotated) or truncated, the follower drains the old file and continues
+//! from the start of the new one.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use futures::Stream;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, SeekFrom};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+/// How often the log file is checked for new output.
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Follow `path`, yielding each line appended after this call.
+///
+/// The file does not need to exist yet. Lines are yielded without their
+/// trailing newline; an incomplete last line is held back until it is
+/// terminated. The stream ends on the first I/O error (which is yielded)
+/// and the background task stops once the stream is dropped.
+pub(crate) fn follow_file(
+    path: PathBuf,
+) -> impl Stream<Item = BoxliteResult<String>> + Send + 'static {
+    let (tx, rx) = mpsc::unbounded_channel();
+    // Lines already in the file are skipped; anything written later is delivered
+    let start = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+    tokio::spawn(async move {
+        let mut follower = Follower::new(path, start);
+        while !tx.is_closed() {
+            match follower.poll_lines().await {
+                Ok(lines) => {
+                    for line in lines {
+                        if tx.send(Ok(line)).is_err() {
+                            return;
+                        }
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(Err(e));
+                    return;
+                }
+            }
+            tokio::time::sleep(FOLLOW_POLL_INTERVAL).await;
+        }
+    });
+
+    UnboundedReceiverStream::new(rx)
+}
+
+/// Read position in the currently followed file.
+struct Follower {
+    path: PathBuf,
+    file: Option<File>,
+    file_id: Option<u64>,
+    /// Offset to seek to when the file is first opened
+    start: u64,
+    pos: u64,
+    partial: Vec<u8>,
+}
+
+impl Follower {
+    fn new(path: PathBuf, start: u64) -> Self {
+        Self {
+            path,
+            file: None,
+            file_id: None,
+            start,
+            pos: 0,
+            partial: Vec::new(),
+        }
+    }
+
+    /// Read everything appended since the last poll and return complete lines.
+    async fn poll_lines(&mut self) -> BoxliteResult<Vec<String>> {
+        if self.file.is_none() && !self.open().await? {
+            return Ok(Vec::new());
+        }
+
+        let mut lines = self.drain().await?;
+
+        // Rotated (new file at the path) or truncated: switch to the new content
+        if self.replaced().await {
+            self.file = None;
+            self.file_id = None;
+            self.start = 0;
+            if self.open().await? {
+                lines.extend(self.drain().await?);
+            }
+        }
+        Ok(lines)
+    }
+
+    /// Open the file at `path`. Returns `false` if it does not exist yet.
+    async fn open(&mut self) -> BoxliteResult<bool> {
+        let mut file = match File::open(&self.path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(log_io_error(&self.path, e)),
+        };
+        let metadata = file
+            .metadata()
+            .await
+            .map_err(|e| log_io_error(&self.path, e))?;
+        let start = self.start.min(metadata.len());
+        file.seek(SeekFrom::Start(start))
+            .await
+            .map_err(|e| log_io_error(&self.path, e))?;
+
+        self.file_id = file_id(&metadata);
+        self.pos = start;
+        self.file = Some(file);
+        Ok(true)
+    }
+
+    /// Read to the end of the open file, splitting off complete lines.
+    async fn drain(&mut self) -> BoxliteResult<Vec<String>> {
+        let Some(file) = self.file.as_mut() else {
+            return Ok(Vec::new());
+        };
+        let mut buf = Vec::new();
+        let n = file
+            .read_to_end(&mut buf)
+            .await
+            .map_err(|e| log_io_error(&self.path, e))?;
+        self.pos += n as u64;
+        self.partial.extend_from_slice(&buf);
+
+        let mut lines = Vec::new();
+        while let Some(idx) = self.partial.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.partial.drain(..=idx).collect();
+            lines.push(String::from_utf8_lossy(&line[..idx]).into_owned());
+        }
+        Ok(lines)
+    }
+
+    /// Whether the path now refers to a different or truncated file.
+    async fn replaced(&self) -> bool {
+        match tokio::fs::metadata(&self.path).await {
+            Ok(metadata) => file_id(&metadata) != self.file_id || metadata.len() < self.pos,
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(unix)]
+fn file_id(metadata: &std::fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.ino())
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &std::fs::Metadata) -> Option<u64> {
+    None
+}
+
+fn log_io_error(path: &Path, e: std::io::Error) -> BoxliteError {
+    BoxliteError::Storage(format!("Failed to read log {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use std::io::Write;
+
+    fn append(path: &Path, content: &str) {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+    }
+
+    async fn next_line(stream: &mut (impl Stream<Item = BoxliteResult<String>> + Unpin)) -> String {
+        tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("timed out waiting for log line")
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_follow_delivers_appended_lines_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("console.log");
+        append(&path, "before subscribe\n");
+
+        let mut stream = Box::pin(follow_file(path.clone()));
+        append(&path, "first\nsecond\n");
+        append(&path, "thi");
+        tokio::time::sleep(FOLLOW_POLL_INTERVAL * 2).await;
+        append(&path, "rd\n");
+
+        assert_eq!(next_line(&mut stream).await, "first");
+        assert_eq!(next_line(&mut stream).await, "second");
+        assert_eq!(next_line(&mut stream).await, "third");
+    }
+
+    #[tokio::test]
+    async fn test_follow_waits_for_file_creation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("console.log");
+
+        let mut stream = Box::pin(follow_file(path.clone()));
+        tokio::time::sleep(FOLLOW_POLL_INTERVAL * 2).await;
+        append(&path, "booting\n");
+
+        assert_eq!(next_line(&mut stream).await, "booting");
+    }
+
+    #[tokio::test]
+    async fn test_follow_reopens_after_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("console.log");
+        append(&path, "");
+
+        let mut stream = Box::pin(follow_file(path.clone()));
+        append(&path, "old\n");
+        assert_eq!(next_line(&mut stream).await, "old");
+
+        std::fs::rename(&path, dir.path().join("console.log.1")).unwrap();
+        append(&path, "new\n");
+        assert_eq!(next_line(&mut stream).await, "new");
+    }
+}
@@ -9,7 +9,8 @@ use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
 use crate::db::BoxStore;
 use crate::litebox::config::BoxConfig;
-use crate::runtime::types::{BoxID, BoxState};
+use crate::runtime::types::{BoxID, BoxState, BoxStatus};
+use chrono::{DateTime, Utc};
 
 /// State backend for box persistence.
 ///
@@ -42,7 +43,7 @@ impl BoxManager {
     pub fn add_box(&self, config: &BoxConfig, state: &BoxState) -> BoxliteResult<()> {
         // Check name uniqueness if name is set
         if let Some(ref name) = config.name
-            && self.lookup_box_id(name)?.is_some()
+            && self.box_by_name(name)?.is_some()
         {
             return Err(BoxliteError::InvalidState(format!(
                 "box with name '{}' already exists",
@@ -89,6 +90,11 @@ impl BoxManager {
         self.store.load(id.as_str())
     }
 
+    /// Get a box by exact name.
+    pub fn box_by_name(&self, name: &str) -> BoxliteResult<Option<(BoxConfig, BoxState)>> {
+        self.store.find_by_name(name)
+    }
+
     /// Lookup a box by ID prefix or name.
     ///
     /// Tries exact name match first, then ID prefix match.
@@ -134,6 +140,14 @@ impl BoxManager {
             .map(|opt| opt.map(|(config, _)| config.id))
     }
 
+    /// Lookup a box by the full ID of its container.
+    pub fn box_by_container_id(
+        &self,
+        container_id: &str,
+    ) -> BoxliteResult<Option<(BoxConfig, BoxState)>> {
+        self.store.find_by_container_id(container_id)
+    }
+
     /// Check if a box exists by exact ID.
     pub fn has_box(&self, id: &BoxID) -> BoxliteResult<bool> {
         self.store.load(id.as_str()).map(|opt| opt.is_some())
@@ -144,6 +158,23 @@ impl BoxManager {
         self.store.list_all()
     }
 
+    /// Get boxes whose status is one of `statuses`.
+    pub fn boxes_by_status(
+        &self,
+        statuses: &[BoxStatus],
+    ) -> BoxliteResult<Vec<(BoxConfig, BoxState)>> {
+        self.store.list_by_status(statuses)
+    }
+
+    /// Get boxes created in `[start, end)`.
+    pub fn boxes_created_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> BoxliteResult<Vec<(BoxConfig, BoxState)>> {
+        self.store.list_created_between(start, end)
+    }
+
     /// Save box state to the database.
     ///
     /// Reads state from the provided BoxState and persists to DB.
@@ -159,6 +190,19 @@ impl BoxManager {
         Ok(())
     }
 
+    /// Persist an updated box configuration (e.g. after a rename).
+    pub fn update_config(&self, config: &BoxConfig) -> BoxliteResult<()> {
+        self.store.update_config(config)?;
+
+        tracing::trace!(
+            box_id = %config.id,
+            name = ?config.name,
+            "Saved box config to database"
+        );
+
+        Ok(())
+    }
+
     /// Load box state from the database.
     ///
     /// Returns the latest state from DB.
@@ -174,19 +218,21 @@ impl BoxManager {
 
     /// Check and handle system reboot.
     ///
-    /// Returns true if a reboot was detected.
-    pub fn check_and_handle_reboot(&self) -> BoxliteResult<bool> {
+    /// Returns the IDs of active boxes reset to stopped (empty if no reboot
+    /// was detected).
+    pub fn check_and_handle_reboot(&self) -> BoxliteResult<Vec<BoxID>> {
         let is_reboot = self.store.check_and_update_boot()?;
+        if !is_reboot {
+            return Ok(Vec::new());
+        }
 
-        if is_reboot {
-            tracing::info!("Detected system reboot, resetting active boxes to stopped");
-            let reset_ids = self.store.reset_active_boxes_after_reboot()?;
-            for id in &reset_ids {
-                tracing::info!(box_id = %id, "Reset box to stopped after reboot");
-            }
+        tracing::info!("Detected system reboot, resetting active boxes to stopped");
+        let reset_ids = self.store.reset_active_boxes_after_reboot()?;
+        for id in &reset_ids {
+            tracing::info!(box_id = %id, "Reset box to stopped after reboot");
         }
 
-        Ok(is_reboot)
+        Ok(reset_ids)
     }
 }
 
@@ -194,7 +240,7 @@ impl BoxManager {
 mod tests {
     use super::*;
     use crate::db::Database;
-    use crate::litebox::config::ContainerRuntimeConfig;
+    use crate::litebox::config::{BOX_CONFIG_VERSION, ContainerRuntimeConfig};
     use crate::runtime::types::{BoxID, BoxStatus, ContainerID};
     use crate::vmm::VmmKind;
     use boxlite_shared::Transport;
@@ -212,8 +258,10 @@ mod tests {
     fn create_test_config(id: &str) -> BoxConfig {
         use crate::runtime::options::{BoxOptions, RootfsSpec};
         BoxConfig {
+            config_version: BOX_CONFIG_VERSION,
             id: BoxID::parse(id).unwrap(),
             name: None,
+            warm_pool: false,
             created_at: Utc::now(),
             container: ContainerRuntimeConfig {
                 id: ContainerID::new(),
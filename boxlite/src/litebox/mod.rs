@@ -3,23 +3,30 @@
 //! Provides lazy initialization and execution capabilities for isolated boxes.
 
 pub(crate) mod box_impl;
+pub(crate) mod bundle;
+mod commit;
 pub(crate) mod config;
 mod exec;
 mod init;
+mod logs;
 mod manager;
 mod state;
 
 pub use exec::{BoxCommand, ExecResult, ExecStderr, ExecStdin, ExecStdout, Execution, ExecutionId};
 pub(crate) use manager::BoxManager;
-pub use state::{BoxState, BoxStatus};
+pub use state::{BoxState, BoxStatus, HealthStatus, StartupTimings};
 
 pub(crate) use box_impl::SharedBoxImpl;
 pub(crate) use init::BoxBuilder;
 
+use crate::images::{ImageConfigChanges, ImageObject};
 use crate::metrics::BoxMetrics;
-use crate::{BoxID, BoxInfo};
+use crate::{BoxID, BoxInfo, DiskSnapshot};
 use boxlite_shared::errors::BoxliteResult;
 pub use config::BoxConfig;
+use futures::Stream;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// LiteBox - Handle to a box.
 ///
@@ -43,7 +50,7 @@ impl LiteBox {
     /// Multiple handles to the same box share the same LiveState.
     pub(crate) fn new(inner: SharedBoxImpl) -> Self {
         let id = inner.id().clone();
-        let name = inner.config.name.clone();
+        let name = inner.name();
         Self { id, name, inner }
     }
 
@@ -60,6 +67,11 @@ impl LiteBox {
         self.inner.info()
     }
 
+    /// Static configuration the box was created with.
+    pub fn config(&self) -> &BoxConfig {
+        &self.inner.config
+    }
+
     /// Start the box (initialize VM).
     ///
     /// For Configured boxes: initializes VM for the first time.
@@ -75,13 +87,190 @@ impl LiteBox {
         self.inner.exec(command).await
     }
 
+    /// Copy a host file into the box at `guest_path` (absolute, inside the container).
+    ///
+    /// Contents are streamed over the guest connection and the file's mode
+    /// bits are preserved. Missing parent directories are created. Returns
+    /// `BoxliteError::NotFound` if `host_path` does not exist.
+    pub async fn copy_to_guest(&self, host_path: &Path, guest_path: &str) -> BoxliteResult<()> {
+        self.inner.copy_to_guest(host_path, guest_path).await
+    }
+
+    /// Copy a file out of the box to `host_path`, preserving its mode bits.
+    ///
+    /// Returns `BoxliteError::NotFound` if `guest_path` does not exist.
+    pub async fn copy_from_guest(&self, guest_path: &str, host_path: &Path) -> BoxliteResult<()> {
+        self.inner.copy_from_guest(guest_path, host_path).await
+    }
+
+    /// Path of the box's console log: `boxes/<id>/console.log`.
+    ///
+    /// The VM's console output (kernel and guest init) is written here while
+    /// the box runs. The file persists across restarts until the box is removed.
+    pub fn log_path(&self) -> PathBuf {
+        self.inner.log_path()
+    }
+
+    /// Read the box's console log.
+    ///
+    /// With `tail: Some(n)` only the last `n` lines are returned. Returns an
+    /// empty string if the box has not been started yet.
+    pub async fn read_logs(&self, tail: Option<usize>) -> BoxliteResult<String> {
+        self.inner.read_logs(tail).await
+    }
+
+    /// Follow the box's console log, like `tail -f`.
+    ///
+    /// Yields each line (without its trailing newline) written after this
+    /// call, reopening the file if it is rotated or recreated. The stream
+    /// never ends on its own; drop it to stop following. Must be called from
+    /// within a Tokio runtime.
+    pub fn follow_logs(&self) -> impl Stream<Item = BoxliteResult<String>> + Send + 'static {
+        logs::follow_file(self.log_path())
+    }
+
+    /// Export the box's container rootfs to `out_path` as a raw disk image.
+    ///
+    /// The image is self-contained and can be booted by another box via
+    /// `RootfsSpec::Disk`. The box must be stopped; a running box returns
+    /// `BoxliteError::InvalidState`, as does one that has never been started.
+    pub async fn export_rootfs(&self, out_path: &Path) -> BoxliteResult<()> {
+        self.inner.export_rootfs(out_path).await
+    }
+
+    /// Pack the box into a portable bundle at `out`.
+    ///
+    /// The bundle is a gzipped tarball with the box's options and metadata
+    /// and, if the box has been started, its disk flattened to a raw image.
+    /// Volumes and other host paths are not included. Restore it with
+    /// `BoxliteRuntime::import_bundle`. The box must be stopped; a running
+    /// box returns `BoxliteError::InvalidState`.
+    pub async fn export_bundle(&self, out: &Path) -> BoxliteResult<()> {
+        self.inner.export_bundle(out).await
+    }
+
+    /// Save the box's rootfs as a new local image tagged `image_ref`.
+    ///
+    /// The image is the box's base image with one layer holding everything
+    /// the box changed, and its config carries the box's command, env,
+    /// working directory and user. Other boxes can then use it by reference.
+    /// Returns the image's manifest digest.
+    ///
+    /// The box must be stopped; a running box returns
+    /// `BoxliteError::InvalidState`. Boxes not created from an image return
+    /// `BoxliteError::Unsupported`.
+    pub async fn commit(&self, image_ref: &str) -> BoxliteResult<String> {
+        let changes = ImageConfigChanges::from_box_options(&self.inner.config.options);
+        let image = self
+            .inner
+            .commit(image_ref, &changes, "boxlite commit")
+            .await?;
+        Ok(image.manifest_digest().to_string())
+    }
+
+    /// Snapshot the stopped box's rootfs as image `image_ref`, applying
+    /// `changes` to the base image's config and recording `created_by` in
+    /// its history.
+    pub(crate) async fn commit_image(
+        &self,
+        image_ref: &str,
+        changes: &ImageConfigChanges,
+        created_by: &str,
+    ) -> BoxliteResult<ImageObject> {
+        self.inner.commit(image_ref, changes, created_by).await
+    }
+
+    /// Rewrite the box's qcow2 disk to drop clusters it no longer uses.
+    ///
+    /// Returns the number of bytes reclaimed on the host. The box must be
+    /// stopped; a running box returns `BoxliteError::InvalidState`.
+    pub async fn compact_disk(&self) -> BoxliteResult<u64> {
+        self.inner.compact_disk().await
+    }
+
+    /// Save a copy of the box's disk as snapshot `name`.
+    ///
+    /// Stored under `snapshots/<name>.qcow2` in the box directory. The box
+    /// must be stopped and have been started at least once; otherwise this
+    /// returns `BoxliteError::InvalidState`. An existing name returns
+    /// `BoxliteError::AlreadyExists`.
+    pub async fn snapshot_disk(&self, name: &str) -> BoxliteResult<DiskSnapshot> {
+        self.inner.snapshot_disk(name).await
+    }
+
+    /// Roll the box's disk back to snapshot `name`.
+    ///
+    /// Changes made since the snapshot are discarded. The box must be
+    /// stopped; a running box returns `BoxliteError::InvalidState`.
+    pub async fn restore_disk(&self, name: &str) -> BoxliteResult<DiskSnapshot> {
+        self.inner.restore_disk(name).await
+    }
+
+    /// Probe whether the guest agent is responsive.
+    ///
+    /// Pings the guest with a short timeout. A box that is not running
+    /// returns `HealthStatus::NotRunning` without connecting, and the probe
+    /// never starts the box. The result is also reported on
+    /// `BoxInfo::health`.
+    pub async fn health(&self) -> BoxliteResult<HealthStatus> {
+        self.inner.health().await
+    }
+
     pub async fn metrics(&self) -> BoxliteResult<BoxMetrics> {
         self.inner.metrics().await
     }
 
+    /// Send a signal (e.g. `libc::SIGTERM`) to the container's main process.
+    ///
+    /// Lets the entrypoint shut down gracefully before `stop()`. Returns
+    /// `BoxliteError::InvalidState` if the box is not running.
+    pub async fn signal(&self, signal: i32) -> BoxliteResult<()> {
+        self.inner.signal(signal).await
+    }
+
     pub async fn stop(&self) -> BoxliteResult<()> {
         self.inner.stop().await
     }
+
+    /// Set the guest's wall clock to the host's current time.
+    ///
+    /// Corrects drift after the host slept or the box ran detached for a
+    /// long time. Returns `BoxliteError::InvalidState` if the box is not
+    /// running. See also `BoxOptions::sync_time_on_resume`.
+    pub async fn sync_time(&self) -> BoxliteResult<()> {
+        self.inner.sync_time().await
+    }
+
+    /// Release this handle while leaving the box's VM running.
+    ///
+    /// The box becomes `Detached` and this handle is invalidated, along with
+    /// its open executions. A new handle from `runtime.get()` reattaches on
+    /// first use. The VM only outlives this process if the box was created
+    /// with `detach: true`. Returns `BoxliteError::InvalidState` unless the
+    /// box is running.
+    pub fn detach(&self) -> BoxliteResult<()> {
+        self.inner.detach()
+    }
+
+    /// Wait until the box stops, either through `stop()` or because its VM
+    /// exited, and return the final status.
+    ///
+    /// Returns immediately for a stopped box and fails with
+    /// `BoxliteError::InvalidState` for one that was never started.
+    pub async fn wait(&self) -> BoxliteResult<BoxStatus> {
+        self.inner.wait().await
+    }
+
+    /// Like [`wait`](Self::wait), but gives up after `timeout`.
+    ///
+    /// Returns `Ok(None)` if the box is still running when the timeout
+    /// elapses.
+    pub async fn wait_timeout(&self, timeout: Duration) -> BoxliteResult<Option<BoxStatus>> {
+        match tokio::time::timeout(timeout, self.inner.wait()).await {
+            Ok(status) => status.map(Some),
+            Err(_) => Ok(None),
+        }
+    }
 }
 
 // ============================================================================
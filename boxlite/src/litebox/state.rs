@@ -4,9 +4,11 @@
 
 use crate::ContainerID;
 use crate::lock::LockId;
+use crate::runtime::options::PortForward;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Lifecycle status of a box.
 ///
@@ -18,6 +20,7 @@ use serde::{Deserialize, Serialize};
 /// create() → Configured (persisted to DB, no VM)
 /// start()  → Running (VM initialized)
 /// stop()   → Stopped (VM terminated, can restart)
+/// detach() → Detached (VM keeps running, reattached by runtime.get())
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -38,12 +41,16 @@ pub enum BoxStatus {
     /// Box is not running. VM process terminated.
     /// Rootfs is preserved, box can be restarted.
     Stopped,
+
+    /// VM is running but no handle is attached to it (see `LiteBox::detach`).
+    /// A handle from `runtime.get()` reattaches on first use.
+    Detached,
 }
 
 impl BoxStatus {
     /// Check if this status represents an active VM (process is running).
     pub fn is_active(&self) -> bool {
-        matches!(self, BoxStatus::Running)
+        matches!(self, BoxStatus::Running | BoxStatus::Detached)
     }
 
     pub fn is_running(&self) -> bool {
@@ -65,15 +72,19 @@ impl BoxStatus {
     }
 
     /// Check if start() can be called from this state.
-    /// Configured boxes need first start, Stopped boxes can restart.
+    /// Configured boxes need first start, Stopped boxes can restart,
+    /// Detached boxes are reattached.
     pub fn can_start(&self) -> bool {
-        matches!(self, BoxStatus::Configured | BoxStatus::Stopped)
+        matches!(
+            self,
+            BoxStatus::Configured | BoxStatus::Stopped | BoxStatus::Detached
+        )
     }
 
     /// Check if stop() can be called from this state.
-    /// Only running boxes can be stopped.
+    /// Running and Detached boxes can be stopped.
     pub fn can_stop(&self) -> bool {
-        matches!(self, BoxStatus::Running)
+        matches!(self, BoxStatus::Running | BoxStatus::Detached)
     }
 
     /// Check if remove() can be called from this state.
@@ -86,11 +97,11 @@ impl BoxStatus {
     }
 
     /// Check if exec() can be called from this state.
-    /// Configured and Stopped will trigger implicit start().
+    /// Configured, Stopped and Detached will trigger implicit start().
     pub fn can_exec(&self) -> bool {
         matches!(
             self,
-            BoxStatus::Configured | BoxStatus::Running | BoxStatus::Stopped
+            BoxStatus::Configured | BoxStatus::Running | BoxStatus::Stopped | BoxStatus::Detached
         )
     }
 
@@ -105,10 +116,16 @@ impl BoxStatus {
             (Configured, Running) |
             (Configured, Stopped) |
             (Configured, Unknown) |
-            // Running → Stopping (graceful) or Stopped (crash)
+            // Running → Stopping (graceful), Stopped (crash) or Detached
             (Running, Stopping) |
             (Running, Stopped) |
             (Running, Unknown) |
+            (Running, Detached) |
+            // Detached → Running (reattach), Stopping or Stopped (crash)
+            (Detached, Running) |
+            (Detached, Stopping) |
+            (Detached, Stopped) |
+            (Detached, Unknown) |
             // Stopping → Stopped (complete) or Unknown (error)
             (Stopping, Stopped) |
             (Stopping, Unknown) |
@@ -126,6 +143,7 @@ impl BoxStatus {
             BoxStatus::Running => "running",
             BoxStatus::Stopping => "stopping",
             BoxStatus::Stopped => "stopped",
+            BoxStatus::Detached => "detached",
         }
     }
 }
@@ -142,6 +160,7 @@ impl std::str::FromStr for BoxStatus {
             "running" => Ok(BoxStatus::Running),
             "stopping" => Ok(BoxStatus::Stopping),
             "stopped" => Ok(BoxStatus::Stopped),
+            "detached" => Ok(BoxStatus::Detached),
             _ => Err(()),
         }
     }
@@ -153,6 +172,45 @@ impl std::fmt::Display for BoxStatus {
     }
 }
 
+/// Result of a guest agent health probe (see `LiteBox::health`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    /// The guest agent answered the ping in time.
+    Healthy,
+
+    /// The box is running but the guest agent did not answer in time.
+    Unresponsive,
+
+    /// The box is not running, so there is nothing to probe.
+    NotRunning,
+}
+
+/// How long each init stage took the last time the box started.
+///
+/// Stages that did not run (e.g. `guest_init` when reattaching, or
+/// `vmm_spawn` vs `vmm_attach`) are `None`. `container_rootfs` and
+/// `guest_rootfs` run in parallel, so the stages may sum to more than `total`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StartupTimings {
+    /// Box directory layout setup
+    pub filesystem: Option<Duration>,
+    /// Image pull/extraction and container disk preparation
+    pub container_rootfs: Option<Duration>,
+    /// Guest rootfs preparation
+    pub guest_rootfs: Option<Duration>,
+    /// VM configuration and spawn
+    pub vmm_spawn: Option<Duration>,
+    /// Attach to an already running VM
+    pub vmm_attach: Option<Duration>,
+    /// Wait for the guest agent to become ready
+    pub guest_connect: Option<Duration>,
+    /// Container initialization inside the guest
+    pub guest_init: Option<Duration>,
+    /// Whole startup, including pipeline overhead
+    pub total: Duration,
+}
+
 /// Dynamic box state (changes during lifecycle).
 ///
 /// This is updated frequently and persisted to database.
@@ -162,6 +220,17 @@ pub struct BoxState {
     /// Current lifecycle status.
     pub status: BoxStatus,
     pub pid: Option<u32>,
+    /// Start time of the `pid` process as read by
+    /// [`process_start_time`](crate::util::process_start_time) at spawn.
+    ///
+    /// Guards against PID reuse when recovering boxes.
+    #[serde(default)]
+    pub pid_start_time: Option<u64>,
+    /// Process group led by the shim, killed as a whole on stop.
+    ///
+    /// Only set when the shim leads its own group.
+    #[serde(default)]
+    pub pgid: Option<u32>,
     pub container_id: Option<ContainerID>,
     /// Last state change timestamp (UTC).
     pub last_updated: DateTime<Utc>,
@@ -170,6 +239,18 @@ pub struct BoxState {
     /// Allocated when the box is first initialized (not at creation time).
     /// Used to retrieve the lock across process restarts.
     pub lock_id: Option<LockId>,
+    /// Host-to-guest port forwards applied when the box last started.
+    ///
+    /// Cleared when the box stops; the host ports are free again.
+    #[serde(default)]
+    pub port_forwards: Vec<PortForward>,
+    /// Restarts made by the restart policy since the box was last started
+    /// or stopped by the user.
+    #[serde(default)]
+    pub restart_count: u32,
+    /// Per-stage timings of the most recent successful start.
+    #[serde(default)]
+    pub startup_timings: Option<StartupTimings>,
 }
 
 impl BoxState {
@@ -179,9 +260,14 @@ impl BoxState {
         Self {
             status: BoxStatus::Configured,
             pid: None,
+            pid_start_time: None,
+            pgid: None,
             container_id: None,
             last_updated: Utc::now(),
             lock_id: None,
+            port_forwards: Vec::new(),
+            restart_count: 0,
+            startup_timings: None,
         }
     }
 
@@ -219,7 +305,14 @@ impl BoxState {
     }
 
     /// Set PID and update timestamp.
+    ///
+    /// A different PID clears `pid_start_time` and `pgid`, which described
+    /// the old process.
     pub fn set_pid(&mut self, pid: Option<u32>) {
+        if self.pid != pid {
+            self.pid_start_time = None;
+            self.pgid = None;
+        }
         self.pid = pid;
         self.last_updated = Utc::now();
     }
@@ -232,6 +325,9 @@ impl BoxState {
     pub fn mark_stop(&mut self) {
         self.status = BoxStatus::Stopped;
         self.pid = None;
+        self.pid_start_time = None;
+        self.pgid = None;
+        self.port_forwards.clear();
         self.last_updated = Utc::now();
     }
 
@@ -244,6 +340,9 @@ impl BoxState {
             self.status = BoxStatus::Stopped;
         }
         self.pid = None;
+        self.pid_start_time = None;
+        self.pgid = None;
+        self.port_forwards.clear();
         self.last_updated = Utc::now();
     }
 }
@@ -260,9 +359,10 @@ mod tests {
 
     #[test]
     fn test_status_is_active() {
-        // Only Running is active (VM process running)
+        // Running and Detached are active (VM process running)
         assert!(!BoxStatus::Configured.is_active());
         assert!(BoxStatus::Running.is_active());
+        assert!(BoxStatus::Detached.is_active());
         assert!(!BoxStatus::Stopping.is_active());
         assert!(!BoxStatus::Stopped.is_active());
         assert!(!BoxStatus::Unknown.is_active());
@@ -279,8 +379,9 @@ mod tests {
 
     #[test]
     fn test_status_can_start() {
-        // Configured and Stopped can be started
+        // Configured and Stopped can be started, Detached reattached
         assert!(BoxStatus::Configured.can_start());
+        assert!(BoxStatus::Detached.can_start());
         assert!(!BoxStatus::Running.can_start());
         assert!(!BoxStatus::Stopping.can_start());
         assert!(BoxStatus::Stopped.can_start());
@@ -289,9 +390,10 @@ mod tests {
 
     #[test]
     fn test_status_can_stop() {
-        // Only Running boxes can be stopped
+        // Running and Detached boxes can be stopped
         assert!(!BoxStatus::Configured.can_stop());
         assert!(BoxStatus::Running.can_stop());
+        assert!(BoxStatus::Detached.can_stop());
         assert!(!BoxStatus::Stopping.can_stop());
         assert!(!BoxStatus::Stopped.can_stop());
         assert!(!BoxStatus::Unknown.can_stop());
@@ -318,6 +420,13 @@ mod tests {
         assert!(BoxStatus::Running.can_transition_to(BoxStatus::Stopping));
         assert!(BoxStatus::Running.can_transition_to(BoxStatus::Stopped));
         assert!(!BoxStatus::Running.can_transition_to(BoxStatus::Configured));
+        assert!(BoxStatus::Running.can_transition_to(BoxStatus::Detached));
+
+        // Detached transitions
+        assert!(BoxStatus::Detached.can_transition_to(BoxStatus::Running));
+        assert!(BoxStatus::Detached.can_transition_to(BoxStatus::Stopped));
+        assert!(!BoxStatus::Detached.can_transition_to(BoxStatus::Configured));
+        assert!(!BoxStatus::Stopped.can_transition_to(BoxStatus::Detached));
 
         // Stopping transitions
         assert!(BoxStatus::Stopping.can_transition_to(BoxStatus::Stopped));
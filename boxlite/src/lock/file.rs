@@ -2,17 +2,26 @@
 //!
 //! This implementation uses flock(2) for actual locking and file existence
 //! for allocation tracking. It is multiprocess-safe and suitable for production use.
+//!
+//! Each lock file also carries a lease (owner PID + timestamp), refreshed on
+//! allocation and on every acquire. The OS drops the `flock` of a crashed
+//! process, but not its lock file; [`FileLockManager::reclaim_stale`] uses
+//! the lease to free such leftovers without a full `clear_all_locks`.
 
 use std::collections::HashSet;
 use std::fs::{self, File, OpenOptions};
+use std::os::unix::fs::FileExt;
 use std::os::unix::io::AsRawFd;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
+use crate::util::is_process_alive;
+
 use super::{LockId, LockManager, Locker};
-use super::{lock_already_allocated, lock_not_allocated, lock_not_found};
+use super::{lock_already_allocated, lock_exhausted, lock_not_allocated, lock_not_found};
 
 /// File-based lock manager for cross-process locking.
 ///
@@ -23,7 +32,7 @@ use super::{lock_already_allocated, lock_not_allocated, lock_not_found};
 ///
 /// ```text
 /// lock_dir/
-/// ├── 0     # Lock file for ID 0
+/// ├── 0     # Lock file for ID 0 (contents: lease "<pid> <unix-secs>")
 /// ├── 1     # Lock file for ID 1
 /// └── ...
 /// ```
@@ -43,6 +52,8 @@ pub struct FileLockManager {
     lock_dir: PathBuf,
     allocated: RwLock<HashSet<LockId>>,
     alloc_lock: Mutex<()>,
+    /// Maximum number of allocated locks (`None` = unlimited).
+    capacity: Option<u32>,
 }
 
 impl FileLockManager {
@@ -81,9 +92,21 @@ impl FileLockManager {
             lock_dir,
             allocated: RwLock::new(allocated),
             alloc_lock: Mutex::new(()),
+            capacity: None,
         })
     }
 
+    /// Create a lock manager that holds at most `capacity` locks.
+    ///
+    /// Like `InMemoryLockManager`, `allocate()` fails with an exhaustion
+    /// error once full and `available()` reports the remaining count. Useful
+    /// for simulating lock exhaustion in tests.
+    pub fn with_capacity<P: AsRef<Path>>(lock_dir: P, capacity: u32) -> BoxliteResult<Self> {
+        let mut manager = Self::new(lock_dir)?;
+        manager.capacity = Some(capacity);
+        Ok(manager)
+    }
+
     /// Open an existing lock manager directory.
     ///
     /// # Errors
@@ -107,6 +130,86 @@ impl FileLockManager {
         self.lock_dir.join(id.0.to_string())
     }
 
+    /// Fail if allocating one more lock would exceed the capacity.
+    fn check_capacity(&self) -> BoxliteResult<()> {
+        if let Some(capacity) = self.capacity
+            && self.allocated.read().unwrap().len() as u32 >= capacity
+        {
+            return Err(lock_exhausted());
+        }
+        Ok(())
+    }
+
+    /// Free locks whose lease owner is dead or whose lease is older than `max_age`.
+    ///
+    /// `in_use` holds the lock IDs persisted box records still point at
+    /// (`BoxState::lock_id`); those are never freed, since a stale lease
+    /// only means nobody acquired the lock lately. Lock files without a
+    /// readable lease are left alone too, as are locks some process still
+    /// holds via `flock`. Returns the reclaimed IDs in order.
+    pub fn reclaim_stale(
+        &self,
+        max_age: Duration,
+        in_use: &HashSet<LockId>,
+    ) -> BoxliteResult<Vec<LockId>> {
+        let _guard = self.alloc_lock.lock().unwrap();
+        let now = unix_now();
+
+        let mut ids: Vec<LockId> = self
+            .allocated
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|id| !in_use.contains(id))
+            .copied()
+            .collect();
+        ids.sort_by_key(|id| id.0);
+
+        let mut reclaimed = Vec::new();
+        for id in ids {
+            let path = self.lock_path(id);
+            let Some(lease) = fs::read_to_string(&path)
+                .ok()
+                .and_then(|s| Lease::parse(&s))
+            else {
+                continue;
+            };
+
+            let owner_dead = !is_process_alive(lease.pid);
+            let expired = now.saturating_sub(lease.acquired_at) > max_age.as_secs();
+            if !owner_dead && !expired {
+                continue;
+            }
+
+            // Never pull a lock out from under a live holder
+            let Ok(file) = File::open(&path) else {
+                continue;
+            };
+            if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+                continue;
+            }
+
+            fs::remove_file(&path).map_err(|e| {
+                BoxliteError::Storage(format!(
+                    "failed to remove lock file {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+            self.allocated.write().unwrap().remove(&id);
+
+            tracing::warn!(
+                lock_id = %id,
+                owner_pid = lease.pid,
+                owner_dead,
+                "Reclaimed stale lock"
+            );
+            reclaimed.push(id);
+        }
+
+        Ok(reclaimed)
+    }
+
     /// Find the next available lock ID.
     fn next_available_id(&self) -> LockId {
         let allocated = self.allocated.read().unwrap();
@@ -121,6 +224,7 @@ impl FileLockManager {
 impl LockManager for FileLockManager {
     fn allocate(&self) -> BoxliteResult<LockId> {
         let _guard = self.alloc_lock.lock().unwrap();
+        self.check_capacity()?;
 
         // Find next available ID
         let id = self.next_available_id();
@@ -145,6 +249,7 @@ impl LockManager for FileLockManager {
                 }
             })?;
 
+        write_lease(&file);
         drop(file);
 
         // Track allocation
@@ -186,6 +291,7 @@ impl LockManager for FileLockManager {
                 return Err(lock_already_allocated(id));
             }
         }
+        self.check_capacity()?;
 
         let path = self.lock_path(id);
 
@@ -207,6 +313,8 @@ impl LockManager for FileLockManager {
                 }
             })?;
 
+        write_lease(&file);
+
         // Track allocation
         self.allocated.write().unwrap().insert(id);
 
@@ -255,8 +363,11 @@ impl LockManager for FileLockManager {
     }
 
     fn available(&self) -> BoxliteResult<Option<u32>> {
-        // File-based locks have no inherent limit
-        Ok(None)
+        // File-based locks have no inherent limit unless a capacity is set
+        let allocated = self.allocated.read().unwrap().len() as u32;
+        Ok(self
+            .capacity
+            .map(|capacity| capacity.saturating_sub(allocated)))
     }
 
     fn allocated_count(&self) -> BoxliteResult<u32> {
@@ -288,6 +399,52 @@ impl LockManager for FileLockManager {
     }
 }
 
+/// Lease recorded in a lock file: who last took it, and when.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Lease {
+    pid: u32,
+    /// Seconds since the Unix epoch
+    acquired_at: u64,
+}
+
+impl Lease {
+    fn current() -> Self {
+        Self {
+            pid: std::process::id(),
+            acquired_at: unix_now(),
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.split_whitespace();
+        let pid = parts.next()?.parse().ok()?;
+        let acquired_at = parts.next()?.parse().ok()?;
+        Some(Self { pid, acquired_at })
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Stamp the current process's lease into a lock file.
+///
+/// Best effort: the lease only feeds [`FileLockManager::reclaim_stale`], so
+/// a failed write must not fail the lock operation itself.
+fn write_lease(file: &File) {
+    let lease = Lease::current();
+    let contents = format!("{} {}\n", lease.pid, lease.acquired_at);
+    if let Err(e) = file
+        .set_len(0)
+        .and_then(|_| file.write_all_at(contents.as_bytes(), 0))
+    {
+        tracing::debug!(error = %e, "Failed to write lock lease");
+    }
+}
+
 /// A file-based lock using flock(2).
 struct FileLock {
     id: LockId,
@@ -305,6 +462,7 @@ impl Locker for FileLock {
         if result != 0 {
             panic!("flock(LOCK_EX) failed: {}", std::io::Error::last_os_error());
         }
+        write_lease(&self.file);
     }
 
     fn unlock(&self) {
@@ -318,6 +476,9 @@ impl Locker for FileLock {
     fn try_lock(&self) -> bool {
         let fd = self.file.as_raw_fd();
         let result = unsafe { libc::flock(fd, libc::LOCK_EX | libc::LOCK_NB) };
+        if result == 0 {
+            write_lease(&self.file);
+        }
         result == 0
     }
 }
@@ -489,6 +650,106 @@ mod tests {
         assert_ne!(id, LockId(42));
     }
 
+    #[test]
+    fn test_capacity_limits_allocation() {
+        let temp_dir = TempDir::new().expect("create temp dir");
+        let manager = FileLockManager::with_capacity(temp_dir.path().join("locks"), 2).unwrap();
+
+        assert_eq!(manager.available().unwrap(), Some(2));
+        let id1 = manager.allocate().unwrap();
+        assert_eq!(manager.available().unwrap(), Some(1));
+        let _id2 = manager.allocate().unwrap();
+        assert_eq!(manager.available().unwrap(), Some(0));
+
+        let err = manager.allocate().unwrap_err();
+        assert_eq!(err.to_string(), lock_exhausted().to_string());
+        assert!(manager.allocate_and_retrieve(LockId(7)).is_err());
+
+        // Freeing a lock makes room again
+        manager.free(id1).unwrap();
+        assert_eq!(manager.available().unwrap(), Some(1));
+        manager.allocate().unwrap();
+    }
+
+    #[test]
+    fn test_unlimited_by_default() {
+        let (manager, _temp) = create_test_manager();
+        manager.allocate().unwrap();
+        assert_eq!(manager.available().unwrap(), None);
+    }
+
+    #[test]
+    fn test_allocate_writes_lease() {
+        let (manager, _temp) = create_test_manager();
+
+        let id = manager.allocate().unwrap();
+        let lease = Lease::parse(&fs::read_to_string(manager.lock_path(id)).unwrap()).unwrap();
+        assert_eq!(lease.pid, std::process::id());
+    }
+
+    #[test]
+    fn test_reclaim_stale_dead_owner() {
+        let (manager, _temp) = create_test_manager();
+        let live = manager.allocate().unwrap();
+        let stale = manager.allocate().unwrap();
+
+        // A PID that has exited and been reaped
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let dead_pid = child.id();
+        child.wait().unwrap();
+        fs::write(
+            manager.lock_path(stale),
+            format!("{} {}\n", dead_pid, unix_now()),
+        )
+        .unwrap();
+
+        let reclaimed = manager
+            .reclaim_stale(Duration::from_secs(3600), &HashSet::new())
+            .unwrap();
+        assert_eq!(reclaimed, vec![stale]);
+        assert!(!manager.lock_path(stale).exists());
+        assert!(manager.lock_path(live).exists());
+        assert_eq!(manager.allocated_count().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_reclaim_stale_expired_lease_skips_held_lock() {
+        let (manager, _temp) = create_test_manager();
+        let old = format!("{} {}\n", std::process::id(), unix_now() - 7200);
+
+        let idle = manager.allocate().unwrap();
+        fs::write(manager.lock_path(idle), &old).unwrap();
+
+        let held = manager.allocate().unwrap();
+        let lock = manager.retrieve(held).unwrap();
+        lock.lock();
+        fs::write(manager.lock_path(held), &old).unwrap();
+
+        let reclaimed = manager
+            .reclaim_stale(Duration::from_secs(60), &HashSet::new())
+            .unwrap();
+        assert_eq!(reclaimed, vec![idle]);
+        assert!(manager.lock_path(held).exists());
+        lock.unlock();
+    }
+
+    #[test]
+    fn test_reclaim_stale_skips_referenced_locks() {
+        let (manager, _temp) = create_test_manager();
+        let old = format!("{} {}\n", std::process::id(), unix_now() - 7200);
+
+        let referenced = manager.allocate().unwrap();
+        fs::write(manager.lock_path(referenced), &old).unwrap();
+        let orphan = manager.allocate().unwrap();
+        fs::write(manager.lock_path(orphan), &old).unwrap();
+
+        let reclaimed = manager
+            .reclaim_stale(Duration::from_secs(60), &HashSet::from([referenced]))
+            .unwrap();
+        assert_eq!(reclaimed, vec![orphan]);
+        assert!(manager.lock_path(referenced).exists());
+    }
+
     #[test]
     fn test_free_all() {
         let (manager, _temp) = create_test_manager();
@@ -16,6 +16,10 @@ pub struct BoxMetricsStorage {
     pub(crate) bytes_sent: AtomicU64,
     /// Bytes received from this box (via stdout/stderr)
     pub(crate) bytes_received: AtomicU64,
+    /// Cumulative bytes read from this box's disks (updated on each metrics sample)
+    pub(crate) disk_read_bytes: AtomicU64,
+    /// Cumulative bytes written to this box's disks (updated on each metrics sample)
+    pub(crate) disk_write_bytes: AtomicU64,
 
     // Timing metrics (set once, never change)
     /// Total time from create() call to LiteBox ready (includes all stages)
@@ -45,6 +49,8 @@ impl Clone for BoxMetricsStorage {
             exec_errors: AtomicU64::new(self.exec_errors.load(Ordering::Relaxed)),
             bytes_sent: AtomicU64::new(self.bytes_sent.load(Ordering::Relaxed)),
             bytes_received: AtomicU64::new(self.bytes_received.load(Ordering::Relaxed)),
+            disk_read_bytes: AtomicU64::new(self.disk_read_bytes.load(Ordering::Relaxed)),
+            disk_write_bytes: AtomicU64::new(self.disk_write_bytes.load(Ordering::Relaxed)),
             total_create_duration_ms: self.total_create_duration_ms,
             guest_boot_duration_ms: self.guest_boot_duration_ms,
             stage_filesystem_setup_ms: self.stage_filesystem_setup_ms,
@@ -140,13 +146,25 @@ impl BoxMetricsStorage {
     pub(crate) fn add_bytes_received(&self, bytes: u64) {
         self.bytes_received.fetch_add(bytes, Ordering::Relaxed);
     }
+
+    /// Record cumulative disk I/O reported by the VMM.
+    ///
+    /// Sources report running totals, so counters only move forward.
+    pub(crate) fn record_disk_io(&self, read_bytes: Option<u64>, write_bytes: Option<u64>) {
+        if let Some(bytes) = read_bytes {
+            self.disk_read_bytes.fetch_max(bytes, Ordering::Relaxed);
+        }
+        if let Some(bytes) = write_bytes {
+            self.disk_write_bytes.fetch_max(bytes, Ordering::Relaxed);
+        }
+    }
 }
 
 /// Handle for querying per-box metrics.
 ///
 /// Snapshot of metrics at query time.
 /// All counters are monotonic and never reset.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct BoxMetrics {
     /// Commands executed on this box
     pub commands_executed_total: u64,
@@ -164,6 +182,16 @@ pub struct BoxMetrics {
     pub cpu_percent: Option<f32>,
     /// Memory usage in bytes
     pub memory_bytes: Option<u64>,
+    /// Mean CPU usage percent over the background sampler's window
+    #[serde(default)]
+    pub cpu_percent_avg: Option<f32>,
+    /// Mean memory usage in bytes over the background sampler's window
+    #[serde(default)]
+    pub memory_bytes_avg: Option<u64>,
+    /// Bytes read from the box's disks
+    pub disk_read_bytes: Option<u64>,
+    /// Bytes written to the box's disks
+    pub disk_write_bytes: Option<u64>,
     /// Network bytes sent (host to guest)
     pub network_bytes_sent: Option<u64>,
     /// Network bytes received (guest to host)
@@ -172,6 +200,10 @@ pub struct BoxMetrics {
     pub network_tcp_connections: Option<u64>,
     /// Total TCP connection errors
     pub network_tcp_errors: Option<u64>,
+    /// Bytes received by the box over the network (host to guest)
+    pub net_rx_bytes: Option<u64>,
+    /// Bytes transmitted by the box over the network (guest to host)
+    pub net_tx_bytes: Option<u64>,
 
     // Stage-level timing breakdown
     /// Time to create box directory structure (milliseconds)
@@ -190,10 +222,16 @@ pub struct BoxMetrics {
 
 impl BoxMetrics {
     /// Create snapshot from storage and system metrics.
+    ///
+    /// Disk counters come from `storage`; `disk_read_bytes`/`disk_write_bytes`
+    /// only indicate whether the current source can report them.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn from_storage(
         storage: &BoxMetricsStorage,
         cpu_percent: Option<f32>,
         memory_bytes: Option<u64>,
+        disk_read_bytes: Option<u64>,
+        disk_write_bytes: Option<u64>,
         network_bytes_sent: Option<u64>,
         network_bytes_received: Option<u64>,
         network_tcp_connections: Option<u64>,
@@ -208,10 +246,18 @@ impl BoxMetrics {
             guest_boot_duration_ms: storage.guest_boot_duration_ms,
             cpu_percent,
             memory_bytes,
+            cpu_percent_avg: None,
+            memory_bytes_avg: None,
+            disk_read_bytes: disk_read_bytes
+                .map(|_| storage.disk_read_bytes.load(Ordering::Relaxed)),
+            disk_write_bytes: disk_write_bytes
+                .map(|_| storage.disk_write_bytes.load(Ordering::Relaxed)),
             network_bytes_sent,
             network_bytes_received,
             network_tcp_connections,
             network_tcp_errors,
+            net_rx_bytes: network_bytes_sent,
+            net_tx_bytes: network_bytes_received,
             stage_filesystem_setup_ms: storage.stage_filesystem_setup_ms,
             stage_image_prepare_ms: storage.stage_image_prepare_ms,
             stage_guest_rootfs_ms: storage.stage_guest_rootfs_ms,
@@ -282,6 +328,38 @@ impl BoxMetrics {
         self.memory_bytes
     }
 
+    /// Mean CPU usage percent over the last samples taken by the background
+    /// sampler (up to 60).
+    ///
+    /// Returns None unless `BoxliteOptions::metrics_interval` is set and a
+    /// sample has been taken.
+    pub fn cpu_percent_avg(&self) -> Option<f32> {
+        self.cpu_percent_avg
+    }
+
+    /// Mean memory usage in bytes over the last samples taken by the
+    /// background sampler (up to 60).
+    ///
+    /// Returns None unless `BoxliteOptions::metrics_interval` is set and a
+    /// sample has been taken.
+    pub fn memory_bytes_avg(&self) -> Option<u64> {
+        self.memory_bytes_avg
+    }
+
+    /// Total bytes read from the box's disks.
+    ///
+    /// Returns None if disk I/O accounting is unavailable on this platform.
+    pub fn disk_read_bytes(&self) -> Option<u64> {
+        self.disk_read_bytes
+    }
+
+    /// Total bytes written to the box's disks.
+    ///
+    /// Returns None if disk I/O accounting is unavailable on this platform.
+    pub fn disk_write_bytes(&self) -> Option<u64> {
+        self.disk_write_bytes
+    }
+
     /// Network bytes sent from host to guest.
     ///
     /// Returns None if network backend doesn't support metrics.
@@ -310,6 +388,20 @@ impl BoxMetrics {
         self.network_tcp_errors
     }
 
+    /// Bytes received by the box over the network (host to guest).
+    ///
+    /// Returns None if network backend doesn't support metrics.
+    pub fn net_rx_bytes(&self) -> Option<u64> {
+        self.net_rx_bytes
+    }
+
+    /// Bytes transmitted by the box over the network (guest to host).
+    ///
+    /// Returns None if network backend doesn't support metrics.
+    pub fn net_tx_bytes(&self) -> Option<u64> {
+        self.net_tx_bytes
+    }
+
     // Stage-level timing getters
 
     /// Time to create box directory structure (milliseconds).
@@ -365,3 +457,56 @@ impl BoxMetrics {
         self.stage_container_init_ms
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_box_metrics_serde_round_trip() {
+        let storage = BoxMetricsStorage::new();
+        storage.record_disk_io(Some(4096), Some(8192));
+        let metrics = BoxMetrics::from_storage(
+            &storage,
+            Some(12.5),
+            Some(64 * 1024 * 1024),
+            Some(0),
+            Some(0),
+            Some(1500),
+            Some(900),
+            Some(3),
+            Some(0),
+        );
+        assert_eq!(metrics.net_rx_bytes(), Some(1500));
+        assert_eq!(metrics.net_tx_bytes(), Some(900));
+
+        let json = serde_json::to_value(&metrics).unwrap();
+        assert_eq!(json["net_rx_bytes"], 1500);
+        assert_eq!(json["net_tx_bytes"], 900);
+
+        let decoded: BoxMetrics = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.net_rx_bytes, Some(1500));
+        assert_eq!(decoded.net_tx_bytes, Some(900));
+        assert_eq!(decoded.disk_read_bytes, Some(4096));
+        assert_eq!(decoded.disk_write_bytes, Some(8192));
+        assert_eq!(decoded.cpu_percent, Some(12.5));
+    }
+
+    #[test]
+    fn test_box_metrics_network_unavailable() {
+        let metrics = BoxMetrics::from_storage(
+            &BoxMetricsStorage::new(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let json = serde_json::to_value(&metrics).unwrap();
+        assert!(json["net_rx_bytes"].is_null());
+        assert!(json["net_tx_bytes"].is_null());
+    }
+}
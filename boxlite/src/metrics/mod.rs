@@ -9,6 +9,9 @@
 //! All counters are monotonic (never decrease). Delta calculation is the caller's
 //! responsibility. Future `boxlite-metrics` crate may provide helpers (deferred).
 //!
+//! Gauges such as CPU usage can optionally be sampled in the background by
+//! setting `BoxliteOptions::metrics_interval` (see `sampler`).
+//!
 //! # Example
 //!
 //! ```rust,no_run
@@ -32,6 +35,8 @@
 
 mod box_metrics;
 mod runtime_metrics;
+mod sampler;
 
 pub use box_metrics::{BoxMetrics, BoxMetricsStorage};
 pub use runtime_metrics::{RuntimeMetrics, RuntimeMetricsStorage};
+pub(crate) use sampler::MetricsWindow;
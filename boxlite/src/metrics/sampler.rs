@@ -0,0 +1,133 @@
+//! Rolling window of periodically sampled VMM metrics.
+//!
+//! Instantaneous reads such as CPU usage are only meaningful as a delta
+//! between two observations. When `BoxliteOptions::metrics_interval` is set,
+//! the runtime samples every running box on a fixed cadence and keeps the
+//! most recent observations here, so `BoxMetrics` can report the value
+//! computed over the last interval instead of over an arbitrary gap, along
+//! with averages over the whole window.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::vmm::controller::VmmMetrics;
+
+/// Number of samples retained per box.
+pub(crate) const SAMPLE_WINDOW_CAPACITY: usize = 60;
+
+/// A single sampled observation.
+#[derive(Clone, Debug)]
+pub(crate) struct MetricsSample {
+    /// When the sample was taken
+    pub(crate) at: Instant,
+    /// Raw metrics reported by the VMM handler
+    pub(crate) metrics: VmmMetrics,
+}
+
+/// Bounded, oldest-first window of metric samples.
+#[derive(Debug, Default)]
+pub(crate) struct MetricsWindow {
+    samples: Mutex<VecDeque<MetricsSample>>,
+}
+
+impl MetricsWindow {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a sample, evicting the oldest once the window is full.
+    pub(crate) fn push(&self, metrics: VmmMetrics) {
+        let mut samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        if samples.len() == SAMPLE_WINDOW_CAPACITY {
+            samples.pop_front();
+        }
+        samples.push_back(MetricsSample {
+            at: Instant::now(),
+            metrics,
+        });
+    }
+
+    /// Most recent sample, if any has been taken.
+    pub(crate) fn latest(&self) -> Option<MetricsSample> {
+        let samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        samples.back().cloned()
+    }
+
+    /// Mean CPU percent and memory bytes over the retained samples.
+    ///
+    /// Samples that did not report a value are left out of its mean; `None`
+    /// when no sample did.
+    pub(crate) fn averages(&self) -> (Option<f32>, Option<u64>) {
+        let samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        let cpu: Vec<f32> = samples
+            .iter()
+            .filter_map(|s| s.metrics.cpu_percent)
+            .collect();
+        let memory: Vec<u64> = samples
+            .iter()
+            .filter_map(|s| s.metrics.memory_bytes)
+            .collect();
+
+        let cpu_avg = (!cpu.is_empty()).then(|| cpu.iter().sum::<f32>() / cpu.len() as f32);
+        let memory_avg =
+            (!memory.is_empty()).then(|| memory.iter().sum::<u64>() / memory.len() as u64);
+        (cpu_avg, memory_avg)
+    }
+
+    /// Number of samples currently retained.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.samples.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(cpu: f32) -> VmmMetrics {
+        VmmMetrics {
+            cpu_percent: Some(cpu),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_window_latest_tracks_last_push() {
+        let window = MetricsWindow::new();
+        assert!(window.latest().is_none());
+
+        window.push(sample(1.0));
+        window.push(sample(2.0));
+        assert_eq!(window.latest().unwrap().metrics.cpu_percent, Some(2.0));
+    }
+
+    #[test]
+    fn test_window_averages() {
+        let window = MetricsWindow::new();
+        assert_eq!(window.averages(), (None, None));
+
+        window.push(sample(10.0));
+        window.push(VmmMetrics {
+            cpu_percent: Some(30.0),
+            memory_bytes: Some(4096),
+            ..Default::default()
+        });
+        window.push(VmmMetrics::default());
+        assert_eq!(window.averages(), (Some(20.0), Some(4096)));
+    }
+
+    #[test]
+    fn test_window_evicts_oldest() {
+        let window = MetricsWindow::new();
+        for i in 0..(SAMPLE_WINDOW_CAPACITY + 5) {
+            window.push(sample(i as f32));
+        }
+        assert_eq!(window.len(), SAMPLE_WINDOW_CAPACITY);
+        assert_eq!(
+            window.latest().unwrap().metrics.cpu_percent,
+            Some((SAMPLE_WINDOW_CAPACITY + 4) as f32)
+        );
+    }
+}
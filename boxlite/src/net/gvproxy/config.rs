@@ -21,6 +21,13 @@ pub struct PortMapping {
     pub host_port: u16,
     /// Guest port to forward to
     pub guest_port: u16,
+    /// Transport protocol ("tcp" or "udp")
+    #[serde(default = "default_mapping_protocol")]
+    pub protocol: String,
+}
+
+fn default_mapping_protocol() -> String {
+    "tcp".to_string()
 }
 
 /// Network configuration for gvproxy instance
@@ -109,6 +116,7 @@ impl GvproxyConfig {
                 .map(|(host_port, guest_port)| PortMapping {
                     host_port,
                     guest_port,
+                    protocol: default_mapping_protocol(),
                 })
                 .collect(),
             ..Default::default()
@@ -171,10 +179,53 @@ impl GvproxyConfig {
     }
 }
 
+impl From<&crate::net::NetworkBackendConfig> for GvproxyConfig {
+    /// Build a gvproxy config from the backend-agnostic network config.
+    ///
+    /// The guest MAC/IP are applied to gvproxy's DHCP static lease so the
+    /// guest keeps a predictable address.
+    fn from(config: &crate::net::NetworkBackendConfig) -> Self {
+        let mut gvproxy = Self::new(Vec::new());
+        gvproxy.port_mappings = config
+            .port_forwards
+            .iter()
+            .map(|forward| PortMapping {
+                host_port: forward.host_port,
+                guest_port: forward.guest_port,
+                protocol: forward.protocol.as_str().to_string(),
+            })
+            .collect();
+        gvproxy.guest_mac = crate::net::constants::mac_to_string(&config.guest_mac);
+        if let Some((ip, _)) = config.guest_ip {
+            gvproxy.guest_ip = ip.to_string();
+        }
+        gvproxy
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_from_backend_config_applies_guest_addressing() {
+        use crate::runtime::options::{PortForward, PortProtocol};
+
+        let backend = crate::net::NetworkBackendConfig::new(vec![
+            PortForward::tcp(8080, 80),
+            PortForward::new(5353, 53, PortProtocol::Udp),
+        ])
+        .with_guest_mac([0x02, 0x11, 0x22, 0x33, 0x44, 0x55])
+        .with_guest_ip("192.168.127.50".parse().unwrap(), 24);
+
+        let config = GvproxyConfig::from(&backend);
+        assert_eq!(config.guest_mac, "02:11:22:33:44:55");
+        assert_eq!(config.guest_ip, "192.168.127.50");
+        assert_eq!(config.port_mappings.len(), 2);
+        assert_eq!(config.port_mappings[0].protocol, "tcp");
+        assert_eq!(config.port_mappings[1].protocol, "udp");
+    }
+
     #[test]
     fn test_default_config() {
         let config = GvproxyConfig::default();
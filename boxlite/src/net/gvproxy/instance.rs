@@ -78,13 +78,19 @@ impl GvproxyInstance {
     /// # Ok::<(), boxlite_shared::errors::BoxliteError>(())
     /// ```
     pub fn new(port_mappings: &[(u16, u16)]) -> BoxliteResult<Self> {
+        // Create config with defaults + port mappings
+        Self::with_config(super::config::GvproxyConfig::new(port_mappings.to_vec()))
+    }
+
+    /// Create a new gvproxy instance from a full configuration
+    ///
+    /// Use this instead of [`new`](Self::new) to override guest addressing,
+    /// DNS zones or other defaults.
+    pub fn with_config(config: super::config::GvproxyConfig) -> BoxliteResult<Self> {
         // Initialize logging callback (one-time setup)
         // This ensures all gvproxy logs are routed to Rust's tracing system
         logging::init_logging();
 
-        // Create config with defaults + port mappings
-        let config = super::config::GvproxyConfig::new(port_mappings.to_vec());
-
         // Create instance via FFI with full config
         let id = ffi::create_instance(&config)?;
 
@@ -54,11 +54,13 @@
 //! ## Example Usage
 //!
 //! ```no_run
+//! use boxlite::PortForward;
 //! use boxlite::net::{NetworkBackendConfig, GvisorTapBackend, NetworkBackend};
 //!
-//! let config = NetworkBackendConfig {
-//!     port_mappings: vec![(8080, 80), (8443, 443)],
-//! };
+//! let config = NetworkBackendConfig::new(vec![
+//!     PortForward::tcp(8080, 80),
+//!     PortForward::tcp(8443, 443),
+//! ]);
 //!
 //! // Create backend - logs from gvproxy will appear in tracing
 //! let backend = GvisorTapBackend::new(config)?;
@@ -74,7 +76,7 @@ mod instance;
 mod logging;
 mod stats;
 
-use super::{ConnectionType, NetworkBackend, NetworkBackendConfig, NetworkBackendEndpoint};
+use super::{NetworkBackend, NetworkBackendConfig, NetworkBackendEndpoint};
 use boxlite_shared::errors::BoxliteResult;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -106,6 +108,8 @@ pub struct GvisorTapBackend {
     instance: Arc<GvproxyInstance>,
     /// Socket path for cross-process communication
     socket_path: PathBuf,
+    /// Configuration the backend was created with
+    config: NetworkBackendConfig,
 }
 
 impl GvisorTapBackend {
@@ -125,23 +129,25 @@ impl GvisorTapBackend {
     /// # Example
     ///
     /// ```no_run
+    /// use boxlite::PortForward;
     /// use boxlite::net::{NetworkBackendConfig, GvisorTapBackend};
     ///
-    /// let config = NetworkBackendConfig {
-    ///     port_mappings: vec![(8080, 80), (8443, 443)],
-    /// };
+    /// let config = NetworkBackendConfig::new(vec![
+    ///     PortForward::tcp(8080, 80),
+    ///     PortForward::tcp(8443, 443),
+    /// ]);
     ///
     /// let backend = GvisorTapBackend::new(config)?;
     /// # Ok::<(), boxlite_shared::errors::BoxliteError>(())
     /// ```
     pub fn new(config: NetworkBackendConfig) -> BoxliteResult<Self> {
         tracing::debug!(
-            "Creating gvisor-tap-vsock backend with port mappings: {:?}",
-            config.port_mappings
+            "Creating gvisor-tap-vsock backend with port forwards: {:?}",
+            config.port_forwards
         );
 
-        // Create gvproxy instance with port mappings
-        let instance = Arc::new(GvproxyInstance::new(&config.port_mappings)?);
+        // Create gvproxy instance with port mappings and guest addressing
+        let instance = Arc::new(GvproxyInstance::with_config(GvproxyConfig::from(&config))?);
 
         // Start background stats logging thread
         instance::start_stats_logging(Arc::downgrade(&instance));
@@ -158,6 +164,7 @@ impl GvisorTapBackend {
         Ok(Self {
             instance,
             socket_path,
+            config,
         })
     }
 
@@ -173,11 +180,10 @@ impl GvisorTapBackend {
     /// # Example
     ///
     /// ```no_run
+    /// use boxlite::PortForward;
     /// use boxlite::net::{NetworkBackendConfig, GvisorTapBackend};
     ///
-    /// let config = NetworkBackendConfig {
-    ///     port_mappings: vec![(8080, 80)],
-    /// };
+    /// let config = NetworkBackendConfig::new(vec![PortForward::tcp(8080, 80)]);
     /// let backend = GvisorTapBackend::new(config)?;
     ///
     /// // Get stats
@@ -193,23 +199,8 @@ impl GvisorTapBackend {
 
 impl NetworkBackend for GvisorTapBackend {
     fn endpoint(&self) -> BoxliteResult<NetworkBackendEndpoint> {
-        // Platform-specific connection type
-        // macOS: UnixDgram with VFKit protocol (SOCK_DGRAM)
-        // Linux: UnixStream with Qemu protocol (SOCK_STREAM)
-        let connection_type = if cfg!(target_os = "macos") {
-            ConnectionType::UnixDgram
-        } else {
-            ConnectionType::UnixStream
-        };
-
-        // Use GUEST_MAC constant - this must match the DHCP static lease in gvproxy config
-        use crate::net::constants::GUEST_MAC;
-
-        Ok(NetworkBackendEndpoint::UnixSocket {
-            path: self.socket_path.clone(),
-            connection_type,
-            mac_address: GUEST_MAC,
-        })
+        // The MAC must match the DHCP static lease in the gvproxy config
+        Ok(self.config.unix_endpoint(self.socket_path.clone()))
     }
 
     fn name(&self) -> &'static str {
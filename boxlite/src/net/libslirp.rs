@@ -11,6 +11,7 @@
 //! - Requires libslirp-helper binary in PATH
 
 use super::{NetworkBackend, NetworkBackendConfig, NetworkBackendEndpoint};
+use crate::runtime::options::PortForward;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use std::os::unix::io::{AsRawFd, RawFd};
 use std::os::unix::net::UnixStream;
@@ -21,9 +22,9 @@ use std::process::{Child, Command};
 /// This backend spawns a libslirp-helper process and communicates via Unix sockets.
 #[derive(Debug)]
 pub struct LibslirpBackend {
-    /// Port mappings: (host_port, guest_port)
+    /// Host-to-guest port forwards
     #[allow(dead_code)]
-    port_mappings: Vec<(u16, u16)>,
+    port_forwards: Vec<PortForward>,
 
     /// The socket file descriptor for communication with libslirp
     #[allow(dead_code)]
@@ -50,7 +51,7 @@ impl LibslirpBackend {
     /// - Helper process fails to start
     pub fn new(config: NetworkBackendConfig) -> BoxliteResult<Self> {
         tracing::info!(
-            port_count = config.port_mappings.len(),
+            port_count = config.port_forwards.len(),
             "Initializing libslirp backend"
         );
 
@@ -75,13 +76,18 @@ impl LibslirpBackend {
         helper_args.push(format!("--fd={}", guest_fd));
 
         // Add port forwarding configuration
-        for (host_port, guest_port) in &config.port_mappings {
-            let forward_spec = format!("tcp:127.0.0.1:{}::{}:tcp", host_port, guest_port);
+        for forward in &config.port_forwards {
+            let proto = forward.protocol.as_str();
+            let forward_spec = format!(
+                "{}:127.0.0.1:{}::{}:{}",
+                proto, forward.host_port, forward.guest_port, proto
+            );
             helper_args.push(format!("--forward={}", forward_spec));
 
             tracing::info!(
-                host_port = host_port,
-                guest_port = guest_port,
+                host_port = forward.host_port,
+                guest_port = forward.guest_port,
+                protocol = proto,
                 "Configuring libslirp port forwarding"
             );
         }
@@ -105,7 +111,7 @@ impl LibslirpBackend {
         std::mem::forget(guest_socket);
 
         Ok(Self {
-            port_mappings: config.port_mappings,
+            port_forwards: config.port_forwards,
             socket_fd: host_fd,
             helper_process: Some(helper_process),
         })
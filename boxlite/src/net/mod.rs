@@ -7,8 +7,10 @@
 //! When no backend is configured (None), the engine uses its default net
 //! implementation.
 
-use boxlite_shared::errors::BoxliteResult;
-use std::path::PathBuf;
+use crate::runtime::options::{PortForward, PortProtocol};
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
 
 pub mod constants;
 
@@ -28,7 +30,11 @@ pub use gvproxy::GvisorTapBackend;
 ///
 /// This represents the connection information that needs to be passed to the engine.
 /// Different backends provide different connection methods that the engine must handle.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+///
+/// Serialized with an explicit `type` tag (`"unix_socket"` / `"tcp"`) so the
+/// representation passed to the shim subprocess stays stable as variants are added.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum NetworkBackendEndpoint {
     /// Path to a Unix socket to connect to.
     /// The path can be passed across process boundaries via JSON.
@@ -40,6 +46,15 @@ pub enum NetworkBackendEndpoint {
         /// This must match the DHCP static lease configured in the network backend
         mac_address: [u8; 6],
     },
+
+    /// TCP address of a network backend, e.g. on a remote hypervisor host.
+    /// Only stream framing is supported over TCP (`ConnectionType::UnixStream`).
+    Tcp {
+        addr: SocketAddr,
+        connection_type: ConnectionType,
+        /// MAC address for the guest network interface
+        mac_address: [u8; 6],
+    },
 }
 
 /// Configuration for network backend initialization.
@@ -48,20 +63,97 @@ pub enum NetworkBackendEndpoint {
 /// to know which backend will be used.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct NetworkBackendConfig {
-    /// Port mappings: (host_port, guest_port)
-    pub port_mappings: Vec<(u16, u16)>,
+    /// Host-to-guest port forwards
+    pub port_forwards: Vec<PortForward>,
+    /// MAC address of the guest interface (DHCP static lease key)
+    #[serde(default = "default_guest_mac")]
+    pub guest_mac: [u8; 6],
+    /// Static guest IPv4 address and prefix length, if overridden
+    #[serde(default)]
+    pub guest_ip: Option<(Ipv4Addr, u8)>,
+}
+
+fn default_guest_mac() -> [u8; 6] {
+    constants::GUEST_MAC
 }
 
 impl NetworkBackendConfig {
-    pub fn new(port_mappings: Vec<(u16, u16)>) -> Self {
-        Self { port_mappings }
+    pub fn new(port_forwards: Vec<PortForward>) -> Self {
+        Self {
+            port_forwards,
+            guest_mac: default_guest_mac(),
+            guest_ip: None,
+        }
+    }
+
+    /// Override the guest MAC address.
+    pub fn with_guest_mac(mut self, mac: [u8; 6]) -> Self {
+        self.guest_mac = mac;
+        self
+    }
+
+    /// Assign a static guest IPv4 address with prefix length.
+    pub fn with_guest_ip(mut self, ip: Ipv4Addr, prefix: u8) -> Self {
+        self.guest_ip = Some((ip, prefix));
+        self
+    }
+
+    /// Build the Unix socket endpoint for a backend listening on `path`.
+    ///
+    /// macOS uses SOCK_DGRAM (VFKit protocol), Linux uses SOCK_STREAM (Qemu protocol).
+    pub fn unix_endpoint(&self, path: PathBuf) -> NetworkBackendEndpoint {
+        let connection_type = if cfg!(target_os = "macos") {
+            ConnectionType::UnixDgram
+        } else {
+            ConnectionType::UnixStream
+        };
+
+        NetworkBackendEndpoint::UnixSocket {
+            path,
+            connection_type,
+            mac_address: self.guest_mac,
+        }
     }
 }
 
+/// Verify every forward's host port can be bound on this host.
+///
+/// Another box (or any other process) already listening on the port would
+/// make the backend's forward fail silently, so this is checked before the
+/// box starts. Duplicate host ports within `forwards` are also rejected.
+pub fn check_host_ports(forwards: &[PortForward]) -> BoxliteResult<()> {
+    let mut seen = std::collections::HashSet::new();
+    for forward in forwards {
+        if !seen.insert((forward.host_port, forward.protocol)) {
+            return Err(BoxliteError::Network(format!(
+                "host port {}/{} is forwarded more than once",
+                forward.host_port, forward.protocol
+            )));
+        }
+
+        let addr = SocketAddr::from(([0, 0, 0, 0], forward.host_port));
+        let result = match forward.protocol {
+            PortProtocol::Tcp => std::net::TcpListener::bind(addr).map(drop),
+            PortProtocol::Udp => std::net::UdpSocket::bind(addr).map(drop),
+        };
+        if let Err(e) = result {
+            return Err(BoxliteError::Network(format!(
+                "host port {}/{} is unavailable: {}",
+                forward.host_port, forward.protocol, e
+            )));
+        }
+    }
+    Ok(())
+}
+
 /// Network metrics from a network backend.
 ///
 /// Contains bandwidth counters and connection statistics.
-#[derive(Debug, Clone, Default)]
+///
+/// The backend lives in the shim subprocess, so the shim periodically
+/// publishes these to a JSON file in the box directory (see
+/// [`write_to`](Self::write_to)) for the host to read.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct NetworkMetrics {
     /// Total bytes sent from host to guest
     pub bytes_sent: u64,
@@ -73,6 +165,25 @@ pub struct NetworkMetrics {
     pub tcp_connection_errors: Option<u64>,
 }
 
+impl NetworkMetrics {
+    /// Publish metrics to `path`, overwriting any previous snapshot.
+    ///
+    /// Written in place (no rename) so it works under the shim's seccomp
+    /// filter; readers may see a torn write and should treat it as a miss.
+    pub fn write_to(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_vec(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)
+    }
+
+    /// Read metrics previously published with [`write_to`](Self::write_to).
+    ///
+    /// Returns None if the file is missing or unreadable.
+    pub fn read_from(path: &Path) -> Option<Self> {
+        let content = std::fs::read(path).ok()?;
+        serde_json::from_slice(&content).ok()
+    }
+}
+
 /// Network backend trait that all net implementations must implement.
 ///
 /// This trait allows engines to work with any network backend without knowing
@@ -99,7 +210,7 @@ pub trait NetworkBackend: Send + Sync + std::fmt::Debug {
 }
 
 /// The protocol type for network connections.
-#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum ConnectionType {
     /// Unix stream socket (SOCK_STREAM) - used by passt, socket_vmnet, libslirp, gvproxy (Linux)
     UnixStream,
@@ -150,3 +261,115 @@ impl NetworkBackendFactory {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tcp_endpoint_serde_round_trip() {
+        let endpoint = NetworkBackendEndpoint::Tcp {
+            addr: "192.168.1.10:7777".parse().unwrap(),
+            connection_type: ConnectionType::UnixStream,
+            mac_address: constants::GUEST_MAC,
+        };
+
+        let json = serde_json::to_value(&endpoint).unwrap();
+        assert_eq!(json["type"], "tcp");
+        assert_eq!(json["addr"], "192.168.1.10:7777");
+
+        let decoded: NetworkBackendEndpoint = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, endpoint);
+    }
+
+    #[test]
+    fn test_config_mac_propagates_to_endpoint() {
+        let mac = [0x02, 0x00, 0x00, 0x00, 0x00, 0x42];
+        let config = NetworkBackendConfig::new(vec![]).with_guest_mac(mac);
+
+        match config.unix_endpoint(PathBuf::from("/tmp/net.sock")) {
+            NetworkBackendEndpoint::UnixSocket { mac_address, .. } => {
+                assert_eq!(mac_address, mac)
+            }
+            other => panic!("unexpected endpoint: {:?}", other),
+        }
+
+        let default = NetworkBackendConfig::new(vec![]);
+        match default.unix_endpoint(PathBuf::from("/tmp/net.sock")) {
+            NetworkBackendEndpoint::UnixSocket { mac_address, .. } => {
+                assert_eq!(mac_address, constants::GUEST_MAC)
+            }
+            other => panic!("unexpected endpoint: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_config_serde_defaults_guest_addressing() {
+        let config: NetworkBackendConfig =
+            serde_json::from_str(r#"{"port_forwards":[{"host_port":8080,"guest_port":80}]}"#)
+                .unwrap();
+        assert_eq!(config.guest_mac, constants::GUEST_MAC);
+        assert_eq!(config.guest_ip, None);
+        assert_eq!(config.port_forwards, vec![PortForward::tcp(8080, 80)]);
+
+        let config =
+            NetworkBackendConfig::new(vec![]).with_guest_ip(Ipv4Addr::new(10, 0, 0, 5), 16);
+        let json = serde_json::to_string(&config).unwrap();
+        let decoded: NetworkBackendConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.guest_ip, Some((Ipv4Addr::new(10, 0, 0, 5), 16)));
+    }
+
+    #[test]
+    fn test_check_host_ports_conflict_between_boxes() {
+        // First box's backend is already listening on its forwarded port
+        let first_box = std::net::TcpListener::bind("0.0.0.0:0").unwrap();
+        let taken = first_box.local_addr().unwrap().port();
+
+        // Second box asking for the same host port conflicts
+        let err = check_host_ports(&[PortForward::tcp(taken, 80)]).unwrap_err();
+        assert!(matches!(err, BoxliteError::Network(_)), "got {:?}", err);
+
+        // A distinct host port is fine
+        let free = std::net::TcpListener::bind("0.0.0.0:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        assert!(check_host_ports(&[PortForward::tcp(free, 80)]).is_ok());
+    }
+
+    #[test]
+    fn test_check_host_ports_rejects_duplicates() {
+        let free = std::net::TcpListener::bind("0.0.0.0:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let err = check_host_ports(&[PortForward::tcp(free, 80), PortForward::tcp(free, 81)])
+            .unwrap_err();
+        assert!(matches!(err, BoxliteError::Network(_)));
+
+        // Same port number over TCP and UDP does not collide
+        assert!(
+            check_host_ports(&[
+                PortForward::tcp(free, 53),
+                PortForward::new(free, 53, PortProtocol::Udp)
+            ])
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_unix_endpoint_serde_tag() {
+        let endpoint = NetworkBackendEndpoint::UnixSocket {
+            path: PathBuf::from("/tmp/net.sock"),
+            connection_type: ConnectionType::UnixDgram,
+            mac_address: constants::GUEST_MAC,
+        };
+
+        let json = serde_json::to_string(&endpoint).unwrap();
+        assert!(json.contains(r#""type":"unix_socket""#));
+        let decoded: NetworkBackendEndpoint = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, endpoint);
+    }
+}
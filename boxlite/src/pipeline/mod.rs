@@ -4,6 +4,7 @@
 //! - Table-driven execution plans based on state
 //! - Parallel and sequential task execution modes
 //! - Arbitrary number of stages and tasks
+//! - Progress callbacks via [`PipelineObserver`]
 //!
 //! ## Architecture
 //!
@@ -38,12 +39,14 @@
 //! ```
 
 mod metrics;
+mod observer;
 #[allow(clippy::module_inception)]
 mod pipeline;
 mod stage;
 mod task;
 
 pub use metrics::{PipelineMetrics, StageMetrics, TaskMetrics};
+pub use observer::PipelineObserver;
 pub use pipeline::{ExecutionPlan, Pipeline, PipelineBuilder, PipelineExecutor};
 pub use stage::{ExecutionMode, Stage};
 pub use task::{BoxedTask, PipelineTask};
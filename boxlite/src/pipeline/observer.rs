@@ -0,0 +1,32 @@
+//! Observer hooks for pipeline progress.
+
+use boxlite_shared::errors::BoxliteError;
+use std::time::Duration;
+
+/// Receives progress callbacks while a pipeline runs.
+///
+/// Called once per task, named by [`PipelineTask::name`](super::PipelineTask::name).
+/// Tasks of a parallel stage may report concurrently and in any order;
+/// sequential tasks and stages report in plan order. All methods default to
+/// no-ops, so implementors only override what they need.
+///
+/// For box initialization the task names are, in order: `filesystem_setup`,
+/// `container_rootfs_prep` / `guest_rootfs_init` (parallel), `vmm_spawn`,
+/// `guest_connect`, `guest_init`. Reattaching to a running box runs
+/// `vmm_attach` then `guest_connect`.
+pub trait PipelineObserver: Send + Sync {
+    /// A task is about to run.
+    fn on_stage_start(&self, _name: &str) {}
+
+    /// A task finished successfully after `duration`.
+    fn on_stage_done(&self, _name: &str, _duration: Duration) {}
+
+    /// A task failed with `err`; the pipeline stops after this stage.
+    fn on_stage_error(&self, _name: &str, _err: &BoxliteError) {}
+}
+
+impl std::fmt::Debug for dyn PipelineObserver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PipelineObserver")
+    }
+}
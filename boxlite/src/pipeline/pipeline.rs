@@ -4,6 +4,7 @@
 //! in parallel or sequential mode.
 
 use super::metrics::{PipelineMetrics, StageMetrics, TaskMetrics};
+use super::observer::PipelineObserver;
 use super::stage::{ExecutionMode, Stage};
 use super::task::BoxedTask;
 use boxlite_shared::errors::BoxliteResult;
@@ -57,6 +58,18 @@ impl PipelineExecutor {
     /// Generic over:
     /// - `Ctx`: Shared pipeline context (use interior mutability for writes)
     pub async fn execute<Ctx>(pipeline: Pipeline<Ctx>, ctx: Ctx) -> BoxliteResult<PipelineMetrics>
+    where
+        Ctx: Clone,
+    {
+        Self::execute_observed(pipeline, ctx, None).await
+    }
+
+    /// Execute a pipeline, reporting each task to `observer`.
+    pub async fn execute_observed<Ctx>(
+        pipeline: Pipeline<Ctx>,
+        ctx: Ctx,
+        observer: Option<&dyn PipelineObserver>,
+    ) -> BoxliteResult<PipelineMetrics>
     where
         Ctx: Clone,
     {
@@ -69,30 +82,16 @@ impl PipelineExecutor {
 
             let task_metrics = match execution {
                 ExecutionMode::Parallel => {
-                    let futures = stage.tasks.into_iter().map(|task| {
-                        let ctx = ctx.clone();
-                        async move {
-                            let name = task.name().to_string();
-                            let task_start = Instant::now();
-                            task.run(ctx).await?;
-                            Ok::<TaskMetrics, boxlite_shared::errors::BoxliteError>(TaskMetrics {
-                                name,
-                                duration_ms: task_start.elapsed().as_millis(),
-                            })
-                        }
-                    });
+                    let futures = stage
+                        .tasks
+                        .into_iter()
+                        .map(|task| run_task(task, ctx.clone(), observer));
                     try_join_all(futures).await?
                 }
                 ExecutionMode::Sequential => {
                     let mut task_metrics = Vec::new();
                     for task in stage.tasks {
-                        let name = task.name().to_string();
-                        let task_start = Instant::now();
-                        task.run(ctx.clone()).await?;
-                        task_metrics.push(TaskMetrics {
-                            name,
-                            duration_ms: task_start.elapsed().as_millis(),
-                        });
+                        task_metrics.push(run_task(task, ctx.clone(), observer).await?);
                     }
                     task_metrics
                 }
@@ -112,3 +111,159 @@ impl PipelineExecutor {
         })
     }
 }
+
+/// Run one task, timing it and reporting to the observer.
+async fn run_task<Ctx>(
+    task: BoxedTask<Ctx>,
+    ctx: Ctx,
+    observer: Option<&dyn PipelineObserver>,
+) -> BoxliteResult<TaskMetrics> {
+    let name = task.name().to_string();
+    if let Some(observer) = observer {
+        observer.on_stage_start(&name);
+    }
+
+    let task_start = Instant::now();
+    let result = task.run(ctx).await;
+    let duration = task_start.elapsed();
+
+    if let Some(observer) = observer {
+        match &result {
+            Ok(()) => observer.on_stage_done(&name, duration),
+            Err(e) => observer.on_stage_error(&name, e),
+        }
+    }
+    result?;
+
+    Ok(TaskMetrics {
+        name,
+        duration_ms: duration.as_millis(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::PipelineTask;
+    use async_trait::async_trait;
+    use boxlite_shared::errors::BoxliteError;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    struct NamedTask {
+        name: &'static str,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl PipelineTask<()> for NamedTask {
+        async fn run(self: Box<Self>, _ctx: ()) -> BoxliteResult<()> {
+            if self.fail {
+                return Err(BoxliteError::Internal(format!("{} failed", self.name)));
+            }
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+    }
+
+    fn task(name: &'static str) -> BoxedTask<()> {
+        Box::new(NamedTask { name, fail: false })
+    }
+
+    #[derive(Default)]
+    struct RecordingObserver {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl PipelineObserver for RecordingObserver {
+        fn on_stage_start(&self, name: &str) {
+            self.events.lock().unwrap().push(format!("start:{}", name));
+        }
+
+        fn on_stage_done(&self, name: &str, _duration: Duration) {
+            self.events.lock().unwrap().push(format!("done:{}", name));
+        }
+
+        fn on_stage_error(&self, name: &str, _err: &BoxliteError) {
+            self.events.lock().unwrap().push(format!("error:{}", name));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_observer_sees_tasks_in_plan_order() {
+        // Same shape as the box init plan
+        let plan = ExecutionPlan::new(vec![
+            Stage::sequential(vec![task("filesystem_setup")]),
+            Stage::parallel(vec![
+                task("container_rootfs_prep"),
+                task("guest_rootfs_init"),
+            ]),
+            Stage::sequential(vec![task("vmm_spawn")]),
+            Stage::sequential(vec![task("guest_connect")]),
+            Stage::sequential(vec![task("guest_init")]),
+        ]);
+        let observer = RecordingObserver::default();
+
+        PipelineExecutor::execute_observed(PipelineBuilder::from_plan(plan), (), Some(&observer))
+            .await
+            .unwrap();
+
+        let events = observer.events.into_inner().unwrap();
+        assert_eq!(events.len(), 12);
+        assert_eq!(
+            events[..2],
+            ["start:filesystem_setup", "done:filesystem_setup"]
+        );
+        // Parallel tasks may interleave, but all finish before the next stage
+        let mut parallel = events[2..6].to_vec();
+        parallel.sort();
+        assert_eq!(
+            parallel,
+            [
+                "done:container_rootfs_prep",
+                "done:guest_rootfs_init",
+                "start:container_rootfs_prep",
+                "start:guest_rootfs_init",
+            ]
+        );
+        assert_eq!(
+            events[6..],
+            [
+                "start:vmm_spawn",
+                "done:vmm_spawn",
+                "start:guest_connect",
+                "done:guest_connect",
+                "start:guest_init",
+                "done:guest_init",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_observer_sees_error_and_pipeline_stops() {
+        let plan = ExecutionPlan::new(vec![
+            Stage::sequential(vec![Box::new(NamedTask {
+                name: "vmm_spawn",
+                fail: true,
+            }) as BoxedTask<()>]),
+            Stage::sequential(vec![task("guest_connect")]),
+        ]);
+        let observer = RecordingObserver::default();
+
+        let result = PipelineExecutor::execute_observed(
+            PipelineBuilder::from_plan(plan),
+            (),
+            Some(&observer),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            observer.events.into_inner().unwrap(),
+            ["start:vmm_spawn", "error:vmm_spawn"]
+        );
+    }
+}
@@ -2,8 +2,8 @@
 
 use boxlite_shared::{
     BindMount, BoxliteError, BoxliteResult, ContainerClient,
-    ContainerConfig as ProtoContainerConfig, ContainerInitRequest, DiskRootfs, MergedRootfs,
-    OverlayRootfs, RootfsInit, container_init_response,
+    ContainerConfig as ProtoContainerConfig, ContainerInitRequest, ContainerSignalRequest,
+    DiskRootfs, MergedRootfs, OverlayRootfs, RootfsInit, container_init_response,
 };
 use tonic::transport::Channel;
 
@@ -103,6 +103,7 @@ impl ContainerInterface {
             entrypoint: image_config.cmd.clone(),
             env: image_config.env.clone(),
             workdir: image_config.working_dir.clone(),
+            user: image_config.user.clone().unwrap_or_default(),
         };
 
         // Convert ContainerMount to proto BindMount
@@ -121,6 +122,7 @@ impl ContainerInterface {
             container_id = %container_id,
             entrypoint = ?image_config.cmd,
             workdir = %image_config.working_dir,
+            user = ?image_config.user,
             env_count = image_config.env.len(),
             rootfs = ?rootfs,
             mounts_count = proto_mounts.len(),
@@ -153,4 +155,22 @@ impl ContainerInterface {
             )),
         }
     }
+
+    /// Send `signal` to the container's main process.
+    pub async fn signal(&mut self, container_id: &str, signal: i32) -> BoxliteResult<()> {
+        let request = ContainerSignalRequest {
+            container_id: container_id.to_string(),
+            signal,
+        };
+
+        let response = self.client.signal(request).await?.into_inner();
+        if response.success {
+            Ok(())
+        } else {
+            Err(BoxliteError::Internal(format!(
+                "Container signal failed: {}",
+                response.error.unwrap_or_default()
+            )))
+        }
+    }
 }
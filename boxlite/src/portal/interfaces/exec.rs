@@ -8,11 +8,19 @@ use boxlite_shared::{
     AttachRequest, BoxliteError, BoxliteResult, ExecOutput, ExecRequest, ExecStdin,
     ExecutionClient, KillRequest, WaitRequest, WaitResponse, exec_output,
 };
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::CancellationToken;
 use tonic::transport::Channel;
 
+/// How long the wait task lets the output pump drain after the process
+/// exits, so a limited execution reports an accurate `truncated` flag.
+const OUTPUT_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Execution service interface.
 #[derive(Clone)]
 pub struct ExecutionInterface {
@@ -72,19 +80,27 @@ impl ExecutionInterface {
         ExecProtocol::spawn_stdin(self.client.clone(), execution_id.clone(), stdin_rx);
 
         // Spawn attach fanout (cancellable)
-        ExecProtocol::spawn_attach(
+        let truncated = Arc::new(AtomicBool::new(false));
+        let pump = ExecProtocol::spawn_attach(
             self.client.clone(),
             execution_id.clone(),
             stdout_tx,
             stderr_tx,
+            OutputBudget::new(command.max_output_bytes, truncated.clone()),
             shutdown_token.clone(),
         );
 
+        // Only limited executions need the pump drained before reporting
+        let drain = command
+            .max_output_bytes
+            .map(|_| OutputDrain { pump, truncated });
+
         // Spawn wait task for terminal status (cancellable)
         ExecProtocol::spawn_wait(
             self.client.clone(),
             execution_id.clone(),
             result_tx,
+            drain,
             shutdown_token,
         );
 
@@ -159,6 +175,60 @@ impl ExecutionInterface {
     }
 }
 
+// ============================================================================
+// Helper: Output limit
+// ============================================================================
+
+/// Byte budget shared by the stdout and stderr of one execution.
+struct OutputBudget {
+    remaining: Option<usize>,
+    truncated: Arc<AtomicBool>,
+}
+
+impl OutputBudget {
+    fn new(limit: Option<usize>, truncated: Arc<AtomicBool>) -> Self {
+        Self {
+            remaining: limit,
+            truncated,
+        }
+    }
+
+    /// The part of `data` that still fits, or `None` once the budget is spent.
+    ///
+    /// A cut never splits a UTF-8 sequence, so the delivered text stays
+    /// within the limit after lossy decoding.
+    fn admit<'a>(&mut self, data: &'a [u8]) -> Option<&'a [u8]> {
+        let Some(remaining) = self.remaining.as_mut() else {
+            return Some(data);
+        };
+        if data.len() <= *remaining {
+            *remaining -= data.len();
+            return Some(data);
+        }
+
+        let mut end = *remaining;
+        while end > 0 && (data[end] & 0xC0) == 0x80 {
+            end -= 1;
+        }
+        *remaining = 0;
+        self.truncated.store(true, Ordering::SeqCst);
+        (end > 0).then(|| &data[..end])
+    }
+}
+
+/// Output pump the wait task drains before reporting a limited execution.
+struct OutputDrain {
+    pump: JoinHandle<()>,
+    truncated: Arc<AtomicBool>,
+}
+
+impl OutputDrain {
+    async fn truncated(self) -> bool {
+        let _ = tokio::time::timeout(OUTPUT_DRAIN_TIMEOUT, self.pump).await;
+        self.truncated.load(Ordering::SeqCst)
+    }
+}
+
 // ============================================================================
 // Helper: Protocol wiring
 // ============================================================================
@@ -201,7 +271,10 @@ impl ExecProtocol {
         } else {
             resp.exit_code
         };
-        ExecResult { exit_code: code }
+        ExecResult {
+            exit_code: code,
+            truncated: false,
+        }
     }
 
     fn spawn_attach(
@@ -209,8 +282,9 @@ impl ExecProtocol {
         execution_id: String,
         stdout_tx: mpsc::UnboundedSender<String>,
         stderr_tx: mpsc::UnboundedSender<String>,
+        mut budget: OutputBudget,
         shutdown_token: CancellationToken,
-    ) {
+    ) -> JoinHandle<()> {
         tokio::spawn(async move {
             let request = AttachRequest {
                 execution_id: execution_id.clone(),
@@ -250,7 +324,7 @@ impl ExecProtocol {
                         match output.transpose() {
                             Some(Ok(output)) => {
                                 message_count += 1;
-                                Self::route_output(output, &stdout_tx, &stderr_tx);
+                                Self::route_output(output, &stdout_tx, &stderr_tx, &mut budget);
                             }
                             Some(Err(e)) => {
                                 tracing::debug!(
@@ -280,22 +354,29 @@ impl ExecProtocol {
                     let _ = stderr_tx.send(format!("Attach failed: {}", e));
                 }
             }
-        });
+        })
     }
 
     fn route_output(
         output: ExecOutput,
         stdout_tx: &mpsc::UnboundedSender<String>,
         stderr_tx: &mpsc::UnboundedSender<String>,
+        budget: &mut OutputBudget,
     ) {
         match output.event {
             Some(exec_output::Event::Stdout(chunk)) => {
-                let stdout_data = String::from_utf8_lossy(&chunk.data).to_string();
+                let Some(data) = budget.admit(&chunk.data) else {
+                    return;
+                };
+                let stdout_data = String::from_utf8_lossy(data).to_string();
                 tracing::trace!(?stdout_data, "Received exec stdout");
                 let _ = stdout_tx.send(stdout_data);
             }
             Some(exec_output::Event::Stderr(chunk)) => {
-                let stderr_data = String::from_utf8_lossy(&chunk.data).to_string();
+                let Some(data) = budget.admit(&chunk.data) else {
+                    return;
+                };
+                let stderr_data = String::from_utf8_lossy(data).to_string();
                 tracing::trace!(?stderr_data, "Received exec stderr");
                 let _ = stderr_tx.send(stderr_data);
             }
@@ -307,6 +388,7 @@ impl ExecProtocol {
         mut client: ExecutionClient<Channel>,
         execution_id: String,
         result_tx: mpsc::UnboundedSender<ExecResult>,
+        drain: Option<OutputDrain>,
         shutdown_token: CancellationToken,
     ) {
         tokio::spawn(async move {
@@ -321,7 +403,10 @@ impl ExecProtocol {
                     tracing::debug!(execution_id = %execution_id, "Wait cancelled during shutdown");
                     // Send a special result indicating cancellation
                     // Using exit code -1 to indicate abnormal termination
-                    let _ = result_tx.send(ExecResult { exit_code: -1 });
+                    let _ = result_tx.send(ExecResult {
+                        exit_code: -1,
+                        truncated: false,
+                    });
                     return;
                 }
                 result = client.wait(request) => result,
@@ -329,7 +414,10 @@ impl ExecProtocol {
 
             match result {
                 Ok(resp) => {
-                    let mapped = Self::map_wait_response(resp.into_inner());
+                    let mut mapped = Self::map_wait_response(resp.into_inner());
+                    if let Some(drain) = drain {
+                        mapped.truncated = drain.truncated().await;
+                    }
                     let _ = result_tx.send(mapped);
                 }
                 Err(e) => {
@@ -338,7 +426,10 @@ impl ExecProtocol {
                         error = %e,
                         "Wait failed"
                     );
-                    let _ = result_tx.send(ExecResult { exit_code: -1 });
+                    let _ = result_tx.send(ExecResult {
+                        exit_code: -1,
+                        truncated: false,
+                    });
                 }
             }
         });
@@ -395,7 +486,6 @@ impl ExecProtocol {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::Duration;
 
     /// Test that CancellationToken correctly signals cancelled state.
     #[tokio::test]
@@ -526,7 +616,10 @@ mod tests {
             tokio::select! {
                 biased;
                 _ = token_clone.cancelled() => {
-                    let _ = result_tx.send(ExecResult { exit_code: -1 });
+                    let _ = result_tx.send(ExecResult {
+                        exit_code: -1,
+                        truncated: false,
+                    });
                 }
                 _ = tokio::time::sleep(Duration::from_secs(3600)) => {
                     // Would normally wait for gRPC response
@@ -642,4 +735,57 @@ mod tests {
         assert_eq!(result1.unwrap(), Some("cancelled"));
         assert_eq!(result2.unwrap(), Some("cancelled"));
     }
+
+    /// `BoxCommand::tty(true)` must ask the guest for a PTY.
+    #[test]
+    fn test_tty_flag_reaches_exec_request() {
+        let request = ExecProtocol::build_exec_request(&BoxCommand::new("sh").tty(true));
+        let tty = request.tty.expect("tty config should be set");
+        assert!(tty.rows > 0 && tty.cols > 0);
+
+        let request = ExecProtocol::build_exec_request(&BoxCommand::new("sh"));
+        assert!(request.tty.is_none());
+    }
+
+    fn stdout_chunk(data: &[u8]) -> ExecOutput {
+        ExecOutput {
+            event: Some(exec_output::Event::Stdout(boxlite_shared::Stdout {
+                data: data.to_vec(),
+            })),
+        }
+    }
+
+    /// Output past `max_output_bytes` is dropped and flagged as truncated.
+    #[test]
+    fn test_output_limit_truncates_at_limit() {
+        let (stdout_tx, mut stdout_rx) = mpsc::unbounded_channel();
+        let (stderr_tx, _stderr_rx) = mpsc::unbounded_channel();
+        let truncated = Arc::new(AtomicBool::new(false));
+        let mut budget = OutputBudget::new(Some(10), truncated.clone());
+
+        for _ in 0..4 {
+            let chunk = stdout_chunk(b"abcd");
+            ExecProtocol::route_output(chunk, &stdout_tx, &stderr_tx, &mut budget);
+        }
+        drop(stdout_tx);
+
+        let mut received = String::new();
+        while let Ok(data) = stdout_rx.try_recv() {
+            received.push_str(&data);
+        }
+        assert_eq!(received, "abcdabcdab");
+        assert!(truncated.load(Ordering::SeqCst));
+    }
+
+    /// A cut falls back to a UTF-8 boundary instead of splitting a character.
+    #[test]
+    fn test_output_limit_respects_utf8_boundary() {
+        let mut budget = OutputBudget::new(Some(2), Arc::new(AtomicBool::new(false)));
+        assert_eq!(budget.admit("aé".as_bytes()), Some("a".as_bytes()));
+        assert_eq!(budget.admit(b"more"), None);
+
+        let mut unlimited = OutputBudget::new(None, Arc::new(AtomicBool::new(false)));
+        assert_eq!(unlimited.admit(b"anything"), Some(&b"anything"[..]));
+        assert!(!unlimited.truncated.load(Ordering::SeqCst));
+    }
 }
@@ -0,0 +1,381 @@
+//! Files service interface.
+//!
+//! Streams file contents between host paths and paths inside the container.
+
+use boxlite_shared::{
+    BoxliteError, BoxliteResult, DownloadRequest, FileChunk, FilesClient, UploadResponse,
+};
+use std::path::Path;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::Channel;
+
+/// Size of each data chunk sent to the guest.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Files service interface.
+pub struct FilesInterface {
+    client: FilesClient<Channel>,
+}
+
+impl FilesInterface {
+    /// Create from a channel.
+    pub fn new(channel: Channel) -> Self {
+        Self {
+            client: FilesClient::new(channel),
+        }
+    }
+
+    /// Copy a host file into the container, preserving its mode bits.
+    ///
+    /// Returns the number of bytes written in the guest.
+    pub async fn upload(
+        &mut self,
+        container_id: &str,
+        host_path: &Path,
+        guest_path: &str,
+    ) -> BoxliteResult<u64> {
+        let mut file = tokio::fs::File::open(host_path)
+            .await
+            .map_err(|e| host_io_error(host_path, e))?;
+        let metadata = file
+            .metadata()
+            .await
+            .map_err(|e| host_io_error(host_path, e))?;
+        if !metadata.is_file() {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "Not a regular file: {}",
+                host_path.display()
+            )));
+        }
+
+        tracing::debug!(
+            host_path = %host_path.display(),
+            guest_path,
+            size = metadata.len(),
+            "Uploading file to guest"
+        );
+
+        let mode = file_mode(&metadata);
+        let container_id = container_id.to_string();
+        let guest_path = guest_path.to_string();
+
+        // Reader task feeds chunks to the request stream
+        let (tx, rx) = mpsc::channel::<FileChunk>(4);
+        let reader = tokio::spawn(async move {
+            let mut buf = vec![0u8; CHUNK_SIZE];
+            let mut first = true;
+            loop {
+                let n = file.read(&mut buf).await?;
+                if n == 0 && !first {
+                    return Ok::<_, std::io::Error>(());
+                }
+                let chunk = if first {
+                    FileChunk {
+                        container_id: container_id.clone(),
+                        path: guest_path.clone(),
+                        mode,
+                        data: buf[..n].to_vec(),
+                    }
+                } else {
+                    FileChunk {
+                        data: buf[..n].to_vec(),
+                        ..Default::default()
+                    }
+                };
+                first = false;
+                if tx.send(chunk).await.is_err() || n == 0 {
+                    return Ok(());
+                }
+            }
+        });
+
+        let response = self
+            .client
+            .upload(ReceiverStream::new(rx))
+            .await
+            .map_err(status_to_error)?;
+
+        reader
+            .await
+            .map_err(|e| BoxliteError::Internal(format!("Upload reader task failed: {}", e)))?
+            .map_err(|e| host_io_error(host_path, e))?;
+
+        let UploadResponse { bytes_written } = response.into_inner();
+        Ok(bytes_written)
+    }
+
+    /// Copy a file out of the container to a host path, preserving its mode bits.
+    ///
+    /// Returns the number of bytes written on the host.
+    pub async fn download(
+        &mut self,
+        container_id: &str,
+        guest_path: &str,
+        host_path: &Path,
+    ) -> BoxliteResult<u64> {
+        tracing::debug!(
+            guest_path,
+            host_path = %host_path.display(),
+            "Downloading file from guest"
+        );
+
+        let mut stream = self
+            .client
+            .download(DownloadRequest {
+                container_id: container_id.to_string(),
+                path: guest_path.to_string(),
+            })
+            .await
+            .map_err(status_to_error)?
+            .into_inner();
+
+        // The first chunk carries the mode; fail before touching the host path
+        let first = stream
+            .message()
+            .await
+            .map_err(status_to_error)?
+            .ok_or_else(|| BoxliteError::Rpc("Download stream ended without data".into()))?;
+
+        // Stream into a temp file next to the target and rename it into
+        // place, so a failed download never leaves a truncated file behind
+        let dir = match host_path.parent() {
+            Some(p) if !p.as_os_str().is_empty() => p,
+            _ => Path::new("."),
+        };
+        let temp = tempfile::Builder::new()
+            .prefix(".boxlite-download")
+            .tempfile_in(dir)
+            .map_err(|e| host_io_error(dir, e))?;
+        let mut file = temp
+            .reopen()
+            .map(tokio::fs::File::from_std)
+            .map_err(|e| host_io_error(temp.path(), e))?;
+        let mut written = first.data.len() as u64;
+        file.write_all(&first.data)
+            .await
+            .map_err(|e| host_io_error(host_path, e))?;
+
+        while let Some(chunk) = stream.message().await.map_err(status_to_error)? {
+            file.write_all(&chunk.data)
+                .await
+                .map_err(|e| host_io_error(host_path, e))?;
+            written += chunk.data.len() as u64;
+        }
+        file.flush()
+            .await
+            .map_err(|e| host_io_error(host_path, e))?;
+
+        set_file_mode(temp.path(), first.mode)
+            .await
+            .map_err(|e| host_io_error(host_path, e))?;
+        temp.persist(host_path)
+            .map_err(|e| host_io_error(host_path, e.error))?;
+
+        Ok(written)
+    }
+}
+
+#[cfg(unix)]
+fn file_mode(metadata: &std::fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o7777
+}
+
+#[cfg(not(unix))]
+fn file_mode(_metadata: &std::fs::Metadata) -> u32 {
+    0o644
+}
+
+#[cfg(unix)]
+async fn set_file_mode(path: &Path, mode: u32) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    tokio::fs::set_permissions(path, std::fs::Permissions::from_mode(mode & 0o7777)).await
+}
+
+#[cfg(not(unix))]
+async fn set_file_mode(_path: &Path, _mode: u32) -> std::io::Result<()> {
+    Ok(())
+}
+
+fn host_io_error(path: &Path, e: std::io::Error) -> BoxliteError {
+    match e.kind() {
+        std::io::ErrorKind::NotFound => {
+            BoxliteError::NotFound(format!("host path {}", path.display()))
+        }
+        _ => BoxliteError::Storage(format!("{}: {}", path.display(), e)),
+    }
+}
+
+fn status_to_error(status: tonic::Status) -> BoxliteError {
+    match status.code() {
+        tonic::Code::NotFound => BoxliteError::NotFound(status.message().to_string()),
+        tonic::Code::InvalidArgument => BoxliteError::InvalidArgument(status.message().to_string()),
+        _ => status.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portal::connection::Connection;
+    use boxlite_shared::{Files, FilesServer, Transport};
+    use std::path::PathBuf;
+    use std::pin::Pin;
+    use tokio_stream::Stream;
+    use tonic::{Request, Response, Status, Streaming};
+
+    /// In-process stand-in for the guest Files service, rooted at a temp dir.
+    struct MockFiles {
+        root: PathBuf,
+    }
+
+    impl MockFiles {
+        fn resolve(&self, path: &str) -> PathBuf {
+            self.root.join(path.trim_start_matches('/'))
+        }
+    }
+
+    #[tonic::async_trait]
+    impl Files for MockFiles {
+        async fn upload(
+            &self,
+            request: Request<Streaming<FileChunk>>,
+        ) -> Result<Response<UploadResponse>, Status> {
+            let mut stream = request.into_inner();
+            let first = stream.message().await?.unwrap();
+            let target = self.resolve(&first.path);
+            std::fs::create_dir_all(target.parent().unwrap()).unwrap();
+
+            let mut data = first.data;
+            while let Some(chunk) = stream.message().await? {
+                data.extend_from_slice(&chunk.data);
+            }
+            std::fs::write(&target, &data).unwrap();
+            set_file_mode(&target, first.mode).await.unwrap();
+            Ok(Response::new(UploadResponse {
+                bytes_written: data.len() as u64,
+            }))
+        }
+
+        type DownloadStream = Pin<Box<dyn Stream<Item = Result<FileChunk, Status>> + Send>>;
+
+        async fn download(
+            &self,
+            request: Request<DownloadRequest>,
+        ) -> Result<Response<Self::DownloadStream>, Status> {
+            let source = self.resolve(&request.into_inner().path);
+            let data = std::fs::read(&source)
+                .map_err(|e| Status::not_found(format!("{}: {}", source.display(), e)))?;
+            let mode = file_mode(&std::fs::metadata(&source).unwrap());
+
+            let mut chunks: Vec<FileChunk> = data
+                .chunks(CHUNK_SIZE)
+                .map(|c| FileChunk {
+                    data: c.to_vec(),
+                    ..Default::default()
+                })
+                .collect();
+            if chunks.is_empty() {
+                chunks.push(FileChunk::default());
+            }
+            chunks[0].mode = mode;
+            let mut items: Vec<Result<FileChunk, Status>> = chunks.into_iter().map(Ok).collect();
+            // Simulates the guest failing partway through the stream
+            if source.ends_with("broken") {
+                items.push(Err(Status::internal("read failed")));
+            }
+            Ok(Response::new(Box::pin(tokio_stream::iter(items))))
+        }
+    }
+
+    async fn start_mock(root: PathBuf) -> FilesInterface {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let incoming =
+            tonic::transport::server::TcpIncoming::from_listener(listener, true, None).unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(FilesServer::new(MockFiles { root }))
+                .serve_with_incoming(incoming),
+        );
+
+        let channel = Connection::new(Transport::Tcp { port })
+            .channel()
+            .await
+            .unwrap();
+        FilesInterface::new(channel)
+    }
+
+    #[tokio::test]
+    async fn test_copy_round_trip_preserves_bytes_and_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let host = tempfile::tempdir().unwrap();
+        let guest = tempfile::tempdir().unwrap();
+        let mut files = start_mock(guest.path().to_path_buf()).await;
+
+        // Larger than one chunk to exercise streaming
+        let content: Vec<u8> = (0..CHUNK_SIZE * 2 + 17).map(|i| (i % 251) as u8).collect();
+        let source = host.path().join("app.conf");
+        std::fs::write(&source, &content).unwrap();
+        std::fs::set_permissions(&source, std::fs::Permissions::from_mode(0o750)).unwrap();
+
+        let uploaded = files
+            .upload("cid", &source, "/etc/app/app.conf")
+            .await
+            .unwrap();
+        assert_eq!(uploaded, content.len() as u64);
+        assert_eq!(
+            std::fs::read(guest.path().join("etc/app/app.conf")).unwrap(),
+            content
+        );
+
+        let dest = host.path().join("copied.conf");
+        let downloaded = files
+            .download("cid", "/etc/app/app.conf", &dest)
+            .await
+            .unwrap();
+        assert_eq!(downloaded, content.len() as u64);
+        assert_eq!(std::fs::read(&dest).unwrap(), content);
+        let mode = std::fs::metadata(&dest).unwrap().permissions().mode() & 0o7777;
+        assert_eq!(mode, 0o750);
+    }
+
+    #[tokio::test]
+    async fn test_copy_missing_source_is_not_found() {
+        let host = tempfile::tempdir().unwrap();
+        let guest = tempfile::tempdir().unwrap();
+        let mut files = start_mock(guest.path().to_path_buf()).await;
+
+        let err = files
+            .upload("cid", &host.path().join("missing"), "/tmp/x")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BoxliteError::NotFound(_)), "got {:?}", err);
+
+        let dest = host.path().join("out");
+        let err = files
+            .download("cid", "/does/not/exist", &dest)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BoxliteError::NotFound(_)), "got {:?}", err);
+        assert!(!dest.exists());
+    }
+
+    #[tokio::test]
+    async fn test_failed_download_keeps_existing_file() {
+        let host = tempfile::tempdir().unwrap();
+        let guest = tempfile::tempdir().unwrap();
+        std::fs::write(guest.path().join("broken"), vec![7u8; CHUNK_SIZE + 1]).unwrap();
+        let mut files = start_mock(guest.path().to_path_buf()).await;
+
+        let dest = host.path().join("out");
+        std::fs::write(&dest, "previous").unwrap();
+        files.download("cid", "/broken", &dest).await.unwrap_err();
+
+        assert_eq!(std::fs::read_to_string(&dest).unwrap(), "previous");
+        assert_eq!(std::fs::read_dir(host.path()).unwrap().count(), 1);
+    }
+}
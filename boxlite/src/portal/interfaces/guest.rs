@@ -2,8 +2,10 @@
 
 use boxlite_shared::{
     BlockDeviceSource, BoxliteError, BoxliteResult, Filesystem, GuestClient, GuestInitRequest,
-    NetworkInit, PingRequest, ShutdownRequest, VirtiofsSource, Volume, guest_init_response,
+    NetworkInit, PingRequest, ShutdownRequest, SyncTimeRequest, VirtiofsSource, Volume,
+    guest_init_response,
 };
+use std::time::{SystemTime, UNIX_EPOCH};
 use tonic::transport::Channel;
 
 /// Guest service interface.
@@ -61,7 +63,6 @@ impl GuestInterface {
     }
 
     /// Ping the guest (health check).
-    #[allow(dead_code)] // API method for future health checks
     pub async fn ping(&mut self) -> BoxliteResult<()> {
         let _response = self.client.ping(PingRequest {}).await?;
         Ok(())
@@ -72,6 +73,19 @@ impl GuestInterface {
         let _response = self.client.shutdown(ShutdownRequest {}).await?;
         Ok(())
     }
+
+    /// Set the guest wall clock to `now`.
+    pub async fn sync_time(&mut self, now: SystemTime) -> BoxliteResult<()> {
+        let since_epoch = now.duration_since(UNIX_EPOCH).map_err(|e| {
+            BoxliteError::InvalidArgument(format!("time before the Unix epoch: {}", e))
+        })?;
+        let request = SyncTimeRequest {
+            unix_seconds: since_epoch.as_secs() as i64,
+            nanos: since_epoch.subsec_nanos(),
+        };
+        let _response = self.client.sync_time(request).await?;
+        Ok(())
+    }
 }
 
 /// Configuration for guest initialization.
@@ -95,6 +109,8 @@ pub enum VolumeConfig {
         read_only: bool,
         /// Optional container_id for convention-based paths
         container_id: Option<String>,
+        /// Extra mount options (e.g. "nosuid", "dax=never")
+        options: Vec<String>,
     },
     /// Block device mount
     BlockDevice {
@@ -118,12 +134,14 @@ impl VolumeConfig {
         mount_point: impl Into<String>,
         read_only: bool,
         container_id: Option<String>,
+        options: Vec<String>,
     ) -> Self {
         Self::Virtiofs {
             tag: tag.into(),
             mount_point: mount_point.into(),
             read_only,
             container_id,
+            options,
         }
     }
 
@@ -151,11 +169,13 @@ impl VolumeConfig {
                 mount_point,
                 read_only,
                 container_id,
+                options,
             } => Volume {
                 mount_point,
                 source: Some(boxlite_shared::volume::Source::Virtiofs(VirtiofsSource {
                     tag,
                     read_only,
+                    options,
                 })),
                 container_id: container_id.unwrap_or_default(),
             },
@@ -3,7 +3,9 @@
 //! Thin facade over service interfaces.
 
 use crate::portal::connection::Connection;
-use crate::portal::interfaces::{ContainerInterface, ExecutionInterface, GuestInterface};
+use crate::portal::interfaces::{
+    ContainerInterface, ExecutionInterface, FilesInterface, GuestInterface,
+};
 use boxlite_shared::{BoxliteResult, Transport};
 
 /// High-level guest session.
@@ -39,6 +41,12 @@ impl GuestSession {
         let channel = self.connection.channel().await?;
         Ok(GuestInterface::new(channel))
     }
+
+    /// Get files interface.
+    pub async fn files(&self) -> BoxliteResult<FilesInterface> {
+        let channel = self.connection.channel().await?;
+        Ok(FilesInterface::new(channel))
+    }
 }
 
 // ============================================================================
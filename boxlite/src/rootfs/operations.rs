@@ -296,6 +296,36 @@ pub fn fix_rootfs_permissions(rootfs: &Path) -> BoxliteResult<()> {
     Ok(())
 }
 
+/// Resolve a host directory to be used as a box rootfs.
+///
+/// Returns the canonical path. The directory must exist and contain `bin` or
+/// `sbin`, otherwise `BoxliteError::InvalidArgument` is returned.
+pub fn resolve_rootfs_dir(dir: &Path) -> BoxliteResult<std::path::PathBuf> {
+    let resolved = dir.canonicalize().map_err(|e| {
+        BoxliteError::InvalidArgument(format!(
+            "Rootfs directory {} is not accessible: {}",
+            dir.display(),
+            e
+        ))
+    })?;
+
+    if !resolved.is_dir() {
+        return Err(BoxliteError::InvalidArgument(format!(
+            "Rootfs path {} is not a directory",
+            dir.display()
+        )));
+    }
+
+    if !resolved.join("bin").exists() && !resolved.join("sbin").exists() {
+        return Err(BoxliteError::InvalidArgument(format!(
+            "Rootfs directory {} does not look like a rootfs (no /bin or /sbin)",
+            dir.display()
+        )));
+    }
+
+    Ok(resolved)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -329,4 +359,29 @@ mod tests {
 
         assert!(!dir.join(".wh..wh..opq").exists());
     }
+
+    #[test]
+    fn test_resolve_rootfs_dir() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join("sbin")).unwrap();
+
+        let resolved = resolve_rootfs_dir(&temp.path().join(".")).unwrap();
+        assert_eq!(resolved, temp.path().canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_resolve_rootfs_dir_rejects_non_rootfs() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("file");
+        fs::write(&file, "").unwrap();
+
+        for path in [temp.path().to_path_buf(), temp.path().join("missing"), file] {
+            let err = resolve_rootfs_dir(&path).unwrap_err();
+            assert!(
+                matches!(err, BoxliteError::InvalidArgument(_)),
+                "{}: {err}",
+                path.display()
+            );
+        }
+    }
 }
@@ -0,0 +1,552 @@
+//! Minimal Containerfile builds.
+//!
+//! Supports FROM, RUN, COPY, ENV, WORKDIR and ENTRYPOINT. All steps run in
+//! one transient box created from the FROM image; its changes are committed
+//! as a single layer on top of that image. Variables are not expanded and
+//! the base image must provide `/bin/sh`.
+
+use std::path::{Path, PathBuf};
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use futures::StreamExt;
+use walkdir::WalkDir;
+
+use crate::images::ImageConfigChanges;
+use crate::litebox::{BoxCommand, LiteBox};
+use crate::runtime::options::{BoxOptions, RootfsSpec};
+use crate::runtime::rt_impl::SharedRuntimeImpl;
+
+/// Repository built images are tagged in; the tag is the build box's ID.
+const BUILD_REPOSITORY: &str = "localhost/boxlite-build";
+
+const SUPPORTED: &str = "FROM, RUN, COPY, ENV, WORKDIR, ENTRYPOINT";
+
+/// One Containerfile instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Instruction {
+    From(String),
+    /// Command line; shell form is wrapped in `/bin/sh -c`.
+    Run(Vec<String>),
+    Copy {
+        sources: Vec<String>,
+        dest: String,
+    },
+    Env(Vec<(String, String)>),
+    Workdir(String),
+    Entrypoint(Vec<String>),
+}
+
+impl std::fmt::Display for Instruction {
+    /// Containerfile text for image history, e.g. `RUN /bin/sh -c make`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::From(image) => write!(f, "FROM {}", image),
+            Instruction::Run(argv) => write!(f, "RUN {}", argv.join(" ")),
+            Instruction::Copy { sources, dest } => {
+                write!(f, "COPY {} {}", sources.join(" "), dest)
+            }
+            Instruction::Env(vars) => {
+                let vars: Vec<String> = vars.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+                write!(f, "ENV {}", vars.join(" "))
+            }
+            Instruction::Workdir(dir) => write!(f, "WORKDIR {}", dir),
+            Instruction::Entrypoint(argv) => write!(
+                f,
+                "ENTRYPOINT {}",
+                serde_json::to_string(argv).map_err(|_| std::fmt::Error)?
+            ),
+        }
+    }
+}
+
+/// Parse a Containerfile into the instructions a build executes.
+///
+/// The first instruction must be FROM and only one FROM is allowed.
+/// Directives outside the supported set return `BoxliteError::Unsupported`,
+/// malformed ones `BoxliteError::InvalidArgument`; both name the line.
+pub(crate) fn parse_containerfile(text: &str) -> BoxliteResult<Vec<Instruction>> {
+    let mut instructions = Vec::new();
+    for (line_no, line) in logical_lines(text) {
+        let invalid =
+            |msg: &str| BoxliteError::InvalidArgument(format!("line {}: {}", line_no, msg));
+
+        let (keyword, rest) = match line.split_once(char::is_whitespace) {
+            Some((keyword, rest)) => (keyword, rest.trim()),
+            None => (line.as_str(), ""),
+        };
+        let keyword = keyword.to_ascii_uppercase();
+        if rest.is_empty() {
+            return Err(invalid(&format!("{} requires arguments", keyword)));
+        }
+
+        let instruction = match keyword.as_str() {
+            "FROM" => {
+                if !instructions.is_empty() {
+                    return Err(BoxliteError::Unsupported(format!(
+                        "line {}: FROM must be the first instruction; multi-stage builds are not supported",
+                        line_no
+                    )));
+                }
+                match rest.split_whitespace().collect::<Vec<_>>().as_slice() {
+                    [image] => Instruction::From(image.to_string()),
+                    _ => {
+                        return Err(BoxliteError::Unsupported(format!(
+                            "line {}: FROM options and stage names are not supported",
+                            line_no
+                        )));
+                    }
+                }
+            }
+            _ if instructions.is_empty() => {
+                return Err(invalid("the first instruction must be FROM"));
+            }
+            "RUN" => Instruction::Run(command_form(rest)),
+            "ENTRYPOINT" => Instruction::Entrypoint(command_form(rest)),
+            "WORKDIR" => Instruction::Workdir(rest.to_string()),
+            "ENV" => Instruction::Env(parse_env(rest).ok_or_else(|| invalid("malformed ENV"))?),
+            "COPY" => {
+                let args = match json_array(rest) {
+                    Some(args) => args,
+                    None => split_words(rest).ok_or_else(|| invalid("unterminated quote"))?,
+                };
+                if let Some(flag) = args.iter().find(|a| a.starts_with("--")) {
+                    return Err(BoxliteError::Unsupported(format!(
+                        "line {}: COPY option {} is not supported",
+                        line_no, flag
+                    )));
+                }
+                match args.split_last() {
+                    Some((dest, sources)) if !sources.is_empty() => Instruction::Copy {
+                        sources: sources.to_vec(),
+                        dest: dest.clone(),
+                    },
+                    _ => return Err(invalid("COPY requires a source and a destination")),
+                }
+            }
+            other => {
+                return Err(BoxliteError::Unsupported(format!(
+                    "line {}: {} is not supported (supported: {})",
+                    line_no, other, SUPPORTED
+                )));
+            }
+        };
+        instructions.push(instruction);
+    }
+
+    if instructions.is_empty() {
+        return Err(BoxliteError::InvalidArgument(
+            "Containerfile has no instructions".into(),
+        ));
+    }
+    Ok(instructions)
+}
+
+/// Run `instructions` in a transient box and commit the result.
+///
+/// Returns the reference of the new image.
+pub(crate) async fn build(
+    runtime: &SharedRuntimeImpl,
+    context_dir: &Path,
+    instructions: Vec<Instruction>,
+) -> BoxliteResult<String> {
+    let context_dir = context_dir.canonicalize().map_err(|e| {
+        BoxliteError::InvalidArgument(format!(
+            "Invalid build context {}: {}",
+            context_dir.display(),
+            e
+        ))
+    })?;
+    let Some((Instruction::From(base), steps)) = instructions.split_first() else {
+        return Err(BoxliteError::InvalidArgument(
+            "the first instruction must be FROM".into(),
+        ));
+    };
+
+    let base_config = runtime
+        .image_manager
+        .pull(base)
+        .await?
+        .load_config()
+        .await?;
+    let workdir = base_config
+        .config()
+        .as_ref()
+        .and_then(|c| c.working_dir().clone())
+        .filter(|dir| !dir.is_empty())
+        .unwrap_or_else(|| "/".to_string());
+
+    // Keep the container alive on a shell instead of the image's entrypoint
+    let options = BoxOptions {
+        rootfs: RootfsSpec::Image(base.clone()),
+        command: Some(vec!["/bin/sh".to_string()]),
+        args: Some(Vec::new()),
+        auto_remove: false,
+        ..Default::default()
+    };
+    let litebox = runtime.create(options, None).await?;
+    let box_id = litebox.id().clone();
+    let image_ref = format!("{}:{}", BUILD_REPOSITORY, box_id.as_str().to_lowercase());
+
+    let mut state = BuildState {
+        context_dir,
+        workdir,
+        changes: ImageConfigChanges::default(),
+    };
+    let result = async {
+        litebox.start().await?;
+        for (i, step) in steps.iter().enumerate() {
+            tracing::info!(box_id = %box_id, "Build step {}/{}: {:?}", i + 1, steps.len(), step);
+            state.apply(&litebox, step).await?;
+        }
+        litebox.stop().await?;
+
+        let stopped = runtime
+            .get(box_id.as_str())
+            .await?
+            .ok_or_else(|| BoxliteError::NotFound(format!("build box {} disappeared", box_id)))?;
+        // All steps land in one layer, so its history names all of them
+        let created_by = steps
+            .iter()
+            .map(Instruction::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+        stopped
+            .commit_image(&image_ref, &state.changes, &created_by)
+            .await
+    }
+    .await;
+
+    if let Err(e) = runtime.remove(box_id.as_str(), true).await {
+        tracing::warn!(box_id = %box_id, error = %e, "Failed to remove build box");
+    }
+    result?;
+
+    tracing::info!(image = %image_ref, base = %base, "Built image");
+    Ok(image_ref)
+}
+
+/// State carried between build steps.
+struct BuildState {
+    context_dir: PathBuf,
+    /// Absolute working directory of RUN and relative COPY destinations.
+    workdir: String,
+    /// Config changes for the committed image.
+    changes: ImageConfigChanges,
+}
+
+impl BuildState {
+    async fn apply(&mut self, litebox: &LiteBox, step: &Instruction) -> BoxliteResult<()> {
+        match step {
+            Instruction::From(_) => Err(BoxliteError::Unsupported(
+                "multi-stage builds are not supported".into(),
+            )),
+            Instruction::Run(argv) => self.run(litebox, argv).await,
+            Instruction::Copy { sources, dest } => self.copy(litebox, sources, dest).await,
+            Instruction::Env(vars) => {
+                for (key, value) in vars {
+                    self.changes.env.retain(|(k, _)| k != key);
+                    self.changes.env.push((key.clone(), value.clone()));
+                }
+                Ok(())
+            }
+            Instruction::Workdir(dir) => {
+                self.workdir = self.resolve(dir);
+                self.changes.working_dir = Some(self.workdir.clone());
+                let mkdir = ["mkdir".to_string(), "-p".to_string(), self.workdir.clone()];
+                self.run(litebox, &mkdir).await
+            }
+            Instruction::Entrypoint(argv) => {
+                self.changes.entrypoint = Some(argv.clone());
+                Ok(())
+            }
+        }
+    }
+
+    /// Run `argv` in the build box, failing on a non-zero exit.
+    async fn run(&self, litebox: &LiteBox, argv: &[String]) -> BoxliteResult<()> {
+        let Some((program, args)) = argv.split_first() else {
+            return Ok(());
+        };
+        let mut command = BoxCommand::new(program)
+            .args(args)
+            .working_dir(&self.workdir);
+        for (key, value) in &self.changes.env {
+            command = command.env(key, value);
+        }
+
+        let mut execution = litebox.exec(command).await?;
+        if let Some(stdout) = execution.stdout() {
+            tokio::spawn(stdout.for_each(|line| async move {
+                tracing::info!(target: "boxlite::build", "{}", line.trim_end());
+            }));
+        }
+        if let Some(stderr) = execution.stderr() {
+            tokio::spawn(stderr.for_each(|line| async move {
+                tracing::info!(target: "boxlite::build", "{}", line.trim_end());
+            }));
+        }
+
+        let result = execution.wait().await?;
+        if !result.success() {
+            return Err(BoxliteError::Execution(format!(
+                "build command {:?} exited with code {}",
+                argv,
+                result.code()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Copy context files into the build box.
+    ///
+    /// A directory source copies its contents. The destination is a
+    /// directory if it ends in `/`, there are several sources or the source
+    /// is a directory. Symlinks inside copied directories are skipped.
+    async fn copy(&self, litebox: &LiteBox, sources: &[String], dest: &str) -> BoxliteResult<()> {
+        let dest_is_dir = dest.ends_with('/') || sources.len() > 1;
+        let dest = self.resolve(dest);
+
+        let mut dirs = Vec::new();
+        let mut files = Vec::new();
+        for source in sources {
+            let path = self.context_path(source)?;
+            if path.is_dir() {
+                for entry in WalkDir::new(&path).sort_by_file_name() {
+                    let entry = entry.map_err(|e| {
+                        BoxliteError::Storage(format!("Failed to read {}: {}", path.display(), e))
+                    })?;
+                    let rel = entry.path().strip_prefix(&path).unwrap_or(entry.path());
+                    let target = guest_join(&dest, rel);
+                    if entry.file_type().is_dir() {
+                        dirs.push(target);
+                    } else if entry.file_type().is_file() {
+                        files.push((entry.path().to_path_buf(), target));
+                    }
+                }
+            } else {
+                let target = if dest_is_dir {
+                    dirs.push(dest.clone());
+                    let name = path.file_name().unwrap_or_default();
+                    guest_join(&dest, Path::new(name))
+                } else {
+                    if let Some(parent) = Path::new(&dest).parent() {
+                        dirs.push(parent.display().to_string());
+                    }
+                    dest.clone()
+                };
+                files.push((path, target));
+            }
+        }
+
+        if !dirs.is_empty() {
+            let mut mkdir = vec!["mkdir".to_string(), "-p".to_string()];
+            mkdir.extend(dirs);
+            self.run(litebox, &mkdir).await?;
+        }
+        for (host_path, guest_path) in files {
+            litebox.copy_to_guest(&host_path, &guest_path).await?;
+        }
+        Ok(())
+    }
+
+    /// `source` inside the build context, rejecting paths that leave it.
+    fn context_path(&self, source: &str) -> BoxliteResult<PathBuf> {
+        let path = self
+            .context_dir
+            .join(source.trim_start_matches('/'))
+            .canonicalize()
+            .map_err(|e| BoxliteError::InvalidArgument(format!("COPY source {}: {}", source, e)))?;
+        if !path.starts_with(&self.context_dir) {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "COPY source {} is outside the build context",
+                source
+            )));
+        }
+        Ok(path)
+    }
+
+    /// `path` made absolute against the current working directory.
+    fn resolve(&self, path: &str) -> String {
+        if path.starts_with('/') {
+            path.to_string()
+        } else {
+            guest_join(&self.workdir, Path::new(path))
+        }
+    }
+}
+
+fn guest_join(dir: &str, rel: &Path) -> String {
+    let rel = rel.display().to_string();
+    match (dir.trim_end_matches('/'), rel.as_str()) {
+        (dir, "") => if dir.is_empty() { "/" } else { dir }.to_string(),
+        (dir, rel) => format!("{}/{}", dir, rel),
+    }
+}
+
+/// Lines with `\` continuations joined and comments dropped, numbered by
+/// the line each instruction starts on.
+fn logical_lines(text: &str) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    for (i, raw) in text.lines().enumerate() {
+        let trimmed = raw.trim();
+        if trimmed.starts_with('#') || (trimmed.is_empty() && current.is_none()) {
+            continue;
+        }
+        let (content, continued) = match trimmed.strip_suffix('\\') {
+            Some(content) => (content.trim_end(), true),
+            None => (trimmed, false),
+        };
+        let (_, line) = current.get_or_insert_with(|| (i + 1, String::new()));
+        if !line.is_empty() && !content.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(content);
+        if !continued {
+            lines.extend(current.take());
+        }
+    }
+    lines.extend(current);
+    lines
+}
+
+/// Exec form (`["a", "b"]`) as is; shell form wrapped in `/bin/sh -c`.
+fn command_form(rest: &str) -> Vec<String> {
+    json_array(rest)
+        .unwrap_or_else(|| vec!["/bin/sh".to_string(), "-c".to_string(), rest.to_string()])
+}
+
+fn json_array(rest: &str) -> Option<Vec<String>> {
+    if !rest.starts_with('[') {
+        return None;
+    }
+    serde_json::from_str(rest).ok()
+}
+
+/// `KEY=value ...` pairs, or the legacy `KEY value` form.
+fn parse_env(rest: &str) -> Option<Vec<(String, String)>> {
+    let words = split_words(rest)?;
+    if !words[0].contains('=') {
+        let (key, value) = rest.split_once(char::is_whitespace)?;
+        return Some(vec![(key.to_string(), value.trim().to_string())]);
+    }
+    words
+        .into_iter()
+        .map(|word| {
+            let (key, value) = word.split_once('=')?;
+            (!key.is_empty()).then(|| (key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Split on whitespace, honouring double quotes and backslash escapes.
+fn split_words(s: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quoted = false;
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            '\\' => {
+                word.push(chars.next()?);
+                in_word = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quoted {
+        return None;
+    }
+    if in_word {
+        words.push(word);
+    }
+    Some(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_containerfile() {
+        let text = r#"
+# Build a tiny web app
+FROM alpine:3.19
+ENV APP_HOME=/srv/app GREETING="hello world"
+WORKDIR /srv/app
+RUN apk add --no-cache python3 && \
+    adduser -D app
+COPY app.py requirements.txt ./
+copy ["static", "/srv/app/static"]
+ENTRYPOINT ["python3", "app.py"]
+"#;
+        let sh = |cmd: &str| vec!["/bin/sh".to_string(), "-c".to_string(), cmd.to_string()];
+
+        assert_eq!(
+            parse_containerfile(text).unwrap(),
+            vec![
+                Instruction::From("alpine:3.19".into()),
+                Instruction::Env(vec![
+                    ("APP_HOME".into(), "/srv/app".into()),
+                    ("GREETING".into(), "hello world".into()),
+                ]),
+                Instruction::Workdir("/srv/app".into()),
+                Instruction::Run(sh("apk add --no-cache python3 && adduser -D app")),
+                Instruction::Copy {
+                    sources: vec!["app.py".into(), "requirements.txt".into()],
+                    dest: "./".into(),
+                },
+                Instruction::Copy {
+                    sources: vec!["static".into()],
+                    dest: "/srv/app/static".into(),
+                },
+                Instruction::Entrypoint(vec!["python3".into(), "app.py".into()]),
+            ]
+        );
+
+        let err = parse_containerfile("FROM alpine\nEXPOSE 8080\n").unwrap_err();
+        assert!(matches!(err, BoxliteError::Unsupported(_)), "{err}");
+        assert!(err.to_string().contains("line 2: EXPOSE"), "{err}");
+
+        let err = parse_containerfile("RUN true\n").unwrap_err();
+        assert!(matches!(err, BoxliteError::InvalidArgument(_)), "{err}");
+
+        let err = parse_containerfile("FROM alpine\nCOPY --chown=app a b\n").unwrap_err();
+        assert!(matches!(err, BoxliteError::Unsupported(_)), "{err}");
+
+        assert!(parse_containerfile("FROM a AS build\n").is_err());
+
+        let rendered: Vec<String> = parse_containerfile(text)
+            .unwrap()
+            .iter()
+            .map(Instruction::to_string)
+            .collect();
+        assert_eq!(
+            rendered,
+            [
+                "FROM alpine:3.19",
+                "ENV APP_HOME=/srv/app GREETING=hello world",
+                "WORKDIR /srv/app",
+                "RUN /bin/sh -c apk add --no-cache python3 && adduser -D app",
+                "COPY app.py requirements.txt ./",
+                "COPY static /srv/app/static",
+                r#"ENTRYPOINT ["python3","app.py"]"#,
+            ]
+        );
+        assert!(parse_containerfile("FROM a\nFROM b\n").is_err());
+    }
+}
@@ -58,6 +58,9 @@ pub mod filenames {
     /// Lock file name
     pub const LOCK_FILE: &str = ".lock";
 
+    /// Per-box console log file name
+    pub const CONSOLE_LOG: &str = "console.log";
+
     pub fn box_home(home_dir: &Path, box_id: &str) -> PathBuf {
         home_dir.join(dirs::BOXES_DIR).join(box_id)
     }
@@ -68,4 +71,9 @@ pub mod filenames {
             .join(dirs::SOCKETS_DIR)
             .join("box.sock")
     }
+
+    /// Network backend stats published by the shim: {box_home}/net-stats.json
+    pub fn net_stats_path(home_dir: &Path, box_id: &str) -> PathBuf {
+        box_home(home_dir, box_id).join("net-stats.json")
+    }
 }
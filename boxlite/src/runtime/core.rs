@@ -1,5 +1,6 @@
 //! High-level sandbox runtime structures.
 
+use std::path::Path;
 use std::sync::OnceLock;
 
 use crate::litebox::LiteBox;
@@ -7,8 +8,10 @@ use crate::metrics::RuntimeMetrics;
 use crate::runtime::options::{BoxOptions, BoxliteOptions};
 use crate::runtime::rt_impl::{RuntimeImpl, SharedRuntimeImpl};
 use crate::runtime::signal_handler::install_signal_handler;
-use crate::runtime::types::BoxInfo;
+use crate::runtime::types::{BoxID, BoxInfo, BoxStatus};
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use chrono::{DateTime, Utc};
+
 // ============================================================================
 // GLOBAL DEFAULT RUNTIME
 // ============================================================================
@@ -52,10 +55,17 @@ impl BoxliteRuntime {
     /// - Another `BoxliteRuntime` is already using the same home directory
     /// - Filesystem initialization fails
     /// - Image API initialization fails
+    ///
+    /// When `options.metrics_interval` (or `options.reap_interval`) is set
+    /// and a Tokio runtime is available, a background metrics sampler (or
+    /// expired box reaper) is started as well. A configured
+    /// `options.warm_pool` starts filling in the background the same way.
     pub fn new(options: BoxliteOptions) -> BoxliteResult<Self> {
-        Ok(Self {
-            rt_impl: RuntimeImpl::new(options)?,
-        })
+        let rt_impl = RuntimeImpl::new(options)?;
+        rt_impl.start_metrics_sampler();
+        rt_impl.start_reaper();
+        rt_impl.refill_warm_pool();
+        Ok(Self { rt_impl })
     }
 
     /// Create a new runtime with default options.
@@ -198,6 +208,59 @@ impl BoxliteRuntime {
         self.rt_impl.create(options, name).await
     }
 
+    /// Get the box named `name`, creating it if it does not exist.
+    ///
+    /// Returns the handle and `true` if the box was newly created. Fails with
+    /// `InvalidState` if a box with this name exists but was created with
+    /// different options. Safe to call concurrently with the same name.
+    ///
+    /// If `options` match the warm pool template, a ready box from the pool
+    /// is renamed to `name` and returned already running.
+    pub async fn get_or_create(
+        &self,
+        options: BoxOptions,
+        name: &str,
+    ) -> BoxliteResult<(LiteBox, bool)> {
+        self.rt_impl.get_or_create(options, name).await
+    }
+
+    /// Create a new box with the same options as an existing box.
+    ///
+    /// The clone has a fresh ID and its own state and disks; nothing from the
+    /// source's writable rootfs is shared.
+    pub async fn clone_box(
+        &self,
+        source_id_or_name: &str,
+        new_name: Option<String>,
+    ) -> BoxliteResult<LiteBox> {
+        self.rt_impl.clone_box(source_id_or_name, new_name).await
+    }
+
+    /// Build an image from a Containerfile/Dockerfile.
+    ///
+    /// Supports FROM, RUN, COPY, ENV, WORKDIR and ENTRYPOINT; other
+    /// directives return `BoxliteError::Unsupported`. The steps run in a
+    /// transient box whose changes are committed as one layer over the FROM
+    /// image. COPY sources are resolved in `context_dir`. Returns the new
+    /// image's reference, usable as `RootfsSpec::Image`.
+    pub async fn build(&self, context_dir: &Path, containerfile: &Path) -> BoxliteResult<String> {
+        self.rt_impl.build(context_dir, containerfile).await
+    }
+
+    /// Create a new box from a bundle written by `LiteBox::export_bundle`.
+    ///
+    /// The box gets a fresh ID and `name`, with the bundled options and disk.
+    /// The box is `Configured`; if the bundle has a disk, `start()` boots it
+    /// with the exported filesystem. Returns
+    /// `BoxliteError::InvalidArgument` for a malformed bundle.
+    pub async fn import_bundle(
+        &self,
+        bundle: &Path,
+        name: Option<String>,
+    ) -> BoxliteResult<LiteBox> {
+        self.rt_impl.import_bundle(bundle, name).await
+    }
+
     /// Get a handle to an existing box by ID or name.
     ///
     /// The `id_or_name` parameter can be either:
@@ -217,6 +280,33 @@ impl BoxliteRuntime {
         self.rt_impl.list_info().await
     }
 
+    /// List boxes that carry every `(key, value)` label in `label_selector`.
+    pub async fn list_info_filtered(
+        &self,
+        label_selector: &[(String, String)],
+    ) -> BoxliteResult<Vec<BoxInfo>> {
+        self.rt_impl.list_info_filtered(label_selector).await
+    }
+
+    /// List boxes whose status is one of `statuses`.
+    ///
+    /// Cheaper than `list_info` when only a few states are of interest, since
+    /// non-matching boxes are never loaded from the database.
+    pub async fn list_by_status(&self, statuses: &[BoxStatus]) -> BoxliteResult<Vec<BoxInfo>> {
+        self.rt_impl.list_by_status(statuses).await
+    }
+
+    /// List boxes created in `[start, end)`, newest first.
+    ///
+    /// Useful for cleanup jobs that target boxes older than some cutoff.
+    pub async fn list_created_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> BoxliteResult<Vec<BoxInfo>> {
+        self.rt_impl.list_created_between(start, end).await
+    }
+
     /// Check if a box with the given ID or name exists.
     pub async fn exists(&self, id_or_name: &str) -> BoxliteResult<bool> {
         self.rt_impl.exists(id_or_name).await
@@ -227,9 +317,65 @@ impl BoxliteRuntime {
         self.rt_impl.metrics().await
     }
 
+    /// Get a handle to the box running the container with the given ID.
+    ///
+    /// Useful for correlating guest-side logs, which only know the container
+    /// ID. Returns `None` if no box has that container.
+    pub async fn get_by_container_id(&self, container_id: &str) -> BoxliteResult<Option<LiteBox>> {
+        self.rt_impl.get_by_container_id(container_id).await
+    }
+
     /// Remove a box completely by ID or name.
     pub async fn remove(&self, id_or_name: &str, force: bool) -> BoxliteResult<()> {
-        self.rt_impl.remove(id_or_name, force)
+        self.rt_impl.remove(id_or_name, force).await
+    }
+
+    /// Stop every box that isn't already stopped.
+    ///
+    /// Continues past individual failures and returns a result per box.
+    pub async fn stop_all(&self) -> BoxliteResult<Vec<(BoxID, BoxliteResult<()>)>> {
+        self.rt_impl.stop_all().await
+    }
+
+    /// Remove every box.
+    ///
+    /// With `force=false`, active boxes fail individually while the rest are
+    /// still removed. Returns a result per box.
+    pub async fn remove_all(&self, force: bool) -> BoxliteResult<Vec<(BoxID, BoxliteResult<()>)>> {
+        self.rt_impl.remove_all(force).await
+    }
+
+    /// Stop and remove every box whose `BoxOptions::ttl` has run out.
+    ///
+    /// Returns the IDs of the removed boxes.
+    pub async fn reap_expired(&self) -> BoxliteResult<Vec<BoxID>> {
+        self.rt_impl.reap_expired().await
+    }
+
+    /// Send `signal` to the main process of every running box with a live handle.
+    ///
+    /// Returns how many boxes were signalled.
+    pub async fn signal_running_boxes(&self, signal: i32) -> usize {
+        self.rt_impl.signal_running_boxes(signal).await
+    }
+
+    /// Forward host SIGTERM/SIGINT to all running boxes.
+    ///
+    /// Installs handlers that relay each signal via
+    /// [`signal_running_boxes`](Self::signal_running_boxes), so the box
+    /// entrypoints can shut down gracefully along with the host process.
+    /// Installing replaces the default terminate-on-signal behavior; forwarding
+    /// stops at `shutdown()` or when the runtime is dropped. Must be called
+    /// from within a Tokio runtime.
+    pub fn forward_signals(&self) -> BoxliteResult<()> {
+        self.rt_impl.forward_signals()
+    }
+
+    /// Rename a box by ID or name.
+    ///
+    /// Fails with `InvalidArgument` if another box already uses `new_name`.
+    pub async fn rename(&self, id_or_name: &str, new_name: &str) -> BoxliteResult<()> {
+        self.rt_impl.rename(id_or_name, new_name)
     }
 
     // ========================================================================
@@ -276,6 +422,17 @@ impl BoxliteRuntime {
         self.rt_impl.shutdown(timeout).await
     }
 
+    /// Shut down the runtime and release its home directory.
+    ///
+    /// A deterministic teardown point for embedders: sampled metrics are
+    /// flushed and boxes are stopped as by `shutdown(None)`, boxes started this session that no handle can
+    /// stop anymore are killed, and the `BOXLITE_HOME` lock is released
+    /// before returning, so a new runtime can open the same home directory
+    /// right away, even while old `LiteBox` handles are still alive.
+    pub async fn close(self) -> BoxliteResult<()> {
+        self.rt_impl.close().await
+    }
+
     // ========================================================================
     // IMAGE OPERATIONS (delegate to ImageManager)
     // ========================================================================
@@ -298,6 +455,40 @@ impl BoxliteRuntime {
         self.rt_impl.image_manager.pull(image_ref).await
     }
 
+    /// Pull an image, calling `progress` as layer bytes are downloaded.
+    ///
+    /// Events carry per-layer `bytes_done`/`bytes_total`; layers download
+    /// concurrently, so events for different layers interleave. Layers
+    /// already on disk are reported once as complete; a fully cached image
+    /// reports nothing.
+    pub async fn pull_image_with_progress(
+        &self,
+        image_ref: &str,
+        progress: &crate::images::PullProgressFn<'_>,
+    ) -> BoxliteResult<crate::images::ImageObject> {
+        let options = crate::images::PullOptions {
+            progress: Some(progress),
+            ..Default::default()
+        };
+        self.pull_image_with(image_ref, &options).await
+    }
+
+    /// Pull an image with explicit options.
+    ///
+    /// `options.platform` (e.g. `"linux/arm64"`) picks the entry of a
+    /// multi-arch image; unset, the host platform is used. Fails with
+    /// `BoxliteError::Image` if the image has no entry for the platform.
+    pub async fn pull_image_with(
+        &self,
+        image_ref: &str,
+        options: &crate::images::PullOptions<'_>,
+    ) -> BoxliteResult<crate::images::ImageObject> {
+        self.rt_impl
+            .image_manager
+            .pull_with(image_ref, options)
+            .await
+    }
+
     /// List all cached images.
     ///
     /// Returns a list of images available in the local content store.
@@ -310,6 +501,45 @@ impl BoxliteRuntime {
     pub async fn list_images(&self) -> BoxliteResult<Vec<crate::runtime::types::ImageInfo>> {
         self.rt_impl.image_manager.list().await
     }
+
+    /// Show details of a cached image.
+    ///
+    /// `reference` is resolved like an image reference passed to
+    /// `BoxOptions::rootfs`, but only against the local store: nothing is
+    /// pulled.
+    ///
+    /// # Returns
+    ///
+    /// Returns the image's `ImageInfo` together with its layer digests and
+    /// OCI config, or `BoxliteError::NotFound` if the image is not cached.
+    pub async fn inspect_image(
+        &self,
+        reference: &str,
+    ) -> BoxliteResult<crate::runtime::types::ImageDetails> {
+        self.rt_impl.image_manager.inspect(reference).await
+    }
+
+    /// Remove a cached image and the layers no other image uses.
+    ///
+    /// Refuses with `BoxliteError::InvalidState` while any box was created
+    /// from the image, unless `force` is set; forced removal only logs a
+    /// warning, and those boxes pull the image again when they next need it.
+    /// Returns `BoxliteError::NotFound` if the image is not cached.
+    pub async fn remove_image(&self, reference: &str, force: bool) -> BoxliteResult<()> {
+        self.rt_impl.remove_image(reference, force).await
+    }
+
+    /// Delete cached image layers that no cached image references.
+    ///
+    /// Layers are stored once per digest and shared between images, so a
+    /// layer stays on disk as long as at least one image lists it.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of layers removed.
+    pub async fn prune_images(&self) -> BoxliteResult<usize> {
+        self.rt_impl.image_manager.prune().await
+    }
 }
 
 // ============================================================================
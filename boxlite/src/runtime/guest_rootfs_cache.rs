@@ -0,0 +1,335 @@
+//! Content-addressed cache of built guest rootfs disks.
+//!
+//! Building the guest rootfs (merge init image layers, inject the guest
+//! agent, pack into ext4) is slow, so the result is cached under
+//! `images/guest-rootfs/{key}/` where `key` hashes every build input:
+//!
+//! ```text
+//! images/guest-rootfs/
+//! └── {key}/
+//!     ├── rootfs.ext4     # Packed guest rootfs (shared read-only base)
+//!     └── manifest.json   # Build inputs; written last, marks the entry complete
+//! ```
+//!
+//! An entry is only reused when its manifest exists and records the same
+//! key; anything else is discarded and rebuilt.
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::disk::Disk;
+
+/// Bump when the way the guest rootfs is assembled changes.
+const CACHE_FORMAT_VERSION: u32 = 1;
+
+const DISK_FILENAME: &str = "rootfs.ext4";
+const MANIFEST_FILENAME: &str = "manifest.json";
+
+/// Everything that determines the content of a built guest rootfs.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct GuestRootfsInputs {
+    /// Digest of the init image layers
+    pub image_digest: String,
+    /// SHA-256 of the injected `boxlite-guest` binary
+    pub guest_binary_sha256: String,
+    /// SHA-256 of a separate kernel image, if one is baked in
+    pub kernel_sha256: Option<String>,
+}
+
+impl GuestRootfsInputs {
+    /// Cache key: hex SHA-256 over the format version and all inputs.
+    pub fn key(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(CACHE_FORMAT_VERSION.to_be_bytes());
+        for part in [
+            self.image_digest.as_str(),
+            self.guest_binary_sha256.as_str(),
+            self.kernel_sha256.as_deref().unwrap_or(""),
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0u8]);
+        }
+        hex::encode(hasher.finalize())
+    }
+}
+
+/// On-disk record of a cache entry.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheManifest {
+    key: String,
+    format_version: u32,
+    inputs: GuestRootfsInputs,
+}
+
+/// Cache of built guest rootfs disks, keyed by [`GuestRootfsInputs::key`].
+pub(crate) struct GuestRootfsCache {
+    root: PathBuf,
+}
+
+impl GuestRootfsCache {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Path of the cached disk for `inputs`, if a valid entry exists.
+    pub fn lookup(&self, inputs: &GuestRootfsInputs) -> Option<PathBuf> {
+        let key = inputs.key();
+        let entry = self.root.join(&key);
+        let manifest: CacheManifest = std::fs::read(entry.join(MANIFEST_FILENAME))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())?;
+
+        let disk_path = entry.join(DISK_FILENAME);
+        if manifest.key != key
+            || manifest.format_version != CACHE_FORMAT_VERSION
+            || manifest.inputs != *inputs
+            || !disk_path.is_file()
+        {
+            tracing::warn!(
+                entry = %entry.display(),
+                "Discarding invalid guest rootfs cache entry"
+            );
+            return None;
+        }
+        Some(disk_path)
+    }
+
+    /// Return the cached disk for `inputs`, running `build` only on a miss.
+    ///
+    /// `build` must produce the disk on the same filesystem as the cache
+    /// (e.g. under the runtime temp dir) so it can be moved into place.
+    pub async fn get_or_build<F, Fut>(
+        &self,
+        inputs: &GuestRootfsInputs,
+        build: F,
+    ) -> BoxliteResult<PathBuf>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = BoxliteResult<Disk>>,
+    {
+        if let Some(disk_path) = self.lookup(inputs) {
+            tracing::info!(
+                disk = %disk_path.display(),
+                "Using cached guest rootfs"
+            );
+            return Ok(disk_path);
+        }
+
+        let disk = build().await?;
+        self.install(inputs, disk)
+    }
+
+    /// Move a freshly built disk into the cache and record its manifest.
+    ///
+    /// The entry is assembled in a staging directory and renamed into place,
+    /// so readers never see a partial entry. If another runtime installed a
+    /// valid entry meanwhile, that one is kept and this build is discarded.
+    fn install(&self, inputs: &GuestRootfsInputs, disk: Disk) -> BoxliteResult<PathBuf> {
+        let key = inputs.key();
+        let entry = self.root.join(&key);
+        let storage_err = |what: &str, path: &Path, e: std::io::Error| {
+            BoxliteError::Storage(format!("Failed to {} {}: {}", what, path.display(), e))
+        };
+
+        std::fs::create_dir_all(&self.root)
+            .map_err(|e| storage_err("create cache directory", &self.root, e))?;
+        let staging = tempfile::Builder::new()
+            .prefix(&format!(".{}-", key))
+            .tempdir_in(&self.root)
+            .map_err(|e| storage_err("create staging directory in", &self.root, e))?;
+
+        let built = disk.leak();
+        let staged_disk = staging.path().join(DISK_FILENAME);
+        crate::util::move_file(&built, &staged_disk)
+            .map_err(|e| storage_err("install guest rootfs disk", &staged_disk, e))?;
+
+        let manifest = CacheManifest {
+            key,
+            format_version: CACHE_FORMAT_VERSION,
+            inputs: inputs.clone(),
+        };
+        let manifest_json = serde_json::to_vec_pretty(&manifest).map_err(|e| {
+            BoxliteError::Internal(format!("Failed to serialize cache manifest: {}", e))
+        })?;
+        let manifest_path = staging.path().join(MANIFEST_FILENAME);
+        std::fs::write(&manifest_path, manifest_json)
+            .map_err(|e| storage_err("write cache manifest", &manifest_path, e))?;
+
+        if let Some(disk_path) = self.lookup(inputs) {
+            return Ok(disk_path);
+        }
+        // Move an invalid leftover aside; it can't be renamed over
+        if entry.exists() {
+            let stale = tempfile::Builder::new()
+                .prefix(&format!(".{}-stale-", manifest.key))
+                .tempdir_in(&self.root)
+                .map_err(|e| storage_err("create staging directory in", &self.root, e))?;
+            std::fs::rename(&entry, stale.path())
+                .map_err(|e| storage_err("move aside stale cache entry", &entry, e))?;
+        }
+
+        let staged = staging.keep();
+        if let Err(e) = std::fs::rename(&staged, &entry) {
+            let _ = std::fs::remove_dir_all(&staged);
+            // Lost the race against a concurrent install
+            return self
+                .lookup(inputs)
+                .ok_or_else(|| storage_err("install cache entry", &entry, e));
+        }
+
+        let disk_path = entry.join(DISK_FILENAME);
+        tracing::info!(
+            disk = %disk_path.display(),
+            "Installed guest rootfs into cache"
+        );
+        Ok(disk_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::disk::DiskFormat;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn inputs(guest: &str) -> GuestRootfsInputs {
+        GuestRootfsInputs {
+            image_digest: "sha256:init".to_string(),
+            guest_binary_sha256: guest.to_string(),
+            kernel_sha256: None,
+        }
+    }
+
+    async fn build_into(dir: &Path, builds: &AtomicUsize) -> BoxliteResult<Disk> {
+        let n = builds.fetch_add(1, Ordering::SeqCst);
+        let path = dir.join(format!("build-{}.ext4", n));
+        std::fs::write(&path, format!("rootfs #{}", n)).unwrap();
+        Ok(Disk::new(path, DiskFormat::Ext4, false))
+    }
+
+    #[tokio::test]
+    async fn test_identical_inputs_reuse_cached_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = GuestRootfsCache::new(dir.path().join("guest-rootfs"));
+        let builds = AtomicUsize::new(0);
+
+        let first = cache
+            .get_or_build(&inputs("abc"), || build_into(dir.path(), &builds))
+            .await
+            .unwrap();
+        let mtime = std::fs::metadata(&first).unwrap().modified().unwrap();
+
+        // A new cache over the same directory (e.g. the next runtime)
+        let cache = GuestRootfsCache::new(dir.path().join("guest-rootfs"));
+        let second = cache
+            .get_or_build(&inputs("abc"), || build_into(dir.path(), &builds))
+            .await
+            .unwrap();
+
+        assert_eq!(builds.load(Ordering::SeqCst), 1);
+        assert_eq!(first, second);
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), "rootfs #0");
+        assert_eq!(
+            std::fs::metadata(&second).unwrap().modified().unwrap(),
+            mtime
+        );
+    }
+
+    #[tokio::test]
+    async fn test_changed_inputs_rebuild() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = GuestRootfsCache::new(dir.path().join("guest-rootfs"));
+        let builds = AtomicUsize::new(0);
+
+        let old = cache
+            .get_or_build(&inputs("abc"), || build_into(dir.path(), &builds))
+            .await
+            .unwrap();
+        let new = cache
+            .get_or_build(&inputs("def"), || build_into(dir.path(), &builds))
+            .await
+            .unwrap();
+
+        assert_eq!(builds.load(Ordering::SeqCst), 2);
+        assert_ne!(old, new);
+    }
+
+    #[tokio::test]
+    async fn test_install_keeps_existing_valid_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("guest-rootfs");
+        let cache = GuestRootfsCache::new(root.clone());
+        let builds = AtomicUsize::new(0);
+
+        let path = cache
+            .get_or_build(&inputs("abc"), || build_into(dir.path(), &builds))
+            .await
+            .unwrap();
+
+        // A concurrent runtime finishing its own build of the same inputs
+        let late = build_into(dir.path(), &builds).await.unwrap();
+        let late_path = late.path().to_path_buf();
+        assert_eq!(cache.install(&inputs("abc"), late).unwrap(), path);
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "rootfs #0");
+        assert!(!late_path.exists());
+        let entries: Vec<_> = std::fs::read_dir(&root).unwrap().collect();
+        assert_eq!(entries.len(), 1, "staging directory left behind");
+    }
+
+    #[tokio::test]
+    async fn test_install_from_temp_on_another_filesystem() {
+        use crate::runtime::layout::{FilesystemLayout, FsLayoutConfig, LayoutDirs};
+
+        // Only images moves (to tmpfs where available); temp stays under home
+        let home = tempfile::tempdir().unwrap();
+        let images =
+            tempfile::tempdir_in("/dev/shm").unwrap_or_else(|_| tempfile::tempdir().unwrap());
+        let layout = FilesystemLayout::new(
+            home.path().to_path_buf(),
+            FsLayoutConfig::default().with_dirs(LayoutDirs {
+                images: Some(images.path().to_path_buf()),
+                ..Default::default()
+            }),
+        );
+        layout.prepare().unwrap();
+
+        let cache = GuestRootfsCache::new(layout.image_layout().guest_rootfs_dir());
+        let temp = layout.temp_dir();
+        let builds = AtomicUsize::new(0);
+        let path = cache
+            .get_or_build(&inputs("abc"), || build_into(&temp, &builds))
+            .await
+            .unwrap();
+
+        assert!(path.starts_with(images.path()));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "rootfs #0");
+        assert_eq!(std::fs::read_dir(&temp).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_manifest_is_rebuilt() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = GuestRootfsCache::new(dir.path().join("guest-rootfs"));
+        let builds = AtomicUsize::new(0);
+
+        let path = cache
+            .get_or_build(&inputs("abc"), || build_into(dir.path(), &builds))
+            .await
+            .unwrap();
+        let manifest = path.parent().unwrap().join(MANIFEST_FILENAME);
+        std::fs::write(&manifest, "{ not json").unwrap();
+        assert!(cache.lookup(&inputs("abc")).is_none());
+
+        cache
+            .get_or_build(&inputs("abc"), || build_into(dir.path(), &builds))
+            .await
+            .unwrap();
+        assert_eq!(builds.load(Ordering::SeqCst), 2);
+        assert_eq!(cache.lookup(&inputs("abc")), Some(path));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::runtime::constants::filenames;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use boxlite_shared::layout::{SharedGuestLayout, dirs as shared_dirs};
 use std::path::{Path, PathBuf};
@@ -43,9 +44,66 @@ pub mod dirs {
     pub const LOCKS_DIR: &str = "locks";
 }
 
+/// Version of the home directory layout written to `VERSION`.
+///
+/// Bump it when the layout changes and register a migration from the
+/// previous version in `runtime::migrations`.
+pub const LAYOUT_VERSION: u32 = 1;
+
+/// Absolute paths that replace home-relative subdirectories.
+///
+/// Each `None` keeps the default under the home directory. Box directories
+/// always stay under `{home}/boxes`: the shim and the jailer locate the home
+/// directory from a box directory.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LayoutDirs {
+    /// Database directory (default: `{home}/db`).
+    #[serde(default)]
+    pub db: Option<PathBuf>,
+    /// OCI image store, including cached rootfs disks (default: `{home}/images`).
+    #[serde(default)]
+    pub images: Option<PathBuf>,
+    /// Runtime and shim logs (default: `{home}/logs`).
+    #[serde(default)]
+    pub logs: Option<PathBuf>,
+    /// Per-entity lock files (default: `{home}/locks`).
+    #[serde(default)]
+    pub locks: Option<PathBuf>,
+    /// Transient files (default: `{home}/tmp`). Results are renamed into
+    /// `images` and `boxes`, or copied when this is on another filesystem,
+    /// so keep it on the same one as `images` to avoid the copy.
+    #[serde(default)]
+    pub temp: Option<PathBuf>,
+}
+
+impl LayoutDirs {
+    /// Check that every override is an absolute path.
+    pub fn validate(&self) -> BoxliteResult<()> {
+        let overrides = [
+            ("db", &self.db),
+            ("images", &self.images),
+            ("logs", &self.logs),
+            ("locks", &self.locks),
+            ("temp", &self.temp),
+        ];
+        for (name, path) in overrides {
+            if let Some(path) = path
+                && !path.is_absolute()
+            {
+                return Err(BoxliteError::InvalidArgument(format!(
+                    "{name} directory must be an absolute path, got: {}",
+                    path.display()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Configuration for filesystem layout behavior.
 ///
-/// Controls platform-specific filesystem features like bind mounts.
+/// Controls platform-specific filesystem features like bind mounts, and
+/// where subdirectories live.
 #[derive(Clone, Debug, Default)]
 pub struct FsLayoutConfig {
     /// Whether bind mount is supported on this platform.
@@ -53,6 +111,8 @@ pub struct FsLayoutConfig {
     /// - `true`: Use bind mount (mounts/ → shared/), expose shared/ to guest
     /// - `false`: Skip bind mount, expose mounts/ directly to guest
     bind_mount_supported: bool,
+    /// Subdirectory overrides.
+    dirs: LayoutDirs,
 }
 
 impl FsLayoutConfig {
@@ -60,6 +120,7 @@ impl FsLayoutConfig {
     pub fn with_bind_mount() -> Self {
         Self {
             bind_mount_supported: true,
+            dirs: LayoutDirs::default(),
         }
     }
 
@@ -67,13 +128,25 @@ impl FsLayoutConfig {
     pub fn without_bind_mount() -> Self {
         Self {
             bind_mount_supported: false,
+            dirs: LayoutDirs::default(),
         }
     }
 
+    /// Place subdirectories at the given absolute paths instead of under the home.
+    pub fn with_dirs(mut self, dirs: LayoutDirs) -> Self {
+        self.dirs = dirs;
+        self
+    }
+
     /// Check if bind mount is supported.
     pub fn is_bind_mount_supported(&self) -> bool {
         self.bind_mount_supported
     }
+
+    /// Subdirectory overrides.
+    pub fn dirs(&self) -> &LayoutDirs {
+        &self.dirs
+    }
 }
 
 // ============================================================================
@@ -95,16 +168,21 @@ impl FilesystemLayout {
         &self.home_dir
     }
 
+    /// Subdirectory overrides this layout was built with.
+    pub fn dirs(&self) -> &LayoutDirs {
+        self.config.dirs()
+    }
+
     pub fn db_dir(&self) -> PathBuf {
-        self.home_dir.join(dirs::DB_DIR)
+        self.subdir(&self.config.dirs.db, dirs::DB_DIR)
     }
 
     pub fn images_dir(&self) -> PathBuf {
-        self.home_dir.join(dirs::IMAGES_DIR)
+        self.subdir(&self.config.dirs.images, dirs::IMAGES_DIR)
     }
 
     pub fn logs_dir(&self) -> PathBuf {
-        self.home_dir.join(dirs::LOGS_DIR)
+        self.subdir(&self.config.dirs.logs, dirs::LOGS_DIR)
     }
 
     /// OCI images layers storage: ~/.boxlite/images/layers
@@ -128,19 +206,66 @@ impl FilesystemLayout {
     /// Contains lock files managed by FileLockManager for multiprocess-safe
     /// locking of individual entities (boxes, volumes, etc.).
     pub fn locks_dir(&self) -> PathBuf {
-        self.home_dir.join(dirs::LOCKS_DIR)
+        self.subdir(&self.config.dirs.locks, dirs::LOCKS_DIR)
     }
 
     /// Temporary directory for transient files: ~/.boxlite/tmp
-    /// Used for disk image creation and other operations that need
-    /// temp files on the same filesystem as the final destination.
+    /// Used for disk image creation and other operations whose results are
+    /// moved into `images` or `boxes` afterwards.
     pub fn temp_dir(&self) -> PathBuf {
-        self.home_dir.join("tmp")
+        self.subdir(&self.config.dirs.temp, "tmp")
+    }
+
+    /// Configured override, or `name` under the home directory.
+    fn subdir(&self, configured: &Option<PathBuf>, name: &str) -> PathBuf {
+        configured
+            .clone()
+            .unwrap_or_else(|| self.home_dir.join(name))
+    }
+
+    /// Layout version file: ~/.boxlite/VERSION
+    pub fn version_path(&self) -> PathBuf {
+        self.home_dir.join("VERSION")
+    }
+
+    /// Layout version recorded in `VERSION`, or `None` if there is no file.
+    pub fn layout_version(&self) -> BoxliteResult<Option<u32>> {
+        let path = self.version_path();
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(BoxliteError::Storage(format!(
+                    "failed to read {}: {e}",
+                    path.display()
+                )));
+            }
+        };
+        contents.trim().parse().map(Some).map_err(|e| {
+            BoxliteError::Storage(format!(
+                "invalid layout version {:?} in {}: {e}",
+                contents.trim(),
+                path.display()
+            ))
+        })
+    }
+
+    /// Record `version` in `VERSION`, replacing the file atomically.
+    pub(crate) fn write_layout_version(&self, version: u32) -> BoxliteResult<()> {
+        let path = self.version_path();
+        let tmp = self.home_dir.join("VERSION.tmp");
+        std::fs::write(&tmp, format!("{version}\n"))
+            .and_then(|()| std::fs::rename(&tmp, &path))
+            .map_err(|e| BoxliteError::Storage(format!("failed to write {}: {e}", path.display())))
     }
 
     /// Initialize the filesystem structure.
     ///
-    /// Creates necessary directories (home_dir, sockets, images, etc.).
+    /// Creates necessary directories (home_dir, sockets, images, overridden
+    /// subdirectories, etc.) and
+    /// stamps a new home with [`LAYOUT_VERSION`]. A home that already has a
+    /// database but no `VERSION` predates versioning and is left for the
+    /// migrations to stamp.
     pub fn prepare(&self) -> BoxliteResult<()> {
         std::fs::create_dir_all(&self.home_dir)
             .map_err(|e| BoxliteError::Storage(format!("failed to create home: {e}")))?;
@@ -157,6 +282,18 @@ impl FilesystemLayout {
         std::fs::create_dir_all(self.image_manifests_dir())
             .map_err(|e| BoxliteError::Storage(format!("failed to create manifests dir: {e}")))?;
 
+        if !self.version_path().exists() && !self.db_dir().exists() {
+            self.write_layout_version(LAYOUT_VERSION)?;
+        }
+
+        // Overridden directories may live on volumes that start out empty
+        let dirs = &self.config.dirs;
+        for dir in [&dirs.db, &dirs.logs, &dirs.locks].into_iter().flatten() {
+            std::fs::create_dir_all(dir).map_err(|e| {
+                BoxliteError::Storage(format!("failed to create {}: {e}", dir.display()))
+            })?;
+        }
+
         Ok(())
     }
 
@@ -222,7 +359,7 @@ impl FilesystemLayout {
 /// │           │   └── work/   # Overlayfs work
 /// │           └── rootfs/     # Final rootfs (overlayfs merged)
 /// ├── shared/             # Guest-visible (ro bind mount → mounts/)
-/// ├── root.qcow2          # Data disk
+/// ├── disk.qcow2          # Data disk
 /// └── console.log         # Kernel/init output
 /// ```
 #[derive(Clone, Debug)]
@@ -325,16 +462,38 @@ impl BoxFilesystemLayout {
         self.box_dir.join("disk.qcow2")
     }
 
+    /// Base disk packed from a directory rootfs: ~/.boxlite/boxes/{box_id}/rootfs-base.ext4
+    ///
+    /// Read-only backing file of `disk.qcow2` for `RootfsSpec::Directory` boxes.
+    pub fn rootfs_base_path(&self) -> PathBuf {
+        self.box_dir.join("rootfs-base.ext4")
+    }
+
+    /// Base disk unpacked from a bundle: ~/.boxlite/boxes/{box_id}/imported-base.raw
+    ///
+    /// Read-only backing file of `disk.qcow2` for boxes created by `import_bundle`.
+    pub fn imported_base_path(&self) -> PathBuf {
+        self.box_dir.join("imported-base.raw")
+    }
+
+    /// Disk snapshots directory: ~/.boxlite/boxes/{box_id}/snapshots
+    ///
+    /// Holds `<name>.qcow2` copies of `disk.qcow2` and their `<name>.json` metadata.
+    pub fn snapshots_dir(&self) -> PathBuf {
+        self.box_dir.join("snapshots")
+    }
+
     /// Console output path: ~/.boxlite/boxes/{box_id}/console.log
     ///
     /// Captures kernel and init output for debugging.
     pub fn console_output_path(&self) -> PathBuf {
-        self.box_dir.join("console.log")
+        self.box_dir.join(filenames::CONSOLE_LOG)
     }
 
     /// PID file path: ~/.boxlite/boxes/{box_id}/shim.pid
     ///
-    /// Written by the shim process in pre_exec (after fork, before exec).
+    /// Written by the shim process in pre_exec (after fork, before exec),
+    /// then again by the parent once the spawn has succeeded.
     /// This is the single source of truth for the shim process PID.
     /// Database PID is a cache that can be reconstructed from this file.
     pub fn pid_file_path(&self) -> PathBuf {
@@ -390,6 +549,7 @@ impl BoxFilesystemLayout {
 /// - layers/: Downloaded layer tarballs
 /// - extracted/: Extracted layer directories
 /// - disk-images/: Cached disk images for COW
+/// - guest-rootfs/: Cached guest rootfs disks keyed by build inputs
 /// - manifests/: Image manifests
 /// - configs/: Image configs
 #[derive(Clone, Debug)]
@@ -422,6 +582,13 @@ impl ImageFilesystemLayout {
         self.images_dir.join("disk-images")
     }
 
+    /// Guest rootfs cache directory: ~/.boxlite/images/guest-rootfs
+    ///
+    /// Built guest rootfs disks, keyed by their build inputs.
+    pub fn guest_rootfs_dir(&self) -> PathBuf {
+        self.images_dir.join("guest-rootfs")
+    }
+
     /// Manifests directory: ~/.boxlite/images/manifests
     pub fn manifests_dir(&self) -> PathBuf {
         self.images_dir.join(dirs::MANIFESTS_DIR)
@@ -452,3 +619,28 @@ impl ImageFilesystemLayout {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_images_dir_override_outside_home() {
+        let home = tempfile::tempdir().unwrap();
+        let disk = tempfile::tempdir().unwrap();
+        let images = disk.path().join("boxlite-images");
+        let layout = FilesystemLayout::new(
+            home.path().to_path_buf(),
+            FsLayoutConfig::default().with_dirs(LayoutDirs {
+                images: Some(images.clone()),
+                ..Default::default()
+            }),
+        );
+
+        layout.prepare().unwrap();
+        assert_eq!(layout.images_dir(), images);
+        assert!(images.join(dirs::LAYERS_DIR).is_dir());
+        assert!(!home.path().join(dirs::IMAGES_DIR).exists());
+        assert_eq!(layout.logs_dir(), home.path().join(dirs::LOGS_DIR));
+    }
+}
@@ -64,7 +64,7 @@ impl RuntimeLock {
             if result != 0 {
                 let err = std::io::Error::last_os_error();
                 if err.kind() == std::io::ErrorKind::WouldBlock {
-                    return Err(BoxliteError::Internal(format!(
+                    return Err(BoxliteError::lock_contention(format!(
                         "Another BoxliteRuntime is already using directory: {}\n\
                          Only one runtime instance can use a BOXLITE_HOME directory at a time.",
                         home_dir.display()
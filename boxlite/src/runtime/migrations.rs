@@ -0,0 +1,128 @@
+//! Home directory layout migrations.
+//!
+//! `FilesystemLayout` records its layout version in `~/.boxlite/VERSION`.
+//! On startup, homes written by an older release are brought up to
+//! [`LAYOUT_VERSION`] one step at a time, recording the version after each
+//! step so an interrupted upgrade resumes where it stopped.
+
+use crate::runtime::layout::{FilesystemLayout, LAYOUT_VERSION};
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+
+/// One upgrade step of the home directory layout.
+pub(crate) struct LayoutMigration {
+    /// Version this migration upgrades from; it leaves the layout at `from + 1`.
+    pub from: u32,
+    pub description: &'static str,
+    pub run: fn(&FilesystemLayout) -> BoxliteResult<()>,
+}
+
+/// Registered migrations, one per layout version below [`LAYOUT_VERSION`].
+const LAYOUT_MIGRATIONS: &[LayoutMigration] = &[LayoutMigration {
+    from: 0,
+    description: "adopt home directory created before layout versioning",
+    run: adopt_unversioned,
+}];
+
+/// Version 1 is the layout that shipped before `VERSION` existed.
+fn adopt_unversioned(_layout: &FilesystemLayout) -> BoxliteResult<()> {
+    Ok(())
+}
+
+/// Bring the home directory up to [`LAYOUT_VERSION`].
+///
+/// Must run with the runtime lock held, after `FilesystemLayout::prepare`.
+pub(crate) fn migrate_layout(layout: &FilesystemLayout) -> BoxliteResult<()> {
+    run_migrations(layout, LAYOUT_MIGRATIONS, LAYOUT_VERSION)
+}
+
+fn run_migrations(
+    layout: &FilesystemLayout,
+    migrations: &[LayoutMigration],
+    target: u32,
+) -> BoxliteResult<()> {
+    // prepare() stamps new homes, so a missing file means a pre-versioning home
+    let mut version = layout.layout_version()?.unwrap_or(0);
+
+    if version > target {
+        return Err(BoxliteError::Unsupported(format!(
+            "home directory {} uses layout version {}, but this build supports up to {}; \
+             upgrade boxlite or use a different home_dir",
+            layout.home_dir().display(),
+            version,
+            target
+        )));
+    }
+
+    while version < target {
+        let migration = migrations
+            .iter()
+            .find(|m| m.from == version)
+            .ok_or_else(|| {
+                BoxliteError::Internal(format!("no layout migration from version {}", version))
+            })?;
+
+        tracing::info!(
+            from = version,
+            to = version + 1,
+            migration = migration.description,
+            "Migrating home directory layout"
+        );
+        (migration.run)(layout).map_err(|e| {
+            BoxliteError::Storage(format!(
+                "Layout migration from version {} ({}) failed: {}",
+                version, migration.description, e
+            ))
+        })?;
+
+        version += 1;
+        layout.write_layout_version(version)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::layout::FsLayoutConfig;
+
+    fn move_cache_dir(layout: &FilesystemLayout) -> BoxliteResult<()> {
+        let home = layout.home_dir();
+        std::fs::rename(home.join("cache"), home.join("images/cache"))
+            .map_err(|e| BoxliteError::Storage(e.to_string()))
+    }
+
+    #[test]
+    fn test_old_version_runs_migrations_and_updates_version() {
+        let home = tempfile::tempdir().unwrap();
+        let layout = FilesystemLayout::new(home.path().to_path_buf(), FsLayoutConfig::default());
+        layout.prepare().unwrap();
+        assert_eq!(layout.layout_version().unwrap(), Some(LAYOUT_VERSION));
+
+        layout.write_layout_version(1).unwrap();
+        std::fs::create_dir(home.path().join("cache")).unwrap();
+        let migrations = [
+            LayoutMigration {
+                from: 1,
+                description: "move cache under images",
+                run: move_cache_dir,
+            },
+            LayoutMigration {
+                from: 2,
+                description: "no-op",
+                run: adopt_unversioned,
+            },
+        ];
+
+        run_migrations(&layout, &migrations, 3).unwrap();
+        assert!(home.path().join("images/cache").is_dir());
+        assert!(!home.path().join("cache").exists());
+        assert_eq!(layout.layout_version().unwrap(), Some(3));
+
+        // Up to date: nothing runs again
+        run_migrations(&layout, &migrations, 3).unwrap();
+
+        let err = run_migrations(&layout, &migrations, 2).unwrap_err();
+        assert!(matches!(err, BoxliteError::Unsupported(_)), "{err}");
+    }
+}
@@ -1,10 +1,15 @@
+pub(crate) mod build;
 pub mod constants;
 pub(crate) mod guest_rootfs;
+pub(crate) mod guest_rootfs_cache;
 pub mod layout;
 pub(crate) mod lock;
+pub(crate) mod migrations;
 pub mod options;
+pub(crate) mod restart;
 pub(crate) mod signal_handler;
 pub mod types;
+pub(crate) mod warm_pool;
 
 mod core;
 pub(crate) mod rt_impl;
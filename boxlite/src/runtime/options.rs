@@ -1,11 +1,18 @@
 //! Configuration for Boxlite.
 
+use crate::images::RetryPolicy;
+use crate::pipeline::PipelineObserver;
 use crate::runtime::constants::envs as const_envs;
+use crate::runtime::layout::LayoutDirs;
 use crate::runtime::layout::dirs as const_dirs;
 use boxlite_shared::errors::BoxliteResult;
 use dirs::home_dir;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
 // ============================================================================
 // Security Options
@@ -27,6 +34,14 @@ pub struct SecurityOptions {
     #[serde(default = "default_jailer_enabled")]
     pub jailer_enabled: bool,
 
+    /// Wrap the shim in the platform sandbox (bwrap on Linux, sandbox-exec on macOS).
+    ///
+    /// When false, the shim is spawned directly with only the pre_exec FD
+    /// cleanup, and no cgroup or seccomp setup. Intended for debugging only.
+    /// Default: true
+    #[serde(default = "default_isolation_enabled")]
+    pub isolation_enabled: bool,
+
     /// Enable seccomp syscall filtering (Linux only).
     ///
     /// When true, applies a whitelist of allowed syscalls.
@@ -34,6 +49,38 @@ pub struct SecurityOptions {
     #[serde(default = "default_seccomp_enabled")]
     pub seccomp_enabled: bool,
 
+    /// Seccomp filter profile (Linux only).
+    ///
+    /// Selects which allowlist is compiled when `seccomp_enabled` is true.
+    /// Default: `SeccompProfile::Strict`
+    #[serde(default)]
+    pub seccomp_profile: SeccompProfile,
+
+    /// Action taken on syscalls outside the allowlist (Linux only).
+    ///
+    /// `Log` lets the process continue while the kernel audit log records
+    /// violations, useful for discovering missing syscalls before enforcing.
+    /// Default: `SeccompAction::Trap`
+    #[serde(default)]
+    pub seccomp_action: SeccompAction,
+
+    /// Extra syscalls to allow on top of the profile's allowlist (Linux only).
+    ///
+    /// Escape hatch for guests that need a syscall the built-in allowlist
+    /// doesn't cover. Syscalls in the blocked list are rejected.
+    /// Default: empty
+    #[serde(default)]
+    pub extra_allowed_syscalls: Vec<String>,
+
+    /// Run the seccomp self-check before spawning boxes (Linux only, debug).
+    ///
+    /// Forks a child that applies the filter and exercises common VMM
+    /// operations, turning a missing allowlist entry into a startup error
+    /// instead of a SIGSYS crash inside the box.
+    /// Default: false
+    #[serde(default)]
+    pub seccomp_self_check: bool,
+
     /// UID to drop to after setup (Linux only).
     ///
     /// - None: Auto-allocate an unprivileged UID
@@ -65,6 +112,15 @@ pub struct SecurityOptions {
     #[serde(default)]
     pub new_net_ns: bool,
 
+    /// Host system directories bind-mounted read-only into the sandbox (Linux only).
+    ///
+    /// Each path is mounted at the same location if it exists. The dynamic
+    /// linker's directory is always added when it lives outside these paths
+    /// (e.g. `/nix/store` on NixOS).
+    /// Default: `/usr`, `/lib`, `/lib64`, `/bin`, `/sbin`
+    #[serde(default = "default_system_mounts")]
+    pub system_mounts: Vec<PathBuf>,
+
     /// Base directory for chroot jails (Linux only).
     ///
     /// Default: /srv/boxlite
@@ -85,6 +141,24 @@ pub struct SecurityOptions {
     #[serde(default = "default_close_fds")]
     pub close_fds: bool,
 
+    /// Verify the shim and libraries copied into the box by SHA-256 (Linux only).
+    ///
+    /// When true, copies are compared by checksum (recorded in `.sha256`
+    /// sidecars) instead of mtime and size, so a corrupted copy is replaced.
+    /// Costs a hash of each file on every box start.
+    /// Default: false
+    #[serde(default)]
+    pub verify_shim_checksums: bool,
+
+    /// How the shim and libraries are placed into the box (Linux only).
+    ///
+    /// `Copy` keeps each box's binaries on separate inodes (Firecracker
+    /// pattern). `Hardlink` and `Reflink` save disk in development or
+    /// single-tenant setups and fall back to copying when unsupported.
+    /// Default: Copy
+    #[serde(default)]
+    pub shim_link_mode: ShimLinkMode,
+
     /// Sanitize environment variables.
     ///
     /// When true, clears all environment variables except those in allowlist.
@@ -116,6 +190,44 @@ pub struct SecurityOptions {
     pub network_enabled: bool,
 }
 
+/// Seccomp filter profile.
+///
+/// Controls how strict the generated syscall allowlist is.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SeccompProfile {
+    /// Minimal allowlist needed for VMM operation (Firecracker-aligned).
+    #[default]
+    Strict,
+    /// Strict allowlist plus extra syscalls useful when debugging.
+    ///
+    /// Security-critical syscalls (e.g. `ptrace`, `process_vm_readv`) stay blocked.
+    Permissive,
+    /// Generate no filter at all.
+    Disabled,
+}
+
+/// Action taken when a syscall is not in the seccomp allowlist.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SeccompAction {
+    /// Send SIGSYS to the offending thread (enforcing mode).
+    #[default]
+    Trap,
+    /// Allow the syscall but record it via `SECCOMP_RET_LOG` (complain mode).
+    Log,
+}
+
+/// How the shim binary and libraries are placed into the box directory.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShimLinkMode {
+    /// Independent copy per box, so boxes never share binary pages.
+    #[default]
+    Copy,
+    /// Hard link to the original when on the same filesystem, else copy.
+    Hardlink,
+    /// Copy-on-write clone on filesystems that support it (btrfs, XFS), else copy.
+    Reflink,
+}
+
 /// Resource limits for the jailed process.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ResourceLimits {
@@ -138,6 +250,39 @@ pub struct ResourceLimits {
     /// Maximum CPU time in seconds (RLIMIT_CPU).
     #[serde(default)]
     pub max_cpu_time: Option<u64>,
+
+    /// Maximum swap usage in bytes (cgroup `memory.swap.max`, Linux only).
+    ///
+    /// `Some(0)` disables swap for the box entirely.
+    #[serde(default)]
+    pub max_swap: Option<u64>,
+
+    /// Relative CPU weight (cgroup `cpu.weight`, Linux only).
+    ///
+    /// Range 1-10000, kernel default is 100. Higher values get more CPU time
+    /// relative to other boxes under contention.
+    #[serde(default)]
+    pub cpu_weight: Option<u32>,
+}
+
+impl ResourceLimits {
+    /// Valid range for `cpu_weight` (matches cgroup v2 `cpu.weight`).
+    pub const CPU_WEIGHT_RANGE: std::ops::RangeInclusive<u32> = 1..=10000;
+
+    /// Validate limit values.
+    pub fn validate(&self) -> BoxliteResult<()> {
+        if let Some(weight) = self.cpu_weight
+            && !Self::CPU_WEIGHT_RANGE.contains(&weight)
+        {
+            return Err(boxlite_shared::errors::BoxliteError::Config(format!(
+                "cpu_weight must be within {}..={}, got {}",
+                Self::CPU_WEIGHT_RANGE.start(),
+                Self::CPU_WEIGHT_RANGE.end(),
+                weight
+            )));
+        }
+        Ok(())
+    }
 }
 
 // Default value functions for SecurityOptions
@@ -146,10 +291,21 @@ fn default_jailer_enabled() -> bool {
     false
 }
 
+fn default_isolation_enabled() -> bool {
+    true
+}
+
 fn default_seccomp_enabled() -> bool {
     false
 }
 
+fn default_system_mounts() -> Vec<PathBuf> {
+    ["/usr", "/lib", "/lib64", "/bin", "/sbin"]
+        .into_iter()
+        .map(PathBuf::from)
+        .collect()
+}
+
 fn default_chroot_base() -> PathBuf {
     PathBuf::from("/srv/boxlite")
 }
@@ -185,14 +341,22 @@ impl Default for SecurityOptions {
     fn default() -> Self {
         Self {
             jailer_enabled: default_jailer_enabled(),
+            isolation_enabled: default_isolation_enabled(),
             seccomp_enabled: default_seccomp_enabled(),
+            seccomp_profile: SeccompProfile::default(),
+            seccomp_action: SeccompAction::default(),
+            extra_allowed_syscalls: Vec::new(),
+            seccomp_self_check: false,
             uid: None,
             gid: None,
             new_pid_ns: false,
             new_net_ns: false,
+            system_mounts: default_system_mounts(),
             chroot_base: default_chroot_base(),
             chroot_enabled: default_chroot_enabled(),
             close_fds: default_close_fds(),
+            verify_shim_checksums: false,
+            shim_link_mode: ShimLinkMode::default(),
             sanitize_env: default_sanitize_env(),
             env_allowlist: default_env_allowlist(),
             resource_limits: ResourceLimits::default(),
@@ -217,6 +381,17 @@ impl SecurityOptions {
         }
     }
 
+    /// Disabled mode: no sandbox at all.
+    ///
+    /// Spawns the shim directly, skipping bwrap/sandbox-exec, cgroups and
+    /// seccomp. Only use this to debug problems inside the sandbox.
+    pub fn disabled() -> Self {
+        Self {
+            isolation_enabled: false,
+            ..Self::development()
+        }
+    }
+
     /// Standard mode: recommended for most use cases.
     ///
     /// Provides good security without being overly restrictive.
@@ -250,6 +425,8 @@ impl SecurityOptions {
                 max_processes: Some(100),
                 max_memory: None,   // Let VM config handle this
                 max_cpu_time: None, // Let VM config handle this
+                max_swap: None,
+                cpu_weight: None,
             },
             ..Default::default()
         }
@@ -335,6 +512,13 @@ impl SecurityOptionsBuilder {
         }
     }
 
+    /// Create a builder starting from disabled (no sandbox) settings.
+    pub fn disabled() -> Self {
+        Self {
+            inner: SecurityOptions::disabled(),
+        }
+    }
+
     /// Create a builder starting from maximum security settings.
     ///
     /// All isolation features enabled.
@@ -354,12 +538,48 @@ impl SecurityOptionsBuilder {
         self
     }
 
+    /// Enable or disable the platform sandbox wrapper.
+    pub fn isolation_enabled(&mut self, enabled: bool) -> &mut Self {
+        self.inner.isolation_enabled = enabled;
+        self
+    }
+
     /// Enable or disable seccomp syscall filtering (Linux only).
     pub fn seccomp_enabled(&mut self, enabled: bool) -> &mut Self {
         self.inner.seccomp_enabled = enabled;
         self
     }
 
+    /// Set the seccomp filter profile (Linux only).
+    pub fn seccomp_profile(&mut self, profile: SeccompProfile) -> &mut Self {
+        self.inner.seccomp_profile = profile;
+        self
+    }
+
+    /// Set the action for syscalls outside the allowlist (Linux only).
+    pub fn seccomp_action(&mut self, action: SeccompAction) -> &mut Self {
+        self.inner.seccomp_action = action;
+        self
+    }
+
+    /// Allow an extra syscall on top of the seccomp profile (Linux only).
+    pub fn extra_allowed_syscall(&mut self, name: impl Into<String>) -> &mut Self {
+        self.inner.extra_allowed_syscalls.push(name.into());
+        self
+    }
+
+    /// Run the seccomp self-check before spawning (Linux only, debug).
+    pub fn seccomp_self_check(&mut self, enabled: bool) -> &mut Self {
+        self.inner.seccomp_self_check = enabled;
+        self
+    }
+
+    /// Set the host system directories mounted read-only (Linux only).
+    pub fn system_mounts(&mut self, mounts: Vec<PathBuf>) -> &mut Self {
+        self.inner.system_mounts = mounts;
+        self
+    }
+
     /// Set UID to drop to after setup (Linux only).
     pub fn uid(&mut self, uid: u32) -> &mut Self {
         self.inner.uid = Some(uid);
@@ -406,6 +626,18 @@ impl SecurityOptionsBuilder {
         self
     }
 
+    /// Verify copied shim and libraries by SHA-256 (Linux only).
+    pub fn verify_shim_checksums(&mut self, enabled: bool) -> &mut Self {
+        self.inner.verify_shim_checksums = enabled;
+        self
+    }
+
+    /// Set how the shim and libraries are placed into the box (Linux only).
+    pub fn shim_link_mode(&mut self, mode: ShimLinkMode) -> &mut Self {
+        self.inner.shim_link_mode = mode;
+        self
+    }
+
     // ─────────────────────────────────────────────────────────────────────
     // Environment settings
     // ─────────────────────────────────────────────────────────────────────
@@ -468,6 +700,18 @@ impl SecurityOptionsBuilder {
         self
     }
 
+    /// Set maximum swap usage in bytes (Linux only). Zero disables swap.
+    pub fn max_swap_bytes(&mut self, bytes: u64) -> &mut Self {
+        self.inner.resource_limits.max_swap = Some(bytes);
+        self
+    }
+
+    /// Set relative CPU weight (1-10000, Linux only).
+    pub fn cpu_weight(&mut self, weight: u32) -> &mut Self {
+        self.inner.resource_limits.cpu_weight = Some(weight);
+        self
+    }
+
     // ─────────────────────────────────────────────────────────────────────
     // macOS-specific settings
     // ─────────────────────────────────────────────────────────────────────
@@ -525,6 +769,177 @@ pub struct BoxliteOptions {
     /// // "alpine" → tries ghcr.io/myorg/alpine, then docker.io/alpine
     /// ```
     pub image_registries: Vec<String>,
+
+    /// Maximum number of CPUs a single box may request.
+    ///
+    /// When set, `create()` rejects boxes whose `cpus` exceeds this ceiling
+    /// with `BoxliteError::InvalidArgument`. `None` (default) means no ceiling.
+    pub max_cpus: Option<u8>,
+
+    /// Maximum memory in MiB a single box may request.
+    ///
+    /// When set, `create()` rejects boxes whose `memory_mib` exceeds this ceiling
+    /// with `BoxliteError::InvalidArgument`. `None` (default) means no ceiling.
+    pub max_memory_mib: Option<u32>,
+
+    /// Interval for background per-box metrics sampling.
+    ///
+    /// When set, the runtime periodically queries every running box's VMM
+    /// metrics so that `BoxMetrics::cpu_percent` reflects usage over the last
+    /// interval. `None` (default) disables the sampler; metrics are then read
+    /// on demand only.
+    pub metrics_interval: Option<Duration>,
+
+    /// Interval for the background reaper of expired boxes.
+    ///
+    /// When set, the runtime periodically calls `reap_expired` to stop and
+    /// remove boxes whose `BoxOptions::ttl` has run out. `None` (default)
+    /// leaves reaping to explicit `reap_expired` calls.
+    pub reap_interval: Option<Duration>,
+
+    /// SIGKILL the shims of boxes started by this runtime when it is dropped.
+    /// Boxes created with `detach: true` are left running.
+    ///
+    /// A best-effort guarantee for hosts where shims can't die with their
+    /// parent: `Drop` can't await a graceful stop, so PIDs recorded at start
+    /// are killed directly. Call `shutdown()` first for a graceful stop.
+    /// Default: false.
+    pub kill_boxes_on_drop: bool,
+
+    /// Output format of the runtime log file (`logs/boxlite.log`).
+    ///
+    /// The tracing subscriber is process-global and installed once, so only
+    /// the first runtime created in a process decides the format.
+    pub log_format: LogFormat,
+
+    /// Rotation policy of the runtime log file.
+    ///
+    /// Like `log_format`, only the first runtime in a process applies it.
+    pub log_rotation: LogRotation,
+
+    /// Database integrity check run when the runtime opens its database.
+    ///
+    /// Default: `Off`.
+    pub db_integrity: DbIntegrityMode,
+
+    /// SQLite connection settings (journal mode, busy timeout).
+    pub database: DatabaseOptions,
+
+    /// Pool of pre-started boxes that serve matching `get_or_create` calls.
+    ///
+    /// Pooled boxes are created from the template, started in the
+    /// background and handed out already running, skipping filesystem setup
+    /// and VM boot. The pool refills after each claim. `None` (default)
+    /// disables the pool.
+    pub warm_pool: Option<WarmPoolOptions>,
+
+    /// Absolute paths for home subdirectories, e.g. images on a large disk
+    /// or logs on a separate volume.
+    ///
+    /// Unset entries (default) stay under `home_dir`.
+    pub layout_dirs: LayoutDirs,
+
+    /// Retries of registry requests (manifests, layers, configs) during
+    /// image pulls.
+    ///
+    /// Connection errors and 5xx responses are retried with exponential
+    /// backoff; 4xx responses fail immediately.
+    pub pull_retry: RetryPolicy,
+
+    /// What the runtime removes from the temp directory when it starts.
+    ///
+    /// Default: `Always`.
+    pub clean_temp_on_start: CleanTempOnStart,
+}
+
+/// Size and template of the runtime's warm box pool.
+#[derive(Clone, Debug)]
+pub struct WarmPoolOptions {
+    /// Number of started boxes to keep ready.
+    pub size: usize,
+
+    /// Options every pooled box is created with, image included.
+    ///
+    /// Only `get_or_create` calls with identical options are served from
+    /// the pool; others create a box as usual.
+    pub template: BoxOptions,
+}
+
+/// SQLite settings for the runtime database (`db/boxlite.db`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DatabaseOptions {
+    /// Use write-ahead logging, so readers don't block the writer.
+    ///
+    /// Default: true
+    pub wal: bool,
+
+    /// How long a statement waits for another connection's lock before
+    /// failing with "database is locked".
+    ///
+    /// Default: 100s
+    pub busy_timeout: Duration,
+}
+
+impl Default for DatabaseOptions {
+    fn default() -> Self {
+        Self {
+            wal: true,
+            busy_timeout: Duration::from_secs(100),
+        }
+    }
+}
+
+/// What to do about database integrity at runtime startup.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DbIntegrityMode {
+    /// Skip the check.
+    #[default]
+    Off,
+    /// Check and log any problems; corrupt box rows may still fail recovery.
+    Check,
+    /// Check and quarantine box rows that fail to deserialize. Rows written
+    /// by a newer boxlite are reported and left in place.
+    Repair,
+}
+
+/// Which leftovers in the temp directory are removed at runtime startup.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CleanTempOnStart {
+    /// Remove every entry.
+    #[default]
+    Always,
+    /// Remove only entries not modified for `older_than`. Newer ones are
+    /// left alone, since another process sharing the home may still be
+    /// writing them.
+    IfEmpty { older_than: Duration },
+    /// Leave the temp directory untouched.
+    Never,
+}
+
+/// Formatting of runtime log lines.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable single-line records (timestamp, level, target, fields).
+    #[default]
+    Pretty,
+    /// One JSON object per line, for log pipelines.
+    Json,
+    /// Abbreviated human-readable records.
+    Compact,
+}
+
+/// When the runtime log file is rotated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogRotation {
+    /// New file every day (`boxlite.log.YYYY-MM-DD`).
+    #[default]
+    Daily,
+    /// New file every hour (`boxlite.log.YYYY-MM-DD-HH`).
+    Hourly,
+    /// A single, ever-growing `boxlite.log`.
+    Never,
+    /// Rotate `boxlite.log` to numbered backups once it reaches `max_bytes`.
+    SizeLimited { max_bytes: u64 },
 }
 
 impl Default for BoxliteOptions {
@@ -540,6 +955,19 @@ impl Default for BoxliteOptions {
         Self {
             home_dir,
             image_registries: Vec::new(),
+            max_cpus: None,
+            max_memory_mib: None,
+            metrics_interval: None,
+            reap_interval: None,
+            kill_boxes_on_drop: false,
+            log_format: LogFormat::default(),
+            log_rotation: LogRotation::default(),
+            db_integrity: DbIntegrityMode::default(),
+            database: DatabaseOptions::default(),
+            warm_pool: None,
+            layout_dirs: LayoutDirs::default(),
+            pull_retry: RetryPolicy::default(),
+            clean_temp_on_start: CleanTempOnStart::default(),
         }
     }
 }
@@ -555,12 +983,55 @@ pub struct BoxOptions {
     /// If set, the COW overlay will have this virtual size, allowing
     /// the container to write more data than the base image size.
     pub disk_size_gb: Option<u64>,
+    /// Layering of the box's writable disk.
+    #[serde(default)]
+    pub disk: DiskConfig,
+    /// Working directory of the container and of commands run in it.
+    ///
+    /// Overrides the image's WORKDIR. `None` (default) keeps the image's.
     pub working_dir: Option<String>,
     pub env: Vec<(String, String)>,
+    /// Command to run instead of the image's ENTRYPOINT (like `docker run --entrypoint`).
+    ///
+    /// Also drops the image's CMD unless `args` is set. `None` (default)
+    /// keeps the image's entrypoint.
+    #[serde(default)]
+    pub command: Option<Vec<String>>,
+    /// Arguments to run instead of the image's CMD (like `docker run image args...`).
+    ///
+    /// Appended to `command` if set, otherwise to the image's ENTRYPOINT.
+    #[serde(default)]
+    pub args: Option<Vec<String>>,
+    /// User the container runs as: `name`, `uid`, `name:group` or `uid:gid`.
+    ///
+    /// Names are resolved against the container's `/etc/passwd` and
+    /// `/etc/group`. `None` (default) uses the image's `User`, or root.
+    #[serde(default)]
+    pub user: Option<String>,
     pub rootfs: RootfsSpec,
+    /// Platform to pick from a multi-arch image rootfs, e.g. `linux/arm64`.
+    ///
+    /// `None` (default) selects the host platform. Guests run without
+    /// emulation, so other architectures only boot on matching hosts.
+    #[serde(default)]
+    pub platform: Option<String>,
     pub volumes: Vec<VolumeSpec>,
     pub network: NetworkSpec,
     pub ports: Vec<PortSpec>,
+    /// MAC address of the guest network interface.
+    ///
+    /// Must be a locally administered unicast address. `None` (default)
+    /// uses the built-in guest MAC.
+    #[serde(default)]
+    pub mac_address: Option<[u8; 6]>,
+    /// Static IPv4 address and prefix length for the guest interface.
+    ///
+    /// Makes the box address predictable for service discovery. `None`
+    /// (default) uses the network backend's default guest address. The
+    /// address must be a host of the box subnet (192.168.127.0/24) other
+    /// than its gateway.
+    #[serde(default)]
+    pub static_ip: Option<(Ipv4Addr, u8)>,
     /// Enable bind mount isolation for the shared mounts directory.
     ///
     /// When true, creates a read-only bind mount from `mounts/` to `shared/`,
@@ -601,32 +1072,133 @@ pub struct BoxOptions {
     /// `SecurityOptions::standard()`, `SecurityOptions::maximum()`.
     #[serde(default)]
     pub security: SecurityOptions,
+
+    /// User-defined key-value metadata (e.g. owner, project).
+    ///
+    /// Persisted with the box config and surfaced on `BoxInfo`. Use
+    /// `BoxliteRuntime::list_info_filtered` to select boxes by label.
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+
+    /// What to do when the box is found dead during runtime recovery.
+    ///
+    /// Defaults to `RestartPolicy::No` (leave it stopped).
+    #[serde(default)]
+    pub restart_policy: RestartPolicy,
+
+    /// Lifetime of the box, counted from creation.
+    ///
+    /// Once `created_at + ttl` has passed, `BoxliteRuntime::reap_expired`
+    /// (or the background reaper, see `BoxliteOptions::reap_interval`) stops
+    /// and removes the box. `None` (default) means the box never expires.
+    #[serde(default)]
+    pub ttl: Option<Duration>,
+
+    /// How many times to wait for the guest agent's ready signal at boot.
+    ///
+    /// Each wait is twice as long as the previous one, starting at
+    /// `ready_backoff`; start fails once all of them elapse. Defaults to 6.
+    #[serde(default = "default_ready_retries")]
+    pub ready_retries: u32,
+
+    /// First wait for the guest agent's ready signal (see `ready_retries`).
+    ///
+    /// Defaults to 500ms, which with 6 retries allows ~31s for boot.
+    #[serde(default = "default_ready_backoff")]
+    pub ready_backoff: Duration,
+
+    /// Guest kernel console log level (`loglevel=N` on the kernel cmdline).
+    ///
+    /// 0 only prints emergencies and quiets boot output; 7 prints debug
+    /// messages. `None` (default) keeps the kernel's default.
+    #[serde(default)]
+    pub kernel_loglevel: Option<u8>,
+
+    /// Extra guest kernel command-line parameters, appended in order
+    /// (e.g. `quiet` or module parameters).
+    ///
+    /// Each entry must be a single token: no whitespace, quotes or control
+    /// characters, and not `--`.
+    #[serde(default)]
+    pub kernel_cmdline_extra: Vec<String>,
+
+    /// Set the guest clock to the host's when reattaching to a detached box.
+    ///
+    /// The guest clock can drift while nothing is attached, e.g. across host
+    /// sleep. Defaults to false; see also `LiteBox::sync_time`.
+    #[serde(default)]
+    pub sync_time_on_resume: bool,
+
+    /// Give the guest the virtio-rng device as an entropy source.
+    ///
+    /// Keeps boot from stalling on hosts where early entropy is scarce.
+    /// Defaults to true. libkrun attaches the device to every VM, so when
+    /// false it is still present but the guest kernel never binds a driver
+    /// to it and seeds its pool from CPU sources (jitter, RDRAND) only.
+    #[serde(default = "default_enable_rng")]
+    pub enable_rng: bool,
+
+    /// Receives progress callbacks for each box initialization stage.
+    ///
+    /// Useful for progress bars and timing diagnostics. Not persisted: a box
+    /// loaded back from the database starts without an observer.
+    #[serde(skip)]
+    pub pipeline_observer: Option<Arc<dyn PipelineObserver>>,
 }
 
 fn default_auto_remove() -> bool {
     true
 }
 
+fn default_ready_retries() -> u32 {
+    6
+}
+
+fn default_ready_backoff() -> Duration {
+    Duration::from_millis(500)
+}
+
 fn default_detach() -> bool {
     false
 }
 
+fn default_enable_rng() -> bool {
+    true
+}
+
 impl Default for BoxOptions {
     fn default() -> Self {
         Self {
             cpus: None,
             memory_mib: None,
             disk_size_gb: None,
+            disk: DiskConfig::default(),
             working_dir: None,
             env: Vec::new(),
+            command: None,
+            args: None,
+            user: None,
             rootfs: RootfsSpec::default(),
+            platform: None,
             volumes: Vec::new(),
             network: NetworkSpec::default(),
             ports: Vec::new(),
+            mac_address: None,
+            static_ip: None,
             isolate_mounts: false,
             auto_remove: default_auto_remove(),
             detach: default_detach(),
             security: SecurityOptions::default(),
+            labels: HashMap::new(),
+            restart_policy: RestartPolicy::default(),
+            ttl: None,
+            ready_retries: default_ready_retries(),
+            ready_backoff: default_ready_backoff(),
+            kernel_loglevel: None,
+            kernel_cmdline_extra: Vec::new(),
+            sync_time_on_resume: false,
+            enable_rng: default_enable_rng(),
+            pipeline_observer: None,
         }
     }
 }
@@ -637,6 +1209,7 @@ impl BoxOptions {
     /// Validates option combinations:
     /// - `auto_remove=true` with `detach=true` is invalid (detached boxes need manual lifecycle control)
     /// - `isolate_mounts=true` is only supported on Linux
+    /// - Resource limits must be within their valid ranges
     pub fn sanitize(&self) -> BoxliteResult<()> {
         // Validate auto_remove + detach combination
         // A detached box that auto-removes doesn't make practical sense:
@@ -657,10 +1230,153 @@ impl BoxOptions {
                 "isolate_mounts is only supported on Linux".to_string(),
             ));
         }
+
+        if self.ready_retries == 0 {
+            return Err(boxlite_shared::errors::BoxliteError::Config(
+                "ready_retries must be at least 1".to_string(),
+            ));
+        }
+
+        if self.command.as_ref().is_some_and(|c| c.is_empty()) {
+            return Err(boxlite_shared::errors::BoxliteError::Config(
+                "command must not be empty".to_string(),
+            ));
+        }
+
+        if let Some(user) = &self.user {
+            UserSpec::parse(user)?;
+        }
+
+        if let Some(level) = self.kernel_loglevel
+            && level > 7
+        {
+            return Err(boxlite_shared::errors::BoxliteError::InvalidArgument(
+                format!("kernel_loglevel must be between 0 and 7, got {}", level),
+            ));
+        }
+        for param in &self.kernel_cmdline_extra {
+            crate::vmm::validate_kernel_param(param)?;
+        }
+
+        if let Some(platform) = &self.platform {
+            if !matches!(self.rootfs, RootfsSpec::Image(_)) {
+                return Err(boxlite_shared::errors::BoxliteError::Config(
+                    "platform can only be combined with an image rootfs".to_string(),
+                ));
+            }
+            platform.parse::<crate::images::Platform>()?;
+        }
+
+        if self.disk.backing_file.is_some() && !matches!(self.rootfs, RootfsSpec::Image(_)) {
+            return Err(boxlite_shared::errors::BoxliteError::Config(
+                "disk.backing_file can only be combined with an image rootfs".to_string(),
+            ));
+        }
+
+        self.security.resource_limits.validate()?;
+        self.validate_network()?;
+        Ok(())
+    }
+
+    /// Rewrite every volume's `host_path`, and `disk.backing_file`, into an
+    /// absolute path.
+    ///
+    /// Done when the box is created, so relative paths stay anchored to the
+    /// caller's working directory rather than wherever the box is later started.
+    pub fn normalize_volume_paths(&mut self) -> BoxliteResult<()> {
+        for volume in &mut self.volumes {
+            volume.host_path = volume.absolute_host_path()?.to_string_lossy().into_owned();
+        }
+        if let Some(backing_file) = &mut self.disk.backing_file {
+            *backing_file = std::path::absolute(&*backing_file).map_err(|e| {
+                boxlite_shared::errors::BoxliteError::InvalidArgument(format!(
+                    "Invalid disk backing file {}: {}",
+                    backing_file.display(),
+                    e
+                ))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Validate guest network addressing (`mac_address`, `static_ip`).
+    ///
+    /// The MAC must be unicast (I/G bit clear) and locally administered
+    /// (U/L bit set) so it cannot collide with vendor-assigned hardware.
+    pub fn validate_network(&self) -> BoxliteResult<()> {
+        if let Some(mac) = self.mac_address {
+            if mac[0] & 0x01 != 0 {
+                return Err(boxlite_shared::errors::BoxliteError::InvalidArgument(
+                    format!(
+                        "mac_address {} is multicast; a unicast address is required",
+                        crate::net::constants::mac_to_string(&mac)
+                    ),
+                ));
+            }
+            if mac[0] & 0x02 == 0 {
+                return Err(boxlite_shared::errors::BoxliteError::InvalidArgument(
+                    format!(
+                        "mac_address {} is not locally administered",
+                        crate::net::constants::mac_to_string(&mac)
+                    ),
+                ));
+            }
+        }
+
+        if let Some((ip, prefix)) = self.static_ip {
+            if prefix > 32 {
+                return Err(boxlite_shared::errors::BoxliteError::InvalidArgument(
+                    format!("static_ip prefix must be within 0..=32, got {}", prefix),
+                ));
+            }
+
+            // The network backend only routes its own subnet
+            use crate::net::constants::{GATEWAY_IP, SUBNET};
+            let (subnet, subnet_prefix): (Ipv4Addr, u32) = SUBNET
+                .split_once('/')
+                .and_then(|(net, len)| Some((net.parse::<Ipv4Addr>().ok()?, len.parse().ok()?)))
+                .expect("SUBNET is a valid CIDR");
+            let host_mask = u32::MAX >> subnet_prefix;
+            let host_bits = u32::from(ip) & host_mask;
+            if u32::from(ip) & !host_mask != u32::from(subnet) {
+                return Err(boxlite_shared::errors::BoxliteError::InvalidArgument(
+                    format!("static_ip {} is outside the box subnet {}", ip, SUBNET),
+                ));
+            }
+            if host_bits == 0 || host_bits == host_mask {
+                return Err(boxlite_shared::errors::BoxliteError::InvalidArgument(
+                    format!(
+                        "static_ip {} is the network or broadcast address of {}",
+                        ip, SUBNET
+                    ),
+                ));
+            }
+            if ip.to_string() == GATEWAY_IP {
+                return Err(boxlite_shared::errors::BoxliteError::InvalidArgument(
+                    format!("static_ip {} is the gateway address", ip),
+                ));
+            }
+        }
+
         Ok(())
     }
 }
 
+/// Layering of a box's writable disk.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DiskConfig {
+    /// Shared base image (raw or qcow2) for the box's disk.
+    ///
+    /// The box's `disk.qcow2` becomes a thin qcow2 overlay whose backing file
+    /// is this path, instead of a base built from the image layers; the image
+    /// is still pulled for its config. Many boxes can share one base. The base
+    /// is only read and must not change or move while overlays reference it.
+    /// Starting or removing a box whose directory holds another box's base
+    /// fails until that box is removed.
+    #[serde(default)]
+    pub backing_file: Option<PathBuf>,
+}
+
 /// How to populate the box root filesystem.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub enum RootfsSpec {
@@ -668,6 +1384,14 @@ pub enum RootfsSpec {
     Image(String),
     /// Use an already prepared rootfs at the given host path.
     RootfsPath(String),
+    /// Boot from a raw rootfs disk image, e.g. one written by
+    /// `LiteBox::export_rootfs`. The file is used as a read-only base and
+    /// must stay in place for the lifetime of the box.
+    Disk(PathBuf),
+    /// Boot from a pre-extracted rootfs directory on the host (must contain
+    /// `bin` or `sbin`). The directory is packed into the box's disk when the
+    /// box first starts; later changes to it are not picked up.
+    Directory(PathBuf),
 }
 
 impl Default for RootfsSpec {
@@ -682,6 +1406,74 @@ pub struct VolumeSpec {
     pub host_path: String,
     pub guest_path: String,
     pub read_only: bool,
+    /// Extra guest mount options (e.g. `nosuid`, `nodev`, `noexec`).
+    #[serde(default)]
+    pub mount_options: Vec<String>,
+    /// DAX caching policy of the virtiofs mount.
+    #[serde(default)]
+    pub cache_mode: VolumeCacheMode,
+}
+
+impl VolumeSpec {
+    /// Absolute form of `host_path`.
+    ///
+    /// A leading `~` expands to the user's home directory and relative paths
+    /// resolve against the current working directory. Existing paths are
+    /// canonicalized; a missing path is returned as-is (absolute) so the
+    /// existence check can report it later.
+    pub fn absolute_host_path(&self) -> BoxliteResult<PathBuf> {
+        let path = if self.host_path == "~" {
+            home_dir_or_err()?
+        } else if let Some(rest) = self.host_path.strip_prefix("~/") {
+            home_dir_or_err()?.join(rest)
+        } else {
+            PathBuf::from(&self.host_path)
+        };
+
+        let path = std::path::absolute(&path).map_err(|e| {
+            boxlite_shared::errors::BoxliteError::Config(format!(
+                "Failed to resolve volume path '{}': {}",
+                self.host_path, e
+            ))
+        })?;
+        Ok(path.canonicalize().unwrap_or(path))
+    }
+}
+
+fn home_dir_or_err() -> BoxliteResult<PathBuf> {
+    home_dir().ok_or_else(|| {
+        boxlite_shared::errors::BoxliteError::Config(
+            "Cannot expand '~' in volume path: home directory is unknown".to_string(),
+        )
+    })
+}
+
+/// Virtiofs DAX caching policy for a volume.
+///
+/// Maps to the guest `dax=` mount option. With DAX, file contents are
+/// mapped straight from the host page cache instead of being copied into
+/// the guest's.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VolumeCacheMode {
+    /// Kernel default (per-inode DAX hints).
+    #[default]
+    Auto,
+    /// Always use DAX.
+    Always,
+    /// Never use DAX; file data goes through the guest page cache.
+    Never,
+}
+
+impl VolumeCacheMode {
+    /// Guest mount option selecting this mode, if any.
+    pub fn mount_option(self) -> Option<&'static str> {
+        match self {
+            VolumeCacheMode::Auto => None,
+            VolumeCacheMode::Always => Some("dax=always"),
+            VolumeCacheMode::Never => Some("dax=never"),
+        }
+    }
 }
 
 /// Network isolation options.
@@ -693,7 +1485,33 @@ pub enum NetworkSpec {
     // Custom(String),
 }
 
-#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+/// Restart policy applied when a box's VM is found dead during recovery.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum RestartPolicy {
+    /// Never restart; the box stays stopped.
+    #[default]
+    No,
+    /// Restart after a crash, at most `max_retries` times in total.
+    OnFailure { max_retries: u32 },
+    /// Always restart after a crash or host reboot.
+    Always,
+}
+
+impl RestartPolicy {
+    /// Whether another restart should be attempted after `restart_count`
+    /// restarts have already been made.
+    pub fn should_restart(&self, restart_count: u32) -> bool {
+        match self {
+            RestartPolicy::No => false,
+            RestartPolicy::OnFailure { max_retries } => restart_count < *max_retries,
+            RestartPolicy::Always => true,
+        }
+    }
+}
+
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
 pub enum PortProtocol {
     #[default]
     Tcp,
@@ -701,6 +1519,22 @@ pub enum PortProtocol {
     // Sctp,
 }
 
+impl PortProtocol {
+    /// Lowercase protocol name ("tcp", "udp").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PortProtocol::Tcp => "tcp",
+            PortProtocol::Udp => "udp",
+        }
+    }
+}
+
+impl std::fmt::Display for PortProtocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 fn default_protocol() -> PortProtocol {
     PortProtocol::Tcp
 }
@@ -715,15 +1549,194 @@ pub struct PortSpec {
     pub host_ip: Option<String>, // Optional bind IP, defaults to 0.0.0.0/:: if None
 }
 
+/// A resolved host-to-guest port forward.
+///
+/// Produced at box start from [`PortSpec`]s and the image's exposed ports,
+/// and translated into network backend forwarding rules.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct PortForward {
+    pub host_port: u16,
+    pub guest_port: u16,
+    #[serde(default = "default_protocol")]
+    pub protocol: PortProtocol,
+}
+
+impl PortForward {
+    pub fn new(host_port: u16, guest_port: u16, protocol: PortProtocol) -> Self {
+        Self {
+            host_port,
+            guest_port,
+            protocol,
+        }
+    }
+
+    /// TCP forward, the common case.
+    pub fn tcp(host_port: u16, guest_port: u16) -> Self {
+        Self::new(host_port, guest_port, PortProtocol::Tcp)
+    }
+}
+
+impl std::fmt::Display for PortForward {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}->{}/{}",
+            self.host_port, self.guest_port, self.protocol
+        )
+    }
+}
+
+/// One side of a `user[:group]` spec.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UserId {
+    Id(u32),
+    Name(String),
+}
+
+impl std::fmt::Display for UserId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UserId::Id(id) => write!(f, "{}", id),
+            UserId::Name(name) => f.write_str(name),
+        }
+    }
+}
+
+/// Parsed container user (`BoxOptions::user`, image `User`).
+///
+/// `Display` gives the normalized form sent to the guest (e.g. `"0100"`
+/// becomes `"100"`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UserSpec {
+    pub user: UserId,
+    pub group: Option<UserId>,
+}
+
+impl UserSpec {
+    /// Parse `name`, `uid`, `name:group` or `uid:gid`.
+    pub fn parse(spec: &str) -> BoxliteResult<Self> {
+        let invalid = |reason: &str| {
+            boxlite_shared::errors::BoxliteError::Config(format!(
+                "invalid user '{}': {}",
+                spec, reason
+            ))
+        };
+        let part = |s: &str| -> BoxliteResult<UserId> {
+            if s.is_empty() {
+                return Err(invalid("empty user or group"));
+            }
+            if s.bytes().all(|b| b.is_ascii_digit()) {
+                return s
+                    .parse()
+                    .map(UserId::Id)
+                    .map_err(|_| invalid("id out of range"));
+            }
+            if s.chars().any(|c| c.is_whitespace() || c == '/' || c == ':') {
+                return Err(invalid("names may not contain whitespace, '/' or ':'"));
+            }
+            Ok(UserId::Name(s.to_string()))
+        };
+
+        let (user, group) = match spec.split_once(':') {
+            Some((user, group)) => (part(user)?, Some(part(group)?)),
+            None => (part(spec)?, None),
+        };
+        Ok(Self { user, group })
+    }
+}
+
+impl std::fmt::Display for UserSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.group {
+            Some(group) => write!(f, "{}:{}", self.user, group),
+            None => write!(f, "{}", self.user),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_user_spec_parse() {
+        let spec = UserSpec::parse("nginx").unwrap();
+        assert_eq!(spec.user, UserId::Name("nginx".into()));
+        assert_eq!(spec.group, None);
+
+        let spec = UserSpec::parse("1000").unwrap();
+        assert_eq!(spec.user, UserId::Id(1000));
+        assert_eq!(spec.group, None);
+
+        let spec = UserSpec::parse("01000:0100").unwrap();
+        assert_eq!(spec.user, UserId::Id(1000));
+        assert_eq!(spec.group, Some(UserId::Id(100)));
+        assert_eq!(spec.to_string(), "1000:100");
+
+        let spec = UserSpec::parse("app:www-data").unwrap();
+        assert_eq!(spec.user, UserId::Name("app".into()));
+        assert_eq!(spec.group, Some(UserId::Name("www-data".into())));
+        assert_eq!(spec.to_string(), "app:www-data");
+
+        for bad in [
+            "",
+            ":",
+            "1000:",
+            ":1000",
+            "1000:1000:1",
+            "99999999999",
+            "a b",
+            "1:2/3",
+        ] {
+            assert!(
+                UserSpec::parse(bad).is_err(),
+                "{:?} should be rejected",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_sanitize_rejects_invalid_user() {
+        let opts = BoxOptions {
+            user: Some("1000:".into()),
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_err());
+
+        let opts = BoxOptions {
+            user: Some("1000:1000".into()),
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+    }
+
+    #[test]
+    fn test_sanitize_kernel_loglevel_range() {
+        for level in [0, 7] {
+            let opts = BoxOptions {
+                kernel_loglevel: Some(level),
+                ..Default::default()
+            };
+            assert!(opts.sanitize().is_ok());
+        }
+
+        let opts = BoxOptions {
+            kernel_loglevel: Some(8),
+            ..Default::default()
+        };
+        assert!(matches!(
+            opts.sanitize(),
+            Err(boxlite_shared::errors::BoxliteError::InvalidArgument(_))
+        ));
+    }
+
     #[test]
     fn test_box_options_defaults() {
         let opts = BoxOptions::default();
         assert!(opts.auto_remove, "auto_remove should default to true");
         assert!(!opts.detach, "detach should default to false");
+        assert!(opts.enable_rng, "enable_rng should default to true");
     }
 
     #[test]
@@ -743,6 +1756,10 @@ mod tests {
             "auto_remove should default to true via serde"
         );
         assert!(!opts.detach, "detach should default to false via serde");
+        assert!(
+            opts.enable_rng,
+            "enable_rng should default to true via serde"
+        );
     }
 
     #[test]
@@ -826,6 +1843,81 @@ mod tests {
         assert!(opts3.sanitize().is_ok());
     }
 
+    #[test]
+    fn test_validate_network_accepts_local_unicast_mac() {
+        let opts = BoxOptions {
+            mac_address: Some([0x02, 0x00, 0x00, 0xaa, 0xbb, 0xcc]),
+            static_ip: Some((Ipv4Addr::new(192, 168, 127, 50), 24)),
+            ..Default::default()
+        };
+        assert!(opts.validate_network().is_ok());
+        assert!(opts.sanitize().is_ok());
+    }
+
+    #[test]
+    fn test_validate_network_rejects_multicast_mac() {
+        let opts = BoxOptions {
+            mac_address: Some([0x03, 0x00, 0x00, 0xaa, 0xbb, 0xcc]),
+            ..Default::default()
+        };
+        let err = opts.validate_network().unwrap_err();
+        assert!(matches!(
+            err,
+            boxlite_shared::errors::BoxliteError::InvalidArgument(_)
+        ));
+        assert!(err.to_string().contains("multicast"));
+    }
+
+    #[test]
+    fn test_validate_network_rejects_universal_mac() {
+        let opts = BoxOptions {
+            mac_address: Some([0x00, 0x1b, 0x21, 0xaa, 0xbb, 0xcc]),
+            ..Default::default()
+        };
+        let err = opts.validate_network().unwrap_err();
+        assert!(err.to_string().contains("locally administered"));
+    }
+
+    #[test]
+    fn test_validate_network_prefix_range() {
+        let mut opts = BoxOptions {
+            static_ip: Some((Ipv4Addr::new(192, 168, 127, 2), 32)),
+            ..Default::default()
+        };
+        assert!(opts.validate_network().is_ok());
+
+        opts.static_ip = Some((Ipv4Addr::new(192, 168, 127, 2), 33));
+        assert!(matches!(
+            opts.validate_network(),
+            Err(boxlite_shared::errors::BoxliteError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_network_static_ip_must_be_a_subnet_host() {
+        for (ip, reason) in [
+            (Ipv4Addr::new(10, 0, 0, 2), "outside"),
+            (Ipv4Addr::new(192, 168, 128, 2), "outside"),
+            (Ipv4Addr::new(192, 168, 127, 0), "network or broadcast"),
+            (Ipv4Addr::new(192, 168, 127, 255), "network or broadcast"),
+            (Ipv4Addr::new(192, 168, 127, 1), "gateway"),
+        ] {
+            let opts = BoxOptions {
+                static_ip: Some((ip, 24)),
+                ..Default::default()
+            };
+            let err = opts.validate_network().unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    boxlite_shared::errors::BoxliteError::InvalidArgument(_)
+                ),
+                "{ip}"
+            );
+            assert!(err.to_string().contains(reason), "{ip}: {err}");
+        }
+    }
+
     // ========================================================================
     // SecurityOptionsBuilder tests
     // ========================================================================
@@ -851,6 +1943,12 @@ mod tests {
         assert!(max.jailer_enabled);
         assert!(max.close_fds);
         assert!(max.sanitize_env);
+        assert!(max.isolation_enabled);
+
+        let disabled = SecurityOptionsBuilder::disabled().build();
+        assert!(!disabled.isolation_enabled);
+        assert!(!disabled.jailer_enabled);
+        assert!(!disabled.seccomp_enabled);
     }
 
     #[test]
@@ -924,4 +2022,55 @@ mod tests {
         assert!(opts1.resource_limits.max_processes.is_none());
         assert_eq!(opts2.resource_limits.max_processes, Some(50));
     }
+
+    #[test]
+    fn test_restart_policy_should_restart() {
+        assert!(!RestartPolicy::No.should_restart(0));
+
+        let on_failure = RestartPolicy::OnFailure { max_retries: 2 };
+        assert!(on_failure.should_restart(0));
+        assert!(on_failure.should_restart(1));
+        assert!(!on_failure.should_restart(2));
+        assert!(!RestartPolicy::OnFailure { max_retries: 0 }.should_restart(0));
+
+        assert!(RestartPolicy::Always.should_restart(100));
+        assert_eq!(BoxOptions::default().restart_policy, RestartPolicy::No);
+    }
+
+    #[test]
+    fn test_volume_host_path_expands_tilde() {
+        let home = home_dir().unwrap();
+        let volume = VolumeSpec {
+            host_path: "~/boxlite-missing-volume-dir".to_string(),
+            guest_path: "/data".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            volume.absolute_host_path().unwrap(),
+            std::path::absolute(home.join("boxlite-missing-volume-dir")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_normalize_volume_paths_resolves_relative() {
+        let mut opts = BoxOptions {
+            volumes: vec![VolumeSpec {
+                host_path: "./src".to_string(),
+                guest_path: "/app".to_string(),
+                read_only: true,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        opts.normalize_volume_paths().unwrap();
+
+        let expected = std::env::current_dir()
+            .unwrap()
+            .join("src")
+            .canonicalize()
+            .unwrap();
+        assert_eq!(PathBuf::from(&opts.volumes[0].host_path), expected);
+        assert!(opts.volumes[0].read_only);
+        assert_eq!(opts.volumes[0].guest_path, "/app");
+    }
 }
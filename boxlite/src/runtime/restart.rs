@@ -0,0 +1,105 @@
+//! Restart policy enforcement for boxes found dead during recovery.
+
+use std::future::Future;
+
+use crate::runtime::options::RestartPolicy;
+use boxlite_shared::BoxliteResult;
+
+/// Maximum start attempts in a single recovery pass.
+///
+/// Bounds `RestartPolicy::Always` so a box that can never start doesn't
+/// keep the runtime retrying forever.
+pub(crate) const MAX_RESTART_ATTEMPTS: u32 = 3;
+
+/// Re-run `start` while `policy` allows it.
+///
+/// `restart_count` is incremented before every attempt so the count
+/// survives a failed start. Returns true once an attempt succeeds.
+pub(crate) async fn restart_with_policy<F, Fut>(
+    policy: RestartPolicy,
+    restart_count: &mut u32,
+    mut start: F,
+) -> bool
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = BoxliteResult<()>>,
+{
+    let mut attempts = 0;
+    while attempts < MAX_RESTART_ATTEMPTS && policy.should_restart(*restart_count) {
+        attempts += 1;
+        *restart_count += 1;
+        match start().await {
+            Ok(()) => return true,
+            Err(e) => {
+                tracing::warn!(
+                    attempt = *restart_count,
+                    policy = ?policy,
+                    error = %e,
+                    "Restart attempt failed"
+                );
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use boxlite_shared::BoxliteError;
+    use std::cell::Cell;
+
+    #[tokio::test]
+    async fn test_on_failure_zero_retries_never_starts() {
+        let calls = Cell::new(0);
+        let mut count = 0;
+        let restarted = restart_with_policy(
+            RestartPolicy::OnFailure { max_retries: 0 },
+            &mut count,
+            || async {
+                calls.set(calls.get() + 1);
+                Ok(())
+            },
+        )
+        .await;
+
+        assert!(!restarted);
+        assert_eq!(calls.get(), 0);
+        assert_eq!(count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_always_triggers_restart() {
+        let calls = Cell::new(0);
+        let mut count = 0;
+        let restarted = restart_with_policy(RestartPolicy::Always, &mut count, || async {
+            calls.set(calls.get() + 1);
+            Ok(())
+        })
+        .await;
+
+        assert!(restarted);
+        assert_eq!(calls.get(), 1);
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_on_failure_stops_at_retry_limit() {
+        let calls = Cell::new(0);
+        let mut count = 1;
+        let restarted = restart_with_policy(
+            RestartPolicy::OnFailure { max_retries: 3 },
+            &mut count,
+            || async {
+                calls.set(calls.get() + 1);
+                Err(BoxliteError::Engine("boot failed".into()))
+            },
+        )
+        .await;
+
+        // Two retries left out of three
+        assert!(!restarted);
+        assert_eq!(calls.get(), 2);
+        assert_eq!(count, 3);
+    }
+}
@@ -1,25 +1,35 @@
 use crate::db::{BoxStore, Database};
+use crate::disk::Qcow2Helper;
 use crate::images::ImageManager;
 use crate::init_logging_for;
-use crate::litebox::config::BoxConfig;
+use crate::litebox::bundle;
+use crate::litebox::config::{BOX_CONFIG_VERSION, BoxConfig};
 use crate::litebox::{BoxManager, LiteBox, SharedBoxImpl};
 use crate::lock::{FileLockManager, LockManager};
 use crate::metrics::{RuntimeMetrics, RuntimeMetricsStorage};
+use crate::runtime::build;
 use crate::runtime::constants::filenames;
 use crate::runtime::guest_rootfs::GuestRootfs;
 use crate::runtime::layout::{FilesystemLayout, FsLayoutConfig};
 use crate::runtime::lock::RuntimeLock;
-use crate::runtime::options::{BoxOptions, BoxliteOptions};
+use crate::runtime::options::{
+    BoxOptions, BoxliteOptions, CleanTempOnStart, DbIntegrityMode, RootfsSpec,
+};
 use crate::runtime::signal_handler::timeout_to_duration;
 use crate::runtime::types::{BoxID, BoxInfo, BoxState, BoxStatus, ContainerID};
+use crate::runtime::warm_pool::WarmPool;
 use crate::vmm::VmmKind;
 use boxlite_shared::{BoxliteError, BoxliteResult, Transport};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock, Weak};
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard, Weak};
 use tokio::sync::OnceCell;
 use tokio_util::sync::CancellationToken;
 
+/// How often a started box's shim process is checked for an unexpected exit.
+const EXIT_WATCH_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
 /// Internal runtime state protected by single lock.
 ///
 /// **Shared via Arc**: This is the actual shared state that can be cloned cheaply.
@@ -50,12 +60,16 @@ pub struct RuntimeImpl {
     // ========================================================================
     // NO COORDINATION NEEDED: Immutable or internally synchronized
     // ========================================================================
+    /// Runtime options (immutable after init)
+    pub(crate) options: BoxliteOptions,
     /// Filesystem layout (immutable after init)
     pub(crate) layout: FilesystemLayout,
     /// Guest rootfs lazy initialization (Arc<OnceCell>)
     pub(crate) guest_rootfs: Arc<OnceCell<GuestRootfs>>,
     /// Runtime-wide metrics (AtomicU64 based, lock-free)
     pub(crate) runtime_metrics: RuntimeMetricsStorage,
+    /// Pre-started boxes for `get_or_create` (internal Mutex, None if disabled)
+    pub(crate) warm_pool: Option<WarmPool>,
 
     /// Per-entity lock manager for multiprocess-safe locking.
     ///
@@ -63,9 +77,9 @@ pub struct RuntimeImpl {
     /// across multiple processes. Similar to Podman's lock manager.
     pub(crate) lock_manager: Arc<dyn LockManager>,
 
-    /// Runtime filesystem lock (held for lifetime). Prevent from multiple process run on same
-    /// BOXLITE_HOME directory
-    pub(crate) _runtime_lock: RuntimeLock,
+    /// Runtime filesystem lock (held until `close()` or drop). Prevent from multiple process
+    /// run on same BOXLITE_HOME directory
+    runtime_lock: Mutex<Option<RuntimeLock>>,
 
     // ========================================================================
     // SHUTDOWN COORDINATION
@@ -75,6 +89,12 @@ pub struct RuntimeImpl {
     /// Use `.is_cancelled()` for sync checks, `.cancelled()` for async select!.
     /// Child tokens are passed to each box via `.child_token()`.
     pub(crate) shutdown_token: CancellationToken,
+    /// Cancels background tasks (metrics sampler, TTL reaper). Child of
+    /// `shutdown_token`; also cancelled when the runtime is dropped.
+    background_token: CancellationToken,
+    /// Shim PIDs of boxes started by this runtime instance and not stopped
+    /// since. Killed on drop when `kill_boxes_on_drop` is set.
+    session_pids: Mutex<HashMap<BoxID, u32>>,
 }
 
 /// Synchronized state protected by RwLock.
@@ -115,13 +135,18 @@ impl RuntimeImpl {
             )));
         }
 
+        options.layout_dirs.validate()?;
+
         // Configure bind mount support based on platform
         #[cfg(target_os = "linux")]
         let fs_config = FsLayoutConfig::with_bind_mount();
         #[cfg(not(target_os = "linux"))]
         let fs_config = FsLayoutConfig::without_bind_mount();
 
-        let layout = FilesystemLayout::new(options.home_dir.clone(), fs_config);
+        let layout = FilesystemLayout::new(
+            options.home_dir.clone(),
+            fs_config.with_dirs(options.layout_dirs.clone()),
+        );
 
         layout.prepare().map_err(|e| {
             BoxliteError::Storage(format!(
@@ -131,9 +156,13 @@ impl RuntimeImpl {
             ))
         })?;
 
-        init_logging_for(&layout)?;
+        init_logging_for(&layout, options.log_format, options.log_rotation)?;
 
         let runtime_lock = RuntimeLock::acquire(layout.home_dir()).map_err(|e| {
+            // Keep contention recognizable so callers can retry
+            if e.is_retryable() {
+                return e;
+            }
             BoxliteError::Internal(format!(
                 "Failed to acquire runtime lock at {}: {}",
                 layout.home_dir().display(),
@@ -141,37 +170,64 @@ impl RuntimeImpl {
             ))
         })?;
 
+        // Upgrade homes written by older releases; refuses newer layouts
+        crate::runtime::migrations::migrate_layout(&layout)?;
+
         // Clean temp dir contents to avoid stale files from previous runs
-        if let Ok(entries) = std::fs::read_dir(layout.temp_dir()) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() {
-                    let _ = std::fs::remove_dir_all(&path);
-                } else {
-                    let _ = std::fs::remove_file(&path);
+        clean_temp_dir(&layout.temp_dir(), options.clean_temp_on_start);
+
+        let db = Database::open_with(&layout.db_dir().join("boxlite.db"), &options.database)
+            .map_err(|e| {
+                BoxliteError::Storage(format!(
+                    "Failed to initialize database at {}: {}",
+                    layout.db_dir().join("boxlite.db").display(),
+                    e
+                ))
+            })?;
+
+        // Optional integrity pass before anything reads box rows
+        match options.db_integrity {
+            DbIntegrityMode::Off => {}
+            DbIntegrityMode::Check => {
+                let report = db.check_integrity()?;
+                if !report.is_ok() {
+                    tracing::warn!(
+                        sqlite_errors = ?report.sqlite_errors,
+                        corrupt_boxes = ?report.corrupt_boxes,
+                        newer_boxes = ?report.newer_boxes,
+                        "Database integrity check found problems; \
+                         use DbIntegrityMode::Repair to quarantine corrupt boxes"
+                    );
+                }
+            }
+            DbIntegrityMode::Repair => {
+                let report = db.repair()?;
+                if !report.is_ok() {
+                    tracing::warn!(
+                        sqlite_errors = ?report.sqlite_errors,
+                        quarantined = report.quarantined,
+                        newer_boxes = ?report.newer_boxes,
+                        "Database integrity repair found problems; corrupt boxes were \
+                         quarantined, boxes from a newer boxlite were left in place"
+                    );
                 }
             }
         }
 
-        let db = Database::open(&layout.db_dir().join("boxlite.db")).map_err(|e| {
+        let image_manager = ImageManager::new(
+            layout.images_dir(),
+            db.clone(),
+            options.image_registries.clone(),
+            options.pull_retry.clone(),
+        )
+        .map_err(|e| {
             BoxliteError::Storage(format!(
-                "Failed to initialize database at {}: {}",
-                layout.db_dir().join("boxlite.db").display(),
+                "Failed to initialize image manager at {}: {}",
+                layout.images_dir().display(),
                 e
             ))
         })?;
 
-        let image_manager =
-            ImageManager::new(layout.images_dir(), db.clone(), options.image_registries).map_err(
-                |e| {
-                    BoxliteError::Storage(format!(
-                        "Failed to initialize image manager at {}: {}",
-                        layout.images_dir().display(),
-                        e
-                    ))
-                },
-            )?;
-
         let box_store = BoxStore::new(db);
 
         // Initialize lock manager for per-entity multiprocess-safe locking
@@ -189,6 +245,11 @@ impl RuntimeImpl {
             "Initialized lock manager"
         );
 
+        let warm_pool = options.warm_pool.as_ref().map(WarmPool::new).transpose()?;
+
+        let shutdown_token = CancellationToken::new();
+        let background_token = shutdown_token.child_token();
+
         let inner = Arc::new(Self {
             sync_state: RwLock::new(SynchronizedState {
                 active_boxes_by_id: HashMap::new(),
@@ -196,18 +257,23 @@ impl RuntimeImpl {
             }),
             box_manager: BoxManager::new(box_store),
             image_manager,
+            options,
             layout,
             guest_rootfs: Arc::new(OnceCell::new()),
             runtime_metrics: RuntimeMetricsStorage::new(),
+            warm_pool,
             lock_manager,
-            _runtime_lock: runtime_lock,
-            shutdown_token: CancellationToken::new(),
+            runtime_lock: Mutex::new(Some(runtime_lock)),
+            shutdown_token,
+            background_token,
+            session_pids: Mutex::new(HashMap::new()),
         });
 
         tracing::debug!("initialized runtime");
 
         // Recover boxes from database
-        inner.recover_boxes()?;
+        let dead_boxes = inner.recover_boxes()?;
+        inner.schedule_restarts(dead_boxes);
 
         Ok(inner)
     }
@@ -227,6 +293,16 @@ impl RuntimeImpl {
         options: BoxOptions,
         name: Option<String>,
     ) -> BoxliteResult<LiteBox> {
+        let (config, state) = self.prepare_box(options, name)?;
+        self.register_box(config, state)
+    }
+
+    /// Validate `options` and build the config and state of a new box.
+    fn prepare_box(
+        &self,
+        mut options: BoxOptions,
+        name: Option<String>,
+    ) -> BoxliteResult<(BoxConfig, BoxState)> {
         // Check if runtime has been shut down
         if self.shutdown_token.is_cancelled() {
             return Err(BoxliteError::Stopped(
@@ -234,25 +310,36 @@ impl RuntimeImpl {
             ));
         }
 
-        // Check DB for existing name
-        if let Some(ref name) = name
-            && self.box_manager.lookup_box_id(name)?.is_some()
-        {
-            return Err(BoxliteError::InvalidArgument(format!(
-                "box with name '{}' already exists",
-                name
-            )));
-        }
+        // Reject requests exceeding runtime-wide resource ceilings
+        self.validate_options(&options)?;
+
+        // Anchor `~` and relative volume paths before they are persisted
+        options.normalize_volume_paths()?;
 
         // Initialize box variables with defaults
-        let (config, mut state) = self.init_box_variables(&options, name);
+        Ok(self.init_box_variables(&options, name))
+    }
 
+    /// Allocate a lock for a new box, persist it and hand out its handle.
+    fn register_box(
+        self: &Arc<Self>,
+        config: BoxConfig,
+        mut state: BoxState,
+    ) -> BoxliteResult<LiteBox> {
         // Allocate lock for this box
         let lock_id = self.lock_manager.allocate()?;
         state.set_lock_id(lock_id);
 
-        // Persist to database immediately (status = Configured)
-        if let Err(e) = self.box_manager.add_box(&config, &state) {
+        // Check the name and persist under the write lock, so concurrent
+        // creates with the same name can't both pass the check
+        let persisted = {
+            let mut sync = self.acquire_write();
+            Self::ensure_name_available(&mut sync, &self.box_manager, config.name.as_deref())
+                .and_then(|()| self.box_manager.add_box(&config, &state))
+        };
+
+        // Persist to database immediately
+        if let Err(e) = persisted {
             // Clean up the allocated lock on failure
             if let Err(free_err) = self.lock_manager.free(lock_id) {
                 tracing::error!(
@@ -267,12 +354,13 @@ impl RuntimeImpl {
         tracing::debug!(
             box_id = %config.id,
             lock_id = %lock_id,
-            "Created box with Configured status"
+            status = %state.status,
+            "Created box"
         );
 
         // Create LiteBox handle with shared BoxImpl
         // This also checks in-memory cache for duplicate names
-        let (box_impl, inserted) = self.get_or_create_box_impl(config, state);
+        let (box_impl, inserted) = self.get_or_create_box_impl(config, state)?;
         if !inserted {
             return Err(BoxliteError::InvalidArgument(
                 "box with this name already exists".into(),
@@ -287,6 +375,389 @@ impl RuntimeImpl {
         Ok(LiteBox::new(box_impl))
     }
 
+    /// Fail with `InvalidArgument` if `name` is used by a live handle or in the DB.
+    ///
+    /// Callers must hold the `sync_state` write lock across this check and
+    /// the write that claims the name.
+    fn ensure_name_available(
+        sync: &mut SynchronizedState,
+        box_manager: &BoxManager,
+        name: Option<&str>,
+    ) -> BoxliteResult<()> {
+        let Some(name) = name else {
+            return Ok(());
+        };
+        let taken_in_cache = match sync.active_boxes_by_name.get(name) {
+            Some(weak) if weak.strong_count() > 0 => true,
+            Some(_) => {
+                // Dead weak ref, clean it up
+                sync.active_boxes_by_name.remove(name);
+                false
+            }
+            None => false,
+        };
+        if taken_in_cache || box_manager.box_by_name(name)?.is_some() {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "box with name '{}' already exists",
+                name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Get the box named `name`, creating it with `options` if it does not exist.
+    ///
+    /// Returns the handle and whether the box was newly created. An existing
+    /// box whose options differ from `options` is rejected with `InvalidState`.
+    /// If a concurrent caller creates the same name first, its box is returned.
+    pub async fn get_or_create(
+        self: &Arc<Self>,
+        mut options: BoxOptions,
+        name: &str,
+    ) -> BoxliteResult<(LiteBox, bool)> {
+        // Compare against the options as they would have been persisted
+        options.normalize_volume_paths()?;
+
+        if let Some(existing) = self.get_by_name(name).await? {
+            Self::ensure_compatible(&existing, &options)?;
+            return Ok((existing, false));
+        }
+
+        let created = match self.claim_warm_box(&options, name).await {
+            Ok(Some(litebox)) => Ok(litebox),
+            Ok(None) => self.create(options.clone(), Some(name.to_string())).await,
+            Err(e) => Err(e),
+        };
+        match created {
+            Ok(litebox) => Ok((litebox, true)),
+            Err(e) => {
+                // Lost the race: another caller persisted this name in between
+                let Some(existing) = self.get_by_name(name).await? else {
+                    return Err(e);
+                };
+                Self::ensure_compatible(&existing, &options)?;
+                Ok((existing, false))
+            }
+        }
+    }
+
+    /// Hand out a ready box from the warm pool under `name`, if one matches.
+    ///
+    /// Returns `None` if the pool is disabled, `options` differ from its
+    /// template, or no box is ready. A claim triggers an asynchronous refill.
+    async fn claim_warm_box(
+        self: &Arc<Self>,
+        options: &BoxOptions,
+        name: &str,
+    ) -> BoxliteResult<Option<LiteBox>> {
+        let Some(pool) = &self.warm_pool else {
+            return Ok(None);
+        };
+        if !pool.matches(options)? {
+            return Ok(None);
+        }
+
+        let mut claimed = None;
+        while let Some(box_id) = pool.take() {
+            // Pooled boxes may have crashed, been removed or been renamed
+            // by hand while waiting
+            match self.get_info(box_id.as_str()).await? {
+                Some(info) if info.name.is_some() => continue,
+                Some(info) if info.status.is_running() => {}
+                Some(_) => {
+                    let _ = self.remove_box(&box_id, true).await;
+                    continue;
+                }
+                None => continue,
+            }
+            if let Err(e) = self.rename(box_id.as_str(), name) {
+                // Keep the box for the next claim; the caller creates one
+                tracing::warn!(box_id = %box_id, name = %name, error = %e, "Failed to claim warm box");
+                pool.push(box_id);
+                break;
+            }
+            claimed = Some(box_id);
+            break;
+        }
+        self.refill_warm_pool();
+
+        let Some(box_id) = claimed else {
+            return Ok(None);
+        };
+        tracing::info!(box_id = %box_id, name = %name, "Claimed box from warm pool");
+        self.get(box_id.as_str()).await
+    }
+
+    /// Top up the warm pool in the background, if it is configured and short.
+    ///
+    /// At most one refill runs at a time. Like the reaper, the task holds
+    /// only a weak reference and stops on shutdown or runtime drop; a box
+    /// that fails to start ends the refill until the next claim.
+    pub(crate) fn refill_warm_pool(self: &Arc<Self>) {
+        let Some(pool) = &self.warm_pool else {
+            return;
+        };
+        if pool.deficit() == 0 || !pool.begin_refill() {
+            return;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            pool.end_refill();
+            tracing::warn!("No Tokio runtime available, warm pool not refilled");
+            return;
+        };
+
+        let weak = Arc::downgrade(self);
+        let token = self.background_token.clone();
+        handle.spawn(async move {
+            loop {
+                let Some(runtime) = weak.upgrade() else {
+                    return;
+                };
+                let Some(pool) = &runtime.warm_pool else {
+                    return;
+                };
+                if token.is_cancelled() {
+                    pool.end_refill();
+                    return;
+                }
+                if pool.deficit() == 0 {
+                    pool.end_refill();
+                    // A claim may have raced with the end of this refill
+                    if pool.deficit() > 0 {
+                        runtime.refill_warm_pool();
+                    }
+                    return;
+                }
+                match runtime.start_warm_box(pool.template()).await {
+                    Ok(box_id) => {
+                        tracing::debug!(box_id = %box_id, "Added box to warm pool");
+                        pool.push(box_id);
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Failed to start warm pool box");
+                        pool.end_refill();
+                        return;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Create and start an unnamed box from the warm pool template.
+    ///
+    /// The box is persisted with the warm pool mark, so a runtime that dies
+    /// before claiming it removes it on the next start.
+    async fn start_warm_box(self: &Arc<Self>, template: &BoxOptions) -> BoxliteResult<BoxID> {
+        let (mut config, state) = self.prepare_box(template.clone(), None)?;
+        config.warm_pool = true;
+        let litebox = self.register_box(config, state)?;
+        let box_id = litebox.id().clone();
+        if let Err(e) = litebox.start().await {
+            drop(litebox);
+            if let Err(remove_err) = self.remove_box(&box_id, true).await {
+                tracing::warn!(box_id = %box_id, error = %remove_err, "Failed to remove warm box");
+            }
+            return Err(e);
+        }
+        Ok(box_id)
+    }
+
+    /// Create a new box with the same options as an existing one.
+    ///
+    /// The clone gets its own ID, container ID, state and disks; only the
+    /// creation options are copied, so it never shares the source's rootfs.
+    pub async fn clone_box(
+        self: &Arc<Self>,
+        source_id_or_name: &str,
+        new_name: Option<String>,
+    ) -> BoxliteResult<LiteBox> {
+        let source = self.get(source_id_or_name).await?.ok_or_else(|| {
+            BoxliteError::NotFound(format!("box not found: {}", source_id_or_name))
+        })?;
+        let options = source.config().options.clone();
+        drop(source);
+
+        self.create(options, new_name).await
+    }
+
+    /// Build an image from `containerfile`, resolving COPY sources in
+    /// `context_dir`. Returns the new image's reference.
+    pub async fn build(
+        self: &Arc<Self>,
+        context_dir: &Path,
+        containerfile: &Path,
+    ) -> BoxliteResult<String> {
+        if self.shutdown_token.is_cancelled() {
+            return Err(BoxliteError::Stopped(
+                "Cannot build image: runtime has been shut down".into(),
+            ));
+        }
+
+        let text = tokio::fs::read_to_string(containerfile)
+            .await
+            .map_err(|e| {
+                BoxliteError::InvalidArgument(format!(
+                    "Failed to read {}: {}",
+                    containerfile.display(),
+                    e
+                ))
+            })?;
+        let instructions = build::parse_containerfile(&text)?;
+        build::build(self, context_dir, instructions).await
+    }
+
+    /// Remove a cached image by reference.
+    ///
+    /// An image that a box was created from is only removed with `force`;
+    /// such boxes pull it again the next time they need it. The image's
+    /// cached rootfs disk is removed with it unless boxes still use it.
+    pub async fn remove_image(&self, reference: &str, force: bool) -> BoxliteResult<()> {
+        let Some(key) = self.image_manager.cached_key(reference, None).await? else {
+            return Err(BoxliteError::NotFound(format!("image {}", reference)));
+        };
+
+        let mut users = Vec::new();
+        for (config, _) in self.box_manager.all_boxes(false)? {
+            let RootfsSpec::Image(image_ref) = &config.options.rootfs else {
+                continue;
+            };
+            let box_key = self
+                .image_manager
+                .cached_key(image_ref, config.options.platform.as_deref())
+                .await
+                .ok()
+                .flatten();
+            if box_key.as_deref() == Some(key.as_str()) {
+                users.push(config.name.unwrap_or_else(|| config.id.to_string()));
+            }
+        }
+
+        if !users.is_empty() {
+            if !force {
+                return Err(BoxliteError::InvalidState(format!(
+                    "image {} is used by box(es) {}; remove them first or force removal",
+                    reference,
+                    users.join(", ")
+                )));
+            }
+            tracing::warn!(
+                image = %key,
+                boxes = %users.join(", "),
+                "Force-removing image still used by boxes"
+            );
+        }
+
+        self.image_manager.remove(&key, !users.is_empty()).await?;
+        Ok(())
+    }
+
+    /// Create a new box from a bundle written by `LiteBox::export_bundle`.
+    ///
+    /// The box gets a fresh ID and container ID, and its disk paths point into
+    /// its own box directory. The box starts out `Configured`; a bundled disk
+    /// becomes the read-only base its first start layers a fresh disk on.
+    pub async fn import_bundle(
+        self: &Arc<Self>,
+        bundle: &Path,
+        name: Option<String>,
+    ) -> BoxliteResult<LiteBox> {
+        if self.shutdown_token.is_cancelled() {
+            return Err(BoxliteError::Stopped(
+                "Cannot import box: runtime has been shut down".into(),
+            ));
+        }
+
+        let temp_base = self.layout.temp_dir();
+        std::fs::create_dir_all(&temp_base).map_err(|e| {
+            BoxliteError::Storage(format!(
+                "Failed to create temp directory {}: {}",
+                temp_base.display(),
+                e
+            ))
+        })?;
+        let staging = tempfile::tempdir_in(&temp_base).map_err(|e| {
+            BoxliteError::Storage(format!("Failed to create bundle staging directory: {}", e))
+        })?;
+
+        let unpacked = tokio::task::spawn_blocking({
+            let bundle = bundle.to_path_buf();
+            let dest = staging.path().to_path_buf();
+            move || bundle::read_bundle(&bundle, &dest)
+        })
+        .await
+        .map_err(|e| BoxliteError::Internal(format!("Bundle import task failed: {}", e)))??;
+
+        let options = unpacked.manifest.options;
+        self.validate_options(&options)?;
+        let (mut config, state) = self.init_box_variables(&options, name);
+
+        let box_dir = if let Some(raw) = unpacked.disk {
+            let layout = self
+                .layout
+                .box_layout(config.id.as_str(), options.isolate_mounts)?;
+            let base = layout.imported_base_path();
+            bundle::attach_disk(&mut config.options, &base);
+
+            let box_dir = config.box_home.clone();
+            let placed = tokio::task::spawn_blocking(move || -> BoxliteResult<()> {
+                std::fs::create_dir_all(base.parent().unwrap_or(&base)).map_err(|e| {
+                    BoxliteError::Storage(format!(
+                        "Failed to create box directory {}: {}",
+                        base.display(),
+                        e
+                    ))
+                })?;
+                crate::util::move_file(&raw, &base).map_err(|e| {
+                    BoxliteError::Storage(format!(
+                        "Failed to move imported disk to {}: {}",
+                        base.display(),
+                        e
+                    ))
+                })
+            })
+            .await
+            .map_err(|e| BoxliteError::Internal(format!("Bundle import task failed: {}", e)))?;
+            if let Err(e) = placed {
+                let _ = std::fs::remove_dir_all(&box_dir);
+                return Err(e);
+            }
+
+            Some(box_dir)
+        } else {
+            None
+        };
+
+        let litebox = self.register_box(config, state).inspect_err(|_| {
+            if let Some(box_dir) = &box_dir {
+                let _ = std::fs::remove_dir_all(box_dir);
+            }
+        })?;
+
+        tracing::info!(
+            box_id = %litebox.id(),
+            source_id = %unpacked.manifest.source_id,
+            bundle = %bundle.display(),
+            "Imported box bundle"
+        );
+        Ok(litebox)
+    }
+
+    /// Check that an existing box was created with `options`.
+    fn ensure_compatible(existing: &LiteBox, options: &BoxOptions) -> BoxliteResult<()> {
+        let to_value = |options: &BoxOptions| {
+            serde_json::to_value(options).map_err(|e| {
+                BoxliteError::Internal(format!("Failed to serialize box options: {}", e))
+            })
+        };
+        if to_value(&existing.config().options)? != to_value(options)? {
+            return Err(BoxliteError::InvalidState(format!(
+                "box '{}' already exists with different options",
+                existing.name().unwrap_or(existing.id().as_str())
+            )));
+        }
+        Ok(())
+    }
+
     /// Get a handle to an existing box by ID or name.
     ///
     /// Returns a LiteBox handle that can be used to operate on the box.
@@ -299,7 +770,7 @@ impl RuntimeImpl {
 
         // Check in-memory cache first (for boxes created but not yet persisted)
         {
-            let sync = self.sync_state.read().unwrap();
+            let sync = self.acquire_read();
 
             // Try as BoxID first
             if let Some(box_id) = BoxID::parse(id_or_name)
@@ -334,7 +805,7 @@ impl RuntimeImpl {
                 "Retrieved box from DB, getting or creating BoxImpl"
             );
 
-            let (box_impl, _) = self.get_or_create_box_impl(config, state);
+            let (box_impl, _) = self.get_or_create_box_impl(config, state)?;
             tracing::trace!(id_or_name = %id_or_name, "LiteBox created successfully");
             return Ok(Some(LiteBox::new(box_impl)));
         }
@@ -343,10 +814,185 @@ impl RuntimeImpl {
         Ok(None)
     }
 
+    /// Get a handle to the box named exactly `name`.
+    ///
+    /// Unlike [`get`](Self::get), IDs and ID prefixes never match, so a name
+    /// that happens to prefix another box's ID does not resolve to that box.
+    async fn get_by_name(self: &Arc<Self>, name: &str) -> BoxliteResult<Option<LiteBox>> {
+        {
+            let sync = self.acquire_read();
+            if let Some(weak) = sync.active_boxes_by_name.get(name)
+                && let Some(strong) = weak.upgrade()
+            {
+                return Ok(Some(LiteBox::new(strong)));
+            }
+        }
+
+        let this = Arc::clone(self);
+        let name_owned = name.to_string();
+        let db_result =
+            tokio::task::spawn_blocking(move || this.box_manager.box_by_name(&name_owned))
+                .await
+                .map_err(|e| BoxliteError::Internal(format!("spawn_blocking failed: {}", e)))??;
+
+        match db_result {
+            Some((config, state)) => {
+                let (box_impl, _) = self.get_or_create_box_impl(config, state)?;
+                Ok(Some(LiteBox::new(box_impl)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Get a handle to the box whose container has ID `container_id`.
+    ///
+    /// Only full 64-character container IDs are accepted.
+    pub async fn get_by_container_id(
+        self: &Arc<Self>,
+        container_id: &str,
+    ) -> BoxliteResult<Option<LiteBox>> {
+        if !ContainerID::is_valid(container_id) {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "invalid container ID '{}': expected {} lowercase hex characters",
+                container_id,
+                ContainerID::FULL_LENGTH
+            )));
+        }
+
+        // Check in-memory cache first
+        {
+            let sync = self.acquire_read();
+            if let Some(strong) = sync
+                .active_boxes_by_id
+                .values()
+                .filter_map(|weak| weak.upgrade())
+                .find(|strong| strong.config.container.id.as_str() == container_id)
+            {
+                return Ok(Some(LiteBox::new(strong)));
+            }
+        }
+
+        let this = Arc::clone(self);
+        let container_id = container_id.to_string();
+        let db_result = tokio::task::spawn_blocking(move || {
+            this.box_manager.box_by_container_id(&container_id)
+        })
+        .await
+        .map_err(|e| BoxliteError::Internal(format!("spawn_blocking failed: {}", e)))??;
+
+        let Some((config, state)) = db_result else {
+            return Ok(None);
+        };
+        let (box_impl, _) = self.get_or_create_box_impl(config, state)?;
+        Ok(Some(LiteBox::new(box_impl)))
+    }
+
     /// Remove a box completely by ID or name.
-    pub fn remove(&self, id_or_name: &str, force: bool) -> BoxliteResult<()> {
+    pub async fn remove(&self, id_or_name: &str, force: bool) -> BoxliteResult<()> {
         let box_id = self.resolve_id(id_or_name)?;
-        self.remove_box(&box_id, force)
+        self.remove_box(&box_id, force).await
+    }
+
+    /// Stop every box that is not already stopped.
+    ///
+    /// Boxes are stopped concurrently. Individual failures don't abort the
+    /// batch; each targeted box gets its own entry in the returned list.
+    pub async fn stop_all(self: &Arc<Self>) -> BoxliteResult<Vec<(BoxID, BoxliteResult<()>)>> {
+        let mut targets = Vec::new();
+        let mut failed = Vec::new();
+        for box_id in self.snapshot_box_ids()? {
+            match self.get(box_id.as_str()).await {
+                Ok(Some(litebox)) if !litebox.info().status.is_stopped() => targets.push(litebox),
+                // Already stopped, or removed since the snapshot
+                Ok(_) => {}
+                Err(e) => failed.push((box_id, Err(e))),
+            }
+        }
+
+        let stop_futures = targets
+            .iter()
+            .map(|litebox| async move { (litebox.id().clone(), litebox.stop().await) });
+        let mut results = futures::future::join_all(stop_futures).await;
+        results.extend(failed);
+        Ok(results)
+    }
+
+    /// Remove every box.
+    ///
+    /// Continues past individual failures (e.g. an active box with
+    /// `force=false`) and returns a result per box.
+    pub async fn remove_all(&self, force: bool) -> BoxliteResult<Vec<(BoxID, BoxliteResult<()>)>> {
+        let mut results = Vec::new();
+        for box_id in self.snapshot_box_ids()? {
+            let result = self.remove_box(&box_id, force).await;
+            results.push((box_id, result));
+        }
+        Ok(results)
+    }
+
+    /// Rename a box by ID or name.
+    ///
+    /// Persists the new name and re-keys the name cache. `LiteBox::name()` of
+    /// existing handles keeps the old name; `info()` and new handles from
+    /// get() see the new one.
+    pub fn rename(&self, id_or_name: &str, new_name: &str) -> BoxliteResult<()> {
+        if new_name.is_empty() {
+            return Err(BoxliteError::InvalidArgument(
+                "box name must not be empty".into(),
+            ));
+        }
+
+        let box_id = self.resolve_id(id_or_name)?;
+
+        // Hold the write lock so a concurrent create() can't claim the name
+        let mut sync = self.acquire_write();
+
+        let taken_in_cache = sync
+            .active_boxes_by_name
+            .get(new_name)
+            .and_then(|weak| weak.upgrade())
+            .is_some_and(|strong| strong.id() != &box_id);
+        let taken_in_db = self
+            .box_manager
+            .box_by_name(new_name)?
+            .is_some_and(|(config, _)| config.id != box_id);
+        if taken_in_cache || taken_in_db {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "box with name '{}' already exists",
+                new_name
+            )));
+        }
+
+        let (mut config, _) = self
+            .box_manager
+            .box_by_id(&box_id)?
+            .ok_or_else(|| BoxliteError::NotFound(box_id.to_string()))?;
+        let old_name = config.name.replace(new_name.to_string());
+        if old_name.as_deref() == Some(new_name) {
+            return Ok(());
+        }
+        // A named box is no longer up for grabs by the warm pool
+        config.warm_pool = false;
+        self.box_manager.update_config(&config)?;
+
+        // Re-key the cached BoxImpl rather than dropping it: it may own the
+        // running VM, and get() must keep returning handles that share it.
+        if let Some(old_name) = &old_name {
+            sync.active_boxes_by_name.remove(old_name);
+        }
+        if let Some(box_impl) = sync.active_boxes_by_id.get(&box_id).and_then(Weak::upgrade) {
+            box_impl.set_name(Some(new_name.to_string()));
+            sync.active_boxes_by_name
+                .insert(new_name.to_string(), Arc::downgrade(&box_impl));
+        }
+
+        tracing::info!(
+            box_id = %box_id,
+            old_name = ?old_name,
+            new_name = %new_name,
+            "Renamed box"
+        );
+        Ok(())
     }
 
     // ========================================================================
@@ -359,7 +1005,7 @@ impl RuntimeImpl {
     pub async fn get_info(self: &Arc<Self>, id_or_name: &str) -> BoxliteResult<Option<BoxInfo>> {
         // Check in-memory cache first (for boxes created but not yet persisted)
         {
-            let sync = self.sync_state.read().unwrap();
+            let sync = self.acquire_read();
 
             // Try as BoxID first
             if let Some(box_id) = BoxID::parse(id_or_name)
@@ -412,7 +1058,7 @@ impl RuntimeImpl {
 
         // Add in-memory boxes not yet persisted
         {
-            let sync = self.sync_state.read().unwrap();
+            let sync = self.acquire_read();
             for (box_id, weak) in &sync.active_boxes_by_id {
                 if !seen_ids.contains(box_id)
                     && let Some(strong) = weak.upgrade()
@@ -424,7 +1070,102 @@ impl RuntimeImpl {
         }
 
         // Sort by creation time (newest first)
-        infos.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        infos.sort_by_key(|info| std::cmp::Reverse(info.created_at));
+        Ok(infos)
+    }
+
+    /// List boxes carrying all of the given labels, newest first.
+    pub async fn list_info_filtered(
+        self: &Arc<Self>,
+        label_selector: &[(String, String)],
+    ) -> BoxliteResult<Vec<BoxInfo>> {
+        let mut infos = self.list_info().await?;
+        infos.retain(|info| info.matches_labels(label_selector));
+        Ok(infos)
+    }
+
+    /// List boxes whose status is one of `statuses`, newest first.
+    ///
+    /// Queries only matching rows from the database, then applies the
+    /// fresher in-memory state of cached boxes on top.
+    pub async fn list_by_status(
+        self: &Arc<Self>,
+        statuses: &[BoxStatus],
+    ) -> BoxliteResult<Vec<BoxInfo>> {
+        let this = Arc::clone(self);
+        let wanted = statuses.to_vec();
+        let db_boxes =
+            tokio::task::spawn_blocking(move || this.box_manager.boxes_by_status(&wanted))
+                .await
+                .map_err(|e| BoxliteError::Internal(format!("spawn_blocking failed: {}", e)))??;
+
+        let mut by_id: HashMap<BoxID, BoxInfo> = db_boxes
+            .into_iter()
+            .map(|(config, state)| (config.id.clone(), BoxInfo::new(&config, &state)))
+            .collect();
+
+        // Cached boxes may be ahead of the database (or not persisted yet)
+        {
+            let sync = self.acquire_read();
+            for (box_id, weak) in &sync.active_boxes_by_id {
+                let Some(strong) = weak.upgrade() else {
+                    continue;
+                };
+                let info = strong.info();
+                if statuses.contains(&info.status) {
+                    by_id.insert(box_id.clone(), info);
+                } else {
+                    by_id.remove(box_id);
+                }
+            }
+        }
+
+        let mut infos: Vec<_> = by_id.into_values().collect();
+        infos.sort_by_key(|info| std::cmp::Reverse(info.created_at));
+        Ok(infos)
+    }
+
+    /// List boxes created in `[start, end)`, newest first.
+    ///
+    /// Queries the database by creation time, then adds cached boxes that
+    /// are in the window but not persisted yet.
+    pub async fn list_created_between(
+        self: &Arc<Self>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> BoxliteResult<Vec<BoxInfo>> {
+        if start >= end {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "Invalid time range: start ({}) must be before end ({})",
+                start, end
+            )));
+        }
+
+        let this = Arc::clone(self);
+        let db_boxes =
+            tokio::task::spawn_blocking(move || this.box_manager.boxes_created_between(start, end))
+                .await
+                .map_err(|e| BoxliteError::Internal(format!("spawn_blocking failed: {}", e)))??;
+
+        let mut by_id: HashMap<BoxID, BoxInfo> = db_boxes
+            .into_iter()
+            .map(|(config, state)| (config.id.clone(), BoxInfo::new(&config, &state)))
+            .collect();
+
+        {
+            let sync = self.acquire_read();
+            for (box_id, weak) in &sync.active_boxes_by_id {
+                if let Some(strong) = weak.upgrade() {
+                    let info = strong.info();
+                    if info.created_at >= start && info.created_at < end {
+                        by_id.insert(box_id.clone(), info);
+                    }
+                }
+            }
+        }
+
+        let mut infos: Vec<_> = by_id.into_values().collect();
+        infos.sort_by_key(|info| std::cmp::Reverse(info.created_at));
         Ok(infos)
     }
 
@@ -434,7 +1175,7 @@ impl RuntimeImpl {
     pub async fn exists(self: &Arc<Self>, id_or_name: &str) -> BoxliteResult<bool> {
         // Check in-memory cache first
         {
-            let sync = self.sync_state.read().unwrap();
+            let sync = self.acquire_read();
 
             // Try as BoxID first
             if let Some(box_id) = BoxID::parse(id_or_name)
@@ -472,6 +1213,224 @@ impl RuntimeImpl {
         RuntimeMetrics::new(self.runtime_metrics.clone())
     }
 
+    /// Start the background per-box metrics sampler, if configured.
+    ///
+    /// Requires a Tokio runtime on the current thread; without one the
+    /// sampler is skipped and metrics fall back to on-demand reads. The task
+    /// holds only a weak reference and stops on shutdown or runtime drop.
+    pub(crate) fn start_metrics_sampler(self: &Arc<Self>) {
+        let Some(interval) = self.options.metrics_interval else {
+            return;
+        };
+        if interval.is_zero() {
+            tracing::warn!("metrics_interval is zero, background sampling disabled");
+            return;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            tracing::warn!("No Tokio runtime available, background metrics sampling disabled");
+            return;
+        };
+
+        let weak = Arc::downgrade(self);
+        let token = self.background_token.clone();
+        handle.spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = ticker.tick() => {}
+                }
+                let Some(runtime) = weak.upgrade() else {
+                    break;
+                };
+                for box_impl in runtime.active_boxes() {
+                    box_impl.sample_metrics();
+                }
+            }
+            tracing::debug!("Metrics sampler stopped");
+        });
+
+        tracing::debug!(
+            interval_ms = interval.as_millis() as u64,
+            "Started metrics sampler"
+        );
+    }
+
+    /// Record a final metrics sample for every running box.
+    ///
+    /// A no-op unless the background sampler is configured, as only sampled
+    /// windows and disk counters would lose the last interval on teardown.
+    fn flush_metrics(&self) {
+        if self.options.metrics_interval.is_none_or(|i| i.is_zero()) {
+            return;
+        }
+        for box_impl in self.active_boxes() {
+            box_impl.sample_metrics();
+        }
+    }
+
+    // ========================================================================
+    // PUBLIC API - EXPIRY
+    // ========================================================================
+
+    /// Stop and remove every box whose TTL has run out.
+    ///
+    /// Active boxes are stopped gracefully first. Failures are logged and
+    /// the box is left for the next pass. Returns the IDs that were removed.
+    pub async fn reap_expired(self: &Arc<Self>) -> BoxliteResult<Vec<BoxID>> {
+        let this = Arc::clone(self);
+        let db_boxes = tokio::task::spawn_blocking(move || this.box_manager.all_boxes(false))
+            .await
+            .map_err(|e| BoxliteError::Internal(format!("spawn_blocking failed: {}", e)))??;
+
+        let now = Utc::now();
+        let mut reaped = Vec::new();
+        for (config, state) in db_boxes {
+            if !config.is_expired(now) {
+                continue;
+            }
+            let box_id = config.id;
+
+            if state.status.is_active()
+                && let Ok(Some(litebox)) = self.get(box_id.as_str()).await
+                && let Err(e) = litebox.stop().await
+            {
+                tracing::warn!(
+                    box_id = %box_id,
+                    error = %e,
+                    "Failed to stop expired box, removing forcefully"
+                );
+            }
+
+            // Stopping an auto_remove box already removed it
+            match self.remove_box(&box_id, true).await {
+                Ok(()) | Err(BoxliteError::NotFound(_)) => {
+                    tracing::info!(box_id = %box_id, "Reaped expired box");
+                    reaped.push(box_id);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        box_id = %box_id,
+                        error = %e,
+                        "Failed to remove expired box"
+                    );
+                }
+            }
+        }
+        Ok(reaped)
+    }
+
+    /// Start the background reaper of expired boxes, if configured.
+    ///
+    /// Like the metrics sampler, it needs a Tokio runtime, holds only a weak
+    /// reference and stops on shutdown or runtime drop.
+    pub(crate) fn start_reaper(self: &Arc<Self>) {
+        let Some(interval) = self.options.reap_interval else {
+            return;
+        };
+        if interval.is_zero() {
+            tracing::warn!("reap_interval is zero, background reaping disabled");
+            return;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            tracing::warn!("No Tokio runtime available, background reaping disabled");
+            return;
+        };
+
+        let weak = Arc::downgrade(self);
+        let token = self.background_token.clone();
+        handle.spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = ticker.tick() => {}
+                }
+                let Some(runtime) = weak.upgrade() else {
+                    break;
+                };
+                if let Err(e) = runtime.reap_expired().await {
+                    tracing::warn!(error = %e, "Failed to reap expired boxes");
+                }
+            }
+            tracing::debug!("Reaper stopped");
+        });
+
+        tracing::debug!(
+            interval_ms = interval.as_millis() as u64,
+            "Started expired box reaper"
+        );
+    }
+
+    /// Send `signal` to the main process of every running box in this runtime.
+    ///
+    /// Only boxes with a live handle are signalled. Returns how many boxes
+    /// were signalled; per-box failures are logged and skipped.
+    pub async fn signal_running_boxes(&self, signal: i32) -> usize {
+        let boxes: Vec<_> = {
+            let sync = self.acquire_read();
+            sync.active_boxes_by_id
+                .values()
+                .filter_map(|weak| weak.upgrade())
+                .filter(|b| b.state.read().status.is_running())
+                .collect()
+        };
+
+        let mut signalled = 0;
+        for b in boxes {
+            match b.signal(signal).await {
+                Ok(()) => signalled += 1,
+                Err(e) => {
+                    tracing::warn!(box_id = %b.config.id, signal, error = %e, "Failed to signal box")
+                }
+            }
+        }
+        signalled
+    }
+
+    /// Relay host SIGTERM/SIGINT to all running boxes until shutdown.
+    ///
+    /// Like the reaper, holds only a weak reference and stops on shutdown
+    /// or runtime drop.
+    pub(crate) fn forward_signals(self: &Arc<Self>) -> BoxliteResult<()> {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let handle = tokio::runtime::Handle::try_current().map_err(|_| {
+            BoxliteError::Unsupported("Signal forwarding requires a Tokio runtime".into())
+        })?;
+        let _enter = handle.enter();
+        let signal_err = |e: std::io::Error| {
+            BoxliteError::Internal(format!("Failed to install signal handler: {}", e))
+        };
+        let mut sig_term = signal(SignalKind::terminate()).map_err(signal_err)?;
+        let mut sig_int = signal(SignalKind::interrupt()).map_err(signal_err)?;
+
+        let weak = Arc::downgrade(self);
+        let token = self.background_token.clone();
+        handle.spawn(async move {
+            loop {
+                let sig = tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = sig_term.recv() => libc::SIGTERM,
+                    _ = sig_int.recv() => libc::SIGINT,
+                };
+                let Some(runtime) = weak.upgrade() else {
+                    break;
+                };
+                let count = runtime.signal_running_boxes(sig).await;
+                tracing::info!(
+                    signal = sig,
+                    boxes = count,
+                    "Forwarded host signal to boxes"
+                );
+            }
+            tracing::debug!("Signal forwarding stopped");
+        });
+        Ok(())
+    }
+
     // ========================================================================
     // PUBLIC API - SHUTDOWN
     // ========================================================================
@@ -496,17 +1455,24 @@ impl RuntimeImpl {
 
         tracing::info!("Initiating runtime shutdown");
 
+        // Take a last sample while the VMs are still up; the cancel below
+        // stops the sampler and makes boxes skip further samples
+        self.flush_metrics();
+
         // Cancel the shutdown token - marks shutdown and signals all in-flight operations
         self.shutdown_token.cancel();
 
+        // Unclaimed warm boxes are never handed out again; remove rather than stop them
+        if let Some(pool) = &self.warm_pool {
+            for box_id in pool.drain() {
+                if let Err(e) = self.remove_box(&box_id, true).await {
+                    tracing::warn!(box_id = %box_id, error = %e, "Failed to remove warm box");
+                }
+            }
+        }
+
         // Collect all active boxes
-        let active_boxes: Vec<SharedBoxImpl> = {
-            let sync = self.sync_state.read().unwrap();
-            sync.active_boxes_by_id
-                .values()
-                .filter_map(|weak| weak.upgrade())
-                .collect()
-        };
+        let active_boxes = self.active_boxes();
 
         if active_boxes.is_empty() {
             tracing::info!("No active boxes to shutdown");
@@ -563,17 +1529,70 @@ impl RuntimeImpl {
         }
     }
 
+    /// Shut down, then release the home directory for other runtimes.
+    ///
+    /// Flushes a final metrics sample for running boxes, stops every cached
+    /// box gracefully, kills session-started shims that
+    /// have no handle left to stop them, and drops the runtime lock even if
+    /// outstanding handles keep this instance alive. Safe to call after
+    /// `shutdown()`; dropping the runtime afterwards is a no-op.
+    pub async fn close(&self) -> BoxliteResult<()> {
+        let result = self.shutdown(None).await;
+        self.kill_session_boxes();
+
+        if let Some(lock) = self.runtime_lock.lock().unwrap().take() {
+            drop(lock);
+            tracing::info!("Runtime closed");
+        }
+        result
+    }
+
     // ========================================================================
     // INTERNAL - BOX OPERATIONS
     // ========================================================================
 
+    /// Snapshot of all live BoxImpl instances in the cache.
+    fn active_boxes(&self) -> Vec<SharedBoxImpl> {
+        let sync = self.acquire_read();
+        sync.active_boxes_by_id
+            .values()
+            .filter_map(|weak| weak.upgrade())
+            .collect()
+    }
+
+    /// IDs of all persisted and cached boxes, newest first.
+    ///
+    /// Taken under the coordination lock so a box whose creation is in
+    /// flight is either fully visible (in the database or cache) or not
+    /// included at all.
+    fn snapshot_box_ids(&self) -> BoxliteResult<Vec<BoxID>> {
+        let sync = self.acquire_write();
+
+        let mut boxes: Vec<_> = self
+            .box_manager
+            .all_boxes(false)?
+            .into_iter()
+            .map(|(config, _)| (config.id, config.created_at))
+            .collect();
+        for (box_id, weak) in &sync.active_boxes_by_id {
+            if let Some(strong) = weak.upgrade()
+                && !boxes.iter().any(|(id, _)| id == box_id)
+            {
+                boxes.push((box_id.clone(), strong.config.created_at));
+            }
+        }
+
+        boxes.sort_by_key(|(_, created_at)| std::cmp::Reverse(*created_at));
+        Ok(boxes.into_iter().map(|(id, _)| id).collect())
+    }
+
     /// Resolve an ID or name to the actual box ID.
     ///
     /// Checks in-memory cache first (for boxes not yet persisted), then database.
     fn resolve_id(&self, id_or_name: &str) -> BoxliteResult<BoxID> {
         // Check in-memory cache first
         {
-            let sync = self.sync_state.read().unwrap();
+            let sync = self.acquire_read();
 
             // Try as BoxID first
             if let Some(box_id) = BoxID::parse(id_or_name)
@@ -597,6 +1616,165 @@ impl RuntimeImpl {
             .ok_or_else(|| BoxliteError::NotFound(id_or_name.to_string()))
     }
 
+    /// Record that this runtime started `box_id` as shim process `pid`.
+    pub(crate) fn track_session_box(&self, box_id: &BoxID, pid: u32) {
+        self.session_pids
+            .lock()
+            .unwrap()
+            .insert(box_id.clone(), pid);
+    }
+
+    /// Forget a session-started box once its shim is gone.
+    pub(crate) fn untrack_session_box(&self, box_id: &BoxID) {
+        self.session_pids.lock().unwrap().remove(box_id);
+    }
+
+    /// SIGKILL the shims of boxes started this session (best effort).
+    ///
+    /// Each PID is verified to still be the box's shim first, so a recycled
+    /// PID is never killed.
+    fn kill_session_boxes(&self) {
+        let session_pids = std::mem::take(&mut *self.session_pids.lock().unwrap());
+        for (box_id, pid) in session_pids {
+            if !crate::util::is_same_process(pid, box_id.as_str(), None) {
+                continue;
+            }
+            if crate::util::kill_process(pid) {
+                tracing::info!(box_id = %box_id, pid = pid, "Killed box on runtime drop");
+            } else {
+                tracing::warn!(box_id = %box_id, pid = pid, "Failed to kill box on runtime drop");
+            }
+        }
+    }
+
+    /// Watch a started box's shim process and react if it exits on its own.
+    ///
+    /// Polls `pid` until the box's `shutdown_token` is cancelled (stop(),
+    /// runtime shutdown, or a previous exit) or the process exits, in which
+    /// case [`handle_unexpected_exit`](Self::handle_unexpected_exit) runs. An
+    /// exited shim nobody reaped yet counts as exited. Holds only a weak
+    /// runtime reference.
+    pub(crate) fn spawn_exit_watcher(
+        self: &Arc<Self>,
+        box_id: BoxID,
+        pid: u32,
+        shutdown_token: CancellationToken,
+    ) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        let weak = Arc::downgrade(self);
+        handle.spawn(async move {
+            let mut ticker = tokio::time::interval(EXIT_WATCH_INTERVAL);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = shutdown_token.cancelled() => return,
+                    _ = ticker.tick() => {}
+                }
+                if crate::util::is_running(pid) {
+                    continue;
+                }
+                let Some(runtime) = weak.upgrade() else {
+                    return;
+                };
+                if let Err(e) = runtime
+                    .handle_unexpected_exit(&box_id, &shutdown_token)
+                    .await
+                {
+                    tracing::warn!(box_id = %box_id, error = %e, "Failed to handle box exit");
+                }
+                return;
+            }
+        });
+    }
+
+    /// Mark a box whose shim exited without stop() as stopped, removing it
+    /// if `auto_remove` is set.
+    ///
+    /// `shutdown_token` is the box's token: if it is already cancelled,
+    /// stop() owns the teardown and this is a no-op. Otherwise it is
+    /// cancelled here so a later stop() doesn't repeat the work.
+    pub(crate) async fn handle_unexpected_exit(
+        &self,
+        box_id: &BoxID,
+        shutdown_token: &CancellationToken,
+    ) -> BoxliteResult<()> {
+        if shutdown_token.is_cancelled() {
+            return Ok(());
+        }
+        shutdown_token.cancel();
+        self.untrack_session_box(box_id);
+
+        let Some((config, mut state)) = self.box_manager.box_by_id(box_id)? else {
+            return Ok(());
+        };
+        tracing::warn!(box_id = %box_id, pid = ?state.pid, "Box process exited unexpectedly");
+
+        let cached = {
+            let sync = self.acquire_read();
+            sync.active_boxes_by_id.get(box_id).and_then(Weak::upgrade)
+        };
+        state.mark_stop();
+        self.box_manager.save_box(box_id, &state)?;
+        if let Some(box_impl) = cached {
+            box_impl.state.write().mark_stop();
+            box_impl.stopped.cancel();
+        }
+        self.invalidate_box_impl(box_id, config.name.as_deref());
+
+        if config.options.auto_remove {
+            tracing::info!(box_id = %box_id, "Removing auto_remove box after exit");
+            self.remove_box(box_id, false).await?;
+        }
+        Ok(())
+    }
+
+    /// Refuse to `action` box `id` while another box's disk is layered on a
+    /// base inside its `box_home` (`DiskConfig::backing_file`).
+    ///
+    /// Starting such a box would write to the base under its overlays, and
+    /// removing it would delete the base. A started overlay's backing path is
+    /// read from its qcow2 header; others fall back to their options.
+    pub(crate) fn ensure_no_overlay_depends_on(
+        &self,
+        id: &BoxID,
+        box_home: &Path,
+        action: &str,
+    ) -> BoxliteResult<()> {
+        let box_home = resolve_path(box_home);
+        let mut dependents = Vec::new();
+        for (config, _) in self.box_manager.all_boxes(false)? {
+            if &config.id == id {
+                continue;
+            }
+            let disk_path = self
+                .layout
+                .box_layout(config.id.as_str(), config.options.isolate_mounts)?
+                .disk_path();
+            let backing = match Qcow2Helper::backing_file(&disk_path) {
+                Ok(Some(base)) => Some(disk_path.parent().unwrap_or(Path::new("/")).join(base)),
+                Ok(None) => None,
+                // Not started yet: the overlay will be built on the configured base
+                Err(_) => config.options.disk.backing_file.clone(),
+            };
+            if backing.is_some_and(|base| resolve_path(&base).starts_with(&box_home)) {
+                dependents.push(config.name.unwrap_or_else(|| config.id.to_string()));
+            }
+        }
+
+        if dependents.is_empty() {
+            return Ok(());
+        }
+        Err(BoxliteError::InvalidState(format!(
+            "cannot {} box {}: its directory holds the disk base of box(es) {}; remove them first",
+            action,
+            id,
+            dependents.join(", ")
+        )))
+    }
+
     /// Remove a box from the runtime (internal implementation).
     ///
     /// This is the internal implementation called by both `BoxliteRuntime::remove()`
@@ -612,19 +1790,41 @@ impl RuntimeImpl {
     /// # Errors
     /// - Box not found
     /// - Box is active and force=false
-    pub(crate) fn remove_box(&self, id: &BoxID, force: bool) -> BoxliteResult<()> {
+    /// - Another box's disk overlay uses a base inside this box's directory
+    pub(crate) async fn remove_box(&self, id: &BoxID, force: bool) -> BoxliteResult<()> {
         tracing::debug!(box_id = %id, force = force, "RuntimeInnerImpl::remove_box called");
 
         // Try to get box from database first
         if let Some((config, state)) = self.box_manager.box_by_id(id)? {
             // Box exists in database - handle as before
+            self.ensure_no_overlay_depends_on(id, &config.box_home, "remove")?;
             let mut state = state;
             if state.status.is_active() {
                 if force {
-                    // Force mode: kill the process directly
-                    if let Some(pid) = state.pid {
+                    // Force mode: kill the shim and its helpers directly,
+                    // unless the PID now belongs to some other process
+                    if let Some(pid) = state.pid
+                        && crate::util::is_same_process(pid, id.as_str(), state.pid_start_time)
+                    {
                         tracing::info!(box_id = %id, pid = pid, "Force killing active box");
-                        crate::util::kill_process(pid);
+                        let (pgid, start_time) = (state.pgid, state.pid_start_time);
+                        let box_id = id.clone();
+                        // Waits up to the kill grace period
+                        tokio::task::spawn_blocking(move || {
+                            crate::util::kill_process_tree(pid);
+                            if let Some(pgid) = pgid {
+                                crate::util::kill_shim_process_group(
+                                    pgid,
+                                    pid,
+                                    box_id.as_str(),
+                                    start_time,
+                                );
+                            }
+                        })
+                        .await
+                        .map_err(|e| {
+                            BoxliteError::Internal(format!("Force kill task failed: {}", e))
+                        })?;
                     }
                     // Update status to stopped and save
                     state.set_status(BoxStatus::Stopped);
@@ -682,7 +1882,7 @@ impl RuntimeImpl {
 
         // Box not in database - check in-memory cache
         let box_impl = {
-            let sync = self.sync_state.read().unwrap();
+            let sync = self.acquire_read();
             sync.active_boxes_by_id
                 .get(id)
                 .and_then(|weak| weak.upgrade())
@@ -700,7 +1900,7 @@ impl RuntimeImpl {
             drop(state);
 
             // Invalidate cache (removes from in-memory maps)
-            self.invalidate_box_impl(id, box_impl.config.name.as_deref());
+            self.invalidate_box_impl(id, box_impl.name().as_deref());
 
             // Delete box directory if it exists
             let box_home = &box_impl.config.box_home;
@@ -727,6 +1927,38 @@ impl RuntimeImpl {
     // INTERNAL - INITIALIZATION
     // ========================================================================
 
+    /// Validate box options against runtime-wide resource ceilings.
+    ///
+    /// Only explicitly requested values are checked; unset ceilings allow anything.
+    fn validate_options(&self, options: &BoxOptions) -> BoxliteResult<()> {
+        if let RootfsSpec::Directory(dir) = &options.rootfs {
+            crate::rootfs::operations::resolve_rootfs_dir(dir)?;
+        }
+
+        if let (Some(requested), Some(max)) = (options.cpus, self.options.max_cpus)
+            && requested > max
+        {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "requested {} cpus exceeds runtime ceiling of {}",
+                requested, max
+            )));
+        }
+
+        if let (Some(requested), Some(max)) = (options.memory_mib, self.options.max_memory_mib)
+            && requested > max
+        {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "requested {} MiB memory exceeds runtime ceiling of {} MiB",
+                requested, max
+            )));
+        }
+
+        // Reject bad guest addressing at create time rather than first start
+        options.validate_network()?;
+
+        Ok(())
+    }
+
     /// Initialize box variables with defaults.
     ///
     /// Creates config and state for a new box. State starts with Configured status.
@@ -757,8 +1989,10 @@ impl RuntimeImpl {
 
         // Create config with defaults + user options
         let config = BoxConfig {
+            config_version: BOX_CONFIG_VERSION,
             id: box_id,
             name,
+            warm_pool: false,
             created_at: now,
             container,
             options: options.clone(),
@@ -775,11 +2009,14 @@ impl RuntimeImpl {
     }
 
     /// Recover boxes from persistent storage on runtime startup.
-    fn recover_boxes(&self) -> BoxliteResult<()> {
+    ///
+    /// Returns the IDs of boxes that were running but whose VM is gone
+    /// (crashed, or reset by a host reboot), for restart policy handling.
+    fn recover_boxes(&self) -> BoxliteResult<Vec<BoxID>> {
         use crate::util::{is_process_alive, is_same_process};
 
         // Check for system reboot and reset active boxes
-        self.box_manager.check_and_handle_reboot()?;
+        let mut dead_boxes = self.box_manager.check_and_handle_reboot()?;
 
         // Clear all locks before recovery - safe because we hold the runtime lock.
         // This ensures a clean slate for lock allocation during recovery.
@@ -796,6 +2033,7 @@ impl RuntimeImpl {
 
         // Phase 1: Clean up boxes that shouldn't persist
         // - auto_remove=true boxes: these are ephemeral and shouldn't survive restarts
+        // - Unclaimed warm pool boxes: the pool that held them is gone
         // - Orphaned active boxes: was Running but directory is missing (crashed mid-operation)
         //
         // Note: We don't remove Configured or Stopped boxes without directories because:
@@ -810,6 +2048,13 @@ impl RuntimeImpl {
                     "Removing auto_remove=true box during recovery"
                 );
                 true
+            } else if config.warm_pool {
+                // Its pool died with the previous runtime; nobody can claim it
+                tracing::info!(
+                    box_id = %config.id,
+                    "Removing unclaimed warm pool box during recovery"
+                );
+                true
             } else if state.status.is_active() && !config.box_home.exists() {
                 // Only remove orphaned boxes that were in an active state
                 // Stopped boxes might not have a directory if never started
@@ -832,7 +2077,24 @@ impl RuntimeImpl {
         // Remove invalid boxes from database and cleanup their directories
         for box_id in &boxes_to_remove {
             // Find the config to get box_home path
-            if let Some((config, _)) = persisted.iter().find(|(c, _)| &c.id == box_id) {
+            if let Some((config, state)) = persisted.iter().find(|(c, _)| &c.id == box_id) {
+                // Warm boxes are left running; stop the shim before its
+                // directory goes away
+                if config.warm_pool
+                    && let Some(pid) = state.pid
+                    && is_same_process(pid, box_id.as_str(), state.pid_start_time)
+                {
+                    crate::util::kill_process_tree(pid);
+                    if let Some(pgid) = state.pgid {
+                        crate::util::kill_shim_process_group(
+                            pgid,
+                            pid,
+                            box_id.as_str(),
+                            state.pid_start_time,
+                        );
+                    }
+                }
+
                 // Clean up box directory if it exists
                 if config.box_home.exists()
                     && let Err(e) = std::fs::remove_dir_all(&config.box_home)
@@ -858,7 +2120,7 @@ impl RuntimeImpl {
 
         if !boxes_to_remove.is_empty() {
             tracing::info!(
-                "Cleaned up {} boxes during recovery (auto_remove or orphaned)",
+                "Cleaned up {} boxes during recovery (auto_remove, warm pool or orphaned)",
                 boxes_to_remove.len()
             );
         }
@@ -903,10 +2165,15 @@ impl RuntimeImpl {
             if pid_file.exists() {
                 match crate::util::read_pid_file(&pid_file) {
                     Ok(pid) => {
-                        if is_process_alive(pid) && is_same_process(pid, box_id.as_str()) {
-                            // Process is alive and it's our boxlite-shim - box stays Running
+                        if is_process_alive(pid)
+                            && is_same_process(pid, box_id.as_str(), state.pid_start_time)
+                        {
+                            // Process is alive and it's our boxlite-shim - box stays
+                            // Running (or Detached, until a handle reattaches)
                             state.set_pid(Some(pid));
-                            state.set_status(BoxStatus::Running);
+                            if state.status != BoxStatus::Detached {
+                                state.set_status(BoxStatus::Running);
+                            }
                             tracing::info!(
                                 box_id = %box_id,
                                 pid = pid,
@@ -937,7 +2204,7 @@ impl RuntimeImpl {
             } else {
                 // No PID file - box was stopped gracefully or never started
                 // Note: Configured boxes won't have a PID file (this is expected)
-                if state.status == BoxStatus::Running {
+                if state.status.is_active() {
                     state.set_status(BoxStatus::Stopped);
                     tracing::warn!(
                         box_id = %box_id,
@@ -949,11 +2216,81 @@ impl RuntimeImpl {
             // Save updated state to database if changed
             if state.status != original_status {
                 self.box_manager.save_box(box_id, &state)?;
+                if original_status.is_active() {
+                    dead_boxes.push(box_id.clone());
+                }
             }
         }
 
         tracing::info!("Box recovery complete");
-        Ok(())
+        Ok(dead_boxes)
+    }
+
+    /// Apply restart policies to boxes found dead during recovery.
+    ///
+    /// Restarts run in the background on the current Tokio runtime; without
+    /// one they are skipped and the boxes stay stopped.
+    fn schedule_restarts(self: &Arc<Self>, dead_boxes: Vec<BoxID>) {
+        let mut candidates = Vec::new();
+        for box_id in dead_boxes {
+            // auto_remove boxes were already dropped by recovery
+            if let Ok(Some((config, state))) = self.box_manager.box_by_id(&box_id)
+                && config
+                    .options
+                    .restart_policy
+                    .should_restart(state.restart_count)
+            {
+                candidates.push(box_id);
+            }
+        }
+        if candidates.is_empty() {
+            return;
+        }
+
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            tracing::warn!(
+                count = candidates.len(),
+                "No Tokio runtime available, skipping restart policies"
+            );
+            return;
+        };
+
+        let this = Arc::clone(self);
+        handle.spawn(async move {
+            for box_id in candidates {
+                match this.restart_box(&box_id).await {
+                    Ok(status) => {
+                        tracing::info!(box_id = %box_id, status = %status, "Applied restart policy")
+                    }
+                    Err(e) => {
+                        tracing::warn!(box_id = %box_id, error = %e, "Failed to apply restart policy")
+                    }
+                }
+            }
+        });
+    }
+
+    /// Restart a dead box according to its restart policy.
+    ///
+    /// Persists the updated restart count and returns the final status.
+    async fn restart_box(self: &Arc<Self>, box_id: &BoxID) -> BoxliteResult<BoxStatus> {
+        let (config, state) = self
+            .box_manager
+            .box_by_id(box_id)?
+            .ok_or_else(|| BoxliteError::NotFound(box_id.to_string()))?;
+        let policy = config.options.restart_policy;
+        let mut restart_count = state.restart_count;
+
+        let (box_impl, _) = self.get_or_create_box_impl(config, state)?;
+        crate::runtime::restart::restart_with_policy(policy, &mut restart_count, || {
+            box_impl.start()
+        })
+        .await;
+
+        let mut state = box_impl.state.write();
+        state.restart_count = restart_count;
+        self.box_manager.save_box(box_id, &state)?;
+        Ok(state.status)
     }
 
     /// Scan filesystem for orphaned box directories and remove them.
@@ -1048,13 +2385,13 @@ impl RuntimeImpl {
         self: &Arc<Self>,
         config: BoxConfig,
         state: BoxState,
-    ) -> (SharedBoxImpl, bool) {
+    ) -> BoxliteResult<(SharedBoxImpl, bool)> {
         use crate::litebox::box_impl::BoxImpl;
 
         let box_id = config.id.clone();
         let box_name = config.name.clone();
 
-        let mut sync = self.sync_state.write().unwrap();
+        let mut sync = self.acquire_write();
 
         // Check by name first (if provided) - prevents duplicate names
         if let Some(ref name) = box_name
@@ -1062,7 +2399,7 @@ impl RuntimeImpl {
         {
             if let Some(strong) = weak.upgrade() {
                 tracing::trace!(name = %name, "Reusing cached BoxImpl by name");
-                return (strong, false);
+                return Ok((strong, false));
             }
             // Dead weak ref, clean it up
             sync.active_boxes_by_name.remove(name);
@@ -1072,7 +2409,7 @@ impl RuntimeImpl {
         if let Some(weak) = sync.active_boxes_by_id.get(&box_id) {
             if let Some(strong) = weak.upgrade() {
                 tracing::trace!(box_id = %box_id, "Reusing cached BoxImpl by ID");
-                return (strong, false);
+                return Ok((strong, false));
             }
             // Dead weak ref, clean it up
             sync.active_boxes_by_id.remove(&box_id);
@@ -1092,7 +2429,7 @@ impl RuntimeImpl {
             tracing::trace!(box_id = %box_id, "Created and cached new BoxImpl (unnamed)");
         }
 
-        (box_impl, true)
+        Ok((box_impl, true))
     }
 
     /// Remove BoxImpl from cache.
@@ -1100,7 +2437,7 @@ impl RuntimeImpl {
     /// Called when box is stopped or removed. Existing handles become stale;
     /// new handles from runtime.get() will get a fresh BoxImpl.
     pub(crate) fn invalidate_box_impl(&self, box_id: &BoxID, box_name: Option<&str>) {
-        let mut sync = self.sync_state.write().unwrap();
+        let mut sync = self.acquire_write();
         sync.active_boxes_by_id.remove(box_id);
         if let Some(name) = box_name {
             sync.active_boxes_by_name.remove(name);
@@ -1112,16 +2449,67 @@ impl RuntimeImpl {
     ///
     /// Use this when you need atomicity across multiple operations on
     /// box_manager or image_manager.
-    #[allow(unused)]
-    pub(crate) fn acquire_write(
-        &self,
-    ) -> BoxliteResult<std::sync::RwLockWriteGuard<'_, SynchronizedState>> {
-        self.sync_state
-            .write()
-            .map_err(|e| BoxliteError::Internal(format!("Coordination lock poisoned: {}", e)))
+    ///
+    /// A poisoned lock is recovered and its poison cleared: the caches only
+    /// hold weak handles and every update is a single insert or remove, so a
+    /// panicking holder can't leave them inconsistent.
+    pub(crate) fn acquire_write(&self) -> RwLockWriteGuard<'_, SynchronizedState> {
+        self.sync_state.write().unwrap_or_else(|e| {
+            tracing::warn!("Coordination lock poisoned; recovering");
+            self.sync_state.clear_poison();
+            e.into_inner()
+        })
+    }
+
+    /// Acquire coordination lock for reading the box caches; see `acquire_write`.
+    pub(crate) fn acquire_read(&self) -> RwLockReadGuard<'_, SynchronizedState> {
+        self.sync_state.read().unwrap_or_else(|e| {
+            tracing::warn!("Coordination lock poisoned; recovering");
+            self.sync_state.clear_poison();
+            e.into_inner()
+        })
+    }
+}
+
+/// Remove leftovers from `dir` according to `policy`.
+///
+/// Best effort: entries that can't be inspected or removed are skipped.
+fn clean_temp_dir(dir: &Path, policy: CleanTempOnStart) {
+    let older_than = match policy {
+        CleanTempOnStart::Never => return,
+        CleanTempOnStart::Always => None,
+        CleanTempOnStart::IfEmpty { older_than } => Some(older_than),
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        if let Some(older_than) = older_than {
+            // A directory's own mtime stays put while files inside it are written
+            let age = newest_mtime(&entry.path()).and_then(|modified| modified.elapsed().ok());
+            if age.is_none_or(|age| age < older_than) {
+                continue;
+            }
+        }
+
+        let path = entry.path();
+        if path.is_dir() {
+            let _ = std::fs::remove_dir_all(&path);
+        } else {
+            let _ = std::fs::remove_file(&path);
+        }
     }
 }
 
+/// Latest modification time of `path` and, for a directory, of anything in it.
+fn newest_mtime(path: &Path) -> Option<std::time::SystemTime> {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok()?.metadata().ok()?.modified().ok())
+        .max()
+}
+
 impl std::fmt::Debug for RuntimeImpl {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RuntimeInner")
@@ -1129,3 +2517,578 @@ impl std::fmt::Debug for RuntimeImpl {
             .finish()
     }
 }
+
+impl Drop for RuntimeImpl {
+    fn drop(&mut self) {
+        self.background_token.cancel();
+        if self.options.kill_boxes_on_drop {
+            self.kill_session_boxes();
+        }
+    }
+}
+
+/// `path` with symlinks resolved as far as it exists, so that paths naming
+/// the same file compare equal.
+fn resolve_path(path: &Path) -> std::path::PathBuf {
+    if let Ok(resolved) = std::fs::canonicalize(path) {
+        return resolved;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) if !parent.as_os_str().is_empty() => {
+            resolve_path(parent).join(name)
+        }
+        _ => std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runtime with a box whose cached state claims it is running as `pid`.
+    async fn runtime_with_running_box(
+        home: &std::path::Path,
+        pid: u32,
+    ) -> (SharedRuntimeImpl, LiteBox, CancellationToken) {
+        let runtime = RuntimeImpl::new(BoxliteOptions {
+            home_dir: home.to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap();
+        let litebox = runtime.create(BoxOptions::default(), None).await.unwrap();
+
+        let box_impl = runtime
+            .active_boxes()
+            .into_iter()
+            .find(|b| b.id() == litebox.id())
+            .unwrap();
+        {
+            let mut state = box_impl.state.write();
+            state.set_status(BoxStatus::Running);
+            state.set_pid(Some(pid));
+            runtime.box_manager.save_box(litebox.id(), &state).unwrap();
+        }
+        let token = box_impl.shutdown_token.clone();
+        (runtime, litebox, token)
+    }
+
+    fn exited_pid() -> u32 {
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let pid = child.id();
+        child.wait().unwrap();
+        pid
+    }
+
+    #[tokio::test]
+    async fn test_exit_watcher_removes_crashed_auto_remove_box() {
+        let home = tempfile::tempdir().unwrap();
+        let pid = exited_pid();
+        let (runtime, litebox, token) = runtime_with_running_box(home.path(), pid).await;
+        let box_id = litebox.id().clone();
+        assert!(litebox.info().status.is_running());
+
+        runtime.spawn_exit_watcher(box_id.clone(), pid, token);
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while runtime.box_manager.box_by_id(&box_id).unwrap().is_some() {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "crashed box was not removed"
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        assert!(litebox.info().status.is_stopped());
+        assert!(runtime.get(box_id.as_str()).await.unwrap().is_none());
+
+        // stop() after the watcher handled the exit is a no-op
+        litebox.stop().await.unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_drop_kills_session_boxes() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let home = tempfile::tempdir().unwrap();
+        let runtime = RuntimeImpl::new(BoxliteOptions {
+            home_dir: home.path().to_path_buf(),
+            kill_boxes_on_drop: true,
+            ..Default::default()
+        })
+        .unwrap();
+        let litebox = runtime.create(BoxOptions::default(), None).await.unwrap();
+
+        // Stand-in shim: its cmdline names boxlite-shim and the box ID
+        let mut shim = std::process::Command::new("sh")
+            .args(["-c", "while :; do sleep 1; done", "boxlite-shim"])
+            .arg(litebox.id().as_str())
+            .spawn()
+            .unwrap();
+        // /proc/<pid>/cmdline is briefly empty right after spawn
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while !crate::util::is_same_process(shim.id(), litebox.id().as_str(), None) {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "stand-in shim not visible"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        runtime.track_session_box(litebox.id(), shim.id());
+
+        drop(litebox);
+        drop(runtime);
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let status = loop {
+            if let Some(status) = shim.try_wait().unwrap() {
+                break status;
+            }
+            if std::time::Instant::now() >= deadline {
+                let _ = shim.kill();
+                panic!("session box survived runtime drop");
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        };
+        assert_eq!(status.signal(), Some(libc::SIGKILL));
+    }
+
+    #[tokio::test]
+    async fn test_get_or_create_claims_warm_box_and_refills() {
+        use crate::runtime::options::WarmPoolOptions;
+
+        let home = tempfile::tempdir().unwrap();
+        let runtime = RuntimeImpl::new(BoxliteOptions {
+            home_dir: home.path().to_path_buf(),
+            warm_pool: Some(WarmPoolOptions {
+                size: 1,
+                template: BoxOptions::default(),
+            }),
+            ..Default::default()
+        })
+        .unwrap();
+
+        // Stand-in for a booted pool box: unit tests can't start VMs
+        let (mut config, state) = runtime.prepare_box(BoxOptions::default(), None).unwrap();
+        config.warm_pool = true;
+        let warm = runtime.register_box(config, state).unwrap();
+        let warm_id = warm.id().clone();
+        {
+            let box_impl = runtime
+                .active_boxes()
+                .into_iter()
+                .find(|b| b.id() == &warm_id)
+                .unwrap();
+            let mut state = box_impl.state.write();
+            state.set_status(BoxStatus::Running);
+            state.set_pid(Some(exited_pid()));
+            runtime.box_manager.save_box(&warm_id, &state).unwrap();
+        }
+        drop(warm);
+        let pool = runtime.warm_pool.as_ref().unwrap();
+        pool.push(warm_id.clone());
+
+        // Keep the refill task from booting a real box
+        runtime.background_token.cancel();
+
+        let (litebox, created) = runtime
+            .get_or_create(BoxOptions::default(), "claimed")
+            .await
+            .unwrap();
+        assert!(created);
+        assert_eq!(litebox.id(), &warm_id);
+        assert_eq!(litebox.name(), Some("claimed"));
+        assert_eq!(pool.deficit(), 1);
+        assert_eq!(pool.refills_started(), 1);
+
+        let (config, _) = runtime.box_manager.box_by_id(&warm_id).unwrap().unwrap();
+        assert!(!config.warm_pool);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_removes_unclaimed_warm_boxes() {
+        let home = tempfile::tempdir().unwrap();
+        let options = BoxliteOptions {
+            home_dir: home.path().to_path_buf(),
+            ..Default::default()
+        };
+
+        // Not auto_remove, which recovery drops regardless
+        let box_options = BoxOptions {
+            auto_remove: false,
+            ..Default::default()
+        };
+
+        let runtime = RuntimeImpl::new(options.clone()).unwrap();
+        let (mut config, state) = runtime.prepare_box(box_options.clone(), None).unwrap();
+        config.warm_pool = true;
+        let warm_id = runtime.register_box(config, state).unwrap().id().clone();
+        let kept_id = runtime
+            .create(box_options, Some("kept".into()))
+            .await
+            .unwrap()
+            .id()
+            .clone();
+        drop(runtime);
+
+        let runtime = RuntimeImpl::new(options).unwrap();
+        assert!(runtime.box_manager.box_by_id(&warm_id).unwrap().is_none());
+        assert!(runtime.box_manager.box_by_id(&kept_id).unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_overlay_base_cannot_be_started_or_removed() {
+        use crate::runtime::options::DiskConfig;
+
+        let home = tempfile::tempdir().unwrap();
+        let runtime = RuntimeImpl::new(BoxliteOptions {
+            home_dir: home.path().to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let base_box = runtime.create(BoxOptions::default(), None).await.unwrap();
+        let (base_config, _) = runtime
+            .box_manager
+            .box_by_id(base_box.id())
+            .unwrap()
+            .unwrap();
+        let overlay = runtime
+            .create(
+                BoxOptions {
+                    disk: DiskConfig {
+                        backing_file: Some(base_config.box_home.join("disk.qcow2")),
+                    },
+                    ..Default::default()
+                },
+                Some("overlay".to_string()),
+            )
+            .await
+            .unwrap();
+
+        let err = runtime.remove_box(base_box.id(), false).await.unwrap_err();
+        assert!(matches!(err, BoxliteError::InvalidState(_)), "{err}");
+        assert!(runtime.box_manager.has_box(base_box.id()).unwrap());
+
+        // Booting the base would change the data under the overlay
+        let err = base_box.start().await.unwrap_err();
+        assert!(matches!(err, BoxliteError::InvalidState(_)), "{err}");
+        assert!(err.to_string().contains("cannot start"), "{err}");
+
+        runtime.remove_box(overlay.id(), false).await.unwrap();
+        runtime.remove_box(base_box.id(), false).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_remove_image_in_use_requires_force() {
+        let home = tempfile::tempdir().unwrap();
+        let runtime = RuntimeImpl::new(BoxliteOptions {
+            home_dir: home.path().to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        // Fake a cached alpine:latest: an index entry and its one layer
+        let layer_path = runtime
+            .layout
+            .image_layers_dir()
+            .join("sha256-alpine-layer.tar.gz");
+        std::fs::write(&layer_path, b"layer").unwrap();
+        let db = Database::open(&runtime.layout.db_dir().join("boxlite.db")).unwrap();
+        crate::db::ImageIndexStore::new(db)
+            .upsert(
+                "docker.io/library/alpine:latest",
+                &crate::db::CachedImage {
+                    manifest_digest: "sha256:alpine-manifest".to_string(),
+                    config_digest: "sha256:alpine-config".to_string(),
+                    layers: vec!["sha256:alpine-layer".to_string()],
+                    cached_at: Utc::now().to_rfc3339(),
+                    complete: true,
+                },
+            )
+            .unwrap();
+
+        // BoxOptions::default() uses alpine:latest
+        runtime
+            .create(BoxOptions::default(), Some("user".to_string()))
+            .await
+            .unwrap();
+
+        let err = runtime
+            .remove_image("alpine:latest", false)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BoxliteError::InvalidState(_)), "{err}");
+        assert!(err.to_string().contains("user"), "{err}");
+        assert!(layer_path.exists());
+
+        // The boxes still boot from the image's cached rootfs disk
+        let disk_path = runtime
+            .layout
+            .image_layout()
+            .disk_images_dir()
+            .join(format!(
+                "{}.ext4",
+                crate::images::disk_image_digest(["sha256:alpine-layer"]).replace(':', "-")
+            ));
+        std::fs::create_dir_all(disk_path.parent().unwrap()).unwrap();
+        std::fs::write(&disk_path, b"rootfs").unwrap();
+
+        runtime.remove_image("alpine:latest", true).await.unwrap();
+        assert!(!layer_path.exists());
+        assert!(disk_path.exists());
+        assert!(runtime.image_manager.list().await.unwrap().is_empty());
+        assert!(matches!(
+            runtime.remove_image("alpine:latest", true).await,
+            Err(BoxliteError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_wait_returns_when_shim_exits() {
+        let home = tempfile::tempdir().unwrap();
+        let (_runtime, litebox, _token) = runtime_with_running_box(home.path(), exited_pid()).await;
+
+        // No live state: wait() watches the recorded shim PID itself
+        let status = tokio::time::timeout(std::time::Duration::from_secs(5), litebox.wait())
+            .await
+            .expect("wait did not observe the shim exit")
+            .unwrap();
+        assert_eq!(status, BoxStatus::Stopped);
+        assert_eq!(litebox.wait().await.unwrap(), BoxStatus::Stopped);
+
+        // A shim that is still alive (this process) keeps the box running
+        let home = tempfile::tempdir().unwrap();
+        let (_runtime, litebox, _token) =
+            runtime_with_running_box(home.path(), std::process::id()).await;
+        let waited = litebox
+            .wait_timeout(std::time::Duration::from_millis(200))
+            .await
+            .unwrap();
+        assert_eq!(waited, None);
+    }
+
+    #[tokio::test]
+    async fn test_detach_then_get_returns_detached_handle() {
+        let home = tempfile::tempdir().unwrap();
+        let (runtime, litebox, token) =
+            runtime_with_running_box(home.path(), std::process::id()).await;
+
+        litebox.detach().unwrap();
+        assert!(token.is_cancelled());
+        assert!(matches!(
+            litebox.detach().unwrap_err(),
+            BoxliteError::Stopped(_)
+        ));
+
+        // The detached handle was dropped from the cache; get() builds a new one
+        let reattached = runtime.get(litebox.id().as_str()).await.unwrap().unwrap();
+        let status = reattached.info().status;
+        assert_eq!(status, BoxStatus::Detached);
+        assert!(status.is_active());
+        assert!(status.can_start());
+        let waited = reattached
+            .wait_timeout(std::time::Duration::from_millis(200))
+            .await
+            .unwrap();
+        assert_eq!(waited, None);
+    }
+
+    #[tokio::test]
+    async fn test_sync_time_requires_running_box() {
+        let home = tempfile::tempdir().unwrap();
+        let runtime = RuntimeImpl::new(BoxliteOptions {
+            home_dir: home.path().to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap();
+        let litebox = runtime.create(BoxOptions::default(), None).await.unwrap();
+
+        let err = litebox.sync_time().await.unwrap_err();
+        assert!(matches!(err, BoxliteError::InvalidState(_)), "{err}");
+    }
+
+    #[test]
+    fn test_clean_temp_on_start_policy() {
+        let home = tempfile::tempdir().unwrap();
+        let temp = home.path().join("tmp");
+        std::fs::create_dir_all(temp.join("pull-in-progress")).unwrap();
+        std::fs::write(temp.join("stale.raw"), b"old").unwrap();
+        std::fs::create_dir_all(temp.join("stale-build")).unwrap();
+        std::fs::write(temp.join("stale-build/layer.tar"), b"old").unwrap();
+        let long_ago = filetime::FileTime::from_unix_time(0, 0);
+        filetime::set_file_mtime(temp.join("stale.raw"), long_ago).unwrap();
+        filetime::set_file_mtime(temp.join("stale-build/layer.tar"), long_ago).unwrap();
+        filetime::set_file_mtime(temp.join("stale-build"), long_ago).unwrap();
+        // An old directory still being written to is in use
+        std::fs::write(temp.join("pull-in-progress/blob.part"), b"new").unwrap();
+        filetime::set_file_mtime(temp.join("pull-in-progress"), long_ago).unwrap();
+
+        let open = |policy| {
+            RuntimeImpl::new(BoxliteOptions {
+                home_dir: home.path().to_path_buf(),
+                clean_temp_on_start: policy,
+                ..Default::default()
+            })
+            .unwrap()
+        };
+
+        drop(open(CleanTempOnStart::Never));
+        assert!(temp.join("pull-in-progress").exists());
+        assert!(temp.join("stale.raw").exists());
+
+        drop(open(CleanTempOnStart::IfEmpty {
+            older_than: std::time::Duration::from_secs(3600),
+        }));
+        assert!(temp.join("pull-in-progress").exists());
+        assert!(!temp.join("stale.raw").exists());
+        assert!(!temp.join("stale-build").exists());
+
+        drop(open(CleanTempOnStart::Always));
+        assert!(!temp.join("pull-in-progress").exists());
+    }
+
+    #[tokio::test]
+    async fn test_poisoned_coordination_lock_recovers() {
+        let home = tempfile::tempdir().unwrap();
+        let runtime = RuntimeImpl::new(BoxliteOptions {
+            home_dir: home.path().to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap();
+        let litebox = runtime.create(BoxOptions::default(), None).await.unwrap();
+
+        std::thread::scope(|s| {
+            let panicked = s
+                .spawn(|| {
+                    let _guard = runtime.sync_state.write().unwrap();
+                    panic!("writer panics while holding the lock");
+                })
+                .join();
+            assert!(panicked.is_err());
+        });
+        assert!(runtime.sync_state.is_poisoned());
+
+        let found = runtime.get(litebox.id().as_str()).await.unwrap();
+        assert_eq!(found.map(|b| b.id().clone()), Some(litebox.id().clone()));
+        assert!(!runtime.sync_state.is_poisoned());
+        assert_eq!(runtime.list_info().await.unwrap().len(), 1);
+        assert_eq!(runtime.active_boxes().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rename_keeps_cached_box() {
+        let home = tempfile::tempdir().unwrap();
+        let runtime = RuntimeImpl::new(BoxliteOptions {
+            home_dir: home.path().to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap();
+        let litebox = runtime
+            .create(BoxOptions::default(), Some("old".into()))
+            .await
+            .unwrap();
+
+        let before = runtime.active_boxes().pop().unwrap();
+
+        runtime.rename("old", "new").unwrap();
+
+        assert_eq!(litebox.info().name.as_deref(), Some("new"));
+        assert!(runtime.get("old").await.unwrap().is_none());
+        let renamed = runtime.get("new").await.unwrap().unwrap();
+        assert_eq!(renamed.id(), litebox.id());
+        assert_eq!(renamed.name(), Some("new"));
+
+        // Renaming to the current name is a no-op
+        runtime.rename("new", "new").unwrap();
+        assert_eq!(litebox.info().name.as_deref(), Some("new"));
+
+        // Same BoxImpl, so handles still share one VM
+        let cached = runtime.active_boxes();
+        assert_eq!(cached.len(), 1);
+        assert!(Arc::ptr_eq(&cached[0], &before));
+    }
+
+    #[tokio::test]
+    async fn test_bundle_round_trip() {
+        let home = tempfile::tempdir().unwrap();
+        let runtime = RuntimeImpl::new(BoxliteOptions {
+            home_dir: home.path().to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap();
+        let options = BoxOptions {
+            cpus: Some(2),
+            memory_mib: Some(768),
+            rootfs: RootfsSpec::Image("alpine:3.19".into()),
+            ..Default::default()
+        };
+        let source = runtime
+            .create(options, Some("source".into()))
+            .await
+            .unwrap();
+
+        // Stand-in for the disk the box wrote while it ran
+        let disk_path = runtime
+            .layout
+            .box_layout(source.id().as_str(), false)
+            .unwrap()
+            .disk_path();
+        std::fs::create_dir_all(disk_path.parent().unwrap()).unwrap();
+        let disk: Vec<u8> = (0..1024 * 1024).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&disk_path, &disk).unwrap();
+
+        let bundle = home.path().join("source.tar.gz");
+        source.export_bundle(&bundle).await.unwrap();
+
+        let imported = runtime
+            .import_bundle(&bundle, Some("copy".into()))
+            .await
+            .unwrap();
+        assert_ne!(imported.id(), source.id());
+        assert_eq!(imported.name(), Some("copy"));
+        assert_eq!(imported.info().status, BoxStatus::Configured);
+        let options = &imported.config().options;
+        assert_eq!(options.cpus, Some(2));
+        assert_eq!(options.memory_mib, Some(768));
+        assert!(matches!(&options.rootfs, RootfsSpec::Image(r) if r == "alpine:3.19"));
+
+        let base = runtime
+            .layout
+            .box_layout(imported.id().as_str(), false)
+            .unwrap()
+            .imported_base_path();
+        assert_eq!(options.disk.backing_file.as_deref(), Some(base.as_path()));
+        assert_eq!(std::fs::read(&base).unwrap(), disk);
+    }
+
+    #[tokio::test]
+    async fn test_export_bundle_requires_stopped_box() {
+        let home = tempfile::tempdir().unwrap();
+        let (_runtime, litebox, _token) = runtime_with_running_box(home.path(), exited_pid()).await;
+
+        let bundle = home.path().join("running.tar.gz");
+        let err = litebox.export_bundle(&bundle).await.unwrap_err();
+        assert!(matches!(err, BoxliteError::InvalidState(_)), "{err}");
+        assert!(!bundle.exists());
+    }
+
+    #[tokio::test]
+    async fn test_unexpected_exit_ignored_after_stop() {
+        let home = tempfile::tempdir().unwrap();
+        let (runtime, litebox, token) = runtime_with_running_box(home.path(), exited_pid()).await;
+
+        // stop() cancels the token first and owns the teardown
+        token.cancel();
+        runtime
+            .handle_unexpected_exit(litebox.id(), &token)
+            .await
+            .unwrap();
+
+        let (_, state) = runtime
+            .box_manager
+            .box_by_id(litebox.id())
+            .unwrap()
+            .unwrap();
+        assert_eq!(state.status, BoxStatus::Running);
+    }
+}
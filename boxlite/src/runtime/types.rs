@@ -10,8 +10,10 @@ use std::collections::HashMap;
 use std::fmt;
 use std::hash::Hash;
 
+use crate::runtime::options::PortForward;
+
 // Re-export status types from litebox module
-pub use crate::litebox::{BoxState, BoxStatus};
+pub use crate::litebox::{BoxState, BoxStatus, HealthStatus, StartupTimings};
 
 // ============================================================================
 // RESOURCE LIMIT TYPES (C-NEWTYPE: Semantic newtypes for distinct concepts)
@@ -417,6 +419,21 @@ pub struct BoxInfo {
 
     /// User-defined labels for filtering and organization.
     pub labels: HashMap<String, String>,
+
+    /// Active host-to-guest port forwards (empty unless running).
+    pub port_forwards: Vec<PortForward>,
+
+    /// ID of the container running inside the box.
+    pub container_id: ContainerID,
+
+    /// Result of the last `LiteBox::health` probe on this handle (None if
+    /// never probed).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health: Option<HealthStatus>,
+
+    /// Per-stage timings of the last successful start (None if never started).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub startup_timings: Option<StartupTimings>,
 }
 
 impl BoxInfo {
@@ -434,12 +451,31 @@ impl BoxInfo {
             image: match &config.options.rootfs {
                 RootfsSpec::Image(r) => r.clone(),
                 RootfsSpec::RootfsPath(p) => format!("rootfs:{}", p),
+                RootfsSpec::Disk(p) => format!("disk:{}", p.display()),
+                RootfsSpec::Directory(p) => format!("dir:{}", p.display()),
             },
             cpus: config.options.cpus.unwrap_or(2),
             memory_mib: config.options.memory_mib.unwrap_or(512),
-            labels: HashMap::new(),
+            labels: config.options.labels.clone(),
+            port_forwards: if state.status.is_running() {
+                state.port_forwards.clone()
+            } else {
+                Vec::new()
+            },
+            container_id: config.container.id.clone(),
+            health: None,
+            startup_timings: state.startup_timings.clone(),
         }
     }
+
+    /// Whether this box carries every `(key, value)` label in `selector`.
+    ///
+    /// An empty selector matches every box.
+    pub fn matches_labels(&self, selector: &[(String, String)]) -> bool {
+        selector
+            .iter()
+            .all(|(key, value)| self.labels.get(key) == Some(value))
+    }
 }
 
 impl PartialEq for BoxInfo {
@@ -452,6 +488,8 @@ impl PartialEq for BoxInfo {
             && self.cpus == other.cpus
             && self.memory_mib == other.memory_mib
             && self.labels == other.labels
+            && self.port_forwards == other.port_forwards
+            && self.container_id == other.container_id
     }
 }
 
@@ -496,6 +534,10 @@ impl BoxStateInfo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImageInfo {
     /// Full image reference (e.g., "docker.io/library/alpine:latest")
+    ///
+    /// Images pulled for a platform other than the host's carry a
+    /// `|<platform>` suffix, so the reference stays unique and can be passed
+    /// back to `remove_image`.
     pub reference: String,
 
     /// Parsed repository name (e.g. "docker.io/library/alpine")
@@ -504,6 +546,11 @@ pub struct ImageInfo {
     /// Parsed image tag (e.g. "latest")
     pub tag: String,
 
+    /// Platform the image was pulled for (e.g. "linux/arm64"), or `None` for
+    /// the host platform.
+    #[serde(default)]
+    pub platform: Option<String>,
+
     /// Image ID (Manifest Digest)
     pub id: String,
 
@@ -512,7 +559,30 @@ pub struct ImageInfo {
     pub cached_at: DateTime<Utc>,
 
     /// Image size in bytes (if available)
+    ///
+    /// This is the space its layer tarballs and config take in the local
+    /// store; layers shared with other images count towards each of them.
     pub size: Option<Bytes>,
+
+    /// Number of layers in the image
+    #[serde(default)]
+    pub layer_count: usize,
+}
+
+/// Everything known about a cached image, as returned by `inspect_image`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageDetails {
+    #[serde(flatten)]
+    pub info: ImageInfo,
+
+    /// Layer digests, base layer first
+    pub layers: Vec<String>,
+
+    /// Config blob digest
+    pub config_digest: String,
+
+    /// OCI image config (entrypoint, env, history, ...)
+    pub config: oci_spec::image::ImageConfiguration,
 }
 
 // ============================================================================
@@ -525,7 +595,7 @@ pub struct ImageInfo {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::litebox::config::{BoxConfig, ContainerRuntimeConfig};
+    use crate::litebox::config::{BOX_CONFIG_VERSION, BoxConfig, ContainerRuntimeConfig};
     use crate::runtime::options::{BoxOptions, RootfsSpec};
     use boxlite_shared::Transport;
     use std::path::PathBuf;
@@ -589,8 +659,10 @@ mod tests {
         let now = Utc::now();
         let box_id = BoxID::parse("01HJK4TNRPQSXYZ8WM6NCVT9R5").unwrap();
         let config = BoxConfig {
+            config_version: BOX_CONFIG_VERSION,
             id: box_id,
             name: None,
+            warm_pool: false,
             created_at: now,
             container: ContainerRuntimeConfig {
                 id: ContainerID::new(),
@@ -610,6 +682,7 @@ mod tests {
         let mut state = BoxState::new();
         state.set_pid(Some(12345));
         let _ = state.transition_to(BoxStatus::Running);
+        state.port_forwards = vec![PortForward::tcp(8080, 80)];
 
         let info = BoxInfo::new(&config, &state);
 
@@ -620,6 +693,11 @@ mod tests {
         assert_eq!(info.image, "python:3.11");
         assert_eq!(info.cpus, 4);
         assert_eq!(info.memory_mib, 1024);
+        assert_eq!(info.port_forwards, vec![PortForward::tcp(8080, 80)]);
+
+        // Stopped boxes no longer hold their host ports
+        state.mark_stop();
+        assert!(BoxInfo::new(&config, &state).port_forwards.is_empty());
     }
 
     #[test]
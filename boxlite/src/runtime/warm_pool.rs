@@ -0,0 +1,99 @@
+//! Pool of pre-started boxes that serve matching `get_or_create` calls.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use crate::runtime::options::{BoxOptions, WarmPoolOptions};
+use crate::runtime::types::BoxID;
+use boxlite_shared::{BoxliteError, BoxliteResult};
+
+/// Ready boxes created from a single template.
+///
+/// Only bookkeeping lives here; creating, starting and claiming boxes is
+/// done by `RuntimeImpl`, which owns the pool.
+pub(crate) struct WarmPool {
+    size: usize,
+    /// Template options, normalized like options passed to `create()`.
+    template: BoxOptions,
+    /// Started, unnamed boxes waiting to be claimed (oldest first).
+    ///
+    /// Held by ID: a `LiteBox` would keep the runtime alive through its
+    /// back-reference.
+    ready: Mutex<VecDeque<BoxID>>,
+    /// Set while a refill task is running, so claims don't stack refills.
+    refilling: AtomicBool,
+    /// Number of refill tasks started.
+    refills_started: AtomicU64,
+}
+
+impl WarmPool {
+    pub(crate) fn new(options: &WarmPoolOptions) -> BoxliteResult<Self> {
+        let mut template = options.template.clone();
+        template.normalize_volume_paths()?;
+        Ok(Self {
+            size: options.size,
+            template,
+            ready: Mutex::new(VecDeque::new()),
+            refilling: AtomicBool::new(false),
+            refills_started: AtomicU64::new(0),
+        })
+    }
+
+    pub(crate) fn template(&self) -> &BoxOptions {
+        &self.template
+    }
+
+    /// Whether a request with `options` may be served by a pooled box.
+    ///
+    /// `options` must already be normalized.
+    pub(crate) fn matches(&self, options: &BoxOptions) -> BoxliteResult<bool> {
+        let to_value = |options: &BoxOptions| {
+            serde_json::to_value(options).map_err(|e| {
+                BoxliteError::Internal(format!("Failed to serialize box options: {}", e))
+            })
+        };
+        Ok(to_value(&self.template)? == to_value(options)?)
+    }
+
+    /// Take the oldest ready box, if any.
+    pub(crate) fn take(&self) -> Option<BoxID> {
+        self.ready.lock().unwrap().pop_front()
+    }
+
+    /// Add a started box to the pool.
+    pub(crate) fn push(&self, box_id: BoxID) {
+        self.ready.lock().unwrap().push_back(box_id);
+    }
+
+    /// Remove and return every ready box.
+    pub(crate) fn drain(&self) -> Vec<BoxID> {
+        self.ready.lock().unwrap().drain(..).collect()
+    }
+
+    /// Number of boxes missing to reach the configured size.
+    pub(crate) fn deficit(&self) -> usize {
+        self.size.saturating_sub(self.ready.lock().unwrap().len())
+    }
+
+    /// Claim the right to run a refill. Returns false if one is running.
+    pub(crate) fn begin_refill(&self) -> bool {
+        let claimed = self
+            .refilling
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok();
+        if claimed {
+            self.refills_started.fetch_add(1, Ordering::Relaxed);
+        }
+        claimed
+    }
+
+    pub(crate) fn end_refill(&self) {
+        self.refilling.store(false, Ordering::Release);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn refills_started(&self) -> u64 {
+        self.refills_started.load(Ordering::Relaxed)
+    }
+}
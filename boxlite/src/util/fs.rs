@@ -0,0 +1,55 @@
+//! File moves that work across filesystems.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Move the file `src` to `dst`, copying if they are on different filesystems.
+///
+/// Tries `rename` first. When that fails with `EXDEV` (e.g. the temp and
+/// images directories were placed on different volumes), the file is copied
+/// to a temporary sibling of `dst`, renamed into place so `dst` is never
+/// half-written, and `src` is removed.
+pub fn move_file(src: &Path, dst: &Path) -> io::Result<()> {
+    match std::fs::rename(src, dst) {
+        Err(e) if e.raw_os_error() == Some(libc::EXDEV) => {}
+        result => return result,
+    }
+
+    let mut tmp = dst.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+
+    let copied = std::fs::copy(src, &tmp).and_then(|_| std::fs::rename(&tmp, dst));
+    if let Err(e) = copied {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e);
+    }
+    std::fs::remove_file(src)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_file_across_filesystems() {
+        let src_dir = tempfile::tempdir().unwrap();
+        let src = src_dir.path().join("layer.tar");
+        std::fs::write(&src, b"layer bytes").unwrap();
+
+        // tmpfs is a different filesystem from the temp dir on most hosts;
+        // elsewhere this covers the plain rename
+        let dst_dir = match tempfile::tempdir_in("/dev/shm") {
+            Ok(dir) => dir,
+            Err(_) => tempfile::tempdir().unwrap(),
+        };
+        let dst = dst_dir.path().join("layer.tar");
+
+        move_file(&src, &dst).unwrap();
+        assert!(!src.exists());
+        assert_eq!(std::fs::read(&dst).unwrap(), b"layer bytes");
+        assert_eq!(std::fs::read_dir(dst_dir.path()).unwrap().count(), 1);
+
+        assert!(move_file(&src, &dst).is_err());
+    }
+}
@@ -0,0 +1,25 @@
+//! Content hashing helpers.
+
+use std::io::Read;
+use std::path::Path;
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use sha2::{Digest, Sha256};
+
+/// Hex SHA-256 of a file's contents.
+pub fn sha256_file(path: &Path) -> BoxliteResult<String> {
+    let hash_err = |e: std::io::Error| {
+        BoxliteError::Storage(format!("Failed to hash {}: {}", path.display(), e))
+    };
+    let mut file = std::fs::File::open(path).map_err(hash_err)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf).map_err(hash_err)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
@@ -1,18 +1,31 @@
 mod binary_finder;
+mod fs;
+mod hash;
 pub mod process;
+pub mod rolling;
+mod space;
 
 pub use binary_finder::{RuntimeBinaryFinder, find_binary};
+pub use fs::move_file;
+pub use hash::sha256_file;
 
 use std::path::PathBuf;
 use std::process::Command;
 
+use crate::runtime::options::LogFormat;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use tracing_appender::non_blocking::NonBlocking;
+use tracing_subscriber::Layer;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{EnvFilter, fmt};
 
-pub use process::{is_process_alive, is_same_process, kill_process, read_pid_file};
+pub use process::{
+    is_process_alive, is_running, is_same_process, kill_process, kill_process_group,
+    kill_process_tree, kill_shim_process_group, pid_tmp_path, process_group_id, process_start_time,
+    read_pid_file, write_pid_file_atomic,
+};
+pub use space::{available_space, ensure_available_space};
 
 #[cfg(any(target_os = "linux", target_os = "macos"))]
 unsafe extern "C" {
@@ -117,18 +130,26 @@ pub fn configure_library_env(cmd: &mut Command, addr: *const libc::c_void) {
     }
 }
 
-pub fn register_to_tracing(non_blocking: NonBlocking, env_filter: EnvFilter) {
+/// Install the global tracing subscriber writing to `non_blocking`.
+///
+/// No-op if a global subscriber is already set.
+pub fn register_to_tracing(non_blocking: NonBlocking, env_filter: EnvFilter, format: LogFormat) {
+    let layer = fmt::layer()
+        .with_writer(non_blocking)
+        .with_target(true)
+        .with_thread_ids(false)
+        .with_file(false)
+        .with_line_number(false)
+        .with_ansi(false);
+    let layer = match format {
+        LogFormat::Pretty => layer.boxed(),
+        LogFormat::Compact => layer.compact().boxed(),
+        LogFormat::Json => layer.json().boxed(),
+    };
+
     let _ = tracing_subscriber::registry()
+        .with(layer)
         .with(env_filter)
-        .with(
-            fmt::layer()
-                .with_writer(non_blocking)
-                .with_target(true)
-                .with_thread_ids(false)
-                .with_file(false)
-                .with_line_number(false)
-                .with_ansi(false),
-        )
         .try_init();
 }
 
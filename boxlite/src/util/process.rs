@@ -1,7 +1,11 @@
 //! Process validation utilities for PID checking and verification.
 
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How long [`kill_process_tree`] waits after SIGTERM before sending SIGKILL.
+const KILL_TREE_GRACE: Duration = Duration::from_secs(2);
 
 /// Read PID from file.
 ///
@@ -13,20 +17,51 @@ use std::path::Path;
 ///
 /// # Returns
 /// * `Ok(pid)` - The PID read from the file
-/// * `Err` - If the file cannot be read or parsed
+/// * `Err` - `Storage` if the file cannot be read, `Internal` if it does not
+///   hold a positive PID
 pub fn read_pid_file(path: &Path) -> BoxliteResult<u32> {
     let content = std::fs::read_to_string(path).map_err(|e| {
         BoxliteError::Storage(format!("Failed to read PID file {}: {}", path.display(), e))
     })?;
 
-    content.trim().parse::<u32>().map_err(|e| {
-        BoxliteError::Storage(format!(
+    match content.trim().parse::<u32>() {
+        Ok(pid) if pid > 0 => Ok(pid),
+        Ok(_) => Err(BoxliteError::Internal(format!(
+            "Invalid PID in file {}: '0'",
+            path.display()
+        ))),
+        Err(e) => Err(BoxliteError::Internal(format!(
             "Invalid PID in file {}: '{}' - {}",
             path.display(),
             content.trim(),
             e
-        ))
-    })
+        ))),
+    }
+}
+
+/// Write `pid` to `path` so that readers never see a partial file.
+///
+/// The PID goes to a sibling temp file (see [`pid_tmp_path`]) which is then
+/// renamed over `path`.
+pub fn write_pid_file_atomic(path: &Path, pid: u32) -> BoxliteResult<()> {
+    let tmp_path = pid_tmp_path(path);
+    std::fs::write(&tmp_path, format!("{}\n", pid))
+        .and_then(|()| std::fs::rename(&tmp_path, path))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&tmp_path);
+            BoxliteError::Storage(format!(
+                "Failed to write PID file {}: {}",
+                path.display(),
+                e
+            ))
+        })
+}
+
+/// Temp file a PID file is staged in before being renamed into place.
+pub fn pid_tmp_path(path: &Path) -> PathBuf {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    PathBuf::from(tmp)
 }
 
 /// Kill a process with SIGKILL.
@@ -38,6 +73,133 @@ pub fn kill_process(pid: u32) -> bool {
     unsafe { libc::kill(pid as i32, libc::SIGKILL) == 0 || !is_process_alive(pid) }
 }
 
+/// Kill every process in a process group with SIGKILL.
+///
+/// # Returns
+/// * `true` - Group was killed or no longer exists
+/// * `false` - Failed to kill (permission denied)
+pub fn kill_process_group(pgid: u32) -> bool {
+    // pgid 0 and 1 would signal our own group or everything
+    if pgid <= 1 {
+        return false;
+    }
+    unsafe {
+        libc::killpg(pgid as libc::pid_t, libc::SIGKILL) == 0
+            || std::io::Error::last_os_error().raw_os_error() == Some(libc::ESRCH)
+    }
+}
+
+/// Kill the process group led by the shim of box `box_id`.
+///
+/// Only a group led by the shim itself (`pgid == shim_pid`) is signalled.
+/// While the leader runs it must pass [`is_same_process`]; once it has
+/// exited, the kernel keeps its PID from being reused while the group has
+/// members, so a group that still exists under that ID is the shim's.
+///
+/// # Returns
+/// * `true` - Group was killed, no longer exists, or isn't the shim's
+/// * `false` - Failed to kill (permission denied)
+pub fn kill_shim_process_group(
+    pgid: u32,
+    shim_pid: u32,
+    box_id: &str,
+    start_time: Option<u64>,
+) -> bool {
+    if pgid != shim_pid || (is_running(pgid) && !is_same_process(pgid, box_id, start_time)) {
+        return true;
+    }
+    kill_process_group(pgid)
+}
+
+/// Process group of `pid`, or `None` if the process doesn't exist.
+pub fn process_group_id(pid: u32) -> Option<u32> {
+    let pgid = unsafe { libc::getpgid(pid as libc::pid_t) };
+    (pgid > 0).then_some(pgid as u32)
+}
+
+/// Kill a process and all of its descendants.
+///
+/// The shim spawns helpers (e.g. gvproxy) that would otherwise outlive it.
+/// The whole tree is collected first, then sent SIGTERM; whatever is still
+/// running after a grace period gets SIGKILL. Blocks for at most the grace
+/// period.
+///
+/// # Returns
+/// * `true` - Every process in the tree is gone
+/// * `false` - Some process survived SIGKILL (permission denied)
+pub fn kill_process_tree(pid: u32) -> bool {
+    let mut tree = vec![pid];
+    let mut next = 0;
+    while next < tree.len() {
+        for child in child_pids(tree[next]) {
+            if !tree.contains(&child) {
+                tree.push(child);
+            }
+        }
+        next += 1;
+    }
+
+    for &p in &tree {
+        unsafe { libc::kill(p as i32, libc::SIGTERM) };
+    }
+
+    let deadline = Instant::now() + KILL_TREE_GRACE;
+    while tree.iter().any(|&p| is_running(p)) && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    let mut all_gone = true;
+    for &p in tree.iter().filter(|&&p| is_running(p)) {
+        all_gone &= kill_process(p);
+    }
+    all_gone
+}
+
+/// Direct children of `pid`.
+fn child_pids(pid: u32) -> Vec<u32> {
+    #[cfg(target_os = "linux")]
+    {
+        let Ok(entries) = std::fs::read_dir("/proc") else {
+            return Vec::new();
+        };
+        entries
+            .filter_map(|e| e.ok()?.file_name().to_str()?.parse::<u32>().ok())
+            .filter(|&child| {
+                // ppid is field 4
+                proc_stat_field(child, 4).and_then(|ppid| ppid.parse::<u32>().ok()) == Some(pid)
+            })
+            .collect()
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        use sysinfo::{Pid, System};
+
+        let mut sys = System::new();
+        sys.refresh_processes();
+        sys.processes()
+            .iter()
+            .filter(|(_, p)| p.parent() == Some(Pid::from_u32(pid)))
+            .map(|(child, _)| child.as_u32())
+            .collect()
+    }
+}
+
+/// Whether `pid` exists and has not exited.
+///
+/// Unlike [`is_process_alive`], an exited child nobody has reaped yet (a
+/// zombie) counts as exited on Linux.
+pub fn is_running(pid: u32) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        // state is field 3
+        if proc_stat_field(pid, 3).as_deref() == Some("Z") {
+            return false;
+        }
+    }
+    is_process_alive(pid)
+}
+
 /// Check if a process with the given PID exists.
 ///
 /// Uses `libc::kill(pid, 0)` which sends a null signal to check existence.
@@ -57,14 +219,25 @@ pub fn is_process_alive(pid: u32) -> bool {
 /// * **Linux**: Read `/proc/{pid}/cmdline` and check for "boxlite-shim" + box_id
 /// * **macOS**: Use `sysinfo` crate to get process name and check for "boxlite-shim"
 ///
+/// When `start_time` is given, the process must also have been started at
+/// that time (see [`process_start_time`]). This catches a recycled PID that
+/// happens to run another shim, e.g. after a reboot.
+///
 /// # Arguments
 /// * `pid` - Process ID to verify
 /// * `box_id` - Expected box ID in the command line
+/// * `start_time` - Expected start time, if recorded
 ///
 /// # Returns
 /// * `true` - PID is our boxlite-shim process
 /// * `false` - PID is different process or doesn't exist
-pub fn is_same_process(pid: u32, box_id: &str) -> bool {
+pub fn is_same_process(pid: u32, box_id: &str, start_time: Option<u64>) -> bool {
+    if let Some(expected) = start_time
+        && process_start_time(pid) != Some(expected)
+    {
+        return false;
+    }
+
     #[cfg(target_os = "linux")]
     {
         is_same_process_linux(pid, box_id)
@@ -84,6 +257,61 @@ pub fn is_same_process(pid: u32, box_id: &str) -> bool {
     }
 }
 
+/// Start time of a process, comparable only with other values from this function.
+///
+/// * **Linux**: `starttime` from `/proc/{pid}/stat`, in clock ticks since boot
+/// * **macOS**: `proc_pidinfo` start time, in microseconds since the epoch
+///
+/// # Returns
+/// * `Some(start_time)` - Process exists and its start time was read
+/// * `None` - Process doesn't exist, or the platform is unsupported
+pub fn process_start_time(pid: u32) -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        // starttime is field 22
+        proc_stat_field(pid, 22)?.parse().ok()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let mut info: libc::proc_bsdinfo = unsafe { std::mem::zeroed() };
+        let size = std::mem::size_of::<libc::proc_bsdinfo>() as libc::c_int;
+        // SAFETY: info is a valid, writable proc_bsdinfo of `size` bytes
+        let written = unsafe {
+            libc::proc_pidinfo(
+                pid as libc::c_int,
+                libc::PROC_PIDTBSDINFO,
+                0,
+                &mut info as *mut _ as *mut libc::c_void,
+                size,
+            )
+        };
+        if written != size {
+            return None;
+        }
+        Some(info.pbi_start_tvsec * 1_000_000 + info.pbi_start_tvusec)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        let _ = pid;
+        None
+    }
+}
+
+/// Field `field` (1-based, as numbered in proc(5)) of `/proc/{pid}/stat`.
+///
+/// `field` must be 3 or later: comm (field 2) may contain spaces and parens,
+/// so the fields after it are found from its last `)`.
+#[cfg(target_os = "linux")]
+fn proc_stat_field(pid: u32, field: usize) -> Option<String> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let (_, rest) = stat.rsplit_once(')')?;
+    rest.split_whitespace()
+        .nth(field.checked_sub(3)?)
+        .map(str::to_string)
+}
+
 #[cfg(target_os = "linux")]
 fn is_same_process_linux(pid: u32, box_id: &str) -> bool {
     use std::fs;
@@ -140,12 +368,30 @@ mod tests {
         assert!(!is_process_alive(888888888));
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_is_running_treats_zombie_as_exited() {
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let pid = child.id();
+
+        // Not reaped yet: the exited child lingers as a zombie
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while is_running(pid) {
+            assert!(Instant::now() < deadline, "child never exited");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(is_process_alive(pid));
+
+        child.wait().unwrap();
+        assert!(!is_process_alive(pid));
+    }
+
     #[test]
     fn test_is_same_process_current() {
         let current_pid = std::process::id();
 
         // Current process is not boxlite-shim, so should return false
-        let result = is_same_process(current_pid, "test123");
+        let result = is_same_process(current_pid, "test123", None);
 
         // On non-Linux/macOS systems, this will return true (fallback)
         #[cfg(any(target_os = "linux", target_os = "macos"))]
@@ -155,8 +401,93 @@ mod tests {
     #[test]
     fn test_is_same_process_invalid() {
         // Invalid PID should return false
-        assert!(!is_same_process(0, "test123"));
-        assert!(!is_same_process(u32::MAX, "test123"));
+        assert!(!is_same_process(0, "test123", None));
+        assert!(!is_same_process(u32::MAX, "test123", None));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_process_start_time_current() {
+        let current_pid = std::process::id();
+        let start_time = process_start_time(current_pid).expect("should read own start time");
+        assert_eq!(process_start_time(current_pid), Some(start_time));
+
+        // A child started later has a start time no earlier than ours
+        let mut child = std::process::Command::new("sleep")
+            .arg("5")
+            .spawn()
+            .unwrap();
+        let child_start = process_start_time(child.id()).unwrap();
+        assert!(child_start >= start_time);
+        child.kill().unwrap();
+        child.wait().unwrap();
+
+        assert_eq!(process_start_time(999999999), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_kill_process_tree() {
+        let mut root = std::process::Command::new("sh")
+            .args(["-c", "sleep 30 & sleep 30 & wait"])
+            .spawn()
+            .unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let children = loop {
+            let children = child_pids(root.id());
+            if children.len() == 2 {
+                break children;
+            }
+            assert!(Instant::now() < deadline, "sleep children not spawned");
+            std::thread::sleep(Duration::from_millis(10));
+        };
+
+        assert!(kill_process_tree(root.id()));
+        root.wait().unwrap();
+        assert!(children.iter().all(|&c| !is_running(c)));
+    }
+
+    #[test]
+    fn test_kill_process_group() {
+        use std::os::unix::process::{CommandExt, ExitStatusExt};
+
+        let mut leader = std::process::Command::new("sh")
+            .args(["-c", "sleep 30 & wait"])
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        let pgid = leader.id();
+        assert_eq!(process_group_id(pgid), Some(pgid));
+        assert_ne!(process_group_id(std::process::id()), Some(pgid));
+
+        assert!(kill_process_group(pgid));
+        let status = leader.wait().unwrap();
+        assert_eq!(status.signal(), Some(libc::SIGKILL));
+
+        // Refused for groups that would hit the caller or every process
+        assert!(!kill_process_group(0));
+        assert!(!kill_process_group(1));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_kill_shim_process_group_spares_other_groups() {
+        use std::os::unix::process::CommandExt;
+
+        let mut leader = std::process::Command::new("sh")
+            .args(["-c", "sleep 30 & wait"])
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        let pgid = leader.id();
+
+        // The leader is alive but not a shim, or not the recorded shim PID
+        assert!(kill_shim_process_group(pgid, pgid, "box123", None));
+        assert!(kill_shim_process_group(pgid, pgid + 1, "box123", None));
+        assert!(leader.try_wait().unwrap().is_none());
+
+        assert!(kill_process_group(pgid));
+        leader.wait().unwrap();
     }
 
     #[test]
@@ -198,6 +529,30 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_write_pid_file_atomic() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("shim.pid");
+
+        write_pid_file_atomic(&path, 4242).unwrap();
+        assert_eq!(read_pid_file(&path).unwrap(), 4242);
+        assert!(!pid_tmp_path(&path).exists());
+
+        // Rewrites replace the old PID
+        write_pid_file_atomic(&path, 7).unwrap();
+        assert_eq!(read_pid_file(&path).unwrap(), 7);
+
+        // Garbage and zero are rejected as internal errors
+        for content in ["12ab\n", "0\n", "", "-5"] {
+            std::fs::write(&path, content).unwrap();
+            let err = read_pid_file(&path).unwrap_err();
+            assert!(
+                matches!(err, BoxliteError::Internal(_)),
+                "{content:?}: {err}"
+            );
+        }
+    }
+
     #[test]
     fn test_read_pid_file_missing() {
         // Non-existent file should return error
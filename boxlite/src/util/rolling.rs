@@ -0,0 +1,138 @@
+//! Size-based rolling log writer.
+//!
+//! `tracing_appender::rolling` only rotates on time boundaries. This writer
+//! rotates when the active file would grow past a byte limit, keeping a
+//! bounded number of numbered backups (`<name>.1` is the most recent).
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Number of rotated files kept next to the active log.
+pub const MAX_BACKUPS: usize = 5;
+
+/// Writer that rotates `<dir>/<name>` once it reaches `max_bytes`.
+#[derive(Debug)]
+pub struct SizeRollingWriter {
+    path: PathBuf,
+    max_bytes: u64,
+    file: File,
+    written: u64,
+}
+
+impl SizeRollingWriter {
+    /// Open (or create) `<dir>/<name>` for appending.
+    pub fn new(dir: impl AsRef<Path>, name: &str, max_bytes: u64) -> io::Result<Self> {
+        let path = dir.as_ref().join(name);
+        let file = open_append(&path)?;
+        let written = file.metadata()?.len();
+        Ok(Self {
+            path,
+            max_bytes,
+            file,
+            written,
+        })
+    }
+
+    /// Path of the `index`-th backup (`<name>.<index>`).
+    fn backup_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    /// Shift backups up by one, move the active file to `.1`, and reopen.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let _ = std::fs::remove_file(self.backup_path(MAX_BACKUPS));
+        for index in (1..MAX_BACKUPS).rev() {
+            let from = self.backup_path(index);
+            if from.exists() {
+                std::fs::rename(&from, self.backup_path(index + 1))?;
+            }
+        }
+        std::fs::rename(&self.path, self.backup_path(1))?;
+
+        self.file = open_append(&self.path)?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl Write for SizeRollingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Never rotate an empty file: a single oversized record still lands
+        if self.written > 0 && self.written + buf.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        let n = self.file.write(buf)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotates_past_size_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = SizeRollingWriter::new(dir.path(), "boxlite.log", 100).unwrap();
+
+        writer.write_all(&[b'a'; 60]).unwrap();
+        assert!(!dir.path().join("boxlite.log.1").exists());
+
+        // Would exceed 100 bytes: the first 60 move to the backup
+        writer.write_all(&[b'b'; 60]).unwrap();
+        writer.flush().unwrap();
+
+        assert_eq!(
+            std::fs::read(dir.path().join("boxlite.log.1")).unwrap(),
+            vec![b'a'; 60]
+        );
+        assert_eq!(
+            std::fs::read(dir.path().join("boxlite.log")).unwrap(),
+            vec![b'b'; 60]
+        );
+    }
+
+    #[test]
+    fn test_keeps_bounded_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = SizeRollingWriter::new(dir.path(), "boxlite.log", 10).unwrap();
+
+        for i in 0..(MAX_BACKUPS + 3) {
+            writer
+                .write_all(format!("record-{:03}", i).as_bytes())
+                .unwrap();
+        }
+
+        let files = std::fs::read_dir(dir.path()).unwrap().count();
+        assert_eq!(files, MAX_BACKUPS + 1);
+        // Most recent backup holds the second-to-last record
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("boxlite.log.1")).unwrap(),
+            format!("record-{:03}", MAX_BACKUPS + 1)
+        );
+    }
+
+    #[test]
+    fn test_resumes_size_of_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("boxlite.log"), [b'x'; 90]).unwrap();
+
+        let mut writer = SizeRollingWriter::new(dir.path(), "boxlite.log", 100).unwrap();
+        writer.write_all(&[b'y'; 20]).unwrap();
+
+        assert!(dir.path().join("boxlite.log.1").exists());
+    }
+}
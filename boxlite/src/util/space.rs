@@ -0,0 +1,66 @@
+//! Free-space checks run before writing large files.
+
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+
+/// Bytes available to unprivileged users on the filesystem holding `path`.
+///
+/// `path` need not exist yet: its nearest existing ancestor is queried.
+#[allow(clippy::unnecessary_cast)] // statvfs field widths differ across platforms
+pub fn available_space(path: &Path) -> BoxliteResult<u64> {
+    let existing = path
+        .ancestors()
+        .find(|p| p.exists())
+        .unwrap_or_else(|| Path::new("/"));
+    let c_path = CString::new(existing.as_os_str().as_bytes()).map_err(|_| {
+        BoxliteError::InvalidArgument(format!("path contains NUL byte: {}", path.display()))
+    })?;
+
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: c_path is NUL-terminated and stat is a valid out-pointer
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(BoxliteError::Storage(format!(
+            "Failed to query free space of {}: {}",
+            existing.display(),
+            std::io::Error::last_os_error()
+        )));
+    }
+    Ok((stat.f_bavail as u64).saturating_mul(stat.f_frsize as u64))
+}
+
+/// Fail with `BoxliteError::Storage` if `path` has less than `required` bytes free.
+///
+/// `what` names the operation in the error, e.g. "pulling alpine:latest".
+pub fn ensure_available_space(path: &Path, required: u64, what: &str) -> BoxliteResult<()> {
+    let available = available_space(path)?;
+    if available < required {
+        return Err(BoxliteError::Storage(format!(
+            "insufficient space on {} for {}: needs about {} MiB, {} MiB available",
+            path.display(),
+            what,
+            required.div_ceil(1024 * 1024),
+            available / (1024 * 1024)
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_available_space_of_temp_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let available = available_space(dir.path()).unwrap();
+        assert!(available > 0);
+        assert!(available_space(&dir.path().join("not/yet/created")).unwrap() > 0);
+
+        let err = ensure_available_space(dir.path(), u64::MAX, "test").unwrap_err();
+        assert!(err.to_string().contains("insufficient space"), "{err}");
+        ensure_available_space(dir.path(), 0, "test").unwrap();
+    }
+}
@@ -0,0 +1,160 @@
+//! Host-side disk I/O accounting for box subprocesses.
+//!
+//! The guest's virtio-blk statistics are not exposed to the host, so disk
+//! I/O is measured from the outside:
+//!
+//! 1. **Process I/O** (Linux): `/proc/<pid>/io` `read_bytes`/`write_bytes`
+//!    of the box subprocess, which performs all virtio-blk backing I/O.
+//! 2. **Disk file growth** (fallback): deltas in the allocated size of the
+//!    box's disk images. Only writes are observable this way, and only those
+//!    that allocate new blocks, so reads are reported as `None`.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Cumulative disk I/O since the box subprocess started.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct DiskIoCounters {
+    pub(crate) read_bytes: Option<u64>,
+    pub(crate) write_bytes: Option<u64>,
+}
+
+/// Read storage-layer I/O counters for a process from `/proc/<pid>/io`.
+#[cfg(target_os = "linux")]
+pub(crate) fn read_process_io(pid: u32) -> Option<DiskIoCounters> {
+    let content = std::fs::read_to_string(format!("/proc/{}/io", pid)).ok()?;
+    parse_proc_io(&content)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn read_process_io(_pid: u32) -> Option<DiskIoCounters> {
+    None
+}
+
+/// Parse the `read_bytes` / `write_bytes` lines of a `/proc/<pid>/io` file.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_proc_io(content: &str) -> Option<DiskIoCounters> {
+    let mut counters = DiskIoCounters::default();
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().parse::<u64>().ok();
+        match key.trim() {
+            "read_bytes" => counters.read_bytes = value,
+            "write_bytes" => counters.write_bytes = value,
+            _ => {}
+        }
+    }
+    (counters.read_bytes.is_some() || counters.write_bytes.is_some()).then_some(counters)
+}
+
+/// Tracks write volume by watching disk image files grow.
+///
+/// Each call to [`sample`](Self::sample) adds any growth in allocated size
+/// since the previous call to a running total. Shrinking (e.g. after a
+/// discard) is ignored so the total stays monotonic.
+#[derive(Debug)]
+pub(crate) struct DiskGrowthTracker {
+    paths: Vec<PathBuf>,
+    state: Mutex<GrowthState>,
+}
+
+#[derive(Debug, Default)]
+struct GrowthState {
+    last_allocated: Option<u64>,
+    written: u64,
+}
+
+impl DiskGrowthTracker {
+    pub(crate) fn new(paths: Vec<PathBuf>) -> Self {
+        Self {
+            paths,
+            state: Mutex::new(GrowthState::default()),
+        }
+    }
+
+    /// Take a sample and return cumulative bytes written since the first sample.
+    ///
+    /// Returns `None` when there are no disks to watch or none can be stat'ed.
+    pub(crate) fn sample(&self) -> Option<u64> {
+        let allocated = self
+            .paths
+            .iter()
+            .filter_map(|path| allocated_bytes(path))
+            .reduce(|a, b| a + b)?;
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(last) = state.last_allocated {
+            state.written += allocated.saturating_sub(last);
+        }
+        state.last_allocated = Some(allocated);
+        Some(state.written)
+    }
+}
+
+/// Bytes actually allocated on the host for a (possibly sparse) file.
+fn allocated_bytes(path: &Path) -> Option<u64> {
+    let meta = std::fs::metadata(path).ok()?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        Some(meta.blocks() * 512)
+    }
+    #[cfg(not(unix))]
+    {
+        Some(meta.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Seek, SeekFrom, Write};
+
+    #[test]
+    fn test_parse_proc_io() {
+        let content = "rchar: 100\nwchar: 200\nsyscr: 3\nsyscw: 4\n\
+                       read_bytes: 4096\nwrite_bytes: 8192\ncancelled_write_bytes: 0\n";
+        assert_eq!(
+            parse_proc_io(content),
+            Some(DiskIoCounters {
+                read_bytes: Some(4096),
+                write_bytes: Some(8192),
+            })
+        );
+        assert_eq!(parse_proc_io("rchar: 1\n"), None);
+    }
+
+    #[test]
+    fn test_growth_tracker_reports_write_delta() {
+        let dir = tempfile::tempdir().unwrap();
+        let disk = dir.path().join("disk.raw");
+        let mut file = std::fs::File::create(&disk).unwrap();
+        file.set_len(64 * 1024 * 1024).unwrap(); // sparse raw disk
+
+        let tracker = DiskGrowthTracker::new(vec![disk.clone()]);
+        assert_eq!(tracker.sample(), Some(0));
+
+        // Write 1 MiB into the middle of the sparse file
+        file.seek(SeekFrom::Start(16 * 1024 * 1024)).unwrap();
+        file.write_all(&vec![0xAB; 1024 * 1024]).unwrap();
+        file.sync_all().unwrap();
+
+        let written = tracker.sample().unwrap();
+        assert!(
+            written >= 1024 * 1024,
+            "expected at least 1 MiB written, got {}",
+            written
+        );
+
+        // No further writes: total stays put
+        assert_eq!(tracker.sample(), Some(written));
+    }
+
+    #[test]
+    fn test_growth_tracker_without_disks() {
+        let tracker = DiskGrowthTracker::new(vec![PathBuf::from("/nonexistent/disk.raw")]);
+        assert_eq!(tracker.sample(), None);
+    }
+}
@@ -14,6 +14,7 @@
 //! - Clear lifecycle boundaries (spawn vs runtime)
 //! - Caller-controlled GuestSession creation
 
+mod disk_io;
 mod handler;
 mod shim;
 mod spawn;
@@ -29,6 +30,12 @@ pub struct VmmMetrics {
     pub cpu_percent: Option<f32>,
     pub memory_bytes: Option<u64>,
     pub disk_bytes: Option<u64>,
+    /// Cumulative bytes read from the box's disks
+    pub disk_read_bytes: Option<u64>,
+    /// Cumulative bytes written to the box's disks
+    pub disk_write_bytes: Option<u64>,
+    /// Network backend counters (None if the backend can't report)
+    pub network: Option<crate::net::NetworkMetrics>,
 }
 
 /// Trait for spawning VMs.
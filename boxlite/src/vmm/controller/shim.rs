@@ -8,7 +8,9 @@ use crate::{
 };
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
+use super::disk_io::{DiskGrowthTracker, read_process_io};
 use super::{VmmController, VmmHandler as VmmHandlerTrait, VmmMetrics, spawn::spawn_subprocess};
+use crate::runtime::constants::filenames::net_stats_path;
 
 // ============================================================================
 // SHIM HANDLER - Runtime operations on running VM
@@ -29,6 +31,14 @@ pub struct ShimHandler {
     /// Shared System instance for CPU metrics calculation across calls.
     /// CPU usage requires comparing snapshots over time, so we must reuse the same System.
     metrics_sys: Mutex<sysinfo::System>,
+    /// Previous cgroup sample for CPU delta calculation (Linux only).
+    #[cfg(target_os = "linux")]
+    last_cgroup_sample: Mutex<Option<(Instant, crate::jailer::cgroup::CgroupStats)>>,
+    /// Disk image growth tracker, used when process I/O counters are unavailable.
+    disk_growth: DiskGrowthTracker,
+    /// Network stats file published by the shim, and the last snapshot read from it.
+    net_stats_path: Option<PathBuf>,
+    last_net_metrics: Mutex<Option<crate::net::NetworkMetrics>>,
 }
 
 impl ShimHandler {
@@ -47,6 +57,11 @@ impl ShimHandler {
             box_id,
             process: Some(process),
             metrics_sys: Mutex::new(sysinfo::System::new()),
+            #[cfg(target_os = "linux")]
+            last_cgroup_sample: Mutex::new(None),
+            disk_growth: DiskGrowthTracker::new(Vec::new()),
+            net_stats_path: None,
+            last_net_metrics: Mutex::new(None),
         }
     }
 
@@ -64,8 +79,69 @@ impl ShimHandler {
             box_id,
             process: None,
             metrics_sys: Mutex::new(sysinfo::System::new()),
+            #[cfg(target_os = "linux")]
+            last_cgroup_sample: Mutex::new(None),
+            disk_growth: DiskGrowthTracker::new(Vec::new()),
+            net_stats_path: None,
+            last_net_metrics: Mutex::new(None),
         }
     }
+
+    /// Set the disk images to watch for the disk I/O fallback.
+    pub fn with_disk_paths(mut self, paths: Vec<PathBuf>) -> Self {
+        self.disk_growth = DiskGrowthTracker::new(paths);
+        self
+    }
+
+    /// Set the network stats file published by the shim's network backend.
+    pub fn with_net_stats_path(mut self, path: PathBuf) -> Self {
+        self.net_stats_path = Some(path);
+        self
+    }
+
+    /// Read the latest published network counters.
+    ///
+    /// Falls back to the previous snapshot if the file is mid-write.
+    fn network_metrics(&self) -> Option<crate::net::NetworkMetrics> {
+        let path = self.net_stats_path.as_ref()?;
+        let mut last = self
+            .last_net_metrics
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(metrics) = crate::net::NetworkMetrics::read_from(path) {
+            *last = Some(metrics);
+        }
+        last.clone()
+    }
+
+    /// Collect process-level metrics via sysinfo.
+    fn process_metrics(&self) -> BoxliteResult<VmmMetrics> {
+        use sysinfo::Pid;
+
+        let pid = Pid::from_u32(self.pid);
+
+        // Use the shared System instance for stateful CPU tracking
+        let mut sys = self
+            .metrics_sys
+            .lock()
+            .map_err(|e| BoxliteError::Internal(format!("metrics_sys lock poisoned: {}", e)))?;
+
+        // Refresh process info - this updates the internal state for delta calculation
+        sys.refresh_process(pid);
+
+        // Try to get process information
+        if let Some(proc_info) = sys.process(pid) {
+            return Ok(VmmMetrics {
+                cpu_percent: Some(proc_info.cpu_usage()),
+                memory_bytes: Some(proc_info.memory()),
+                disk_bytes: None, // Not available from process-level APIs
+                ..Default::default()
+            });
+        }
+
+        // Process not found or not running - return empty metrics
+        Ok(VmmMetrics::default())
+    }
 }
 
 impl VmmHandlerTrait for ShimHandler {
@@ -157,30 +233,33 @@ impl VmmHandlerTrait for ShimHandler {
     }
 
     fn metrics(&self) -> BoxliteResult<VmmMetrics> {
-        use sysinfo::Pid;
-
-        let pid = Pid::from_u32(self.pid);
-
-        // Use the shared System instance for stateful CPU tracking
-        let mut sys = self
-            .metrics_sys
-            .lock()
-            .map_err(|e| BoxliteError::Internal(format!("metrics_sys lock poisoned: {}", e)))?;
-
-        // Refresh process info - this updates the internal state for delta calculation
-        sys.refresh_process(pid);
+        let mut metrics = self.process_metrics()?;
+
+        // Prefer authoritative cgroup accounting when the box has a cgroup
+        #[cfg(target_os = "linux")]
+        if let Some(stats) = crate::jailer::cgroup::read_stats(self.box_id.as_str()) {
+            let now = Instant::now();
+            let mut last = self.last_cgroup_sample.lock().map_err(|e| {
+                BoxliteError::Internal(format!("last_cgroup_sample lock poisoned: {}", e))
+            })?;
+
+            metrics.memory_bytes = Some(stats.memory_current);
+            if let Some((prev_at, prev)) = last.as_ref() {
+                metrics.cpu_percent = Some(stats.cpu_percent_since(prev, now - *prev_at));
+            }
+            *last = Some((now, stats));
+        }
 
-        // Try to get process information
-        if let Some(proc_info) = sys.process(pid) {
-            return Ok(VmmMetrics {
-                cpu_percent: Some(proc_info.cpu_usage()),
-                memory_bytes: Some(proc_info.memory()),
-                disk_bytes: None, // Not available from process-level APIs
-            });
+        match read_process_io(self.pid) {
+            Some(io) => {
+                metrics.disk_read_bytes = io.read_bytes;
+                metrics.disk_write_bytes = io.write_bytes;
+            }
+            None => metrics.disk_write_bytes = self.disk_growth.sample(),
         }
+        metrics.network = self.network_metrics();
 
-        // Process not found or not running - return empty metrics
-        Ok(VmmMetrics::default())
+        Ok(metrics)
     }
 
     fn is_running(&self) -> bool {
@@ -277,7 +356,9 @@ impl VmmController for ShimController {
             network_config: config.network_config.clone(), // Pass port mappings to subprocess (shim creates gvproxy)
             network_backend_endpoint: None, // Will be populated by shim (not serialized)
             home_dir: config.home_dir.clone(),
+            layout_dirs: config.layout_dirs.clone(),
             console_output: config.console_output.clone(),
+            kernel: config.kernel.clone(),
             detach: config.detach,
             parent_pid: config.parent_pid,
         };
@@ -311,6 +392,7 @@ impl VmmController for ShimController {
             self.engine_type,
             &config_json,
             &config.home_dir,
+            &config.layout_dirs,
             self.box_id.as_str(),
             &self.options,
         )?;
@@ -331,7 +413,16 @@ impl VmmController for ShimController {
 
         // Create handler for the running VM
         // Note: stdio is null (no pipes), so no LogStreamHandler needed
-        let handler = ShimHandler::from_child(child, self.box_id.clone());
+        let disk_paths = config
+            .block_devices
+            .devices()
+            .iter()
+            .filter(|device| !device.read_only)
+            .map(|device| device.disk_path.clone())
+            .collect();
+        let handler = ShimHandler::from_child(child, self.box_id.clone())
+            .with_disk_paths(disk_paths)
+            .with_net_stats_path(net_stats_path(&config.home_dir, self.box_id.as_str()));
 
         tracing::info!(
             box_id = %self.box_id,
@@ -6,7 +6,7 @@ use std::{
 };
 
 use crate::jailer::Jailer;
-use crate::runtime::layout::FilesystemLayout;
+use crate::runtime::layout::{FilesystemLayout, LayoutDirs};
 use crate::runtime::options::BoxOptions;
 use crate::util::configure_library_env;
 use crate::vmm::VmmKind;
@@ -20,6 +20,7 @@ use libkrun_sys::krun_create_ctx;
 /// * `engine_type` - Type of VM engine to use
 /// * `config_json` - Serialized BoxConfig
 /// * `home_dir` - BoxLite home directory
+/// * `layout_dirs` - Home subdirectories placed elsewhere
 /// * `box_id` - Unique box identifier
 /// * `options` - Box options (includes security and volumes)
 ///
@@ -31,6 +32,7 @@ pub(crate) fn spawn_subprocess(
     engine_type: VmmKind,
     config_json: &str,
     home_dir: &Path,
+    layout_dirs: &LayoutDirs,
     box_id: &str,
     options: &BoxOptions,
 ) -> BoxliteResult<Child> {
@@ -44,13 +46,18 @@ pub(crate) fn spawn_subprocess(
 
     // Create filesystem layout and box directory
     use crate::runtime::layout::FsLayoutConfig;
-    let layout = FilesystemLayout::new(home_dir.to_path_buf(), FsLayoutConfig::default());
+    let layout = FilesystemLayout::new(
+        home_dir.to_path_buf(),
+        FsLayoutConfig::default().with_dirs(layout_dirs.clone()),
+    );
     let box_dir = layout.boxes_dir().join(box_id);
 
-    // Create Jailer with security options and volumes
+    // Create Jailer with security options, volumes and the disk base
     let jailer = Jailer::new(box_id, &box_dir)
         .with_security(options.security.clone())
-        .with_volumes(options.volumes.clone());
+        .with_volumes(options.volumes.clone())
+        .with_disk_backing_file(options.disk.backing_file.clone())
+        .with_layout_dirs(layout.dirs().clone());
 
     // Setup pre-spawn isolation (cgroups on Linux, no-op on macOS)
     jailer.setup_pre_spawn()?;
@@ -73,7 +80,7 @@ pub(crate) fn spawn_subprocess(
     cmd.stdout(Stdio::null());
     cmd.stderr(Stdio::null());
 
-    cmd.spawn().map_err(|e| {
+    let mut child = cmd.spawn().map_err(|e| {
         let err_msg = format!(
             "Failed to spawn VM subprocess at {}: {}",
             binary_path.display(),
@@ -81,5 +88,15 @@ pub(crate) fn spawn_subprocess(
         );
         tracing::error!("{}", err_msg);
         BoxliteError::Engine(err_msg)
-    })
+    })?;
+
+    // The pre_exec hook records the PID before exec, but skips it when the
+    // path can't be pre-allocated; record it from here too so a PID file
+    // left by a previous run is never mistaken for this shim
+    if let Err(e) = crate::util::write_pid_file_atomic(&box_dir.join("shim.pid"), child.id()) {
+        let _ = child.kill();
+        let _ = child.wait();
+        return Err(e);
+    }
+    Ok(child)
 }
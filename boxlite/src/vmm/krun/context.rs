@@ -22,6 +22,8 @@ use libkrun_sys::{
 /// Thin wrapper that owns a libkrun context.
 pub struct KrunContext {
     ctx_id: u32,
+    /// Parameters appended to the kernel command line by `set_exec`.
+    kernel_cmdline: Vec<CString>,
 }
 
 impl KrunContext {
@@ -82,7 +84,10 @@ impl KrunContext {
             )));
         }
         tracing::trace!(ctx_id = ctx, "krun_create_ctx succeeded");
-        Ok(Self { ctx_id: ctx as u32 })
+        Ok(Self {
+            ctx_id: ctx as u32,
+            kernel_cmdline: Vec::new(),
+        })
     }
 
     pub unsafe fn set_vm_config(&self, cpus: u8, memory_mib: u32) -> BoxliteResult<()> {
@@ -204,6 +209,28 @@ impl KrunContext {
         }
     }
 
+    /// Append parameters (e.g. `loglevel=4`) to the guest kernel command line.
+    ///
+    /// Takes effect at the next `set_exec`. Parameters are checked with
+    /// [`validate_kernel_param`](crate::vmm::validate_kernel_param).
+    pub fn append_kernel_cmdline(&mut self, params: &[String]) -> BoxliteResult<()> {
+        for param in params {
+            crate::vmm::validate_kernel_param(param)?;
+            self.kernel_cmdline.push(
+                CString::new(param.as_str())
+                    .map_err(|e| BoxliteError::Engine(format!("invalid kernel param: {e}")))?,
+            );
+        }
+        Ok(())
+    }
+
+    /// Set the guest init executable, its arguments and environment.
+    ///
+    /// `krun_set_kernel`, libkrun's cmdline setter, also swaps out the
+    /// bundled libkrunfw kernel. Instead, the parameters added with
+    /// [`append_kernel_cmdline`](Self::append_kernel_cmdline) go after the
+    /// environment, which libkrun writes into the kernel command line ahead
+    /// of the `--` that starts init's arguments.
     pub unsafe fn set_exec(
         &self,
         exec: &str,
@@ -238,8 +265,11 @@ impl KrunContext {
         }
 
         let env_storage = Self::env_to_cstring(env)?;
-        let mut env_ptrs: Vec<*const std::ffi::c_char> =
-            env_storage.iter().map(|entry| entry.as_ptr()).collect();
+        let mut env_ptrs: Vec<*const std::ffi::c_char> = env_storage
+            .iter()
+            .chain(&self.kernel_cmdline)
+            .map(|entry| entry.as_ptr())
+            .collect();
         env_ptrs.push(ptr::null());
 
         check_status("krun_set_exec", unsafe {
@@ -415,6 +445,31 @@ impl KrunContext {
         }
     }
 
+    /// Add a stream network backend via an already-connected file descriptor.
+    ///
+    /// Used for backends reached over TCP, where libkrun cannot connect by path.
+    /// The frames are the same length-prefixed stream protocol as `UnixStream`.
+    /// libkrun takes ownership of `fd`.
+    pub unsafe fn add_net_stream_fd(
+        &self,
+        fd: i32,
+        features: u32,
+        mac_address: [u8; 6],
+    ) -> BoxliteResult<()> {
+        tracing::debug!(fd, features, "Adding network backend via stream fd");
+
+        check_status("krun_add_net_unixstream", unsafe {
+            krun_add_net_unixstream(
+                self.ctx_id,
+                ptr::null(),          // c_path: NULL (use fd instead)
+                fd,                   // fd: connected stream socket
+                mac_address.as_ptr(), // c_mac: valid MAC address (required, not NULL!)
+                features,             // features: virtio-net features bitmask
+                0,                    // flags: 0 for default
+            )
+        })
+    }
+
     /// Add a virtiofs mount, sharing a host directory with the guest.
     ///
     /// # Arguments
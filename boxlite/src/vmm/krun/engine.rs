@@ -2,9 +2,26 @@
 
 use super::context::KrunContext;
 use crate::runtime::constants::network;
-use crate::vmm::{InstanceSpec, Vmm, VmmConfig, VmmInstance, engine::VmmInstanceImpl};
+use crate::vmm::{
+    InstanceSpec, KernelSpec, Vmm, VmmConfig, VmmInstance, engine::VmmInstanceImpl,
+    validate_kernel_param,
+};
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
+/// Kernel parameter that stops the guest from probing virtio-rng.
+const VIRTIO_RNG_DISABLE_PARAM: &str = "initcall_blacklist=virtio_rng_driver_init";
+
+/// Virtio-net feature flags offered for external network backends.
+fn net_features() -> u32 {
+    use crate::vmm::krun::constants::network_features::*;
+    NET_FEATURE_CSUM
+        | NET_FEATURE_GUEST_CSUM
+        | NET_FEATURE_GUEST_TSO4
+        | NET_FEATURE_GUEST_UFO
+        | NET_FEATURE_HOST_TSO4
+        | NET_FEATURE_HOST_UFO
+}
+
 /// Libkrun-specific VMM instance implementation.
 struct KrunVmmInstance {
     context: KrunContext,
@@ -167,6 +184,26 @@ impl Krun {
         guest_args
     }
 
+    /// Parameters appended to libkrun's default kernel command line.
+    fn kernel_params(kernel: &KernelSpec) -> BoxliteResult<Vec<String>> {
+        let mut params = Vec::new();
+        if let Some(level) = kernel.loglevel {
+            params.push(format!("loglevel={}", level));
+        }
+        if !kernel.rng {
+            // libkrun always attaches a virtio-rng device and has no API to
+            // leave it out; keep the guest driver from binding instead, so
+            // nothing in the guest reads from it
+            tracing::debug!("virtio-rng disabled: blacklisting its guest driver");
+            params.push(VIRTIO_RNG_DISABLE_PARAM.to_string());
+        }
+        for param in &kernel.extra {
+            validate_kernel_param(param)?;
+            params.push(param.clone());
+        }
+        Ok(params)
+    }
+
     fn set_entrypoint(config: &InstanceSpec, ctx: &mut KrunContext) -> Result<(), BoxliteError> {
         // Prepare entrypoint - the VM runs the guest agent which will:
         // 1. Mount virtiofs shares
@@ -183,6 +220,13 @@ impl Krun {
             tracing::trace!(index = i, arg = ?arg, "Entrypoint argument");
         }
 
+        let kernel_params = Self::kernel_params(&config.kernel)?;
+        if !kernel_params.is_empty() {
+            tracing::debug!(params = ?kernel_params, "Appending kernel cmdline parameters");
+        }
+
+        ctx.append_kernel_cmdline(&kernel_params)?;
+
         // Set executable and arguments with provided environment
         unsafe {
             ctx.set_exec(guest_executable, &guest_args, &config.guest_entrypoint.env)?;
@@ -272,14 +316,7 @@ impl Vmm for Krun {
                             ))
                         })?;
 
-                        // Configure virtio-net feature flags
-                        use crate::vmm::krun::constants::network_features::*;
-                        let features = NET_FEATURE_CSUM
-                            | NET_FEATURE_GUEST_CSUM
-                            | NET_FEATURE_GUEST_TSO4
-                            | NET_FEATURE_GUEST_UFO
-                            | NET_FEATURE_HOST_TSO4
-                            | NET_FEATURE_HOST_UFO;
+                        let features = net_features();
 
                         // Pass the socket path to libkrun (not FD)
                         // libkrun will connect and send the VFKit magic handshake if needed
@@ -292,6 +329,40 @@ impl Vmm for Krun {
 
                         tracing::debug!("Successfully configured Unix socket net");
                     }
+                    crate::net::NetworkBackendEndpoint::Tcp {
+                        addr,
+                        connection_type,
+                        mac_address,
+                    } => {
+                        // libkrun only connects by Unix socket path, so connect the
+                        // TCP stream here and hand over the fd. Stream framing works
+                        // over TCP; datagram framing has no TCP equivalent.
+                        if *connection_type != crate::net::ConnectionType::UnixStream {
+                            return Err(BoxliteError::Network(format!(
+                                "network backend at {} uses {:?}, which libkrun cannot use over TCP",
+                                addr, connection_type
+                            )));
+                        }
+
+                        tracing::info!(
+                            addr = %addr,
+                            mac_address = ?mac_address,
+                            "Configuring TCP net"
+                        );
+
+                        let stream = std::net::TcpStream::connect(addr).map_err(|e| {
+                            BoxliteError::Network(format!(
+                                "Failed to connect to network backend at {}: {}",
+                                addr, e
+                            ))
+                        })?;
+                        let _ = stream.set_nodelay(true);
+
+                        use std::os::fd::IntoRawFd;
+                        ctx.add_net_stream_fd(stream.into_raw_fd(), net_features(), *mac_address)?;
+
+                        tracing::debug!("Successfully configured TCP net");
+                    }
                 }
             } else {
                 // No network connection specified - use libkrun's built-in TSI net
@@ -467,3 +538,72 @@ impl Vmm for Krun {
         Ok(VmmInstance::new(Box::new(instance)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kernel_loglevel_maps_to_cmdline() {
+        assert!(
+            Krun::kernel_params(&KernelSpec::default())
+                .unwrap()
+                .is_empty()
+        );
+
+        let kernel = KernelSpec {
+            loglevel: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(Krun::kernel_params(&kernel).unwrap(), ["loglevel=3"]);
+    }
+
+    #[test]
+    fn test_kernel_cmdline_extra() {
+        let kernel = KernelSpec {
+            loglevel: Some(4),
+            extra: vec!["quiet".into(), "virtio_blk.queue_depth=64".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            Krun::kernel_params(&kernel).unwrap(),
+            ["loglevel=4", "quiet", "virtio_blk.queue_depth=64"]
+        );
+
+        for bad in [
+            "quiet\ninit=/bin/sh",
+            "quiet init=/bin/sh",
+            "a=\"b",
+            "a='b'",
+            "--",
+        ] {
+            let kernel = KernelSpec {
+                extra: vec![bad.into()],
+                ..Default::default()
+            };
+            assert!(
+                matches!(
+                    Krun::kernel_params(&kernel),
+                    Err(BoxliteError::InvalidArgument(_))
+                ),
+                "{:?} accepted",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_rng_device_follows_flag() {
+        let params = Krun::kernel_params(&KernelSpec::default()).unwrap();
+        assert!(!params.iter().any(|p| p == VIRTIO_RNG_DISABLE_PARAM));
+
+        let kernel = KernelSpec {
+            rng: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            Krun::kernel_params(&kernel).unwrap(),
+            [VIRTIO_RNG_DISABLE_PARAM]
+        );
+    }
+}
@@ -1,6 +1,6 @@
 //! Engine abstraction for Boxlite runtime.
 
-use boxlite_shared::errors::BoxliteError;
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -172,8 +172,14 @@ pub struct InstanceSpec {
     pub network_backend_endpoint: Option<crate::net::NetworkBackendEndpoint>,
     /// Home directory for boxlite runtime (~/.boxlite or BOXLITE_HOME)
     pub home_dir: PathBuf,
+    /// Subdirectories of the runtime home placed elsewhere (logs, images, ...)
+    #[serde(default)]
+    pub layout_dirs: crate::runtime::layout::LayoutDirs,
     /// Optional file path to redirect console output (kernel/init messages)
     pub console_output: Option<PathBuf>,
+    /// Guest kernel command-line settings
+    #[serde(default)]
+    pub kernel: KernelSpec,
     /// Whether the box should continue running when the parent process exits.
     /// When false, a watchdog thread monitors parent PID and triggers shutdown.
     pub detach: bool,
@@ -182,6 +188,57 @@ pub struct InstanceSpec {
     pub parent_pid: u32,
 }
 
+/// Guest kernel settings applied through the kernel command line.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct KernelSpec {
+    /// Console log level (`loglevel=N`), 0-7.
+    pub loglevel: Option<u8>,
+    /// Extra parameters appended in order, e.g. `quiet`.
+    #[serde(default)]
+    pub extra: Vec<String>,
+    /// Whether the guest uses the virtio-rng entropy device.
+    ///
+    /// The device is always attached; when false its guest driver is kept
+    /// from loading.
+    #[serde(default = "default_rng")]
+    pub rng: bool,
+}
+
+fn default_rng() -> bool {
+    true
+}
+
+impl Default for KernelSpec {
+    fn default() -> Self {
+        Self {
+            loglevel: None,
+            extra: Vec::new(),
+            rng: default_rng(),
+        }
+    }
+}
+
+/// Reject kernel cmdline parameters that would corrupt the command line.
+///
+/// A parameter must be a single token: whitespace would split it, quotes
+/// would unbalance libkrun's quoting and `--` would hand the rest of the
+/// command line to init as arguments.
+pub(crate) fn validate_kernel_param(param: &str) -> BoxliteResult<()> {
+    if param.is_empty()
+        || param == "--"
+        || param
+            .chars()
+            .any(|c| c.is_control() || c.is_whitespace() || c == '"' || c == '\'')
+    {
+        return Err(BoxliteError::InvalidArgument(format!(
+            "invalid kernel cmdline parameter {:?}: must be a single non-empty token \
+             without quotes or control characters, and not `--`",
+            param
+        )));
+    }
+    Ok(())
+}
+
 /// Entrypoint configuration that the guest should run.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Entrypoint {
@@ -9,7 +9,7 @@
 
 use std::path::PathBuf;
 
-use super::guest_volume::GuestVolumeManager;
+use super::guest_volume::{GuestVolumeManager, MountOptions};
 
 /// Container bind mount entry.
 ///
@@ -57,10 +57,12 @@ impl<'a> ContainerVolumeManager<'a> {
     /// # Arguments
     /// * `container_id` - Container ID for path construction
     /// * `volume_name` - Volume identifier (e.g., "data", "config")
-    /// * `tag` - Virtiofs tag name (e.g., "uservol0")
+    /// * `tag` - Virtiofs tag name (e.g., "uservol-3f2a9c1b7d40")
     /// * `host_path` - Path on host to share
     /// * `container_path` - Mount point in container (user-specified)
     /// * `read_only` - Whether the mount is read-only
+    /// * `mount` - Guest-side mount options for the virtiofs share
+    #[allow(clippy::too_many_arguments)]
     pub fn add_volume(
         &mut self,
         container_id: &str,
@@ -69,6 +71,7 @@ impl<'a> ContainerVolumeManager<'a> {
         host_path: PathBuf,
         container_path: &str,
         read_only: bool,
+        mount: MountOptions,
     ) {
         // Add virtiofs share to guest with container_id
         // Guest will mount at convention path: /run/boxlite/shared/containers/{container_id}/volumes/{tag}
@@ -78,6 +81,7 @@ impl<'a> ContainerVolumeManager<'a> {
             None,
             read_only,
             Some(container_id.to_string()),
+            mount,
         );
 
         // Record container bind mount - guest constructs source path from convention
@@ -10,6 +10,7 @@ use std::path::{Path, PathBuf};
 
 use crate::disk::DiskFormat;
 use crate::portal::interfaces::VolumeConfig;
+use crate::runtime::options::VolumeCacheMode;
 use crate::vmm::{BlockDevice, BlockDevices, FsShares};
 
 /// Tracked virtiofs share entry.
@@ -23,6 +24,28 @@ pub struct FsShareEntry {
     pub read_only: bool,
     /// Optional container_id for convention-based paths.
     pub container_id: Option<String>,
+    /// Guest-side mount options.
+    pub mount: MountOptions,
+}
+
+/// Guest-side options for a virtiofs mount.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MountOptions {
+    /// Extra mount options (e.g. `nosuid`, `nodev`).
+    pub options: Vec<String>,
+    /// DAX caching policy.
+    pub cache_mode: VolumeCacheMode,
+}
+
+impl MountOptions {
+    /// Full option list sent to the guest, including the cache mode.
+    pub fn guest_options(&self) -> Vec<String> {
+        let mut options = self.options.clone();
+        if let Some(cache) = self.cache_mode.mount_option() {
+            options.push(cache.to_string());
+        }
+        options
+    }
 }
 
 /// Tracked block device entry.
@@ -77,6 +100,7 @@ impl GuestVolumeManager {
     ///
     /// `guest_path`: Where to mount in guest. `None` = guest determines from tag.
     /// `container_id`: For user volumes, enables convention-based paths.
+    /// `mount`: Guest-side mount options.
     pub fn add_fs_share(
         &mut self,
         tag: &str,
@@ -84,6 +108,7 @@ impl GuestVolumeManager {
         guest_path: Option<&str>,
         read_only: bool,
         container_id: Option<String>,
+        mount: MountOptions,
     ) {
         self.fs_shares.push(FsShareEntry {
             tag: tag.to_string(),
@@ -91,6 +116,7 @@ impl GuestVolumeManager {
             guest_path: guest_path.map(String::from),
             read_only,
             container_id,
+            mount,
         });
     }
 
@@ -192,6 +218,7 @@ impl GuestVolumeManager {
                 mount_point,
                 entry.read_only,
                 entry.container_id.clone(),
+                entry.mount.guest_options(),
             ));
         }
 
@@ -223,3 +250,44 @@ impl Default for GuestVolumeManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mount_options_propagate_to_guest_mounts() {
+        let mut manager = GuestVolumeManager::new();
+        manager.add_fs_share(
+            "uservol-abc",
+            PathBuf::from("/srv/data"),
+            None,
+            true,
+            Some("cid".to_string()),
+            MountOptions {
+                options: vec!["nosuid".to_string(), "nodev".to_string()],
+                cache_mode: VolumeCacheMode::Never,
+            },
+        );
+        manager.add_fs_share(
+            "shared",
+            PathBuf::from("/srv/shared"),
+            None,
+            false,
+            None,
+            MountOptions::default(),
+        );
+
+        let mounts = manager.build_guest_mounts();
+        let VolumeConfig::Virtiofs { tag, options, .. } = &mounts[0] else {
+            panic!("expected virtiofs mount, got {:?}", mounts[0]);
+        };
+        assert_eq!(tag, "uservol-abc");
+        assert_eq!(options, &["nosuid", "nodev", "dax=never"]);
+
+        let VolumeConfig::Virtiofs { options, .. } = &mounts[1] else {
+            panic!("expected virtiofs mount, got {:?}", mounts[1]);
+        };
+        assert!(options.is_empty());
+    }
+}
@@ -8,4 +8,4 @@ mod container_volume;
 mod guest_volume;
 
 pub use container_volume::{ContainerMount, ContainerVolumeManager};
-pub use guest_volume::GuestVolumeManager;
+pub use guest_volume::{GuestVolumeManager, MountOptions};
@@ -28,6 +28,7 @@ impl TestContext {
         let options = BoxliteOptions {
             home_dir: temp_dir.path().to_path_buf(),
             image_registries: vec![],
+            ..Default::default()
         };
         let runtime = BoxliteRuntime::new(options).expect("Failed to create runtime");
         Self {
@@ -615,6 +616,33 @@ async fn test_metrics_returns_stopped_error() {
     let _ = ctx.runtime.remove(handle.id().as_str(), true).await;
 }
 
+/// Test that the background sampler populates cpu_percent across snapshots.
+#[tokio::test]
+async fn test_metrics_sampler_populates_cpu_percent() {
+    let temp_dir = TempDir::new_in("/tmp").expect("Failed to create temp dir");
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        image_registries: vec![],
+        metrics_interval: Some(Duration::from_millis(200)),
+        ..Default::default()
+    })
+    .expect("Failed to create runtime");
+
+    let handle = runtime.create(default_box_options(), None).await.unwrap();
+    handle.start().await.unwrap();
+
+    // Let the sampler take a few samples so a CPU delta exists
+    tokio::time::sleep(Duration::from_millis(800)).await;
+    let first = handle.metrics().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    let second = handle.metrics().await.unwrap();
+
+    assert!(first.cpu_percent.is_some());
+    assert!(second.cpu_percent.is_some());
+
+    let _ = runtime.remove(handle.id().as_str(), true).await;
+}
+
 /// Test that create() returns Stopped error after runtime.shutdown().
 #[tokio::test]
 async fn test_create_after_shutdown_returns_stopped() {
@@ -0,0 +1,72 @@
+//! Integration tests for runtime log output.
+//!
+//! The tracing subscriber is process-global, so these tests live in their own
+//! test binary and share a single initialization.
+
+use boxlite::runtime::options::{BoxliteOptions, LogFormat, LogRotation};
+use boxlite::{BoxliteRuntime, init_logging_for};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+/// Concatenated contents of every log file in `logs_dir`.
+fn read_logs(logs_dir: &Path) -> String {
+    let mut content = String::new();
+    for entry in std::fs::read_dir(logs_dir).unwrap().flatten() {
+        if entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with("boxlite.log")
+        {
+            content.push_str(&std::fs::read_to_string(entry.path()).unwrap_or_default());
+        }
+    }
+    content
+}
+
+#[test]
+fn test_json_log_format_chosen_on_first_init() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        log_format: LogFormat::Json,
+        ..Default::default()
+    })
+    .unwrap();
+
+    // Later initializations don't replace the subscriber
+    let layout = boxlite::runtime::layout::FilesystemLayout::new(
+        temp_dir.path().to_path_buf(),
+        boxlite::runtime::layout::FsLayoutConfig::without_bind_mount(),
+    );
+    init_logging_for(&layout, LogFormat::Compact, LogRotation::Hourly).unwrap();
+
+    tracing::info!(marker = "json-format-check", "hello from the test");
+
+    // The appender writes on a background thread
+    let logs_dir = temp_dir.path().join("logs");
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let content = loop {
+        let content = read_logs(&logs_dir);
+        if content.contains("json-format-check") {
+            break content;
+        }
+        assert!(Instant::now() < deadline, "log line was not written");
+        std::thread::sleep(Duration::from_millis(20));
+    };
+
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        let value: serde_json::Value = serde_json::from_str(line)
+            .unwrap_or_else(|e| panic!("not a JSON log line ({}): {}", e, line));
+        assert!(value.is_object());
+    }
+    let record: serde_json::Value = content
+        .lines()
+        .find(|l| l.contains("json-format-check"))
+        .map(|l| serde_json::from_str(l).unwrap())
+        .unwrap();
+    assert_eq!(record["fields"]["message"], "hello from the test");
+    assert_eq!(record["level"], "INFO");
+
+    drop(runtime);
+}
@@ -1,6 +1,7 @@
 //! Integration tests for network backend selection and configuration.
 
 use boxlite::net::{NetworkBackendConfig, NetworkBackendFactory};
+use boxlite::{PortForward, PortProtocol};
 
 #[test]
 #[cfg(all(not(feature = "libslirp-backend"), not(feature = "gvproxy-backend")))]
@@ -22,11 +23,15 @@ fn test_no_backend_when_no_features_enabled() {
 #[test]
 fn test_network_config_creation() {
     // Test NetworkConfig constructor
-    let port_mappings = vec![(8080, 80), (3000, 3000), (5432, 5432)];
-    let config = NetworkBackendConfig::new(port_mappings.clone());
-
-    assert_eq!(config.port_mappings.len(), 3);
-    assert_eq!(config.port_mappings, port_mappings);
+    let port_forwards = vec![
+        PortForward::tcp(8080, 80),
+        PortForward::tcp(3000, 3000),
+        PortForward::new(5353, 53, PortProtocol::Udp),
+    ];
+    let config = NetworkBackendConfig::new(port_forwards.clone());
+
+    assert_eq!(config.port_forwards.len(), 3);
+    assert_eq!(config.port_forwards, port_forwards);
 }
 
 #[tokio::test]
@@ -1,7 +1,8 @@
 //! Integration tests for runtime initialization and locking behavior.
 
-use boxlite::BoxliteRuntime;
-use boxlite::runtime::options::BoxliteOptions;
+use boxlite::runtime::options::{BoxOptions, BoxliteOptions, RootfsSpec};
+use boxlite::{BoxStatus, BoxliteRuntime, HealthStatus};
+use boxlite_shared::BoxliteError;
 use std::thread;
 use std::time::Duration;
 use tempfile::TempDir;
@@ -14,6 +15,7 @@ fn test_runtime_prevents_concurrent_access() {
     let config1 = BoxliteOptions {
         home_dir: temp_dir.path().to_path_buf(),
         image_registries: vec![],
+        ..Default::default()
     };
     let runtime1 = BoxliteRuntime::new(config1).unwrap();
 
@@ -21,6 +23,7 @@ fn test_runtime_prevents_concurrent_access() {
     let config2 = BoxliteOptions {
         home_dir: temp_dir.path().to_path_buf(),
         image_registries: vec![],
+        ..Default::default()
     };
     let result = BoxliteRuntime::new(config2);
     assert!(result.is_err());
@@ -36,6 +39,7 @@ fn test_runtime_prevents_concurrent_access() {
     let config3 = BoxliteOptions {
         home_dir: temp_dir.path().to_path_buf(),
         image_registries: vec![],
+        ..Default::default()
     };
     let _runtime2 = BoxliteRuntime::new(config3).unwrap();
 }
@@ -49,6 +53,7 @@ fn test_runtime_lock_released_on_drop() {
         let config = BoxliteOptions {
             home_dir: temp_dir.path().to_path_buf(),
             image_registries: vec![],
+            ..Default::default()
         };
         let _runtime = BoxliteRuntime::new(config).unwrap();
     } // Lock released here
@@ -57,6 +62,7 @@ fn test_runtime_lock_released_on_drop() {
     let config2 = BoxliteOptions {
         home_dir: temp_dir.path().to_path_buf(),
         image_registries: vec![],
+        ..Default::default()
     };
     let _runtime2 = BoxliteRuntime::new(config2).unwrap();
 }
@@ -70,6 +76,7 @@ fn test_runtime_lock_across_threads() {
     let config1 = BoxliteOptions {
         home_dir: dir_path.clone(),
         image_registries: vec![],
+        ..Default::default()
     };
     let _runtime1 = BoxliteRuntime::new(config1).unwrap();
 
@@ -79,6 +86,7 @@ fn test_runtime_lock_across_threads() {
         let config = BoxliteOptions {
             home_dir: dir_clone,
             image_registries: vec![],
+            ..Default::default()
         };
         BoxliteRuntime::new(config)
     });
@@ -96,6 +104,7 @@ fn test_different_home_dirs_independent() {
     let config1 = BoxliteOptions {
         home_dir: temp_dir1.path().to_path_buf(),
         image_registries: vec![],
+        ..Default::default()
     };
     let _runtime1 = BoxliteRuntime::new(config1).unwrap();
 
@@ -103,6 +112,7 @@ fn test_different_home_dirs_independent() {
     let config2 = BoxliteOptions {
         home_dir: temp_dir2.path().to_path_buf(),
         image_registries: vec![],
+        ..Default::default()
     };
     let _runtime2 = BoxliteRuntime::new(config2).unwrap();
 
@@ -118,6 +128,7 @@ fn test_lock_file_created() {
     let config = BoxliteOptions {
         home_dir: temp_dir.path().to_path_buf(),
         image_registries: vec![],
+        ..Default::default()
     };
     let _runtime = BoxliteRuntime::new(config).unwrap();
 
@@ -133,6 +144,7 @@ fn test_lock_survives_short_operations() {
     let config1 = BoxliteOptions {
         home_dir: temp_dir.path().to_path_buf(),
         image_registries: vec![],
+        ..Default::default()
     };
     let runtime = BoxliteRuntime::new(config1).unwrap();
 
@@ -143,9 +155,681 @@ fn test_lock_survives_short_operations() {
     let config2 = BoxliteOptions {
         home_dir: temp_dir.path().to_path_buf(),
         image_registries: vec![],
+        ..Default::default()
     };
     let result = BoxliteRuntime::new(config2);
     assert!(result.is_err());
 
     drop(runtime);
 }
+
+#[tokio::test]
+async fn test_resource_ceiling_rejects_excess_cpus() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let config = BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        max_cpus: Some(2),
+        ..Default::default()
+    };
+    let runtime = BoxliteRuntime::new(config).unwrap();
+
+    // Requesting more than the ceiling fails
+    let result = runtime
+        .create(
+            BoxOptions {
+                cpus: Some(4),
+                auto_remove: false,
+                ..Default::default()
+            },
+            None,
+        )
+        .await;
+    assert!(matches!(result, Err(BoxliteError::InvalidArgument(_))));
+
+    // Requesting exactly the ceiling succeeds
+    let result = runtime
+        .create(
+            BoxOptions {
+                cpus: Some(2),
+                auto_remove: false,
+                ..Default::default()
+            },
+            None,
+        )
+        .await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_rename_box() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let options = || BoxOptions {
+        auto_remove: false,
+        ..Default::default()
+    };
+    let handle = runtime
+        .create(options(), Some("old-name".to_string()))
+        .await
+        .unwrap();
+    let id = handle.id().clone();
+    runtime
+        .create(options(), Some("other".to_string()))
+        .await
+        .unwrap();
+
+    // Taken names are rejected
+    let result = runtime.rename("old-name", "other").await;
+    assert!(matches!(result, Err(BoxliteError::InvalidArgument(_))));
+
+    runtime.rename("old-name", "new-name").await.unwrap();
+
+    let renamed = runtime.get("new-name").await.unwrap().unwrap();
+    assert_eq!(renamed.id(), &id);
+    assert_eq!(renamed.name(), Some("new-name"));
+    assert!(runtime.get("old-name").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_list_info_filtered_by_labels() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let labeled = |pairs: &[(&str, &str)]| BoxOptions {
+        auto_remove: false,
+        labels: pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+        ..Default::default()
+    };
+    let selector = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    };
+
+    let infra_a = runtime
+        .create(labeled(&[("team", "infra"), ("env", "prod")]), None)
+        .await
+        .unwrap();
+    let infra_b = runtime
+        .create(labeled(&[("team", "infra"), ("env", "dev")]), None)
+        .await
+        .unwrap();
+    runtime
+        .create(labeled(&[("team", "web")]), None)
+        .await
+        .unwrap();
+
+    let mut ids: Vec<_> = runtime
+        .list_info_filtered(&selector(&[("team", "infra")]))
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|info| info.id.to_string())
+        .collect();
+    ids.sort();
+    let mut expected = vec![infra_a.id().to_string(), infra_b.id().to_string()];
+    expected.sort();
+    assert_eq!(ids, expected);
+
+    // All labels must match
+    let prod = runtime
+        .list_info_filtered(&selector(&[("team", "infra"), ("env", "prod")]))
+        .await
+        .unwrap();
+    assert_eq!(prod.len(), 1);
+    assert_eq!(&prod[0].id, infra_a.id());
+    assert_eq!(prod[0].labels.get("env").map(String::as_str), Some("prod"));
+
+    // No selector returns every box
+    assert_eq!(runtime.list_info_filtered(&[]).await.unwrap().len(), 3);
+    assert!(
+        runtime
+            .list_info_filtered(&selector(&[("team", "data")]))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_list_by_status() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let options = || BoxOptions {
+        auto_remove: false,
+        ..Default::default()
+    };
+    let first = runtime.create(options(), None).await.unwrap();
+    let second = runtime.create(options(), None).await.unwrap();
+
+    let configured = runtime
+        .list_by_status(&[BoxStatus::Configured])
+        .await
+        .unwrap();
+    assert_eq!(configured.len(), 2);
+    // Newest first
+    assert_eq!(&configured[0].id, second.id());
+    assert_eq!(&configured[1].id, first.id());
+
+    assert!(
+        runtime
+            .list_by_status(&[BoxStatus::Running, BoxStatus::Stopped])
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // Persisted boxes are found after the cached handles are dropped
+    drop((first, second));
+    let configured = runtime
+        .list_by_status(&[BoxStatus::Configured, BoxStatus::Running])
+        .await
+        .unwrap();
+    assert_eq!(configured.len(), 2);
+}
+
+#[tokio::test]
+async fn test_list_created_between() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let before = chrono::Utc::now() - chrono::Duration::seconds(1);
+    let litebox = runtime
+        .create(
+            BoxOptions {
+                auto_remove: false,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let after = chrono::Utc::now() + chrono::Duration::seconds(1);
+
+    let in_window = runtime.list_created_between(before, after).await.unwrap();
+    assert_eq!(in_window.len(), 1);
+    assert_eq!(&in_window[0].id, litebox.id());
+
+    let earlier = runtime
+        .list_created_between(before - chrono::Duration::hours(1), before)
+        .await
+        .unwrap();
+    assert!(earlier.is_empty());
+
+    let err = runtime
+        .list_created_between(after, before)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BoxliteError::InvalidArgument(_)));
+}
+
+#[tokio::test]
+async fn test_reap_expired_removes_only_expired_boxes() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let options = |ttl| BoxOptions {
+        auto_remove: false,
+        ttl,
+        ..Default::default()
+    };
+    let expiring = runtime
+        .create(options(Some(Duration::from_millis(10))), None)
+        .await
+        .unwrap();
+    let long_lived = runtime
+        .create(options(Some(Duration::from_secs(3600))), None)
+        .await
+        .unwrap();
+    let forever = runtime.create(options(None), None).await.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let reaped = runtime.reap_expired().await.unwrap();
+
+    assert_eq!(reaped, vec![expiring.id().clone()]);
+    assert!(!runtime.exists(expiring.id().as_str()).await.unwrap());
+    assert!(runtime.exists(long_lived.id().as_str()).await.unwrap());
+    assert!(runtime.exists(forever.id().as_str()).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_get_by_container_id() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let litebox = runtime
+        .create(
+            BoxOptions {
+                auto_remove: false,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let box_id = litebox.id().clone();
+    let container_id = litebox.info().container_id;
+
+    let found = runtime
+        .get_by_container_id(container_id.as_str())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.id(), &box_id);
+
+    // Resolved from the database once no handle is cached
+    drop((litebox, found));
+    let found = runtime
+        .get_by_container_id(container_id.as_str())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.id(), &box_id);
+
+    let unknown = "0".repeat(64);
+    assert!(
+        runtime
+            .get_by_container_id(&unknown)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(matches!(
+        runtime.get_by_container_id("not-a-container-id").await,
+        Err(BoxliteError::InvalidArgument(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_health_of_box_that_is_not_running() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let litebox = runtime
+        .create(
+            BoxOptions {
+                auto_remove: false,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(litebox.info().health, None);
+
+    assert_eq!(litebox.health().await.unwrap(), HealthStatus::NotRunning);
+
+    // The probe neither starts the box nor connects to it
+    let info = litebox.info();
+    assert_eq!(info.status, BoxStatus::Configured);
+    assert_eq!(info.health, Some(HealthStatus::NotRunning));
+}
+
+#[tokio::test]
+async fn test_signal_box_that_is_not_running() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let litebox = runtime
+        .create(
+            BoxOptions {
+                auto_remove: false,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let err = litebox.signal(libc::SIGTERM).await.unwrap_err();
+    assert!(matches!(err, BoxliteError::InvalidState(_)), "{:?}", err);
+    // Signalling never starts the box
+    assert_eq!(litebox.info().status, BoxStatus::Configured);
+
+    // Boxes that are not running are skipped by the broadcast
+    assert_eq!(runtime.signal_running_boxes(libc::SIGTERM).await, 0);
+}
+
+#[tokio::test]
+async fn test_close_stops_boxes_and_releases_home() {
+    let temp_dir = TempDir::new().unwrap();
+    let options = || BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    };
+    let runtime = BoxliteRuntime::new(options()).unwrap();
+
+    let box_options = || BoxOptions {
+        auto_remove: false,
+        ..Default::default()
+    };
+    let first = runtime.create(box_options(), None).await.unwrap();
+    let second = runtime.create(box_options(), None).await.unwrap();
+
+    // Handles outlive the runtime value; the lock is released anyway
+    runtime.close().await.unwrap();
+
+    let reopened = BoxliteRuntime::new(options()).unwrap();
+    for litebox in [&first, &second] {
+        let info = reopened
+            .get_info(litebox.id().as_str())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.status, BoxStatus::Stopped);
+    }
+}
+
+#[tokio::test]
+async fn test_stop_all_and_remove_all() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        let litebox = runtime
+            .create(
+                BoxOptions {
+                    auto_remove: false,
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        ids.push(litebox.id().clone());
+    }
+
+    let results = runtime.stop_all().await.unwrap();
+    assert_eq!(results.len(), 3);
+    for (box_id, result) in &results {
+        assert!(result.is_ok(), "stop {} failed: {:?}", box_id, result);
+        assert!(ids.contains(box_id));
+    }
+    for id in &ids {
+        let info = runtime.get_info(id.as_str()).await.unwrap().unwrap();
+        assert_eq!(info.status, BoxStatus::Stopped);
+    }
+
+    // Already-stopped boxes are skipped
+    assert!(runtime.stop_all().await.unwrap().is_empty());
+
+    let results = runtime.remove_all(false).await.unwrap();
+    assert_eq!(results.len(), 3);
+    assert!(results.iter().all(|(_, result)| result.is_ok()));
+    assert!(runtime.list_info().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_read_logs_from_box_dir() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let handle = runtime.create(BoxOptions::default(), None).await.unwrap();
+    let log_path = handle.log_path();
+    assert_eq!(
+        log_path,
+        temp_dir
+            .path()
+            .join("boxes")
+            .join(handle.id().as_str())
+            .join("console.log")
+    );
+
+    // Never started: no output yet
+    assert_eq!(handle.read_logs(None).await.unwrap(), "");
+
+    std::fs::create_dir_all(log_path.parent().unwrap()).unwrap();
+    std::fs::write(&log_path, "boot\ninit\nready\n").unwrap();
+    assert_eq!(handle.read_logs(None).await.unwrap(), "boot\ninit\nready\n");
+    assert_eq!(handle.read_logs(Some(2)).await.unwrap(), "init\nready\n");
+}
+
+#[tokio::test]
+async fn test_get_or_create_is_idempotent() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let (first, created) = runtime
+        .get_or_create(BoxOptions::default(), "worker")
+        .await
+        .unwrap();
+    assert!(created);
+
+    let (second, created) = runtime
+        .get_or_create(BoxOptions::default(), "worker")
+        .await
+        .unwrap();
+    assert!(!created);
+    assert_eq!(first.id(), second.id());
+    assert_eq!(runtime.list_info().await.unwrap().len(), 1);
+
+    let different = BoxOptions {
+        cpus: Some(4),
+        ..Default::default()
+    };
+    let err = runtime
+        .get_or_create(different, "worker")
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(err, BoxliteError::InvalidState(_)), "got: {err}");
+}
+
+#[tokio::test]
+async fn test_get_or_create_ignores_id_prefix_matches() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let unnamed = runtime.create(BoxOptions::default(), None).await.unwrap();
+    let prefix = &unnamed.id().as_str()[..8];
+
+    let (named, created) = runtime
+        .get_or_create(BoxOptions::default(), prefix)
+        .await
+        .unwrap();
+    assert!(created);
+    assert_ne!(named.id(), unnamed.id());
+    assert_eq!(named.name(), Some(prefix));
+}
+
+#[tokio::test]
+async fn test_clone_box_copies_options() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let options = BoxOptions {
+        cpus: Some(2),
+        memory_mib: Some(768),
+        rootfs: RootfsSpec::Image("python:alpine".to_string()),
+        ..Default::default()
+    };
+    let source = runtime
+        .create(options, Some("template".to_string()))
+        .await
+        .unwrap();
+
+    let clone = runtime
+        .clone_box("template", Some("copy".to_string()))
+        .await
+        .unwrap();
+    assert_ne!(clone.id(), source.id());
+    assert_eq!(clone.name(), Some("copy"));
+    assert_ne!(clone.config().box_home, source.config().box_home);
+    assert_ne!(clone.config().container.id, source.config().container.id);
+
+    let cloned = &clone.config().options;
+    assert_eq!(cloned.cpus, Some(2));
+    assert_eq!(cloned.memory_mib, Some(768));
+    assert!(matches!(&cloned.rootfs, RootfsSpec::Image(image) if image == "python:alpine"));
+
+    let err = runtime.clone_box("missing", None).await.err().unwrap();
+    assert!(matches!(err, BoxliteError::NotFound(_)), "got: {err}");
+}
+
+#[tokio::test]
+async fn test_export_rootfs_of_stopped_box() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let handle = runtime.create(BoxOptions::default(), None).await.unwrap();
+    let out = temp_dir.path().join("rootfs.img");
+
+    // Never started: there is no disk to export yet
+    let err = handle.export_rootfs(&out).await.unwrap_err();
+    assert!(matches!(err, BoxliteError::InvalidState(_)), "got: {err}");
+
+    // Stand in for the disk a previous run left behind
+    let box_dir = temp_dir.path().join("boxes").join(handle.id().as_str());
+    std::fs::create_dir_all(&box_dir).unwrap();
+    std::fs::write(box_dir.join("disk.qcow2"), vec![0xab; 4096]).unwrap();
+
+    handle.export_rootfs(&out).await.unwrap();
+    let exported = std::fs::metadata(&out).unwrap();
+    assert!(exported.len() > 0);
+}
+
+#[tokio::test]
+async fn test_create_with_directory_rootfs() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: temp_dir.path().join("home"),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let rootfs = temp_dir.path().join("rootfs");
+    std::fs::create_dir_all(rootfs.join("bin")).unwrap();
+    let handle = runtime
+        .create(
+            BoxOptions {
+                rootfs: RootfsSpec::Directory(rootfs.clone()),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(
+        matches!(&handle.config().options.rootfs, RootfsSpec::Directory(dir) if *dir == rootfs)
+    );
+
+    // Not a rootfs: no /bin or /sbin
+    let empty = temp_dir.path().join("empty");
+    std::fs::create_dir_all(&empty).unwrap();
+    let err = runtime
+        .create(
+            BoxOptions {
+                rootfs: RootfsSpec::Directory(empty),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .err()
+        .unwrap();
+    assert!(
+        matches!(err, BoxliteError::InvalidArgument(_)),
+        "got: {err}"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_create_same_name_single_winner() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = std::sync::Arc::new(
+        BoxliteRuntime::new(BoxliteOptions {
+            home_dir: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap(),
+    );
+
+    let tasks: Vec<_> = (0..8)
+        .map(|_| {
+            let runtime = runtime.clone();
+            tokio::spawn(async move {
+                runtime
+                    .create(BoxOptions::default(), Some("contended".to_string()))
+                    .await
+            })
+        })
+        .collect();
+
+    let mut created = Vec::new();
+    for task in tasks {
+        match task.await.unwrap() {
+            Ok(litebox) => created.push(litebox),
+            Err(err) => assert!(
+                matches!(err, BoxliteError::InvalidArgument(_)),
+                "got: {err}"
+            ),
+        }
+    }
+
+    assert_eq!(created.len(), 1);
+    assert_eq!(runtime.list_info().await.unwrap().len(), 1);
+}
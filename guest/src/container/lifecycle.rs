@@ -6,9 +6,10 @@
 use super::command::ContainerCommand;
 use super::spec::UserMount;
 use super::stdio::ContainerStdio;
+use super::user::resolve_user;
 use super::{kill, start};
 use crate::layout::GuestLayout;
-use boxlite_shared::errors::BoxliteResult;
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use libcontainer::container::Container as LibContainer;
 use libcontainer::signal::Signal;
 use std::collections::HashMap;
@@ -85,6 +86,7 @@ impl Container {
         entrypoint: Vec<String>,
         env: Vec<String>,
         workdir: impl AsRef<Path>,
+        user: &str,
         user_mounts: Vec<UserMount>,
     ) -> BoxliteResult<Self> {
         let rootfs = rootfs.as_ref();
@@ -95,6 +97,7 @@ impl Container {
 
         // Validate inputs early
         start::validate_container_inputs(rootfs, &entrypoint, workdir)?;
+        let user = resolve_user(rootfs, user)?;
 
         // Parse existing env into map (KEY=VALUE)
         let mut env_map: HashMap<String, String> = HashMap::new();
@@ -117,6 +120,7 @@ impl Container {
             &entrypoint,
             &env,
             workdir,
+            user,
             &layout.containers_dir(),
             &user_mounts,
         )?;
@@ -348,6 +352,27 @@ impl Container {
         Ok(())
     }
 
+    /// Send `signal` to the container's init process.
+    pub fn signal(&self, signal: i32) -> BoxliteResult<()> {
+        let signal = Signal::try_from(signal).map_err(|_| {
+            BoxliteError::InvalidArgument(format!("Invalid signal number: {}", signal))
+        })?;
+
+        let mut container = LibContainer::load(self.container_state_path()).map_err(|e| {
+            BoxliteError::InvalidState(format!("Container {} not found: {}", self.id, e))
+        })?;
+        if !container.can_kill() {
+            return Err(BoxliteError::InvalidState(format!(
+                "Container {} is not running",
+                self.id
+            )));
+        }
+
+        container
+            .kill(signal, false)
+            .map_err(|e| BoxliteError::Internal(format!("Failed to signal container: {}", e)))
+    }
+
     fn container_state_path(&self) -> PathBuf {
         self.state_root.join(&self.id)
     }
@@ -77,6 +77,8 @@ mod spec;
 mod start;
 #[cfg(target_os = "linux")]
 mod stdio;
+#[cfg(target_os = "linux")]
+mod user;
 
 #[cfg(target_os = "linux")]
 pub use lifecycle::Container;
@@ -3,6 +3,7 @@
 //! Creates OCI-compliant runtime specifications following the runtime-spec standard.
 
 use super::capabilities::all_capabilities;
+use super::user::ContainerUser;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use std::path::Path;
 
@@ -31,7 +32,7 @@ pub struct UserMount {
 /// - Default capabilities (matching runc defaults)
 /// - Standard namespaces (pid, ipc, uts, mount)
 /// - UID/GID mappings for user namespace
-/// - Root user (uid=0, gid=0)
+/// - The given process user (root unless the image sets `User`)
 /// - Resource limits (rlimits)
 /// - No new privileges disabled (allows sudo)
 ///
@@ -39,12 +40,14 @@ pub struct UserMount {
 /// Since we're inside a VM with single-tenant isolation, cgroup resource limits
 /// provide minimal benefit. See comments in build_default_namespaces() and
 /// build_standard_mounts() to re-enable if needed.
+#[allow(clippy::too_many_arguments)]
 pub fn create_oci_spec(
     container_id: &str,
     rootfs: &str,
     entrypoint: &[String],
     env: &[String],
     workdir: &str,
+    user: ContainerUser,
     bundle_path: &Path,
     user_mounts: &[UserMount],
 ) -> BoxliteResult<Spec> {
@@ -83,7 +86,7 @@ pub fn create_oci_spec(
         );
     }
 
-    let process = build_process_spec(entrypoint, env, workdir, caps)?;
+    let process = build_process_spec(entrypoint, env, workdir, user, caps)?;
     let root = build_root_spec(rootfs)?;
     let linux = build_linux_spec(container_id, namespaces)?;
 
@@ -148,11 +151,12 @@ fn build_process_spec(
     entrypoint: &[String],
     env: &[String],
     workdir: &str,
+    user: ContainerUser,
     caps: oci_spec::runtime::LinuxCapabilities,
 ) -> BoxliteResult<oci_spec::runtime::Process> {
     let user = UserBuilder::default()
-        .uid(0u32)
-        .gid(0u32)
+        .uid(user.uid)
+        .gid(user.gid)
         .build()
         .map_err(|e| BoxliteError::Internal(format!("Failed to build user spec: {}", e)))?;
 
@@ -4,6 +4,7 @@
 //! Separated from container.rs to group by lifecycle phase (Prepare → Execute).
 
 use super::spec;
+use super::user::ContainerUser;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use libcontainer::container::builder::ContainerBuilder;
 use libcontainer::container::Container as LibContainer;
@@ -87,12 +88,14 @@ pub(crate) fn create_container_etc_files(
 }
 
 /// Create OCI bundle (config.json + rootfs reference)
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn create_oci_bundle(
     container_id: &str,
     rootfs: &Path,
     entrypoint: &[String],
     env: &[String],
     workdir: &Path,
+    user: ContainerUser,
     bundle_root: &Path,
     user_mounts: &[spec::UserMount],
 ) -> BoxliteResult<PathBuf> {
@@ -120,6 +123,7 @@ pub(crate) fn create_oci_bundle(
         workdir
             .to_str()
             .ok_or_else(|| BoxliteError::Internal("Invalid workdir path".to_string()))?,
+        user,
         &bundle_path,
         user_mounts,
     )?;
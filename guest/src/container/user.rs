@@ -0,0 +1,142 @@
+//! Container user resolution
+//!
+//! Resolves an OCI image `User` string (`user`, `uid`, `user:group`,
+//! `uid:gid`, ...) to numeric IDs using the container rootfs's
+//! `/etc/passwd` and `/etc/group`, like Docker does.
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use std::path::Path;
+
+/// Numeric identity the container process runs as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContainerUser {
+    pub uid: u32,
+    pub gid: u32,
+}
+
+impl ContainerUser {
+    pub const ROOT: Self = Self { uid: 0, gid: 0 };
+}
+
+/// Resolve `user` against the rootfs at `rootfs`.
+///
+/// An empty string means root. A numeric user that has no passwd entry is
+/// allowed and gets gid 0 unless a group is given; a named user or group
+/// must exist.
+pub fn resolve_user(rootfs: &Path, user: &str) -> BoxliteResult<ContainerUser> {
+    if user.is_empty() {
+        return Ok(ContainerUser::ROOT);
+    }
+    let passwd = std::fs::read_to_string(rootfs.join("etc/passwd")).unwrap_or_default();
+    let group = std::fs::read_to_string(rootfs.join("etc/group")).unwrap_or_default();
+    resolve_user_from(&passwd, &group, user)
+}
+
+/// Resolve `user` given the contents of `/etc/passwd` and `/etc/group`.
+fn resolve_user_from(passwd: &str, groups: &str, user: &str) -> BoxliteResult<ContainerUser> {
+    if user.is_empty() {
+        return Ok(ContainerUser::ROOT);
+    }
+
+    let (user_part, group_part) = match user.split_once(':') {
+        Some((u, g)) => (u, Some(g)),
+        None => (user, None),
+    };
+
+    let (uid, default_gid) = match (user_part.parse::<u32>(), passwd_lookup(passwd, user_part)) {
+        (Ok(uid), entry) => (uid, entry.map(|(_, gid)| gid).unwrap_or(0)),
+        (Err(_), Some((uid, gid))) => (uid, gid),
+        (Err(_), None) => {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "user '{}' not found in container /etc/passwd",
+                user_part
+            )));
+        }
+    };
+
+    let gid = match group_part {
+        None => default_gid,
+        Some(group) => match group.parse::<u32>() {
+            Ok(gid) => gid,
+            Err(_) => group_lookup(groups, group).ok_or_else(|| {
+                BoxliteError::InvalidArgument(format!(
+                    "group '{}' not found in container /etc/group",
+                    group
+                ))
+            })?,
+        },
+    };
+
+    Ok(ContainerUser { uid, gid })
+}
+
+/// Find `(uid, gid)` for a user name or uid in passwd-format `content`.
+fn passwd_lookup(content: &str, user: &str) -> Option<(u32, u32)> {
+    content.lines().find_map(|line| {
+        let fields: Vec<&str> = line.split(':').collect();
+        if fields.len() < 4 {
+            return None;
+        }
+        let uid = fields[2].parse().ok()?;
+        let gid = fields[3].parse().ok()?;
+        (fields[0] == user || fields[2] == user).then_some((uid, gid))
+    })
+}
+
+/// Find the gid for a group name in group-format `content`.
+fn group_lookup(content: &str, group: &str) -> Option<u32> {
+    content.lines().find_map(|line| {
+        let fields: Vec<&str> = line.split(':').collect();
+        if fields.len() < 3 || fields[0] != group {
+            return None;
+        }
+        fields[2].parse().ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSWD: &str =
+        "root:x:0:0:root:/root:/bin/sh\nnginx:x:101:101:nginx:/nonexistent:/bin/false\n";
+    const GROUP: &str = "root:x:0:\nnginx:x:101:\nwww-data:x:33:\n";
+
+    fn resolve(user: &str) -> BoxliteResult<ContainerUser> {
+        resolve_user_from(PASSWD, GROUP, user)
+    }
+
+    #[test]
+    fn test_resolve_user_forms() {
+        assert_eq!(resolve("").unwrap(), ContainerUser::ROOT);
+        assert_eq!(
+            resolve("nginx").unwrap(),
+            ContainerUser { uid: 101, gid: 101 }
+        );
+        assert_eq!(
+            resolve("101").unwrap(),
+            ContainerUser { uid: 101, gid: 101 }
+        );
+        assert_eq!(
+            resolve("nginx:www-data").unwrap(),
+            ContainerUser { uid: 101, gid: 33 }
+        );
+        assert_eq!(
+            resolve("1000:1000").unwrap(),
+            ContainerUser {
+                uid: 1000,
+                gid: 1000
+            }
+        );
+        assert_eq!(
+            resolve("1000").unwrap(),
+            ContainerUser { uid: 1000, gid: 0 }
+        );
+    }
+
+    #[test]
+    fn test_resolve_unknown_names_fail() {
+        assert!(resolve("nobody").is_err());
+        assert!(resolve("nginx:missing").is_err());
+    }
+}
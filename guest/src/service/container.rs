@@ -8,7 +8,8 @@ use std::path::Path;
 use crate::service::server::GuestServer;
 use boxlite_shared::{
     container_init_response, rootfs_init, Container as ContainerService, ContainerInitError,
-    ContainerInitRequest, ContainerInitResponse, ContainerInitSuccess, Filesystem, RootfsInit,
+    ContainerInitRequest, ContainerInitResponse, ContainerInitSuccess, ContainerSignalRequest,
+    ContainerSignalResponse, Filesystem, RootfsInit,
 };
 use nix::mount::{mount, MsFlags};
 use tonic::{Request, Response, Status};
@@ -208,6 +209,7 @@ impl ContainerService for GuestServer {
         debug!(
             entrypoint = ?config.entrypoint,
             workdir = %config.workdir,
+            user = %config.user,
             env_count = config.env.len(),
             shared_rootfs = %shared_rootfs.display(),
             bundle_rootfs = %bundle_rootfs.display(),
@@ -230,6 +232,7 @@ impl ContainerService for GuestServer {
             config.entrypoint,
             config.env,
             &config.workdir,
+            &config.user,
             user_mounts,
         ) {
             Ok(container) => {
@@ -281,4 +284,38 @@ impl ContainerService for GuestServer {
             }
         }
     }
+
+    async fn signal(
+        &self,
+        request: Request<ContainerSignalRequest>,
+    ) -> Result<Response<ContainerSignalResponse>, Status> {
+        let req = request.into_inner();
+        info!(
+            container_id = %req.container_id,
+            signal = req.signal,
+            "container signal request"
+        );
+
+        let container = self
+            .containers
+            .lock()
+            .await
+            .get(&req.container_id)
+            .cloned()
+            .ok_or_else(|| {
+                Status::not_found(format!("Container not found: {}", req.container_id))
+            })?;
+
+        let result = container.lock().await.signal(req.signal);
+        Ok(Response::new(match result {
+            Ok(()) => ContainerSignalResponse {
+                success: true,
+                error: None,
+            },
+            Err(e) => ContainerSignalResponse {
+                success: false,
+                error: Some(e.to_string()),
+            },
+        }))
+    }
 }
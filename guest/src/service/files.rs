@@ -0,0 +1,338 @@
+//! Files service implementation.
+//!
+//! Streams file contents between the host and a container's rootfs
+//! (Upload, Download RPCs).
+
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Component, Path, PathBuf};
+use std::pin::Pin;
+
+use crate::service::server::GuestServer;
+use boxlite_shared::{DownloadRequest, FileChunk, Files as FilesService, UploadResponse};
+use nix::errno::Errno;
+use nix::fcntl::{openat2, OFlag, OpenHow, ResolveFlag};
+use nix::sys::stat::{mkdirat, Mode};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status, Streaming};
+use tracing::{debug, info};
+
+/// Size of each data chunk sent to the host.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Map an absolute container path to a path relative to the container's rootfs.
+///
+/// Rejects relative paths and `..` components. Symlinks are left for
+/// [`open_in_root`] to resolve.
+fn resolve_container_path(path: &str) -> Result<PathBuf, String> {
+    let path = Path::new(path);
+    if !path.is_absolute() {
+        return Err(format!(
+            "Container path must be absolute: {}",
+            path.display()
+        ));
+    }
+
+    let mut resolved = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::RootDir | Component::CurDir => {}
+            Component::Normal(part) => resolved.push(part),
+            Component::ParentDir | Component::Prefix(_) => {
+                return Err(format!(
+                    "Container path must not contain '..': {}",
+                    path.display()
+                ));
+            }
+        }
+    }
+
+    if resolved.as_os_str().is_empty() {
+        return Err("Container path must name a file".to_string());
+    }
+    Ok(resolved)
+}
+
+/// Open `rel` (from [`resolve_container_path`]) inside `rootfs`.
+///
+/// Resolution uses `RESOLVE_IN_ROOT`, so symlinks in the container, absolute
+/// or relative, resolve as they would for the container and never lead out
+/// of `rootfs`. With `create_parents`, missing parent directories are made
+/// one component at a time the same way.
+fn open_in_root(
+    rootfs: &Path,
+    rel: &Path,
+    flags: OFlag,
+    create_parents: bool,
+) -> std::io::Result<std::fs::File> {
+    let root = openat2_in_root(
+        None,
+        rootfs,
+        OFlag::O_PATH | OFlag::O_DIRECTORY,
+        ResolveFlag::empty(),
+    )?;
+
+    if create_parents {
+        let mut prefix = PathBuf::new();
+        for part in rel.parent().into_iter().flat_map(Path::iter) {
+            let dir = if prefix.as_os_str().is_empty() {
+                root.try_clone()?
+            } else {
+                openat2_in_root(
+                    Some(&root),
+                    &prefix,
+                    OFlag::O_PATH | OFlag::O_DIRECTORY,
+                    ResolveFlag::RESOLVE_IN_ROOT,
+                )?
+            };
+            match mkdirat(Some(dir.as_raw_fd()), part, Mode::from_bits_truncate(0o755)) {
+                Ok(()) | Err(Errno::EEXIST) => {}
+                Err(e) => return Err(e.into()),
+            }
+            prefix.push(part);
+        }
+    }
+
+    openat2_in_root(Some(&root), rel, flags, ResolveFlag::RESOLVE_IN_ROOT).map(Into::into)
+}
+
+fn openat2_in_root(
+    dir: Option<&OwnedFd>,
+    path: &Path,
+    flags: OFlag,
+    resolve: ResolveFlag,
+) -> std::io::Result<OwnedFd> {
+    let mut how = OpenHow::new()
+        .flags(flags | OFlag::O_CLOEXEC)
+        .resolve(resolve | ResolveFlag::RESOLVE_NO_MAGICLINKS);
+    // openat2 rejects a mode unless it may create the file
+    if flags.contains(OFlag::O_CREAT) {
+        how = how.mode(Mode::from_bits_truncate(0o644));
+    }
+    let dirfd = dir.map_or(nix::libc::AT_FDCWD, |fd| fd.as_raw_fd());
+    let fd = openat2(dirfd, path, how)?;
+    // SAFETY: openat2 just returned this descriptor and nothing else owns it
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+/// Run [`open_in_root`] on the blocking pool.
+async fn open_in_root_async(
+    rootfs: PathBuf,
+    rel: PathBuf,
+    flags: OFlag,
+    create_parents: bool,
+) -> std::io::Result<tokio::fs::File> {
+    tokio::task::spawn_blocking(move || open_in_root(&rootfs, &rel, flags, create_parents))
+        .await
+        .map_err(std::io::Error::other)?
+        .map(tokio::fs::File::from_std)
+}
+
+fn io_status(context: &str, path: &Path, e: std::io::Error) -> Status {
+    let message = format!("{} {}: {}", context, path.display(), e);
+    match e.kind() {
+        std::io::ErrorKind::NotFound => Status::not_found(message),
+        std::io::ErrorKind::PermissionDenied => Status::permission_denied(message),
+        _ => Status::internal(message),
+    }
+}
+
+impl GuestServer {
+    fn container_rootfs(&self, container_id: &str) -> Result<PathBuf, String> {
+        if container_id.is_empty() {
+            return Err("Missing container_id".to_string());
+        }
+        Ok(self.layout.container(container_id).rootfs_dir())
+    }
+}
+
+#[tonic::async_trait]
+impl FilesService for GuestServer {
+    async fn upload(
+        &self,
+        request: Request<Streaming<FileChunk>>,
+    ) -> Result<Response<UploadResponse>, Status> {
+        let mut stream = request.into_inner();
+
+        // First chunk carries the destination metadata
+        let first = stream
+            .message()
+            .await?
+            .ok_or_else(|| Status::invalid_argument("Empty upload stream"))?;
+        let rootfs = self
+            .container_rootfs(&first.container_id)
+            .map_err(Status::invalid_argument)?;
+        let rel = resolve_container_path(&first.path).map_err(Status::invalid_argument)?;
+        let target = rootfs.join(&rel);
+        info!(path = %first.path, mode = format!("{:o}", first.mode), "Receiving file upload");
+
+        let mut file = open_in_root_async(
+            rootfs,
+            rel,
+            OFlag::O_WRONLY | OFlag::O_CREAT | OFlag::O_TRUNC,
+            true,
+        )
+        .await
+        .map_err(|e| io_status("Failed to create", &target, e))?;
+
+        let mut written = first.data.len() as u64;
+        file.write_all(&first.data)
+            .await
+            .map_err(|e| io_status("Failed to write", &target, e))?;
+
+        while let Some(chunk) = stream.message().await? {
+            file.write_all(&chunk.data)
+                .await
+                .map_err(|e| io_status("Failed to write", &target, e))?;
+            written += chunk.data.len() as u64;
+        }
+
+        file.flush()
+            .await
+            .map_err(|e| io_status("Failed to flush", &target, e))?;
+        file.set_permissions(std::fs::Permissions::from_mode(first.mode & 0o7777))
+            .await
+            .map_err(|e| io_status("Failed to set mode on", &target, e))?;
+
+        debug!(path = %first.path, bytes = written, "File upload complete");
+        Ok(Response::new(UploadResponse {
+            bytes_written: written,
+        }))
+    }
+
+    type DownloadStream = Pin<Box<dyn Stream<Item = Result<FileChunk, Status>> + Send>>;
+
+    async fn download(
+        &self,
+        request: Request<DownloadRequest>,
+    ) -> Result<Response<Self::DownloadStream>, Status> {
+        let req = request.into_inner();
+        let rootfs = self
+            .container_rootfs(&req.container_id)
+            .map_err(Status::invalid_argument)?;
+        let rel = resolve_container_path(&req.path).map_err(Status::invalid_argument)?;
+        let source = rootfs.join(&rel);
+        info!(path = %req.path, "Sending file download");
+
+        let mut file = open_in_root_async(rootfs, rel, OFlag::O_RDONLY, false)
+            .await
+            .map_err(|e| io_status("Failed to open", &source, e))?;
+        let metadata = file
+            .metadata()
+            .await
+            .map_err(|e| io_status("Failed to stat", &source, e))?;
+        if !metadata.is_file() {
+            return Err(Status::invalid_argument(format!(
+                "Not a regular file: {}",
+                req.path
+            )));
+        }
+        let mode = metadata.permissions().mode() & 0o7777;
+
+        let (tx, rx) = mpsc::channel::<Result<FileChunk, Status>>(4);
+        tokio::spawn(async move {
+            let mut buf = vec![0u8; CHUNK_SIZE];
+            let mut first = true;
+            loop {
+                let n = match file.read(&mut buf).await {
+                    Ok(n) => n,
+                    Err(e) => {
+                        let _ = tx.send(Err(io_status("Failed to read", &source, e))).await;
+                        return;
+                    }
+                };
+                // Always send at least one chunk so the host learns the mode
+                if n == 0 && !first {
+                    return;
+                }
+                let chunk = FileChunk {
+                    container_id: String::new(),
+                    path: String::new(),
+                    mode: if first { mode } else { 0 },
+                    data: buf[..n].to_vec(),
+                };
+                first = false;
+                if tx.send(Ok(chunk)).await.is_err() || n == 0 {
+                    return;
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_container_path() {
+        assert_eq!(
+            resolve_container_path("/etc/app/config.toml").unwrap(),
+            Path::new("etc/app/config.toml")
+        );
+        assert_eq!(
+            resolve_container_path("/./tmp/out").unwrap(),
+            Path::new("tmp/out")
+        );
+    }
+
+    #[test]
+    fn test_resolve_container_path_rejects_escape() {
+        assert!(resolve_container_path("/../../etc/shadow").is_err());
+        assert!(resolve_container_path("relative/path").is_err());
+        assert!(resolve_container_path("/").is_err());
+    }
+
+    #[test]
+    fn test_open_in_root_keeps_symlinks_inside_rootfs() {
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret"), "host").unwrap();
+        let rootfs = tempfile::tempdir().unwrap();
+        std::fs::create_dir(rootfs.path().join("etc")).unwrap();
+        std::fs::write(rootfs.path().join("etc/secret"), "container").unwrap();
+
+        // Absolute and `..` links resolve against the rootfs, not the guest
+        std::os::unix::fs::symlink(outside.path(), rootfs.path().join("abs")).unwrap();
+        std::os::unix::fs::symlink("../../../../etc", rootfs.path().join("up")).unwrap();
+
+        let read = |rel: &str| {
+            let mut file =
+                open_in_root(rootfs.path(), Path::new(rel), OFlag::O_RDONLY, false).unwrap();
+            let mut content = String::new();
+            std::io::Read::read_to_string(&mut file, &mut content).unwrap();
+            content
+        };
+        assert_eq!(read("up/secret"), "container");
+        assert!(open_in_root(
+            rootfs.path(),
+            Path::new("abs/secret"),
+            OFlag::O_RDONLY,
+            false
+        )
+        .is_err());
+
+        // Creating through the link can't reach the host directory either
+        assert!(open_in_root(
+            rootfs.path(),
+            Path::new("abs/new/file"),
+            OFlag::O_WRONLY | OFlag::O_CREAT,
+            true,
+        )
+        .is_err());
+        assert!(!outside.path().join("new").exists());
+
+        open_in_root(
+            rootfs.path(),
+            Path::new("up/app/conf"),
+            OFlag::O_WRONLY | OFlag::O_CREAT,
+            true,
+        )
+        .unwrap();
+        assert!(rootfs.path().join("etc/app/conf").is_file());
+    }
+}
@@ -1,12 +1,12 @@
 //! Guest service implementation.
 //!
-//! Handles guest initialization and management (Init, Ping, Shutdown RPCs).
+//! Handles guest initialization and management (Init, Ping, Shutdown, SyncTime RPCs).
 
 use crate::service::server::GuestServer;
 use boxlite_shared::{
     guest_init_response, Guest as GuestService, GuestInitError, GuestInitRequest,
     GuestInitResponse, GuestInitSuccess, PingRequest, PingResponse, ShutdownRequest,
-    ShutdownResponse,
+    ShutdownResponse, SyncTimeRequest, SyncTimeResponse,
 };
 use tonic::{Request, Response, Status};
 use tracing::{debug, error, info};
@@ -119,4 +119,30 @@ impl GuestService for GuestServer {
         info!("Graceful shutdown complete");
         Ok(Response::new(ShutdownResponse {}))
     }
+
+    async fn sync_time(
+        &self,
+        request: Request<SyncTimeRequest>,
+    ) -> Result<Response<SyncTimeResponse>, Status> {
+        let req = request.into_inner();
+        if req.nanos >= 1_000_000_000 {
+            return Err(Status::invalid_argument(format!(
+                "nanos out of range: {}",
+                req.nanos
+            )));
+        }
+
+        let ts = nix::libc::timespec {
+            tv_sec: req.unix_seconds as nix::libc::time_t,
+            tv_nsec: req.nanos as nix::libc::c_long,
+        };
+        if unsafe { nix::libc::clock_settime(nix::libc::CLOCK_REALTIME, &ts) } != 0 {
+            let err = std::io::Error::last_os_error();
+            error!(error = %err, "Failed to set guest clock");
+            return Err(Status::internal(format!("Failed to set clock: {}", err)));
+        }
+
+        debug!(unix_seconds = req.unix_seconds, "Guest clock synced");
+        Ok(Response::new(SyncTimeResponse {}))
+    }
 }
@@ -3,9 +3,11 @@
 //! This module contains the gRPC server and service implementations:
 //! - `guest`: Guest initialization and management (Init, Ping, Shutdown RPCs)
 //! - `container`: Container lifecycle (Init RPC)
+//! - `files`: File transfer into/out of containers (Upload, Download RPCs)
 //! - `execution`: Command execution (Exec, Wait, Kill RPCs)
 
 mod container;
 pub(crate) mod exec;
+mod files;
 mod guest;
 pub(crate) mod server;
@@ -19,10 +19,11 @@ pub(crate) struct GuestInitState {
 
 /// Guest agent server.
 ///
-/// Implements three gRPC services:
+/// Implements four gRPC services:
 /// - Guest: Agent initialization and management
 /// - Container: OCI container lifecycle
 /// - Execution: Command execution with bidirectional streaming
+/// - Files: File transfer into and out of containers
 pub(crate) struct GuestServer {
     /// Guest filesystem layout
     pub layout: GuestLayout,
@@ -54,7 +55,7 @@ impl GuestServer {
     /// Run the tonic server listening on the specified transport.
     ///
     /// Binds to the specified transport (Unix, TCP, or Vsock) and serves
-    /// all three gRPC services on a single port.
+    /// all gRPC services on a single port.
     ///
     /// If `notify_uri` is provided, connects to that URI after the server
     /// is ready to serve, signaling readiness to the host.
@@ -77,7 +78,8 @@ impl GuestServer {
         let server_builder = Server::builder()
             .add_service(boxlite_shared::ContainerServer::from_arc(server.clone()))
             .add_service(boxlite_shared::GuestServer::from_arc(server.clone()))
-            .add_service(boxlite_shared::ExecutionServer::from_arc(server.clone()));
+            .add_service(boxlite_shared::ExecutionServer::from_arc(server.clone()))
+            .add_service(boxlite_shared::FilesServer::from_arc(server.clone()));
 
         match transport {
             Transport::Vsock { port } => {
@@ -9,12 +9,22 @@ pub struct VirtiofsMount;
 
 impl VirtiofsMount {
     /// Mount virtiofs tag to mount point.
-    pub fn mount(tag: &str, mount_point: &Path, read_only: bool) -> BoxliteResult<()> {
+    ///
+    /// `options` holds extra mount options: known flags (`nosuid`, `nodev`,
+    /// ...) become mount flags, anything else (e.g. `dax=never`) is passed to
+    /// the filesystem as data.
+    pub fn mount(
+        tag: &str,
+        mount_point: &Path,
+        read_only: bool,
+        options: &[String],
+    ) -> BoxliteResult<()> {
         tracing::info!(
-            "Mounting virtiofs: {} → {} ({})",
+            "Mounting virtiofs: {} → {} ({}) options={:?}",
             tag,
             mount_point.display(),
-            if read_only { "ro" } else { "rw" }
+            if read_only { "ro" } else { "rw" },
+            options
         );
 
         // Create mount point
@@ -26,7 +36,7 @@ impl VirtiofsMount {
             ))
         })?;
 
-        let mut flags = MsFlags::empty();
+        let (mut flags, data) = parse_mount_options(options);
         if read_only {
             flags |= MsFlags::MS_RDONLY;
         }
@@ -36,7 +46,7 @@ impl VirtiofsMount {
             mount_point,
             Some("virtiofs"),
             flags,
-            None::<&str>,
+            data.as_deref(),
         )
         .map_err(|e| {
             BoxliteError::Storage(format!(
@@ -56,3 +66,48 @@ impl VirtiofsMount {
         Ok(())
     }
 }
+
+/// Split mount options into mount flags and a filesystem data string.
+fn parse_mount_options(options: &[String]) -> (MsFlags, Option<String>) {
+    let mut flags = MsFlags::empty();
+    let mut data = Vec::new();
+    for option in options {
+        match option.as_str() {
+            "ro" => flags |= MsFlags::MS_RDONLY,
+            "rw" => {}
+            "nosuid" => flags |= MsFlags::MS_NOSUID,
+            "nodev" => flags |= MsFlags::MS_NODEV,
+            "noexec" => flags |= MsFlags::MS_NOEXEC,
+            "noatime" => flags |= MsFlags::MS_NOATIME,
+            "nodiratime" => flags |= MsFlags::MS_NODIRATIME,
+            "relatime" => flags |= MsFlags::MS_RELATIME,
+            "sync" => flags |= MsFlags::MS_SYNCHRONOUS,
+            other => data.push(other),
+        }
+    }
+    let data = (!data.is_empty()).then(|| data.join(","));
+    (flags, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mount_options() {
+        let options: Vec<String> = ["nosuid", "nodev", "dax=never", "noexec"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let (flags, data) = parse_mount_options(&options);
+        assert_eq!(
+            flags,
+            MsFlags::MS_NOSUID | MsFlags::MS_NODEV | MsFlags::MS_NOEXEC
+        );
+        assert_eq!(data.as_deref(), Some("dax=never"));
+
+        let (flags, data) = parse_mount_options(&[]);
+        assert!(flags.is_empty());
+        assert!(data.is_none());
+    }
+}
@@ -75,6 +75,7 @@ fn status_to_string(status: BoxStatus) -> &'static str {
         BoxStatus::Running => "running",
         BoxStatus::Stopping => "stopping",
         BoxStatus::Stopped => "stopped",
+        BoxStatus::Detached => "detached",
     }
 }
 
@@ -817,6 +818,8 @@ pub unsafe extern "C" fn boxlite_box_metrics(
             let json = serde_json::json!({
                 "cpu_percent": metrics.cpu_percent,
                 "memory_bytes": metrics.memory_bytes,
+                "disk_read_bytes": metrics.disk_read_bytes,
+                "disk_write_bytes": metrics.disk_write_bytes,
                 "commands_executed_total": metrics.commands_executed_total,
                 "exec_errors_total": metrics.exec_errors_total,
                 "bytes_sent_total": metrics.bytes_sent_total,
@@ -826,7 +829,9 @@ pub unsafe extern "C" fn boxlite_box_metrics(
                 "network_bytes_sent": metrics.network_bytes_sent,
                 "network_bytes_received": metrics.network_bytes_received,
                 "network_tcp_connections": metrics.network_tcp_connections,
-                "network_tcp_errors": metrics.network_tcp_errors
+                "network_tcp_errors": metrics.network_tcp_errors,
+                "net_rx_bytes": metrics.net_rx_bytes,
+                "net_tx_bytes": metrics.net_tx_bytes
             });
             write_json_output(json, out_json)
         }
@@ -29,6 +29,7 @@ fn status_to_string(status: BoxStatus) -> String {
         BoxStatus::Running => "running",
         BoxStatus::Stopping => "stopping",
         BoxStatus::Stopped => "stopped",
+        BoxStatus::Detached => "detached",
     }
     .to_string()
 }
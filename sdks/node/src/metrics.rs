@@ -60,6 +60,10 @@ pub struct JsBoxMetrics {
     pub cpu_percent: Option<f64>,
     /// Memory usage in bytes
     pub memory_bytes: Option<f64>,
+    /// Bytes read from the box's disks
+    pub disk_read_bytes: Option<f64>,
+    /// Bytes written to the box's disks
+    pub disk_write_bytes: Option<f64>,
 
     // Network metrics
     /// Network bytes sent (host to guest)
@@ -70,6 +74,10 @@ pub struct JsBoxMetrics {
     pub network_tcp_connections: Option<f64>,
     /// Total TCP connection errors
     pub network_tcp_errors: Option<f64>,
+    /// Bytes received by the box (host to guest)
+    pub net_rx_bytes: Option<f64>,
+    /// Bytes transmitted by the box (guest to host)
+    pub net_tx_bytes: Option<f64>,
 
     // Stage-level timing breakdown
     /// Time to create box directory structure (milliseconds)
@@ -102,12 +110,16 @@ impl From<BoxMetrics> for JsBoxMetrics {
             // Resource usage
             cpu_percent: m.cpu_percent.map(|v| v as f64),
             memory_bytes: m.memory_bytes.map(|v| v as f64),
+            disk_read_bytes: m.disk_read_bytes.map(|v| v as f64),
+            disk_write_bytes: m.disk_write_bytes.map(|v| v as f64),
 
             // Network metrics (convert u64 to f64 for JavaScript)
             network_bytes_sent: m.network_bytes_sent.map(|v| v as f64),
             network_bytes_received: m.network_bytes_received.map(|v| v as f64),
             network_tcp_connections: m.network_tcp_connections.map(|v| v as f64),
             network_tcp_errors: m.network_tcp_errors.map(|v| v as f64),
+            net_rx_bytes: m.net_rx_bytes.map(|v| v as f64),
+            net_tx_bytes: m.net_tx_bytes.map(|v| v as f64),
 
             // Stage timing (convert u128 to f64 for JavaScript)
             stage_filesystem_setup_ms: m.stage_filesystem_setup_ms.map(|v| v as f64),
@@ -109,6 +109,7 @@ impl From<JsVolumeSpec> for VolumeSpec {
             host_path: v.host_path,
             guest_path: v.guest_path,
             read_only: v.read_only.unwrap_or(false),
+            ..Default::default()
         }
     }
 }
@@ -202,10 +203,17 @@ impl From<JsBoxOptions> for BoxOptions {
             volumes,
             network,
             ports,
+            mac_address: None,     // Not exposed in JS API yet
+            static_ip: None,       // Not exposed in JS API yet
             isolate_mounts: false, // Not exposed in JS API yet
             auto_remove: js_opts.auto_remove.unwrap_or(false),
             detach: js_opts.detach.unwrap_or(false),
             security: Default::default(), // Use default security options
+            labels: Default::default(),   // Not exposed in JS API yet
+            ttl: None,                    // Not exposed in JS API yet
+            restart_policy: Default::default(), // Not exposed in JS API yet
+            // Readiness retry/backoff not exposed in JS API yet
+            ..Default::default()
         }
     }
 }
@@ -62,6 +62,10 @@ pub(crate) struct PyBoxMetrics {
     #[pyo3(get)]
     pub(crate) memory_bytes: Option<u64>,
     #[pyo3(get)]
+    pub(crate) disk_read_bytes: Option<u64>,
+    #[pyo3(get)]
+    pub(crate) disk_write_bytes: Option<u64>,
+    #[pyo3(get)]
     pub(crate) network_bytes_sent: Option<u64>,
     #[pyo3(get)]
     pub(crate) network_bytes_received: Option<u64>,
@@ -69,6 +73,10 @@ pub(crate) struct PyBoxMetrics {
     pub(crate) network_tcp_connections: Option<u64>,
     #[pyo3(get)]
     pub(crate) network_tcp_errors: Option<u64>,
+    #[pyo3(get)]
+    pub(crate) net_rx_bytes: Option<u64>,
+    #[pyo3(get)]
+    pub(crate) net_tx_bytes: Option<u64>,
     // Stage-level timing breakdown
     #[pyo3(get)]
     pub(crate) stage_filesystem_setup_ms: Option<u128>,
@@ -110,10 +118,14 @@ impl From<BoxMetrics> for PyBoxMetrics {
             guest_boot_duration_ms: metrics.guest_boot_duration_ms(),
             cpu_percent: metrics.cpu_percent(),
             memory_bytes: metrics.memory_bytes(),
+            disk_read_bytes: metrics.disk_read_bytes(),
+            disk_write_bytes: metrics.disk_write_bytes(),
             network_bytes_sent: metrics.network_bytes_sent(),
             network_bytes_received: metrics.network_bytes_received(),
             network_tcp_connections: metrics.network_tcp_connections(),
             network_tcp_errors: metrics.network_tcp_errors(),
+            net_rx_bytes: metrics.net_rx_bytes(),
+            net_tx_bytes: metrics.net_tx_bytes(),
             stage_filesystem_setup_ms: metrics.stage_filesystem_setup_ms(),
             stage_image_prepare_ms: metrics.stage_image_prepare_ms(),
             stage_guest_rootfs_ms: metrics.stage_guest_rootfs_ms(),
@@ -233,6 +233,7 @@ impl From<PySecurityOptions> for SecurityOptions {
                 max_processes: py_opts.max_processes,
                 max_memory: py_opts.max_memory,
                 max_cpu_time: py_opts.max_cpu_time,
+                ..Default::default()
             },
             ..Default::default()
         }
@@ -403,6 +404,7 @@ impl From<PyVolumeSpec> for VolumeSpec {
             host_path: v.host,
             guest_path: v.guest,
             read_only: v.read_only,
+            ..Default::default()
         }
     }
 }